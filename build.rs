@@ -1,33 +1,43 @@
-#[cfg(target_os = "windows")]
-mod build{
-	fn try_env(var: &str, path: &str) -> Option<String> {
-		use std::env;
-		match env::var(var) {
-			Ok(var) => Some(var+path),
-			Err(_) => None,
-		}
-	}
+use std::collections::HashMap;
+use std::env;
+use std::process::Command;
 
-	pub fn build(){
-		let path = try_env("CUDA_LIB_PATH","")
-		.or(try_env("AMDAPPSDKROOT","/lib/x86_64"))
-		.or(try_env("INTELOCLSDKROOT","/lib/x64"));
-		match path {
-			Some(path)=>{
-				println!("cargo:rustc-link-search=native={}",path);
-			},
-			None=>println!("Error: cannot find env vars"),
-		}
-	}
-}
+#[path = "build/search.rs"]
+mod search;
 
-#[cfg(not(target_os = "windows"))]
-mod build{
-	pub fn build(){
+/// Returns the `libdir` of the `OpenCL` package as reported by `pkg-config`,
+/// if it is installed and knows of one.
+fn pkg_config_libdir() -> Option<String> {
+	let output = match Command::new("pkg-config").args(["--variable=libdir", "OpenCL"]).output() {
+		Ok(output) => output,
+		Err(_) => return None,
+	};
 
+	if output.status.success() {
+		String::from_utf8(output.stdout).ok()
+	} else {
+		None
 	}
 }
 
 fn main() {
-	build::build();
-}
\ No newline at end of file
+	println!("cargo:rerun-if-changed=build.rs");
+	println!("cargo:rerun-if-changed=build/search.rs");
+
+	let env: HashMap<String, String> = env::vars().collect();
+
+	for var in search::SEARCH_VARS.iter().cloned()
+			.chain(env.keys().map(|k| k.as_str()).filter(|k| k.starts_with("CUDA_PATH_V")))
+	{
+		println!("cargo:rerun-if-env-changed={}", var);
+	}
+
+	let pkg_config_libdir = match env.get("CARGO_CFG_TARGET_OS").map(|s| s.as_str()) {
+		Some("windows") | Some("macos") | Some("ios") => None,
+		_ => pkg_config_libdir(),
+	};
+
+	for line in search::link_directives(&env, pkg_config_libdir.as_deref()) {
+		println!("{}", line);
+	}
+}
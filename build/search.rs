@@ -0,0 +1,113 @@
+//! Library search logic for the build script.
+//!
+//! Kept free of any access to the environment or filesystem (which
+//! `build.rs` provides) so that it can be tested from within the crate (see
+//! `tests::link_search`).
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Environment variables which affect the search, each of which the build
+/// script is re-run upon a change to.
+pub static SEARCH_VARS: &[&str] = &["OPENCL_LIB_DIR", "OCL_ROOT", "CUDA_PATH",
+    "CUDA_LIB_PATH", "AMDAPPSDKROOT", "INTELOCLSDKROOT"];
+
+/// Returns the value of `var` if it is set and not empty.
+fn var<'e>(env: &'e HashMap<String, String>, var: &str) -> Option<&'e str> {
+    match env.get(var) {
+        Some(val) if !val.is_empty() => Some(val),
+        _ => None,
+    }
+}
+
+/// Returns the directories to search for the OpenCL library, in order of
+/// preference, for the target described by `env` (containing the variables
+/// provided to build scripts, such as `CARGO_CFG_TARGET_OS`).
+///
+/// `OPENCL_LIB_DIR` always comes first. Thereafter:
+///
+/// * Linux (and other Unixes): `pkg_config_libdir`, the `libdir` of the
+///   `OpenCL` package as reported by `pkg-config` (if available).
+/// * Windows: the SDK locations given by `OCL_ROOT` (the OCL-SDK), then
+///   `CUDA_PATH` followed by each versioned `CUDA_PATH_V*` (the NVIDIA GPU
+///   Computing Toolkit, newest first), then the older `CUDA_LIB_PATH`,
+///   `AMDAPPSDKROOT`, and `INTELOCLSDKROOT`.
+/// * macOS: nothing further (the framework is found by the linker).
+pub fn search_dirs(env: &HashMap<String, String>, pkg_config_libdir: Option<&str>)
+        -> Vec<String>
+{
+    let mut dirs: Vec<String> = var(env, "OPENCL_LIB_DIR").into_iter()
+        .map(|dir| dir.to_owned()).collect();
+
+    match var(env, "CARGO_CFG_TARGET_OS") {
+        Some("windows") => {
+            let x64 = var(env, "CARGO_CFG_TARGET_ARCH") == Some("x86_64");
+
+            if let Some(root) = var(env, "OCL_ROOT") {
+                dirs.push(format!("{}/lib/{}", root, if x64 { "x86_64" } else { "x86" }));
+            }
+
+            let mut cuda_versions: Vec<(&str, &str)> = env.iter()
+                .filter(|&(k, v)| k.starts_with("CUDA_PATH_V") && !v.is_empty())
+                .map(|(k, v)| (&k["CUDA_PATH_V".len()..], v.as_str()))
+                .collect();
+            cuda_versions.sort_by_key(|&(v, _)| Reverse(version_key(v)));
+
+            let cuda_lib = if x64 { "lib/x64" } else { "lib/Win32" };
+
+            for cuda_path in var(env, "CUDA_PATH").into_iter()
+                    .chain(cuda_versions.into_iter().map(|(_, v)| v))
+            {
+                let dir = format!("{}/{}", cuda_path, cuda_lib);
+                if !dirs.contains(&dir) { dirs.push(dir); }
+            }
+
+            if let Some(dir) = var(env, "CUDA_LIB_PATH") { dirs.push(dir.to_owned()); }
+            if let Some(root) = var(env, "AMDAPPSDKROOT") { dirs.push(format!("{}/lib/x86_64", root)); }
+            if let Some(root) = var(env, "INTELOCLSDKROOT") { dirs.push(format!("{}/lib/x64", root)); }
+        },
+        Some("macos") | Some("ios") => (),
+        _ => {
+            if let Some(dir) = pkg_config_libdir {
+                let dir = dir.trim();
+                if !dir.is_empty() && !dirs.iter().any(|d| d == dir) { dirs.push(dir.to_owned()); }
+            }
+        },
+    }
+
+    dirs
+}
+
+/// Returns the numeric components of a `CUDA_PATH_V*` suffix (`11_2` is
+/// `[11, 2]`) for ordering.
+fn version_key(suffix: &str) -> Vec<u32> {
+    suffix.split('_').map(|c| c.parse().unwrap_or(0)).collect()
+}
+
+/// Returns the lines the build script prints to link the OpenCL library for
+/// the target described by `env`.
+///
+/// Nothing is linked if the `dynamic-loading` or `stub` feature is enabled.
+pub fn link_directives(env: &HashMap<String, String>, pkg_config_libdir: Option<&str>)
+        -> Vec<String>
+{
+    if env.contains_key("CARGO_FEATURE_DYNAMIC_LOADING") || env.contains_key("CARGO_FEATURE_STUB") {
+        return Vec::new();
+    }
+
+    let mut lines: Vec<String> = search_dirs(env, pkg_config_libdir).into_iter()
+        .map(|dir| format!("cargo:rustc-link-search=native={}", dir))
+        .collect();
+
+    match var(env, "CARGO_CFG_TARGET_OS") {
+        Some("macos") | Some("ios") => lines.push("cargo:rustc-link-lib=framework=OpenCL".to_owned()),
+        Some("windows") if lines.is_empty() => {
+            lines.push("cargo:warning=No OpenCL SDK found. Set OPENCL_LIB_DIR to the directory \
+                containing OpenCL.lib.".to_owned());
+            lines.push("cargo:rustc-link-lib=OpenCL".to_owned());
+        },
+        _ => lines.push("cargo:rustc-link-lib=OpenCL".to_owned()),
+    }
+
+    lines
+}
@@ -0,0 +1,53 @@
+//! Timed comparison of reading and writing a large buffer in a single
+//! transfer and in chunks of various sizes (see `BufferCmd::chunk_size`).
+//!
+//! Smaller chunks allow other work to be interleaved with a transfer (and
+//! avoid driver limits on the size of a single transfer) at the cost of
+//! some throughput due to the overhead of each additional command.
+
+extern crate ocl;
+extern crate time;
+
+use ocl::{ProQue, Buffer};
+
+const BUFFER_LEN: usize = 1 << 24;
+const CHUNK_SIZES: [usize; 4] = [1 << 16, 1 << 20, 1 << 22, 1 << 24];
+const ITERS: usize = 10;
+
+
+fn print_elapsed(title: &str, start_ns: u64) {
+    let elapsed_ns = time::precise_time_ns() - start_ns;
+    let bytes = (BUFFER_LEN * 4 * ITERS) as f64;
+    println!("    {}: {:.3} ms per transfer, {:.3} GB/s.", title,
+        elapsed_ns as f64 / 1_000_000.0 / ITERS as f64, bytes / elapsed_ns as f64);
+}
+
+fn main() {
+    let ocl_pq = ProQue::builder().src("__kernel void nop() {}").dims([BUFFER_LEN]).build()
+        .unwrap();
+    let buffer = Buffer::<f32>::new(ocl_pq.queue(), None, [BUFFER_LEN], None).unwrap();
+    let src = vec![1.0f32; BUFFER_LEN];
+    let mut dst = vec![0.0f32; BUFFER_LEN];
+
+    println!("Transferring {} MiB {} times...", BUFFER_LEN * 4 >> 20, ITERS);
+
+    let start = time::precise_time_ns();
+    for _ in 0..ITERS { buffer.write(&src).enq().unwrap(); }
+    print_elapsed("Write (single)", start);
+
+    let start = time::precise_time_ns();
+    for _ in 0..ITERS { buffer.read(&mut dst).enq().unwrap(); }
+    print_elapsed("Read (single)", start);
+
+    for &chunk_size in CHUNK_SIZES.iter() {
+        let start = time::precise_time_ns();
+        for _ in 0..ITERS { buffer.write(&src).chunk_size(chunk_size).enq().unwrap(); }
+        print_elapsed(&format!("Write ({} KiB chunks)", chunk_size >> 10), start);
+
+        let start = time::precise_time_ns();
+        for _ in 0..ITERS { buffer.read(&mut dst).chunk_size(chunk_size).enq().unwrap(); }
+        print_elapsed(&format!("Read ({} KiB chunks)", chunk_size >> 10), start);
+    }
+
+    assert!(dst.iter().all(|&v| v == 1.0));
+}
@@ -0,0 +1,74 @@
+//! Timed comparison of processing a large dataset in chunks sequentially
+//! (write, compute, and read each chunk in turn) with processing it using a
+//! `DoubleBuffer`, which overlaps computation with transfers.
+//!
+//! The amount of overlap depends on the device being able to transfer and
+//! compute concurrently. Manipulate the consts below to adjust the balance
+//! between transfer and computation time.
+
+extern crate ocl;
+extern crate time;
+
+use ocl::{ProQue, Queue, Buffer, DoubleBuffer};
+
+const DATASET_SIZE: usize = 1 << 24;
+const CHUNK_LEN: usize = 1 << 20;
+const KERNEL_ITERS: u32 = 200;
+
+
+fn print_elapsed(title: &str, start_ns: u64) {
+    let elapsed_ns = time::precise_time_ns() - start_ns;
+    println!("    {}: {:.3} ms.", title, elapsed_ns as f64 / 1_000_000.0);
+}
+
+fn main() {
+    let src = r#"
+        __kernel void work(__global float* buf, uint iters) {
+            float val = buf[get_global_id(0)];
+            for (uint i = 0; i < iters; i++) {
+                val = sqrt(val * val + 1.0f);
+            }
+            buf[get_global_id(0)] = val;
+        }
+    "#;
+
+    let ocl_pq = ProQue::builder().src(src).dims([CHUNK_LEN]).build().unwrap();
+    let transfer_queue = Queue::new(&ocl_pq.context(), ocl_pq.queue().device().clone()).unwrap();
+
+    let data: Vec<f32> = (0..DATASET_SIZE).map(|v| (v % 1000) as f32).collect();
+    let mut sequential = Vec::with_capacity(DATASET_SIZE);
+    let mut overlapped = Vec::with_capacity(DATASET_SIZE);
+
+    println!("Processing {} elements in chunks of {}...", DATASET_SIZE, CHUNK_LEN);
+
+    // Sequential:
+    let buffer = Buffer::<f32>::new(ocl_pq.queue(), None, [CHUNK_LEN], None).unwrap();
+    let kernel = ocl_pq.create_kernel("work").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(KERNEL_ITERS);
+    let mut result = vec![0.0f32; CHUNK_LEN];
+
+    let start = time::precise_time_ns();
+    for chunk in data.chunks(CHUNK_LEN) {
+        buffer.write(chunk).enq().unwrap();
+        kernel.cmd().gws([chunk.len()]).enq().unwrap();
+        buffer.read(&mut result[..chunk.len()]).enq().unwrap();
+        sequential.extend_from_slice(&result[..chunk.len()]);
+    }
+    print_elapsed("Sequential", start);
+
+    // Overlapped:
+    let mut dbuf = DoubleBuffer::<f32>::new(&transfer_queue, ocl_pq.queue(), CHUNK_LEN).unwrap();
+    let mut kernel = ocl_pq.create_kernel("work").unwrap()
+        .arg_buf_named::<f32>("buf", None)
+        .arg_scl(KERNEL_ITERS);
+
+    let start = time::precise_time_ns();
+    dbuf.run(data.chunks(CHUNK_LEN), |buffer, len, wait, new| {
+        try!(kernel.set_arg_buf_named("buf", Some(buffer)));
+        kernel.cmd().queue(ocl_pq.queue()).gws([len]).ewait(wait).enew(new).enq()
+    }, |result| overlapped.extend_from_slice(result)).unwrap();
+    print_elapsed("Double buffered", start);
+
+    assert!(sequential == overlapped);
+}
@@ -0,0 +1,89 @@
+//! Timed measurements of the host-side overhead of enqueuing commands, with
+//! and without wait lists and new events.
+//!
+//! Each command is a trivial kernel so that the time taken is dominated by
+//! the enqueue calls themselves. Increase `ENQUEUE_ITERS` for more stable
+//! results.
+
+extern crate ocl;
+extern crate time;
+
+use ocl::{core, ProQue, Event, EventList};
+use ocl::core::Event as EventCore;
+
+const ENQUEUE_ITERS: usize = 20000;
+const WAIT_LIST_LEN: usize = 8;
+
+
+fn print_elapsed(title: &str, start_ns: u64) {
+    let elapsed_ns = time::precise_time_ns() - start_ns;
+    println!("    {}: {:.3} ms total, {:.3} us per enqueue.", title,
+        elapsed_ns as f64 / 1_000_000.0,
+        elapsed_ns as f64 / 1000.0 / ENQUEUE_ITERS as f64);
+}
+
+fn main() {
+    let src = r#"
+        __kernel void nop() {}
+    "#;
+
+    let ocl_pq = ProQue::builder().src(src).dims([1]).build().unwrap();
+    let kern = ocl_pq.create_kernel("nop").unwrap();
+
+    println!("Enqueuing {} kernels per run...", ENQUEUE_ITERS);
+
+    // No wait list or new event:
+    let start = time::precise_time_ns();
+    for _ in 0..ENQUEUE_ITERS {
+        kern.enq().unwrap();
+    }
+    print_elapsed("No events", start);
+    ocl_pq.queue().finish().unwrap();
+
+    // New event only:
+    let start = time::precise_time_ns();
+    for _ in 0..ENQUEUE_ITERS {
+        let mut event = Event::empty();
+        kern.cmd().enew(&mut event).enq().unwrap();
+    }
+    print_elapsed("New 'Event'", start);
+    ocl_pq.queue().finish().unwrap();
+
+    // Wait list of core events (a slice is passed directly) and a new core
+    // event written in place:
+    let mut wait_list: Vec<EventCore> = (0..WAIT_LIST_LEN).map(|_| {
+        let mut event = unsafe { EventCore::null() };
+        kern.cmd().enew(&mut event).enq().unwrap();
+        event
+    }).collect();
+
+    let start = time::precise_time_ns();
+    for i in 0..ENQUEUE_ITERS {
+        let mut event = unsafe { EventCore::null() };
+        kern.cmd().ewait(&wait_list).enew(&mut event).enq().unwrap();
+        wait_list[i % WAIT_LIST_LEN] = event;
+    }
+    print_elapsed("'Vec<core::Event>' wait list, new 'core::Event'", start);
+    ocl_pq.queue().finish().unwrap();
+
+    // Wait list from an `EventList`:
+    let mut event_list = EventList::new();
+    for _ in 0..WAIT_LIST_LEN {
+        kern.cmd().enew(&mut event_list).enq().unwrap();
+    }
+
+    let start = time::precise_time_ns();
+    for _ in 0..ENQUEUE_ITERS {
+        let mut event = Event::empty();
+        kern.cmd().ewait(&event_list).enew(&mut event).enq().unwrap();
+    }
+    print_elapsed("'EventList' wait list, new 'Event'", start);
+    ocl_pq.queue().finish().unwrap();
+
+    // Waiting on a slice of events:
+    let start = time::precise_time_ns();
+    for _ in 0..ENQUEUE_ITERS {
+        core::wait_for_events(&wait_list).unwrap();
+    }
+    print_elapsed("'core::wait_for_events' (complete events)", start);
+}
@@ -29,7 +29,7 @@ static SRC: &'static str = r#"
 
 fn main() {
 	let dims = [2048];
-	let platforms = Platform::list();
+	let platforms = Platform::list().unwrap();
 
 	println!("Looping through avaliable platforms ({}):", platforms.len());
 
@@ -37,7 +37,7 @@ fn main() {
     for p_idx in 0..platforms.len() {
     	let platform = &platforms[p_idx];
 
-    	let devices = Device::list_all(platform);
+    	let devices = Device::list_all(platform).unwrap();
 
     	// [NOTE]: A new context can also be created for each device if desired.
     	let context = Context::builder()
@@ -96,8 +96,8 @@ fn main() {
 
 
 fn print_platform_info(platform: &Platform) {
-	printc!(blue: "{}", platform);
-	let devices = Device::list_all(platform);
+	printc!(blue: "{:#}", platform);
+	let devices = Device::list_all(platform).unwrap();
 	printc!(blue: " {{ Total Device Count: {} }}", devices.len());
 	print!("\n");
 }
@@ -105,7 +105,7 @@ fn print_platform_info(platform: &Platform) {
 
 fn print_device_info(device: &Device) {
 	if PRINT_DETAILED_DEVICE {
-		printlnc!(teal: "{}", device);
+		printlnc!(teal: "{:#}", device);
 	} else {
 		if !PRINT_DETAILED { print!("{t}", t = TAB); } 
 		printlnc!(teal: "Device (terse) {{ Name: {}, Vendor: {} }}", device.name(), device.vendor());
@@ -114,7 +114,7 @@ fn print_device_info(device: &Device) {
 
 
 fn print_context_info(context: &Context) {
-	printlnc!(purple: "{}", context);
+	printlnc!(purple: "{:#}", context);
 }
 
 
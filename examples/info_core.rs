@@ -22,7 +22,7 @@ static SRC: &'static str = r#"
 "#;
 
 fn main() {
-    let platforms = Platform::list();
+    let platforms = Platform::list().unwrap();
     // let platform = platforms[platforms.len() - 1];
     for platform in platforms.iter() {
         print_platform(platform.clone());
@@ -30,7 +30,7 @@ fn main() {
 }
 
 fn print_platform(platform: Platform) {
-    for device in Device::list_all(&platform) {
+    for device in Device::list_all(&platform).unwrap() {
         print_platform_device(platform.clone(), device);
     }
 }
@@ -56,6 +56,9 @@ fn print_platform_device(platform: Platform, device: Device) {
     kernel.cmd().enew(&mut event_list).enq().unwrap();
     event_list.wait().unwrap();
 
+    // Used only on this thread:
+    let kernel_core = unsafe { kernel.core_as_ref() };
+
     let mut event = Event::empty();
     buffer.cmd().write(&vec![0.0; DIMS[0]]).enew(&mut event).enq().unwrap();
     event.wait().unwrap();
@@ -581,7 +584,7 @@ fn print_platform_device(platform: Platform, device: Device) {
             {t}MapCount: {}\n\
             {t}ReferenceCount: {}\n\
             {t}Context: {}\n\
-            {t}AssociatedMemobject: {}\n\
+            {t}AssociatedMemObject: {}\n\
             {t}Offset: {}\n\
         ",
         core::get_mem_object_info(&buffer, MemInfo::Type),
@@ -591,7 +594,7 @@ fn print_platform_device(platform: Platform, device: Device) {
         core::get_mem_object_info(&buffer, MemInfo::MapCount),
         core::get_mem_object_info(&buffer, MemInfo::ReferenceCount),
         core::get_mem_object_info(&buffer, MemInfo::Context),
-        core::get_mem_object_info(&buffer, MemInfo::AssociatedMemobject),
+        core::get_mem_object_info(&buffer, MemInfo::AssociatedMemObject),
         core::get_mem_object_info(&buffer, MemInfo::Offset),
         t = util::colors::TAB,
     );
@@ -685,7 +688,7 @@ fn print_platform_device(platform: Platform, device: Device) {
                 {t}{t}MapCount: {}\n\
                 {t}{t}ReferenceCount: {}\n\
                 {t}{t}Context: {}\n\
-                {t}{t}AssociatedMemobject: {}\n\
+                {t}{t}AssociatedMemObject: {}\n\
                 {t}{t}Offset: {}\n\
             ",
             core::get_mem_object_info(&buffer, MemInfo::Type),
@@ -695,7 +698,7 @@ fn print_platform_device(platform: Platform, device: Device) {
             core::get_mem_object_info(&buffer, MemInfo::MapCount),
             core::get_mem_object_info(&buffer, MemInfo::ReferenceCount),
             core::get_mem_object_info(&buffer, MemInfo::Context),
-            core::get_mem_object_info(&buffer, MemInfo::AssociatedMemobject),
+            core::get_mem_object_info(&buffer, MemInfo::AssociatedMemObject),
             core::get_mem_object_info(&buffer, MemInfo::Offset),
             t = util::colors::TAB,
         );
@@ -867,12 +870,12 @@ fn print_platform_device(platform: Platform, device: Device) {
             {t}Program: {}\n\
             {t}Attributes: {}\n\
         ",
-        core::get_kernel_info(&kernel, KernelInfo::FunctionName),
-        core::get_kernel_info(&kernel, KernelInfo::NumArgs),
-        core::get_kernel_info(&kernel, KernelInfo::ReferenceCount),
-        core::get_kernel_info(&kernel, KernelInfo::Context),
-        core::get_kernel_info(&kernel, KernelInfo::Program),
-        core::get_kernel_info(&kernel, KernelInfo::Attributes),
+        core::get_kernel_info(kernel_core, KernelInfo::FunctionName),
+        core::get_kernel_info(kernel_core, KernelInfo::NumArgs),
+        core::get_kernel_info(kernel_core, KernelInfo::ReferenceCount),
+        core::get_kernel_info(kernel_core, KernelInfo::Context),
+        core::get_kernel_info(kernel_core, KernelInfo::Program),
+        core::get_kernel_info(kernel_core, KernelInfo::Attributes),
         t = util::colors::TAB,
     );
 
@@ -923,11 +926,11 @@ fn print_platform_device(platform: Platform, device: Device) {
             {t}TypeQualifier: {}\n\
             {t}Name: {}\n\
         ",
-        core::get_kernel_arg_info(&kernel, 0, KernelArgInfo::AddressQualifier),
-        core::get_kernel_arg_info(&kernel, 0, KernelArgInfo::AccessQualifier),
-        core::get_kernel_arg_info(&kernel, 0, KernelArgInfo::TypeName),
-        core::get_kernel_arg_info(&kernel, 0, KernelArgInfo::TypeQualifier),
-        core::get_kernel_arg_info(&kernel, 0, KernelArgInfo::Name),
+        core::get_kernel_arg_info(kernel_core, 0, KernelArgInfo::AddressQualifier),
+        core::get_kernel_arg_info(kernel_core, 0, KernelArgInfo::AccessQualifier),
+        core::get_kernel_arg_info(kernel_core, 0, KernelArgInfo::TypeName),
+        core::get_kernel_arg_info(kernel_core, 0, KernelArgInfo::TypeQualifier),
+        core::get_kernel_arg_info(kernel_core, 0, KernelArgInfo::Name),
         t = util::colors::TAB,
     );
 
@@ -957,12 +960,12 @@ fn print_platform_device(platform: Platform, device: Device) {
             {t}PrivateMemSize: {}\n\
             {t}GlobalWorkSize: {}\n\
         ",
-        core::get_kernel_work_group_info(&kernel, &device, KernelWorkGroupInfo::WorkGroupSize),
-        core::get_kernel_work_group_info(&kernel, &device, KernelWorkGroupInfo::CompileWorkGroupSize),
-        core::get_kernel_work_group_info(&kernel, &device, KernelWorkGroupInfo::LocalMemSize),
-        core::get_kernel_work_group_info(&kernel, &device, KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple),
-        core::get_kernel_work_group_info(&kernel, &device, KernelWorkGroupInfo::PrivateMemSize),
-        // core::get_kernel_work_group_info(&kernel, &device, 
+        core::get_kernel_work_group_info(kernel_core, &device, KernelWorkGroupInfo::WorkGroupSize),
+        core::get_kernel_work_group_info(kernel_core, &device, KernelWorkGroupInfo::CompileWorkGroupSize),
+        core::get_kernel_work_group_info(kernel_core, &device, KernelWorkGroupInfo::LocalMemSize),
+        core::get_kernel_work_group_info(kernel_core, &device, KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple),
+        core::get_kernel_work_group_info(kernel_core, &device, KernelWorkGroupInfo::PrivateMemSize),
+        // core::get_kernel_work_group_info(kernel_core, &device, 
         //  KernelWorkGroupInfo::GlobalWorkSize).unwrap(),
         "[KernelWorkGroupInfo::GlobalWorkSize not avaliable in this configuration]",
         t = util::colors::TAB,
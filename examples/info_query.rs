@@ -0,0 +1,37 @@
+//! Timed repeated device info queries (`Device::info`).
+//!
+//! Small results (scalars, handles, short strings) are read without a heap
+//! allocation for the result bytes. Large results, such as the extension
+//! list, still allocate.
+
+extern crate ocl;
+extern crate time;
+
+use ocl::{Platform, Device};
+use ocl::enums::DeviceInfo;
+
+const QUERY_ITERS: usize = 100000;
+
+
+fn print_elapsed(title: &str, start_ns: u64) {
+    let elapsed_ns = time::precise_time_ns() - start_ns;
+    println!("    {}: {:.3} ms total, {:.3} ns per query.", title,
+        elapsed_ns as f64 / 1_000_000.0, elapsed_ns as f64 / QUERY_ITERS as f64);
+}
+
+fn main() {
+    let device = Device::first(Platform::default()).unwrap();
+    println!("Device: '{}', {} queries of each kind...", device.name(), QUERY_ITERS);
+
+    for &(title, kind) in &[("MaxWorkGroupSize", DeviceInfo::MaxWorkGroupSize),
+            ("MaxWorkItemSizes", DeviceInfo::MaxWorkItemSizes),
+            ("Name", DeviceInfo::Name),
+            ("Extensions", DeviceInfo::Extensions)]
+    {
+        let start = time::precise_time_ns();
+        for _ in 0..QUERY_ITERS {
+            device.info(kind);
+        }
+        print_elapsed(title, start);
+    }
+}
@@ -0,0 +1,77 @@
+//! Timed comparison of setting every argument of a kernel before each
+//! enqueue with and without skipping unchanged arguments
+//! (`Kernel::cache_args`).
+//!
+//! Only one of the ten arguments changes between iterations.
+
+extern crate ocl;
+extern crate time;
+
+use ocl::{ProQue, Buffer, Kernel};
+
+const WORK_SIZE: usize = 64;
+const ITERS: usize = 10000;
+const ARG_NAMES: [&'static str; 8] = ["b", "c", "d", "e", "f", "g", "h", "i"];
+
+
+fn print_elapsed(title: &str, start_ns: u64) {
+    let elapsed_ns = time::precise_time_ns() - start_ns;
+    println!("    {}: {:.3} ms total, {:.3} us per iteration.", title,
+        elapsed_ns as f64 / 1_000_000.0, elapsed_ns as f64 / 1000.0 / ITERS as f64);
+}
+
+fn run(kernel: &mut Kernel, buffer: &Buffer<u32>) {
+    for i in 0..ITERS {
+        kernel.set_arg_buf_named("buf", Some(buffer)).unwrap();
+        kernel.set_arg_scl_named("step", i as u32).unwrap();
+
+        for (j, &name) in ARG_NAMES.iter().enumerate() {
+            kernel.set_arg_scl_named(name, j as u32).unwrap();
+        }
+
+        kernel.enq().unwrap();
+    }
+
+    kernel.default_queue().unwrap().finish().unwrap();
+}
+
+fn main() {
+    let src = r#"
+        __kernel void add(__global uint* buf, uint step, uint b, uint c, uint d, uint e,
+                uint f, uint g, uint h, uint i)
+        {
+            buf[get_global_id(0)] = step + b + c + d + e + f + g + h + i;
+        }
+    "#;
+
+    let ocl_pq = ProQue::builder().src(src).dims([WORK_SIZE]).build().unwrap();
+    let buffer = ocl_pq.create_buffer::<u32>().unwrap();
+
+    let new_kernel = |cache: bool| {
+        let mut kernel = ocl_pq.create_kernel("add").unwrap()
+            .cache_args(cache)
+            .arg_buf_named("buf", Some(&buffer))
+            .arg_scl_named::<u32>("step", None);
+
+        for &name in ARG_NAMES.iter() {
+            kernel = kernel.arg_scl_named::<u32>(name, None);
+        }
+        kernel
+    };
+
+    println!("Setting 10 arguments and enqueuing {} times...", ITERS);
+
+    let mut kernel = new_kernel(false);
+    let start = time::precise_time_ns();
+    run(&mut kernel, &buffer);
+    print_elapsed("Uncached", start);
+
+    let mut kernel = new_kernel(true);
+    let start = time::precise_time_ns();
+    run(&mut kernel, &buffer);
+    print_elapsed("Cached", start);
+
+    let mut vec = vec![0u32; WORK_SIZE];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == (ITERS as u32 - 1) + (0..8).sum::<u32>()));
+}
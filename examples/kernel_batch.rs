@@ -0,0 +1,54 @@
+//! Timed comparison of enqueuing a kernel repeatedly with `::enq` and
+//! enqueuing the same launches as a single batch with `::enq_batch`.
+//!
+//! Small global work sizes make the per-launch host overhead dominate.
+
+extern crate ocl;
+extern crate time;
+
+use ocl::ProQue;
+use ocl::core::WorkSizes;
+
+const WORK_SIZE: usize = 64;
+const LAUNCH_COUNT: usize = 10000;
+
+
+fn print_elapsed(title: &str, start_ns: u64) {
+    let elapsed_ns = time::precise_time_ns() - start_ns;
+    println!("    {}: {:.3} ms total, {:.3} us per launch.", title,
+        elapsed_ns as f64 / 1_000_000.0, elapsed_ns as f64 / 1000.0 / LAUNCH_COUNT as f64);
+}
+
+fn main() {
+    let src = r#"
+        __kernel void add_one(__global uint* buf) {
+            buf[get_global_id(0)] += 1;
+        }
+    "#;
+
+    let ocl_pq = ProQue::builder().src(src).dims([WORK_SIZE]).build().unwrap();
+    let buffer = ocl_pq.create_buffer::<u32>().unwrap();
+    let kernel = ocl_pq.create_kernel("add_one").unwrap().arg_buf(&buffer);
+
+    let launches: Vec<WorkSizes> = (0..LAUNCH_COUNT)
+        .map(|_| WorkSizes::new(&[WORK_SIZE], None, None).unwrap())
+        .collect();
+
+    println!("Enqueuing {} launches of {} work items...", LAUNCH_COUNT, WORK_SIZE);
+
+    let start = time::precise_time_ns();
+    for _ in 0..LAUNCH_COUNT {
+        kernel.enq().unwrap();
+    }
+    ocl_pq.queue().finish().unwrap();
+    print_elapsed("Individual", start);
+
+    let start = time::precise_time_ns();
+    kernel.enq_batch(&launches).unwrap();
+    ocl_pq.queue().finish().unwrap();
+    print_elapsed("Batched", start);
+
+    let mut vec = vec![0u32; WORK_SIZE];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == LAUNCH_COUNT as u32 * 2));
+}
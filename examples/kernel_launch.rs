@@ -0,0 +1,61 @@
+//! Timed comparison of the host overhead of enqueuing a no-op kernel with
+//! `Kernel::enq`, with `core::enqueue_kernel_dims` directly, and with the
+//! per-launch work group info query which `Kernel::enq` used to perform.
+//!
+//! The kernel does nothing so that the host overhead dominates.
+
+extern crate ocl;
+extern crate time;
+
+use ocl::{core, ProQue};
+use ocl::core::WorkSizes;
+use ocl::enums::{KernelWorkGroupInfo, KernelWorkGroupInfoResult};
+
+const WORK_SIZE: usize = 64;
+const LAUNCH_COUNT: usize = 10000;
+
+
+fn print_elapsed(title: &str, start_ns: u64) {
+    let elapsed_ns = time::precise_time_ns() - start_ns;
+    println!("    {}: {:.3} ms total, {:.3} us per launch.", title,
+        elapsed_ns as f64 / 1_000_000.0, elapsed_ns as f64 / 1000.0 / LAUNCH_COUNT as f64);
+}
+
+fn main() {
+    let src = r#"
+        __kernel void no_op() {}
+    "#;
+
+    let ocl_pq = ProQue::builder().src(src).dims([WORK_SIZE]).build().unwrap();
+    let kernel = ocl_pq.create_kernel("no_op").unwrap()
+        .lws([WORK_SIZE])
+        .validate_dims(true);
+    let dims = WorkSizes::new(&[WORK_SIZE], Some(&[WORK_SIZE]), None).unwrap();
+
+    println!("Enqueuing {} launches of a no-op kernel...", LAUNCH_COUNT);
+
+    let start = time::precise_time_ns();
+    for _ in 0..LAUNCH_COUNT {
+        kernel.enq().unwrap();
+    }
+    ocl_pq.queue().finish().unwrap();
+    print_elapsed("Kernel::enq", start);
+
+    let start = time::precise_time_ns();
+    for _ in 0..LAUNCH_COUNT {
+        match kernel.wg_info(ocl_pq.queue().device(), KernelWorkGroupInfo::WorkGroupSize) {
+            KernelWorkGroupInfoResult::WorkGroupSize(size) => assert!(size >= WORK_SIZE),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+        core::enqueue_kernel_dims(ocl_pq.queue(), &kernel, &dims, None, None).unwrap();
+    }
+    ocl_pq.queue().finish().unwrap();
+    print_elapsed("Queried per launch", start);
+
+    let start = time::precise_time_ns();
+    for _ in 0..LAUNCH_COUNT {
+        core::enqueue_kernel_dims(ocl_pq.queue(), &kernel, &dims, None, None).unwrap();
+    }
+    ocl_pq.queue().finish().unwrap();
+    print_elapsed("core::enqueue_kernel_dims", start);
+}
@@ -0,0 +1,9 @@
+//! Prints a report of every platform and device (and the default selection)
+//! for inclusion in a bug report.
+
+extern crate ocl;
+
+fn main() {
+    println!("{}", ocl::report());
+    println!("{}", ocl::selection_report());
+}
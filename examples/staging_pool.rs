@@ -0,0 +1,62 @@
+//! Timed comparison of transfers made directly from host memory with those
+//! made through a `StagingPool`.
+//!
+//! Manipulate the consts below to try different transfer and chunk sizes.
+
+extern crate ocl;
+extern crate time;
+
+use ocl::{ProQue, Buffer, StagingPool};
+
+const DATASET_SIZE: usize = 1 << 22;
+const TRANSFER_ITERS: usize = 20;
+
+const CHUNK_SIZE: usize = 1 << 20;
+const CHUNK_COUNT: usize = 4;
+
+
+fn print_elapsed(title: &str, start_ns: u64) {
+    let elapsed_ns = time::precise_time_ns() - start_ns;
+    let bytes = (DATASET_SIZE * 4 * TRANSFER_ITERS) as f64;
+    println!("    {}: {:.3} ms total, {:.3} GB/s.", title, elapsed_ns as f64 / 1_000_000.0,
+        bytes / elapsed_ns as f64);
+}
+
+fn main() {
+    let ocl_pq = ProQue::builder().src("__kernel void nop() {}").dims([DATASET_SIZE])
+        .build().unwrap();
+
+    let buffer = Buffer::<f32>::new(ocl_pq.queue(), None, [DATASET_SIZE], None).unwrap();
+    let pool = StagingPool::new(ocl_pq.queue(), CHUNK_SIZE, CHUNK_COUNT).unwrap();
+
+    let src: Vec<f32> = (0..DATASET_SIZE).map(|v| v as f32).collect();
+    let mut dst = vec![0.0f32; DATASET_SIZE];
+
+    println!("Transferring {} bytes {} times...", DATASET_SIZE * 4, TRANSFER_ITERS);
+
+    let start = time::precise_time_ns();
+    for _ in 0..TRANSFER_ITERS {
+        buffer.write(&src).enq().unwrap();
+    }
+    print_elapsed("Direct write", start);
+
+    let start = time::precise_time_ns();
+    for _ in 0..TRANSFER_ITERS {
+        pool.write(&buffer, &src).unwrap();
+    }
+    print_elapsed(&format!("Staged write ({} x {} byte chunks)", CHUNK_COUNT, CHUNK_SIZE), start);
+
+    let start = time::precise_time_ns();
+    for _ in 0..TRANSFER_ITERS {
+        buffer.read(&mut dst).enq().unwrap();
+    }
+    print_elapsed("Direct read", start);
+
+    let start = time::precise_time_ns();
+    for _ in 0..TRANSFER_ITERS {
+        pool.read(&buffer, &mut dst).unwrap();
+    }
+    print_elapsed(&format!("Staged read ({} x {} byte chunks)", CHUNK_COUNT, CHUNK_SIZE), start);
+
+    assert_eq!(src, dst);
+}
@@ -37,7 +37,7 @@ fn main() {
 	let dims = [data_set_size];
 	let mut threads = Vec::new();
 
-	let platforms = Platform::list();
+	let platforms = Platform::list().unwrap();
 	// let platforms = &platforms_all[(platforms_all.len() - 1)..platforms_all.len()];
 
 	println!("Looping through avaliable platforms ({}):", platforms.len());
@@ -47,7 +47,7 @@ fn main() {
     	let platform = &platforms[p_idx];
     	printlnc!(green: "Platform[{}]: {} ({})", p_idx, platform.name(), platform.vendor());
 
-    	let devices = Device::list_all(platform);
+    	let devices = Device::list_all(platform).unwrap();
 
     	println!("DEVICES: {:?}", devices);
 
@@ -0,0 +1,56 @@
+//! Timed comparison of reads and writes performed by copying with those
+//! performed by mapping (see `TransferMode`).
+//!
+//! Mapping is expected to be faster on integrated GPUs (which share memory
+//! with the host) for buffers created with `MEM_ALLOC_HOST_PTR`.
+
+extern crate ocl;
+extern crate time;
+
+use ocl::{flags, ProQue, Buffer};
+use ocl::enums::TransferMode;
+
+const DATASET_SIZE: usize = 1 << 22;
+const TRANSFER_ITERS: usize = 20;
+
+
+fn print_elapsed(title: &str, start_ns: u64) {
+    let elapsed_ns = time::precise_time_ns() - start_ns;
+    let bytes = (DATASET_SIZE * 4 * TRANSFER_ITERS) as f64;
+    println!("    {}: {:.3} ms total, {:.3} GB/s.", title, elapsed_ns as f64 / 1_000_000.0,
+        bytes / elapsed_ns as f64);
+}
+
+fn main() {
+    let ocl_pq = ProQue::builder().src("__kernel void nop() {}").dims([DATASET_SIZE])
+        .build().unwrap();
+
+    println!("Device: '{}', host unified memory: {}.", ocl_pq.queue().device().name(),
+        ocl_pq.queue().device_host_unified_memory());
+
+    let buffer = Buffer::<f32>::builder()
+        .flags(flags::MEM_READ_WRITE | flags::MEM_ALLOC_HOST_PTR)
+        .dims([DATASET_SIZE])
+        .build(ocl_pq.queue()).unwrap();
+
+    let src: Vec<f32> = (0..DATASET_SIZE).map(|v| v as f32).collect();
+    let mut dst = vec![0.0f32; DATASET_SIZE];
+
+    println!("Transferring {} bytes {} times...", DATASET_SIZE * 4, TRANSFER_ITERS);
+
+    for &mode in &[TransferMode::Copy, TransferMode::Map, TransferMode::Auto] {
+        let start = time::precise_time_ns();
+        for _ in 0..TRANSFER_ITERS {
+            buffer.write(&src).transfer_mode(mode).enq().unwrap();
+        }
+        print_elapsed(&format!("Write ({:?})", mode), start);
+
+        let start = time::precise_time_ns();
+        for _ in 0..TRANSFER_ITERS {
+            buffer.read(&mut dst).transfer_mode(mode).enq().unwrap();
+        }
+        print_elapsed(&format!("Read ({:?})", mode), start);
+
+        assert_eq!(src, dst);
+    }
+}
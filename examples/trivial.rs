@@ -104,7 +104,7 @@ fn main_exploded() {
     // (1) Define which platform and device(s) to use. Create a context,
     // queue, and program then define some dims (compare to step 1 above).
     let platform = Platform::default();
-    let device = Device::first(platform);
+    let device = Device::first(platform).unwrap();
     let context = Context::builder()
         .platform(platform)
         .devices(device.clone())
@@ -171,7 +171,180 @@ enum_from_primitive! {
         CL_INVALID_COMPILER_OPTIONS                     = -66,
         CL_INVALID_LINKER_OPTIONS                       = -67,
         CL_INVALID_DEVICE_PARTITION_COUNT               = -68,
+        CL_INVALID_PIPE_SIZE                            = -69,
+        CL_INVALID_DEVICE_QUEUE                         = -70,
+        CL_INVALID_GL_SHAREGROUP_REFERENCE_KHR          = -1000,
         CL_PLATFORM_NOT_FOUND_KHR                       = -1001,
+        CL_INVALID_D3D10_DEVICE_KHR                     = -1002,
+        CL_INVALID_D3D10_RESOURCE_KHR                   = -1003,
+        CL_D3D10_RESOURCE_ALREADY_ACQUIRED_KHR          = -1004,
+        CL_D3D10_RESOURCE_NOT_ACQUIRED_KHR              = -1005,
+        CL_INVALID_D3D11_DEVICE_KHR                     = -1006,
+        CL_INVALID_D3D11_RESOURCE_KHR                   = -1007,
+        CL_D3D11_RESOURCE_ALREADY_ACQUIRED_KHR          = -1008,
+        CL_D3D11_RESOURCE_NOT_ACQUIRED_KHR              = -1009,
+        CL_INVALID_DX9_MEDIA_ADAPTER_KHR                = -1010,
+        CL_INVALID_DX9_MEDIA_SURFACE_KHR                = -1011,
+        CL_DX9_MEDIA_SURFACE_ALREADY_ACQUIRED_KHR       = -1012,
+        CL_DX9_MEDIA_SURFACE_NOT_ACQUIRED_KHR           = -1013,
+        CL_DEVICE_PARTITION_FAILED_EXT                  = -1057,
+        CL_INVALID_PARTITION_COUNT_EXT                  = -1058,
+        CL_INVALID_PARTITION_NAME_EXT                   = -1059,
+        CL_EGL_RESOURCE_NOT_ACQUIRED_KHR                = -1092,
+        CL_INVALID_EGL_OBJECT_KHR                       = -1093,
+    }
+}
+
+impl Status {
+    /// Returns a one-line description of the meaning of this status code
+    /// (paraphrased from the OpenCL specification).
+    pub fn description(&self) -> &'static str {
+        match *self {
+            Status::CL_SUCCESS => "The command executed successfully.",
+            Status::CL_DEVICE_NOT_FOUND => 
+                "No OpenCL devices that matched the given device type were found.",
+            Status::CL_DEVICE_NOT_AVAILABLE => "The device is currently not available.",
+            Status::CL_COMPILER_NOT_AVAILABLE => "A compiler is not available for the device.",
+            Status::CL_MEM_OBJECT_ALLOCATION_FAILURE => 
+                "There was a failure to allocate memory for a memory object.",
+            Status::CL_OUT_OF_RESOURCES => 
+                "There was a failure to allocate resources required by the implementation on the device.",
+            Status::CL_OUT_OF_HOST_MEMORY => 
+                "There was a failure to allocate resources required by the implementation on the host.",
+            Status::CL_PROFILING_INFO_NOT_AVAILABLE => 
+                "Profiling information is not available for the event.",
+            Status::CL_MEM_COPY_OVERLAP => "The source and destination regions of a copy overlap.",
+            Status::CL_IMAGE_FORMAT_MISMATCH => 
+                "The source and destination images do not use the same image format.",
+            Status::CL_IMAGE_FORMAT_NOT_SUPPORTED => "The image format is not supported.",
+            Status::CL_BUILD_PROGRAM_FAILURE => 
+                "There was a failure to build the program executable.",
+            Status::CL_MAP_FAILURE => 
+                "There was a failure to map the requested region into the host address space.",
+            Status::CL_MISALIGNED_SUB_BUFFER_OFFSET => 
+                "The sub-buffer offset is not aligned to the device's base address alignment.",
+            Status::CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST => 
+                "The execution status of an event in the wait list is a negative integer.",
+            Status::CL_COMPILE_PROGRAM_FAILURE => 
+                "There was a failure to compile the program source.",
+            Status::CL_LINKER_NOT_AVAILABLE => "A linker is not available for the device.",
+            Status::CL_LINK_PROGRAM_FAILURE => 
+                "There was a failure to link the compiled binaries and/or libraries.",
+            Status::CL_DEVICE_PARTITION_FAILED => 
+                "The device could not be partitioned as requested.",
+            Status::CL_KERNEL_ARG_INFO_NOT_AVAILABLE => 
+                "Argument information is not available for the kernel.",
+            Status::CL_INVALID_VALUE => "One or more argument values are invalid.",
+            Status::CL_INVALID_DEVICE_TYPE => "The device type is not a valid value.",
+            Status::CL_INVALID_PLATFORM => "The platform is not a valid platform.",
+            Status::CL_INVALID_DEVICE => 
+                "The device is not a valid device or is not associated with the context or platform.",
+            Status::CL_INVALID_CONTEXT => "The context is not a valid context.",
+            Status::CL_INVALID_QUEUE_PROPERTIES => 
+                "The command-queue properties are valid but not supported by the device.",
+            Status::CL_INVALID_COMMAND_QUEUE => "The command-queue is not a valid command-queue.",
+            Status::CL_INVALID_HOST_PTR => 
+                "The host pointer is invalid for the given memory flags.",
+            Status::CL_INVALID_MEM_OBJECT => "The memory object is not a valid memory object.",
+            Status::CL_INVALID_IMAGE_FORMAT_DESCRIPTOR => 
+                "The image format descriptor is not valid.",
+            Status::CL_INVALID_IMAGE_SIZE => 
+                "The image dimensions exceed the maximum supported by the device.",
+            Status::CL_INVALID_SAMPLER => "The sampler is not a valid sampler object.",
+            Status::CL_INVALID_BINARY => "An invalid program binary was encountered for a device.",
+            Status::CL_INVALID_BUILD_OPTIONS => "The build options are invalid.",
+            Status::CL_INVALID_PROGRAM => "The program is not a valid program object.",
+            Status::CL_INVALID_PROGRAM_EXECUTABLE => 
+                "There is no successfully built program executable for the device.",
+            Status::CL_INVALID_KERNEL_NAME => "The kernel name was not found in the program.",
+            Status::CL_INVALID_KERNEL_DEFINITION => 
+                "The kernel function definition is not the same for all devices.",
+            Status::CL_INVALID_KERNEL => "The kernel is not a valid kernel object.",
+            Status::CL_INVALID_ARG_INDEX => "The argument index is not valid for the kernel.",
+            Status::CL_INVALID_ARG_VALUE => 
+                "The argument value is not valid for the kernel argument.",
+            Status::CL_INVALID_ARG_SIZE => 
+                "The argument size does not match the size of the kernel argument.",
+            Status::CL_INVALID_KERNEL_ARGS => "The kernel argument values have not been specified.",
+            Status::CL_INVALID_WORK_DIMENSION => "The number of work dimensions is not valid.",
+            Status::CL_INVALID_WORK_GROUP_SIZE => 
+                "The local work size is invalid for the global work size or the device/kernel limits.",
+            Status::CL_INVALID_WORK_ITEM_SIZE => 
+                "The number of work-items in a dimension exceeds the device maximum.",
+            Status::CL_INVALID_GLOBAL_OFFSET => 
+                "The global work offset exceeds the maximum supported value.",
+            Status::CL_INVALID_EVENT_WAIT_LIST => 
+                "The event wait list is invalid or contains invalid events.",
+            Status::CL_INVALID_EVENT => "The event is not a valid event object.",
+            Status::CL_INVALID_OPERATION => "The operation is not valid in the current state.",
+            Status::CL_INVALID_GL_OBJECT => "The OpenGL object is not valid.",
+            Status::CL_INVALID_BUFFER_SIZE => 
+                "The buffer size is zero or exceeds the maximum allocation size.",
+            Status::CL_INVALID_MIP_LEVEL => "The mipmap level is not valid.",
+            Status::CL_INVALID_GLOBAL_WORK_SIZE => 
+                "The global work size is zero or exceeds the maximum supported value.",
+            Status::CL_INVALID_PROPERTY => "A property name or value is not valid.",
+            Status::CL_INVALID_IMAGE_DESCRIPTOR => "The image descriptor is not valid.",
+            Status::CL_INVALID_COMPILER_OPTIONS => "The compiler options are invalid.",
+            Status::CL_INVALID_LINKER_OPTIONS => "The linker options are invalid.",
+            Status::CL_INVALID_DEVICE_PARTITION_COUNT => 
+                "The requested device partition count is not valid.",
+            Status::CL_INVALID_PIPE_SIZE => 
+                "The pipe packet size or maximum packet count is not valid.",
+            Status::CL_INVALID_DEVICE_QUEUE => "The device queue is not a valid device queue.",
+            Status::CL_INVALID_GL_SHAREGROUP_REFERENCE_KHR => 
+                "The OpenGL share group reference is not valid.",
+            Status::CL_PLATFORM_NOT_FOUND_KHR => "No valid ICD platforms were found.",
+            Status::CL_INVALID_D3D10_DEVICE_KHR => "The Direct3D 10 device is not valid.",
+            Status::CL_INVALID_D3D10_RESOURCE_KHR => "The Direct3D 10 resource is not valid.",
+            Status::CL_D3D10_RESOURCE_ALREADY_ACQUIRED_KHR => 
+                "The Direct3D 10 resource has already been acquired.",
+            Status::CL_D3D10_RESOURCE_NOT_ACQUIRED_KHR => 
+                "The Direct3D 10 resource has not been acquired.",
+            Status::CL_INVALID_D3D11_DEVICE_KHR => "The Direct3D 11 device is not valid.",
+            Status::CL_INVALID_D3D11_RESOURCE_KHR => "The Direct3D 11 resource is not valid.",
+            Status::CL_D3D11_RESOURCE_ALREADY_ACQUIRED_KHR => 
+                "The Direct3D 11 resource has already been acquired.",
+            Status::CL_D3D11_RESOURCE_NOT_ACQUIRED_KHR => 
+                "The Direct3D 11 resource has not been acquired.",
+            Status::CL_INVALID_DX9_MEDIA_ADAPTER_KHR => "The DirectX 9 media adapter is not valid.",
+            Status::CL_INVALID_DX9_MEDIA_SURFACE_KHR => "The DirectX 9 media surface is not valid.",
+            Status::CL_DX9_MEDIA_SURFACE_ALREADY_ACQUIRED_KHR => 
+                "The DirectX 9 media surface has already been acquired.",
+            Status::CL_DX9_MEDIA_SURFACE_NOT_ACQUIRED_KHR => 
+                "The DirectX 9 media surface has not been acquired.",
+            Status::CL_DEVICE_PARTITION_FAILED_EXT => 
+                "The device could not be partitioned as requested.",
+            Status::CL_INVALID_PARTITION_COUNT_EXT => 
+                "The requested device partition count is not valid.",
+            Status::CL_INVALID_PARTITION_NAME_EXT => "The device partition name is not valid.",
+            Status::CL_EGL_RESOURCE_NOT_ACQUIRED_KHR => "The EGL resource has not been acquired.",
+            Status::CL_INVALID_EGL_OBJECT_KHR => "The EGL object is not valid.",
+        }
+    }
+}
+
+impl Status {
+    /// Returns true if an operation which failed with this status may succeed
+    /// if retried later or with a smaller workload.
+    ///
+    /// | Status                             | Retryable |
+    /// |------------------------------------|-----------|
+    /// | `CL_DEVICE_NOT_AVAILABLE`          | yes       |
+    /// | `CL_MEM_OBJECT_ALLOCATION_FAILURE` | yes       |
+    /// | `CL_OUT_OF_RESOURCES`              | yes       |
+    /// | `CL_OUT_OF_HOST_MEMORY`            | yes       |
+    /// | everything else (incl. `CL_INVALID_*`) | no    |
+    ///
+    /// Use `ocl::RetryPolicy::classifier` to override this classification.
+    pub fn is_retryable(&self) -> bool {
+        match *self {
+            Status::CL_DEVICE_NOT_AVAILABLE |
+            Status::CL_MEM_OBJECT_ALLOCATION_FAILURE |
+            Status::CL_OUT_OF_RESOURCES |
+            Status::CL_OUT_OF_HOST_MEMORY => true,
+            _ => false,
+        }
     }
 }
 
@@ -476,10 +649,10 @@ pub const CL_PROGRAM_BINARY_TYPE_LIBRARY:               cl_bitfield = 0x2;
 pub const CL_PROGRAM_BINARY_TYPE_EXECUTABLE:            cl_bitfield = 0x4;
 
 // cl_build_status 
-pub const CL_BUILD_SUCCESS:                             cl_uint = 0;
-pub const CL_BUILD_NONE:                                cl_uint = !0 - 1;
-pub const CL_BUILD_ERROR:                               cl_uint = !0 - 2;
-pub const CL_BUILD_IN_PROGRESS:                         cl_uint = !0 - 3;
+pub const CL_BUILD_SUCCESS:                             cl_build_status = 0;
+pub const CL_BUILD_NONE:                                cl_build_status = -1;
+pub const CL_BUILD_ERROR:                               cl_build_status = -2;
+pub const CL_BUILD_IN_PROGRESS:                         cl_build_status = -3;
 
 // cl_kernel_info
 pub const CL_KERNEL_FUNCTION_NAME:                      cl_uint = 0x1190;
@@ -572,11 +745,91 @@ pub const CL_PROFILING_COMMAND_START:                   cl_uint = 0x1282;
 pub const CL_PROFILING_COMMAND_END:                     cl_uint = 0x1283;
 
 
-//#[link_args = "-L$OPENCL_LIB -lOpenCL"]
-#[cfg_attr(target_os = "macos", link(name = "OpenCL", kind = "framework"))]
-#[cfg_attr(target_os = "windows", link(name = "OpenCL"))]
-#[cfg_attr(not(target_os = "macos"), link(name = "OpenCL"))]
-extern "C" {
+/// Declares the OpenCL API functions.
+///
+/// Without the `dynamic-loading` or `stub` features these are the `extern`
+/// functions themselves, linked as directed by the build script (see
+/// `build.rs`).
+///
+/// With `dynamic-loading`, each is an `unsafe fn` of the same signature
+/// which resolves its symbol from the library loaded at runtime upon first
+/// use (see `ocl::is_opencl_available`). Should the library or symbol be
+/// unavailable, an error code or null pointer is returned in place of
+/// calling it and the reason recorded for `core` functions to report.
+///
+/// With `stub`, each calls the method of the same name of `ClApi`, which the
+/// in-crate stub implements for a subset of functions. The rest return
+/// an error code or null pointer in the same way.
+macro_rules! cl_api {
+    ($(pub fn $name:ident($($arg:ident: $ty:ty),* $(,)*) -> $ret:ty;)*) => {
+        // The library (or macOS framework) is linked by the build script:
+        #[cfg(not(any(feature = "dynamic-loading", feature = "stub")))]
+        extern "C" {
+            $(pub fn $name($($arg: $ty),*) -> $ret;)*
+        }
+
+        $(
+            #[cfg(feature = "dynamic-loading")]
+            #[allow(non_snake_case)]
+            pub unsafe fn $name($($arg: $ty),*) -> $ret {
+                static SYMBOL: ::loader::Symbol = ::loader::Symbol::new(stringify!($name),
+                    concat!(stringify!($name), "\0"));
+
+                match SYMBOL.addr() {
+                    Some(addr) => {
+                        let f: unsafe extern "C" fn($($ty),*) -> $ret = ::std::mem::transmute(addr);
+                        f($($arg),*)
+                    },
+                    None => Unavailable::unavailable(),
+                }
+            }
+        )*
+
+        /// The functions implemented by the stub (see `::stub`), each of
+        /// which is otherwise unsupported.
+        #[cfg(feature = "stub")]
+        #[allow(non_snake_case)]
+        pub(crate) trait ClApi {
+            $(
+                unsafe fn $name($($arg: $ty),*) -> $ret {
+                    ::stub::unsupported(stringify!($name))
+                }
+            )*
+        }
+
+        $(
+            #[cfg(feature = "stub")]
+            #[allow(non_snake_case)]
+            pub unsafe fn $name($($arg: $ty),*) -> $ret {
+                <::stub::Stub as ClApi>::$name($($arg),*)
+            }
+        )*
+    };
+}
+
+/// The value returned by a `cl_h` function in place of calling an
+/// unavailable library function.
+#[cfg(any(feature = "dynamic-loading", feature = "stub"))]
+pub(crate) trait Unavailable {
+    fn unavailable() -> Self;
+}
+
+#[cfg(any(feature = "dynamic-loading", feature = "stub"))]
+impl Unavailable for cl_int {
+    fn unavailable() -> cl_int {
+        // Must not be `CL_PLATFORM_NOT_FOUND_KHR`, which is retried:
+        Status::CL_INVALID_OPERATION as cl_int
+    }
+}
+
+#[cfg(any(feature = "dynamic-loading", feature = "stub"))]
+impl Unavailable for *mut c_void {
+    fn unavailable() -> *mut c_void {
+        ::std::ptr::null_mut()
+    }
+}
+
+cl_api! {
     // Platform API
     pub fn clGetPlatformIDs(num_entries:   cl_uint,
                             platforms:     *mut cl_platform_id,
@@ -847,9 +1100,10 @@ extern "C" {
     // //################## NEW 1.2 ###################
     // extern CL_API_ENTRY cl_int CL_API_CALL
     // clUnloadPlatformCompiler(cl_platform_id /* platform */) CL_API_SUFFIX__VERSION_1_2;
-    // //################## NEW 1.2 ###################
-    // [DISABLED DUE TO PLATFORM INCOMPATABILITY]
-    // pub fn clUnloadPlatformCompiler(platform: cl_platform_id) -> cl_int;
+    pub fn clUnloadPlatformCompiler(platform: cl_platform_id) -> cl_int;
+
+    // [DEPRICATED 1.1]
+    pub fn clUnloadCompiler() -> cl_int;
 
     pub fn clGetProgramInfo(program: cl_program,
                         param_name: cl_program_info,
@@ -1137,8 +1391,8 @@ extern "C" {
                          map_flags: cl_map_flags,
                          origin: *const size_t,
                          region: *const size_t,
-                         image_row_pitch: size_t,
-                         image_slc_pitch: size_t,
+                         image_row_pitch: *mut size_t,
+                         image_slice_pitch: *mut size_t,
                          num_events_in_wait_list: cl_uint,
                          event_wait_list: *const cl_event,
                          event: *mut cl_event,
@@ -1196,9 +1450,9 @@ extern "C" {
                              event_wait_list: *const cl_event,
                              event: *mut cl_event) -> cl_int;
 
-    // //##### DEPRICATED 1.1 #####
-    // pub fn clEnqueueMarker(command_queue: cl_command_queue,
-    //                 event: *mut cl_event) -> cl_int;
+    // [DEPRICATED 1.1]
+    pub fn clEnqueueMarker(command_queue: cl_command_queue,
+                    event: *mut cl_event) -> cl_int;
 
     // //################## NEW 1.2 ###################
     // extern CL_API_ENTRY cl_int CL_API_CALL
@@ -1212,10 +1466,10 @@ extern "C" {
              event_wait_list: *const cl_event,
              event: *mut cl_event) -> cl_int;
 
-    // //##### DEPRICATED 1.1 #####
-    // pub fn clEnqueueWaitForEvents(command_queue: cl_command_queue,
-    //                        num_events: cl_uint,
-    //                        event_list: *mut cl_event) -> cl_int;
+    // [DEPRICATED 1.1]
+    pub fn clEnqueueWaitForEvents(command_queue: cl_command_queue,
+                           num_events: cl_uint,
+                           event_list: *const cl_event) -> cl_int;
 
     // //################## NEW 1.2 ###################
     // extern CL_API_ENTRY cl_int CL_API_CALL
@@ -1240,8 +1494,8 @@ extern "C" {
              event: *mut cl_event) -> cl_int;
 
 
-    // //##### DEPRICATED 1.1 #####
-    // pub fn clEnqueueBarrier(command_queue: cl_command_queue) -> cl_int;
+    // [DEPRICATED 1.1]
+    pub fn clEnqueueBarrier(command_queue: cl_command_queue) -> cl_int;
 
     // //##### DEPRICATED 1.1 #####
     // Extension function access
@@ -1249,7 +1503,7 @@ extern "C" {
     // or NULL if a valid function can not be found. The client must
     // check to make sure the address is not NULL, before using or
     // or calling the returned function address.
-    // pub fn clGetExtensionFunctionAddress(func_name: *mut c_char);
+    pub fn clGetExtensionFunctionAddress(func_name: *const c_char) -> *mut c_void;
 
     // //################## NEW 1.2 ###################
     // extern CL_API_ENTRY void * CL_API_CALL 
@@ -1260,4 +1514,13 @@ extern "C" {
     //################## NEW 1.2 ###################
     pub fn clGetExtensionFunctionAddressForPlatform(platform: cl_platform_id,
                        func_name: *const c_char) -> *mut c_void;
+
+    //################## NEW 2.1 ###################
+    pub fn clGetDeviceAndHostTimer(device: cl_device_id,
+                       device_timestamp: *mut cl_ulong,
+                       host_timestamp: *mut cl_ulong) -> cl_int;
+
+    //################## NEW 2.1 ###################
+    pub fn clGetHostTimer(device: cl_device_id,
+                       host_timestamp: *mut cl_ulong) -> cl_int;
 }
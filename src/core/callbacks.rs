@@ -0,0 +1,235 @@
+//! Trampolines through which the OpenCL implementation calls user closures.
+//!
+//! Closures are passed to the implementation by way of the `user_data`
+//! pointer of each callback and are run on whichever thread the
+//! implementation chooses, so must be `Send + 'static`. Panics are caught
+//! within every trampoline (unwinding into the implementation is undefined
+//! behavior) and reported rather than propagated: each is logged (with the
+//! `logging` feature, at the error level with the target
+//! `ocl::core::callback`, or to stderr otherwise) and counted (see
+//! `callback_panic_count`).
+//!
+//! ## Ownership
+//!
+//! One-shot closures (those of event, memory object destructor, program
+//! build, and native kernel callbacks) are registered with `register_once`.
+//! Each is kept in a slot shared by the registering function and the
+//! trampoline so that it is dropped exactly once whether the callback is
+//! invoked after registration returns, synchronously during registration (as
+//! some implementations do), or never (because registration failed).
+//!
+//! Context notify closures, registered with `register_persistent`, may be
+//! invoked any number of times for as long as the context exists. OpenCL
+//! (prior to 3.0) provides no way to learn when a context is destroyed, so
+//! they are dropped only if registration fails.
+
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use libc::{c_void, c_char, size_t};
+use num::FromPrimitive;
+use cl_h::{cl_event, cl_int, cl_mem, cl_program};
+use error::{Error as OclError, Result as OclResult};
+use core::CommandExecutionStatus;
+
+/// The slot holding a one-shot closure until it is taken by a trampoline.
+type Slot<F> = Mutex<Option<F>>;
+
+/// The number of callback closures which have panicked.
+static PANIC_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of callback closures, process-wide, which have
+/// panicked (each panic having been caught instead of unwinding into the
+/// OpenCL implementation).
+///
+/// Callbacks run on threads chosen by the implementation, so this (or the
+/// `logging` feature) is the only way to learn of such a panic.
+pub fn callback_panic_count() -> usize {
+    PANIC_COUNT.load(Ordering::SeqCst)
+}
+
+/// Runs `f`, catching any panic and reporting it as having occurred within
+/// the callback registered by `fn_name` instead of unwinding.
+///
+/// Returns true if `f` panicked.
+pub(crate) fn catch_panic<F: FnOnce()>(fn_name: &str, f: F) -> bool {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(()) => false,
+        Err(_) => {
+            PANIC_COUNT.fetch_add(1, Ordering::SeqCst);
+            report_panic(fn_name);
+            true
+        },
+    }
+}
+
+#[cfg(feature = "logging")]
+fn report_panic(fn_name: &str) {
+    error!(target: "ocl::core::callback", "ocl::core::{}(): The callback closure panicked.",
+        fn_name);
+}
+
+#[cfg(not(feature = "logging"))]
+fn report_panic(fn_name: &str) {
+    eprintln!("ocl::core::{}(): The callback closure panicked.", fn_name);
+}
+
+/// Registers the one-shot closure `f` by calling `register` with the
+/// `user_data` pointer to pass to the implementation (along with the
+/// trampoline for `F`, such as `event_trampoline::<F>`).
+///
+/// If `register` returns an error, the closure is dropped (unless the
+/// trampoline has already taken it) and the error returned.
+pub(crate) fn register_once<F, R>(f: F, register: R) -> OclResult<()>
+        where F: Send + 'static, R: FnOnce(*mut c_void) -> OclResult<()>
+{
+    let slot: Arc<Slot<F>> = Arc::new(Mutex::new(Some(f)));
+    let user_data = Arc::into_raw(slot.clone()) as *mut c_void;
+
+    match register(user_data) {
+        Ok(()) => Ok(()),
+        Err(err) => {
+            // The callback will never be invoked. Reclaim the trampoline's
+            // reference unless it was invoked during registration:
+            if Arc::strong_count(&slot) > 1 {
+                unsafe { drop(Arc::from_raw(user_data as *const Slot<F>)); }
+            }
+            Err(err)
+        },
+    }
+}
+
+/// Registers the closure `f`, which may be invoked any number of times, by
+/// calling `register` with the `user_data` pointer to pass to the
+/// implementation.
+///
+/// If `register` returns an error the closure is dropped and the error
+/// returned. Otherwise it is never dropped.
+pub(crate) fn register_persistent<F, R>(f: F, register: R) -> OclResult<()>
+        where F: Send + Sync + 'static, R: FnOnce(*mut c_void) -> OclResult<()>
+{
+    let user_data = Box::into_raw(Box::new(f)) as *mut c_void;
+
+    match register(user_data) {
+        Ok(()) => Ok(()),
+        Err(err) => {
+            // Any invocations during registration have returned:
+            unsafe { drop(Box::from_raw(user_data as *mut F)); }
+            Err(err)
+        },
+    }
+}
+
+/// Takes the closure registered with `register_once` from its slot,
+/// releasing the reference held on behalf of the trampoline.
+///
+/// Must be called only once for each registration.
+unsafe fn take_once<F>(user_data: *mut c_void) -> Option<F> {
+    let slot = Arc::from_raw(user_data as *const Slot<F>);
+    let f = match slot.lock() {
+        Ok(mut f) => f.take(),
+        Err(poisoned) => poisoned.into_inner().take(),
+    };
+    f
+}
+
+/// Converts the status passed to an event callback into the execution
+/// status which triggered it or, if the command terminated abnormally, an
+/// error.
+fn event_status(status: cl_int) -> OclResult<CommandExecutionStatus> {
+    match CommandExecutionStatus::from_i32(status) {
+        Some(status) => Ok(status),
+        None => match OclError::err_status::<(), _>(status, "clSetEventCallback",
+                "The command terminated abnormally.") {
+            Err(err) => Err(err),
+            Ok(()) => OclError::err(format!("ocl::core::set_event_callback_fn(): Unknown \
+                command execution status: '{}'.", status)),
+        },
+    }
+}
+
+/// The trampoline for event callback closures (see
+/// `core::set_event_callback_fn`).
+pub(crate) extern "C" fn event_trampoline<F>(_: cl_event, status: cl_int,
+        user_data: *mut c_void)
+        where F: FnOnce(OclResult<CommandExecutionStatus>) + Send + 'static
+{
+    catch_panic("set_event_callback_fn", || {
+        if let Some(f) = unsafe { take_once::<F>(user_data) } {
+            f(event_status(status));
+        }
+    });
+}
+
+/// The trampoline for memory object destructor closures (see
+/// `core::set_mem_object_destructor_callback`).
+pub(crate) extern "C" fn mem_destructor_trampoline<F>(_: cl_mem, user_data: *mut c_void)
+        where F: FnOnce() + Send + 'static
+{
+    catch_panic("set_mem_object_destructor_callback", || {
+        if let Some(f) = unsafe { take_once::<F>(user_data) } {
+            f();
+        }
+    });
+}
+
+/// The trampoline for program build closures (see
+/// `core::build_program_notify`).
+pub(crate) extern "C" fn build_program_trampoline<F>(_: cl_program, user_data: *mut c_void)
+        where F: FnOnce() + Send + 'static
+{
+    catch_panic("build_program_notify", || {
+        if let Some(f) = unsafe { take_once::<F>(user_data) } {
+            f();
+        }
+    });
+}
+
+/// The trampoline for native kernel closures (see `core::enqueue_native`).
+///
+/// `args` points to (a copy of) the words written by `enqueue_native`: the
+/// `user_data` pointer of the closure, the memory object count `n`, `n`
+/// pointers to the contents of each memory object (substituted by the
+/// implementation), and `n` sizes in bytes.
+pub(crate) extern "C" fn native_trampoline<F>(args: *mut c_void)
+        where F: FnOnce(&mut [&mut [u8]]) + Send + 'static
+{
+    catch_panic("enqueue_native", || unsafe {
+        let words = args as *mut usize;
+
+        if let Some(f) = take_once::<F>(*words as *mut c_void) {
+            let mem_count = *words.offset(1);
+            let ptrs = words.offset(2) as *const *mut u8;
+            let sizes = words.offset(2 + mem_count as isize);
+
+            let mut mems: Vec<&mut [u8]> = (0..mem_count as isize)
+                .map(|i| slice::from_raw_parts_mut(*ptrs.offset(i), *sizes.offset(i)))
+                .collect();
+
+            f(&mut mems);
+        }
+    });
+}
+
+/// The trampoline for context notify closures (see
+/// `core::create_context_notify`).
+///
+/// The closure receives the error information string provided by the
+/// implementation.
+pub(crate) extern "C" fn context_notify_trampoline<F>(errinfo: *const c_char,
+        _: *const c_void, _: size_t, user_data: *mut c_void)
+        where F: Fn(&str) + Send + Sync + 'static
+{
+    catch_panic("create_context_notify", || unsafe {
+        let f = &*(user_data as *const F);
+        let errinfo = if errinfo.is_null() {
+            Cow::Borrowed("")
+        } else {
+            CStr::from_ptr(errinfo).to_string_lossy()
+        };
+        f(&errinfo);
+    });
+}
@@ -4,22 +4,33 @@
 //!
 //! Redundant casts are temporary for development and will be removed.
 //!
+//! ## Errors
+//!
+//! Every error code returned by the driver is passed back to the caller as an
+//! `Err` (usually `Error::Status`). Enable the `panic_on_error` feature to
+//! panic at the point of failure instead (useful during development).
+//!
 //! POSSIBLE TODO: Break this file up
 //!
 
 // #![allow(dead_code)]
 
 use std::ptr;
+use std::slice;
+use std::panic;
 use std::mem;
 use std::io::Read;
 use std::ffi::CString;
 use std::iter;
+use std::cmp;
 // #[cfg(any(feature="kernel_debug_sleep", target_os="windows"))] use std::thread;
 // #[cfg(any(feature="kernel_debug_sleep", target_os="windows"))] use std::time::Duration;
 use std::thread;
 use std::time::Duration;
 use std::env;
 use std::fmt::Debug;
+#[cfg(test)] use std::cell::RefCell;
+#[cfg(test)] use std::collections::HashMap;
 use libc::{size_t, c_void};
 use num::FromPrimitive;
 
@@ -29,8 +40,11 @@ use cl_h::{self, cl_bool, cl_int, cl_uint, cl_platform_id, cl_device_id, cl_devi
     cl_mem_flags, cl_mem_object_type, cl_buffer_create_type, cl_event, cl_program, 
     cl_addressing_mode, cl_filter_mode, cl_command_queue_info, cl_command_queue, cl_image_info, 
     cl_sampler, cl_sampler_info, cl_program_info, cl_kernel_info, cl_kernel_arg_info, 
-    cl_kernel_work_group_info, cl_event_info, cl_profiling_info};
-use error::{Error as OclError, Result as OclResult};
+    cl_kernel_work_group_info, cl_event_info, cl_profiling_info, cl_ulong};
+use error::{Error as OclError, Result as OclResult, ProgramBuildError};
+use util;
+use core::callbacks;
+#[cfg(feature = "logging")] use core::logging;
 use core::{self, OclPrm, PlatformId, DeviceId, Context, ContextProperties, ContextInfo, 
     ContextInfoResult,  MemFlags, CommandQueue, Mem, MemObjectType, Program, Kernel, 
     ClEventPtrNew, Event, Sampler, KernelArg, DeviceType, ImageFormat, 
@@ -41,8 +55,8 @@ use core::{self, OclPrm, PlatformId, DeviceId, Context, ContextProperties, Conte
     KernelInfoResult, KernelArgInfo, KernelArgInfoResult, KernelWorkGroupInfo, 
     KernelWorkGroupInfoResult, ClEventRef, ClWaitList, EventInfo, EventInfoResult, ProfilingInfo, 
     ProfilingInfoResult, CreateContextCallbackFn, UserDataPtr, ClPlatformIdPtr, ClDeviceIdPtr, 
-    EventCallbackFn, BuildProgramCallbackFn, MemMigrationFlags, MapFlags, BufferRegion, 
-    BufferCreateType};
+    EventCallbackFn, BuildProgramCallbackFn, NativeKernelFn, MemMigrationFlags, MapFlags, BufferRegion, 
+    BufferCreateType, WorkSizes, InfoBytes};
 
 // #[cfg(feature="kernel_debug_sleep")] 
 const KERNEL_DEBUG_SLEEP_DURATION_MS: u64 = 150;
@@ -53,11 +67,202 @@ const KERNEL_DEBUG_SLEEP_DURATION_MS: u64 = 150;
 //============================================================================
 //============================================================================
 
-/// [DEPRICATED] Evaluates `errcode` and returns an `Err` with a failure
-/// message if it is not 0.
+/// Evaluates `errcode` and returns an `Err` containing a `Status` error if it
+/// is not `CL_SUCCESS`.
+///
+/// `cl_fn_name` is the name of the OpenCL function which returned `errcode`.
+/// `fn_info` should return the key arguments of the call (e.g. "size=4096
+/// offset=8192") and is only called if an error has actually occurred.
+///
+/// Panics instead of returning an error if the `panic_on_error` feature is
+/// enabled.
+///
+/// With the `dynamic-loading` feature, the error recorded when the function
+/// could not be called at all (see `is_opencl_available`) takes precedence.
+///
+fn errcode_try<F>(cl_fn_name: &'static str, fn_info: F, errcode: cl_int) -> OclResult<()>
+        where F: FnOnce() -> String
+{
+    let load_error = load_error();
+
+    if load_error.is_none() && errcode == cl_h::Status::CL_SUCCESS as cl_int {
+        Ok(())
+    } else {
+        let result = match load_error {
+            Some(err) => Err(err),
+            None => OclError::err_status(errcode, cl_fn_name, fn_info()),
+        };
+
+        if cfg!(feature="panic_on_error") {
+            if let Err(ref err) = result { panic!("{:?}", err); }
+        }
+
+        result
+    }
+}
+
+/// Evaluates the `errcode` of a command enqueued on `command_queue` as
+/// `errcode_try` does, then logs the command if the `logging` feature is
+/// enabled (see `::logging`).
+///
+/// `new_event_ptr` must be null or point to the new event of the command.
+fn enqueue_try<F>(cl_fn_name: &'static str, command_queue: &CommandQueue, wait_list_len: cl_uint,
+        new_event_ptr: *mut cl_event, fn_info: F, errcode: cl_int) -> OclResult<()>
+        where F: FnOnce() -> String
+{
+    #[cfg(feature = "logging")]
+    {
+        if logging::enqueue_enabled() {
+            // Evaluated before logging, which queries the queue, so that the
+            // load error (if any) of the command itself is the one returned:
+            let info = fn_info();
+            let result = errcode_try(cl_fn_name, || info.clone(), errcode);
+            unsafe { logging::enqueue(cl_fn_name, command_queue, &info, wait_list_len,
+                new_event_ptr, errcode); }
+            return result;
+        }
+    }
+    #[cfg(not(feature = "logging"))]
+    let _ = (command_queue, wait_list_len, new_event_ptr);
+
+    errcode_try(cl_fn_name, fn_info, errcode)
+}
+
+/// Returns the error explaining why the most recently called `cl_h` function
+/// did not reach the library, if it did not (see `::loader`).
+#[cfg(feature = "dynamic-loading")]
+fn load_error() -> Option<OclError> {
+    ::loader::take_error()
+}
+
+/// Returns the error for the most recently called `cl_h` function if it is
+/// not supported by the stub (see `::stub`).
+#[cfg(feature = "stub")]
+fn load_error() -> Option<OclError> {
+    ::stub::take_error()
+}
+
+#[cfg(not(any(feature = "dynamic-loading", feature = "stub")))]
+fn load_error() -> Option<OclError> {
+    None
+}
+
+/// The number of times an info query is attempted when the result grows
+/// between querying its size and its value.
+pub(crate) const INFO_QUERY_ATTEMPTS: usize = 3;
+
+/// The largest info query result (in bytes) accepted from a driver.
+pub(crate) const INFO_RESULT_SIZE_MAX: size_t = 1 << 28;
+
+/// Returns the size (in bytes) of an info query result.
 ///
-fn errcode_try(cl_fn_name: &'static str, fn_info: &str, errcode: cl_int) -> OclResult<()> {
-    OclError::err_status(errcode, cl_fn_name, fn_info)
+/// `query` calls a `clGet*Info` function with the `param_value_size`,
+/// `param_value`, and `param_value_size_ret` arguments it is passed.
+fn get_info_size<Q, I>(cl_fn_name: &'static str, fn_info: I, query: &Q) -> OclResult<size_t>
+        where Q: Fn(size_t, *mut c_void, *mut size_t) -> cl_int, I: Fn() -> String
+{
+    let mut result_size: size_t = 0;
+    let errcode = query(0, ptr::null_mut(), &mut result_size as *mut size_t);
+    try!(errcode_try(cl_fn_name, &fn_info, errcode));
+
+    if result_size > INFO_RESULT_SIZE_MAX {
+        return OclError::err(format!("ocl::core::{}: The reported result size ({} bytes) \
+            exceeds the maximum of {} bytes ({}).", cl_fn_name, result_size,
+            INFO_RESULT_SIZE_MAX, fn_info()));
+    }
+    Ok(result_size)
+}
+
+/// Returns the bytes of an info query result, allocating `result_size`
+/// bytes (see `get_info_size`), stored inline if small enough.
+///
+/// The allocated capacity is always the size passed to the query. The size
+/// the query reports having written must not exceed it and the result is
+/// truncated to that size if it is smaller.
+fn get_info_value<Q, I>(cl_fn_name: &'static str, fn_info: I, query: &Q, result_size: size_t)
+        -> OclResult<InfoBytes>
+        where Q: Fn(size_t, *mut c_void, *mut size_t) -> cl_int, I: Fn() -> String
+{
+    let mut result = InfoBytes::zeroed(result_size);
+
+    // If result size is zero, return an empty info result directly:
+    if result_size == 0 {
+        return Ok(result);
+    }
+
+    let capacity = result.len();
+    let mut size_ret: size_t = capacity;
+    let errcode = query(capacity, result.as_mut_ptr() as *mut c_void, &mut size_ret);
+    try!(errcode_try(cl_fn_name, &fn_info, errcode));
+
+    if size_ret > capacity {
+        return OclError::err(format!("ocl::core::{}: The reported result size ({} bytes) \
+            exceeds the size of the result buffer ({} bytes) ({}).", cl_fn_name, size_ret,
+            capacity, fn_info()));
+    }
+    result.truncate(size_ret);
+    Ok(result)
+}
+
+/// Queries the size, then the bytes, of an info query result (see
+/// `get_info_size` and `get_info_value`).
+pub(crate) fn get_info_bytes<Q, I>(cl_fn_name: &'static str, fn_info: I, query: Q) -> OclResult<InfoBytes>
+        where Q: Fn(size_t, *mut c_void, *mut size_t) -> cl_int, I: Fn() -> String
+{
+    get_info_bytes_checked(cl_fn_name, fn_info, query, |_| Ok(()))
+}
+
+/// Queries the size, then the bytes, of an info query result, passing the
+/// size to `check` first.
+///
+/// If the value query fails with `CL_INVALID_VALUE` because the result grew
+/// after its size was queried, the query is repeated (up to
+/// `INFO_QUERY_ATTEMPTS` times in all).
+fn get_info_bytes_checked<Q, I, C>(cl_fn_name: &'static str, fn_info: I, query: Q,
+        check: C) -> OclResult<InfoBytes>
+        where Q: Fn(size_t, *mut c_void, *mut size_t) -> cl_int, I: Fn() -> String,
+            C: Fn(size_t) -> OclResult<()>
+{
+    let mut result_size = try!(get_info_size(cl_fn_name, &fn_info, &query));
+    let mut attempts = 1;
+
+    loop {
+        try!(check(result_size));
+
+        let err = match get_info_value(cl_fn_name, &fn_info, &query, result_size) {
+            Ok(result) => return Ok(result),
+            Err(err) => err,
+        };
+
+        if attempts == INFO_QUERY_ATTEMPTS || !err.is_status(cl_h::Status::CL_INVALID_VALUE) {
+            return Err(err);
+        }
+
+        match get_info_size(cl_fn_name, &fn_info, &query) {
+            Ok(new_size) if new_size > result_size => result_size = new_size,
+            _ => return Err(err),
+        }
+        attempts += 1;
+    }
+}
+
+/// Returns the size of `mem` in bytes, formatted for use in error info.
+fn fmt_mem_len(mem: &Mem) -> String {
+    let mut size: size_t = 0;
+
+    let errcode = unsafe { cl_h::clGetMemObjectInfo(
+        mem.as_ptr(),
+        cl_h::CL_MEM_SIZE,
+        mem::size_of::<size_t>(),
+        &mut size as *mut _ as *mut c_void,
+        ptr::null_mut(),
+    ) };
+
+    if errcode == cl_h::Status::CL_SUCCESS as cl_int {
+        size.to_string()
+    } else {
+        String::from("<unknown>")
+    }
 }
 
 /// Maps options of slices to pointers and a length.
@@ -126,88 +331,47 @@ fn resolve_event_ptrs(wait_list: Option<&ClWaitList>,
     Ok((wait_list_len, wait_list_ptr, new_event_ptr))
 }
 
-/// Converts an array option reference into a pointer to the contained array.
-fn resolve_work_dims(work_dims: &Option<[usize; 3]>) -> *const size_t {
-    match work_dims {
-        &Some(ref w) => w as *const [usize; 3] as *const size_t,
-        &None => 0 as *const size_t,
-    }
-}
-
 /// If the program pointed to by `cl_program` for any of the devices listed in 
-/// `device_ids` has a build log of any length, it will be returned as an 
-/// errcode result.
+/// `device_ids` has a build log of any length, it will be returned, along
+/// with the name, build status, and build log of every other device, as an
+/// `Error::ProgramBuild`.
 ///
 pub fn program_build_err<D: ClDeviceIdPtr + Debug>(program: &Program, device_ids: &[D]) -> OclResult<()> {
-    // let mut size = 0 as size_t;
-
     if device_ids.len() == 0 {
         return OclError::err("ocl::core::program_build_err(): Device list is empty. Aborting.");
     }
 
-    // [DEBUG]:
-    // println!("ocl::core::program_build_err(): device_ids: {:?}", device_ids);
+    let mut logs = Vec::with_capacity(device_ids.len());
+    let mut log_found = false;
 
     for device_id in device_ids.iter() {
-        // unsafe {
-        //     let name = cl_h::CL_PROGRAM_BUILD_LOG as cl_program_build_info;
-
-        //     let mut errcode = cl_h::clGetProgramBuildInfo(
-        //         program.as_ptr(),
-        //         device_id.as_ptr(),
-        //         name,
-        //         0,
-        //         ptr::null_mut(),
-        //         &mut size,
-        //     );
-        //     try!(errcode_try("clGetProgramBuildInfo", "program_build_err()", errcode));
-
-        //     let mut pbi: Vec<u8> = iter::repeat(32u8).take(size as usize).collect();
-
-        //     errcode = cl_h::clGetProgramBuildInfo(
-        //         program.as_ptr(),
-        //         device_id.as_ptr(),
-        //         name,
-        //         size,
-        //         pbi.as_mut_ptr() as *mut c_void,
-        //         ptr::null_mut(),
-        //     );
-        //     try!(errcode_try("clGetProgramBuildInfo", "program_build_err()", errcode));
-
-        //     if size > 1 {
-        //         let pbi_nonull = try!(String::from_utf8(pbi));
-        //         let pbi_errcode_string = format!(
-        //             "\n\n\
-        //             ###################### OPENCL PROGRAM BUILD DEBUG OUTPUT ######################\
-        //             \n\n{}\n\
-        //             ###############################################################################\
-        //             \n\n",
-        //             pbi_nonull);
-
-        //         return OclError::err(pbi_errcode_string);
-        //     }
-        // }
+        let device_name: String = get_device_info(device_id, DeviceInfo::Name).into();
+
+        let status = match get_program_build_info(program, device_id,
+            ProgramBuildInfo::BuildStatus)
+        {
+            ProgramBuildInfoResult::BuildStatus(status) => status,
+            ProgramBuildInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::core::program_build_err(): Unexpected \
+                'ProgramBuildInfoResult' variant."),
+        };
 
         match get_program_build_info(program, device_id, ProgramBuildInfo::BuildLog) {
             ProgramBuildInfoResult::BuildLog(log) => {
-                if log.len() > 1 {
-                    let log_readable = format!(
-                        "\n\n\
-                        ###################### OPENCL PROGRAM BUILD DEBUG OUTPUT ######################\
-                        \n\n{}\n\
-                        ###############################################################################\
-                        \n\n",
-                        log);
-
-                    return OclError::err(log_readable);
-                }
+                if log.len() > 1 { log_found = true; }
+                logs.push((device_name, status, log));
             },
             ProgramBuildInfoResult::Error(err) => return Err(*err),
-            _ => panic!("ocl::core::program_build_err(): Unexpected 'ProgramBuildInfoResult' variant."),
+            _ => return OclError::err("ocl::core::program_build_err(): Unexpected \
+                'ProgramBuildInfoResult' variant."),
         }
     }
 
-    Ok(())
+    if log_found {
+        Err(OclError::ProgramBuild(ProgramBuildError::new(logs)))
+    } else {
+        Ok(())
+    }
 }
 
 //============================================================================
@@ -220,6 +384,42 @@ pub fn program_build_err<D: ClDeviceIdPtr + Debug>(program: &Program, device_ids
 //============================= Platform API =================================
 //============================================================================
 
+/// Returns true if the OpenCL library is available.
+///
+/// With the `dynamic-loading` feature, the library is loaded at runtime (by
+/// the first call to this or any other function which calls into OpenCL)
+/// and this returns false if it, or its `clGetPlatformIDs` function, can not
+/// be found. Every function which calls into OpenCL then returns
+/// `Error::LibraryNotLoaded`. Set the `OCL_LIBRARY` environment variable to
+/// the name or path of the library to load one other than the default.
+///
+/// Without the feature, the library is linked and this always returns true.
+///
+/// Whether any platforms are installed is a separate question (see
+/// `get_platform_ids`).
+#[cfg(feature = "dynamic-loading")]
+pub fn is_opencl_available() -> bool {
+    ::loader::is_available()
+}
+
+/// Returns true if the OpenCL library is available.
+///
+/// The library is linked (the `dynamic-loading` feature is not enabled), so
+/// this always returns true.
+#[cfg(not(any(feature = "dynamic-loading", feature = "stub")))]
+pub fn is_opencl_available() -> bool {
+    true
+}
+
+/// Returns true if the OpenCL library is available.
+///
+/// The `stub` feature is enabled, replacing the library with a stand-in
+/// which never executes anything, so this always returns false.
+#[cfg(feature = "stub")]
+pub fn is_opencl_available() -> bool {
+    false
+}
+
 /// Returns a list of available platforms as 'core' objects.
 pub fn get_platform_ids() -> OclResult<Vec<PlatformId>> {
     let mut num_platforms = 0 as cl_uint;
@@ -238,9 +438,8 @@ pub fn get_platform_ids() -> OclResult<Vec<PlatformId>> {
 
         while errcode == cl_h::Status::CL_PLATFORM_NOT_FOUND_KHR as i32 {
             if iters_rmng == 0 {
-                return OclError::err(format!("core::get_platform_ids(): \
-                    CL_PLATFORM_NOT_FOUND_KHR... Unable to get platform id list after {} \
-                    seconds of waiting.", (iters_rmng * sleep_ms) / 1000));
+                return OclError::err_status(errcode, "clGetPlatformIDs", format!("Unable to \
+                    get platform id list after {} seconds of waiting.", (5 * sleep_ms) / 1000));
             }
 
             // Sleep to allow the ICD to refresh or whatever it does:
@@ -255,7 +454,7 @@ pub fn get_platform_ids() -> OclResult<Vec<PlatformId>> {
         }
     }
 
-    try!(errcode_try("clGetPlatformIDs", "", errcode));
+    try!(errcode_try("clGetPlatformIDs", || String::new(), errcode));
 
     // If no platforms are found, return an empty vec directly:
     if num_platforms == 0 {
@@ -279,7 +478,7 @@ pub fn get_platform_ids() -> OclResult<Vec<PlatformId>> {
             ptr::null_mut()
         )
     };
-    try!(errcode_try("clGetPlatformIDs", "", errcode));
+    try!(errcode_try("clGetPlatformIDs", || String::new(), errcode));
     
     Ok(platforms)
 }
@@ -287,47 +486,30 @@ pub fn get_platform_ids() -> OclResult<Vec<PlatformId>> {
 /// Returns platform information of the requested type.
 pub fn get_platform_info<P: ClPlatformIdPtr>(platform: Option<P>, request: PlatformInfo,
         ) -> PlatformInfoResult
+{
+    PlatformInfoResult::from_info_bytes(request, platform_info_bytes(platform, request))
+}
+
+/// Returns platform information of the requested type as raw bytes.
+pub fn get_platform_info_bytes<P: ClPlatformIdPtr>(platform: Option<P>, request: PlatformInfo,
+        ) -> OclResult<Vec<u8>>
+{
+    platform_info_bytes(platform, request).map(InfoBytes::into_vec)
+}
+
+fn platform_info_bytes<P: ClPlatformIdPtr>(platform: Option<P>, request: PlatformInfo,
+        ) -> OclResult<InfoBytes>
 {
     let platform_ptr: cl_platform_id = match platform {
         Some(p) => unsafe { p.as_ptr() },
         None => ptr::null_mut() as cl_platform_id,
     };
 
-    let mut result_size = 0 as size_t;
-
-    let errcode = unsafe {
-        cl_h::clGetPlatformInfo(
-            platform_ptr,
-            request as cl_platform_info,
-            0 as size_t,
-            ptr::null_mut(),
-            &mut result_size as *mut size_t,
-        )
-    };
-
-    if let Err(err) = errcode_try("clGetPlatformInfo", "", errcode) {
-        return PlatformInfoResult::Error(Box::new(err));
-    }
-
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return PlatformInfoResult::from_bytes(request, Ok(vec![]));
-    }
-        
-    let mut result: Vec<u8> = iter::repeat(32u8).take(result_size as usize).collect();
-
-    let errcode = unsafe {
-        cl_h::clGetPlatformInfo(
-            platform_ptr,
-            request as cl_platform_info,
-            result_size as size_t,
-            result.as_mut_ptr() as *mut c_void,
-            ptr::null_mut() as *mut size_t,
-        )
-    };
-
-    let result = errcode_try("clGetPlatformInfo", "", errcode).and(Ok(result));
-    PlatformInfoResult::from_bytes(request, result)
+    get_info_bytes("clGetPlatformInfo", || format!("request={:?}", request),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetPlatformInfo(platform_ptr, request as cl_platform_info, size, value,
+                size_ret)
+        })
 }
 
 //============================================================================
@@ -370,10 +552,12 @@ pub fn get_device_ids/*<P: ClPlatformIdPtr>*/(
         device_ids.as_mut_ptr() as *mut cl_device_id,
         &mut devices_available,
     ) };
-    try!(errcode_try("clGetDeviceIDs", "", errcode));
+    try!(errcode_try("clGetDeviceIDs", || format!("platform={:?} device_types={:?} \
+        devices_max={}", platform, device_types, devices_max), errcode));
 
-    // Trim vec len:
-    unsafe { device_ids.set_len(devices_available as usize); }
+    // Trim vec len (never beyond what was allocated, whatever the count
+    // reported):
+    device_ids.truncate(cmp::min(devices_available, devices_max) as usize);
     device_ids.shrink_to_fit();
 
     Ok(device_ids)
@@ -381,45 +565,47 @@ pub fn get_device_ids/*<P: ClPlatformIdPtr>*/(
 
 /// Returns information about a device.
 ///
-#[allow(unused_variables)]
 pub fn get_device_info<D: ClDeviceIdPtr>(device: &D, request: DeviceInfo,
         ) -> DeviceInfoResult
 {
-    let mut result_size: size_t = 0;
-
-    let errcode = unsafe { cl_h::clGetDeviceInfo(
-        device.as_ptr() as cl_device_id,
-        request as cl_device_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
-
-    // try!(errcode_try("clGetDeviceInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetDeviceInfo", "", errcode) {
-        return DeviceInfoResult::Error(Box::new(err));
-    }
+    DeviceInfoResult::from_info_bytes(request, device_info_bytes(device, request))
+}
 
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return DeviceInfoResult::from_bytes(request, Ok(vec![]));
-    }
+/// Returns information about a device as raw bytes.
+pub fn get_device_info_bytes<D: ClDeviceIdPtr>(device: &D, request: DeviceInfo,
+        ) -> OclResult<Vec<u8>>
+{
+    device_info_bytes(device, request).map(InfoBytes::into_vec)
+}
 
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
+#[cfg(test)]
+thread_local! {
+    static DEVICE_INFO_QUERIES: RefCell<HashMap<(usize, cl_device_info), usize>> =
+        RefCell::new(HashMap::new());
+}
 
-    let errcode = unsafe { cl_h::clGetDeviceInfo(
-        device.as_ptr() as cl_device_id,
-        request as cl_device_info,
-        result_size  as size_t,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) };
+/// Returns the number of `clGetDeviceInfo` calls made for `request` on
+/// `device` by the current thread, allowing tests to verify caching.
+#[cfg(test)]
+pub(crate) fn device_info_query_count<D: ClDeviceIdPtr>(device: &D, request: DeviceInfo)
+        -> usize
+{
+    let key = (unsafe { device.as_ptr() } as usize, request as cl_device_info);
+    DEVICE_INFO_QUERIES.with(|q| q.borrow().get(&key).cloned().unwrap_or(0))
+}
 
-    // try!(errcode_try("clGetDeviceInfo", "", errcode));
-    // DeviceInfoResult::from_bytes(request, result)
+fn device_info_bytes<D: ClDeviceIdPtr>(device: &D, request: DeviceInfo) -> OclResult<InfoBytes> {
+    #[cfg(test)]
+    DEVICE_INFO_QUERIES.with(|q| {
+        let key = (unsafe { device.as_ptr() } as usize, request as cl_device_info);
+        *q.borrow_mut().entry(key).or_insert(0) += 1;
+    });
 
-    let result = errcode_try("clGetDeviceInfo", "", errcode).and(Ok(result));
-    DeviceInfoResult::from_bytes(request, result)
+    get_info_bytes("clGetDeviceInfo", || format!("request={:?}", request),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetDeviceInfo(device.as_ptr() as cl_device_id, request as cl_device_info,
+                size, value, size_ret)
+        })
 }
 
 /// [UNIMPLEMENTED]
@@ -433,13 +619,56 @@ pub fn create_sub_devices() -> OclResult<()> {
 }
 
 /// Increments the reference count of a device.
+///
+/// [Version Controlled: OpenCL 1.2+] Only affects sub-devices. Root-level
+/// devices are unaffected (the call succeeds).
 pub unsafe fn retain_device(device: &DeviceId) -> OclResult<()> {
-    errcode_try("clRetainDevice", "", cl_h::clRetainDevice(device.as_ptr()))
+    errcode_try("clRetainDevice", || format!("device={:?}", device),
+        cl_h::clRetainDevice(device.as_ptr()))
 }
 
 /// Decrements the reference count of a device.
+///
+/// [Version Controlled: OpenCL 1.2+] Only affects sub-devices. Root-level
+/// devices are unaffected (the call succeeds).
 pub unsafe fn release_device(device: &DeviceId) -> OclResult<()> {
-    errcode_try("clReleaseDevice", "", cl_h::clReleaseDevice(device.as_ptr())) 
+    errcode_try("clReleaseDevice", || format!("device={:?}", device),
+        cl_h::clReleaseDevice(device.as_ptr())) 
+}
+
+/// Returns a `(device_timestamp, host_timestamp)` pair sampled at (nearly)
+/// the same moment, in nanoseconds.
+///
+/// The device timestamp is on the clock used for event profiling and the
+/// host timestamp on the host clock (see `::get_host_timer`).
+///
+/// [Version Controlled: OpenCL 2.1+] The device and its platform must both
+/// support OpenCL 2.1 or later.
+pub fn get_device_and_host_timer<D: ClDeviceIdPtr>(device: &D) -> OclResult<(u64, u64)> {
+    let mut device_timestamp: cl_ulong = 0;
+    let mut host_timestamp: cl_ulong = 0;
+
+    try!(errcode_try("clGetDeviceAndHostTimer", || String::new(), unsafe {
+        cl_h::clGetDeviceAndHostTimer(device.as_ptr() as cl_device_id, &mut device_timestamp,
+            &mut host_timestamp)
+    }));
+
+    Ok((device_timestamp, host_timestamp))
+}
+
+/// Returns the current value of the host clock as seen by `device`, in
+/// nanoseconds.
+///
+/// [Version Controlled: OpenCL 2.1+] The device and its platform must both
+/// support OpenCL 2.1 or later.
+pub fn get_host_timer<D: ClDeviceIdPtr>(device: &D) -> OclResult<u64> {
+    let mut host_timestamp: cl_ulong = 0;
+
+    try!(errcode_try("clGetHostTimer", || String::new(), unsafe {
+        cl_h::clGetHostTimer(device.as_ptr() as cl_device_id, &mut host_timestamp)
+    }));
+
+    Ok(host_timestamp)
 }
 
 //============================================================================
@@ -457,6 +686,45 @@ pub unsafe fn release_device(device: &DeviceId) -> OclResult<()> {
 pub fn create_context<D: ClDeviceIdPtr>(properties: &Option<ContextProperties>, device_ids: &[D],
             pfn_notify: Option<CreateContextCallbackFn>, user_data: Option<UserDataPtr>
         ) -> OclResult<Context> 
+{
+    // [FIXME]: Disabled:
+    let user_data_ptr = match user_data {
+        // Some(ud_ptr) => ud_ptr,
+        Some(_) => ptr::null_mut(),
+        None => ptr::null_mut(), 
+    };
+
+    create_context_raw(properties, device_ids, pfn_notify, user_data_ptr)
+}
+
+/// Returns a new context pointer valid for all devices in `device_ids`,
+/// calling `notify` with a description of each error reported by the
+/// implementation for the lifetime of the context.
+///
+/// `notify` may be called from any thread, including concurrently. Panics
+/// within it are caught and reported rather than propagated. It is dropped
+/// if creation fails but otherwise lives as long as the process (OpenCL
+/// provides no way to learn when the context is destroyed).
+pub fn create_context_notify<D, F>(properties: &Option<ContextProperties>, device_ids: &[D],
+            notify: F) -> OclResult<Context> 
+        where D: ClDeviceIdPtr, F: Fn(&str) + Send + Sync + 'static
+{
+    let mut context = None;
+
+    try!(callbacks::register_persistent(notify, |user_data| {
+        context = Some(try!(create_context_raw(properties, device_ids,
+            Some(callbacks::context_notify_trampoline::<F>), user_data)));
+        Ok(())
+    }));
+
+    context.ok_or_else(|| OclError::new("ocl::core::create_context_notify(): Context \
+        creation failed."))
+}
+
+/// Creates a context, passing `pfn_notify` and `user_data` directly.
+fn create_context_raw<D: ClDeviceIdPtr>(properties: &Option<ContextProperties>, device_ids: &[D],
+            pfn_notify: Option<CreateContextCallbackFn>, user_data_ptr: UserDataPtr
+        ) -> OclResult<Context> 
 {
     if device_ids.len() == 0 {
         return OclError::err("ocl::core::create_context(): No devices specified.");
@@ -493,16 +761,8 @@ pub fn create_context<D: ClDeviceIdPtr>(properties: &Option<ContextProperties>,
     //     // None => ptr::null::<CreateContextCallbackFn>(),
     // } };
 
-    // [FIXME]: Disabled:
-    let user_data_ptr = match user_data {
-        // Some(ud_ptr) => ud_ptr,
-        Some(_) => ptr::null_mut(),
-        None => ptr::null_mut(), 
-    };
-    
     let mut errcode: cl_int = 0;
 
-    // [FIXME]: Callback function and data unimplemented.
     let context = unsafe { Context::from_fresh_ptr(cl_h::clCreateContext(
         properties_ptr, 
         device_ids.len() as cl_uint, 
@@ -513,7 +773,8 @@ pub fn create_context<D: ClDeviceIdPtr>(properties: &Option<ContextProperties>,
     )) };
     // [DEBUG]: 
     // println!("CREATE_CONTEXT: CONTEXT PTR: {:?}", context);
-    errcode_try("clCreateContext", "", errcode).and(Ok(context))
+    errcode_try("clCreateContext", || format!("properties={:?} device_count={}", properties,
+        device_ids.len()), errcode).and(Ok(context))
 }
 
 /// [UNIMPLEMENTED]
@@ -528,12 +789,14 @@ pub fn create_context_from_type() -> OclResult<()> {
 
 /// Increments the reference count of a context.
 pub unsafe fn retain_context(context: &Context) -> OclResult<()> {
-    errcode_try("clRetainContext", "", cl_h::clRetainContext(context.as_ptr()))
+    errcode_try("clRetainContext", || format!("context={:?}", context),
+        cl_h::clRetainContext(context.as_ptr()))
 }
 
 /// Decrements reference count of a context.
 pub unsafe fn release_context(context: &Context) -> OclResult<()> {
-    errcode_try("clReleaseContext", "", cl_h::clReleaseContext(context.as_ptr()))
+    errcode_try("clReleaseContext", || format!("context={:?}", context),
+        cl_h::clReleaseContext(context.as_ptr()))
 }
 
 /// Returns various kinds of context information.
@@ -548,52 +811,32 @@ pub unsafe fn release_context(context: &Context) -> OclResult<()> {
 pub fn get_context_info(context: &Context, request: ContextInfo) 
         -> ContextInfoResult
 {
-   let mut result_size: size_t = 0;
-
-    let errcode = unsafe { cl_h::clGetContextInfo(   
-        context.as_ptr() as cl_context,
-        request as cl_context_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut usize,
-    ) };
-
-    if let Err(err) = errcode_try("clGetContextInfo", "", errcode) {
-        return ContextInfoResult::Error(Box::new(err));
-    }
+    let fn_info = || format!("request={:?}", request);
+    let query = |size, value, size_ret| unsafe {
+        cl_h::clGetContextInfo(context.as_ptr() as cl_context, request as cl_context_info, size,
+            value, size_ret)
+    };
 
     // Check for invalid context pointer (a potentially hard to track down bug)
     // using ridiculous and probably platform-specific logic [if the `Devices` 
     // variant is passed and we're not in the release config]:
-    if !cfg!(release) {
-        let err_if_zero_result_size = request as cl_context_info == cl_h::CL_CONTEXT_DEVICES;
-
-        if result_size > 10000 || (result_size == 0 && err_if_zero_result_size) {
-            return ContextInfoResult::Error(Box::new(OclError::new("\n\nocl::core::context_info(): \
-                Possible invalid context detected. \n\
-                Context info result size is either '> 10k bytes' or '== 0'. Almost certainly an \n\
-                invalid context object. If not, please file an issue at: \n\
-                https://github.com/cogciprocate/ocl/issues.\n\n")));
+    let check = |result_size: size_t| {
+        if !cfg!(release) {
+            let err_if_zero_result_size = request as cl_context_info == cl_h::CL_CONTEXT_DEVICES;
+
+            if result_size > 10000 || (result_size == 0 && err_if_zero_result_size) {
+                return OclError::err("\n\nocl::core::context_info(): \
+                    Possible invalid context detected. \n\
+                    Context info result size is either '> 10k bytes' or '== 0'. Almost certainly an \n\
+                    invalid context object. If not, please file an issue at: \n\
+                    https://github.com/cogciprocate/ocl/issues.\n\n");
+            }
         }
-    }
-
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return ContextInfoResult::from_bytes(request, Ok(vec![]));
-    }
-
-    let mut result: Vec<u8> = iter::repeat(0).take(result_size).collect();
-
-    let errcode = unsafe { cl_h::clGetContextInfo(   
-        context.as_ptr() as cl_context,
-        request as cl_context_info,
-        result_size as size_t,
-        result.as_mut_ptr() as *mut c_void,
-        0 as *mut usize,
-    ) };
+        Ok(())
+    };
 
-    let result = errcode_try("clGetContextInfo", "", errcode).and(Ok(result));
-    ContextInfoResult::from_bytes(request, result)
+    ContextInfoResult::from_info_bytes(request, get_info_bytes_checked("clGetContextInfo",
+        fn_info, query, check))
 }
 
 //============================================================================
@@ -617,19 +860,19 @@ pub fn create_command_queue<D: ClDeviceIdPtr>(
         cl_h::CL_QUEUE_PROFILING_ENABLE, 
         &mut errcode
     )) };
-    errcode_try("clCreateCommandQueue", "", errcode).and(Ok(cq))
+    errcode_try("clCreateCommandQueue", || format!("context={:?} device={:?}", context,
+        unsafe { device.as_ptr() }), errcode).and(Ok(cq))
 }
 
 /// Increments the reference count of a command queue.
 pub unsafe fn retain_command_queue(queue: &CommandQueue) -> OclResult<()> {
-    errcode_try("clRetainCommandQueue", "", cl_h::clRetainCommandQueue(queue.as_ptr()))
+    errcode_try("clRetainCommandQueue", || format!("queue={:?}", queue),
+        cl_h::clRetainCommandQueue(queue.as_ptr()))
 }
 
 /// Decrements the reference count of a command queue.
-///
-/// [FIXME]: Return result
 pub unsafe fn release_command_queue(queue: &CommandQueue) -> OclResult<()> {
-    errcode_try("clReleaseCommandQueue", "", 
+    errcode_try("clReleaseCommandQueue", || format!("queue={:?}", queue), 
         cl_h::clReleaseCommandQueue(queue.as_ptr()))
 }
 
@@ -637,40 +880,13 @@ pub unsafe fn release_command_queue(queue: &CommandQueue) -> OclResult<()> {
 pub fn get_command_queue_info(queue: &CommandQueue, request: CommandQueueInfo,
         ) -> CommandQueueInfoResult
 {
-    let mut result_size: size_t = 0;
-
-    let errcode = unsafe { cl_h::clGetCommandQueueInfo(
-        queue.as_ptr() as cl_command_queue,
-        request as cl_command_queue_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
-
-    // try!(errcode_try("clGetCommandQueueInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetCommandQueueInfo", "", errcode) {
-        return CommandQueueInfoResult::Error(Box::new(err));
-    }
+    let result = get_info_bytes("clGetCommandQueueInfo", || format!("request={:?}", request),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetCommandQueueInfo(queue.as_ptr() as cl_command_queue,
+                request as cl_command_queue_info, size, value, size_ret)
+        });
 
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return CommandQueueInfoResult::from_bytes(request, Ok(vec![]));
-    }
-
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
-
-    let errcode = unsafe { cl_h::clGetCommandQueueInfo(
-        queue.as_ptr() as cl_command_queue,
-        request as cl_command_queue_info,
-        result_size,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) }; 
-
-    // errcode_try("clGetCommandQueueInfo", "", errcode)
-    //     .and(Ok(CommandQueueInfoResult::TemporaryPlaceholderVariant(result)))
-    let result = errcode_try("clGetCommandQueueInfo", "", errcode).and(Ok(result));
-    CommandQueueInfoResult::from_bytes(request, result)
+    CommandQueueInfoResult::from_info_bytes(request, result)
 }
 
 //============================================================================
@@ -707,7 +923,8 @@ pub unsafe fn create_buffer<T: OclPrm>(
         host_ptr, 
         &mut errcode,
     );
-    try!(errcode_try("clCreateBuffer", "", errcode));
+    try!(errcode_try("clCreateBuffer", || format!("flags={:?} len={} size={} data={}", flags,
+        len, len * mem::size_of::<T>(), data.is_some()), errcode));
     debug_assert!(!buf_ptr.is_null());
 
     Ok(Mem::from_fresh_ptr(buf_ptr))
@@ -729,6 +946,7 @@ pub fn create_sub_buffer(
         ) -> OclResult<Mem> 
 {
     let buffer_create_type = BufferCreateType::Region;
+    let region = buffer_create_info.to_raw();
 
     let mut errcode = 0i32;
 
@@ -736,10 +954,11 @@ pub fn create_sub_buffer(
         buffer.as_ptr(),
         flags.bits(),
         buffer_create_type as cl_buffer_create_type,
-        buffer_create_info as *const _ as *const c_void,
+        &region as *const cl_h::cl_buffer_region as *const c_void,
         &mut errcode,
     ) };
-    try!(errcode_try("clCreateSubBuffer", "", errcode));
+    try!(errcode_try("clCreateSubBuffer", || format!("flags={:?} origin={} size={} buffer_len={}",
+        flags, buffer_create_info.origin, buffer_create_info.size, fmt_mem_len(buffer)), errcode));
     debug_assert!(!sub_buf_ptr.is_null()); 
 
     unsafe { Ok(Mem::from_fresh_ptr(sub_buf_ptr)) }    
@@ -778,7 +997,8 @@ pub unsafe fn create_image<T>(
         host_ptr,
         &mut errcode as *mut cl_int,
     ); 
-    try!(errcode_try("clCreateImage", "", errcode));
+    try!(errcode_try("clCreateImage", || format!("flags={:?} format={:?} desc={:?} data={}",
+        flags, format, desc, data.is_some()), errcode));
     debug_assert!(!image_ptr.is_null());
 
     Ok(Mem::from_fresh_ptr(image_ptr))
@@ -786,12 +1006,14 @@ pub unsafe fn create_image<T>(
 
 /// Increments the reference counter of a mem object.
 pub unsafe fn retain_mem_object(mem: &Mem) -> OclResult<()> {
-    errcode_try("clRetainMemObject", "", cl_h::clRetainMemObject(mem.as_ptr()))
+    errcode_try("clRetainMemObject", || format!("mem={:?}", mem),
+        cl_h::clRetainMemObject(mem.as_ptr()))
 }
 
 /// Decrements the reference counter of a mem object.
 pub unsafe fn release_mem_object(mem: &Mem) -> OclResult<()> {
-    errcode_try("clReleaseMemObject", "", cl_h::clReleaseMemObject(mem.as_ptr()))
+    errcode_try("clReleaseMemObject", || format!("mem={:?}", mem),
+        cl_h::clReleaseMemObject(mem.as_ptr()))
 }
 
 /// Returns a list of supported image formats.
@@ -809,6 +1031,18 @@ pub fn get_supported_image_formats(
             flags: MemFlags,
             image_type: MemObjectType,
         ) -> OclResult<Vec<ImageFormat>> 
+{
+    ImageFormat::list_from_raw(try!(supported_image_formats_raw(context, flags, image_type)))
+}
+
+/// Returns the image formats supported by `context` without conversion,
+/// including any (vendor specific formats, for example) unknown to
+/// `ImageFormat`.
+pub(crate) fn supported_image_formats_raw(
+            context: &Context,
+            flags: MemFlags,
+            image_type: MemObjectType,
+        ) -> OclResult<Vec<cl_image_format>>
 {
     let mut num_image_formats = 0 as cl_uint;
 
@@ -820,7 +1054,8 @@ pub fn get_supported_image_formats(
         ptr::null_mut() as *mut cl_image_format,
         &mut num_image_formats as *mut cl_uint,
     ) };
-    try!(errcode_try("clGetSupportedImageFormats", "", errcode));
+    try!(errcode_try("clGetSupportedImageFormats", || format!("flags={:?} image_type={:?}",
+        flags, image_type), errcode));
 
     // If no formats found, return an empty list directly:
     if num_image_formats == 0 {
@@ -841,96 +1076,52 @@ pub fn get_supported_image_formats(
         image_formats.as_mut_ptr() as *mut _ as *mut cl_image_format,
         0 as *mut cl_uint,
     ) };
-    try!(errcode_try("clGetSupportedImageFormats", "", errcode));
+    try!(errcode_try("clGetSupportedImageFormats", || format!("flags={:?} image_type={:?}",
+        flags, image_type), errcode));
 
-    ImageFormat::list_from_raw(image_formats)
+    Ok(image_formats)
 }
 
 
 /// Get mem object info.
 pub fn get_mem_object_info(obj: &Mem, request: MemInfo) -> MemInfoResult {
-    let mut result_size: size_t = 0;
-
-    let errcode = unsafe { cl_h::clGetMemObjectInfo(
-        obj.as_ptr() as cl_mem,
-        request as cl_mem_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
+    let result = get_info_bytes("clGetMemObjectInfo", || format!("request={:?}", request),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetMemObjectInfo(obj.as_ptr() as cl_mem, request as cl_mem_info, size, value,
+                size_ret)
+        });
 
-    // try!(errcode_try("clGetMemObjectInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetMemObjectInfo", "", errcode) {
-        return MemInfoResult::Error(Box::new(err));
-    }
-
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return MemInfoResult::from_bytes(request, Ok(vec![]));
-    }
-
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
-
-    let errcode = unsafe { cl_h::clGetMemObjectInfo(
-        obj.as_ptr() as cl_mem,
-        request as cl_mem_info,
-        result_size,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) };    
-    // errcode_try("clGetMemObjectInfo", "", errcode)
-    //     .and(Ok(MemInfoResult::TemporaryPlaceholderVariant(result)))
-
-    let result = errcode_try("clGetMemObjectInfo", "", errcode).and(Ok(result));
-    MemInfoResult::from_bytes(request, result)
+    MemInfoResult::from_info_bytes(request, result)
 }
 
 
 /// Get image info.
 pub fn get_image_info(obj: &Mem, request: ImageInfo) -> ImageInfoResult {
-    let mut result_size: size_t = 0;
-
-    let errcode = unsafe { cl_h::clGetImageInfo(
-        obj.as_ptr() as cl_mem,
-        request as cl_image_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
-
-    // try!(errcode_try("clGetImageInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetImageInfo", "", errcode) {
-        return ImageInfoResult::Error(Box::new(err));
-    }
-
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return ImageInfoResult::from_bytes(request, Ok(vec![]));
-    }
-
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
-
-    let errcode = unsafe { cl_h::clGetImageInfo(
-        obj.as_ptr() as cl_mem,
-        request as cl_image_info,
-        result_size,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) };    
+    let result = get_info_bytes("clGetImageInfo", || format!("request={:?}", request),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetImageInfo(obj.as_ptr() as cl_mem, request as cl_image_info, size, value,
+                size_ret)
+        });
 
-    // println!("GET_COMMAND_QUEUE_INFO(): errcode: {}, result: {:?}", errcode, result);
-    // try!(errcode_try("clGetImageInfo", "", errcode));
-    // ImageInfoResult::from_bytes(request, result)
-    let result = errcode_try("clGetImageInfo", "", errcode).and(Ok(result));
-    ImageInfoResult::from_bytes(request, result)
+    ImageInfoResult::from_info_bytes(request, result)
 }
 
-/// [UNIMPLEMENTED]
-pub fn set_mem_object_destructor_callback() -> OclResult<()> {
-    // cl_h::clSetMemObjectDestructorCallback(memobj: cl_mem,
-    //                                     pfn_notify: extern fn (cl_mem, *mut c_void),
-    //                                     user_data: *mut c_void) -> cl_int;
-    unimplemented!();
+/// Registers `callback` to be called once `mem` has been destroyed (after
+/// its reference count reaches zero and any commands using it complete).
+///
+/// Callbacks registered on the same memory object are called in the reverse
+/// order of registration. `callback` may be called from any thread and
+/// panics within it are caught and reported rather than propagated.
+///
+/// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clSetMemObjectDestructorCallback.html)
+pub fn set_mem_object_destructor_callback<F>(mem: &Mem, callback: F) -> OclResult<()>
+        where F: FnOnce() + Send + 'static
+{
+    callbacks::register_once(callback, |user_data| unsafe {
+        errcode_try("clSetMemObjectDestructorCallback", || format!("mem={:?}", mem),
+            cl_h::clSetMemObjectDestructorCallback(mem.as_ptr(),
+                Some(callbacks::mem_destructor_trampoline::<F>), user_data))
+    })
 }
 
 //============================================================================
@@ -953,17 +1144,21 @@ pub fn create_sampler(context: &Context, normalize_coords: bool, addressing_mode
         &mut errcode,
     )) };
 
-    errcode_try("clCreateSampler", "", errcode).and(Ok(sampler))
+    errcode_try("clCreateSampler", || format!("normalize_coords={} addressing_mode={:?} \
+        filter_mode={:?}", normalize_coords, addressing_mode, filter_mode),
+        errcode).and(Ok(sampler))
 }
 
 /// Increments a sampler reference counter.
 pub unsafe fn retain_sampler(sampler: &Sampler) -> OclResult<()> {
-    errcode_try("clRetainSampler", "", cl_h::clRetainSampler(sampler.as_ptr()))
+    errcode_try("clRetainSampler", || format!("sampler={:?}", sampler),
+        cl_h::clRetainSampler(sampler.as_ptr()))
 }
 
 /// Decrements a sampler reference counter.
 pub unsafe fn release_sampler(sampler: &Sampler) -> OclResult<()> {
-    errcode_try("clReleaseSampler", "", cl_h::clReleaseSampler(sampler.as_ptr()))
+    errcode_try("clReleaseSampler", || format!("sampler={:?}", sampler),
+        cl_h::clReleaseSampler(sampler.as_ptr()))
 }
 
 /// Returns information about the sampler object.
@@ -972,40 +1167,13 @@ pub unsafe fn release_sampler(sampler: &Sampler) -> OclResult<()> {
 pub fn get_sampler_info(obj: &Sampler, request: SamplerInfo,
     ) -> SamplerInfoResult
 {
-    let mut result_size: size_t = 0;
+    let result = get_info_bytes("clGetSamplerInfo", || format!("request={:?}", request),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetSamplerInfo(obj.as_ptr() as cl_sampler, request as cl_sampler_info, size,
+                value, size_ret)
+        });
 
-    let errcode = unsafe { cl_h::clGetSamplerInfo(
-        obj.as_ptr() as cl_sampler,
-        request as cl_sampler_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
-
-    // try!(errcode_try("clGetSamplerInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetSamplerInfo", "", errcode) {
-        return SamplerInfoResult::Error(Box::new(err));
-    }
-
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return SamplerInfoResult::from_bytes(request, Ok(vec![]));
-    }
-
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
-
-    let errcode = unsafe { cl_h::clGetSamplerInfo(
-        obj.as_ptr() as cl_sampler,
-        request as cl_sampler_info,
-        result_size,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) };    
-
-    // errcode_try("clGetSamplerInfo", "", errcode)
-    //     .and(Ok(SamplerInfoResult::TemporaryPlaceholderVariant(result)))
-    let result = errcode_try("clGetSamplerInfo", "", errcode).and(Ok(result));
-    SamplerInfoResult::from_bytes(request, result)
+    SamplerInfoResult::from_info_bytes(request, result)
 }
 
 //============================================================================
@@ -1036,7 +1204,8 @@ pub fn create_program_with_source(
         ks_lens.as_ptr() as *const usize,
         &mut errcode,
     ) };
-    try!(errcode_try("clCreateProgramWithSource", "", errcode));
+    try!(errcode_try("clCreateProgramWithSource", || format!("src_count={}", src_strings.len()),
+        errcode));
 
     unsafe { Ok(Program::from_fresh_ptr(program)) }
 }
@@ -1073,10 +1242,12 @@ pub fn create_program_with_binary<D: ClDeviceIdPtr>(
         binary_status.as_mut_ptr(),
         &mut errcode,
     ) };
-    try!(errcode_try("clCreateProgramWithBinary", "", errcode));
+    try!(errcode_try("clCreateProgramWithBinary", || format!("device_count={} lengths={:?}",
+        devices.len(), lengths), errcode));
 
     for i in 0..binary_status.len() {
-        try!(errcode_try("clCreateProgramWithBinary", &format!("(): Device [{}]", i), binary_status[i]));
+        try!(errcode_try("clCreateProgramWithBinary", || format!("device_idx={} length={}",
+            i, lengths[i]), binary_status[i]));
     }
 
     unsafe { Ok(Program::from_fresh_ptr(program)) }
@@ -1094,25 +1265,22 @@ pub fn create_program_with_built_in_kernels() -> OclResult<()> {
 
 /// Increments a program reference counter.
 pub unsafe fn retain_program(program: &Program) -> OclResult<()> {
-    errcode_try("clRetainProgram", "", cl_h::clRetainProgram(program.as_ptr()))
+    errcode_try("clRetainProgram", || format!("program={:?}", program),
+        cl_h::clRetainProgram(program.as_ptr()))
 }
 
 /// Decrements a program reference counter.
 pub unsafe fn release_program(program: &Program) -> OclResult<()> {
-    errcode_try("clReleaseKernel", "", cl_h::clReleaseProgram(program.as_ptr()))
+    errcode_try("clReleaseProgram", || format!("program={:?}", program),
+        cl_h::clReleaseProgram(program.as_ptr()))
 }
 
 pub struct UserDataPh(usize);
 
-impl UserDataPh {
-    fn unwrapped(&self) -> *mut c_void {
-        ptr::null_mut()
-    }
-}
-
 /// Builds a program.
 ///
-/// Callback functions are not yet supported.
+/// Raw callback functions are not supported. Use `::build_program_notify`
+/// to be notified when the build completes.
 pub fn build_program<D: ClDeviceIdPtr + Debug>(
             program: &Program,
             devices: &[D],
@@ -1121,16 +1289,49 @@ pub fn build_program<D: ClDeviceIdPtr + Debug>(
             user_data: Option<Box<UserDataPh>>,
         ) -> OclResult<()> 
 {
-    assert!(pfn_notify.is_none() && user_data.is_none(),
-        "ocl::core::build_program(): Callback functions not yet implemented.");
+    if pfn_notify.is_some() || user_data.is_some() {
+        return OclError::err("ocl::core::build_program(): Raw callback functions are not \
+            supported. Use 'core::build_program_notify' instead.");
+    }
 
+    build_program_raw(program, devices, options, None, ptr::null_mut())
+}
+
+/// Begins building a program, returning without waiting for the build to
+/// complete, and calls `notify` once it has (successfully or not).
+///
+/// Query `ProgramBuildInfo::BuildStatus` (and `BuildLog`) from within or
+/// after `notify` to determine the outcome. `notify` may be called from any
+/// thread, possibly before this function returns, and panics within it are
+/// caught and reported rather than propagated.
+pub fn build_program_notify<D, F>(
+            program: &Program,
+            devices: &[D],
+            options: &CString,
+            notify: F,
+        ) -> OclResult<()> 
+        where D: ClDeviceIdPtr + Debug, F: FnOnce() + Send + 'static
+{
+    callbacks::register_once(notify, |user_data| {
+        build_program_raw(program, devices, options,
+            Some(callbacks::build_program_trampoline::<F>), user_data)
+    })
+}
+
+/// Builds a program, passing `pfn_notify` and `user_data` directly.
+fn build_program_raw<D: ClDeviceIdPtr + Debug>(
+            program: &Program,
+            devices: &[D],
+            options: &CString,
+            pfn_notify: Option<extern "C" fn (cl_program, *mut c_void)>,
+            user_data: *mut c_void,
+        ) -> OclResult<()> 
+{
     if devices.len() == 0 { return OclError::err("ocl::core::build_program: \
         No devices specified."); }
 
-    let user_data = match user_data {
-        Some(ud) => ud.unwrapped(),
-        None => ptr::null_mut(),
-    };
+    #[cfg(feature = "logging")]
+    let start = ::std::time::Instant::now();
 
     let errcode = unsafe { cl_h::clBuildProgram(
         program.as_ptr() as cl_program,
@@ -1141,12 +1342,30 @@ pub fn build_program<D: ClDeviceIdPtr + Debug>(
         user_data,
     ) };    
 
-    if errcode == cl_h::Status::CL_BUILD_PROGRAM_FAILURE as i32 {
-        program_build_err(program, devices)
+    #[cfg(feature = "logging")]
+    let duration = start.elapsed();
+
+    let result = if errcode == cl_h::Status::CL_BUILD_PROGRAM_FAILURE as i32 {
+        // No build log was available if `program_build_err` succeeds, return
+        // the status error:
+        program_build_err(program, devices).and_then(|_| errcode_try("clBuildProgram",
+            || format!("device_count={} options={:?}", devices.len(), options), errcode))
     } else {
-        try!(errcode_try("clBuildProgram", "", errcode));
-        Ok(()) 
+        errcode_try("clBuildProgram", || format!("device_count={} options={:?}",
+            devices.len(), options), errcode)
+    };
+
+    // Logged once the result is known, as querying the build logs would
+    // otherwise consume the load error (if any) of `clBuildProgram`:
+    #[cfg(feature = "logging")]
+    {
+        if logging::build_enabled() {
+            logging::build(program, devices, &options.to_string_lossy(), pfn_notify.is_some(),
+                duration, errcode);
+        }
     }
+
+    result
 }
 
 /// [UNIMPLEMENTED]
@@ -1177,94 +1396,107 @@ pub fn link_program() -> OclResult<()> {
     unimplemented!();
 }
 
-// [DISABLED DUE TO PLATFORM INCOMPATABILITY]
-// /// [UNTESTED]
-// /// Unloads a platform compiler.
-// pub fn unload_platform_compiler(platform: &PlatformId) -> OclResult<()> {
-//     unsafe { errcode_try("clUnloadPlatformCompiler", "", 
-//         cl_h::clUnloadPlatformCompiler(platform.as_ptr())) }
-// }
+/// Allows the implementation to release the resources allocated by the
+/// compiler for `platform`.
+///
+/// This is a hint. Programs may still be built afterwards, the compiler will
+/// simply be reloaded. Implementations for which unloading is not meaningful
+/// return success without doing anything.
+///
+/// [Version Controlled: OpenCL 1.2+] See `unload_compiler` for OpenCL 1.1.
+///
+/// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clUnloadPlatformCompiler.html)
+pub fn unload_platform_compiler(platform: &PlatformId) -> OclResult<()> {
+    unsafe { errcode_try("clUnloadPlatformCompiler", || format!("platform={:?}", platform),
+        cl_h::clUnloadPlatformCompiler(platform.as_ptr())) }
+}
+
+/// [DEPRICATED 1.1] Allows the implementation to release the resources
+/// allocated by the compiler.
+///
+/// Use `unload_platform_compiler` on platforms supporting OpenCL 1.2 or
+/// later.
+pub fn unload_compiler() -> OclResult<()> {
+    unsafe { errcode_try("clUnloadCompiler", || String::new(), cl_h::clUnloadCompiler()) }
+}
 
 /// Get program info.
 pub fn get_program_info(obj: &Program, request: ProgramInfo) -> ProgramInfoResult {
-    let mut result_size: size_t = 0;
-
-    let errcode = unsafe { cl_h::clGetProgramInfo(
-        obj.as_ptr() as cl_program,
-        request as cl_program_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
+    let result = get_info_bytes("clGetProgramInfo", || format!("request={:?}", request),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetProgramInfo(obj.as_ptr() as cl_program, request as cl_program_info, size,
+                value, size_ret)
+        });
+
+    ProgramInfoResult::from_info_bytes(request, result)
+}
+
+/// Returns the binary of a program for each of its devices, in the order of
+/// `CL_PROGRAM_DEVICES`.
+///
+/// The binary of a device for which the program has not been built is empty.
+pub fn get_program_binaries(obj: &Program) -> OclResult<Vec<(DeviceId, Vec<u8>)>> {
+    let query = |request: ProgramInfo| {
+        get_info_bytes("clGetProgramInfo", || format!("request={:?}", request),
+            |size, value, size_ret| unsafe {
+                cl_h::clGetProgramInfo(obj.as_ptr() as cl_program, request as cl_program_info,
+                    size, value, size_ret)
+            })
+    };
 
-    // try!(errcode_try("clGetProgramInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetProgramInfo", "", errcode) {
-        return ProgramInfoResult::Error(Box::new(err));
-    }
+    let devices = try!(query(ProgramInfo::Devices));
+    let devices = try!(unsafe { util::bytes_to_vec::<DeviceId>(&devices) });
+    let sizes = try!(query(ProgramInfo::BinarySizes));
+    let sizes = try!(unsafe { util::bytes_to_vec::<size_t>(&sizes) });
 
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return ProgramInfoResult::from_bytes(request, Ok(vec![]));
+    if devices.len() != sizes.len() {
+        return OclError::err(format!("ocl::core::get_program_binaries: Device count ({}) does \
+            not match binary size count ({}).", devices.len(), sizes.len()));
     }
 
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
+    let mut binaries: Vec<Vec<u8>> = sizes.iter().map(|&size| vec![0u8; size]).collect();
+    let mut binary_ptrs: Vec<*mut u8> = binaries.iter_mut().map(|bin| {
+        if bin.is_empty() { ptr::null_mut() } else { bin.as_mut_ptr() }
+    }).collect();
 
     let errcode = unsafe { cl_h::clGetProgramInfo(
         obj.as_ptr() as cl_program,
-        request as cl_program_info,
-        result_size,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) };    
+        ProgramInfo::Binaries as cl_program_info,
+        binary_ptrs.len() * mem::size_of::<*mut u8>(),
+        binary_ptrs.as_mut_ptr() as *mut c_void,
+        ptr::null_mut(),
+    ) };
+    try!(errcode_try("clGetProgramInfo", || format!("request={:?} sizes={:?}",
+        ProgramInfo::Binaries, sizes), errcode));
 
-    // errcode_try("clGetProgramInfo", "", errcode)
-    //     .and(Ok(ProgramInfoResult::TemporaryPlaceholderVariant(result)))
-    let result = errcode_try("clGetProgramInfo", "", errcode).and(Ok(result));
-    ProgramInfoResult::from_bytes(request, result)
+    Ok(devices.into_iter().zip(binaries.into_iter()).collect())
 }
 
 /// Get program build info.
 pub fn get_program_build_info<D: ClDeviceIdPtr + Debug>(obj: &Program, device_obj: &D, 
             request: ProgramBuildInfo) -> ProgramBuildInfoResult
 {
-    let mut result_size: size_t = 0;
-
-    // println!("ocl::core::get_program_build_info(): device_obj: {:?}", device_obj);
-
-    let errcode = unsafe { cl_h::clGetProgramBuildInfo(
-        obj.as_ptr() as cl_program,
-        device_obj.as_ptr() as cl_device_id,
-        request as cl_program_build_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
-
-    // try!(errcode_try("clGetProgramBuildInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetProgramBuildInfo", "", errcode) {
-        return ProgramBuildInfoResult::Error(Box::new(err));
-    }
-
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return ProgramBuildInfoResult::from_bytes(request, Ok(vec![]));
-    }
-
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
+    ProgramBuildInfoResult::from_info_bytes(request, program_build_info_bytes(obj, device_obj,
+        request))
+}
 
-    let errcode = unsafe { cl_h::clGetProgramBuildInfo(
-        obj.as_ptr() as cl_program,
-        device_obj.as_ptr() as cl_device_id,
-        request as cl_program_build_info,
-        result_size as size_t,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) };    
+/// Get program build info as raw bytes.
+pub fn get_program_build_info_bytes<D: ClDeviceIdPtr + Debug>(obj: &Program, device_obj: &D, 
+            request: ProgramBuildInfo) -> OclResult<Vec<u8>>
+{
+    program_build_info_bytes(obj, device_obj, request).map(InfoBytes::into_vec)
+}
 
-    // errcode_try("clGetProgramBuildInfo", "", errcode)
-    //     .and(Ok(ProgramBuildInfoResult::TemporaryPlaceholderVariant(result)))
-    let result = errcode_try("clGetProgramBuildInfo", "", errcode).and(Ok(result));
-    ProgramBuildInfoResult::from_bytes(request, result)
+fn program_build_info_bytes<D: ClDeviceIdPtr + Debug>(obj: &Program, device_obj: &D,
+            request: ProgramBuildInfo) -> OclResult<InfoBytes>
+{
+    get_info_bytes("clGetProgramBuildInfo", || format!("request={:?} device={:?}", request,
+            device_obj),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetProgramBuildInfo(obj.as_ptr() as cl_program,
+                device_obj.as_ptr() as cl_device_id, request as cl_program_build_info, size,
+                value, size_ret)
+        })
 }
 
 //============================================================================
@@ -1285,7 +1517,7 @@ pub fn create_kernel(
         try!(CString::new(name.as_bytes())).as_ptr(), 
         &mut err,
     )) };
-    errcode_try("clCreateKernel", name, err).and(Ok(kernel))
+    errcode_try("clCreateKernel", || format!("name={}", name), err).and(Ok(kernel))
 }
 
 /// [UNIMPLEMENTED]
@@ -1299,12 +1531,14 @@ pub fn create_kernels_in_program() -> OclResult<()> {
 
 /// Increments a kernel reference counter.
 pub unsafe fn retain_kernel(kernel: &Kernel) -> OclResult<()> {
-    errcode_try("clRetainKernel", "", cl_h::clRetainKernel(kernel.as_ptr()))
+    errcode_try("clRetainKernel", || format!("kernel={:?}", kernel),
+        cl_h::clRetainKernel(kernel.as_ptr()))
 }
 
 /// Decrements a kernel reference counter.
 pub unsafe fn release_kernel(kernel: &Kernel) -> OclResult<()> {
-    errcode_try("clReleaseKernel", "", cl_h::clReleaseKernel(kernel.as_ptr()))
+    errcode_try("clReleaseKernel", || format!("kernel={:?}", kernel),
+        cl_h::clReleaseKernel(kernel.as_ptr()))
 }
 
 
@@ -1366,194 +1600,180 @@ pub fn set_kernel_arg<T: OclPrm>(kernel: &Kernel, arg_index: u32, arg: KernelArg
             arg_value,
     ) };
 
-    if err != 0 {
-        let name = get_kernel_name(&kernel);
-        errcode_try("clSetKernelArg", &name, err)
-    } else {
-        Ok(())
-    }
+    errcode_try("clSetKernelArg", || format!("kernel={} arg_index={} arg_size={}",
+        get_kernel_name(&kernel), arg_index, arg_size), err)
 } 
 
 /// Get kernel info.
 pub fn get_kernel_info(obj: &Kernel, request: KernelInfo,
         ) -> KernelInfoResult
 {
-    let mut result_size: size_t = 0;
-
-    let errcode = unsafe { cl_h::clGetKernelInfo(
-        obj.as_ptr() as cl_kernel,
-        request as cl_kernel_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
-
-    // try!(errcode_try("clGetKernelInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetKernelInfo", "", errcode) {
-        return KernelInfoResult::Error(Box::new(err));
-    }
-
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return KernelInfoResult::from_bytes(request, Ok(vec![]));
-    }
-
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
+    KernelInfoResult::from_info_bytes(request, kernel_info_bytes(obj, request))
+}
 
-    let errcode = unsafe { cl_h::clGetKernelInfo(
-        obj.as_ptr() as cl_kernel,
-        request as cl_kernel_info,
-        result_size,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) };    
+/// Get kernel info as raw bytes.
+pub fn get_kernel_info_bytes(obj: &Kernel, request: KernelInfo,
+        ) -> OclResult<Vec<u8>>
+{
+    kernel_info_bytes(obj, request).map(InfoBytes::into_vec)
+}
 
-    // println!("GET_COMMAND_QUEUE_INFO(): errcode: {}, result: {:?}", errcode, result);
-    // errcode_try("clGetKernelInfo", "", errcode)
-    //     .and(KernelInfoResult::from_bytes(request, result))
-    let result = errcode_try("clGetKernelInfo", "", errcode).and(Ok(result));
-    KernelInfoResult::from_bytes(request, result)
+fn kernel_info_bytes(obj: &Kernel, request: KernelInfo) -> OclResult<InfoBytes> {
+    get_info_bytes("clGetKernelInfo", || format!("request={:?}", request),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetKernelInfo(obj.as_ptr() as cl_kernel, request as cl_kernel_info, size,
+                value, size_ret)
+        })
 }
 
 /// Get kernel arg info.
 pub fn get_kernel_arg_info(obj: &Kernel, arg_index: u32, request: KernelArgInfo,
         ) -> KernelArgInfoResult
 {
-    let mut result_size: size_t = 0;
-
-    let errcode = unsafe { cl_h::clGetKernelArgInfo(
-        obj.as_ptr() as cl_kernel,
-        arg_index as cl_uint,
-        request as cl_kernel_arg_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
+    let result = get_info_bytes("clGetKernelArgInfo", || format!("request={:?} arg_index={}",
+            request, arg_index),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetKernelArgInfo(obj.as_ptr() as cl_kernel, arg_index as cl_uint,
+                request as cl_kernel_arg_info, size, value, size_ret)
+        });
 
-    // try!(errcode_try("clGetKernelArgInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetKernelArgInfo", "", errcode) {
-        return KernelArgInfoResult::Error(Box::new(err));
-    }
-
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return KernelArgInfoResult::from_bytes(request, Ok(vec![]));
-    }
-
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
-
-    let errcode = unsafe { cl_h::clGetKernelArgInfo(
-        obj.as_ptr() as cl_kernel,
-        arg_index as cl_uint,
-        request as cl_kernel_arg_info,
-        result_size,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) };    
-
-    // println!("GET_COMMAND_QUEUE_INFO(): errcode: {}, result: {:?}", errcode, result);
-    // errcode_try("clGetKernelArgInfo", "", errcode)
-    //     .and(KernelArgInfoResult::from_bytes(request, result))
-    let result = errcode_try("clGetKernelArgInfo", "", errcode).and(Ok(result));
-    KernelArgInfoResult::from_bytes(request, result)
+    KernelArgInfoResult::from_info_bytes(request, result)
 }
 
 /// Get kernel work group info.
 pub fn get_kernel_work_group_info<D: ClDeviceIdPtr>(obj: &Kernel, device_obj: &D, 
             request: KernelWorkGroupInfo) -> KernelWorkGroupInfoResult
 {
-    let mut result_size: size_t = 0;
+    let result = get_info_bytes("clGetKernelWorkGroupInfo", || format!("request={:?}", request),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetKernelWorkGroupInfo(obj.as_ptr() as cl_kernel,
+                device_obj.as_ptr() as cl_device_id, request as cl_kernel_work_group_info,
+                size, value, size_ret)
+        });
 
-    let errcode = unsafe { cl_h::clGetKernelWorkGroupInfo(
-        obj.as_ptr() as cl_kernel,
-        device_obj.as_ptr() as cl_device_id,
-        request as cl_kernel_work_group_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
+    KernelWorkGroupInfoResult::from_info_bytes(request, result)
+}
 
-    // try!(errcode_try("clGetKernelWorkGroupInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetKernelWorkGroupInfo", "", errcode) {
-        return KernelWorkGroupInfoResult::Error(Box::new(err));
-    }
+//============================================================================
+//========================== Event Object APIs ===============================
+//============================================================================
 
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return KernelWorkGroupInfoResult::from_bytes(request, Ok(vec![]));
-    }
+/// Returns a description of each event in `event_ptrs` having a negative
+/// (error) execution status, formatted for use in error info.
+fn fmt_failed_events(event_ptrs: &[cl_event]) -> String {
+    let mut failed: Vec<String> = Vec::new();
 
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
+    for (idx, &event_ptr) in event_ptrs.iter().enumerate() {
+        let mut status: cl_int = 0;
 
-    let errcode = unsafe { cl_h::clGetKernelWorkGroupInfo(
-        obj.as_ptr() as cl_kernel,
-        device_obj.as_ptr() as cl_device_id,
-        request as cl_kernel_work_group_info,
-        result_size,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) };    
+        let errcode = unsafe { cl_h::clGetEventInfo(
+            event_ptr,
+            cl_h::CL_EVENT_COMMAND_EXECUTION_STATUS,
+            mem::size_of::<cl_int>(),
+            &mut status as *mut _ as *mut c_void,
+            ptr::null_mut(),
+        ) };
+
+        if errcode != cl_h::Status::CL_SUCCESS as cl_int {
+            failed.push(format!("[{}]: status unavailable (errcode: {})", idx, errcode));
+        } else if status < 0 {
+            match cl_h::Status::from_i32(status) {
+                Some(s) => failed.push(format!("[{}]: {:?}", idx, s)),
+                None => failed.push(format!("[{}]: {}", idx, status)),
+            }
+        }
+    }
 
-    // println!("GET_COMMAND_QUEUE_INFO(): errcode: {}, result: {:?}", errcode, result);
-    // errcode_try("clGetKernelWorkGroupInfo", "", errcode)
-    //     .and(KernelWorkGroupInfoResult::from_bytes(request, result))
-    let result = errcode_try("clGetKernelWorkGroupInfo", "", errcode).and(Ok(result));
-    KernelWorkGroupInfoResult::from_bytes(request, result)
+    failed.join(", ")
 }
 
-//============================================================================
-//========================== Event Object APIs ===============================
-//============================================================================
+/// Returns a description of the context associated with each event in
+/// `event_ptrs` if they do not all match, formatted for use in error info.
+fn fmt_mismatched_event_contexts(event_ptrs: &[cl_event]) -> Option<String> {
+    let contexts: Vec<cl_context> = event_ptrs.iter().map(|&event_ptr| {
+        let mut context: cl_context = ptr::null_mut();
 
-/// Blocks until the first `num_events` events in `event_list` are complete.
-pub fn wait_for_events(num_events: u32, event_list: &ClWaitList) -> OclResult<()> {
-    assert!(event_list.count() >= num_events);
+        unsafe { cl_h::clGetEventInfo(
+            event_ptr,
+            cl_h::CL_EVENT_CONTEXT,
+            mem::size_of::<cl_context>(),
+            &mut context as *mut _ as *mut c_void,
+            ptr::null_mut(),
+        ) };
 
-    let errcode = unsafe {
-        cl_h::clWaitForEvents(num_events, event_list.as_ptr_ptr())
-    };
+        context
+    }).collect();
 
-    errcode_try("clWaitForEvents", "", errcode)
+    if contexts.iter().all(|&c| c == contexts[0]) {
+        None
+    } else {
+        Some(contexts.iter().enumerate().map(|(idx, c)| format!("[{}]: {:?}", idx, c))
+            .collect::<Vec<_>>().join(", "))
+    }
 }
 
-/// Get event info.
-pub fn get_event_info(event: &Event, request: EventInfo,
-        ) -> EventInfoResult
-{
-    let mut result_size: size_t = 0;
+/// Blocks until each event in `event_ptrs` is complete.
+fn wait_for_event_ptrs(event_ptrs: &[cl_event]) -> OclResult<()> {
+    if event_ptrs.is_empty() { return Ok(()); }
 
-    let errcode = unsafe { cl_h::clGetEventInfo(
-        *event.as_ptr_ref(),
-        request as cl_event_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
+    let errcode = unsafe {
+        cl_h::clWaitForEvents(event_ptrs.len() as cl_uint, event_ptrs.as_ptr())
+    };
 
-    // try!(errcode_try("clGetEventInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetEventInfo", "", errcode) {
-        return EventInfoResult::Error(Box::new(err));
+    if errcode == cl_h::Status::CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST as cl_int {
+        errcode_try("clWaitForEvents", || format!("num_events={}, failed events: {}",
+            event_ptrs.len(), fmt_failed_events(event_ptrs)), errcode)
+    } else if errcode == cl_h::Status::CL_INVALID_CONTEXT as cl_int {
+        match fmt_mismatched_event_contexts(event_ptrs) {
+            Some(contexts) => OclError::err(format!("ocl::core::wait_for_events: All events \
+                must be associated with the same context (event contexts: {}).", contexts)),
+            None => errcode_try("clWaitForEvents", || format!("num_events={}",
+                event_ptrs.len()), errcode),
+        }
+    } else {
+        errcode_try("clWaitForEvents", || format!("num_events={}", event_ptrs.len()), errcode)
     }
+}
 
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return EventInfoResult::from_bytes(request, Ok(vec![]));
-    }
+/// Blocks until each event in `events` is complete.
+///
+/// An empty slice returns immediately.
+///
+/// ## Errors
+///
+/// If any event has terminated abnormally, the returned error lists each
+/// event (by index) with a negative execution status along with that status.
+/// All events must be associated with the same context, otherwise an error
+/// describing the context of each event is returned.
+///
+/// [SDK](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clWaitForEvents.html)
+pub fn wait_for_events(events: &[Event]) -> OclResult<()> {
+    wait_for_event_list(&events)
+}
 
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
+/// Blocks until each event in `event_list` is complete.
+///
+/// Equivalent to `::wait_for_events` but accepts any wait list (such as an
+/// `EventList`).
+pub fn wait_for_event_list(event_list: &ClWaitList) -> OclResult<()> {
+    let event_ptrs = match event_list.count() {
+        0 => &[][..],
+        count => unsafe { slice::from_raw_parts(event_list.as_ptr_ptr(), count as usize) },
+    };
+    wait_for_event_ptrs(event_ptrs)
+}
 
-    let errcode = unsafe { cl_h::clGetEventInfo(
-        *event.as_ptr_ref(),
-        request as cl_event_info,
-        result_size,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) };   
+/// Get event info.
+pub fn get_event_info(event: &Event, request: EventInfo,
+        ) -> EventInfoResult
+{
+    let result = get_info_bytes("clGetEventInfo", || format!("request={:?}", request),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetEventInfo(*event.as_ptr_ref(), request as cl_event_info, size, value,
+                size_ret)
+        });
 
-    // errcode_try("clGetEventInfo", "", errcode)
-    //     .and(Ok(EventInfoResult::TemporaryPlaceholderVariant(result)))
-    let result = errcode_try("clGetEventInfo", "", errcode).and(Ok(result));
-    EventInfoResult::from_bytes(request, result)
+    EventInfoResult::from_info_bytes(request, result)
 }
 
 /// [UNTESTED]
@@ -1561,18 +1781,20 @@ pub fn get_event_info(event: &Event, request: EventInfo,
 pub fn create_user_event(context: &Context) -> OclResult<Event> {
     let mut errcode = 0;
     let event = unsafe { Event::from_fresh_ptr(cl_h::clCreateUserEvent(context.as_ptr(), &mut errcode)) };
-    errcode_try("clCreateUserEvent", "", errcode).and(Ok(event))
+    errcode_try("clCreateUserEvent", || format!("context={:?}", context), errcode).and(Ok(event))
 }
 
 /// Increments an event's reference counter.
 pub unsafe fn retain_event<'e, E: ClEventRef<'e>>(event: &'e E) -> OclResult<()> {
     // cl_h::clRetainEvent(event: cl_event) -> cl_int;
-    errcode_try("clRetainEvent", "", cl_h::clRetainEvent(*event.as_ptr_ref()))
+    errcode_try("clRetainEvent", || format!("event={:?}", *event.as_ptr_ref()),
+        cl_h::clRetainEvent(*event.as_ptr_ref()))
 }
 
 /// Decrements an event's reference counter.
 pub unsafe fn release_event<'e, E: ClEventRef<'e>>(event: &'e E) -> OclResult<()> {
-    errcode_try("clReleaseEvent", "", cl_h::clReleaseEvent(*event.as_ptr_ref()))
+    errcode_try("clReleaseEvent", || format!("event={:?}", *event.as_ptr_ref()),
+        cl_h::clReleaseEvent(*event.as_ptr_ref()))
 }
 
 /// [UNTESTED]
@@ -1580,7 +1802,8 @@ pub unsafe fn release_event<'e, E: ClEventRef<'e>>(event: &'e E) -> OclResult<()
 pub fn set_user_event_status<'e,E: ClEventRef<'e>>(event: &'e E, 
             execution_status: CommandExecutionStatus) -> OclResult<()>
 {
-    unsafe { errcode_try("clSetUserEventStatus", "", cl_h::clSetUserEventStatus(
+    unsafe { errcode_try("clSetUserEventStatus", || format!("execution_status={:?}",
+        execution_status), cl_h::clSetUserEventStatus(
         *event.as_ptr_ref(), execution_status as cl_int)) }
 }
 
@@ -1593,12 +1816,34 @@ pub unsafe fn set_event_callback<'e, E: ClEventRef<'e>>(
             user_data: *mut c_void,
         ) -> OclResult<()> 
 {
-    errcode_try("clSetEventCallback", "", cl_h::clSetEventCallback(
-        *event.as_ptr_ref(), 
-        callback_trigger as cl_int, 
-        callback_receiver, 
-        user_data,
-    ))
+    errcode_try("clSetEventCallback", || format!("callback_trigger={:?}", callback_trigger),
+        cl_h::clSetEventCallback(
+        *event.as_ptr_ref(), 
+        callback_trigger as cl_int, 
+        callback_receiver, 
+        user_data,
+    ))
+}
+
+/// Registers `callback` to be called once the command associated with
+/// `event` reaches the `callback_trigger` status (or terminates abnormally,
+/// in which case it receives an error).
+///
+/// `callback` may be called from any thread, possibly before this function
+/// returns, and panics within it are caught and reported rather than
+/// propagated. It must not call blocking functions such as
+/// `::wait_for_events` or `::finish`.
+pub fn set_event_callback_fn<'e, E, F>(
+            event: &'e E,
+            callback_trigger: CommandExecutionStatus,
+            callback: F,
+        ) -> OclResult<()> 
+        where E: ClEventRef<'e>, F: FnOnce(OclResult<CommandExecutionStatus>) + Send + 'static
+{
+    callbacks::register_once(callback, |user_data| unsafe {
+        set_event_callback(event, callback_trigger, Some(callbacks::event_trampoline::<F>),
+            user_data)
+    })
 }
 
 //============================================================================
@@ -1609,42 +1854,15 @@ pub unsafe fn set_event_callback<'e, E: ClEventRef<'e>>(
 pub fn get_event_profiling_info(event: &Event, request: ProfilingInfo,
         ) -> ProfilingInfoResult
 {
-    let mut result_size: size_t = 0;
     let event: cl_event = unsafe { *event.as_ptr_ref() };
 
-    let errcode = unsafe { cl_h::clGetEventProfilingInfo(
-        event,
-        request as cl_profiling_info,
-        0 as size_t,
-        0 as *mut c_void,
-        &mut result_size as *mut size_t,
-    ) };
-
-    // try!(errcode_try("clGetEventProfilingInfo", "", errcode));
-    if let Err(err) = errcode_try("clGetEventProfilingInfo", "", errcode) {
-        return ProfilingInfoResult::Error(Box::new(err));
-    }
-
-    // If result size is zero, return an empty info result directly:
-    if result_size == 0 {
-        return ProfilingInfoResult::from_bytes(request, Ok(vec![]));
-    }
-
-    let mut result: Vec<u8> = iter::repeat(0u8).take(result_size).collect();
-
-    let errcode = unsafe { cl_h::clGetEventProfilingInfo(
-        event,
-        request as cl_profiling_info,
-        result_size,
-        result.as_mut_ptr() as *mut _ as *mut c_void,
-        0 as *mut size_t,
-    ) };    
+    let result = get_info_bytes("clGetEventProfilingInfo", || format!("request={:?}", request),
+        |size, value, size_ret| unsafe {
+            cl_h::clGetEventProfilingInfo(event, request as cl_profiling_info, size, value,
+                size_ret)
+        });
 
-    // println!("GET_COMMAND_QUEUE_INFO(): errcode: {}, result: {:?}", errcode, result);
-    // errcode_try("clGetEventProfilingInfo", "", errcode)
-    //     .and(Ok(ProfilingInfoResult::TemporaryPlaceholderVariant(result)))
-    let result = errcode_try("clGetEventProfilingInfo", "", errcode).and(Ok(result));
-    ProfilingInfoResult::from_bytes(request, result)
+    ProfilingInfoResult::from_info_bytes(request, result)
 }
 
 //============================================================================
@@ -1657,7 +1875,8 @@ pub fn get_event_profiling_info(event: &Event, request: ProfilingInfo,
 /// Issues all previously queued OpenCL commands in a command-queue to the 
 /// device associated with the command-queue.
 pub fn flush(command_queue: &CommandQueue) -> OclResult<()> {
-    unsafe { errcode_try("clFlush", "", cl_h::clFlush(command_queue.as_ptr())) }
+    unsafe { errcode_try("clFlush", || format!("queue={:?}", command_queue),
+        cl_h::clFlush(command_queue.as_ptr())) }
 }
 
 /// Waits for a queue to finish.
@@ -1667,7 +1886,7 @@ pub fn flush(command_queue: &CommandQueue) -> OclResult<()> {
 pub fn finish(command_queue: &CommandQueue) -> OclResult<()> {
     unsafe { 
         let errcode = cl_h::clFinish(command_queue.as_ptr());
-        errcode_try("clFinish", "", errcode)
+        errcode_try("clFinish", || format!("queue={:?}", command_queue), errcode)
     }
 }
 
@@ -1714,7 +1933,9 @@ pub unsafe fn enqueue_read_buffer<T: OclPrm>(
         new_event_ptr,
     );
 
-    errcode_try("clEnqueueReadBuffer", "", errcode)
+    enqueue_try("clEnqueueReadBuffer", command_queue, wait_list_len, new_event_ptr,
+        || format!("block={} offset={} size={} buffer_len={}",
+        block, offset_bytes, data.len() * mem::size_of::<T>(), fmt_mem_len(buffer)), errcode)
 }
 
 /// Enqueues a command to read from a rectangular region from a buffer object to host memory.
@@ -1786,7 +2007,13 @@ pub unsafe fn enqueue_read_buffer_rect<T: OclPrm>(
         wait_list_ptr,
         new_event_ptr,
     );
-    errcode_try("clEnqueueReadBufferRect", "", errcode)
+    enqueue_try("clEnqueueReadBufferRect", command_queue, wait_list_len, new_event_ptr,
+        || format!("block={} buffer_origin={:?} \
+        host_origin={:?} region={:?} buffer_row_pitch={} buffer_slc_pitch={} host_row_pitch={} \
+        host_slc_pitch={} data_len={} buffer_len={}", block, buffer_origin_bytes,
+        host_origin_bytes, region_bytes, buffer_row_pitch_bytes, buffer_slc_pitch_bytes,
+        host_row_pitch_bytes, host_slc_pitch_bytes, data.len() * mem::size_of::<T>(),
+        fmt_mem_len(buffer)), errcode)
 }
 
 /// Enqueues a write from host memory, `data`, to device memory referred to by
@@ -1817,7 +2044,9 @@ pub fn enqueue_write_buffer<T: OclPrm>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueWriteBuffer", "", errcode)
+    enqueue_try("clEnqueueWriteBuffer", command_queue, wait_list_len, new_event_ptr,
+        || format!("block={} offset={} size={} buffer_len={}",
+        block, offset_bytes, data.len() * mem::size_of::<T>(), fmt_mem_len(buffer)), errcode)
 }
 
 /// Enqueues a command to write from a rectangular region from host memory to a buffer object.
@@ -1873,13 +2102,28 @@ pub fn enqueue_write_buffer_rect<T: OclPrm>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueWriteBufferRect", "", errcode)
+    enqueue_try("clEnqueueWriteBufferRect", command_queue, wait_list_len, new_event_ptr,
+        || format!("block={} buffer_origin={:?} \
+        host_origin={:?} region={:?} buffer_row_pitch={} buffer_slc_pitch={} host_row_pitch={} \
+        host_slc_pitch={} data_len={} buffer_len={}", block, buffer_origin_bytes,
+        host_origin_bytes, region_bytes, buffer_row_pitch_bytes, buffer_slc_pitch_bytes,
+        host_row_pitch_bytes, host_slc_pitch_bytes, data.len() * mem::size_of::<T>(),
+        fmt_mem_len(buffer)), errcode)
 }
 
 /// Enqueues a command to fill a buffer object with a pattern of a given pattern size.
 ///
+/// `offset` and `len` are in elements. To fill with a single value, pass it
+/// as a one element pattern, e.g. `&[value]`.
+///
 /// ## Pattern (from [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueFillBuffer.html))
 ///
+/// The pattern size must be one of 1, 2, 4, 8, 16, 32, 64, or 128 bytes and
+/// both `offset` and `len` must be multiples of the pattern length. These
+/// are checked before the command is enqueued.
+///
+/// Requires OpenCL 1.2 or later.
+///
 pub fn enqueue_fill_buffer<T: OclPrm>(
             command_queue: &CommandQueue,
             buffer: &Mem,
@@ -1891,6 +2135,21 @@ pub fn enqueue_fill_buffer<T: OclPrm>(
         ) -> OclResult<()> 
 {
     let pattern_size = pattern.len() * mem::size_of::<T>();
+
+    if pattern.is_empty() {
+        return OclError::err("ocl::core::enqueue_fill_buffer: The pattern must not be empty.");
+    }
+    if !pattern_size.is_power_of_two() || pattern_size > 128 {
+        return OclError::err(format!("ocl::core::enqueue_fill_buffer: Invalid pattern size \
+            ({} bytes). The size of the pattern must be one of 1, 2, 4, 8, 16, 32, 64, or 128 \
+            bytes.", pattern_size));
+    }
+    if offset % pattern.len() != 0 || len % pattern.len() != 0 {
+        return OclError::err(format!("ocl::core::enqueue_fill_buffer: The offset ({}) and \
+            length ({}) must be multiples of the pattern length ({}).", offset, len,
+            pattern.len()));
+    }
+
     let offset_bytes = offset * mem::size_of::<T>();
     let size_bytes = len * mem::size_of::<T>();
 
@@ -1908,7 +2167,9 @@ pub fn enqueue_fill_buffer<T: OclPrm>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueFillBuffer", "", errcode)
+    enqueue_try("clEnqueueFillBuffer", command_queue, wait_list_len, new_event_ptr,
+        || format!("pattern_size={} offset={} size={} \
+        buffer_len={}", pattern_size, offset_bytes, size_bytes, fmt_mem_len(buffer)), errcode)
 }
 
 /// [UNTESTED]
@@ -1942,7 +2203,10 @@ pub fn enqueue_copy_buffer<T: OclPrm>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueCopyBuffer", "", errcode)
+    enqueue_try("clEnqueueCopyBuffer", command_queue, wait_list_len, new_event_ptr,
+        || format!("src_offset={} dst_offset={} size={} \
+        src_buffer_len={} dst_buffer_len={}", src_offset_bytes, dst_offset_bytes, len_bytes,
+        fmt_mem_len(src_buffer), fmt_mem_len(dst_buffer)), errcode)
 }
 
 /// Enqueues a command to copy a rectangular region from a buffer object to
@@ -1993,7 +2257,12 @@ pub fn enqueue_copy_buffer_rect<T: OclPrm>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueCopyBufferRect", "", errcode)
+    enqueue_try("clEnqueueCopyBufferRect", command_queue, wait_list_len, new_event_ptr,
+        || format!("src_origin={:?} dst_origin={:?} \
+        region={:?} src_row_pitch={} src_slc_pitch={} dst_row_pitch={} dst_slc_pitch={} \
+        src_buffer_len={} dst_buffer_len={}", src_origin_bytes, dst_origin_bytes, region_bytes,
+        src_row_pitch_bytes, src_slc_pitch_bytes, dst_row_pitch_bytes, dst_slc_pitch_bytes,
+        fmt_mem_len(src_buffer), fmt_mem_len(dst_buffer)), errcode)
 }
 
 
@@ -2042,7 +2311,10 @@ pub unsafe fn enqueue_read_image<T>(
         wait_list_ptr,
         new_event_ptr,
     );
-    errcode_try("clEnqueueReadImage", "", errcode)
+    enqueue_try("clEnqueueReadImage", command_queue, wait_list_len, new_event_ptr,
+        || format!("block={} origin={:?} region={:?} row_pitch={} \
+        slc_pitch={} data_len={}", block, origin, region, row_pitch, slc_pitch,
+        data.len() * mem::size_of::<T>()), errcode)
 }
 
 
@@ -2083,10 +2355,12 @@ pub fn enqueue_write_image<T>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueWriteImage", "", errcode)
+    enqueue_try("clEnqueueWriteImage", command_queue, wait_list_len, new_event_ptr,
+        || format!("block={} origin={:?} region={:?} \
+        input_row_pitch={} input_slc_pitch={} data_len={}", block, origin, region,
+        input_row_pitch, input_slc_pitch, data.len() * mem::size_of::<T>()), errcode)
 }
 
-/// [UNTESTED]
 /// Enqueues a command to fill an image object with a specified color.
 ///
 /// ## Fill Color (from [SDK docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueFillImage.html)
@@ -2099,7 +2373,10 @@ pub fn enqueue_write_image<T>(
 /// unormalized unsigned integer type. The fill color will be converted to the
 /// appropriate image channel format and order associated with image.
 ///
-/// TODO: Trait constraints for `T`. Presumably it should be 32bits? Testing needed.
+/// `color` must therefore consist of four 32-bit components (`f32`, `i32`,
+/// or `u32`). This is checked before the command is enqueued.
+///
+/// Requires OpenCL 1.2 or later.
 pub fn enqueue_fill_image<T>(
             command_queue: &CommandQueue,
             image: &Mem,
@@ -2110,8 +2387,11 @@ pub fn enqueue_fill_image<T>(
             new_event: Option<&mut ClEventPtrNew>,
         ) -> OclResult<()> 
 {
-    // let origin_bytes = [origin[0] * mem::size_of::<T>(), origin[1], origin[2]];
-    // let region_bytes = [region[0] * mem::size_of::<T>(), region[1], region[2]];
+    if color.len() != 4 || mem::size_of::<T>() != 4 {
+        return OclError::err(format!("ocl::core::enqueue_fill_image: The fill color must have \
+            four 32-bit components (found {} components of {} bytes).", color.len(),
+            mem::size_of::<T>()));
+    }
 
     let (wait_list_len, wait_list_ptr, new_event_ptr) 
         = try!(resolve_event_ptrs(wait_list, new_event));
@@ -2126,7 +2406,9 @@ pub fn enqueue_fill_image<T>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueFillImage", "", errcode)
+    enqueue_try("clEnqueueFillImage", command_queue, wait_list_len, new_event_ptr,
+        || format!("origin={:?} region={:?}", origin, region),
+        errcode)
 }
 
 
@@ -2162,7 +2444,9 @@ pub fn enqueue_copy_image<T>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueCopyImage", "", errcode)
+    enqueue_try("clEnqueueCopyImage", command_queue, wait_list_len, new_event_ptr,
+        || format!("src_origin={:?} dst_origin={:?} region={:?}",
+        src_origin, dst_origin, region), errcode)
 }
 
 /// [UNTESTED]
@@ -2198,7 +2482,10 @@ pub fn enqueue_copy_image_to_buffer<T: OclPrm>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueCopyImageToBuffer", "", errcode)
+    enqueue_try("clEnqueueCopyImageToBuffer", command_queue, wait_list_len, new_event_ptr,
+        || format!("src_origin={:?} region={:?} \
+        dst_offset={} dst_buffer_len={}", src_origin, region, dst_offset_bytes,
+        fmt_mem_len(dst_buffer)), errcode)
 }
 
 /// [UNTESTED]
@@ -2234,7 +2521,10 @@ pub fn enqueue_copy_buffer_to_image<T: OclPrm>(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueCopyBufferToImage", "", errcode)
+    enqueue_try("clEnqueueCopyBufferToImage", command_queue, wait_list_len, new_event_ptr,
+        || format!("src_offset={} dst_origin={:?} \
+        region={:?} src_buffer_len={}", src_offset_bytes, dst_origin, region,
+        fmt_mem_len(src_buffer)), errcode)
 }
 
 /// [UNTESTED] 
@@ -2294,55 +2584,57 @@ pub unsafe fn enqueue_map_buffer<T: OclPrm>(
         new_event_ptr,
         &mut errcode,
     );
-    try!(errcode_try("clEnqueueMapBuffer", "", errcode));
+    try!(enqueue_try("clEnqueueMapBuffer", command_queue, wait_list_len, new_event_ptr,
+        || format!("block={} map_flags={:?} offset={} \
+        size={} buffer_len={}", block, map_flags, offset_bytes, size_bytes, fmt_mem_len(buffer)),
+        errcode));
 
     Ok(mapped_ptr)
 }
 
-/// [UNTESTED]
 /// Enqueues a command to map a region of the image object given by `image` into
-/// the host address space and returns a pointer to this mapped region.
+/// the host address space and returns a pointer to this mapped region along
+/// with the row and slice pitch (in bytes) of the mapped region.
 ///
-/// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueMapBuffer.html)
+/// `origin` and `region` are expressed in pixels. Unused dimensions of
+/// `region` (e.g. the 3rd dimension of a 2D image) must be `1`. The slice
+/// pitch returned for 1D and 2D images is `0`.
+///
+/// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueMapImage.html)
 ///
 /// ## Stability
 ///
 /// This function will eventually return a safe wrapper for the mapped host
-/// memory. Until then, just create a `Vec` from the returned pointer using
-/// `size` / size_of::<T>() as the length and capacity.
+/// memory.
 ///
 /// ## Safety
 ///
-/// Caller must ensure that the returned pointer is not used until the map is complete. Use
-/// `new_event` to monitor it. [TEMPORARY] It also must be ensured that memory referred to by the returned pointer is not dropped, reused, or otherwise interfered with until `enqueue_unmap_mem_object` is called.
-///
-/// 
-/// TODO: Return a new wrapped type representing the newly mapped memory.
-///
-/// [`EventList::get_clone`]: /ocl/ocl/struct.EventList.html#method.last_clone
-///
+/// Caller must ensure that the returned pointer is not used until the map is
+/// complete. Use `new_event` to monitor it. It also must be ensured that
+/// memory referred to by the returned pointer is not dropped, reused, or
+/// otherwise interfered with until `enqueue_unmap_mem_object` is called.
 ///
-pub unsafe fn enqueue_map_image<T>(
+pub unsafe fn enqueue_map_image(
             command_queue: &CommandQueue,
             image: &Mem,
             block: bool,
             map_flags: MapFlags,
             origin: [usize; 3],
             region: [usize; 3],
-            row_pitch: usize,
-            slc_pitch: usize,        
             wait_list: Option<&ClWaitList>, 
             new_event: Option<&mut ClEventPtrNew>,
-        ) -> OclResult<*mut c_void> 
+        ) -> OclResult<(*mut c_void, usize, usize)> 
 {
-    // let origin_bytes = [origin[0] * mem::size_of::<T>(), origin[1], origin[2]];
-    // let region_bytes = [region[0] * mem::size_of::<T>(), region[1], region[2]];
-    let row_pitch_bytes = row_pitch * mem::size_of::<T>();
-    let slc_pitch_bytes = slc_pitch * mem::size_of::<T>();
+    if region.iter().any(|&r| r == 0) {
+        return OclError::err(format!("ocl::core::enqueue_map_image: Invalid region: {:?}. \
+            Each dimension of 'region' must be at least 1.", region));
+    }
 
     let (wait_list_len, wait_list_ptr, new_event_ptr) =
         try!(resolve_event_ptrs(wait_list, new_event));
     let mut errcode = 0i32;
+    let mut row_pitch = 0usize;
+    let mut slc_pitch = 0usize;
 
     let mapped_ptr = cl_h::clEnqueueMapImage(
         command_queue.as_ptr(),
@@ -2351,19 +2643,25 @@ pub unsafe fn enqueue_map_image<T>(
         map_flags.bits(),
         &origin as *const _ as *const usize,
         &region as *const _ as *const usize,
-        row_pitch_bytes,
-        slc_pitch_bytes,
+        &mut row_pitch,
+        &mut slc_pitch,
         wait_list_len,
         wait_list_ptr,
         new_event_ptr,
         &mut errcode,
     );
-    try!(errcode_try("clEnqueueMapImage", "", errcode));
+    try!(enqueue_try("clEnqueueMapImage", command_queue, wait_list_len, new_event_ptr,
+        || format!("block={} map_flags={:?} origin={:?} \
+        region={:?}", block, map_flags, origin, region), errcode));
 
-    Ok(mapped_ptr)
+    if mapped_ptr.is_null() {
+        return OclError::err("ocl::core::enqueue_map_image: 'clEnqueueMapImage' returned a \
+            null pointer.");
+    }
+
+    Ok((mapped_ptr, row_pitch, slc_pitch))
 }
 
-/// [UNTESTED]
 /// Enqueues a command to unmap a previously mapped region of a memory object.
 ///
 /// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueUnmapMemObject.html)
@@ -2391,7 +2689,8 @@ pub fn enqueue_unmap_mem_object(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueUnmapMemObject", "", errcode)
+    enqueue_try("clEnqueueUnmapMemObject", command_queue, wait_list_len, new_event_ptr,
+        || format!("mapped_ptr={:?}", mapped_ptr), errcode)
 }
 
 /// [UNTESTED]
@@ -2423,14 +2722,15 @@ pub fn enqueue_migrate_mem_objects(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueMigrateMemObjects", "", errcode)
+    enqueue_try("clEnqueueMigrateMemObjects", command_queue, wait_list_len, new_event_ptr,
+        || format!("num_mem_objects={} flags={:?}",
+        num_mem_objects, flags), errcode)
 }
 
 /// Enqueues a command to execute a kernel on a device.
 ///
-/// # Stability
-/// 
-/// Work dimension/offset sizes *may* eventually be wrapped up in specialized types.
+/// The work sizes and offset are checked for dimensional consistency (see
+/// `WorkSizes`). Use `::enqueue_kernel_dims` to pass a `WorkSizes` directly.
 ///
 /// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueNDRangeKernel.html)
 pub fn enqueue_kernel(
@@ -2445,36 +2745,35 @@ pub fn enqueue_kernel(
             // kernel_name: Option<&str>
         ) -> OclResult<()> 
 {
-    // if !cfg!(release) {
-    //     #[allow(unused_imports)] use std::thread;
-    //     #[allow(unused_imports)] use std::time::Duration;
-    // }
+    let dim_count = work_dims as usize;
 
-    // #[cfg(feature="kernel_debug_print")] 
-    // println!("Resolving events: wait_list: {:?}, new_event: {:?}", wait_list, new_event);
-
-    let (wait_list_len, wait_list_ptr, new_event_ptr) = 
-        try!(resolve_event_ptrs(wait_list, new_event));
-
-    // #[cfg(feature="kernel_debug_print")] 
-    // println!("Resolving global work offset: {:?}...", global_work_offset);
-
-    let gwo = resolve_work_dims(&global_work_offset);
-
-    // #[cfg(feature="kernel_debug_print")] 
-    // println!("Assigning global work size: {:?}...", global_work_dims);
-
-    let gws = global_work_dims as *const size_t;
+    if dim_count == 0 || dim_count > 3 {
+        return OclError::err(format!("ocl::core::enqueue_kernel: Invalid number of work \
+            dimensions: '{}'. Must be 1, 2, or 3.", work_dims));
+    }
 
-    // #[cfg(feature="kernel_debug_print")] 
-    // println!("Resolving local work size: {:?}...", local_work_dims);
+    let dims = try!(WorkSizes::new(&global_work_dims[..dim_count],
+        local_work_dims.as_ref().map(|lws| &lws[..dim_count]),
+        global_work_offset.as_ref().map(|gwo| &gwo[..dim_count])));
 
-    let lws = resolve_work_dims(&local_work_dims);
+    enqueue_kernel_dims(command_queue, kernel, &dims, wait_list, new_event)
+}
 
-    // #[cfg(feature="kernel_debug_print")] 
-    // println!("Preparing to print all details...");
+/// Enqueues a command to execute a kernel on a device using the sizes and
+/// offset specified by `work_dims`.
+///
+/// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueNDRangeKernel.html)
+pub fn enqueue_kernel_dims(
+            command_queue: &CommandQueue,
+            kernel: &Kernel,
+            work_dims: &WorkSizes,
+            wait_list: Option<&ClWaitList>, 
+            new_event: Option<&mut ClEventPtrNew>,
+        ) -> OclResult<()> 
+{
+    let (wait_list_len, wait_list_ptr, new_event_ptr) = 
+        try!(resolve_event_ptrs(wait_list, new_event));
 
-    // #[cfg(feature="kernel_debug_print")]
     if cfg!(feature="kernel_debug_print") {
         print!("core::enqueue_kernel('{}': \
             work_dims: {}, \
@@ -2486,24 +2785,23 @@ pub fn enqueue_kernel(
             new_event_ptr: {:?}) \
             ",
             get_kernel_name(&kernel),
-            work_dims,
-            global_work_offset,
-            global_work_dims,
-            local_work_dims,
+            work_dims.dim_count(),
+            work_dims.global_work_offset(),
+            work_dims.global_work_size(),
+            work_dims.local_work_size(),
             wait_list_len,
             wait_list_ptr,
             new_event_ptr,
         );
     }
 
-
     let errcode = unsafe { cl_h::clEnqueueNDRangeKernel(
             command_queue.as_ptr(),
             kernel.as_ptr() as cl_kernel,
-            work_dims,
-            gwo,
-            gws,
-            lws,
+            work_dims.dim_count(),
+            work_dims.gwo_ptr(),
+            work_dims.gws_ptr(),
+            work_dims.lws_ptr(),
             wait_list_len,
             wait_list_ptr,
             new_event_ptr,
@@ -2514,15 +2812,14 @@ pub fn enqueue_kernel(
         thread::sleep(Duration::from_millis(KERNEL_DEBUG_SLEEP_DURATION_MS));
     }
 
-    if errcode != 0 {
-        let name = get_kernel_name(&kernel);
-        errcode_try("clEnqueueNDRangeKernel", &name, errcode)
-    } else {
-        Ok(())
-    }
+    enqueue_try("clEnqueueNDRangeKernel", command_queue, wait_list_len, new_event_ptr,
+        || format!("kernel={} dim_count={} gwo={:?} gws={:?} \
+        lws={:?} wait_list_len={}", get_kernel_name(&kernel), work_dims.dim_count(),
+        work_dims.global_work_offset(), work_dims.global_work_size(),
+        work_dims.local_work_size(), wait_list_len), errcode)
 }
 
-/// [UNTESTED] Enqueues a command to execute a kernel on a device.
+/// Enqueues a command to execute a kernel on a device.
 ///
 /// The kernel is executed using a single work-item.
 ///
@@ -2530,6 +2827,10 @@ pub fn enqueue_kernel(
 /// with work_dim = 1, global_work_offset = NULL, global_work_size[0] set to 1,
 /// and local_work_size[0] set to 1.
 ///
+/// `clEnqueueTask` is deprecated as of OpenCL 2.0. Use
+/// `standard::Kernel::enq_task` (or `KernelCmd::task`) to fall back to an
+/// equivalent NDRange launch automatically on such devices.
+///
 /// [SDK]: https://www.khronos.org/registry/cl/sdk/1.0/docs/man/xhtml/clEnqueueTask.html
 ///
 pub fn enqueue_task(
@@ -2537,7 +2838,6 @@ pub fn enqueue_task(
             kernel: &Kernel,
             wait_list: Option<&ClWaitList>, 
             new_event: Option<&mut ClEventPtrNew>,
-            kernel_name: Option<&str>
         ) -> OclResult<()> 
 {
     let (wait_list_len, wait_list_ptr, new_event_ptr) = 
@@ -2550,25 +2850,110 @@ pub fn enqueue_task(
             wait_list_ptr,
             new_event_ptr,
     ) };
-    errcode_try("clEnqueueTask", kernel_name.unwrap_or(""), errcode)    
+    enqueue_try("clEnqueueTask", command_queue, wait_list_len, new_event_ptr,
+        || format!("kernel={}", get_kernel_name(kernel)), errcode)
 }
 
-/// [UNIMPLEMENTED]
-pub fn enqueue_native_kernel() -> OclResult<()> {
-    // cl_h::clEnqueueNativeKernel(command_queue: cl_command_queue,
-    //                          user_func: extern fn (*mut c_void),
-    //                          args: *mut c_void,
-    //                          cb_args: size_t,
-    //                          num_mem_objects: cl_uint,
-    //                          mem_list: *const cl_mem,
-    //                          args_mem_loc: *const *const c_void,
-    //                          num_events_in_wait_list: cl_uint,
-    //                          event_wait_list: *const cl_event,
-    //                          event: *mut cl_event) -> cl_int;
-    unimplemented!();
+/// Enqueues a native C/C++ function (not compiled using the OpenCL
+/// compiler) to be run by the device (which must support
+/// `EXEC_NATIVE_KERNEL`).
+///
+/// The `args` bytes are copied by the implementation and the copy is passed
+/// to `user_func`. Each entry of `args_mem_loc` must point to a location
+/// within `args` which contains the `cl_mem` handle of the corresponding
+/// entry of `mem_list`. These locations are overwritten in the copy with
+/// pointers to the (host-accessible) contents of each memory object.
+///
+/// ## Safety
+///
+/// `user_func` must correctly interpret `args` and the locations in
+/// `args_mem_loc` must lie within `args` and be aligned for a pointer.
+///
+/// [SDK]: https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueNativeKernel.html
+pub unsafe fn enqueue_native_kernel(
+            command_queue: &CommandQueue,
+            user_func: NativeKernelFn,
+            args: &mut [u8],
+            mem_list: &[&Mem],
+            args_mem_loc: &[*const c_void],
+            wait_list: Option<&ClWaitList>, 
+            new_event: Option<&mut ClEventPtrNew>,
+        ) -> OclResult<()>
+{
+    if mem_list.len() != args_mem_loc.len() {
+        return OclError::err("ocl::core::enqueue_native_kernel: 'mem_list' and 'args_mem_loc' \
+            must be the same length.");
+    }
+
+    let (wait_list_len, wait_list_ptr, new_event_ptr) = 
+        try!(resolve_event_ptrs(wait_list, new_event));
+
+    let mem_ptrs: Vec<cl_mem> = mem_list.iter().map(|m| m.as_ptr()).collect();
+
+    let errcode = cl_h::clEnqueueNativeKernel(
+        command_queue.as_ptr(),
+        Some(user_func),
+        args.as_mut_ptr() as *mut c_void,
+        args.len() as size_t,
+        mem_ptrs.len() as cl_uint,
+        if mem_ptrs.is_empty() { ptr::null() } else { mem_ptrs.as_ptr() },
+        if args_mem_loc.is_empty() { ptr::null() } else { args_mem_loc.as_ptr() },
+        wait_list_len,
+        wait_list_ptr,
+        new_event_ptr,
+    );
+    enqueue_try("clEnqueueNativeKernel", command_queue, wait_list_len, new_event_ptr,
+        || format!("mem_objects={}", mem_list.len()), errcode)
+}
+
+/// Enqueues a closure to be run on the host by the device (which must
+/// support `EXEC_NATIVE_KERNEL`, usually only CPU devices) in order with the
+/// other commands in `command_queue`.
+///
+/// The closure is passed the host-accessible contents of each memory object
+/// in `mem_list` as a byte slice (in the same order). Panics within the
+/// closure are caught and reported rather than propagated.
+///
+/// If the command is never executed (because the queue is released first,
+/// for example) the closure is leaked.
+pub fn enqueue_native<F>(
+            command_queue: &CommandQueue,
+            mem_list: &[&Mem],
+            wait_list: Option<&ClWaitList>, 
+            new_event: Option<&mut ClEventPtrNew>,
+            func: F,
+        ) -> OclResult<()>
+        where F: FnOnce(&mut [&mut [u8]]) + Send + 'static
+{
+    let mut sizes = Vec::with_capacity(mem_list.len());
+    for mem in mem_list {
+        match get_mem_object_info(mem, MemInfo::Size) {
+            MemInfoResult::Size(size) => sizes.push(size),
+            MemInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::core::enqueue_native: Unexpected 'MemInfoResult' \
+                variant."),
+        }
+    }
+
+    callbacks::register_once(func, |user_data| {
+        let mut words: Vec<usize> = Vec::with_capacity(2 + mem_list.len() * 2);
+        words.push(user_data as usize);
+        words.push(mem_list.len());
+        words.extend(mem_list.iter().map(|m| unsafe { m.as_ptr() } as usize));
+        words.extend(sizes);
+
+        let args_mem_loc: Vec<*const c_void> = (0..mem_list.len())
+            .map(|i| &words[2 + i] as *const usize as *const c_void)
+            .collect();
+
+        let args = unsafe { slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8,
+            words.len() * mem::size_of::<usize>()) };
+
+        unsafe { enqueue_native_kernel(command_queue, callbacks::native_trampoline::<F>, args,
+            mem_list, &args_mem_loc, wait_list, new_event) }
+    })
 }
 
-/// [UNTESTED]
 /// Enqueues a marker command which waits for either a list of events to
 /// complete, or all previously enqueued commands to complete.
 ///
@@ -2588,10 +2973,11 @@ pub fn enqueue_marker_with_wait_list(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueMarkerWithWaitList", "", errcode)
+    enqueue_try("clEnqueueMarkerWithWaitList", command_queue, wait_list_len, new_event_ptr,
+        || format!("wait_list_len={}", wait_list_len),
+        errcode)
 }
 
-/// [UNTESTED]
 /// A synchronization point that enqueues a barrier operation.
 ///
 /// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clEnqueueBarrierWithWaitList.html)
@@ -2610,7 +2996,141 @@ pub fn enqueue_barrier_with_wait_list(
         wait_list_ptr,
         new_event_ptr,
     ) };
-    errcode_try("clEnqueueBarrierWithWaitList", "", errcode)
+    enqueue_try("clEnqueueBarrierWithWaitList", command_queue, wait_list_len, new_event_ptr,
+        || format!("wait_list_len={}", wait_list_len),
+        errcode)
+}
+
+/// Enqueues a marker command which completes once all previously enqueued
+/// commands have completed.
+///
+/// An event is always created by the implementation; if `new_event` is
+/// `None` it is released immediately.
+///
+/// Deprecated as of OpenCL 1.2. See `::enqueue_marker_with_wait_list` and
+/// `::enqueue_marker_compat`.
+///
+/// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.1/docs/man/xhtml/clEnqueueMarker.html)
+pub fn enqueue_marker(
+            command_queue: &CommandQueue,
+            new_event: Option<&mut ClEventPtrNew>,
+        ) -> OclResult<()> 
+{
+    let mut tmp_event = unsafe { Event::null() };
+    let new_event_ptr = match new_event {
+        Some(ne) => try!(ne.ptr_mut_ptr_new()),
+        None => try!(tmp_event.ptr_mut_ptr_new()),
+    };
+
+    let errcode = unsafe { cl_h::clEnqueueMarker(
+        command_queue.as_ptr(),
+        new_event_ptr,
+    ) };
+    enqueue_try("clEnqueueMarker", command_queue, 0, new_event_ptr,
+        || String::new(), errcode)
+}
+
+/// Enqueues a barrier command which prevents any subsequently enqueued
+/// command from starting until all previously enqueued commands have
+/// completed.
+///
+/// Deprecated as of OpenCL 1.2. See `::enqueue_barrier_with_wait_list` and
+/// `::enqueue_barrier_compat`.
+///
+/// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.1/docs/man/xhtml/clEnqueueBarrier.html)
+pub fn enqueue_barrier(command_queue: &CommandQueue) -> OclResult<()> {
+    let errcode = unsafe { cl_h::clEnqueueBarrier(command_queue.as_ptr()) };
+    enqueue_try("clEnqueueBarrier", command_queue, 0, ptr::null_mut(),
+        || String::new(), errcode)
+}
+
+/// Enqueues a command which prevents any subsequently enqueued command from
+/// starting until each event in `wait_list` has completed.
+///
+/// An empty wait list enqueues nothing.
+///
+/// Deprecated as of OpenCL 1.2. See `::enqueue_barrier_with_wait_list` and
+/// `::enqueue_barrier_compat`.
+///
+/// [SDK Docs](https://www.khronos.org/registry/cl/sdk/1.1/docs/man/xhtml/clEnqueueWaitForEvents.html)
+pub fn enqueue_wait_for_events(
+            command_queue: &CommandQueue,
+            wait_list: &ClWaitList,
+        ) -> OclResult<()> 
+{
+    if wait_list.count() == 0 { return Ok(()); }
+
+    let errcode = unsafe { cl_h::clEnqueueWaitForEvents(
+        command_queue.as_ptr(),
+        wait_list.count(),
+        wait_list.as_ptr_ptr(),
+    ) };
+    enqueue_try("clEnqueueWaitForEvents", command_queue, wait_list.count(), ptr::null_mut(),
+        || format!("num_events={}", wait_list.count()),
+        errcode)
+}
+
+/// Enqueues a marker command using `::enqueue_marker_with_wait_list` when
+/// `platform_version` is 1.2 or later and emulating it with the deprecated
+/// 1.1 functions otherwise.
+///
+/// When emulated, a non-empty `wait_list` is enqueued with
+/// `::enqueue_wait_for_events` before the marker. The marker will then wait
+/// for all previously enqueued commands (rather than only those in
+/// `wait_list`) and commands enqueued afterwards will also wait on
+/// `wait_list`. Ordering is therefore never weaker than requested.
+///
+/// `platform_version` is the `(major, minor)` OpenCL version of the
+/// platform associated with `command_queue` (see `Queue::platform_version`).
+pub fn enqueue_marker_compat(
+            command_queue: &CommandQueue,
+            wait_list: Option<&ClWaitList>, 
+            new_event: Option<&mut ClEventPtrNew>,
+            platform_version: (u32, u32),
+        ) -> OclResult<()> 
+{
+    if platform_version >= (1, 2) {
+        enqueue_marker_with_wait_list(command_queue, wait_list, new_event)
+    } else {
+        if let Some(wl) = wait_list {
+            try!(enqueue_wait_for_events(command_queue, wl));
+        }
+        enqueue_marker(command_queue, new_event)
+    }
+}
+
+/// Enqueues a barrier command using `::enqueue_barrier_with_wait_list` when
+/// `platform_version` is 1.2 or later and emulating it with the deprecated
+/// 1.1 functions otherwise.
+///
+/// When emulated, a non-empty `wait_list` is enqueued with
+/// `::enqueue_wait_for_events` and an empty one with `::enqueue_barrier`. If
+/// `new_event` is specified, a marker (which completes once all previously
+/// enqueued commands, including the barrier, have completed) is enqueued
+/// afterwards to provide it.
+///
+/// `platform_version` is the `(major, minor)` OpenCL version of the
+/// platform associated with `command_queue` (see `Queue::platform_version`).
+pub fn enqueue_barrier_compat(
+            command_queue: &CommandQueue,
+            wait_list: Option<&ClWaitList>, 
+            new_event: Option<&mut ClEventPtrNew>,
+            platform_version: (u32, u32),
+        ) -> OclResult<()> 
+{
+    if platform_version >= (1, 2) {
+        return enqueue_barrier_with_wait_list(command_queue, wait_list, new_event);
+    }
+
+    match wait_list {
+        Some(wl) if wl.count() > 0 => try!(enqueue_wait_for_events(command_queue, wl)),
+        _ => try!(enqueue_barrier(command_queue)),
+    }
+
+    match new_event {
+        Some(ne) => enqueue_marker(command_queue, Some(ne)),
+        None => Ok(()),
+    }
 }
 
 
@@ -2638,8 +3158,8 @@ pub fn enqueue_barrier_with_wait_list(
 /// Returns an error if:
 ///
 /// - `func_name` cannot be converted to a `CString`.
-/// - The specified function does not exist for the implementation.
-/// - 'platform' is not a valid platform.
+/// - The specified function does not exist for the implementation or
+///   'platform' is not a valid platform (an `ExtensionUnavailable` error).
 ///
 // Extension function access
 //
@@ -2669,8 +3189,30 @@ pub unsafe fn get_extension_function_address_for_platform(platform: &PlatformId,
     );
 
     if ext_fn == 0 as *mut c_void { 
-        OclError::err("The specified function does not exist for the implementation or 'platform' \
-            is not a valid platform.")
+        Err(OclError::ExtensionUnavailable(func_name.to_owned()))
+    } else {
+        Ok(ext_fn)
+    }
+}
+
+/// [DEPRICATED 1.1] Returns the address of the extension function named by
+/// `func_name` without regard to platform.
+///
+/// Use `get_extension_function_address_for_platform` on platforms supporting
+/// OpenCL 1.2 or later.
+///
+/// # Errors
+/// 
+/// Returns an `ExtensionUnavailable` error if the function does not exist
+/// for the implementation or an error if `func_name` cannot be converted
+/// to a `CString`.
+pub unsafe fn get_extension_function_address(func_name: &str) -> OclResult<*mut c_void> {
+    let func_name_c = try!(CString::new(func_name));
+
+    let ext_fn = cl_h::clGetExtensionFunctionAddress(func_name_c.as_ptr());
+
+    if ext_fn == 0 as *mut c_void { 
+        Err(OclError::ExtensionUnavailable(func_name.to_owned()))
     } else {
         Ok(ext_fn)
     }
@@ -2684,38 +3226,124 @@ pub unsafe fn get_extension_function_address_for_platform(platform: &PlatformId,
 // MANY OF THESE NEED TO BE MORPHED INTO THE MORE GENERAL VERSIONS AND MOVED UP
 
 
-/// Returns the default platform if set by an environment variable or config
-/// file.
+/// Environment variables which select the default platform by index, in
+/// order of precedence (`OCL_DEFAULT_PLATFORM_IDX` is the older name).
+const PLATFORM_INDEX_VARS: &'static [&'static str] = &["OCL_PLATFORM_INDEX",
+    "OCL_DEFAULT_PLATFORM_IDX"];
+
+/// Environment variable which selects a single default device by index among
+/// those of the default device type.
+const DEVICE_INDEX_VARS: &'static [&'static str] = &["OCL_DEVICE_INDEX"];
+
+/// Environment variables which select the default device type, in order of
+/// precedence (`OCL_DEFAULT_DEVICE_TYPE` is the older name).
+const DEVICE_TYPE_VARS: &'static [&'static str] = &["OCL_DEVICE_TYPE",
+    "OCL_DEFAULT_DEVICE_TYPE"];
+
+/// Returns the name and value of the first of `vars` which is set and not
+/// empty.
+fn selection_var(vars: &[&'static str]) -> Option<(&'static str, String)> {
+    vars.iter().filter_map(|&var| match env::var(var) {
+        Ok(ref val) if !val.trim().is_empty() => Some((var, val.trim().to_owned())),
+        _ => None,
+    }).next()
+}
+
+/// Formats a list of names as `[0] 'first', [1] 'second'`.
+fn indexed_names(names: &[String]) -> String {
+    if names.is_empty() { return "(none)".to_owned(); }
+    names.iter().enumerate().map(|(idx, name)| format!("[{}] '{}'", idx, name))
+        .collect::<Vec<_>>().join(", ")
+}
+
+fn platform_names(platforms: &[PlatformId]) -> Vec<String> {
+    platforms.iter().map(|p| get_platform_info(Some(p.clone()), PlatformInfo::Name).to_string())
+        .collect()
+}
+
+fn device_names(devices: &[DeviceId]) -> Vec<String> {
+    devices.iter().map(|d| get_device_info(d, DeviceInfo::Name).to_string()).collect()
+}
+
+/// Parses `val`, the value of the index variable `var`, and checks it
+/// against `names`, the `kind`s (platforms or devices) available.
+fn selection_index(var: &str, val: &str, kind: &str, names: &[String]) -> OclResult<usize> {
+    match val.parse::<usize>() {
+        Ok(idx) if idx < names.len() => Ok(idx),
+        Ok(idx) => OclError::err(format!("The environment variable '{}' selects {} index [{}], \
+            which is out of range. Available {}s: {}.", var, kind, idx, kind, indexed_names(names))),
+        Err(_) => OclError::err(format!("The environment variable '{}' ('{}') is not a valid {} \
+            index. Available {}s: {}.", var, val, kind, kind, indexed_names(names))),
+    }
+}
+
+/// Returns the default platform index as set by the `OCL_PLATFORM_INDEX` (or
+/// older `OCL_DEFAULT_PLATFORM_IDX`) environment variable, or zero if unset
+/// or invalid.
+///
+/// Use `default_platform` to have an invalid value reported.
 pub fn default_platform_idx() -> usize {
-    match env::var("OCL_DEFAULT_PLATFORM_IDX") {
-        Ok(s) => s.parse::<usize>().unwrap_or(0),
-        Err(_) => 0,
+    match selection_var(PLATFORM_INDEX_VARS) {
+        Some((_, val)) => val.parse::<usize>().unwrap_or(0),
+        None => 0,
     }
 }
 
-/// Returns the default or first platform.
-pub fn default_platform() -> OclResult<PlatformId> {
-    let platform_list = try!(get_platform_ids());
+/// Returns the default platform and a description of why it was chosen.
+///
+/// Returns `Error::NoPlatforms`, describing the installation (see
+/// `util::diagnostics`), if there are none.
+fn select_default_platform() -> OclResult<(PlatformId, String)> {
+    let platform_list = match get_platform_ids() {
+        Err(ref err) if err.is_status(cl_h::Status::CL_PLATFORM_NOT_FOUND_KHR) => {
+            return Err(OclError::NoPlatforms(util::diagnostics()));
+        },
+        result => try!(result),
+    };
 
     if platform_list.len() == 0 {
-        OclError::err("No platforms found!")
-    } else {
-        let default_platform_idx = default_platform_idx();
-        if default_platform_idx > platform_list.len() - 1 {
-            OclError::err(format!("The default platform set by the environment variable \
-                'OCL_DEFAULT_PLATFORM_IDX' has an index which is out of range \
-                (index: [{}], max: [{}]).", default_platform_idx, platform_list.len() - 1))
-        } else {
-            Ok(platform_list[default_platform_idx].clone())
-        }
+        return Err(OclError::NoPlatforms(util::diagnostics()));
+    }
+
+    match selection_var(PLATFORM_INDEX_VARS) {
+        Some((var, val)) => {
+            let names = platform_names(&platform_list);
+            let idx = try!(selection_index(var, &val, "platform", &names));
+            Ok((platform_list[idx].clone(), format!("[{}] '{}' (selected by {}={})",
+                idx, names[idx], var, val)))
+        },
+        None => {
+            let name = get_platform_info(Some(platform_list[0].clone()), PlatformInfo::Name);
+            Ok((platform_list[0].clone(), format!("[0] '{}' (first available; {} not set)",
+                name, PLATFORM_INDEX_VARS[0])))
+        },
     }
 }
 
-/// Returns the default device type bitflags as specified by environment
+/// Returns the platform used when none is specified.
+///
+/// This is the first platform unless one is selected by index with the
+/// `OCL_PLATFORM_INDEX` (or older `OCL_DEFAULT_PLATFORM_IDX`) environment
+/// variable. An invalid index is an error listing the available platforms.
+pub fn default_platform() -> OclResult<PlatformId> {
+    select_default_platform().map(|(platform, _)| platform)
+}
+
+/// Returns every platform or, if one is selected by the `OCL_PLATFORM_INDEX`
+/// (or older `OCL_DEFAULT_PLATFORM_IDX`) environment variable, only that one.
+pub fn selected_platforms() -> OclResult<Vec<PlatformId>> {
+    match selection_var(PLATFORM_INDEX_VARS) {
+        Some(_) => default_platform().map(|platform| vec![platform]),
+        None => get_platform_ids(),
+    }
+}
+
+/// Returns the default device type bitflags as specified by the
+/// `OCL_DEVICE_TYPE` (or older `OCL_DEFAULT_DEVICE_TYPE`) environment
 /// variable or `DEVICE_TYPE_ALL`.
 pub fn default_device_type() -> OclResult<DeviceType> {
-    match env::var("OCL_DEFAULT_DEVICE_TYPE") {
-        Ok(ref s) => match s.trim() {
+    match selection_var(DEVICE_TYPE_VARS) {
+        Some((var, ref s)) => match s.to_uppercase().as_str() {
             "DEFAULT" => Ok(core::DEVICE_TYPE_DEFAULT),
             "CPU" => Ok(core::DEVICE_TYPE_CPU),
             "GPU" => Ok(core::DEVICE_TYPE_GPU),
@@ -2723,11 +3351,105 @@ pub fn default_device_type() -> OclResult<DeviceType> {
             "CUSTOM" => Ok(core::DEVICE_TYPE_CUSTOM),
             "ALL" => Ok(core::DEVICE_TYPE_ALL),
             _ => OclError::err(format!("The default device type set by the environment variable \
-                'OCL_DEFAULT_DEVICE_TYPE': ('{}') is invalid. Valid types are: 'DEFAULT', 'CPU', \
-                'GPU', 'ACCELERATOR', 'CUSTOM', and 'ALL'.", s)),
+                '{}': ('{}') is invalid. Valid types are: 'DEFAULT', 'CPU', \
+                'GPU', 'ACCELERATOR', 'CUSTOM', and 'ALL'.", var, s)),
+        },
+        None => Ok(core::DEVICE_TYPE_ALL),
+    }
+}
+
+/// Returns the default devices on `platform` and a description of why they
+/// were chosen.
+fn select_default_devices(platform: &PlatformId) -> OclResult<(Vec<DeviceId>, String)> {
+    let device_type = try!(default_device_type());
+    let type_desc = match selection_var(DEVICE_TYPE_VARS) {
+        Some((var, val)) => format!("{:?}, selected by {}={}", device_type, var, val),
+        None => format!("any type; {} not set", DEVICE_TYPE_VARS[0]),
+    };
+
+    let devices = match get_device_ids(platform, Some(device_type), None) {
+        Ok(ref devices) if !devices.is_empty() => devices.clone(),
+        result => {
+            let all = try!(get_device_ids(platform, Some(core::DEVICE_TYPE_ALL), None));
+            let cause = match result {
+                Err(err) => format!(" ({})", err.status().map(|s| format!("{:?}", s))
+                    .unwrap_or_else(|| err.to_string())),
+                Ok(_) => String::new(),
+            };
+            return OclError::err(format!("No devices ({}) found on the platform{}. Available \
+                devices: {}.", type_desc, cause, indexed_names(&device_names(&all))));
+        },
+    };
+
+    match selection_var(DEVICE_INDEX_VARS) {
+        Some((var, val)) => {
+            let names = device_names(&devices);
+            let idx = try!(selection_index(var, &val, "device", &names));
+            Ok((vec![devices[idx]], format!("[{}] '{}' (selected by {}={} among devices of {})",
+                idx, names[idx], var, val, type_desc)))
+        },
+        None => {
+            let desc = format!("{} (all devices of {}; {} not set)",
+                indexed_names(&device_names(&devices)), type_desc, DEVICE_INDEX_VARS[0]);
+            Ok((devices, desc))
+        },
+    }
+}
+
+/// Returns the devices on `platform` used when none are specified.
+///
+/// These are the devices of the default device type (see
+/// `default_device_type`) or, if the `OCL_DEVICE_INDEX` environment variable
+/// is set, only the one at that index among them. An invalid index, or a
+/// device type of which there are no devices, is an error listing the
+/// available devices.
+pub fn default_devices(platform: &PlatformId) -> OclResult<Vec<DeviceId>> {
+    select_default_devices(platform).map(|(devices, _)| devices)
+}
+
+/// Returns a description of the platform and devices used when none are
+/// specified, along with the environment variables responsible and what is
+/// available, suitable for inclusion in a bug report.
+///
+/// Selections made explicitly (such as with `ProQueBuilder::platform` or
+/// `ContextBuilder::devices`) always take precedence over the default
+/// selection and its environment variables.
+pub fn selection_report() -> String {
+    let mut report = String::from("OpenCL default selection:\n");
+
+    match select_default_platform() {
+        Ok((platform, platform_desc)) => {
+            report.push_str(&format!("Platform: {}\n", platform_desc));
+
+            match select_default_devices(&platform) {
+                Ok((_, devices_desc)) => report.push_str(&format!("Devices: {}\n", devices_desc)),
+                Err(err) => report.push_str(&format!("Devices: Error: {}\n", err)),
+            }
+        },
+        Err(err) => report.push_str(&format!("Platform: Error: {}\n", err)),
+    }
+
+    match get_platform_ids() {
+        Ok(platforms) => {
+            report.push_str(&format!("Available platforms: {}\n",
+                indexed_names(&platform_names(&platforms))));
+
+            for (idx, platform) in platforms.iter().enumerate() {
+                let devices = get_device_ids(platform, Some(core::DEVICE_TYPE_ALL), None)
+                    .map(|devices| indexed_names(&device_names(&devices)))
+                    .unwrap_or_else(|err| format!("Error: {}", err));
+                report.push_str(&format!("Available devices on platform [{}]: {}\n", idx, devices));
+            }
         },
-        Err(_) => Ok(core::DEVICE_TYPE_ALL),
+        Err(err) => report.push_str(&format!("Available platforms: Error: {}\n", err)),
+    }
+
+    report.push_str("Environment:");
+    for var in PLATFORM_INDEX_VARS.iter().chain(DEVICE_INDEX_VARS).chain(DEVICE_TYPE_VARS) {
+        report.push_str(&format!(" {}={}", var, env::var(var).unwrap_or_else(|_| "(unset)".to_owned())));
     }
+
+    report
 }
 
 
@@ -2772,14 +3494,11 @@ pub fn create_build_program<D: ClDeviceIdPtr + Debug>(
 //     max_work_group_size
 // }
 
-#[allow(dead_code)]
 /// Blocks until an event is complete.
+///
+/// See `::wait_for_events`.
 pub fn wait_for_event(event: &Event) -> OclResult<()> {
-    let errcode = unsafe {
-        let event_ptr = *event.as_ptr_ref();
-        cl_h::clWaitForEvents(1, &event_ptr)
-    };
-    errcode_try("clWaitForEvents", "", errcode)
+    wait_for_events(slice::from_ref(event))
 }
 
 /// Returns the status of `event`.
@@ -2795,7 +3514,8 @@ pub fn get_event_status<'e, E: ClEventRef<'e>>(event: &'e E) -> OclResult<Comman
             ptr::null_mut(),
         )
     };
-    try!(errcode_try("clGetEventInfo", "", errcode));
+    try!(errcode_try("clGetEventInfo", || format!("event={:?}", unsafe { *event.as_ptr_ref() }),
+        errcode));
 
     CommandExecutionStatus::from_i32(status_int).ok_or(OclError::new("Error converting \
         'clGetEventInfo' status output."))
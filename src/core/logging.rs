@@ -0,0 +1,80 @@
+//! Log records for enqueued commands and program builds (`logging`
+//! feature).
+//!
+//! Commands are logged at the trace level with the target
+//! `ocl::core::enqueue` and builds at the debug level with the target
+//! `ocl::core::build`. Each record is a single line of `key=value` fields
+//! following the name of the OpenCL function, including the raw handles
+//! involved (queue, device, program, and new event) so that records can be
+//! correlated with one another and with other tools.
+//!
+//! Nothing is queried or formatted unless the record's level is enabled for
+//! its target.
+
+use std::time::Duration;
+use std::fmt::Debug;
+use num::FromPrimitive;
+use log::Level;
+use cl_h::{cl_int, cl_uint, cl_event, Status};
+use core::{self, CommandQueue, CommandQueueInfo, CommandQueueInfoResult, Program,
+    ProgramBuildInfo, ClDeviceIdPtr};
+use util;
+
+/// The target of enqueue records.
+pub const ENQUEUE: &'static str = "ocl::core::enqueue";
+
+/// The target of build records.
+pub const BUILD: &'static str = "ocl::core::build";
+
+/// Returns true if enqueue records are enabled.
+pub fn enqueue_enabled() -> bool {
+    log_enabled!(target: ENQUEUE, Level::Trace)
+}
+
+/// Returns true if build records are enabled.
+pub fn build_enabled() -> bool {
+    log_enabled!(target: BUILD, Level::Debug)
+}
+
+/// Formats `errcode` as the name of its status.
+fn fmt_status(errcode: cl_int) -> String {
+    match Status::from_i32(errcode) {
+        Some(status) => format!("{:?}", status),
+        None => errcode.to_string(),
+    }
+}
+
+/// Logs a command enqueued by `cl_fn_name` on `command_queue`, where `info`
+/// holds the command-specific fields (sizes, offsets, etc.).
+///
+/// `new_event_ptr` must be null or point to the new event of the command.
+pub unsafe fn enqueue(cl_fn_name: &'static str, command_queue: &CommandQueue, info: &str,
+        wait_list_len: cl_uint, new_event_ptr: *mut cl_event, errcode: cl_int)
+{
+    let device = match core::get_command_queue_info(command_queue, CommandQueueInfo::Device) {
+        CommandQueueInfoResult::Device(device) => format!("{:?}", device.as_ptr()),
+        _ => String::from("<unknown>"),
+    };
+    let event = if new_event_ptr.is_null() { None } else { Some(*new_event_ptr) };
+
+    trace!(target: ENQUEUE, "{} queue={:?} device={}{}{} wait_list_len={} event={:?} status={}",
+        cl_fn_name, command_queue.as_ptr(), device, if info.is_empty() { "" } else { " " }, info,
+        wait_list_len, event, fmt_status(errcode));
+}
+
+/// Logs a build of `program` by `clBuildProgram`, which took `duration`
+/// (only the time taken to begin the build when `is_async`).
+pub fn build<D: ClDeviceIdPtr + Debug>(program: &Program, devices: &[D], options: &str,
+        is_async: bool, duration: Duration, errcode: cl_int)
+{
+    let log_len: usize = devices.iter().map(|device| {
+        match core::get_program_build_info_bytes(program, device, ProgramBuildInfo::BuildLog) {
+            Ok(bytes) => util::string_from_info_bytes(bytes).trim().len(),
+            Err(_) => 0,
+        }
+    }).sum();
+
+    debug!(target: BUILD, "clBuildProgram program={:?} device_count={} options={:?} async={} \
+        duration={:?} log_len={} status={}", unsafe { program.as_ptr() }, devices.len(), options,
+        is_async, duration, log_len, fmt_status(errcode));
+}
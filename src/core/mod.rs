@@ -86,6 +86,8 @@
 
 mod functions;
 mod types;
+pub(crate) mod callbacks;
+#[cfg(feature = "logging")] mod logging;
 
 use std::fmt::{Display, Debug};
 // use std::num::{Zero, One};
@@ -95,9 +97,10 @@ use num::{NumCast, FromPrimitive, ToPrimitive};
 use rand::distributions::range::SampleRange;
 use cl_h;
 
-pub use self::functions::{ get_platform_ids, get_platform_info,
-    get_device_ids, get_device_info, create_sub_devices, retain_device,
-    release_device, create_context, create_context_from_type, retain_context,
+pub use self::functions::{ is_opencl_available, get_platform_ids, get_platform_info, get_platform_info_bytes,
+    get_device_ids, get_device_info, get_device_info_bytes, create_sub_devices, retain_device,
+    release_device, get_device_and_host_timer, get_host_timer, create_context,
+    create_context_notify, create_context_from_type, retain_context,
     release_context, get_context_info, create_command_queue, retain_command_queue,
     release_command_queue, get_command_queue_info, create_buffer,
     create_sub_buffer, create_image, retain_mem_object, release_mem_object,
@@ -105,29 +108,42 @@ pub use self::functions::{ get_platform_ids, get_platform_info,
     set_mem_object_destructor_callback, create_sampler, retain_sampler,
     release_sampler, get_sampler_info, create_program_with_source,
     create_program_with_binary, create_program_with_built_in_kernels,
-    retain_program, release_program, build_program, compile_program, link_program,
-    create_build_program, get_program_info,
-    get_program_build_info, create_kernel, create_kernels_in_program,
+    retain_program, release_program, build_program, build_program_notify, compile_program, link_program,
+    unload_platform_compiler, unload_compiler,
+    create_build_program, get_program_info, get_program_binaries,
+    get_program_build_info, get_program_build_info_bytes, create_kernel, create_kernels_in_program,
     retain_kernel, release_kernel, set_kernel_arg, get_kernel_info,
-    get_kernel_arg_info, get_kernel_work_group_info, wait_for_events,
+    get_kernel_info_bytes, get_kernel_arg_info, get_kernel_work_group_info, wait_for_events,
+    wait_for_event_list,
     get_event_info, create_user_event, retain_event, release_event,
-    set_user_event_status, set_event_callback, get_event_profiling_info, flush,
+    set_user_event_status, set_event_callback, set_event_callback_fn, get_event_profiling_info, flush,
     finish, enqueue_read_buffer, enqueue_read_buffer_rect, enqueue_write_buffer,
     enqueue_write_buffer_rect, enqueue_copy_buffer, enqueue_fill_buffer,
     enqueue_copy_buffer_rect, enqueue_read_image, enqueue_write_image,
     enqueue_fill_image, enqueue_copy_image, enqueue_copy_image_to_buffer,
     enqueue_copy_buffer_to_image, enqueue_map_buffer, enqueue_map_image,
     enqueue_unmap_mem_object, enqueue_migrate_mem_objects, enqueue_kernel,
-    enqueue_task, enqueue_native_kernel, enqueue_marker_with_wait_list,
-    enqueue_barrier_with_wait_list, get_extension_function_address_for_platform,
+    enqueue_kernel_dims, enqueue_task, enqueue_native_kernel, enqueue_native, enqueue_marker_with_wait_list,
+    enqueue_barrier_with_wait_list, enqueue_marker, enqueue_barrier, enqueue_wait_for_events,
+    enqueue_marker_compat, enqueue_barrier_compat, get_extension_function_address_for_platform,
+    get_extension_function_address,
     wait_for_event, get_event_status, default_platform_idx,
-    program_build_err, verify_context, default_platform, default_device_type };
+    program_build_err, verify_context, default_platform, default_device_type, default_devices,
+    selected_platforms, selection_report };
+
+pub use self::callbacks::callback_panic_count;
 
 pub use self::types::abs::{ClEventPtrNew, ClEventRef, ClPlatformIdPtr, ClDeviceIdPtr, EventRefWrapper,
     PlatformId, DeviceId, Context, CommandQueue, Mem, Program, Kernel, Event, EventList, Sampler,
     ClWaitList};
 
-pub use self::types::structs::{ContextProperties, ImageFormat, ImageDescriptor, BufferRegion};
+pub use self::types::structs::{ContextProperties, ImageFormat, FormatMismatch, ImageDescriptor,
+    BufferRegion, WorkSizes};
+pub(crate) use self::types::structs::InfoBytes;
+#[cfg(test)] pub(crate) use self::types::structs::INFO_BYTES_INLINE_MAX;
+#[cfg(test)] pub(crate) use self::types::structs::set_info_bytes_inline_threshold;
+#[cfg(test)] pub(crate) use self::functions::device_info_query_count;
+#[cfg(test)] pub(crate) use self::functions::{get_info_bytes, INFO_QUERY_ATTEMPTS, INFO_RESULT_SIZE_MAX};
 
 pub use self::types::enums::{KernelArg, PlatformInfoResult, DeviceInfoResult,
     ContextInfoResult, ContextProperty, CommandQueueInfoResult, MemInfoResult,
@@ -135,6 +151,18 @@ pub use self::types::enums::{KernelArg, PlatformInfoResult, DeviceInfoResult,
     KernelInfoResult, KernelArgInfoResult, KernelWorkGroupInfoResult,
     EventInfoResult, ProfilingInfoResult};
 
+pub use self::types::vectors::{AsClSlice,
+    ClChar2, ClChar3, ClChar4, ClChar8, ClChar16,
+    ClUchar2, ClUchar3, ClUchar4, ClUchar8, ClUchar16,
+    ClShort2, ClShort3, ClShort4, ClShort8, ClShort16,
+    ClUshort2, ClUshort3, ClUshort4, ClUshort8, ClUshort16,
+    ClInt2, ClInt3, ClInt4, ClInt8, ClInt16,
+    ClUint2, ClUint3, ClUint4, ClUint8, ClUint16,
+    ClLong2, ClLong3, ClLong4, ClLong8, ClLong16,
+    ClUlong2, ClUlong3, ClUlong4, ClUlong8, ClUlong16,
+    ClFloat2, ClFloat3, ClFloat4, ClFloat8, ClFloat16,
+    ClDouble2, ClDouble3, ClDouble4, ClDouble8, ClDouble16};
+
 //=============================================================================
 //================================ CONSTANTS ==================================
 //=============================================================================
@@ -152,25 +180,210 @@ pub type EventCallbackFn = extern "C" fn (cl_h::cl_event, i32, *mut libc::c_void
 pub type CreateContextCallbackFn = extern "C" fn (*const libc::c_char, *const libc::c_void, 
     libc::size_t, *mut libc::c_void);
 pub type BuildProgramCallbackFn = extern "C" fn (*mut libc::c_void, *mut libc::c_void);
+pub type NativeKernelFn = extern "C" fn (*mut libc::c_void);
 pub type UserDataPtr = *mut libc::c_void;
 
 //=============================================================================
 //================================== TRAITS ===================================
 //=============================================================================
 
-/// [POSSIBLY INCOMPLETE] A number compatible with OpenCL.
+/// A primitive type usable within OpenCL kernels as a buffer element, image
+/// pixel component, or kernel argument.
+///
+/// Includes the Rust equivalents of the OpenCL scalar types as well as the
+/// vector types (`ClFloat4`, `ClUint2`, etc.).
+///
+/// Implementations must have the same size and layout as the type they
+/// correspond to on the device because their bytes are copied to and from
+/// device memory as-is, hence `unsafe`. Every bit pattern the device may
+/// write must also be a valid value of the type.
+///
+/// ## Unsupported Types
+///
+/// The following are deliberately not `OclPrm` types:
+///
+/// * `bool`: Has invalid bit patterns and OpenCL `bool` may not be used in
+///   kernel arguments or memory objects. Use `u8` (or `i32`) instead.
+/// * `char`: Has invalid bit patterns. Use `u32` (or `u8` for ASCII).
+///
+/// Using either as an element or argument type fails to compile:
+///
+/// ```compile_fail
+/// # use ocl::Buffer;
+/// fn bools(buffer: Buffer<bool>) {}
+/// ```
+///
+/// ```compile_fail
+/// # use ocl::Kernel;
+/// fn arg(kernel: Kernel) -> Kernel { kernel.arg_scl('a') }
+/// ```
+///
+/// ## Deprecated Types
+///
+/// `usize` and `isize` are still `OclPrm` types but are deprecated and will
+/// be removed in a future version. Their size depends on the host and does
+/// not necessarily match the device `size_t` (a 64-bit host with a 32-bit
+/// device, for example). Migrate to `u32`/`u64` (`i32`/`i64`) matching the
+/// width used in the kernel, replacing `Buffer<usize>` with `Buffer<u64>`
+/// and `.arg_scl(len)` with `.arg_scl(len as u32)`, for example.
+///
+/// Note that the compiler emits **no** deprecation warning for these types
+/// (trait implementations can not be marked `#[deprecated]`), so existing
+/// uses must be found by searching for them.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a valid OpenCL element or argument type (`OclPrm`)",
+    note = "`bool` and `char` are not supported: use `u8`, `i32` or `u32` as appropriate, \
+        or `impl_ocl_prm!(unsafe ..)` for your own `#[repr(C)]` types"
+)]
+pub unsafe trait OclPrm: Copy + Clone + Default + PartialEq + Debug + Send + Sync + 'static {
+    /// Returns a copy of this value with the byte order of each of its
+    /// scalar components reversed, or `None` if this is not supported.
+    ///
+    /// Used when transferring data to or from a device with a different
+    /// endianness than the host (see `BufferBuilder::swap_bytes`).
+    /// Implemented for all scalar and vector types. Returns `None` for types
+    /// implemented with `impl_ocl_prm!`.
+    fn swap_bytes(self) -> Option<Self> {
+        None
+    }
+}
+
+macro_rules! impl_ocl_prm_int {
+    ($($t:ty),+) => {
+        $(
+            unsafe impl OclPrm for $t {
+                fn swap_bytes(self) -> Option<$t> {
+                    Some(<$t>::swap_bytes(self))
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_ocl_prm_float {
+    ($($t:ty),+) => {
+        $(
+            unsafe impl OclPrm for $t {
+                fn swap_bytes(self) -> Option<$t> {
+                    Some(<$t>::from_bits(self.to_bits().swap_bytes()))
+                }
+            }
+        )+
+    };
+}
+
+impl_ocl_prm_int!(u8, i8, u16, i16, u32, i32, u64, i64);
+
+// Deprecated (documented only, as the compiler emits no warning for trait
+// impls): the size of `usize` and `isize` depends on the host and does not
+// necessarily match the device `size_t`. Use `u32`/`u64` (`i32`/`i64`)
+// instead. See the `OclPrm` docs.
+impl_ocl_prm_int!(usize, isize);
+impl_ocl_prm_float!(f32, f64);
+
+/// Half-precision floats (OpenCL `half`). Usable as buffer and image
+/// elements on any device but only as kernel arguments on devices supporting
+/// `cl_khr_fp16`.
+#[cfg(feature = "f16")]
+impl_ocl_prm_float!(::half::f16);
+
+#[cfg(feature = "complex")]
+macro_rules! impl_ocl_prm_complex {
+    ($($t:ty),+) => {
+        $(
+            unsafe impl OclPrm for ::num::complex::Complex<$t> {
+                fn swap_bytes(self) -> Option<::num::complex::Complex<$t>> {
+                    Some(::num::complex::Complex::new(
+                        <$t>::from_bits(self.re.to_bits().swap_bytes()),
+                        <$t>::from_bits(self.im.to_bits().swap_bytes())))
+                }
+            }
+        )+
+    };
+}
+
+/// Complex numbers (re-exported as `ocl::Complex`), laid out as the real
+/// component followed by the imaginary and therefore equivalent to OpenCL
+/// `float2` and `double2`. `Complex<f64>` requires `cl_khr_fp64` as does
+/// `f64`.
+#[cfg(feature = "complex")]
+impl_ocl_prm_complex!(f32, f64);
+
+/// Implements `OclPrm` for one or more user-defined structs so that they can
+/// be used as buffer elements and kernel arguments.
+///
+/// Each type must be `#[repr(C)]` and implement `Copy`, `Clone`, `Default`,
+/// `PartialEq`, and `Debug`. An expected size in bytes may be given after
+/// `=>`, causing compilation to fail if the size of the type differs:
+///
+/// ```text
+/// #[derive(Clone, Copy, Debug, Default, PartialEq)]
+/// #[repr(C)]
+/// struct Particle { pos: ClFloat4, vel: ClFloat4, mass: f32, _pad: [f32; 3] }
+///
+/// impl_ocl_prm!(unsafe Particle => 48);
+/// ```
+///
+/// ## Safety
+///
+/// `OclPrm` is an `unsafe` trait (see its docs) and the invocation must be
+/// prefixed with `unsafe` to assert that each type upholds its contract.
+/// Omitting it fails to compile:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate ocl;
+/// #[derive(Clone, Copy, Debug, Default, PartialEq)]
+/// #[repr(C)]
+/// struct Pair { a: u32, b: u32 }
+///
+/// impl_ocl_prm!(Pair);
+/// # fn main() {}
+/// ```
+///
+/// ## Matching the OpenCL struct layout
+///
+/// The layout of the Rust struct must match the layout of the corresponding
+/// struct in the kernel source exactly. In particular, OpenCL vector types
+/// are aligned to their own size (a `float4` member is 16-byte aligned and a
+/// `float3` occupies 16 bytes), so use the types in `ocl::vectors` for vector
+/// members or add explicit padding fields wherever the device compiler would
+/// insert padding. Padding at the end of the struct must also be accounted
+/// for so that the size of the array stride is identical on the host and
+/// device. Checking `sizeof` within a kernel is an easy way to confirm.
+///
+#[macro_export]
+macro_rules! impl_ocl_prm {
+    (unsafe $($t:ty),+ $(,)*) => {
+        $(
+            unsafe impl $crate::core::OclPrm for $t {}
+
+            const _: () = assert!(::std::mem::size_of::<$t>() > 0,
+                "impl_ocl_prm!: zero-sized types are not valid OpenCL types");
+        )+
+    };
+    (unsafe $($t:ty => $size:expr),+ $(,)*) => {
+        $(
+            impl_ocl_prm!(unsafe $t);
+
+            const _: () = assert!(::std::mem::size_of::<$t>() == $size,
+                "impl_ocl_prm!: size of type does not match the expected size");
+        )+
+    };
+}
+
+/// [POSSIBLY INCOMPLETE] A scalar number compatible with OpenCL.
 /// 
 /// TODO: Clean up and evaluate.
 ///
 /// TODO: Ensure various types of image color data are encompassed by this 
 /// definition.
-pub trait OclPrm: 
-    Copy + Clone + PartialOrd + NumCast + Default + /*Zero + One +*/ Add + Sub + Mul + Div + 
-    Rem + Display + Debug + FromPrimitive + ToPrimitive + SampleRange {}
+pub trait OclScl: 
+    OclPrm + PartialOrd + NumCast + /*Zero + One +*/ Add + Sub + Mul + Div + 
+    Rem + Display + FromPrimitive + ToPrimitive + SampleRange {}
 
-impl<T> OclPrm for T where T: 
-    Copy + Clone + PartialOrd + NumCast + Default + /*Zero + One +*/ Add + Sub + Mul + Div + 
-    Rem + Display + Debug + FromPrimitive + ToPrimitive + SampleRange {}
+impl<T> OclScl for T where T: 
+    OclPrm + PartialOrd + NumCast + /*Zero + One +*/ Add + Sub + Mul + Div + 
+    Rem + Display + FromPrimitive + ToPrimitive + SampleRange {}
 
 // impl<'a, T> OclPrm for &'a T where T: 
 //     Copy + Clone + PartialOrd + NumCast + Default + Zero + One + Add + Sub + Mul + Div + Rem + Display + Debug + FromPrimitive + ToPrimitive + SampleRange {}
@@ -314,17 +527,6 @@ bitflags! {
 }
 
 
-bitflags! {
-	/// cl_program_binary_type
-    pub flags ProgramBinaryType: u64 {
-		const PROGRAM_BINARY_TYPE_NONE = 0x0,
-		const PROGRAM_BINARY_TYPE_COMPILED_OBJECT = 0x1,
-		const PROGRAM_BINARY_TYPE_LIBRARY = 0x2,
-		const PROGRAM_BINARY_TYPE_EXECUTABLE = 0x4,
-    }
-}
-
-
 bitflags! {
 	/// cl_kernel_arg_type_qualifer 
     pub flags KernelArgTypeQualifier: u64 {
@@ -344,6 +546,7 @@ enum_from_primitive! {
     /// Specifies the number of channels and the channel layout i.e. the memory layout in which channels are stored in the image. Valid values are described in the table below. (from SDK)
     #[repr(C)]
     #[derive(Clone, Copy, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub enum ImageChannelOrder {
         R = cl_h::CL_R as isize,
         A = cl_h::CL_A as isize,
@@ -373,6 +576,7 @@ enum_from_primitive! {
     /// Describes the size of the channel data type. The number of bits per element determined by the image_channel_data_type and image_channel_order must be a power of two. The list of supported values is described in the table below. (from SDK)
     #[repr(C)]
     #[derive(Clone, Copy, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub enum ImageChannelDataType {
         // Each channel component is a normalized signed 8-bit integer value:
         SnormInt8 = cl_h::CL_SNORM_INT8 as isize,
@@ -633,6 +837,7 @@ enum_from_primitive! {
 	/// cl_mem_object_type
     #[repr(C)]
     #[derive(Clone, Copy, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub enum MemObjectType {
         Buffer = cl_h::CL_MEM_OBJECT_BUFFER as isize,
         Image2d = cl_h::CL_MEM_OBJECT_IMAGE2D as isize,
@@ -657,7 +862,7 @@ enum_from_primitive! {
         MapCount = cl_h::CL_MEM_MAP_COUNT as isize,
         ReferenceCount = cl_h::CL_MEM_REFERENCE_COUNT as isize,
         Context = cl_h::CL_MEM_CONTEXT as isize,
-        AssociatedMemobject = cl_h::CL_MEM_ASSOCIATED_MEMOBJECT as isize,
+        AssociatedMemObject = cl_h::CL_MEM_ASSOCIATED_MEMOBJECT as isize,
         Offset = cl_h::CL_MEM_OFFSET as isize,
     }
 }
@@ -767,6 +972,19 @@ enum_from_primitive! {
 }
 
 
+enum_from_primitive! {
+	/// cl_program_binary_type
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum ProgramBinaryType {
+        None = cl_h::CL_PROGRAM_BINARY_TYPE_NONE as isize,
+        CompiledObject = cl_h::CL_PROGRAM_BINARY_TYPE_COMPILED_OBJECT as isize,
+        Library = cl_h::CL_PROGRAM_BINARY_TYPE_LIBRARY as isize,
+        Executable = cl_h::CL_PROGRAM_BINARY_TYPE_EXECUTABLE as isize,
+    }
+}
+
+
 enum_from_primitive! {
 	/// cl_kernel_info
     #[repr(C)]
@@ -34,9 +34,31 @@
 //! cases to provide complete protection against segfaults due to
 //! dereferencing old pointers particularly on certain *ahem* platforms.
 //!
+//! ### Thread Safety
 //!
+//! "All OpenCL API calls are thread-safe except `clSetKernelArg`", which is
+//! safe "so long as concurrent calls operate on different `cl_kernel`
+//! objects" (OpenCL 1.2 specification, Appendix A.2). Every wrapper except
+//! `Kernel` is therefore `Send` and `Sync`. `Kernel` is neither because
+//! clones share the same `cl_kernel` (see its documentation).
 //!
-//! 
+//! These decisions are checked at compile time at the end of this module.
+//!
+//! ### Raw Pointers
+//!
+//! For interop with other OpenCL wrappers or hand-written FFI, each
+//! reference counted wrapper (and `EventList`) can be converted to and from
+//! raw pointers:
+//!
+//! * `as_ptr` returns the raw pointer without incrementing its reference
+//!   count. It is only valid for as long as the wrapper (or another
+//!   reference to the object) is; retain it before storing it any longer.
+//! * `from_raw_retained` increments the reference count. The caller keeps
+//!   its own reference and remains responsible for releasing it.
+//! * `from_raw_unretained` takes over the caller's reference, which is
+//!   released when the wrapper is dropped. The caller must not release it.
+//!
+//! Both constructors return an error for a null pointer.
 //!
 //! [SDK]: https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/abstractDataTypes.html
 
@@ -47,7 +69,7 @@ use std::marker::Sized;
 use libc;
 use cl_h::{cl_platform_id, cl_device_id,  cl_context, cl_command_queue, cl_mem, cl_program, 
 	cl_kernel, cl_event, cl_sampler};
-use core::{self, CommandExecutionStatus};
+use core::{self, CommandExecutionStatus, MemInfo};
 use error::{Result as OclResult, Error as OclError};
 use util;
 
@@ -155,6 +177,7 @@ impl PlatformId {
 }
 
 unsafe impl ClPlatformIdPtr for PlatformId {}
+// Platform IDs are immutable and never released:
 unsafe impl Sync for PlatformId {}
 unsafe impl Send for PlatformId {}
 
@@ -189,6 +212,8 @@ impl DeviceId {
 }
 
 unsafe impl ClDeviceIdPtr for DeviceId {}
+// Root device IDs are immutable and never released (sub-devices are
+// unimplemented):
 unsafe impl Sync for DeviceId {}
 unsafe impl Send for DeviceId {}
 
@@ -217,6 +242,7 @@ impl Context {
 	}
 }
 
+// Reference counting and every call using a context are thread safe (A.2):
 unsafe impl Sync for Context {}
 unsafe impl Send for Context {}
 
@@ -276,13 +302,14 @@ impl AsRef<CommandQueue> for CommandQueue {
     }
 }
 
+// Commands may be enqueued from any thread; the queue itself serializes
+// them (A.2):
 unsafe impl Sync for CommandQueue {}
 unsafe impl Send for CommandQueue {}
 
 
 
 /// cl_mem
-#[derive(Debug)]
 pub struct Mem(cl_mem);
 
 impl Mem {
@@ -315,6 +342,34 @@ impl Drop for Mem {
 	}
 }
 
+/// Shows the raw pointer. The alternate form (`{:#?}`) also shows the type,
+/// flags, size, offset, map count, reference count, and associated memory
+/// object, queried from the implementation (a `Mem` always holds a reference
+/// so the object is still valid).
+impl ::std::fmt::Debug for Mem {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+		if !f.alternate() {
+			return f.debug_tuple("Mem").field(&self.0).finish();
+		}
+
+		let info = |request| core::get_mem_object_info(self, request);
+
+		f.debug_struct("Mem")
+			.field("ptr", &self.0)
+			.field("type", &format_args!("{}", info(MemInfo::Type)))
+			.field("flags", &format_args!("{}", info(MemInfo::Flags)))
+			.field("size", &format_args!("{}", info(MemInfo::Size)))
+			.field("offset", &format_args!("{}", info(MemInfo::Offset)))
+			.field("map_count", &format_args!("{}", info(MemInfo::MapCount)))
+			.field("reference_count", &format_args!("{}", info(MemInfo::ReferenceCount)))
+			.field("associated_mem_object", &format_args!("{}",
+				info(MemInfo::AssociatedMemObject)))
+			.finish()
+	}
+}
+
+// Memory object calls are thread safe (A.2). Synchronizing access to the
+// contents of a memory object is up to its users, as on the device:
 unsafe impl Sync for Mem {}
 unsafe impl Send for Mem {}
 
@@ -350,6 +405,7 @@ impl Drop for Program {
 	}
 }
 
+// Building and querying programs is thread safe (A.2):
 unsafe impl Sync for Program {}
 unsafe impl Send for Program {}
 
@@ -365,12 +421,15 @@ unsafe impl Send for Program {}
 ///
 /// ### Thread Safety
 ///
-/// Not thread safe: do not implement `Send` or `Sync`.
+/// Neither `Send` nor `Sync`: `clSetKernelArg` may not be called
+/// concurrently on the same `cl_kernel` (OpenCL 1.2 specification, Appendix
+/// A.2) and clones share the same `cl_kernel`, so sending either of a pair
+/// of clones to another thread would allow exactly that.
 ///
-/// It's possible to do with some work but it's not worth the bother, just 
-/// make another identical kernel in the other thread and call it good.
+/// The higher level `ocl::Kernel`, which owns its `cl_kernel`, is `Send`.
+/// To use a kernel from several threads at once, create an identical kernel
+/// in each thread.
 ///
-/// 
 #[derive(Debug)]
 pub struct Kernel(cl_kernel);
 
@@ -403,7 +462,12 @@ impl Drop for Kernel {
 
 
 /// cl_event
+///
+/// Has the same layout as a raw `cl_event`, allowing a slice of events to be
+/// passed directly as a wait list (see the `ClWaitList` implementations for
+/// `&[Event]` and `Vec<Event>`) without copying.
 #[derive(Debug)]
+#[repr(C)]
 pub struct Event(cl_event);
 
 impl Event {
@@ -430,11 +494,11 @@ impl Event {
 		Event(0 as cl_event)
 	}
 
-	// /// Returns a pointer, do not store it unless you will manage its
-	// /// associated reference count carefully (as does `EventList`).
-	// pub unsafe fn as_ptr(&self) -> cl_event {
-	// 	self.0
-	// }
+	/// Returns a pointer, do not store it unless you will manage its
+	/// associated reference count carefully (as does `EventList`).
+	pub unsafe fn as_ptr(&self) -> cl_event {
+		self.0
+	}
 
 	/// Returns an immutable reference to a pointer, do not deref and store it unless 
 	/// you will manage its associated reference count carefully.
@@ -482,6 +546,26 @@ unsafe impl ClWaitList for Event {
 	}
 }
 
+unsafe impl<'a> ClWaitList for &'a [Event] {
+	unsafe fn as_ptr_ptr(&self) -> *const cl_event {
+		if self.is_empty() { ptr::null() } else { self.as_ptr() as *const cl_event }
+	}
+
+	fn count(&self) -> u32 {
+		self.len() as u32
+	}
+}
+
+unsafe impl ClWaitList for Vec<Event> {
+	unsafe fn as_ptr_ptr(&self) -> *const cl_event {
+		ClWaitList::as_ptr_ptr(&self.as_slice())
+	}
+
+	fn count(&self) -> u32 {
+		self.len() as u32
+	}
+}
+
 impl Clone for Event {
 	fn clone(&self) -> Event {
 		unsafe { core::retain_event(self).expect("core::Event::clone"); }
@@ -498,6 +582,8 @@ impl Drop for Event {
 }
 
 // unsafe impl EventPtr for Event {}
+// Waiting on, querying, and setting callbacks for events are thread safe
+// (A.2):
 unsafe impl Sync for Event {}
 unsafe impl Send for Event {}
 
@@ -554,6 +640,49 @@ impl EventList {
         self.event_ptrs.len() as u32
     }
 
+	/// Returns a pointer to the first of the list's `cl_event`s, which are
+	/// contiguous, or a null pointer if the list is empty. Valid until the
+	/// list is next modified. Retain any event stored beyond the life of the
+	/// list (see the module documentation).
+	pub unsafe fn as_ptr(&self) -> *const cl_event {
+		ClWaitList::as_ptr_ptr(self)
+	}
+
+	/// Returns a new list containing each of `ptrs`, incrementing the
+	/// reference count of each. The caller keeps its own references.
+	///
+	/// Returns an error, having retained none of them, if any is null.
+	pub unsafe fn from_raw_retained(ptrs: &[cl_event]) -> OclResult<EventList> {
+		if ptrs.iter().any(|ptr| ptr.is_null()) {
+			return OclError::err("core::EventList::from_raw_retained: Null event pointer.");
+		}
+
+		let mut list = EventList::new();
+
+		for ptr in ptrs.iter() {
+			// Events already retained are released when `list` drops:
+			try!(core::retain_event(&EventRefWrapper(ptr, 1)));
+			list.event_ptrs.push(*ptr);
+		}
+
+		Ok(list)
+	}
+
+	/// Returns a new list containing each of `ptrs`, taking over the
+	/// caller's reference to each, which is released when the list is
+	/// dropped (or the event cleared). The caller must not release them.
+	///
+	/// Returns an error, having taken over none of them, if any is null.
+	pub unsafe fn from_raw_unretained(ptrs: &[cl_event]) -> OclResult<EventList> {
+		if ptrs.iter().any(|ptr| ptr.is_null()) {
+			return OclError::err("core::EventList::from_raw_unretained: Null event pointer.");
+		}
+
+		let mut list = EventList::new();
+		list.event_ptrs.extend_from_slice(ptrs);
+		Ok(list)
+	}
+
 	/// Returns an immutable reference to a pointer, do not deref and store it unless 
 	/// you will manage its associated reference count carefully.
 	pub unsafe fn as_ptr_ref(&self) -> &cl_event {
@@ -695,6 +824,7 @@ impl AsRef<EventList> for EventList {
     }
 }
 
+// Only the (owned) list is mutated, which requires `&mut self`:
 unsafe impl Sync for EventList {}
 unsafe impl Send for EventList {}
 
@@ -730,5 +860,84 @@ impl Drop for Sampler {
 	}
 }
 
+// Samplers are immutable once created (A.2):
 unsafe impl Sync for Sampler {}
 unsafe impl Send for Sampler {}
+
+
+//=============================================================================
+//============================== RAW CONSTRUCTORS =============================
+//=============================================================================
+
+/// Implements `from_raw_retained` and `from_raw_unretained` (see "Raw
+/// Pointers" in the module documentation).
+macro_rules! impl_from_raw {
+	($ty:ident, $ptr_ty:ident, $retain:path) => {
+		impl $ty {
+			/// Returns a new wrapper around `ptr`, incrementing its reference
+			/// count. The caller keeps its own reference.
+			///
+			/// Returns an error if `ptr` is null or can not be retained.
+			pub unsafe fn from_raw_retained(ptr: $ptr_ty) -> OclResult<$ty> {
+				if ptr.is_null() {
+					return OclError::err(concat!("core::", stringify!($ty),
+						"::from_raw_retained: Null pointer."));
+				}
+
+				let new_core = $ty(ptr);
+
+				match $retain(&new_core) {
+					Ok(()) => Ok(new_core),
+					Err(err) => {
+						// Not retained, so must not be released:
+						mem::forget(new_core);
+						Err(err)
+					},
+				}
+			}
+
+			/// Returns a new wrapper around `ptr`, taking over the caller's
+			/// reference, which is released when the wrapper is dropped. The
+			/// caller must not release it.
+			///
+			/// Returns an error if `ptr` is null.
+			pub unsafe fn from_raw_unretained(ptr: $ptr_ty) -> OclResult<$ty> {
+				if ptr.is_null() {
+					return OclError::err(concat!("core::", stringify!($ty),
+						"::from_raw_unretained: Null pointer."));
+				}
+
+				Ok($ty(ptr))
+			}
+		}
+	};
+}
+
+impl_from_raw!(Context, cl_context, core::retain_context);
+impl_from_raw!(CommandQueue, cl_command_queue, core::retain_command_queue);
+impl_from_raw!(Mem, cl_mem, core::retain_mem_object);
+impl_from_raw!(Program, cl_program, core::retain_program);
+impl_from_raw!(Kernel, cl_kernel, core::retain_kernel);
+impl_from_raw!(Event, cl_event, core::retain_event);
+impl_from_raw!(Sampler, cl_sampler, core::retain_sampler);
+
+
+//=============================================================================
+//============================ THREAD SAFETY CHECKS ===========================
+//=============================================================================
+
+// See "Thread Safety" in the module documentation:
+const _: fn() = || {
+    util::assert_send::<PlatformId>(); util::assert_sync::<PlatformId>();
+    util::assert_send::<DeviceId>(); util::assert_sync::<DeviceId>();
+    util::assert_send::<Context>(); util::assert_sync::<Context>();
+    util::assert_send::<CommandQueue>(); util::assert_sync::<CommandQueue>();
+    util::assert_send::<Mem>(); util::assert_sync::<Mem>();
+    util::assert_send::<Program>(); util::assert_sync::<Program>();
+    util::assert_send::<Event>(); util::assert_sync::<Event>();
+    util::assert_send::<EventList>(); util::assert_sync::<EventList>();
+    util::assert_send::<Sampler>(); util::assert_sync::<Sampler>();
+
+    let _ = <Kernel as util::NotSend<_>>::check;
+    let _ = <Kernel as util::NotSync<_>>::check;
+};
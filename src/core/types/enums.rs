@@ -35,13 +35,16 @@ use std;
 // use std::error::Error;
 use std::convert::Into;
 use libc::{size_t, c_void};
+use num::FromPrimitive;
 use util;
 use core::{OclPrm, CommandQueueProperties, PlatformId, PlatformInfo, DeviceId, DeviceInfo, 
     ContextInfo, Context, CommandQueueInfo, Mem, MemInfo, Sampler, SamplerInfo, ProgramInfo, 
     ProgramBuildInfo, KernelInfo, KernelArgInfo, KernelWorkGroupInfo, ImageInfo, ImageFormat, 
-    EventInfo, ProfilingInfo};
+    EventInfo, ProfilingInfo, BuildStatus, DeviceExecCapabilities, MemObjectType, MemFlags,
+    AddressingMode, FilterMode, CommandQueue, CommandType, CommandExecutionStatus,
+    Program, ProgramBinaryType, InfoBytes};
 use error::{Result as OclResult, Error as OclError};
-// use cl_h;
+use cl_h::{cl_image_format, cl_platform_id};
 
 
 
@@ -173,7 +176,12 @@ pub enum PlatformInfoResult {
 }
 
 impl PlatformInfoResult {
-    pub fn from_bytes(request: PlatformInfo, result: OclResult<Vec<u8>>) 
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: PlatformInfo, result: OclResult<Vec<u8>>) -> PlatformInfoResult {
+        PlatformInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: PlatformInfo, result: OclResult<InfoBytes>) 
             -> PlatformInfoResult
     {
         // match result_string {
@@ -196,7 +204,7 @@ impl PlatformInfoResult {
 
         match result {
             Ok(result) => { 
-                let string = String::from_utf8_lossy(&result[0..result.len()-1]).into_owned();
+                let string = util::string_from_info_bytes(result.into_vec());
 
                 match request {
                     PlatformInfo::Profile => PlatformInfoResult::Profile(string),
@@ -253,9 +261,9 @@ pub enum DeviceInfoResult {
     Type(TemporaryPlaceholderType),
     VendorId(TemporaryPlaceholderType),
     MaxComputeUnits(TemporaryPlaceholderType),
-    MaxWorkItemDimensions(TemporaryPlaceholderType),
+    MaxWorkItemDimensions(u32),
     MaxWorkGroupSize(usize),
-    MaxWorkItemSizes(TemporaryPlaceholderType),
+    MaxWorkItemSizes(Vec<usize>),
     PreferredVectorWidthChar(TemporaryPlaceholderType),
     PreferredVectorWidthShort(TemporaryPlaceholderType),
     PreferredVectorWidthInt(TemporaryPlaceholderType),
@@ -288,22 +296,22 @@ pub enum DeviceInfoResult {
     LocalMemSize(TemporaryPlaceholderType),
     ErrorCorrectionSupport(TemporaryPlaceholderType),
     ProfilingTimerResolution(TemporaryPlaceholderType),
-    EndianLittle(TemporaryPlaceholderType),
+    EndianLittle(bool),
     Available(TemporaryPlaceholderType),
     CompilerAvailable(TemporaryPlaceholderType),
-    ExecutionCapabilities(TemporaryPlaceholderType),
+    ExecutionCapabilities(DeviceExecCapabilities),
     QueueProperties(TemporaryPlaceholderType),
-    Name(TemporaryPlaceholderType),
+    Name(String),
     Vendor(TemporaryPlaceholderType),
     DriverVersion(TemporaryPlaceholderType),
     Profile(TemporaryPlaceholderType),
-    Version(TemporaryPlaceholderType),
-    Extensions(TemporaryPlaceholderType),
-    Platform(TemporaryPlaceholderType),
+    Version(String),
+    Extensions(String),
+    Platform(PlatformId),
     DoubleFpConfig(TemporaryPlaceholderType),
     HalfFpConfig(TemporaryPlaceholderType),
     PreferredVectorWidthHalf(TemporaryPlaceholderType),
-    HostUnifiedMemory(TemporaryPlaceholderType),
+    HostUnifiedMemory(bool),
     NativeVectorWidthChar(TemporaryPlaceholderType),
     NativeVectorWidthShort(TemporaryPlaceholderType),
     NativeVectorWidthInt(TemporaryPlaceholderType),
@@ -330,19 +338,70 @@ pub enum DeviceInfoResult {
 }
 
 impl DeviceInfoResult {
-    pub fn from_bytes(request: DeviceInfo, result: OclResult<Vec<u8>>) 
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: DeviceInfo, result: OclResult<Vec<u8>>) -> DeviceInfoResult {
+        DeviceInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: DeviceInfo, result: OclResult<InfoBytes>) 
             -> DeviceInfoResult
     {
         match result {
             Ok(result) => { match request {
             DeviceInfo::MaxWorkGroupSize => {
-                let r0 = unsafe { util::bytes_to::<usize>(&result) };
-                let size = unsafe { util::bytes_into::<usize>(result) };
-                debug_assert_eq!(r0, size);
-                // println!("\n\nDEVICEINFORESULT::FROM_BYTES(MAXWORKGROUPSIZE): r1: {}, r2: {}", r1, r2);
-                DeviceInfoResult::MaxWorkGroupSize(size)
+                match unsafe { util::bytes_to::<usize>(&result) } {
+                    Ok(size) => DeviceInfoResult::MaxWorkGroupSize(size),
+                    Err(err) => DeviceInfoResult::Error(Box::new(err)),
+                }
+            },
+            DeviceInfo::MaxWorkItemDimensions => {
+                match unsafe { util::bytes_to::<u32>(&result) } {
+                    Ok(dims) => DeviceInfoResult::MaxWorkItemDimensions(dims),
+                    Err(err) => DeviceInfoResult::Error(Box::new(err)),
+                }
+            },
+            DeviceInfo::Name => {
+                DeviceInfoResult::Name(util::string_from_info_bytes(result.into_vec()))
             },
-            _ => DeviceInfoResult::TemporaryPlaceholderVariant(result),
+            DeviceInfo::MaxWorkItemSizes => {
+                match unsafe { util::bytes_to_vec::<usize>(&result) } {
+                    Ok(sizes) => DeviceInfoResult::MaxWorkItemSizes(sizes),
+                    Err(err) => DeviceInfoResult::Error(Box::new(err)),
+                }
+            },
+            DeviceInfo::EndianLittle => {
+                match unsafe { util::bytes_to::<u32>(&result) } {
+                    Ok(little) => DeviceInfoResult::EndianLittle(little != 0),
+                    Err(err) => DeviceInfoResult::Error(Box::new(err)),
+                }
+            },
+            DeviceInfo::HostUnifiedMemory => {
+                match unsafe { util::bytes_to::<u32>(&result) } {
+                    Ok(unified) => DeviceInfoResult::HostUnifiedMemory(unified != 0),
+                    Err(err) => DeviceInfoResult::Error(Box::new(err)),
+                }
+            },
+            DeviceInfo::ExecutionCapabilities => {
+                match unsafe { util::bytes_to::<u64>(&result) } {
+                    Ok(caps) => DeviceInfoResult::ExecutionCapabilities(
+                        DeviceExecCapabilities::from_bits_truncate(caps)),
+                    Err(err) => DeviceInfoResult::Error(Box::new(err)),
+                }
+            },
+            DeviceInfo::Version => {
+                DeviceInfoResult::Version(util::string_from_info_bytes(result.into_vec()))
+            },
+            DeviceInfo::Extensions => {
+                DeviceInfoResult::Extensions(util::string_from_info_bytes(result.into_vec()))
+            },
+            DeviceInfo::Platform => {
+                match unsafe { util::bytes_to::<cl_platform_id>(&result) } {
+                    Ok(ptr) => DeviceInfoResult::Platform(unsafe {
+                        PlatformId::from_fresh_ptr(ptr) }),
+                    Err(err) => DeviceInfoResult::Error(Box::new(err)),
+                }
+            },
+            _ => DeviceInfoResult::TemporaryPlaceholderVariant(result.into_vec()),
         } }
             Err(err) => DeviceInfoResult::Error(Box::new(err)),
         }
@@ -367,6 +426,15 @@ impl std::fmt::Display for DeviceInfoResult {
                 write!(f, "{}", to_string_retarded(v))
             },
             &DeviceInfoResult::MaxWorkGroupSize(size) => write!(f, "{}", size),
+            &DeviceInfoResult::MaxWorkItemDimensions(dims) => write!(f, "{}", dims),
+            &DeviceInfoResult::MaxWorkItemSizes(ref sizes) => write!(f, "{:?}", sizes),
+            &DeviceInfoResult::Name(ref name) => write!(f, "{}", name),
+            &DeviceInfoResult::EndianLittle(little) => write!(f, "{}", little),
+            &DeviceInfoResult::HostUnifiedMemory(unified) => write!(f, "{}", unified),
+            &DeviceInfoResult::ExecutionCapabilities(caps) => write!(f, "{:?}", caps),
+            &DeviceInfoResult::Version(ref version) => write!(f, "{}", version),
+            &DeviceInfoResult::Extensions(ref extensions) => write!(f, "{}", extensions),
+            &DeviceInfoResult::Platform(ref platform) => write!(f, "{:?}", platform),
             &DeviceInfoResult::Error(ref err) => write!(f, "{}", err.status_code()),
             r @ _ => panic!("DeviceInfoResult: Converting '{:?}' to string not yet implemented.", r),
         }
@@ -393,26 +461,40 @@ pub enum ContextInfoResult {
 }
 
 impl ContextInfoResult {
+    /// Returns a new info result from the raw bytes of a query result.
     pub fn from_bytes(request: ContextInfo, result: OclResult<Vec<u8>>) -> ContextInfoResult {
+        ContextInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: ContextInfo, result: OclResult<InfoBytes>)
+            -> ContextInfoResult
+    {
         match result {
             Ok(result) => { match request {
                 ContextInfo::ReferenceCount => {
-                    ContextInfoResult::ReferenceCount(util::bytes_to_u32(&result))
+                    match unsafe { util::bytes_to::<u32>(&result) } {
+                        Ok(count) => ContextInfoResult::ReferenceCount(count),
+                        Err(err) => ContextInfoResult::Error(Box::new(err)),
+                    }
                 },
                 ContextInfo::Devices => {
-                    ContextInfoResult::Devices(
-                        unsafe { util::bytes_into_vec::<DeviceId>(result) }
-                    )
+                    match unsafe { util::bytes_to_vec::<DeviceId>(&result) } {
+                        Ok(devices) => ContextInfoResult::Devices(devices),
+                        Err(err) => ContextInfoResult::Error(Box::new(err)),
+                    }
                 },
                 ContextInfo::Properties => {
                     // unsafe { ContextInfoResult::Properties(
                     //     ContextInfoOrPropertiesPointerType.from_u32(util::bytes_into::
                     //         <cl_h::cl_context_properties>(result))
                     // ) }
-                    ContextInfoResult::Properties(result)
+                    ContextInfoResult::Properties(result.into_vec())
                 },
                 ContextInfo::NumDevices => {
-                    ContextInfoResult::NumDevices(util::bytes_to_u32(&result))
+                    match unsafe { util::bytes_to::<u32>(&result) } {
+                        Ok(num) => ContextInfoResult::NumDevices(num),
+                        Err(err) => ContextInfoResult::Error(Box::new(err)),
+                    }
                 },
             } }
             Err(err) => ContextInfoResult::Error(Box::new(err)),
@@ -446,9 +528,12 @@ impl Into<String> for ContextInfoResult {
 
 
 
-/// [UNSTABLE][INCOMPLETE] A command queue info result.
+/// [UNSTABLE] A command queue info result.
+///
+/// `Context` contains a retained handle. The `ReferenceCount` is stale as
+/// soon as it is returned (other threads or the implementation itself may
+/// retain or release the queue at any time) and is useful for debugging only.
 pub enum CommandQueueInfoResult {
-    TemporaryPlaceholderVariant(Vec<u8>),
     Context(Context),
     Device(DeviceId),
     ReferenceCount(u32),
@@ -457,31 +542,41 @@ pub enum CommandQueueInfoResult {
 }
 
 impl CommandQueueInfoResult {
-    pub fn from_bytes(request: CommandQueueInfo, result: OclResult<Vec<u8>>) 
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: CommandQueueInfo, result: OclResult<Vec<u8>>)
+            -> CommandQueueInfoResult
+    {
+        CommandQueueInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: CommandQueueInfo, result: OclResult<InfoBytes>) 
             -> CommandQueueInfoResult 
     {
-        match result {
-            Ok(result) => { match request {
-                _ => CommandQueueInfoResult::TemporaryPlaceholderVariant(result),
-                // CommandQueueInfo::ReferenceCount => {
-                //     CommandQueueInfoResult::ReferenceCount(util::bytes_to_u32(&result))
-                // },
-                // CommandQueueInfo::Devices => {
-                //     CommandQueueInfoResult::Devices(
-                //         unsafe { util::bytes_into_vec::<DeviceId>(result) }
-                //     )
-                // },
-                // CommandQueueInfo::Properties => {
-                //     // unsafe { CommandQueueInfoResult::Properties(
-                //     //     CommandQueueInfoOrPropertiesPointerType.from_u32(util::bytes_into::
-                //     //         <cl_h::cl_context_properties>(result))
-                //     // ) }
-                //     CommandQueueInfoResult::Properties(result)
-                // },
-                // CommandQueueInfo::NumDevices => {
-                //     CommandQueueInfoResult::NumDevices(util::bytes_to_u32(&result))
-                // },
-            } }
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => return CommandQueueInfoResult::Error(Box::new(err)),
+        };
+
+        let info_result = unsafe { match request {
+            CommandQueueInfo::Context => {
+                util::bytes_to::<*mut c_void>(&result)
+                    .and_then(|ptr| Context::from_raw_retained(ptr))
+                    .map(CommandQueueInfoResult::Context)
+            },
+            CommandQueueInfo::Device => {
+                util::bytes_to::<DeviceId>(&result).map(CommandQueueInfoResult::Device)
+            },
+            CommandQueueInfo::ReferenceCount => {
+                util::bytes_to::<u32>(&result).map(CommandQueueInfoResult::ReferenceCount)
+            },
+            CommandQueueInfo::Properties => {
+                util::bytes_to::<u64>(&result).map(|props| CommandQueueInfoResult::Properties(
+                    CommandQueueProperties::from_bits_truncate(props)))
+            },
+        } };
+
+        match info_result {
+            Ok(res) => res,
             Err(err) => CommandQueueInfoResult::Error(Box::new(err)),
         }
     }
@@ -496,11 +591,11 @@ impl std::fmt::Debug for CommandQueueInfoResult {
 impl std::fmt::Display for CommandQueueInfoResult {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            &CommandQueueInfoResult::TemporaryPlaceholderVariant(ref v) => {
-               write!(f, "{}", to_string_retarded(v))
-            },
+            &CommandQueueInfoResult::Context(ref context) => write!(f, "{:?}", context),
+            &CommandQueueInfoResult::Device(ref device) => write!(f, "{:?}", device),
+            &CommandQueueInfoResult::ReferenceCount(count) => write!(f, "{}", count),
+            &CommandQueueInfoResult::Properties(props) => write!(f, "{:?}", props),
             &CommandQueueInfoResult::Error(ref err) => write!(f, "{}", err.status_code()),
-            _ => panic!("CommandQueueInfoResult: Converting this variant to string not yet implemented."),
         }
     }
 }
@@ -513,30 +608,79 @@ impl Into<String> for CommandQueueInfoResult {
 
 
 
-/// [UNSTABLE][INCOMPLETE] A mem info result.
+/// [UNSTABLE] A mem info result.
+///
+/// The `Context` and `AssociatedMemObject` variants contain retained handles
+/// (dropping them is always safe). `AssociatedMemObject` and `HostPtr` are
+/// `None` unless the memory object is a sub-buffer or was created with a host
+/// pointer, respectively.
 pub enum MemInfoResult {
-    TemporaryPlaceholderVariant(Vec<u8>),
-    Type(TemporaryPlaceholderType),
-    Flags(TemporaryPlaceholderType),
-    Size(TemporaryPlaceholderType),
-    HostPtr(TemporaryPlaceholderType),
-    MapCount(TemporaryPlaceholderType),
-    ReferenceCount(TemporaryPlaceholderType),
-    Context(TemporaryPlaceholderType),
-    AssociatedMemobject(TemporaryPlaceholderType),
-    Offset(TemporaryPlaceholderType),
+    Type(MemObjectType),
+    Flags(MemFlags),
+    Size(usize),
+    HostPtr(Option<*mut c_void>),
+    MapCount(u32),
+    ReferenceCount(u32),
+    Context(Context),
+    AssociatedMemObject(Option<Mem>),
+    Offset(usize),
     Error(Box<OclError>),
 }
 
 
 impl MemInfoResult {
-    pub fn from_bytes(request: MemInfo, result: OclResult<Vec<u8>>) 
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: MemInfo, result: OclResult<Vec<u8>>) -> MemInfoResult {
+        MemInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: MemInfo, result: OclResult<InfoBytes>) 
             -> MemInfoResult 
     {
-        match result {
-            Ok(result) => { match request {
-                _ => MemInfoResult::TemporaryPlaceholderVariant(result),
-            } }
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => return MemInfoResult::Error(Box::new(err)),
+        };
+
+        let info_result = unsafe { match request {
+            MemInfo::Type => {
+                util::bytes_to::<u32>(&result).and_then(|ty| {
+                    MemObjectType::from_u32(ty).ok_or(OclError::new(format!("Error converting \
+                        '{}' to a 'MemObjectType'.", ty)))
+                }).map(MemInfoResult::Type)
+            },
+            MemInfo::Flags => {
+                util::bytes_to::<u64>(&result)
+                    .map(|flags| MemInfoResult::Flags(MemFlags::from_bits_truncate(flags)))
+            },
+            MemInfo::Size => util::bytes_to::<usize>(&result).map(MemInfoResult::Size),
+            MemInfo::HostPtr => {
+                util::bytes_to::<*mut c_void>(&result)
+                    .map(|ptr| MemInfoResult::HostPtr(if ptr.is_null() { None } else { Some(ptr) }))
+            },
+            MemInfo::MapCount => util::bytes_to::<u32>(&result).map(MemInfoResult::MapCount),
+            MemInfo::ReferenceCount => {
+                util::bytes_to::<u32>(&result).map(MemInfoResult::ReferenceCount)
+            },
+            MemInfo::Context => {
+                util::bytes_to::<*mut c_void>(&result)
+                    .and_then(|ptr| Context::from_raw_retained(ptr))
+                    .map(MemInfoResult::Context)
+            },
+            MemInfo::AssociatedMemObject => {
+                util::bytes_to::<*mut c_void>(&result).and_then(|ptr| {
+                    if ptr.is_null() {
+                        Ok(MemInfoResult::AssociatedMemObject(None))
+                    } else {
+                        Mem::from_raw_retained(ptr).map(|m| MemInfoResult::AssociatedMemObject(Some(m)))
+                    }
+                })
+            },
+            MemInfo::Offset => util::bytes_to::<usize>(&result).map(MemInfoResult::Offset),
+        } };
+
+        match info_result {
+            Ok(res) => res,
             Err(err) => MemInfoResult::Error(Box::new(err)),
         }
     }
@@ -551,11 +695,16 @@ impl std::fmt::Debug for MemInfoResult {
 impl std::fmt::Display for MemInfoResult {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            &MemInfoResult::TemporaryPlaceholderVariant(ref v) => {
-               write!(f, "{}", to_string_retarded(v))
-            },
+            &MemInfoResult::Type(ty) => write!(f, "{:?}", ty),
+            &MemInfoResult::Flags(flags) => write!(f, "{:?}", flags),
+            &MemInfoResult::Size(size) => write!(f, "{}", size),
+            &MemInfoResult::HostPtr(ptr) => write!(f, "{:?}", ptr),
+            &MemInfoResult::MapCount(count) => write!(f, "{}", count),
+            &MemInfoResult::ReferenceCount(count) => write!(f, "{}", count),
+            &MemInfoResult::Context(ref context) => write!(f, "{:?}", context),
+            &MemInfoResult::AssociatedMemObject(ref mem) => write!(f, "{:?}", mem),
+            &MemInfoResult::Offset(offset) => write!(f, "{}", offset),
             &MemInfoResult::Error(ref err) => write!(f, "{}", err.status_code()),
-            _ => panic!("MemInfoResult: Converting this variant to string not yet implemented."),
         }
     }
 }
@@ -568,9 +717,11 @@ impl Into<String> for MemInfoResult {
 
 
 
-/// [UNSTABLE][INCOMPLETE] An image info result.
+/// [UNSTABLE] An image info result.
+///
+/// `Buffer` contains a retained handle and is `None` unless the image is a
+/// 1D image buffer.
 pub enum ImageInfoResult {
-    TemporaryPlaceholderVariant(Vec<u8>),
     Format(ImageFormat),
     ElementSize(usize),
     RowPitch(usize),
@@ -579,24 +730,64 @@ pub enum ImageInfoResult {
     Height(usize),
     Depth(usize),
     ArraySize(usize),
-    Buffer(Mem),
+    Buffer(Option<Mem>),
     NumMipLevels(u32),
     NumSamples(u32),
     Error(Box<OclError>),
 }
 
 impl ImageInfoResult {
-    // TODO: IMPLEMENT THIS PROPERLY.
-    pub fn from_bytes(request: ImageInfo, result: OclResult<Vec<u8>>) -> ImageInfoResult
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: ImageInfo, result: OclResult<Vec<u8>>) -> ImageInfoResult {
+        ImageInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: ImageInfo, result: OclResult<InfoBytes>)
+            -> ImageInfoResult
     {
-        match result {
-            Ok(result) => { match request {
-                ImageInfo::ElementSize => {
-                    let size = unsafe { util::bytes_into::<usize>(result) };
-                    ImageInfoResult::ElementSize(size)
-                },
-                _ => ImageInfoResult::TemporaryPlaceholderVariant(result),
-            } }
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => return ImageInfoResult::Error(Box::new(err)),
+        };
+
+        let info_result = unsafe { match request {
+            ImageInfo::Format => {
+                util::bytes_to::<cl_image_format>(&result)
+                    .and_then(ImageFormat::from_raw)
+                    .map(ImageInfoResult::Format)
+            },
+            ImageInfo::ElementSize => {
+                util::bytes_to::<usize>(&result).map(ImageInfoResult::ElementSize)
+            },
+            ImageInfo::RowPitch => util::bytes_to::<usize>(&result).map(ImageInfoResult::RowPitch),
+            ImageInfo::SlicePitch => {
+                util::bytes_to::<usize>(&result).map(ImageInfoResult::SlicePitch)
+            },
+            ImageInfo::Width => util::bytes_to::<usize>(&result).map(ImageInfoResult::Width),
+            ImageInfo::Height => util::bytes_to::<usize>(&result).map(ImageInfoResult::Height),
+            ImageInfo::Depth => util::bytes_to::<usize>(&result).map(ImageInfoResult::Depth),
+            ImageInfo::ArraySize => {
+                util::bytes_to::<usize>(&result).map(ImageInfoResult::ArraySize)
+            },
+            ImageInfo::Buffer => {
+                util::bytes_to::<*mut c_void>(&result).and_then(|ptr| {
+                    if ptr.is_null() {
+                        Ok(ImageInfoResult::Buffer(None))
+                    } else {
+                        Mem::from_raw_retained(ptr).map(|m| ImageInfoResult::Buffer(Some(m)))
+                    }
+                })
+            },
+            ImageInfo::NumMipLevels => {
+                util::bytes_to::<u32>(&result).map(ImageInfoResult::NumMipLevels)
+            },
+            ImageInfo::NumSamples => {
+                util::bytes_to::<u32>(&result).map(ImageInfoResult::NumSamples)
+            },
+        } };
+
+        match info_result {
+            Ok(res) => res,
             Err(err) => ImageInfoResult::Error(Box::new(err)),
         }
     }
@@ -611,12 +802,18 @@ impl std::fmt::Debug for ImageInfoResult {
 impl std::fmt::Display for ImageInfoResult {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            &ImageInfoResult::TemporaryPlaceholderVariant(ref v) => {
-               write!(f, "{}", to_string_retarded(v))
-            },
+            &ImageInfoResult::Format(ref format) => write!(f, "{:?}", format),
             &ImageInfoResult::ElementSize(s) => write!(f, "{}", s),
+            &ImageInfoResult::RowPitch(s) => write!(f, "{}", s),
+            &ImageInfoResult::SlicePitch(s) => write!(f, "{}", s),
+            &ImageInfoResult::Width(s) => write!(f, "{}", s),
+            &ImageInfoResult::Height(s) => write!(f, "{}", s),
+            &ImageInfoResult::Depth(s) => write!(f, "{}", s),
+            &ImageInfoResult::ArraySize(s) => write!(f, "{}", s),
+            &ImageInfoResult::Buffer(ref buffer) => write!(f, "{:?}", buffer),
+            &ImageInfoResult::NumMipLevels(s) => write!(f, "{}", s),
+            &ImageInfoResult::NumSamples(s) => write!(f, "{}", s),
             &ImageInfoResult::Error(ref err) => write!(f, "{}", err.status_code()),
-            _ => panic!("ImageInfoResult: Converting this variant to string not yet implemented."),
         }
     }
 }
@@ -628,25 +825,61 @@ impl Into<String> for ImageInfoResult {
 }
 
 
-/// [UNSTABLE][INCOMPLETE] A sampler info result.
+/// [UNSTABLE] A sampler info result.
+///
+/// `Context` contains a retained handle.
 pub enum SamplerInfoResult {
-    TemporaryPlaceholderVariant(Vec<u8>),
-    ReferenceCount(TemporaryPlaceholderType),
-    Context(TemporaryPlaceholderType),
-    NormalizedCoords(TemporaryPlaceholderType),
-    AddressingMode(TemporaryPlaceholderType),
-    FilterMode(TemporaryPlaceholderType),
+    ReferenceCount(u32),
+    Context(Context),
+    NormalizedCoords(bool),
+    AddressingMode(AddressingMode),
+    FilterMode(FilterMode),
     Error(Box<OclError>),
 }
 
 impl SamplerInfoResult {
-    pub fn from_bytes(request: SamplerInfo, result: OclResult<Vec<u8>>) 
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: SamplerInfo, result: OclResult<Vec<u8>>) -> SamplerInfoResult {
+        SamplerInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: SamplerInfo, result: OclResult<InfoBytes>) 
             -> SamplerInfoResult 
     {
-        match result {
-            Ok(result) => { match request {
-                _ => SamplerInfoResult::TemporaryPlaceholderVariant(result),
-            } }
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => return SamplerInfoResult::Error(Box::new(err)),
+        };
+
+        let info_result = unsafe { match request {
+            SamplerInfo::ReferenceCount => {
+                util::bytes_to::<u32>(&result).map(SamplerInfoResult::ReferenceCount)
+            },
+            SamplerInfo::Context => {
+                util::bytes_to::<*mut c_void>(&result)
+                    .and_then(|ptr| Context::from_raw_retained(ptr))
+                    .map(SamplerInfoResult::Context)
+            },
+            SamplerInfo::NormalizedCoords => {
+                util::bytes_to::<u32>(&result)
+                    .map(|norm| SamplerInfoResult::NormalizedCoords(norm != 0))
+            },
+            SamplerInfo::AddressingMode => {
+                util::bytes_to::<u32>(&result).and_then(|mode| {
+                    AddressingMode::from_u32(mode).ok_or(OclError::new(format!("Error \
+                        converting '{}' to an 'AddressingMode'.", mode)))
+                }).map(SamplerInfoResult::AddressingMode)
+            },
+            SamplerInfo::FilterMode => {
+                util::bytes_to::<u32>(&result).and_then(|mode| {
+                    FilterMode::from_u32(mode).ok_or(OclError::new(format!("Error \
+                        converting '{}' to a 'FilterMode'.", mode)))
+                }).map(SamplerInfoResult::FilterMode)
+            },
+        } };
+
+        match info_result {
+            Ok(res) => res,
             Err(err) => SamplerInfoResult::Error(Box::new(err)),
         }
     }
@@ -661,11 +894,12 @@ impl std::fmt::Debug for SamplerInfoResult {
 impl std::fmt::Display for SamplerInfoResult {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            &SamplerInfoResult::TemporaryPlaceholderVariant(ref v) => {
-               write!(f, "{}", to_string_retarded(v))
-            },
+            &SamplerInfoResult::ReferenceCount(count) => write!(f, "{}", count),
+            &SamplerInfoResult::Context(ref context) => write!(f, "{:?}", context),
+            &SamplerInfoResult::NormalizedCoords(norm) => write!(f, "{}", norm),
+            &SamplerInfoResult::AddressingMode(mode) => write!(f, "{:?}", mode),
+            &SamplerInfoResult::FilterMode(mode) => write!(f, "{:?}", mode),
             &SamplerInfoResult::Error(ref err) => write!(f, "{}", err.status_code()),
-            _ => panic!("SamplerInfoResult: Converting this variant to string not yet implemented."),
         }
     }
 }
@@ -681,7 +915,7 @@ impl Into<String> for SamplerInfoResult {
 /// [UNSTABLE][INCOMPLETE] A program info result.
 pub enum ProgramInfoResult {
     TemporaryPlaceholderVariant(Vec<u8>),
-    ReferenceCount(TemporaryPlaceholderType),
+    ReferenceCount(u32),
     Context(TemporaryPlaceholderType),
     NumDevices(TemporaryPlaceholderType),
     Devices(TemporaryPlaceholderType),
@@ -694,12 +928,23 @@ pub enum ProgramInfoResult {
 }
 
 impl ProgramInfoResult {
-    pub fn from_bytes(request: ProgramInfo, result: OclResult<Vec<u8>>) 
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: ProgramInfo, result: OclResult<Vec<u8>>) -> ProgramInfoResult {
+        ProgramInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: ProgramInfo, result: OclResult<InfoBytes>) 
             -> ProgramInfoResult 
     {
         match result {
             Ok(result) => { match request {
-                _ => ProgramInfoResult::TemporaryPlaceholderVariant(result),
+                ProgramInfo::ReferenceCount => {
+                    match unsafe { util::bytes_to::<u32>(&result) } {
+                        Ok(count) => ProgramInfoResult::ReferenceCount(count),
+                        Err(err) => ProgramInfoResult::Error(Box::new(err)),
+                    }
+                },
+                _ => ProgramInfoResult::TemporaryPlaceholderVariant(result.into_vec()),
             } }
             Err(err) => ProgramInfoResult::Error(Box::new(err)),
         }
@@ -718,6 +963,7 @@ impl std::fmt::Display for ProgramInfoResult {
             &ProgramInfoResult::TemporaryPlaceholderVariant(ref v) => {
                write!(f, "{}", to_string_retarded(v))
             },
+            &ProgramInfoResult::ReferenceCount(count) => write!(f, "{}", count),
             &ProgramInfoResult::Error(ref err) => write!(f, "{}", err.status_code()),
             _ => panic!("ProgramInfoResult: Converting this variant to string not yet implemented."),
         }
@@ -731,32 +977,58 @@ impl Into<String> for ProgramInfoResult {
 }
 
 
-/// [UNSTABLE][INCOMPLETE] A program build info result.
+/// [UNSTABLE] A program build info result.
+///
+/// `BinaryType` requires OpenCL 1.2 or later.
 pub enum ProgramBuildInfoResult {
-    TemporaryPlaceholderVariant(Vec<u8>),
-    BuildStatus(TemporaryPlaceholderType),
-    BuildOptions(TemporaryPlaceholderType),
+    BuildStatus(BuildStatus),
+    BuildOptions(String),
     BuildLog(String),
-    BinaryType(TemporaryPlaceholderType),
+    BinaryType(ProgramBinaryType),
     Error(Box<OclError>),
 }
 
 impl ProgramBuildInfoResult {
-    pub fn from_bytes(request: ProgramBuildInfo, result: OclResult<Vec<u8>>) 
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: ProgramBuildInfo, result: OclResult<Vec<u8>>)
+            -> ProgramBuildInfoResult
+    {
+        ProgramBuildInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: ProgramBuildInfo, result: OclResult<InfoBytes>) 
             -> ProgramBuildInfoResult 
     {
-        match result {
-            Ok(result) => { match request {
-                ProgramBuildInfo::BuildLog => {
-                    let string = match String::from_utf8(result) {
-                        Ok(s) => s,
-                        Err(err) => return ProgramBuildInfoResult::Error(Box::new(OclError::from(err))),
-                    };
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => return ProgramBuildInfoResult::Error(Box::new(err)),
+        };
+
+        let info_result = unsafe { match request {
+            ProgramBuildInfo::BuildStatus => {
+                util::bytes_to::<i32>(&result).and_then(|status| {
+                    BuildStatus::from_i32(status).ok_or(OclError::new(format!("Error converting \
+                        '{}' to a 'BuildStatus'.", status)))
+                }).map(ProgramBuildInfoResult::BuildStatus)
+            },
+            ProgramBuildInfo::BuildOptions => {
+                Ok(ProgramBuildInfoResult::BuildOptions(
+                    util::string_from_info_bytes(result.into_vec())))
+            },
+            ProgramBuildInfo::BuildLog => {
+                Ok(ProgramBuildInfoResult::BuildLog(
+                    util::string_from_info_bytes(result.into_vec())))
+            },
+            ProgramBuildInfo::BinaryType => {
+                util::bytes_to::<u32>(&result).and_then(|ty| {
+                    ProgramBinaryType::from_u32(ty).ok_or(OclError::new(format!("Error \
+                        converting '{}' to a 'ProgramBinaryType'.", ty)))
+                }).map(ProgramBuildInfoResult::BinaryType)
+            },
+        } };
 
-                    ProgramBuildInfoResult::BuildLog(string)
-                },
-                _ => ProgramBuildInfoResult::TemporaryPlaceholderVariant(result),
-            } }
+        match info_result {
+            Ok(res) => res,
             Err(err) => ProgramBuildInfoResult::Error(Box::new(err)),
         }
     }
@@ -771,12 +1043,11 @@ impl std::fmt::Debug for ProgramBuildInfoResult {
 impl std::fmt::Display for ProgramBuildInfoResult {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            &ProgramBuildInfoResult::TemporaryPlaceholderVariant(ref v) => {
-               write!(f, "{}", to_string_retarded(v))
-            },
+            &ProgramBuildInfoResult::BuildStatus(ref s) => write!(f, "{:?}", s),
+            &ProgramBuildInfoResult::BuildOptions(ref s) => write!(f, "{}", s),
             &ProgramBuildInfoResult::BuildLog(ref s) => write!(f, "{}", s),
+            &ProgramBuildInfoResult::BinaryType(ref t) => write!(f, "{:?}", t),
             &ProgramBuildInfoResult::Error(ref err) => write!(f, "{}", err.status_code()),
-            _ => panic!("ProgramBuildInfoResult: Converting this variant to string not yet implemented."),
         }
     }
 }
@@ -789,41 +1060,60 @@ impl Into<String> for ProgramBuildInfoResult {
 
 
 
-/// [UNSTABLE][INCOMPLETE] A kernel info result.
+/// [UNSTABLE] A kernel info result.
+///
+/// `Context` and `Program` contain retained handles. `Attributes` (OpenCL
+/// 1.2+) is a space-separated list of the attributes specified in the kernel
+/// source using `__attribute__`.
 pub enum KernelInfoResult {
-    TemporaryPlaceholderVariant(Vec<u8>),
     FunctionName(String),
-    NumArgs(TemporaryPlaceholderType),
-    ReferenceCount(TemporaryPlaceholderType),
-    Context(TemporaryPlaceholderType),
-    Program(TemporaryPlaceholderType),
-    Attributes(TemporaryPlaceholderType),
+    NumArgs(u32),
+    ReferenceCount(u32),
+    Context(Context),
+    Program(Program),
+    Attributes(String),
     Error(Box<OclError>),
 }
 
 impl KernelInfoResult {
-    pub fn from_bytes(request: KernelInfo, result: OclResult<Vec<u8>>)
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: KernelInfo, result: OclResult<Vec<u8>>) -> KernelInfoResult {
+        KernelInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: KernelInfo, result: OclResult<InfoBytes>)
             -> KernelInfoResult
     {
-        match result {
-            Ok(result) => match request {
-                // KernelInfo::MaxWorkGroupSize => {
-                //     let r0 = unsafe { util::bytes_to::<usize>(&result) };
-                //     let size = unsafe { util::bytes_into::<usize>(result) };
-                //     debug_assert_eq!(r0, size);
-                //     // println!("\n\nDEVICEINFORESULT::FROM_BYTES(MAXWORKGROUPSIZE): r1: {}, r2: {}", r1, r2);
-                //     KernelInfoResult::MaxWorkGroupSize(size)
-                // },
-                KernelInfo::FunctionName => {
-                    let string = match String::from_utf8(result) {
-                        Ok(s) => s,
-                        Err(err) => return KernelInfoResult::Error(Box::new(OclError::from(err))),
-                    };
-
-                    KernelInfoResult::FunctionName(string)
-                },
-                _ => KernelInfoResult::TemporaryPlaceholderVariant(result),
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => return KernelInfoResult::Error(Box::new(err)),
+        };
+
+        let info_result = unsafe { match request {
+            KernelInfo::FunctionName => {
+                Ok(KernelInfoResult::FunctionName(util::string_from_info_bytes(result.into_vec())))
+            },
+            KernelInfo::NumArgs => util::bytes_to::<u32>(&result).map(KernelInfoResult::NumArgs),
+            KernelInfo::ReferenceCount => {
+                util::bytes_to::<u32>(&result).map(KernelInfoResult::ReferenceCount)
             },
+            KernelInfo::Context => {
+                util::bytes_to::<*mut c_void>(&result)
+                    .and_then(|ptr| Context::from_raw_retained(ptr))
+                    .map(KernelInfoResult::Context)
+            },
+            KernelInfo::Program => {
+                util::bytes_to::<*mut c_void>(&result)
+                    .and_then(|ptr| Program::from_raw_retained(ptr))
+                    .map(KernelInfoResult::Program)
+            },
+            KernelInfo::Attributes => {
+                Ok(KernelInfoResult::Attributes(util::string_from_info_bytes(result.into_vec())))
+            },
+        } };
+
+        match info_result {
+            Ok(res) => res,
             Err(err) => KernelInfoResult::Error(Box::new(err)),
         }
     }
@@ -838,10 +1128,13 @@ impl std::fmt::Debug for KernelInfoResult {
 impl std::fmt::Display for KernelInfoResult {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            &KernelInfoResult::TemporaryPlaceholderVariant(ref v) => write!(f, "{}", to_string_retarded(v)),
             &KernelInfoResult::FunctionName(ref s) => write!(f, "{}", s),
+            &KernelInfoResult::NumArgs(num) => write!(f, "{}", num),
+            &KernelInfoResult::ReferenceCount(count) => write!(f, "{}", count),
+            &KernelInfoResult::Context(ref context) => write!(f, "{:?}", context),
+            &KernelInfoResult::Program(ref program) => write!(f, "{:?}", program),
+            &KernelInfoResult::Attributes(ref s) => write!(f, "{}", s),
             &KernelInfoResult::Error(ref err) => write!(f, "{}", err.status_code()),
-            _ => panic!("KernelInfoResult: Converting this variant to string not yet implemented."),
         }
     }
 }
@@ -866,14 +1159,19 @@ pub enum KernelArgInfoResult {
 }
 
 impl KernelArgInfoResult {
-    pub fn from_bytes(request: KernelArgInfo, result: OclResult<Vec<u8>>) 
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: KernelArgInfo, result: OclResult<Vec<u8>>) -> KernelArgInfoResult {
+        KernelArgInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: KernelArgInfo, result: OclResult<InfoBytes>) 
             -> KernelArgInfoResult
     {
         match result {
             Ok(result) => match request {
                 // KernelArgInfo::MaxWorkGroupSize => {
                 //     let r0 = unsafe { util::bytes_to::<usize>(&result) };
-                //     let size = unsafe { util::bytes_into::<usize>(result) };
+                //     let size = unsafe { util::bytes_to::<usize>(&result) };
                 //     debug_assert_eq!(r0, size);
                 //     // println!("\n\nDEVICEINFORESULT::FROM_BYTES(MAXWORKGROUPSIZE): r1: {}, r2: {}", r1, r2);
                 //     KernelArgInfoResult::MaxWorkGroupSize(size)
@@ -881,7 +1179,7 @@ impl KernelArgInfoResult {
                 // KernelArgInfo::FunctionName => {
                 //     KernelArgInfoResult::FunctionName(try!(String::from_utf8(result)))
                 // },
-                _ => KernelArgInfoResult::TemporaryPlaceholderVariant(result),
+                _ => KernelArgInfoResult::TemporaryPlaceholderVariant(result.into_vec()),
             },
             Err(err) => KernelArgInfoResult::Error(Box::new(err)),
         }
@@ -927,19 +1225,34 @@ pub enum KernelWorkGroupInfoResult {
 }
 
 impl KernelWorkGroupInfoResult {
-    pub fn from_bytes(request: KernelWorkGroupInfo, result: OclResult<Vec<u8>>) 
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: KernelWorkGroupInfo, result: OclResult<Vec<u8>>)
+            -> KernelWorkGroupInfoResult
+    {
+        KernelWorkGroupInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: KernelWorkGroupInfo, result: OclResult<InfoBytes>) 
             -> KernelWorkGroupInfoResult
     {
         match result {
             Ok(result) => match request {
+                KernelWorkGroupInfo::WorkGroupSize => {
+                    match unsafe { util::bytes_to::<usize>(&result) } {
+                        Ok(size) => KernelWorkGroupInfoResult::WorkGroupSize(size),
+                        Err(err) => KernelWorkGroupInfoResult::Error(Box::new(err)),
+                    }
+                },
                 KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple => {
-                    let size = unsafe { util::bytes_into::<usize>(result) };
-                    KernelWorkGroupInfoResult::PreferredWorkGroupSizeMultiple(size)
+                    match unsafe { util::bytes_to::<usize>(&result) } {
+                        Ok(size) => KernelWorkGroupInfoResult::PreferredWorkGroupSizeMultiple(size),
+                        Err(err) => KernelWorkGroupInfoResult::Error(Box::new(err)),
+                    }
                 },
                 // KernelWorkGroupInfo::FunctionName => {
                 //     KernelWorkGroupInfoResult::FunctionName(try!(String::from_utf8(result)))
                 // },
-                _ => KernelWorkGroupInfoResult::TemporaryPlaceholderVariant(result),
+                _ => KernelWorkGroupInfoResult::TemporaryPlaceholderVariant(result.into_vec()),
             },
             Err(err) => KernelWorkGroupInfoResult::Error(Box::new(err)),
         }
@@ -955,6 +1268,7 @@ impl std::fmt::Debug for KernelWorkGroupInfoResult {
 impl std::fmt::Display for KernelWorkGroupInfoResult {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
+            &KernelWorkGroupInfoResult::WorkGroupSize(s) => write!(f, "{}", s),
             &KernelWorkGroupInfoResult::PreferredWorkGroupSizeMultiple(s) => write!(f, "{}", s),
             &KernelWorkGroupInfoResult::TemporaryPlaceholderVariant(ref v) => {
                write!(f, "{}", to_string_retarded(v))
@@ -973,30 +1287,89 @@ impl Into<String> for KernelWorkGroupInfoResult {
 
 
 
-/// [UNSTABLE][INCOMPLETE] An event info result.
+/// [UNSTABLE] An event info result.
+///
+/// `CommandQueue` and `Context` contain retained handles. `CommandQueue` is
+/// `None` for user events. A negative (error) execution status is returned
+/// as the `Error` variant.
 pub enum EventInfoResult {
-    TemporaryPlaceholderVariant(Vec<u8>),
-    CommandQueue(TemporaryPlaceholderType),
-    CommandType(TemporaryPlaceholderType),
-    ReferenceCount(TemporaryPlaceholderType),
-    CommandExecutionStatus(TemporaryPlaceholderType),
-    Context(TemporaryPlaceholderType),
+    CommandQueue(Option<CommandQueue>),
+    CommandType(CommandType),
+    ReferenceCount(u32),
+    CommandExecutionStatus(CommandExecutionStatus),
+    Context(Context),
     Error(Box<OclError>),
 }
 
 impl EventInfoResult {
-    pub fn from_bytes(request: EventInfo, result: OclResult<Vec<u8>>) 
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: EventInfo, result: OclResult<Vec<u8>>) -> EventInfoResult {
+        EventInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: EventInfo, result: OclResult<InfoBytes>) 
             -> EventInfoResult 
     {
-        match result {
-            Ok(result) => { match request {
-                _ => EventInfoResult::TemporaryPlaceholderVariant(result),
-            } }
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => return EventInfoResult::Error(Box::new(err)),
+        };
+
+        let info_result = unsafe { match request {
+            EventInfo::CommandQueue => {
+                util::bytes_to::<*mut c_void>(&result).and_then(|ptr| {
+                    if ptr.is_null() {
+                        Ok(EventInfoResult::CommandQueue(None))
+                    } else {
+                        CommandQueue::from_raw_retained(ptr)
+                            .map(|q| EventInfoResult::CommandQueue(Some(q)))
+                    }
+                })
+            },
+            EventInfo::CommandType => {
+                util::bytes_to::<u32>(&result).and_then(|ty| {
+                    CommandType::from_u32(ty).ok_or(OclError::new(format!("Error converting \
+                        '{}' to a 'CommandType'.", ty)))
+                }).map(EventInfoResult::CommandType)
+            },
+            EventInfo::ReferenceCount => {
+                util::bytes_to::<u32>(&result).map(EventInfoResult::ReferenceCount)
+            },
+            EventInfo::CommandExecutionStatus => {
+                util::bytes_to::<i32>(&result).and_then(|status| {
+                    if status < 0 {
+                        Err(event_status_err(status))
+                    } else {
+                        CommandExecutionStatus::from_i32(status).ok_or(OclError::new(format!(
+                            "Error converting '{}' to a 'CommandExecutionStatus'.", status)))
+                    }
+                }).map(EventInfoResult::CommandExecutionStatus)
+            },
+            EventInfo::Context => {
+                util::bytes_to::<*mut c_void>(&result)
+                    .and_then(|ptr| Context::from_raw_retained(ptr))
+                    .map(EventInfoResult::Context)
+            },
+        } };
+
+        match info_result {
+            Ok(res) => res,
             Err(err) => EventInfoResult::Error(Box::new(err)),
         }
     }
 }
 
+/// Returns the error corresponding to a negative (abnormal termination)
+/// command execution status.
+fn event_status_err(status: i32) -> OclError {
+    match OclError::err_status::<(), _>(status, "clGetEventInfo", "The command associated \
+        with the event terminated abnormally.")
+    {
+        Err(err) => err,
+        Ok(_) => OclError::new(format!("Invalid command execution status: '{}'.", status)),
+    }
+}
+
 impl std::fmt::Debug for EventInfoResult {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{}", &self.to_string())
@@ -1006,11 +1379,12 @@ impl std::fmt::Debug for EventInfoResult {
 impl std::fmt::Display for EventInfoResult {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            &EventInfoResult::TemporaryPlaceholderVariant(ref v) => {
-               write!(f, "{}", to_string_retarded(v))
-            },
+            &EventInfoResult::CommandQueue(ref queue) => write!(f, "{:?}", queue),
+            &EventInfoResult::CommandType(ty) => write!(f, "{:?}", ty),
+            &EventInfoResult::ReferenceCount(count) => write!(f, "{}", count),
+            &EventInfoResult::CommandExecutionStatus(status) => write!(f, "{:?}", status),
+            &EventInfoResult::Context(ref context) => write!(f, "{:?}", context),
             &EventInfoResult::Error(ref err) => write!(f, "{}", err.status_code()),
-            _ => panic!("EventInfoResult: Converting this variant to string not yet implemented."),
         }
     }
 }
@@ -1034,12 +1408,17 @@ pub enum ProfilingInfoResult {
 }
 
 impl ProfilingInfoResult {
-    pub fn from_bytes(request: ProfilingInfo, result: OclResult<Vec<u8>>) 
+    /// Returns a new info result from the raw bytes of a query result.
+    pub fn from_bytes(request: ProfilingInfo, result: OclResult<Vec<u8>>) -> ProfilingInfoResult {
+        ProfilingInfoResult::from_info_bytes(request, result.map(InfoBytes::Heap))
+    }
+
+    pub(crate) fn from_info_bytes(request: ProfilingInfo, result: OclResult<InfoBytes>) 
             -> ProfilingInfoResult 
     {
         match result {
             Ok(result) => { match request {
-                _ => ProfilingInfoResult::TemporaryPlaceholderVariant(result),
+                _ => ProfilingInfoResult::TemporaryPlaceholderVariant(result.into_vec()),
             } }
             Err(err) => ProfilingInfoResult::Error(Box::new(err)),
         }
@@ -1077,10 +1456,13 @@ fn to_string_retarded(v: &Vec<u8>) -> String {
     if v.len() == 4 {
         util::bytes_to_u32(&v[..]).to_string()
     } else if v.len() == 8 {
-        unsafe { util::bytes_to::<usize>(&v[..]).to_string() }
+        unsafe { util::bytes_to::<u64>(&v[..]).map(|n| n.to_string()).unwrap_or_default() }
     } else if v.len() == 3 * 8 {
-        unsafe { format!("{:?}", util::bytes_to_vec::<usize>(&v[..])) }
+        unsafe { util::bytes_to_vec::<u64>(&v[..]).map(|n| format!("{:?}", n)).unwrap_or_default() }
     } else {
-        String::from_utf8(v.clone()).unwrap_or(format!("{:?}", v))
+        match std::str::from_utf8(v) {
+            Ok(_) => util::string_from_info_bytes(v.clone()),
+            Err(_) => format!("{:?}", v),
+        }
     }
 }
@@ -0,0 +1,62 @@
+//! `Serialize` and `Deserialize` for bitflags, as lists of flag names.
+//!
+//! Flags are written as a sequence of the names of their constants (e.g.
+//! `["MEM_READ_WRITE", "MEM_COPY_HOST_PTR"]`) rather than as raw bits, so
+//! that persisted values remain readable. Unknown names are rejected.
+
+use std::fmt;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::{self, SeqAccess, Visitor};
+use core::{MemFlags, CommandQueueProperties, MEM_READ_WRITE, MEM_WRITE_ONLY, MEM_READ_ONLY,
+    MEM_USE_HOST_PTR, MEM_ALLOC_HOST_PTR, MEM_COPY_HOST_PTR, MEM_HOST_WRITE_ONLY,
+    MEM_HOST_READ_ONLY, MEM_HOST_NO_ACCESS, QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE,
+    QUEUE_PROFILING_ENABLE};
+
+macro_rules! impl_serde_flags {
+    ($ty:ident, [$($flag:ident),+]) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let names: Vec<&'static str> = [$(($flag, stringify!($flag))),+].iter()
+                    .filter(|&&(flag, _)| self.contains(flag))
+                    .map(|&(_, name)| name)
+                    .collect();
+                names.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$ty, D::Error> {
+                struct FlagsVisitor;
+
+                impl<'de> Visitor<'de> for FlagsVisitor {
+                    type Value = $ty;
+
+                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        write!(f, concat!("a list of ", stringify!($ty), " flag names"))
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<$ty, A::Error> {
+                        const NAMES: &'static [&'static str] = &[$(stringify!($flag)),+];
+                        let mut flags = $ty::empty();
+
+                        while let Some(name) = try!(seq.next_element::<String>()) {
+                            flags = flags | match &name[..] {
+                                $(stringify!($flag) => $flag,)+
+                                _ => return Err(de::Error::unknown_variant(&name, NAMES)),
+                            };
+                        }
+                        Ok(flags)
+                    }
+                }
+
+                deserializer.deserialize_seq(FlagsVisitor)
+            }
+        }
+    };
+}
+
+impl_serde_flags!(MemFlags, [MEM_READ_WRITE, MEM_WRITE_ONLY, MEM_READ_ONLY, MEM_USE_HOST_PTR,
+    MEM_ALLOC_HOST_PTR, MEM_COPY_HOST_PTR, MEM_HOST_WRITE_ONLY, MEM_HOST_READ_ONLY,
+    MEM_HOST_NO_ACCESS]);
+impl_serde_flags!(CommandQueueProperties, [QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE,
+    QUEUE_PROFILING_ENABLE]);
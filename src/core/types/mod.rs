@@ -4,3 +4,5 @@ pub mod abs;
 // pub mod flag;
 pub mod enums;
 pub mod structs;
+pub mod vectors;
+#[cfg(feature = "serde")] mod flags_serde;
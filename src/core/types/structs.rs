@@ -1,11 +1,16 @@
 //! Rust implementations of various structs used by the OpenCL API.
 
+use std::cmp;
+#[cfg(test)] use std::cell::Cell;
+use std::ops::{Deref, DerefMut};
 use num::FromPrimitive;
+use libc::size_t;
 use error::{Error as OclError, Result as OclResult};
-use util;
+use util::ToClBytes;
 use cl_h::{self, cl_mem};
-use core::{Mem, MemObjectType, ImageChannelOrder, ImageChannelDataType, 
-        ContextProperty, ContextInfoOrPropertiesPointerType as PropKind, PlatformId};
+use core::{functions, Mem, MemObjectType, ImageChannelOrder, ImageChannelDataType, 
+        ContextProperty, ContextInfoOrPropertiesPointerType as PropKind, PlatformId, Context,
+        ImageInfo, ImageInfoResult};
 
 
 /// Context properties list.
@@ -54,7 +59,6 @@ impl ContextProperties {
         platform
     } 
 
-    /// [UNTESTED: Not properly tested]
     /// Converts this list into a packed-byte representation as specified
     /// [here](https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clCreateContext.html).
     ///
@@ -62,37 +66,35 @@ impl ContextProperties {
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::with_capacity(128);
 
-        unsafe { 
-            // For each property:
-            for prop in self.0.iter() {
-                // Convert both the kind of property (a u32) and the value (variable type/size) 
-                // into just a core byte vector (Vec<u8>):
-                let (kind, val) = match prop {
-                    &ContextProperty::Platform(ref platform_id_core) => (
-                        util::into_bytes(PropKind::Platform as cl_h::cl_uint),
-                        util::into_bytes(platform_id_core.as_ptr() as cl_h::cl_platform_id) 
-                    ),
-                    &ContextProperty::InteropUserSync(sync) => (
-                        util::into_bytes(PropKind::InteropUserSync as cl_h::cl_uint),
-                        util::into_bytes(sync as cl_h::cl_bool)
-                    ),
-                    _ => continue,
-                };
-
-                // Property Kind Enum:
-                bytes.extend_from_slice(&kind);
-                // 32 bits of padding:
-                bytes.extend_from_slice(&util::into_bytes(0 as u32));
-                // Value:
-                bytes.extend_from_slice(&val);
-                // 32 bits of padding:
-                bytes.extend_from_slice(&util::into_bytes(0 as u32));
-            }
-
-            // Add a terminating 0:
-            bytes.extend_from_slice(&util::into_bytes(0 as usize));
+        // For each property:
+        for prop in self.0.iter() {
+            // Convert both the kind of property (a u32) and the value (variable type/size) 
+            // into just a core byte vector (Vec<u8>):
+            let (kind, val) = match prop {
+                &ContextProperty::Platform(ref platform_id_core) => (
+                    (PropKind::Platform as cl_h::cl_uint).to_cl_bytes(),
+                    unsafe { platform_id_core.as_ptr() }.to_cl_bytes()
+                ),
+                &ContextProperty::InteropUserSync(sync) => (
+                    (PropKind::InteropUserSync as cl_h::cl_uint).to_cl_bytes(),
+                    (sync as cl_h::cl_bool).to_cl_bytes()
+                ),
+                _ => continue,
+            };
+
+            // Property Kind Enum:
+            bytes.extend_from_slice(&kind);
+            // 32 bits of padding:
+            bytes.extend_from_slice(&0u32.to_cl_bytes());
+            // Value:
+            bytes.extend_from_slice(&val);
+            // 32 bits of padding:
+            bytes.extend_from_slice(&0u32.to_cl_bytes());
         }
 
+        // Add a terminating 0:
+        bytes.extend_from_slice(&0usize.to_cl_bytes());
+
         bytes.shrink_to_fit();
         bytes
     }
@@ -138,11 +140,173 @@ impl Into<Vec<u8>> for ContextProperties {
 /// devices in context associated with buffer for which the origin value is
 /// aligned to the CL_DEVICE_MEM_BASE_ADDR_ALIGN value.
 ///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct BufferRegion {
     pub origin: usize,
     pub size: usize,
 }
 
+impl BufferRegion {
+    /// Returns the region of `len` elements of type `T` beginning at the
+    /// element, `origin`, converted to bytes.
+    ///
+    /// Returns an error if the region, in bytes, overflows a `usize`.
+    pub fn from_elements<T>(origin: usize, len: usize) -> OclResult<BufferRegion> {
+        let elem_size = ::std::mem::size_of::<T>();
+
+        match (origin.checked_mul(elem_size), len.checked_mul(elem_size)) {
+            (Some(origin_bytes), Some(size_bytes)) if origin_bytes.checked_add(size_bytes)
+                    .is_some() => Ok(BufferRegion { origin: origin_bytes, size: size_bytes }),
+            _ => OclError::err(format!("ocl::core::BufferRegion::from_elements: The region \
+                (origin: {}, len: {}) is too large to be expressed in bytes.", origin, len)),
+        }
+    }
+
+    /// Returns a `cl_buffer_region` suitable for passing to
+    /// `clCreateSubBuffer`.
+    pub fn to_raw(&self) -> cl_h::cl_buffer_region {
+        cl_h::cl_buffer_region {
+            origin: self.origin,
+            size: self.size,
+        }
+    }
+}
+
+
+/// The global work size, local work size, and global work offset of a kernel
+/// launch, all sharing the same number of dimensions.
+///
+/// Used by `::enqueue_kernel_dims` (and internally by `::enqueue_kernel`).
+/// The local work size and global work offset are optional and are passed to
+/// OpenCL as `NULL` when absent.
+///
+/// Unused trailing dimensions are stored as `1` (sizes) or `0` (offset).
+///
+/// ## Examples
+///
+/// ```text
+/// let dims = WorkSizes::new(&[1024, 64], None, None).unwrap()
+///     .lws(&[16, 16]).unwrap();
+/// assert_eq!(dims.dim_count(), 2);
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorkSizes {
+    dim_count: u32,
+    gws: [usize; 3],
+    lws: Option<[usize; 3]>,
+    gwo: Option<[usize; 3]>,
+}
+
+impl WorkSizes {
+    /// Returns a new `WorkSizes`.
+    ///
+    /// The length of `gws` is the number of dimensions (1, 2, or 3).
+    ///
+    /// ## Errors
+    ///
+    /// `gws` must have between one and three elements and `lws` and `gwo`, if
+    /// specified, must each have the same number of elements as `gws`.
+    ///
+    pub fn new(gws: &[usize], lws: Option<&[usize]>, gwo: Option<&[usize]>)
+            -> OclResult<WorkSizes>
+    {
+        if gws.len() == 0 || gws.len() > 3 {
+            return OclError::err(format!("ocl::core::WorkSizes::new: The global work size \
+                must have between one and three dimensions (gws: {:?}).", gws));
+        }
+
+        let mut dims = WorkSizes {
+            dim_count: gws.len() as u32,
+            gws: try!(Self::to_array(gws, 1, "global work size", gws.len())),
+            lws: None,
+            gwo: None,
+        };
+
+        if let Some(lws) = lws { dims = try!(dims.lws(lws)); }
+        if let Some(gwo) = gwo { dims = try!(dims.gwo(gwo)); }
+        Ok(dims)
+    }
+
+    /// Sets the local work size (builder-style).
+    ///
+    /// ## Errors
+    ///
+    /// `lws` must have the same number of dimensions as the global work size.
+    pub fn lws(mut self, lws: &[usize]) -> OclResult<WorkSizes> {
+        self.lws = Some(try!(Self::to_array(lws, 1, "local work size", self.dim_count as usize)));
+        Ok(self)
+    }
+
+    /// Sets the global work offset (builder-style).
+    ///
+    /// ## Errors
+    ///
+    /// `gwo` must have the same number of dimensions as the global work size.
+    pub fn gwo(mut self, gwo: &[usize]) -> OclResult<WorkSizes> {
+        self.gwo = Some(try!(Self::to_array(gwo, 0, "global work offset", self.dim_count as usize)));
+        Ok(self)
+    }
+
+    /// Returns the number of dimensions.
+    pub fn dim_count(&self) -> u32 {
+        self.dim_count
+    }
+
+    /// Returns the global work size.
+    pub fn global_work_size(&self) -> &[usize; 3] {
+        &self.gws
+    }
+
+    /// Returns the local work size, if specified.
+    pub fn local_work_size(&self) -> Option<&[usize; 3]> {
+        self.lws.as_ref()
+    }
+
+    /// Returns the global work offset, if specified.
+    pub fn global_work_offset(&self) -> Option<&[usize; 3]> {
+        self.gwo.as_ref()
+    }
+
+    /// Returns a pointer to the global work size array.
+    pub fn gws_ptr(&self) -> *const size_t {
+        &self.gws as *const [usize; 3] as *const size_t
+    }
+
+    /// Returns a pointer to the local work size array or `NULL` if unspecified.
+    pub fn lws_ptr(&self) -> *const size_t {
+        match self.lws {
+            Some(ref lws) => lws as *const [usize; 3] as *const size_t,
+            None => 0 as *const size_t,
+        }
+    }
+
+    /// Returns a pointer to the global work offset array or `NULL` if
+    /// unspecified.
+    pub fn gwo_ptr(&self) -> *const size_t {
+        match self.gwo {
+            Some(ref gwo) => gwo as *const [usize; 3] as *const size_t,
+            None => 0 as *const size_t,
+        }
+    }
+
+    /// Copies `vals` into a 3D array, filling the remainder with `fill`,
+    /// after ensuring that it has exactly `dim_count` elements.
+    fn to_array(vals: &[usize], fill: usize, name: &str, dim_count: usize)
+            -> OclResult<[usize; 3]>
+    {
+        if vals.len() != dim_count {
+            return OclError::err(format!("ocl::core::WorkSizes: The {} ({:?}) has {} \
+                dimension(s) but the global work size has {}. All work dimensions must have \
+                the same number of dimensions.", name, vals, vals.len(), dim_count));
+        }
+
+        let mut array = [fill; 3];
+        array[..dim_count].copy_from_slice(vals);
+        Ok(array)
+    }
+}
+
 
 /// Image format properties used by `Image`.
 ///
@@ -158,6 +322,7 @@ pub struct BufferRegion {
 /// OpenCL implementations must maintain the minimum precision specified by the number of bits in image_channel_data_type. If the image format specified by image_channel_order, and image_channel_data_type cannot be supported by the OpenCL implementation, then the call to clCreateImage will return a NULL memory object.
 ///
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ImageFormat {
     pub channel_order: ImageChannelOrder,
     pub channel_data_type: ImageChannelDataType,
@@ -218,9 +383,19 @@ impl ImageFormat {
     /// (https://www.khronos.org/registry/cl/sdk/2.0/docs/man/xhtml/cl_khr_gl_depth_images.html).
     /// 
     /// TODO: Validate combinations.
-    /// TODO: Use `core::get_image_info` to check these with a test.
+    ///
+    /// Formats unknown to this table return zero. Use `::verify_pixel_bytes`
+    /// to check the table against an implementation.
     ///
     pub fn pixel_bytes(&self) -> usize {
+        // Packed formats store every channel in a single value:
+        match self.channel_data_type {
+            ImageChannelDataType::UnormShort565 => return 2,
+            ImageChannelDataType::UnormShort555 => return 2,
+            ImageChannelDataType::UnormInt101010 => return 4,
+            _ => (),
+        }
+
         let channel_count = match self.channel_order {
             ImageChannelOrder::R => 1,
             ImageChannelOrder::A => 1,
@@ -234,9 +409,9 @@ impl ImageFormat {
             // This format can only be used if channel data type = CL_UNORM_INT8, CL_SNORM_INT8, CL_SIGNED_INT8 or CL_UNSIGNED_INT8:
             ImageChannelOrder::Argb => 4,
             // This format can only be used if channel data type = CL_UNORM_INT8, CL_UNORM_INT16, CL_SNORM_INT8, CL_SNORM_INT16, CL_HALF_FLOAT, or CL_FLOAT:
-            ImageChannelOrder::Intensity => 4,
+            ImageChannelOrder::Intensity => 1,
             // This format can only be used if channel data type = CL_UNORM_INT8, CL_UNORM_INT16, CL_SNORM_INT8, CL_SNORM_INT16, CL_HALF_FLOAT, or CL_FLOAT:
-            ImageChannelOrder::Luminance => 4,
+            ImageChannelOrder::Luminance => 1,
             ImageChannelOrder::Rx => 2,
             ImageChannelOrder::Rgx => 4,
             // This format can only be used if channel data type = CL_UNORM_SHORT_565, CL_UNORM_SHORT_555 or CL_UNORM_INT101010:
@@ -284,6 +459,71 @@ impl ImageFormat {
 
         channel_count * channel_size
     }
+
+    /// Checks `::pixel_bytes` against the element size reported by the
+    /// implementation (`CL_IMAGE_ELEMENT_SIZE`) for each 2D image format
+    /// supported by `context`, returning any which disagree.
+    ///
+    /// A 1x1 image is created (and dropped) for each format. Formats unknown
+    /// to `ImageFormat` or to the `::pixel_bytes` table are skipped.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the supported formats can not be listed or an
+    /// image of a supported format can not be created or queried.
+    pub fn verify_pixel_bytes(context: &Context) -> OclResult<Vec<FormatMismatch>> {
+        let flags = ::flags::MEM_READ_ONLY;
+        let formats_raw = try!(functions::supported_image_formats_raw(context, flags,
+            MemObjectType::Image2d));
+        let mut mismatches = Vec::new();
+
+        for format in formats_raw.into_iter().filter_map(|raw| ImageFormat::from_raw(raw).ok()) {
+            let pixel_bytes = format.pixel_bytes();
+            if pixel_bytes == 0 { continue; }
+
+            let desc = ImageDescriptor::new(MemObjectType::Image2d, 1, 1, 1, 0, 0, 0, None);
+            let image = try!(unsafe { functions::create_image::<u8>(context, flags, &format,
+                &desc, None) });
+
+            let element_size = match functions::get_image_info(&image, ImageInfo::ElementSize) {
+                ImageInfoResult::ElementSize(size) => size,
+                ImageInfoResult::Error(err) => return Err(*err),
+                _ => return OclError::err("ocl::core::ImageFormat::verify_pixel_bytes: \
+                    Unexpected 'ImageInfoResult' variant."),
+            };
+
+            if element_size != pixel_bytes {
+                mismatches.push(FormatMismatch {
+                    format: format,
+                    pixel_bytes: pixel_bytes,
+                    element_size: element_size,
+                });
+            }
+        }
+
+        Ok(mismatches)
+    }
+}
+
+
+/// An image format for which `ImageFormat::pixel_bytes` disagrees with the
+/// element size reported by the implementation (see
+/// `ImageFormat::verify_pixel_bytes`).
+#[derive(Debug, Clone)]
+pub struct FormatMismatch {
+    pub format: ImageFormat,
+    /// The size given by `ImageFormat::pixel_bytes`.
+    pub pixel_bytes: usize,
+    /// The size reported by the implementation (`CL_IMAGE_ELEMENT_SIZE`).
+    pub element_size: usize,
+}
+
+impl ::std::fmt::Display for FormatMismatch {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{:?}/{:?}: pixel_bytes: {}, CL_IMAGE_ELEMENT_SIZE: {}",
+            self.format.channel_order, self.format.channel_data_type, self.pixel_bytes,
+            self.element_size)
+    }
 }
 
 
@@ -320,8 +560,13 @@ impl ImageFormat {
 ///
 /// Note
 /// Concurrent reading from, writing to and copying between both a buffer object and 1D image buffer object associated with the buffer object is undefined. Only reading from both a buffer object and 1D image buffer object associated with the buffer object is defined.
+///
+/// With the `serde` feature, `buffer` is omitted when serializing and left
+/// as `None` when deserializing. Serializing a descriptor which refers to a
+/// buffer is an error.
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ImageDescriptor {
     pub image_type: MemObjectType,
     pub image_width: usize,
@@ -332,9 +577,20 @@ pub struct ImageDescriptor {
     pub image_slice_pitch: usize,
     num_mip_levels: u32,
     num_samples: u32,
+    #[cfg_attr(feature = "serde", serde(skip_deserializing,
+        skip_serializing_if = "Option::is_none", serialize_with = "serialize_no_buffer"))]
     pub buffer: Option<Mem>,
 }
 
+/// Rejects the buffer of an `ImageDescriptor`, which can not be serialized.
+#[cfg(feature = "serde")]
+fn serialize_no_buffer<S: ::serde::Serializer>(_: &Option<Mem>, _: S)
+        -> ::std::result::Result<S::Ok, S::Error>
+{
+    Err(<S::Error as ::serde::ser::Error>::custom("ImageDescriptor::buffer: Memory objects can \
+        not be serialized."))
+}
+
 impl ImageDescriptor {
     pub fn new(image_type: MemObjectType, width: usize, height: usize, depth: usize, 
                 array_size: usize, row_pitch: usize, slc_pitch: usize, buffer: Option<Mem>,
@@ -353,6 +609,125 @@ impl ImageDescriptor {
         }
     }
 
+    /// Returns a new descriptor for an image with dimensions `dims`
+    /// (`[width, height, depth]`), after checking that they, `array_size`,
+    /// and `buffer` make sense for `image_type`.
+    ///
+    /// If `image_type` is `None`, it is inferred: a 1D image buffer if
+    /// `buffer` is given, otherwise a 1D or 2D image array if `array_size` is
+    /// non-zero (2D if the height exceeds one), otherwise a 1D, 2D, or 3D
+    /// image depending on the last dimension exceeding one.
+    ///
+    /// The number of images in an image array may be given by `array_size`
+    /// or, if it is zero, by the dimension following the last image
+    /// dimension (the height of a 1D array or the depth of a 2D array).
+    /// Dimensions unused by the image type are set to 1 and an unused array
+    /// size to 0.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error stating the rule violated if:
+    ///
+    /// * `image_type` is not an image type or the width is zero,
+    /// * a 1D image (or image array) has a height or depth greater than one,
+    ///   or a 2D image (or image array) a depth greater than one,
+    /// * a 2D or 3D image has a height of zero or a 3D image a depth of zero,
+    /// * an image array has an array size of zero or two conflicting array
+    ///   sizes, or an image other than an array has one greater than one,
+    /// * a 1D image buffer lacks a buffer or another image type has one.
+    pub fn checked(image_type: Option<MemObjectType>, dims: [usize; 3], array_size: usize,
+            buffer: Option<Mem>) -> OclResult<ImageDescriptor>
+    {
+        let [width, height, depth] = dims;
+
+        let image_type = match image_type {
+            Some(ty) => ty,
+            None if buffer.is_some() => MemObjectType::Image1dBuffer,
+            None if array_size > 0 && height > 1 => MemObjectType::Image2dArray,
+            None if array_size > 0 => MemObjectType::Image1dArray,
+            None if depth > 1 => MemObjectType::Image3d,
+            None if height > 1 => MemObjectType::Image2d,
+            None => MemObjectType::Image1d,
+        };
+
+        let name = match image_type {
+            MemObjectType::Image1d => "1D images (Image1d)",
+            MemObjectType::Image1dBuffer => "1D image buffers (Image1dBuffer)",
+            MemObjectType::Image1dArray => "1D image arrays (Image1dArray)",
+            MemObjectType::Image2d => "2D images (Image2d)",
+            MemObjectType::Image2dArray => "2D image arrays (Image2dArray)",
+            MemObjectType::Image3d => "3D images (Image3d)",
+            MemObjectType::Buffer => return OclError::err("ocl::core::ImageDescriptor::checked: \
+                'Buffer' is not an image type."),
+        };
+
+        let err = |rule: String| -> OclResult<ImageDescriptor> {
+            OclError::err(format!("ocl::core::ImageDescriptor::checked: {} {}", name, rule))
+        };
+
+        if width == 0 {
+            return err("require a width of at least 1.".to_owned());
+        }
+
+        // The dimension beyond the image dimensions, holding the array size
+        // of an image array, and those which must be one:
+        let (image_dims, trailing) = match image_type {
+            MemObjectType::Image1d | MemObjectType::Image1dBuffer => (1, None),
+            MemObjectType::Image1dArray => (1, Some(height)),
+            MemObjectType::Image2d => (2, None),
+            MemObjectType::Image2dArray => (2, Some(depth)),
+            _ => (3, None),
+        };
+
+        // The height and depth are each an image dimension (at least 1), the
+        // array size of an image array, or unused (at most 1):
+        for &(dim_idx, dim, dim_name) in [(1, height, "height"), (2, depth, "depth")].iter() {
+            if dim_idx < image_dims {
+                if dim == 0 {
+                    return err(format!("require a {} of at least 1.", dim_name));
+                }
+            } else if dim_idx == image_dims && trailing.is_some() {
+                continue;
+            } else if dim > 1 {
+                return err(format!("require a {} of 1 (or 0), found {}.", dim_name, dim));
+            }
+        }
+
+        let array_size = match trailing {
+            Some(trailing) => {
+                if array_size == 0 && trailing <= 1 {
+                    return err("require an array size of at least 1, given either as the array \
+                        size or as the dimension following the image dimensions.".to_owned());
+                }
+                if array_size != 0 && trailing > 1 && trailing != array_size {
+                    return err(format!("require the array size ({}) and the dimension following \
+                        the image dimensions ({}) to agree when both are given.", array_size,
+                        trailing));
+                }
+                if array_size != 0 { array_size } else { trailing }
+            },
+            None => {
+                if array_size > 1 {
+                    return err(format!("may not have an array size greater than 1 (only image \
+                        arrays may), found {}.", array_size));
+                }
+                0
+            },
+        };
+
+        match (image_type, buffer.is_some()) {
+            (MemObjectType::Image1dBuffer, false) => return err("require a buffer.".to_owned()),
+            (MemObjectType::Image1dBuffer, true) | (_, false) => (),
+            (_, true) => return err("may not have a buffer (only 1D image buffers may)."
+                .to_owned()),
+        }
+
+        Ok(ImageDescriptor::new(image_type, width,
+            if image_dims >= 2 { height } else { 1 },
+            if image_dims >= 3 { depth } else { 1 },
+            array_size, 0, 0, buffer))
+    }
+
     pub fn to_raw(&self) -> cl_h::cl_image_desc {
         cl_h::cl_image_desc {
             image_type: self.image_type as u32,
@@ -372,3 +747,97 @@ impl ImageDescriptor {
     }
 }
 
+
+
+/// The largest info query result (in bytes) stored inline by `InfoBytes`.
+pub(crate) const INFO_BYTES_INLINE_MAX: usize = 64;
+
+#[cfg(test)]
+thread_local! {
+    static INFO_BYTES_INLINE_THRESHOLD: Cell<usize> = Cell::new(INFO_BYTES_INLINE_MAX);
+}
+
+/// Sets the largest result stored inline by `InfoBytes` on the current
+/// thread (clamped to `INFO_BYTES_INLINE_MAX`), allowing tests to force
+/// results onto the heap.
+#[cfg(test)]
+pub(crate) fn set_info_bytes_inline_threshold(threshold: usize) {
+    INFO_BYTES_INLINE_THRESHOLD.with(|t| t.set(cmp::min(threshold, INFO_BYTES_INLINE_MAX)));
+}
+
+#[cfg(test)]
+fn info_bytes_inline_threshold() -> usize {
+    INFO_BYTES_INLINE_THRESHOLD.with(|t| t.get())
+}
+
+#[cfg(not(test))]
+fn info_bytes_inline_threshold() -> usize {
+    INFO_BYTES_INLINE_MAX
+}
+
+/// The raw bytes of an info query result.
+///
+/// Results of up to `INFO_BYTES_INLINE_MAX` bytes (most scalars, handles,
+/// and short strings) are stored inline, avoiding a heap allocation. Larger
+/// results (extension lists, program binaries, etc.) are stored in a `Vec`.
+pub(crate) enum InfoBytes {
+    Inline([u8; INFO_BYTES_INLINE_MAX], usize),
+    Heap(Vec<u8>),
+}
+
+impl InfoBytes {
+    /// Returns `len` zeroed bytes, stored inline if small enough.
+    pub fn zeroed(len: usize) -> InfoBytes {
+        if len <= info_bytes_inline_threshold() {
+            InfoBytes::Inline([0; INFO_BYTES_INLINE_MAX], len)
+        } else {
+            InfoBytes::Heap(vec![0; len])
+        }
+    }
+
+    /// Returns true if the bytes are stored inline.
+    #[cfg(test)]
+    pub fn is_inline(&self) -> bool {
+        match *self {
+            InfoBytes::Inline(..) => true,
+            InfoBytes::Heap(_) => false,
+        }
+    }
+
+    /// Shortens the bytes to `len`, having no effect if they are already no
+    /// longer than `len`.
+    pub fn truncate(&mut self, len: usize) {
+        match *self {
+            InfoBytes::Inline(_, ref mut l) => *l = cmp::min(*l, len),
+            InfoBytes::Heap(ref mut vec) => vec.truncate(len),
+        }
+    }
+
+    /// Returns the bytes as a `Vec`, copying them if stored inline.
+    pub fn into_vec(self) -> Vec<u8> {
+        match self {
+            InfoBytes::Inline(bytes, len) => bytes[..len].to_vec(),
+            InfoBytes::Heap(vec) => vec,
+        }
+    }
+}
+
+impl Deref for InfoBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match *self {
+            InfoBytes::Inline(ref bytes, len) => &bytes[..len],
+            InfoBytes::Heap(ref vec) => vec,
+        }
+    }
+}
+
+impl DerefMut for InfoBytes {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match *self {
+            InfoBytes::Inline(ref mut bytes, len) => &mut bytes[..len],
+            InfoBytes::Heap(ref mut vec) => vec,
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Host-side equivalents of the OpenCL vector types (`float4`, `uint2`,
+//! etc.).
+//!
+//! Each type has the same size and alignment as its device counterpart. An
+//! n-component vector is aligned to its own size and 3-component vectors
+//! occupy (and are aligned to) the space of 4 components, as required by the
+//! OpenCL specification (section 6.1.5).
+//!
+//! Components are accessible by index (`vec[0]`), through the public inner
+//! array (`vec.0`), or by converting to and from arrays:
+//!
+//! ```text
+//! let mut v = ClFloat4::from([1.0, 2.0, 3.0, 4.0]);
+//! v[3] += 1.0;
+//! let arr: [f32; 4] = v.into();
+//! ```
+//!
+//! The 2, 3, and 4-component types also convert to and from tuples and,
+//! with the `mint` feature enabled, `mint::Vector2`/`Vector3`/`Vector4`.
+//!
+//! ## Zero-Copy Conversion
+//!
+//! Slices of arrays (or `mint` vectors) with the same size as a vector type
+//! can be reinterpreted without copying using `AsClSlice::as_cl_slice`
+//! provided they are suitably aligned (checked at run time). The reverse,
+//! `ClFloat4::as_array_slice` etc., always succeeds. Neither is available
+//! for the 3-component types, which contain padding.
+//!
+//! ```text
+//! let positions: Vec<[f32; 4]> = ...;
+//! match positions.as_cl_slice() {
+//!     Some(vecs) => buffer.write(vecs).enq().unwrap(),
+//!     None => buffer.write(&positions.iter().map(|&p| ClFloat4::from(p))
+//!         .collect::<Vec<_>>()).enq().unwrap(),
+//! }
+//! ```
+
+use std::mem;
+use std::slice;
+use std::ops::{Index, IndexMut};
+use core::OclPrm;
+use util;
+
+/// Slices which can be reinterpreted as a slice of an OpenCL vector type
+/// without copying.
+pub trait AsClSlice<V: OclPrm> {
+    /// Returns the contents of this slice as a slice of `V` if the slice
+    /// is aligned appropriately for `V`, otherwise `None`.
+    fn as_cl_slice(&self) -> Option<&[V]>;
+}
+
+macro_rules! cl_vec {
+    ($name:ident, $cl_name:expr, $scl:ty, $len:expr, $size:expr, $align:tt) => {
+        #[doc = "The OpenCL `"]
+        #[doc = $cl_name]
+        #[doc = "` vector type."]
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        #[repr(C, align($align))]
+        pub struct $name(pub [$scl; $len]);
+
+        unsafe impl OclPrm for $name {
+            fn swap_bytes(self) -> Option<$name> {
+                let mut vec = self;
+                for c in vec.0.iter_mut() {
+                    *c = match OclPrm::swap_bytes(*c) {
+                        Some(swapped) => swapped,
+                        None => return None,
+                    };
+                }
+                Some(vec)
+            }
+        }
+
+        impl Index<usize> for $name {
+            type Output = $scl;
+
+            fn index(&self, idx: usize) -> &$scl {
+                &self.0[idx]
+            }
+        }
+
+        impl IndexMut<usize> for $name {
+            fn index_mut(&mut self, idx: usize) -> &mut $scl {
+                &mut self.0[idx]
+            }
+        }
+
+        impl From<[$scl; $len]> for $name {
+            fn from(arr: [$scl; $len]) -> $name {
+                $name(arr)
+            }
+        }
+
+        impl From<$name> for [$scl; $len] {
+            fn from(vec: $name) -> [$scl; $len] {
+                vec.0
+            }
+        }
+
+        const _: () = assert!(mem::size_of::<$name>() == $size &&
+            mem::align_of::<$name>() == $align);
+    };
+}
+
+/// Zero-copy conversion to a slice of arrays for vector types without
+/// padding.
+macro_rules! cl_vec_array_slice {
+    ($name:ident, $scl:ty, $len:expr) => {
+        impl $name {
+            /// Reinterprets a slice of vectors as a slice of arrays without
+            /// copying.
+            pub fn as_array_slice(vecs: &[$name]) -> &[[$scl; $len]] {
+                unsafe { slice::from_raw_parts(vecs.as_ptr() as *const [$scl; $len], vecs.len()) }
+            }
+        }
+
+        cl_vec_as_cl_slice!($name, [$scl; $len]);
+    };
+}
+
+/// Zero-copy conversion from a slice of `$src`, which must have the same
+/// layout as the vector type apart from alignment.
+macro_rules! cl_vec_as_cl_slice {
+    ($name:ident, $src:ty) => {
+        impl AsClSlice<$name> for [$src] {
+            fn as_cl_slice(&self) -> Option<&[$name]> {
+                if util::is_aligned(self.as_ptr() as usize, mem::align_of::<$name>())
+                        .unwrap_or(false) {
+                    Some(unsafe { slice::from_raw_parts(self.as_ptr() as *const $name,
+                        self.len()) })
+                } else {
+                    None
+                }
+            }
+        }
+
+        const _: () = assert!(mem::size_of::<$name>() == mem::size_of::<$src>());
+    };
+}
+
+/// Conversions to and from tuples and `mint` vectors.
+macro_rules! cl_vec_tuple {
+    ($name:ident, $scl:ty, $tup:ty, ($($idx:tt),+), $mint:ident, ($($field:ident),+)) => {
+        impl From<$tup> for $name {
+            fn from(tup: $tup) -> $name {
+                $name([$(tup.$idx),+])
+            }
+        }
+
+        impl From<$name> for $tup {
+            fn from(vec: $name) -> $tup {
+                ($(vec.0[$idx]),+)
+            }
+        }
+
+        #[cfg(feature = "mint")]
+        impl From<::mint::$mint<$scl>> for $name {
+            fn from(vec: ::mint::$mint<$scl>) -> $name {
+                $name([$(vec.$field),+])
+            }
+        }
+
+        #[cfg(feature = "mint")]
+        impl From<$name> for ::mint::$mint<$scl> {
+            fn from(vec: $name) -> ::mint::$mint<$scl> {
+                ::mint::$mint { $($field: vec.0[$idx]),+ }
+            }
+        }
+    };
+}
+
+macro_rules! cl_vecs {
+    ($scl:ty, $scl_size:expr, $n2:ident, $n3:ident, $n4:ident, $n8:ident, $n16:ident,
+            $c2:expr, $c3:expr, $c4:expr, $c8:expr, $c16:expr, $a2:tt, $a4:tt, $a8:tt, $a16:tt) => {
+        cl_vec!($n2, $c2, $scl, 2, $scl_size * 2, $a2);
+        cl_vec!($n3, $c3, $scl, 3, $scl_size * 4, $a4);
+        cl_vec!($n4, $c4, $scl, 4, $scl_size * 4, $a4);
+        cl_vec!($n8, $c8, $scl, 8, $scl_size * 8, $a8);
+        cl_vec!($n16, $c16, $scl, 16, $scl_size * 16, $a16);
+
+        cl_vec_tuple!($n2, $scl, ($scl, $scl), (0, 1), Vector2, (x, y));
+        cl_vec_tuple!($n3, $scl, ($scl, $scl, $scl), (0, 1, 2), Vector3, (x, y, z));
+        cl_vec_tuple!($n4, $scl, ($scl, $scl, $scl, $scl), (0, 1, 2, 3), Vector4, (x, y, z, w));
+
+        cl_vec_array_slice!($n2, $scl, 2);
+        cl_vec_array_slice!($n4, $scl, 4);
+        cl_vec_array_slice!($n8, $scl, 8);
+        cl_vec_array_slice!($n16, $scl, 16);
+
+        #[cfg(feature = "mint")] cl_vec_as_cl_slice!($n2, ::mint::Vector2<$scl>);
+        #[cfg(feature = "mint")] cl_vec_as_cl_slice!($n4, ::mint::Vector4<$scl>);
+    };
+}
+
+cl_vecs!(i8, 1, ClChar2, ClChar3, ClChar4, ClChar8, ClChar16,
+    "char2", "char3", "char4", "char8", "char16", 2, 4, 8, 16);
+cl_vecs!(u8, 1, ClUchar2, ClUchar3, ClUchar4, ClUchar8, ClUchar16,
+    "uchar2", "uchar3", "uchar4", "uchar8", "uchar16", 2, 4, 8, 16);
+cl_vecs!(i16, 2, ClShort2, ClShort3, ClShort4, ClShort8, ClShort16,
+    "short2", "short3", "short4", "short8", "short16", 4, 8, 16, 32);
+cl_vecs!(u16, 2, ClUshort2, ClUshort3, ClUshort4, ClUshort8, ClUshort16,
+    "ushort2", "ushort3", "ushort4", "ushort8", "ushort16", 4, 8, 16, 32);
+cl_vecs!(i32, 4, ClInt2, ClInt3, ClInt4, ClInt8, ClInt16,
+    "int2", "int3", "int4", "int8", "int16", 8, 16, 32, 64);
+cl_vecs!(u32, 4, ClUint2, ClUint3, ClUint4, ClUint8, ClUint16,
+    "uint2", "uint3", "uint4", "uint8", "uint16", 8, 16, 32, 64);
+cl_vecs!(i64, 8, ClLong2, ClLong3, ClLong4, ClLong8, ClLong16,
+    "long2", "long3", "long4", "long8", "long16", 16, 32, 64, 128);
+cl_vecs!(u64, 8, ClUlong2, ClUlong3, ClUlong4, ClUlong8, ClUlong16,
+    "ulong2", "ulong3", "ulong4", "ulong8", "ulong16", 16, 32, 64, 128);
+cl_vecs!(f32, 4, ClFloat2, ClFloat3, ClFloat4, ClFloat8, ClFloat16,
+    "float2", "float3", "float4", "float8", "float16", 8, 16, 32, 64);
+cl_vecs!(f64, 8, ClDouble2, ClDouble3, ClDouble4, ClDouble8, ClDouble16,
+    "double2", "double3", "double4", "double8", "double16", 16, 32, 64, 128);
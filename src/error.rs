@@ -9,9 +9,14 @@ use std::convert::Into;
 // use std::collections::str::FromUtf8Error;
 // use std::ffi;
 use std::default::Default;
+use std::thread;
+use std::time::Duration;
 use num::FromPrimitive;
 
 use cl_h::Status;
+use core::BuildStatus;
+use util::Diagnostics;
+
 /// `ocl::Error` result type.
 pub type Result<T> = std::result::Result<T, self::Error>;
 
@@ -20,6 +25,41 @@ pub type Result<T> = std::result::Result<T, self::Error>;
 ///
 /// Implements the usual error traits.
 ///
+/// Errors returned by the OpenCL API are represented by the `Status` variant
+/// which contains the typed status code (`ocl::enums::Status`), the name of
+/// the function which returned it, and some optional extra info. Match on it
+/// rather than on the error text:
+///
+/// ```text
+/// match result {
+///     Err(ocl::Error::Status { status: Status::CL_OUT_OF_RESOURCES, .. }) => { ... },
+///     ...
+/// }
+/// ```
+///
+/// Wrapped errors (`Nul`, `Io`, and `FromUtf8Error`) are available through
+/// `std::error::Error::source`. `Error` is `Send + Sync + 'static` and can
+/// therefore be converted into `Box<std::error::Error + Send + Sync>` (or
+/// `anyhow::Error`) using `?`:
+///
+/// ```
+/// use std::error::Error as StdError;
+///
+/// fn read_src() -> ocl::Result<String> {
+///     let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "kernel.cl");
+///     Err(io_err.into())
+/// }
+///
+/// fn app() -> Result<String, Box<StdError + Send + Sync>> {
+///     let src = read_src()?;
+///     Ok(src)
+/// }
+///
+/// let err = app().unwrap_err();
+/// let ocl_err = err.downcast_ref::<ocl::Error>().unwrap();
+/// assert!(ocl_err.source().unwrap().is::<std::io::Error>());
+/// ```
+///
 /// ## Stability
 ///
 /// The `String` variant may eventually be removed. Many more variants and
@@ -29,7 +69,7 @@ pub type Result<T> = std::result::Result<T, self::Error>;
 /// unique to each function which generates it (yeah that'll be fun to
 /// implement).
 ///
-/// `UnspecifiedDimensions` may be moved into a sub-type.
+/// `DimsUnspecified` may be moved into a sub-type.
 ///
 /// For now, don't assume the existence of or check for any of the above.
 ///
@@ -41,7 +81,13 @@ pub enum Error {
     Nul(std::ffi::NulError),
     Io(std::io::Error),
     FromUtf8Error(std::string::FromUtf8Error),
-    UnspecifiedDimensions,
+    DimsUnspecified { op: &'static str, desc: String },
+    ProgramBuild(ProgramBuildError),
+    ExtensionUnavailable(String),
+    LibraryNotLoaded,
+    StubBackend(&'static str),
+    NoPlatforms(Diagnostics),
+    VersionUnsupported { op: &'static str, required: (u32, u32), found: (u32, u32) },
 }
 
 impl self::Error {
@@ -58,12 +104,17 @@ impl self::Error {
 
     /// Returns a new `ocl::Result::Err` containing an `ocl::Error` with the 
     /// given error code and description.
+    ///
+    /// Returns `Ok(T::default())` if `errcode` is `CL_SUCCESS`. Error codes
+    /// unknown to `Status` (vendor specific codes, for example) produce a
+    /// `String` variant containing the raw code.
     pub fn err_status<T: Default, S: Into<String>>(errcode: i32, fn_name: &'static str, fn_info: S) 
             -> self::Result<T> 
     {
         let status = match Status::from_i32(errcode) {
             Some(s) => s,
-            None => panic!("ocl::core::errcode_try(): Invalid error code: '{}'. Aborting.", errcode),
+            None => return Error::err(format!("Error executing function: {}(\"{}\"): \
+                Unknown status error code: '{}'.", fn_name, fn_info.into(), errcode)),
         };
 
         if let Status::CL_SUCCESS = status {
@@ -75,6 +126,31 @@ impl self::Error {
         }
     }
 
+    /// Returns a new `DimsUnspecified` error for the operation, `op`, which
+    /// required dimensions which were never specified.
+    pub fn dims_unspecified(op: &'static str) -> self::Error {
+        Error::DimsUnspecified {
+            op: op,
+            desc: format!("{}: No dimensions have been specified. Dimensions must be set \
+                before this operation can be performed.", op),
+        }
+    }
+
+    /// Returns a new `ocl::Result::Err` containing a `DimsUnspecified` error
+    /// for the operation, `op`.
+    pub fn err_dims_unspecified<T>(op: &'static str) -> self::Result<T> {
+        Err(Error::dims_unspecified(op))
+    }
+
+    /// Returns a new `ocl::Result::Err` containing a `VersionUnsupported`
+    /// error for the operation, `op`, which requires OpenCL `required` where
+    /// only `found` is supported.
+    pub fn err_version_unsupported<T>(op: &'static str, required: (u32, u32), found: (u32, u32))
+            -> self::Result<T>
+    {
+        Err(Error::VersionUnsupported { op: op, required: required, found: found })
+    }
+
     /// If this is a `String` variant, concatenate `txt` to the front of the
     /// contained string. Otherwise, do nothing at all.
     pub fn prepend<'s, S: AsRef<&'s str>>(&'s mut self, txt: S) {
@@ -96,7 +172,46 @@ impl self::Error {
             &Error::Status { ref status, .. } => format!("{:?}", status),
             _ => String::from(""),
         }
-    }    
+    }
+
+    /// Returns the status code if this is a `Status` variant.
+    pub fn status(&self) -> Option<Status> {
+        match self {
+            &Error::Status { ref status, .. } => Some(status.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this is a `Status` variant containing `status`.
+    pub fn is_status(&self, status: Status) -> bool {
+        match self {
+            &Error::Status { status: ref s, .. } => *s == status,
+            _ => false,
+        }
+    }
+
+    /// Returns true if the error was caused by a failure to allocate memory
+    /// or resources on either the host or the device (`CL_OUT_OF_HOST_MEMORY`,
+    /// `CL_OUT_OF_RESOURCES`, or `CL_MEM_OBJECT_ALLOCATION_FAILURE`).
+    pub fn is_out_of_memory(&self) -> bool {
+        self.is_status(Status::CL_OUT_OF_HOST_MEMORY) ||
+            self.is_status(Status::CL_OUT_OF_RESOURCES) ||
+            self.is_status(Status::CL_MEM_OBJECT_ALLOCATION_FAILURE)
+    }
+
+    /// Returns true if the status is `CL_INVALID_ARG_VALUE`.
+    pub fn is_invalid_arg_value(&self) -> bool {
+        self.is_status(Status::CL_INVALID_ARG_VALUE)
+    }
+
+    /// Returns true if this is a `Status` error which may succeed if retried
+    /// (see `Status::is_retryable`). All other variants are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            &Error::Status { ref status, .. } => status.is_retryable(),
+            _ => false,
+        }
+    }
 }
 
 impl std::error::Error for self::Error {
@@ -107,11 +222,30 @@ impl std::error::Error for self::Error {
             &Error::FromUtf8Error(ref err) => err.description(),
             &Error::Status { ref desc, .. } => desc,
             &Error::String(ref desc) => &desc,
-            &Error::UnspecifiedDimensions => "Cannot convert to a valid set of dimensions. \
-                Please specify some dimensions.",
+            &Error::DimsUnspecified { ref desc, .. } => desc,
+            &Error::ProgramBuild(ref err) => err.description(),
+            &Error::ExtensionUnavailable(_) => "Extension function unavailable.",
+            &Error::LibraryNotLoaded => "The OpenCL library could not be loaded. Ensure that an \
+                OpenCL implementation (ICD loader) is installed or set `OCL_LIBRARY` to the name \
+                or path of the library to load.",
+            &Error::StubBackend(_) => "Not supported by the stub backend.",
+            &Error::NoPlatforms(_) => "No OpenCL platforms found.",
+            &Error::VersionUnsupported { .. } => "Unsupported OpenCL version.",
             // _ => panic!("OclError::description()"),
         }
     }
+
+    /// Returns the underlying error for the `Nul`, `Io`, `FromUtf8Error`, and
+    /// `ProgramBuild` variants.
+    fn source(&self) -> Option<&(std::error::Error + 'static)> {
+        match self {
+            &Error::Nul(ref err) => Some(err),
+            &Error::Io(ref err) => Some(err),
+            &Error::FromUtf8Error(ref err) => Some(err),
+            &Error::ProgramBuild(ref err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 impl Into<String> for self::Error {
@@ -151,17 +285,228 @@ impl From<std::string::FromUtf8Error> for self::Error {
     }
 }
 
+/// `Status` errors are displayed compactly on a single line as:
+/// `clFunctionName(key=value ...): CL_STATUS_NAME (code)`. Use `{:?}` for the
+/// full, more verbose, description.
 impl std::fmt::Display for self::Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         use std::error::Error;
-        f.write_str(&self.description())
+        match self {
+            &self::Error::Status { ref status, fn_name, ref fn_info, .. } => {
+                write!(f, "{}({}): {:?} ({})", fn_name, fn_info, status, status.clone() as i32)
+            },
+            &self::Error::Nul(ref err) => write!(f, "{}", err),
+            &self::Error::Io(ref err) => write!(f, "{}", err),
+            &self::Error::FromUtf8Error(ref err) => write!(f, "{}", err),
+            &self::Error::ProgramBuild(ref err) => write!(f, "{}", err),
+            &self::Error::ExtensionUnavailable(ref name) => write!(f, "The extension \
+                function, '{}', is unavailable on this platform.", name),
+            &self::Error::StubBackend(fn_name) => write!(f, "{}: Not supported by the stub \
+                backend (the `stub` feature is enabled), which never executes anything.", fn_name),
+            &self::Error::NoPlatforms(ref diagnostics) => write!(f, "No OpenCL platforms \
+                found.\n{}", diagnostics),
+            &self::Error::VersionUnsupported { op, required, found } => write!(f, "{}: Requires \
+                OpenCL {}.{} or later, found {}.{}.", op, required.0, required.1, found.0,
+                found.1),
+            _ => f.write_str(&self.description()),
+        }
     }
 }
 
 impl std::fmt::Debug for self::Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         use std::error::Error;
-        f.write_str(&self.description())
+        match self {
+            &self::Error::ProgramBuild(ref err) => write!(f, "{}", err),
+            &self::Error::ExtensionUnavailable(_) => write!(f, "{}", self),
+            &self::Error::StubBackend(_) => write!(f, "{}", self),
+            &self::Error::NoPlatforms(_) => write!(f, "{}", self),
+            &self::Error::VersionUnsupported { .. } => write!(f, "{}", self),
+            _ => f.write_str(&self.description()),
+        }
+    }
+}
+
+impl From<ProgramBuildError> for self::Error {
+    fn from(err: ProgramBuildError) -> self::Error {
+        self::Error::ProgramBuild(err)
+    }
+}
+
+
+/// Retries a fallible operation with exponential backoff for as long as the
+/// errors it returns are retryable.
+///
+/// Errors are classified using `Error::is_retryable` unless a custom
+/// classifier has been set with `::classifier`.
+///
+/// The closure passed to `::run` is given the (zero-based) attempt number
+/// which can be used, for example, to reduce the batch size on each retry:
+///
+/// ```text
+/// let policy = RetryPolicy::new().max_attempts(4);
+/// let result = policy.run(|attempt| enqueue_batch(batch_size >> attempt));
+/// ```
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_delay: Duration,
+    backoff_factor: u32,
+    max_delay: Duration,
+    classifier: Option<Box<Fn(&Error) -> bool + Send + Sync>>,
+}
+
+impl RetryPolicy {
+    /// Returns a new `RetryPolicy` which makes up to 3 attempts, waiting
+    /// 10ms before the first retry and doubling the delay thereafter, up to
+    /// a maximum of 1s.
+    pub fn new() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(10),
+            backoff_factor: 2,
+            max_delay: Duration::from_secs(1),
+            classifier: None,
+        }
+    }
+
+    /// Sets the maximum number of attempts, including the first (builder-style).
+    pub fn max_attempts(mut self, max_attempts: u32) -> RetryPolicy {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the delay before the first retry (builder-style).
+    pub fn initial_delay(mut self, initial_delay: Duration) -> RetryPolicy {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    /// Sets the factor the delay is multiplied by after each retry
+    /// (builder-style).
+    pub fn backoff_factor(mut self, backoff_factor: u32) -> RetryPolicy {
+        self.backoff_factor = backoff_factor;
+        self
+    }
+
+    /// Sets the longest delay between attempts (builder-style). The delay
+    /// stops growing once it reaches this value.
+    pub fn max_delay(mut self, max_delay: Duration) -> RetryPolicy {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Overrides the default classification of retryable errors
+    /// (builder-style).
+    pub fn classifier<F>(mut self, classifier: F) -> RetryPolicy
+            where F: Fn(&Error) -> bool + Send + Sync + 'static
+    {
+        self.classifier = Some(Box::new(classifier));
+        self
+    }
+
+    /// Returns true if `err` should be retried under this policy.
+    pub fn is_retryable(&self, err: &Error) -> bool {
+        match self.classifier {
+            Some(ref classifier) => classifier(err),
+            None => err.is_retryable(),
+        }
+    }
+
+    /// Calls `op` until it succeeds, returns an error which is not retryable,
+    /// or the maximum number of attempts has been made. Returns the result of
+    /// the last attempt.
+    pub fn run<T, F>(&self, mut op: F) -> self::Result<T>
+            where F: FnMut(u32) -> self::Result<T>
+    {
+        let mut delay = std::cmp::min(self.initial_delay, self.max_delay);
+        let mut attempt = 0;
+
+        loop {
+            match op(attempt) {
+                Err(err) => {
+                    if attempt + 1 >= self.max_attempts || !self.is_retryable(&err) {
+                        return Err(err);
+                    }
+                },
+                ok => return ok,
+            }
+
+            thread::sleep(delay);
+            delay = match delay.checked_mul(self.backoff_factor) {
+                Some(next) => std::cmp::min(next, self.max_delay),
+                None => self.max_delay,
+            };
+            attempt += 1;
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy::new()
+    }
+}
+
+
+/// A program build failure containing the build status and build log of
+/// each device the program was built for.
+///
+/// Returned within `ocl::Error::ProgramBuild` by `core::build_program`,
+/// `Program::new`, `ProgramBuilder::build`, and `ProQueBuilder::build`.
+///
+/// ```text
+/// match Program::builder().src(src).build(&context) {
+///     Err(ocl::Error::ProgramBuild(ref err)) => show_log(err.first_log().unwrap_or("")),
+///     ...
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ProgramBuildError {
+    logs: Vec<(String, BuildStatus, String)>,
+}
+
+impl ProgramBuildError {
+    /// Returns a new `ProgramBuildError` from a list of
+    /// `(device name, build status, build log)` entries, one per device.
+    pub fn new(logs: Vec<(String, BuildStatus, String)>) -> ProgramBuildError {
+        ProgramBuildError { logs: logs }
+    }
+
+    /// Returns the `(device name, build status, build log)` entry for each
+    /// device.
+    pub fn logs(&self) -> &[(String, BuildStatus, String)] {
+        &self.logs
+    }
+
+    /// Returns the first non-empty build log, if any.
+    pub fn first_log(&self) -> Option<&str> {
+        self.logs.iter().map(|&(_, _, ref log)| log.as_str()).find(|log| !log.trim().is_empty())
+    }
+}
+
+impl std::error::Error for ProgramBuildError {
+    fn description(&self) -> &str {
+        "OpenCL program build failed"
+    }
+}
+
+impl std::fmt::Display for ProgramBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        try!(write!(f, "\n\n\
+            ###################### OPENCL PROGRAM BUILD DEBUG OUTPUT ######################\n"));
+
+        for &(ref device_name, ref status, ref log) in self.logs.iter() {
+            try!(write!(f, "\n------ Device: {} (Build Status: {:?}) ------\n\n{}\n",
+                device_name, status, log.trim_end()));
+        }
+
+        write!(f, "\n###############################################################################\n\n")
+    }
+}
+
+impl std::fmt::Debug for ProgramBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self)
     }
 }
 
@@ -179,9 +524,9 @@ fn fmt_status_desc(status: Status, fn_name: &'static str, fn_info: &str) -> Stri
     format!("\n\n\
         ################################ OPENCL ERROR ############################### \
         \n\nError executing function: {}{}  \
-        \n\nStatus error code: {:?} ({})  \
+        \n\nStatus error code: {:?} ({}): {}  \
         \n\nPlease visit the following url for more information: \n\n{}{}{}  \n\n\
         ############################################################################# \n",
-        fn_name, fn_info_string, status.clone(), status as i32, 
+        fn_name, fn_info_string, status.clone(), status.clone() as i32, status.description(), 
         SDK_DOCS_URL_PRE, fn_name, SDK_DOCS_URL_SUF)
 }
\ No newline at end of file
@@ -35,40 +35,81 @@
 // #![feature(question_mark)]
 // #![feature(stmt_expr_attributes)]
 
+#[cfg(all(feature = "stub", feature = "dynamic-loading"))]
+compile_error!("The `stub` and `dynamic-loading` features are mutually exclusive.");
+
 #[macro_use] extern crate enum_primitive;
 #[macro_use] extern crate bitflags;
 extern crate libc;
 extern crate num;
 extern crate rand;
+#[cfg(feature = "f16")] extern crate half;
+#[cfg(feature = "mint")] extern crate mint;
+#[cfg(feature = "ndarray")] extern crate ndarray;
+#[cfg(feature = "serde")] #[macro_use] extern crate serde;
+#[cfg(feature = "logging")] #[macro_use] extern crate log;
+#[cfg(all(test, feature = "serde"))] extern crate serde_json;
 
 pub mod util;
-#[cfg(test)] mod tests;
-mod standard;
+#[macro_use] mod standard;
 mod error;
-pub mod core;
+#[macro_use] pub mod core;
 pub mod cl_h;
+#[cfg(feature = "dynamic-loading")] mod loader;
+#[cfg(feature = "stub")] mod stub;
+#[cfg(any(test, feature = "testing"))] pub mod testing;
+#[cfg(test)] mod tests;
 
-pub use standard::{Platform, Device, Context, Program, Queue, Kernel, Buffer, Image, Event, 
-	EventList, Sampler, SpatialDims, ProQue};
-pub use self::error::{Error, Result};
+pub use standard::{Platform, Device, Context, Program, Queue, Kernel, Buffer, BufferView, Image,
+	Event, EventList, Sampler, SpatialDims, SpatialDimsCoords, ProQue, StagingPool, DoubleBuffer, DeviceLimits,
+	SharedKernel, Scope, scope, Graph, GraphEvents, WeakContext, WeakQueue, WeakProgram, Local, report,
+	BenchWorkload, BenchReport, BenchEntry, ImageOrigin, ImageRegion, BinaryDump,
+	TimelineTranslator, EventSpans, MappedMem};
+pub use self::error::{Error, Result, ProgramBuildError, RetryPolicy};
+pub use core::{is_opencl_available, selection_report};
+pub use util::{diagnostics, Diagnostics};
+#[cfg(feature = "f16")] pub use half::f16;
+#[cfg(feature = "complex")] pub use num::complex::Complex;
 
 
 pub mod traits {
 	//! Commonly used traits.
-	pub use standard::{WorkDims, MemLen};
-	pub use core::OclPrm;
+	pub use standard::{WorkDims, MemLen, ArgVal};
+	pub use core::{OclPrm, OclScl, AsClSlice};
+}
+
+pub mod multi {
+	//! Running one job across several devices.
+
+	pub use standard::run_partitioned;
 }
 
 pub mod builders {
 	//! Builders and associated settings-related types.
 
-	pub use standard::{ContextBuilder, BuildOpt, ProgramBuilder, ImageBuilder, ProQueBuilder,
+	pub use standard::{ContextBuilder, BuildOpt, DefineValue, ProgramBuilder, ImageBuilder, BufferBuilder, ProQueBuilder,
 		DeviceSpecifier, BufferCmd, BufferCmdKind, BufferCmdDataShape, 
 		ImageCmd, ImageCmdKind, KernelCmd};
 	pub use core::{ImageFormat, ImageDescriptor, ContextProperties};
 	// #[cfg(not(release))] pub use standard::BufferTest;
 }
 
+pub mod vectors {
+	//! Host-side equivalents of the OpenCL vector types.
+
+	pub use core::{AsClSlice,
+		ClChar2, ClChar3, ClChar4, ClChar8, ClChar16,
+		ClUchar2, ClUchar3, ClUchar4, ClUchar8, ClUchar16,
+		ClShort2, ClShort3, ClShort4, ClShort8, ClShort16,
+		ClUshort2, ClUshort3, ClUshort4, ClUshort8, ClUshort16,
+		ClInt2, ClInt3, ClInt4, ClInt8, ClInt16,
+		ClUint2, ClUint3, ClUint4, ClUint8, ClUint16,
+		ClLong2, ClLong3, ClLong4, ClLong8, ClLong16,
+		ClUlong2, ClUlong3, ClUlong4, ClUlong8, ClUlong16,
+		ClFloat2, ClFloat3, ClFloat4, ClFloat8, ClFloat16,
+		ClDouble2, ClDouble3, ClDouble4, ClDouble8, ClDouble16};
+}
+
 pub mod flags {
 	//! Bitflags for various parameter types.
 
@@ -95,9 +136,6 @@ pub mod flags {
 		MemMigrationFlags, MIGRATE_MEM_OBJECT_HOST, MIGRATE_MEM_OBJECT_CONTENT_UNDEFINED,
 		// cl_map_flags - bitfield
 		MapFlags, MAP_READ, MAP_WRITE, MAP_WRITE_INVALIDATE_REGION,
-		// cl_program_binary_type
-		ProgramBinaryType, PROGRAM_BINARY_TYPE_NONE, PROGRAM_BINARY_TYPE_COMPILED_OBJECT, 
-			PROGRAM_BINARY_TYPE_LIBRARY, PROGRAM_BINARY_TYPE_EXECUTABLE,
 		// cl_kernel_arg_type_qualifer 
 		KernelArgTypeQualifier, KERNEL_ARG_TYPE_NONE, KERNEL_ARG_TYPE_CONST, 
 			KERNEL_ARG_TYPE_RESTRICT, KERNEL_ARG_TYPE_VOLATILE,	
@@ -107,14 +145,15 @@ pub mod flags {
 pub mod enums {
 	//! Enumerators for settings and information requests.
 
-	pub use standard::{DeviceSpecifier, BufferCmdKind, BufferCmdDataShape};
+	pub use standard::{DeviceSpecifier, BufferCmdKind, BufferCmdDataShape, TransferMode,
+		DefineStrategy, WaitOutcome};
 
 	// API enums.
 	pub use core::{ImageChannelOrder, ImageChannelDataType, Cbool, Polling, PlatformInfo,
 		DeviceInfo, DeviceMemCacheType, DeviceLocalMemType, ContextInfo,
 		ContextInfoOrPropertiesPointerType, PartitionProperty, CommandQueueInfo, ChannelType, 
 		MemObjectType, MemInfo, ImageInfo, AddressingMode, FilterMode, SamplerInfo, ProgramInfo,
-		ProgramBuildInfo, BuildStatus, KernelInfo, KernelArgInfo, KernelArgAddressQualifier, 
+		ProgramBuildInfo, BuildStatus, ProgramBinaryType, KernelInfo, KernelArgInfo, KernelArgAddressQualifier, 
 		KernelArgAccessQualifier, KernelWorkGroupInfo, EventInfo, CommandType, 
 		CommandExecutionStatus, BufferCreateType, ProfilingInfo};
 
@@ -0,0 +1,245 @@
+//! Runtime loading of the OpenCL library (the `dynamic-loading` feature).
+//!
+//! The library is opened the first time any `cl_h` function is called (or
+//! `is_opencl_available` is), trying the name in the `OCL_LIBRARY`
+//! environment variable if set or the usual name(s) for the platform
+//! otherwise. Each symbol is then resolved and cached upon its first use.
+//!
+//! When the library, or a symbol (a function introduced in a later OpenCL
+//! version than the library provides, for example), is unavailable, the
+//! `cl_h` function returns an error code or null pointer without calling
+//! anything and records the reason. `core` functions report it (see
+//! `take_error`) in place of the error the code would otherwise produce.
+//! The reason is cleared by the next `cl_h` call on the same thread, so it
+//! only ever describes the most recent one.
+
+use std::cell::Cell;
+use std::env;
+use std::ffi::{CStr, CString};
+use std::sync::Once;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use libc::c_void;
+use error::Error as OclError;
+
+/// The environment variable which, if set, overrides the name (or path) of
+/// the library to load.
+pub const LIBRARY_VAR: &'static str = "OCL_LIBRARY";
+
+#[cfg(target_os = "macos")]
+static LIBRARY_NAMES: &'static [&'static str] =
+    &["/System/Library/Frameworks/OpenCL.framework/OpenCL"];
+#[cfg(target_os = "windows")]
+static LIBRARY_NAMES: &'static [&'static str] = &["OpenCL.dll"];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+static LIBRARY_NAMES: &'static [&'static str] = &["libOpenCL.so.1", "libOpenCL.so"];
+
+/// Functions introduced after OpenCL 1.0, with the version introducing
+/// them. Any other function is assumed to be from 1.0.
+static FUNCTION_VERSIONS: &'static [(&'static str, (u32, u32))] = &[
+    ("clCreateSubBuffer", (1, 1)),
+    ("clSetMemObjectDestructorCallback", (1, 1)),
+    ("clCreateUserEvent", (1, 1)),
+    ("clSetUserEventStatus", (1, 1)),
+    ("clSetEventCallback", (1, 1)),
+    ("clEnqueueReadBufferRect", (1, 1)),
+    ("clEnqueueWriteBufferRect", (1, 1)),
+    ("clEnqueueCopyBufferRect", (1, 1)),
+    ("clCreateSubDevices", (1, 2)),
+    ("clRetainDevice", (1, 2)),
+    ("clReleaseDevice", (1, 2)),
+    ("clCreateImage", (1, 2)),
+    ("clCreateProgramWithBuiltInKernels", (1, 2)),
+    ("clCompileProgram", (1, 2)),
+    ("clLinkProgram", (1, 2)),
+    ("clUnloadPlatformCompiler", (1, 2)),
+    ("clGetKernelArgInfo", (1, 2)),
+    ("clEnqueueFillBuffer", (1, 2)),
+    ("clEnqueueFillImage", (1, 2)),
+    ("clEnqueueMigrateMemObjects", (1, 2)),
+    ("clEnqueueMarkerWithWaitList", (1, 2)),
+    ("clEnqueueBarrierWithWaitList", (1, 2)),
+    ("clGetExtensionFunctionAddressForPlatform", (1, 2)),
+    ("clGetDeviceAndHostTimer", (2, 1)),
+    ("clGetHostTimer", (2, 1)),
+];
+
+/// OpenCL versions, oldest first.
+static VERSIONS: &'static [(u32, u32)] = &[(1, 0), (1, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+
+/// Address value of a symbol which has been looked up and not found.
+const MISSING: usize = 1;
+
+static LOAD: Once = Once::new();
+static LIBRARY: AtomicUsize = AtomicUsize::new(0);
+
+/// Why the most recent call to a `cl_h` function on this thread did not
+/// reach the library.
+#[derive(Clone, Copy, Debug)]
+enum LoadError {
+    LibraryNotLoaded,
+    SymbolMissing(&'static str),
+}
+
+thread_local! {
+    static LOAD_ERROR: Cell<Option<LoadError>> = Cell::new(None);
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::ffi::CStr;
+    use libc::{self, c_void};
+
+    pub unsafe fn open(name: &CStr) -> *mut c_void {
+        libc::dlopen(name.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL)
+    }
+
+    pub unsafe fn symbol(library: *mut c_void, name: &CStr) -> *mut c_void {
+        libc::dlsym(library, name.as_ptr())
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::ffi::CStr;
+    use libc::{c_void, c_char};
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn LoadLibraryA(file_name: *const c_char) -> *mut c_void;
+        fn GetProcAddress(module: *mut c_void, proc_name: *const c_char) -> *mut c_void;
+    }
+
+    pub unsafe fn open(name: &CStr) -> *mut c_void {
+        LoadLibraryA(name.as_ptr())
+    }
+
+    pub unsafe fn symbol(library: *mut c_void, name: &CStr) -> *mut c_void {
+        GetProcAddress(library, name.as_ptr())
+    }
+}
+
+/// Returns the names to try, in order, when loading the library.
+fn library_names() -> Vec<String> {
+    match env::var(LIBRARY_VAR) {
+        Ok(ref name) if !name.is_empty() => vec![name.clone()],
+        _ => LIBRARY_NAMES.iter().map(|&name| name.to_owned()).collect(),
+    }
+}
+
+/// Returns the handle of the library, loading it if this is the first call,
+/// or `None` if it could not be loaded.
+fn library() -> Option<*mut c_void> {
+    LOAD.call_once(|| {
+        for name in library_names() {
+            let name = match CString::new(name) {
+                Ok(name) => name,
+                Err(_) => continue,
+            };
+
+            let handle = unsafe { sys::open(&name) };
+
+            if !handle.is_null() {
+                LIBRARY.store(handle as usize, Ordering::Release);
+                break;
+            }
+        }
+    });
+
+    match LIBRARY.load(Ordering::Acquire) {
+        0 => None,
+        handle => Some(handle as *mut c_void),
+    }
+}
+
+/// A lazily resolved library symbol, one of which is declared by each `cl_h`
+/// function.
+pub struct Symbol {
+    name: &'static str,
+    c_name: &'static str,
+    addr: AtomicUsize,
+}
+
+impl Symbol {
+    /// Returns a new, unresolved, symbol. `c_name` is `name` with a
+    /// terminating nul.
+    pub const fn new(name: &'static str, c_name: &'static str) -> Symbol {
+        Symbol { name: name, c_name: c_name, addr: AtomicUsize::new(0) }
+    }
+
+    /// Returns the address of the symbol, resolving it if necessary, or
+    /// records the reason it is unavailable and returns `None`.
+    ///
+    /// Clears any reason recorded by an earlier call on this thread.
+    pub fn addr(&self) -> Option<usize> {
+        LOAD_ERROR.with(|e| e.set(None));
+        let mut addr = self.addr.load(Ordering::Acquire);
+
+        if addr == 0 {
+            addr = match library() {
+                Some(library) => {
+                    let c_name = unsafe { CStr::from_bytes_with_nul_unchecked(self.c_name.as_bytes()) };
+                    match unsafe { sys::symbol(library, c_name) } as usize {
+                        0 => MISSING,
+                        addr => addr,
+                    }
+                },
+                None => MISSING,
+            };
+            self.addr.store(addr, Ordering::Release);
+        }
+
+        if addr == MISSING {
+            let err = if library().is_some() {
+                LoadError::SymbolMissing(self.name)
+            } else {
+                LoadError::LibraryNotLoaded
+            };
+            LOAD_ERROR.with(|e| e.set(Some(err)));
+            None
+        } else {
+            Some(addr)
+        }
+    }
+}
+
+/// Returns the OpenCL version which introduced the function, `name`, and
+/// the version preceding it (the latest a library lacking it can support).
+fn function_versions(name: &str) -> ((u32, u32), (u32, u32)) {
+    let required = FUNCTION_VERSIONS.iter().find(|&&(fn_name, _)| fn_name == name)
+        .map(|&(_, version)| version)
+        .unwrap_or((1, 0));
+    let found = VERSIONS.iter().cloned().take_while(|&version| version < required).last()
+        .unwrap_or((0, 0));
+    (required, found)
+}
+
+/// Returns (and clears) the error explaining why the most recent `cl_h`
+/// function called on this thread did not reach the library, if it did not.
+///
+/// A missing symbol is reported as `Error::VersionUnsupported`, the
+/// function most likely being from a later OpenCL version than the library
+/// supports.
+pub fn take_error() -> Option<OclError> {
+    LOAD_ERROR.with(|e| e.replace(None)).map(|err| match err {
+        LoadError::LibraryNotLoaded => OclError::LibraryNotLoaded,
+        LoadError::SymbolMissing(name) => {
+            let (required, found) = function_versions(name);
+            OclError::VersionUnsupported { op: name, required: required, found: found }
+        },
+    })
+}
+
+/// Loads the library (if not already loaded) and returns the address of its
+/// `clGetPlatformIDs` function, if found.
+pub fn get_platform_ids_addr() -> Option<usize> {
+    static GET_PLATFORM_IDS: Symbol = Symbol::new("clGetPlatformIDs", "clGetPlatformIDs\0");
+    let addr = GET_PLATFORM_IDS.addr();
+    take_error();
+    addr
+}
+
+/// Loads the library (if not already loaded) and returns true if it
+/// provides `clGetPlatformIDs`.
+pub fn is_available() -> bool {
+    get_platform_ids_addr().is_some()
+}
@@ -0,0 +1,260 @@
+//! Device selection by micro-benchmark.
+
+use std;
+use std::time::Duration;
+use core::{DeviceInfo, DeviceInfoResult, ProfilingInfo, ProfilingInfoResult};
+use error::{Error as OclError, Result as OclResult};
+use standard::{Platform, Device, Context, Program, Queue, Kernel, Buffer, Event, SpatialDims};
+use util;
+
+/// Runs discarded before timing, to exclude one-time costs such as
+/// compilation and first-touch allocation.
+const WARMUP_RUNS: usize = 2;
+
+/// Timed runs, per device.
+const TIMED_RUNS: usize = 5;
+
+/// The number of `float4`s copied by `BenchWorkload::MemoryBandwidth` (64MiB
+/// in each direction), reduced to fit within a device's maximum allocation.
+const BANDWIDTH_LEN: usize = 1 << 22;
+
+/// The work size and iterations (of eight independent multiply-adds) of
+/// `BenchWorkload::Fp32Throughput`.
+const THROUGHPUT_LEN: usize = 1 << 18;
+const THROUGHPUT_ITERS: u32 = 256;
+
+static BANDWIDTH_SRC: &'static str = r#"
+    __kernel void copy(__global const float4* src, __global float4* dst) {
+        uint idx = get_global_id(0);
+        dst[idx] = src[idx];
+    }
+"#;
+
+static THROUGHPUT_SRC: &'static str = r#"
+    __kernel void mad(__global float* buf, uint iters) {
+        uint idx = get_global_id(0);
+        float a = buf[idx];
+        float b = a + 1.0f;
+        float4 x = (float4)(a, b, a * 2.0f, b * 2.0f);
+        float4 y = x + 0.5f;
+
+        for (uint i = 0; i < iters; i++) {
+            x = mad(x, y, a);
+            y = mad(y, x, b);
+        }
+
+        buf[idx] = x.s0 + x.s1 + x.s2 + x.s3 + y.s0 + y.s1 + y.s2 + y.s3;
+    }
+"#;
+
+/// A kernel used to compare devices (see `Device::benchmark_select`).
+#[derive(Debug, Clone)]
+pub enum BenchWorkload {
+    /// Copies a large buffer, limited by global memory bandwidth.
+    MemoryBandwidth,
+    /// Chains floating point multiply-adds, limited by arithmetic
+    /// throughput.
+    Fp32Throughput,
+    /// A kernel named `name`, built from `src`, taking a single
+    /// `__global float*` argument of `dims.to_len()` elements and enqueued
+    /// with a global work size of `dims`.
+    Custom {
+        src: String,
+        name: String,
+        dims: SpatialDims,
+    },
+}
+
+impl BenchWorkload {
+    /// Builds the workload's kernel and buffers for `queue`.
+    fn kernel(&self, queue: &Queue) -> OclResult<Kernel> {
+        match *self {
+            BenchWorkload::MemoryBandwidth => {
+                let max_len = (queue.device_limits().max_mem_alloc_size() / 16) as usize;
+                let len = std::cmp::min(BANDWIDTH_LEN, max_len) * 4;
+                let program = try!(Program::builder().src(BANDWIDTH_SRC).build_for_queue(queue));
+                let src = try!(Buffer::<f32>::builder().dims([len]).fill_val(1.0).build(queue));
+                let dst = try!(Buffer::<f32>::builder().dims([len]).build(queue));
+
+                Ok(try!(Kernel::new("copy", &program, queue))
+                    .gws([len / 4])
+                    .arg_buf(&src)
+                    .arg_buf(&dst))
+            },
+            BenchWorkload::Fp32Throughput => {
+                let program = try!(Program::builder().src(THROUGHPUT_SRC).build_for_queue(queue));
+                let buf = try!(Buffer::<f32>::builder().dims([THROUGHPUT_LEN]).fill_val(1.0)
+                    .build(queue));
+
+                Ok(try!(Kernel::new("mad", &program, queue))
+                    .gws([THROUGHPUT_LEN])
+                    .arg_buf(&buf)
+                    .arg_scl(THROUGHPUT_ITERS))
+            },
+            BenchWorkload::Custom { ref src, ref name, dims } => {
+                let program = try!(Program::builder().src(src.as_str()).build_for_queue(queue));
+                let buf = try!(Buffer::<f32>::builder().dims([dims.to_len()]).build(queue));
+
+                Ok(try!(Kernel::new(name.as_str(), &program, queue))
+                    .gws(dims)
+                    .arg_buf(&buf))
+            },
+        }
+    }
+}
+
+/// The timings of one candidate device (see `BenchReport`).
+#[derive(Debug)]
+pub struct BenchEntry {
+    device: Device,
+    timings: OclResult<Vec<Duration>>,
+}
+
+impl BenchEntry {
+    /// Returns the device.
+    pub fn device(&self) -> Device {
+        self.device
+    }
+
+    /// Returns the execution time of each timed run, or the error which
+    /// excluded the device.
+    pub fn timings(&self) -> Result<&[Duration], &OclError> {
+        match self.timings {
+            Ok(ref timings) => Ok(timings),
+            Err(ref err) => Err(err),
+        }
+    }
+
+    /// Returns the mean execution time of the timed runs, or `None` if the
+    /// device was excluded.
+    pub fn mean(&self) -> Option<Duration> {
+        match self.timings {
+            Ok(ref timings) if !timings.is_empty() => {
+                let total: Duration = timings.iter().sum();
+                Some(total / timings.len() as u32)
+            },
+            _ => None,
+        }
+    }
+}
+
+/// The timings of every candidate of a benchmark (see
+/// `Device::benchmark_select`).
+#[derive(Debug)]
+pub struct BenchReport {
+    workload: BenchWorkload,
+    entries: Vec<BenchEntry>,
+}
+
+impl BenchReport {
+    /// Runs `workload` on each device of `candidates`.
+    pub(crate) fn run(workload: BenchWorkload, candidates: &[Device]) -> BenchReport {
+        let entries = candidates.iter().map(|&device| {
+            BenchEntry {
+                device: device,
+                timings: time_device(&workload, device),
+            }
+        }).collect();
+
+        BenchReport { workload: workload, entries: entries }
+    }
+
+    /// Returns the workload.
+    pub fn workload(&self) -> &BenchWorkload {
+        &self.workload
+    }
+
+    /// Returns the entry of each candidate, in order, including any which
+    /// were excluded.
+    pub fn entries(&self) -> &[BenchEntry] {
+        &self.entries
+    }
+
+    /// Returns the device with the lowest mean execution time, if any
+    /// completed the benchmark. Ties go to the earlier candidate.
+    pub fn winner(&self) -> Option<Device> {
+        let mut winner: Option<(Device, Duration)> = None;
+
+        for entry in self.entries.iter() {
+            if let Some(mean) = entry.mean() {
+                match winner {
+                    Some((_, best)) if best <= mean => (),
+                    _ => winner = Some((entry.device, mean)),
+                }
+            }
+        }
+
+        winner.map(|(device, _)| device)
+    }
+}
+
+impl std::fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let winner = self.winner();
+        match self.workload {
+            BenchWorkload::Custom { ref name, dims, .. } => try!(writeln!(f,
+                "Benchmark: Custom ('{}', {:?})", name, dims)),
+            ref workload => try!(writeln!(f, "Benchmark: {:?}", workload)),
+        }
+
+        for entry in self.entries.iter() {
+            try!(write!(f, "  {}: ", entry.device.name()));
+
+            match entry.timings {
+                Ok(ref timings) => {
+                    let mean = entry.mean().unwrap_or(Duration::from_secs(0));
+                    try!(write!(f, "mean {:.3} ms over {} runs",
+                        mean.as_secs() as f64 * 1e3 + mean.subsec_nanos() as f64 / 1e6,
+                        timings.len()));
+                    if winner.map(|w| w.as_core() == entry.device.as_core()) == Some(true) {
+                        try!(write!(f, " (winner)"));
+                    }
+                    try!(writeln!(f, ""));
+                },
+                Err(ref err) => try!(writeln!(f, "excluded: {}", err)),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds `workload` for `device` on a new context and profiling queue,
+/// then returns the execution time of each timed run.
+fn time_device(workload: &BenchWorkload, device: Device) -> OclResult<Vec<Duration>> {
+    let platform = match device.info(DeviceInfo::Platform) {
+        DeviceInfoResult::Platform(platform) => Platform::new(platform),
+        DeviceInfoResult::Error(err) => return Err(*err),
+        res => return OclError::err(format!("ocl::Device::benchmark_select: Unexpected device \
+            info result: {:?}", res)),
+    };
+
+    let context = try!(Context::builder().platform(platform).devices(device).build());
+    let queue = try!(Queue::new(&context, device));
+    let kernel = try!(workload.kernel(&queue));
+    let mut timings = Vec::with_capacity(TIMED_RUNS);
+
+    for run in 0..(WARMUP_RUNS + TIMED_RUNS) {
+        let mut event = Event::empty();
+        try!(kernel.cmd().enew(&mut event).enq());
+        try!(event.wait());
+
+        if run >= WARMUP_RUNS {
+            let start = try!(timestamp(&event, ProfilingInfo::Start));
+            let end = try!(timestamp(&event, ProfilingInfo::End));
+            timings.push(Duration::from_nanos(end.saturating_sub(start)));
+        }
+    }
+
+    Ok(timings)
+}
+
+/// Returns a profiling counter of `event`, in nanoseconds.
+fn timestamp(event: &Event, request: ProfilingInfo) -> OclResult<u64> {
+    match event.profiling_info(request) {
+        ProfilingInfoResult::TemporaryPlaceholderVariant(bytes) => unsafe {
+            util::bytes_to::<u64>(&bytes)
+        },
+        ProfilingInfoResult::Error(err) => Err(*err),
+        _ => OclError::err("ocl::Device::benchmark_select: Unexpected profiling info result."),
+    }
+}
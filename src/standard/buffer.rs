@@ -1,13 +1,20 @@
 //! Interfaces with a buffer.
 
 use std;
+use std::cmp;
+use std::ptr;
+use std::mem;
 use std::marker::PhantomData;
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, Range};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use core::{self, OclPrm, Mem as MemCore, MemFlags, 
-    MemInfo, MemInfoResult, ClEventPtrNew, ClWaitList};
+use core::{self, OclPrm, Mem as MemCore, MemFlags, MapFlags,
+    MemInfo, MemInfoResult, ClEventPtrNew, ClWaitList, BufferRegion};
 use error::{Error as OclError, Result as OclResult};
-use standard::{Queue, MemLen, SpatialDims};
+use standard::{Context, Queue, MemLen, SpatialDims, EventList, StagingPool, BufferView,
+    MappedMem, ImageOrigin, ImageRegion, resolve_queue};
+use util;
 
 
 fn check_len(mem_len: usize, data_len: usize, offset: usize) -> OclResult<()> {
@@ -19,6 +26,130 @@ fn check_len(mem_len: usize, data_len: usize, offset: usize) -> OclResult<()> {
     Ok(())
 }
 
+/// Returns an error if the rectangular `region` at `origin` does not lie
+/// within `len` elements (of a buffer or host slice, as named by `side`)
+/// laid out with the row and slice pitches given, in elements.
+///
+/// As in OpenCL, a pitch of zero is computed from the region: the row pitch
+/// being `region[0]` and the slice pitch `region[1] * row_pitch`.
+fn check_rect(side: &str, len: usize, origin: [usize; 3], region: [usize; 3],
+        row_pitch: usize, slc_pitch: usize) -> OclResult<()>
+{
+    if region.iter().any(|&r| r == 0) { return OclError::err(format!(
+        "ocl::BufferCmd::enq(): The rectangular region ({:?}) must not be empty.", region)); }
+
+    let row_pitch = if row_pitch == 0 { region[0] } else { row_pitch };
+    if row_pitch < region[0] { return OclError::err(format!(
+        "ocl::BufferCmd::enq(): The {} row pitch ({}) is less than the region width ({}).",
+        side, row_pitch, region[0])); }
+
+    let min_slc_pitch = match region[1].checked_mul(row_pitch) {
+        Some(p) => p,
+        None => return OclError::err("ocl::BufferCmd::enq(): Rectangular region overflow."),
+    };
+    let slc_pitch = if slc_pitch == 0 { min_slc_pitch } else { slc_pitch };
+    if slc_pitch < min_slc_pitch || slc_pitch % row_pitch != 0 { return OclError::err(format!(
+        "ocl::BufferCmd::enq(): The {} slice pitch ({}) must be a multiple of the row pitch \
+        ({}) of at least the region height ({}) times the row pitch.", side, slc_pitch,
+        row_pitch, region[1])); }
+
+    let end = origin[2].checked_add(region[2] - 1)
+        .and_then(|z| z.checked_mul(slc_pitch))
+        .and_then(|z| origin[1].checked_add(region[1] - 1)
+            .and_then(|y| y.checked_mul(row_pitch))
+            .and_then(|y| y.checked_add(z)))
+        .and_then(|yz| origin[0].checked_add(region[0]).and_then(|x| x.checked_add(yz)));
+
+    match end {
+        Some(end) if end <= len => Ok(()),
+        _ => OclError::err(format!("ocl::BufferCmd::enq(): The rectangular region ({:?}) at \
+            origin ({:?}) with a row pitch of {} and a slice pitch of {} extends beyond the end \
+            of the {} (len: {}).", region, origin, row_pitch, slc_pitch, side, len)),
+    }
+}
+
+/// Returns the row and slice pitches, in elements, of an array of `dims`.
+fn rect_pitches(dims: ImageRegion) -> (usize, usize) {
+    (dims[0], dims[0] * dims[1])
+}
+
+/// Reverses the byte order of each element of `data` in place.
+pub(super) fn swap_slice<T: OclPrm>(data: &mut [T]) -> OclResult<()> {
+    for val in data.iter_mut() {
+        *val = match val.swap_bytes() {
+            Some(swapped) => swapped,
+            None => return OclError::err("ocl::BufferCmd::enq(): Byte swapping is not                 supported for this type ('OclPrm::swap_bytes' returned 'None')."),
+        };
+    }
+    Ok(())
+}
+
+/// Returns a copy of `data` with the byte order of each element reversed.
+fn swapped<T: OclPrm>(data: &[T]) -> OclResult<Vec<T>> {
+    let mut vec = data.to_vec();
+    try!(swap_slice(&mut vec));
+    Ok(vec)
+}
+
+/// Reads from `obj_core` into `data` by mapping the region starting at
+/// `offset`, copying from it, then unmapping it.
+///
+/// Blocks until the map is complete. `new_event` is associated with the
+/// unmap.
+fn map_read<T: OclPrm>(queue: &Queue, obj_core: &MemCore, offset: usize, data: &mut [T],
+        wait_list: Option<&ClWaitList>, new_event: Option<&mut ClEventPtrNew>) -> OclResult<()>
+{
+    unsafe {
+        let mapped_ptr = try!(core::enqueue_map_buffer::<T>(queue, obj_core, true,
+            core::MAP_READ, offset, data.len(), wait_list, None));
+        ptr::copy_nonoverlapping(mapped_ptr as *const u8, data.as_mut_ptr() as *mut u8,
+            data.len() * mem::size_of::<T>());
+        core::enqueue_unmap_mem_object(queue, obj_core, mapped_ptr, None, new_event)
+    }
+}
+
+/// Writes `data` to `obj_core` by mapping the region starting at `offset`,
+/// copying into it, then unmapping it.
+///
+/// Blocks until the map is complete. `new_event` is associated with the
+/// unmap.
+fn map_write<T: OclPrm>(queue: &Queue, obj_core: &MemCore, offset: usize, data: &[T],
+        wait_list: Option<&ClWaitList>, new_event: Option<&mut ClEventPtrNew>) -> OclResult<()>
+{
+    // The previous contents of the region need not be copied to the host:
+    let map_flags = if queue.device_version() >= (1, 2) {
+        core::MAP_WRITE_INVALIDATE_REGION
+    } else {
+        core::MAP_WRITE
+    };
+
+    unsafe {
+        let mapped_ptr = try!(core::enqueue_map_buffer::<T>(queue, obj_core, true, map_flags,
+            offset, data.len(), wait_list, None));
+        ptr::copy_nonoverlapping(data.as_ptr() as *const u8, mapped_ptr as *mut u8,
+            data.len() * mem::size_of::<T>());
+        core::enqueue_unmap_mem_object(queue, obj_core, mapped_ptr, None, new_event)
+    }
+}
+
+/// Makes `new_event` (if any) complete once each of `chunk_events` has,
+/// by enqueuing a marker, then blocks until they have if `block` is true.
+fn finish_chunks(queue: &Queue, chunk_events: &EventList, block: bool,
+        new_event: Option<&mut ClEventPtrNew>) -> OclResult<()>
+{
+    if let Some(new_event) = new_event {
+        let wait_list = if chunk_events.len() > 0 {
+            Some(chunk_events as &ClWaitList)
+        } else {
+            None
+        };
+        try!(core::enqueue_marker_compat(queue, wait_list, Some(new_event),
+            queue.platform_version()));
+    }
+
+    if block && chunk_events.len() > 0 { chunk_events.wait() } else { Ok(()) }
+}
+
 /// The type of operation to be performed by a command.
 pub enum BufferCmdKind<'b, T: 'b> {
     Unspecified,
@@ -56,6 +187,27 @@ pub enum BufferCmdDataShape {
     },
 }
 
+/// The method used to transfer data between host memory and a buffer when
+/// reading or writing.
+///
+/// Mapping avoids a copy on devices which share memory with the host (such
+/// as integrated GPUs) when the buffer was allocated in host accessible
+/// memory. Either method produces identical results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferMode {
+    /// Maps blocking, linear reads and writes when the buffer was created
+    /// with `MEM_ALLOC_HOST_PTR` or `MEM_USE_HOST_PTR` and the device shares
+    /// memory with the host (see `Queue::device_host_unified_memory`).
+    /// Copies otherwise.
+    Auto,
+    /// Always copies (`clEnqueueReadBuffer` / `clEnqueueWriteBuffer`).
+    Copy,
+    /// Always maps linear reads and writes, copying to or from the mapped
+    /// region on the host then unmapping it. Mapped transfers block until
+    /// the map is complete. Rectangular transfers are always copied.
+    Map,
+}
+
 /// A buffer command builder used to enqueue reads, writes, fills, and copies.
 ///
 /// Create one using `Buffer::cmd` or with shortcut methods such as
@@ -80,7 +232,9 @@ pub enum BufferCmdDataShape {
 /// ```
 ///
 pub struct BufferCmd<'b, T: 'b + OclPrm> {
-    queue: &'b Queue,
+    queue: Option<&'b Queue>,
+    default_queue: Option<&'b Queue>,
+    context: Option<&'b Context>,
     obj_core: &'b MemCore,
     block: bool,
     lock_block: bool,
@@ -89,6 +243,16 @@ pub struct BufferCmd<'b, T: 'b + OclPrm> {
     ewait: Option<&'b ClWaitList>,
     enew: Option<&'b mut ClEventPtrNew>,
     mem_len: usize,
+    uninit: Option<&'b AtomicBool>,
+    swap_bytes: bool,
+    mem_flags: MemFlags,
+    transfer_mode: TransferMode,
+    chunk_size: Option<usize>,
+    staging_pool: Option<&'b StagingPool>,
+    retain_mem: bool,
+    owned_data: Option<Vec<T>>,
+    view: Option<Range<usize>>,
+    copy_dst_len: Option<usize>,
 }
 
 /// [UNSTABLE]: All methods still in a state of tweakification.
@@ -98,9 +262,19 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
     /// (the length of the device side buffer).
     pub fn new(queue: &'b Queue, obj_core: &'b MemCore, mem_len: usize) 
             -> BufferCmd<'b, T>
+    {
+        BufferCmd::with_defaults(Some(queue), None, obj_core, mem_len)
+    }
+
+    /// Returns a new buffer command builder using the default queue of
+    /// either the buffer or, failing that, `context` (see the `Queue` docs).
+    fn with_defaults(default_queue: Option<&'b Queue>, context: Option<&'b Context>,
+            obj_core: &'b MemCore, mem_len: usize) -> BufferCmd<'b, T>
     {
         BufferCmd {
-            queue: queue,
+            queue: None,
+            default_queue: default_queue,
+            context: context,
             obj_core: obj_core,
             block: true,
             lock_block: false,
@@ -109,12 +283,31 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
             ewait: None,
             enew: None,
             mem_len: mem_len,
+            uninit: None,
+            swap_bytes: false,
+            mem_flags: core::MEM_READ_WRITE,
+            transfer_mode: TransferMode::Copy,
+            chunk_size: None,
+            staging_pool: None,
+            retain_mem: false,
+            owned_data: None,
+            view: None,
+            copy_dst_len: None,
         }
     }
 
-    /// Specifies a queue to use for this call only.
+    /// Restricts this command to the elements of the buffer within `range`,
+    /// relative to which the offset of the command is then interpreted (see
+    /// `BufferView::cmd`).
+    pub(crate) fn within(mut self, range: Range<usize>) -> BufferCmd<'b, T> {
+        self.view = Some(range);
+        self
+    }
+
+    /// Specifies a queue to use for this call only, overriding the default
+    /// queues of the buffer and its context (see the `Queue` docs).
     pub fn queue(mut self, queue: &'b Queue) -> BufferCmd<'b, T> {
-        self.queue = queue;
+        self.queue = Some(queue);
         self
     }
 
@@ -136,6 +329,58 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
         self
     }
 
+    /// Specifies the method used to transfer data for a read or write, for
+    /// this call only.
+    ///
+    /// Defaults to the buffer's setting (see `Buffer::set_transfer_mode`).
+    pub fn transfer_mode(mut self, mode: TransferMode) -> BufferCmd<'b, T> {
+        self.transfer_mode = mode;
+        self
+    }
+
+    /// Splits a linear read or write into sequential transfers of at most
+    /// `chunk_size` bytes each (rounded down to a whole number of elements).
+    ///
+    /// Very large single transfers can stall other work on the device and
+    /// some drivers fail outright when a transfer exceeds 2GiB. Each chunk
+    /// waits on the events given with `::ewait` and a new event requested
+    /// with `::enew` is provided by a marker which completes once every
+    /// chunk has.
+    ///
+    /// Chunks are pipelined through a staging pool instead if one is given
+    /// with `::staging_pool`.
+    pub fn chunk_size(mut self, chunk_size: usize) -> BufferCmd<'b, T> {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// Pipelines a chunked read or write (see `::chunk_size`) through
+    /// `pool`, using the smaller of the two chunk sizes.
+    ///
+    /// The transfers are enqueued on the pool's queue and always block
+    /// (byte swapping, if enabled, is performed within the staging buffers).
+    /// Commands using the buffer enqueued on other queues must have completed
+    /// beforehand, except for those given with `::ewait`.
+    pub fn staging_pool(mut self, pool: &'b StagingPool) -> BufferCmd<'b, T> {
+        self.staging_pool = Some(pool);
+        self
+    }
+
+    /// Keeps the memory objects used by this command alive until it
+    /// completes, even if every `Buffer` (or `Image`) referring to them is
+    /// dropped beforehand.
+    ///
+    /// Not all implementations retain the memory objects of in-flight
+    /// commands internally, so dropping the last reference to a buffer right
+    /// after a non-blocking command using it can otherwise release it too
+    /// early. A marker is enqueued after the command to do so (see
+    /// `Queue::retain_until_complete`). Disabled by default except for
+    /// `::write_owned`.
+    pub fn retain_mem(mut self, retain: bool) -> BufferCmd<'b, T> {
+        self.retain_mem = retain;
+        self
+    }
+
     /// Sets the linear offset for an operation.
     /// 
     /// ## Panics
@@ -180,7 +425,8 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
     /// ## Safety
     ///
     /// Caller must ensure that the container referred to by `dst_data` lives 
-    /// until the call completes.
+    /// until the call completes. The buffer itself is only kept alive by the
+    /// command if `::retain_mem` is enabled.
     ///
     /// ## Panics
     ///
@@ -195,17 +441,42 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
 
     /// Specifies that this command will be a write operation.
     ///
+    /// `src_data` must remain unmodified until a non-blocking write
+    /// completes and the buffer is only kept alive by the command if
+    /// `::retain_mem` is enabled. Use `::write_owned` to avoid both concerns.
+    ///
     /// ## Panics
     ///
     /// The command operation kind must not have already been specified
     ///
     pub fn write(mut self, src_data: &'b [T]) -> BufferCmd<'b, T> {
-        assert!(self.kind.is_unspec(), "ocl::BufferCmd::write(): Operation kind \
-            already set for this command.");
+        assert!(self.kind.is_unspec() && self.owned_data.is_none(), "ocl::BufferCmd::write(): \
+            Operation kind already set for this command.");
         self.kind = BufferCmdKind::Write { data: src_data };
         self
     }
 
+    /// Specifies that this command will be a non-blocking write of
+    /// `src_data`, which is dropped once the write completes.
+    ///
+    /// The memory object is kept alive until then as well (see
+    /// `::retain_mem`), so both the data and the buffer may be dropped by the
+    /// caller immediately after enqueuing. The write may still be made to
+    /// block with `::block`.
+    ///
+    /// ## Panics
+    ///
+    /// The command operation kind must not have already been specified
+    ///
+    pub fn write_owned(mut self, src_data: Vec<T>) -> BufferCmd<'b, T> {
+        assert!(self.kind.is_unspec() && self.owned_data.is_none(), "ocl::BufferCmd::\
+            write_owned(): Operation kind already set for this command.");
+        self.owned_data = Some(src_data);
+        self.block = false;
+        self.retain_mem = true;
+        self
+    }
+
     /// Specifies that this command will be a copy operation.
     ///
     /// If `.block(..)` has been set it will be ignored.
@@ -223,11 +494,36 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
     {
         assert!(self.kind.is_unspec(), "ocl::BufferCmd::copy(): Operation kind \
             already set for this command.");
+        dst_buffer.uninit.store(false, Ordering::SeqCst);
         self.kind = BufferCmdKind::Copy { 
             dst_buffer: dst_buffer.core_as_ref(),
             dst_offset: dst_offset,
             len: len,
         }; 
+        self.copy_dst_len = Some(dst_buffer.len());
+        self
+    }
+
+    /// Specifies that this command will be a copy to the elements of
+    /// `dst_view`, all of which are copied to.
+    ///
+    /// If `.block(..)` has been set it will be ignored.
+    ///
+    /// ## Panics
+    ///
+    /// The command operation kind must not have already been specified
+    ///
+    pub fn copy_to_view(mut self, dst_view: &'b BufferView<T>) -> BufferCmd<'b, T> {
+        assert!(self.kind.is_unspec(), "ocl::BufferCmd::copy_to_view(): Operation kind \
+            already set for this command.");
+        let dst_buffer = dst_view.buffer();
+        dst_buffer.uninit.store(false, Ordering::SeqCst);
+        self.kind = BufferCmdKind::Copy {
+            dst_buffer: dst_buffer.core_as_ref(),
+            dst_offset: dst_view.offset(),
+            len: dst_view.len(),
+        };
+        self.copy_dst_len = Some(dst_buffer.len());
         self
     }
 
@@ -239,12 +535,14 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
     ///
     /// The command operation kind must not have already been specified
     ///
-    pub fn copy_to_image(mut self, image: &'b MemCore, dst_origin: [usize; 3], 
-                region: [usize; 3]) -> BufferCmd<'b, T> 
+    pub fn copy_to_image<O, R>(mut self, image: &'b MemCore, dst_origin: O, region: R)
+            -> BufferCmd<'b, T>
+            where O: Into<ImageOrigin>, R: Into<ImageRegion>
     {
         assert!(self.kind.is_unspec(), "ocl::BufferCmd::copy_to_image(): Operation kind \
             already set for this command.");
-        self.kind = BufferCmdKind::CopyToImage { image: image, dst_origin: dst_origin, region: region }; 
+        self.kind = BufferCmdKind::CopyToImage { image: image,
+            dst_origin: dst_origin.into().to_array(), region: region.into().to_array() };
         self
     }
 
@@ -268,17 +566,19 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
     ///
     /// Only valid for 'read', 'write', and 'copy' modes. Will error if used
     /// with 'fill' or 'copy to image'.
-    pub fn rect(mut self, src_origin: [usize; 3], dst_origin: [usize; 3], region: [usize; 3],
+    pub fn rect<S, D, R>(mut self, src_origin: S, dst_origin: D, region: R,
                 src_row_pitch: usize, src_slc_pitch: usize, dst_row_pitch: usize, 
                 dst_slc_pitch: usize) -> BufferCmd<'b, T>
+            where S: Into<ImageOrigin>, D: Into<ImageOrigin>, R: Into<ImageRegion>
     {
         if let BufferCmdDataShape::Lin { offset } = self.shape {
             assert!(offset == 0, "ocl::BufferCmd::rect(): This command builder has already been \
                 set to linear mode with '::offset`. You cannot call both '::offset' and '::rect'.");
         }
 
-        self.shape = BufferCmdDataShape::Rect { src_origin: src_origin, dst_origin: dst_origin,
-            region: region, src_row_pitch: src_row_pitch, src_slc_pitch: src_slc_pitch,
+        self.shape = BufferCmdDataShape::Rect { src_origin: src_origin.into().to_array(),
+            dst_origin: dst_origin.into().to_array(), region: region.into().to_array(),
+            src_row_pitch: src_row_pitch, src_slc_pitch: src_slc_pitch,
             dst_row_pitch: dst_row_pitch, dst_slc_pitch: dst_slc_pitch };
         self
     }
@@ -310,50 +610,334 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
         self
     }
 
+    /// Reads the rest of the buffer, from the offset (if set) to its end,
+    /// into a newly allocated `Vec` then returns it. Always blocks.
+    ///
+    /// ```rust,no_run
+    /// # fn main() { run().unwrap() }
+    /// # fn run() -> ocl::Result<()> {
+    /// # let pro_que = try!(ocl::ProQue::builder().src("").dims([1024]).build());
+    /// # let buffer = try!(pro_que.create_buffer::<f32>());
+    /// // Reads the last 24 elements using a specific queue:
+    /// let tail = try!(buffer.cmd().queue(pro_que.queue()).offset(1000).read_to_vec());
+    /// assert_eq!(tail.len(), 24);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// The offset must be within the buffer and the command may not be
+    /// rectangular (see `::rect`).
+    ///
+    /// ## Panics
+    ///
+    /// The command operation kind must not have already been specified.
+    ///
+    pub fn read_to_vec(self) -> OclResult<Vec<T>> {
+        let len = try!(self.remaining_len("read_to_vec"));
+        let mut vec = vec![T::default(); len];
+        try!(self.reborrow().read(&mut vec).enq());
+        Ok(vec)
+    }
+
+    /// Resizes `vec` to the length of the rest of the buffer, from the
+    /// offset (if set) to its end, then reads into it. Always blocks.
+    ///
+    /// Any existing allocation is reused. `vec` is left untouched if the
+    /// offset or shape of the command is invalid.
+    ///
+    /// ## Errors
+    ///
+    /// The offset must be within the buffer and the command may not be
+    /// rectangular (see `::rect`).
+    ///
+    /// ## Panics
+    ///
+    /// The command operation kind must not have already been specified.
+    ///
+    pub fn read_into(self, vec: &mut Vec<T>) -> OclResult<()> {
+        let len = try!(self.remaining_len("read_into"));
+        vec.resize(len, T::default());
+        self.reborrow().read(vec).enq()
+    }
+
+    /// Writes all of `data` to the rest of the buffer, from the offset (if
+    /// set) to its end. Always blocks.
+    ///
+    /// ## Errors
+    ///
+    /// The length of `data` must equal the length of the rest of the buffer,
+    /// the offset must be within the buffer, and the command may not be
+    /// rectangular (see `::rect`).
+    ///
+    /// ## Panics
+    ///
+    /// The command operation kind must not have already been specified.
+    ///
+    pub fn write_all(self, data: &[T]) -> OclResult<()> {
+        let len = try!(self.remaining_len("write_all"));
+        if data.len() != len {
+            return OclError::err(format!("ocl::BufferCmd::write_all(): The length of the data \
+                ({}) does not match the length of the buffer{} ({}).", data.len(),
+                if len == self.len_limit() { "" } else { " after the offset" }, len));
+        }
+        self.reborrow().write(data).block(true).enq()
+    }
+
+    /// Returns the length of the buffer from the offset to its end, used by
+    /// the whole-buffer reads and writes.
+    fn remaining_len(&self, fn_name: &str) -> OclResult<usize> {
+        assert!(self.kind.is_unspec() && self.owned_data.is_none(), "ocl::BufferCmd::{}(): \
+            Operation kind already set for this command.", fn_name);
+
+        let len = self.len_limit();
+
+        match self.shape {
+            BufferCmdDataShape::Lin { offset } => {
+                if offset < len {
+                    Ok(len - offset)
+                } else {
+                    OclError::err(format!("ocl::BufferCmd::{}(): Offset out of range (offset: \
+                        {}, buffer length: {}).", fn_name, offset, len))
+                }
+            },
+            BufferCmdDataShape::Rect { .. } => OclError::err(format!("ocl::BufferCmd::{}(): \
+                Not valid for rectangular commands.", fn_name)),
+        }
+    }
+
+    /// Returns the length of the buffer, or of the view, this command is
+    /// restricted to.
+    fn len_limit(&self) -> usize {
+        match self.view {
+            Some(ref view) => view.end - view.start,
+            None => self.mem_len,
+        }
+    }
+
+    /// Translates the offset of a command restricted to a view (see
+    /// `::within`) into an offset within the whole buffer, checking that the
+    /// command lies within the view.
+    fn resolve_view(mut self) -> OclResult<BufferCmd<'b, T>> {
+        let view = match self.view.take() {
+            Some(view) => view,
+            None => return Ok(self),
+        };
+        let view_len = view.end - view.start;
+
+        let offset = match self.shape {
+            BufferCmdDataShape::Lin { offset } => offset,
+            BufferCmdDataShape::Rect { .. } => return OclError::err("ocl::BufferCmd::enq(): \
+                Rectangular commands are not valid on buffer views."),
+        };
+
+        let len = match self.kind {
+            BufferCmdKind::Read { ref data } => Some(data.len()),
+            BufferCmdKind::Write { data } => Some(data.len()),
+            BufferCmdKind::Copy { len, .. } => Some(len),
+            BufferCmdKind::Fill { pattern, len } => {
+                // Fills the rest of the view rather than of the buffer:
+                let len = len.unwrap_or(view_len.saturating_sub(offset));
+                self.kind = BufferCmdKind::Fill { pattern: pattern, len: Some(len) };
+                Some(len)
+            },
+            BufferCmdKind::CopyToImage { .. } => return OclError::err("ocl::BufferCmd::enq(): \
+                Copies to images are not valid on buffer views."),
+            BufferCmdKind::Unspecified => self.owned_data.as_ref().map(|data| data.len()),
+        };
+
+        if let Some(len) = len {
+            try!(check_len(view_len, len, offset));
+        }
+
+        self.shape = BufferCmdDataShape::Lin { offset: view.start + offset };
+        Ok(self)
+    }
+
+    /// Returns this command with its lifetime shortened to `'d` so that it
+    /// can be completed using data local to the caller.
+    fn reborrow<'d>(self) -> BufferCmd<'d, T> where 'b: 'd {
+        BufferCmd {
+            queue: self.queue,
+            default_queue: self.default_queue,
+            context: self.context,
+            obj_core: self.obj_core,
+            block: self.block,
+            lock_block: self.lock_block,
+            kind: self.kind,
+            shape: self.shape,
+            ewait: self.ewait,
+            enew: self.enew.map(|enew| -> &'d mut ClEventPtrNew { enew }),
+            mem_len: self.mem_len,
+            uninit: self.uninit,
+            swap_bytes: self.swap_bytes,
+            mem_flags: self.mem_flags,
+            transfer_mode: self.transfer_mode,
+            chunk_size: self.chunk_size,
+            staging_pool: self.staging_pool,
+            retain_mem: self.retain_mem,
+            owned_data: self.owned_data,
+            view: self.view,
+            copy_dst_len: self.copy_dst_len,
+        }
+    }
+
 
     // core::enqueue_copy_buffer::<f32, core::EventList>(&queue, &src_buffer, &dst_buffer, 
     //     copy_range.0, copy_range.0, copy_range.1 - copy_range.0, None,
     //     None).unwrap();
 
+    /// Returns true if a linear read or write should be performed by mapping
+    /// (see `TransferMode`).
+    fn use_map(&self, queue: &Queue, block: bool) -> bool {
+        match self.transfer_mode {
+            TransferMode::Copy => false,
+            TransferMode::Map => true,
+            TransferMode::Auto => block && queue.device_host_unified_memory() &&
+                (self.mem_flags.contains(core::MEM_ALLOC_HOST_PTR) ||
+                self.mem_flags.contains(core::MEM_USE_HOST_PTR)),
+        }
+    }
+
     /// Enqueues this command.
+    ///
+    /// In debug builds, reading from a buffer created with
+    /// `BufferBuilder::uninit` before anything has been written to it prints
+    /// a warning (once).
+    ///
+    /// On devices or platforms which do not support OpenCL 1.2, fills are
+    /// performed with a (blocking) write from host memory instead of
+    /// `clEnqueueFillBuffer`.
+    ///
+    /// If byte swapping is enabled for the buffer (see
+    /// `BufferBuilder::swap_bytes`) and the endianness of the device differs
+    /// from that of the host, the elements of reads, writes, and fill
+    /// patterns are byte swapped on the host. Swapped writes always block
+    /// and swapped reads must block.
+    ///
+    /// Linear reads and writes are performed by mapping rather than copying
+    /// according to the transfer mode (see `::transfer_mode`) and split into
+    /// chunks if a chunk size has been set (see `::chunk_size`).
+    ///
+    /// If enabled with `::retain_mem` (or `::write_owned`), the memory
+    /// objects used by the command, along with any owned data, are kept
+    /// alive until it completes.
+    ///
+    /// ## Errors
+    ///
+    /// Chunking is only valid for linear reads and writes and the chunk size
+    /// must be at least the size of one element. A staging pool may only be
+    /// used along with a chunk size.
+    ///
+    /// Returns an error if no queue has been specified for the command and
+    /// neither the buffer nor its context has a default queue.
     pub fn enq(self) -> OclResult<()> {
+        let queue = try!(resolve_queue("ocl::BufferCmd::enq", self.queue, self.default_queue,
+            self.context));
+        try!(self.resolve_view()).enq_on(&queue)
+    }
+
+    /// Enqueues this command on `queue`.
+    fn enq_on(self, queue: &Queue) -> OclResult<()> {
+        if self.retain_mem || self.owned_data.is_some() {
+            return self.enq_retained(queue);
+        }
+
+        let swap = self.swap_bytes && queue.device_endian_mismatch();
+        // Swapped writes always block:
+        let (map_reads, map_writes) = (self.use_map(queue, self.block),
+            self.use_map(queue, swap || self.block));
+
+        if let Some(uninit) = self.uninit {
+            match self.kind {
+                BufferCmdKind::Read { .. } => {
+                    if cfg!(debug_assertions) && uninit.swap(false, Ordering::SeqCst) {
+                        println!("WARNING: ocl::BufferCmd::enq(): Reading from an \
+                            uninitialized buffer. Contents are undefined until written.");
+                    }
+                },
+                BufferCmdKind::Write { .. } | BufferCmdKind::Fill { .. } => {
+                    uninit.store(false, Ordering::SeqCst);
+                },
+                _ => (),
+            }
+        }
+
+        if let Some(chunk_size) = self.chunk_size {
+            return self.enq_chunked(queue, chunk_size, swap, map_reads, map_writes);
+        }
+
+        if self.staging_pool.is_some() {
+            return OclError::err("ocl::BufferCmd::enq(): '::staging_pool' may only be used \
+                along with '::chunk_size'.");
+        }
+
         match self.kind {
             BufferCmdKind::Read { data } => { 
-                match self.shape {
+                if swap && !self.block { return OclError::err("ocl::BufferCmd::enq(): \
+                    Byte swapped reads must block. Use '::read' instead of '::read_async'."); }
+
+                try!(match self.shape {
                     BufferCmdDataShape::Lin { offset } => {                        
                         try!(check_len(self.mem_len, data.len(), offset));
 
-                        unsafe { core::enqueue_read_buffer(self.queue, self.obj_core, self.block, 
-                            offset, data, self.ewait, self.enew) }
+                        if map_reads {
+                            map_read(queue, self.obj_core, offset, data, self.ewait,
+                                self.enew)
+                        } else {
+                            unsafe { core::enqueue_read_buffer(queue, self.obj_core,
+                                self.block, offset, data, self.ewait, self.enew) }
+                        }
                     },
                     BufferCmdDataShape::Rect { src_origin, dst_origin, region, src_row_pitch, src_slc_pitch,
                             dst_row_pitch, dst_slc_pitch } => 
                     {
-                        // Verify dims given.
-                        // try!(Ok(()));
+                        try!(check_rect("buffer", self.mem_len, src_origin, region,
+                            src_row_pitch, src_slc_pitch));
+                        try!(check_rect("host slice", data.len(), dst_origin, region,
+                            dst_row_pitch, dst_slc_pitch));
 
-                        unsafe { core::enqueue_read_buffer_rect(self.queue, self.obj_core, 
+                        unsafe { core::enqueue_read_buffer_rect(queue, self.obj_core, 
                             self.block, src_origin, dst_origin, region, src_row_pitch, 
                             src_slc_pitch, dst_row_pitch, dst_slc_pitch, data, 
                             self.ewait, self.enew) }
                     }
-                }
+                });
+
+                if swap { swap_slice(data) } else { Ok(()) }
             },
             BufferCmdKind::Write { data } => {
+                let swapped_data: Vec<T>;
+                let (data, block) = if swap {
+                    swapped_data = try!(swapped(data));
+                    (&swapped_data[..], true)
+                } else {
+                    (data, self.block)
+                };
+
                 match self.shape {
                     BufferCmdDataShape::Lin { offset } => {
                         try!(check_len(self.mem_len, data.len(), offset));
-                        core::enqueue_write_buffer(self.queue, self.obj_core, self.block, 
-                            offset, data, self.ewait, self.enew)
+
+                        if map_writes {
+                            map_write(queue, self.obj_core, offset, data, self.ewait,
+                                self.enew)
+                        } else {
+                            core::enqueue_write_buffer(queue, self.obj_core, block, 
+                                offset, data, self.ewait, self.enew)
+                        }
                     },
                     BufferCmdDataShape::Rect { src_origin, dst_origin, region, src_row_pitch, src_slc_pitch,
                             dst_row_pitch, dst_slc_pitch } => 
                     {
-                        // Verify dims given.
-                        // try!(Ok(()));
+                        try!(check_rect("buffer", self.mem_len, src_origin, region,
+                            src_row_pitch, src_slc_pitch));
+                        try!(check_rect("host slice", data.len(), dst_origin, region,
+                            dst_row_pitch, dst_slc_pitch));
 
-                        core::enqueue_write_buffer_rect(self.queue, self.obj_core, 
-                            self.block, src_origin, dst_origin, region, src_row_pitch, 
+                        core::enqueue_write_buffer_rect(queue, self.obj_core, 
+                            block, src_origin, dst_origin, region, src_row_pitch, 
                             src_slc_pitch, dst_row_pitch, dst_slc_pitch, data, 
                             self.ewait, self.enew)
                     }
@@ -363,37 +947,59 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
                 match self.shape {
                     BufferCmdDataShape::Lin { offset } => {
                         try!(check_len(self.mem_len, len, offset));
-                        core::enqueue_copy_buffer::<T>(self.queue, 
+                        if let Some(dst_len) = self.copy_dst_len {
+                            try!(check_len(dst_len, len, dst_offset));
+                        }
+                        core::enqueue_copy_buffer::<T>(queue, 
                             self.obj_core, dst_buffer, offset, dst_offset, len, 
                             self.ewait, self.enew)
                     },
                     BufferCmdDataShape::Rect { src_origin, dst_origin, region, src_row_pitch, src_slc_pitch,
                             dst_row_pitch, dst_slc_pitch } => 
                     {
-                        // Verify dims given.
-                        // try!(Ok(()));
-                        
                         if dst_offset != 0 || len != 0 { return OclError::err(
                             "ocl::BufferCmd::enq(): For 'rect' shaped copies, destination \
                             offset and length must be zero. Ex.: \
                             'cmd().copy(&{{buf_name}}, 0, 0)..'.");
                         }
-                        core::enqueue_copy_buffer_rect::<T>(self.queue, self.obj_core, dst_buffer,
+                        try!(check_rect("source buffer", self.mem_len, src_origin, region,
+                            src_row_pitch, src_slc_pitch));
+                        if let Some(dst_len) = self.copy_dst_len {
+                            try!(check_rect("destination buffer", dst_len, dst_origin, region,
+                                dst_row_pitch, dst_slc_pitch));
+                        }
+
+                        core::enqueue_copy_buffer_rect::<T>(queue, self.obj_core, dst_buffer,
                         src_origin, dst_origin, region, src_row_pitch, src_slc_pitch, 
                         dst_row_pitch, dst_slc_pitch, self.ewait, self.enew)
                     },
                 }
             },
             BufferCmdKind::Fill { pattern, len } => {
+                let swapped_pattern: Vec<T>;
+                let pattern = if swap {
+                    swapped_pattern = try!(swapped(pattern));
+                    &swapped_pattern[..]
+                } else {
+                    pattern
+                };
+
                 match self.shape {
                     BufferCmdDataShape::Lin { offset } => {
                         let len = match len {
                             Some(l) => l,
-                            None => self.mem_len,
+                            None => self.mem_len.saturating_sub(offset),
                         };
-                        try!(check_len(self.mem_len, pattern.len() * len, offset));
-                        core::enqueue_fill_buffer(self.queue, self.obj_core, pattern, 
-                            offset, len, self.ewait, self.enew)
+                        try!(check_len(self.mem_len, len, offset));
+
+                        if cmp::min(queue.device_version(), queue.platform_version()) < (1, 2) {
+                            let data: Vec<T> = pattern.iter().cloned().cycle().take(len).collect();
+                            core::enqueue_write_buffer(queue, self.obj_core, true, 
+                                offset, &data, self.ewait, self.enew)
+                        } else {
+                            core::enqueue_fill_buffer(queue, self.obj_core, pattern, 
+                                offset, len, self.ewait, self.enew)
+                        }
                     },
                     BufferCmdDataShape::Rect { .. } => {
                         return OclError::err("ocl::BufferCmd::enq(): Rectangular fill is not a \
@@ -406,6 +1012,270 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
             _ => unimplemented!(),
         }
     }
+
+    /// Enqueues this command then keeps the memory objects it uses, and any
+    /// owned data, alive until it completes.
+    fn enq_retained(mut self, queue: &Queue) -> OclResult<()> {
+        let mut mems = vec![self.obj_core.clone()];
+
+        match self.kind {
+            BufferCmdKind::Copy { dst_buffer, .. } => mems.push(dst_buffer.clone()),
+            BufferCmdKind::CopyToImage { image, .. } => mems.push(image.clone()),
+            _ => (),
+        }
+
+        self.retain_mem = false;
+
+        match self.owned_data.take() {
+            Some(data) => {
+                try!(BufferCmd {
+                    queue: self.queue,
+                    default_queue: self.default_queue,
+                    context: self.context,
+                    obj_core: self.obj_core,
+                    block: self.block,
+                    lock_block: self.lock_block,
+                    kind: BufferCmdKind::Write { data: &data },
+                    shape: self.shape,
+                    ewait: self.ewait,
+                    enew: match self.enew { Some(enew) => Some(enew), None => None },
+                    mem_len: self.mem_len,
+                    uninit: self.uninit,
+                    swap_bytes: self.swap_bytes,
+                    mem_flags: self.mem_flags,
+                    transfer_mode: self.transfer_mode,
+                    chunk_size: self.chunk_size,
+                    staging_pool: self.staging_pool,
+                    retain_mem: false,
+                    owned_data: None,
+                    view: None,
+                    copy_dst_len: self.copy_dst_len,
+                }.enq_on(queue));
+
+                queue.retain_until_complete((mems, data))
+            },
+            None => {
+                try!(self.enq_on(queue));
+                queue.retain_until_complete(mems)
+            },
+        }
+    }
+
+    /// Enqueues this command as a series of transfers of up to `chunk_size`
+    /// bytes each.
+    fn enq_chunked(self, queue: &Queue, chunk_size: usize, swap: bool, map_reads: bool,
+            map_writes: bool) -> OclResult<()>
+    {
+        let chunk_len = chunk_size / mem::size_of::<T>();
+
+        if chunk_len == 0 {
+            return OclError::err(format!("ocl::BufferCmd::enq(): The chunk size ({} bytes) is \
+                smaller than the size of a single element ({} bytes).", chunk_size,
+                mem::size_of::<T>()));
+        }
+
+        let offset = match self.shape {
+            BufferCmdDataShape::Lin { offset } => offset,
+            BufferCmdDataShape::Rect { .. } => return OclError::err("ocl::BufferCmd::enq(): \
+                Chunked transfers must be linear."),
+        };
+
+        let obj_core = self.obj_core;
+        let mut chunk_events = EventList::new();
+
+        match self.kind {
+            BufferCmdKind::Read { data } => {
+                if swap && !self.block { return OclError::err("ocl::BufferCmd::enq(): \
+                    Byte swapped reads must block. Use '::read' instead of '::read_async'."); }
+
+                try!(check_len(self.mem_len, data.len(), offset));
+
+                if let Some(pool) = self.staging_pool {
+                    try!(pool.read_mem(obj_core, self.mem_len, swap, offset, data, chunk_size,
+                        self.ewait));
+                    return finish_chunks(queue, &chunk_events, false, self.enew);
+                }
+
+                for (i, chunk) in data.chunks_mut(chunk_len).enumerate() {
+                    let chunk_offset = offset + i * chunk_len;
+
+                    if map_reads {
+                        try!(map_read(queue, obj_core, chunk_offset, chunk, self.ewait,
+                            Some(&mut chunk_events)));
+                    } else {
+                        try!(unsafe { core::enqueue_read_buffer(queue, obj_core, false,
+                            chunk_offset, chunk, self.ewait, Some(&mut chunk_events)) });
+                    }
+                }
+
+                try!(finish_chunks(queue, &chunk_events, self.block, self.enew));
+                if swap { swap_slice(data) } else { Ok(()) }
+            },
+            BufferCmdKind::Write { data } => {
+                try!(check_len(self.mem_len, data.len(), offset));
+
+                if let Some(pool) = self.staging_pool {
+                    try!(pool.write_mem(obj_core, self.mem_len, swap, offset, data, chunk_size,
+                        self.ewait));
+                    return finish_chunks(queue, &chunk_events, false, self.enew);
+                }
+
+                let swapped_data: Vec<T>;
+                let (data, block) = if swap {
+                    swapped_data = try!(swapped(data));
+                    (&swapped_data[..], true)
+                } else {
+                    (data, self.block)
+                };
+
+                for (i, chunk) in data.chunks(chunk_len).enumerate() {
+                    let chunk_offset = offset + i * chunk_len;
+
+                    if map_writes {
+                        try!(map_write(queue, obj_core, chunk_offset, chunk, self.ewait,
+                            Some(&mut chunk_events)));
+                    } else {
+                        try!(core::enqueue_write_buffer(queue, obj_core, false, chunk_offset,
+                            chunk, self.ewait, Some(&mut chunk_events)));
+                    }
+                }
+
+                finish_chunks(queue, &chunk_events, block, self.enew)
+            },
+            BufferCmdKind::Unspecified => OclError::err("ocl::BufferCmd::enq(): No operation \
+                specified. Use '.read(...)', 'write(...)', etc. before calling '.enq()'."),
+            _ => OclError::err("ocl::BufferCmd::enq(): Chunked transfers are only supported for \
+                reads and writes."),
+        }
+    }
+}
+
+
+/// A builder for `Buffer`.
+///
+/// Unless host data is provided, new buffers are filled with `T::default()`
+/// (zero for all numeric types) after creation. Use `::fill_val` to fill
+/// with a different value or `::uninit` to skip the fill entirely.
+///
+/// ## Examples
+///
+/// ```text
+/// let buffer = Buffer::<f32>::builder().dims([1024]).fill_val(1.0).build(&queue).unwrap();
+/// ```
+pub struct BufferBuilder<T: OclPrm> {
+    flags: MemFlags,
+    dims: SpatialDims,
+    fill_val: Option<T>,
+    swap_bytes: bool,
+    transfer_mode: TransferMode,
+}
+
+impl<T: OclPrm> BufferBuilder<T> {
+    /// Returns a new `BufferBuilder` with the flags, `ocl::MEM_READ_WRITE`,
+    /// unspecified dimensions, and a fill value of `T::default()`.
+    pub fn new() -> BufferBuilder<T> {
+        BufferBuilder {
+            flags: core::MEM_READ_WRITE,
+            dims: SpatialDims::Unspecified,
+            fill_val: Some(Default::default()),
+            swap_bytes: false,
+            transfer_mode: TransferMode::Auto,
+        }
+    }
+
+    /// Sets the memory flags.
+    pub fn flags<'a>(&'a mut self, flags: MemFlags) -> &'a mut BufferBuilder<T> {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the dimensions (and therefore the length).
+    pub fn dims<'a, D: Into<SpatialDims>>(&'a mut self, dims: D) -> &'a mut BufferBuilder<T> {
+        self.dims = dims.into();
+        self
+    }
+
+    /// Sets the value every element is set to after creation when no host
+    /// data is given.
+    pub fn fill_val<'a>(&'a mut self, fill_val: T) -> &'a mut BufferBuilder<T> {
+        self.fill_val = Some(fill_val);
+        self
+    }
+
+    /// Skips filling the buffer after creation.
+    ///
+    /// The contents of the buffer are undefined (whatever happened to be in
+    /// device memory, possibly left over from another process) until written.
+    pub fn uninit<'a>(&'a mut self) -> &'a mut BufferBuilder<T> {
+        self.fill_val = None;
+        self
+    }
+
+    /// Enables or disables byte swapping of transferred data when the
+    /// endianness of the device differs from that of the host (see
+    /// `Buffer::set_swap_bytes`). Disabled by default.
+    pub fn swap_bytes<'a>(&'a mut self, swap_bytes: bool) -> &'a mut BufferBuilder<T> {
+        self.swap_bytes = swap_bytes;
+        self
+    }
+
+    /// Sets the method used to transfer data for reads and writes (see
+    /// `Buffer::set_transfer_mode`). Defaults to `TransferMode::Auto`.
+    pub fn transfer_mode<'a>(&'a mut self, mode: TransferMode) -> &'a mut BufferBuilder<T> {
+        self.transfer_mode = mode;
+        self
+    }
+
+    /// Builds and returns a new `Buffer`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a `DimsUnspecified` error if no dimensions have been set.
+    pub fn build(&self, queue: &Queue) -> OclResult<Buffer<T>> {
+        Buffer::new_with_fill(Some(queue), None, Some(self.flags), self.dims.clone(), None,
+            self.fill_val, self.swap_bytes, self.transfer_mode)
+    }
+
+    /// Builds and returns a new `Buffer`, initializing its contents with
+    /// `data` (requires the `ocl::MEM_COPY_HOST_PTR` or
+    /// `ocl::MEM_USE_HOST_PTR` flag). No fill is performed.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a `DimsUnspecified` error if no dimensions have been set.
+    pub fn build_with_data(&self, queue: &Queue, data: &[T]) -> OclResult<Buffer<T>> {
+        Buffer::new_with_fill(Some(queue), None, Some(self.flags), self.dims.clone(), Some(data),
+            None, self.swap_bytes, self.transfer_mode)
+    }
+
+    /// Builds and returns a new `Buffer` within `context`, without a default
+    /// queue of its own. Commands use the default queue of the context
+    /// unless another is specified (see the `Queue` docs).
+    ///
+    /// ## Errors
+    ///
+    /// Returns a `DimsUnspecified` error if no dimensions have been set.
+    /// Filling the buffer (see `::fill_val` and `::uninit`) requires the
+    /// context to have a default queue.
+    pub fn build_in_context(&self, context: &Context) -> OclResult<Buffer<T>> {
+        Buffer::new_with_fill(None, Some(context), Some(self.flags), self.dims.clone(), None,
+            self.fill_val, self.swap_bytes, self.transfer_mode)
+    }
+
+    /// Builds and returns a new `Buffer` within `context`, without a default
+    /// queue of its own, initializing its contents with `data` (see
+    /// `::build_with_data` and `::build_in_context`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns a `DimsUnspecified` error if no dimensions have been set.
+    /// Byte swapping `data` requires the context to have a default queue.
+    pub fn build_in_context_with_data(&self, context: &Context, data: &[T])
+            -> OclResult<Buffer<T>>
+    {
+        Buffer::new_with_fill(None, Some(context), Some(self.flags), self.dims.clone(),
+            Some(data), None, self.swap_bytes, self.transfer_mode)
+    }
 }
 
 
@@ -414,38 +1284,123 @@ impl<'b, T: 'b + OclPrm> BufferCmd<'b, T> {
 /// Data is stored remotely in a memory buffer on the device associated with 
 /// `queue`.
 ///
-#[derive(Debug, Clone)]
+/// Commands are enqueued on the default queue of the buffer or, for buffers
+/// created with `BufferBuilder::build_in_context`, that of its context,
+/// unless another is specified (see the `Queue` docs).
+///
+#[derive(Clone)]
 pub struct Buffer<T: OclPrm> {
     obj_core: MemCore,
-    queue: Queue,
+    queue: Option<Queue>,
+    context: Option<Context>,
     dims: SpatialDims,
     len: usize,
+    uninit: Arc<AtomicBool>,
+    swap_bytes: bool,
+    flags: MemFlags,
+    transfer_mode: TransferMode,
+    parent: Option<MemCore>,
     _data: PhantomData<T>,
 }
 
 impl<T: OclPrm> Buffer<T> {
+    /// Returns a new buffer builder.
+    pub fn builder() -> BufferBuilder<T> {
+        BufferBuilder::new()
+    }
+
     /// Creates a new buffer
     ///
+    /// If `data` is `None`, the buffer is filled with `T::default()` (see
+    /// `::builder` for other options).
+    ///
+    /// ## Errors
+    ///
+    /// Returns a `DimsUnspecified` error if `dims` has no dimensions
+    /// specified (i.e. `SpatialDims::Unspecified`) and an error if the size
+    /// of the buffer exceeds the maximum allocation size of the device.
+    ///
     /// [UNSTABLE]: New method, arguments still in a state of flux.
     pub fn new<D: MemLen>(queue: &Queue, flags: Option<MemFlags>, dims: D, data: Option<&[T]>) 
             -> OclResult<Buffer<T>>
+    {
+        Buffer::new_with_fill(Some(queue), None, flags, dims, data, Some(Default::default()),
+            false, TransferMode::Auto)
+    }
+
+    /// Creates a new buffer, filling it with `fill_val` if `data` is `None`.
+    /// Leaves it uninitialized if both are `None`.
+    ///
+    /// The buffer is created within the context of `queue`, if given, which
+    /// becomes its default queue. Otherwise it is created within `context`
+    /// and uses its default queue.
+    fn new_with_fill<D: MemLen>(queue: Option<&Queue>, context: Option<&Context>,
+            flags: Option<MemFlags>, dims: D, data: Option<&[T]>, fill_val: Option<T>,
+            swap_bytes: bool, transfer_mode: TransferMode) -> OclResult<Buffer<T>>
     {
         let flags = flags.unwrap_or(core::MEM_READ_WRITE);
-        let dims: SpatialDims = dims.to_lens().into();
+        let dims: SpatialDims = match dims.to_lens() {
+            Ok(lens) => lens.into(),
+            Err(_) => return OclError::err_dims_unspecified("ocl::Buffer::new"),
+        };
         // let len = dims.to_len_padded(queue.device().max_wg_size()).expect("[FIXME]: Buffer::new: TEMP");
         let len = dims.to_len();
-        let obj_core = unsafe { try!(core::create_buffer(queue.context_core_as_ref(), flags, len,
-            data)) };
+        let size = len * mem::size_of::<T>();
+
+        let context_core = match (queue, context) {
+            (Some(queue), _) => {
+                try!(queue.device_limits().check_alloc_size("ocl::Buffer::new", size));
+                queue.context_core_as_ref()
+            },
+            (None, Some(context)) => {
+                for idx in 0..context.devices().len() {
+                    try!(try!(context.device_limits(idx)).check_alloc_size("ocl::Buffer::new",
+                        size));
+                }
+                context.core_as_ref()
+            },
+            (None, None) => return OclError::err("ocl::Buffer::new: A queue or context must be \
+                specified."),
+        };
+
+        let swap = match data {
+            Some(_) if swap_bytes => try!(resolve_queue("ocl::Buffer::new", None, queue,
+                context)).device_endian_mismatch(),
+            _ => false,
+        };
+
+        let swapped_data: Vec<T>;
+        let data = match data {
+            Some(d) if swap => {
+                if flags.contains(core::MEM_USE_HOST_PTR) { return OclError::err(
+                    "ocl::Buffer::new: Byte swapping cannot be used with 'MEM_USE_HOST_PTR'."); }
+                swapped_data = try!(swapped(d));
+                Some(&swapped_data[..])
+            },
+            d => d,
+        };
+
+        let obj_core = unsafe { try!(core::create_buffer(context_core, flags, len, data)) };
 
         let buf = Buffer {
             obj_core: obj_core,
-            queue: queue.clone(),
+            queue: queue.cloned(),
+            context: context.cloned(),
             dims: dims,
             len: len,
+            uninit: Arc::new(AtomicBool::new(data.is_none() && fill_val.is_none())),
+            swap_bytes: swap_bytes,
+            flags: flags,
+            transfer_mode: transfer_mode,
+            parent: None,
             _data: PhantomData,
         };
         
-        if data.is_none() { try!(buf.cmd().fill(&[Default::default()], None).enq()); }
+        if data.is_none() {
+            if let Some(fill_val) = fill_val {
+                try!(buf.cmd().fill(&[fill_val], None).enq());
+            }
+        }
         Ok(buf)
     }
 
@@ -457,7 +1412,13 @@ impl<T: OclPrm> Buffer<T> {
     /// for more info.
     ///
     pub fn cmd<'b>(&'b self) -> BufferCmd<'b, T> {
-        BufferCmd::new(&self.queue, &self.obj_core, self.len)
+        let mut cmd = BufferCmd::with_defaults(self.queue.as_ref(), self.context.as_ref(),
+            &self.obj_core, self.len);
+        cmd.uninit = Some(&self.uninit);
+        cmd.swap_bytes = self.swap_bytes;
+        cmd.mem_flags = self.flags;
+        cmd.transfer_mode = self.transfer_mode;
+        cmd
     }
 
     /// Returns a buffer command builder used to read.
@@ -482,6 +1443,268 @@ impl<T: OclPrm> Buffer<T> {
         self.cmd().write(data)
     }
 
+    /// Returns a buffer command builder used to read the rectangular
+    /// `region` at `buffer_origin` within this buffer to `host_origin`
+    /// within `data`, laid out as an array of `host_dims`.
+    ///
+    /// The buffer's row and slice pitches are computed from its dimensions
+    /// (a buffer with dimensions `[4096, 4096]` having rows of 4096
+    /// elements, for example) and those of `data` from `host_dims`, all in
+    /// elements. Use `BufferCmd::rect` to specify pitches directly.
+    ///
+    /// Call `.enq()` to enqueue the command. Regions which do not lie within
+    /// the buffer or `data` are rejected when enqueued.
+    pub fn read_rect<'b, B, H, R, D>(&'b self, buffer_origin: B, host_origin: H, region: R,
+            host_dims: D, data: &'b mut [T]) -> BufferCmd<'b, T>
+            where B: Into<ImageOrigin>, H: Into<ImageOrigin>, R: Into<ImageRegion>,
+                D: Into<ImageRegion>
+    {
+        let (buffer_row_pitch, buffer_slc_pitch) = self.rect_pitches();
+        let (host_row_pitch, host_slc_pitch) = rect_pitches(host_dims.into());
+
+        self.cmd().read(data).rect(buffer_origin, host_origin, region, buffer_row_pitch,
+            buffer_slc_pitch, host_row_pitch, host_slc_pitch)
+    }
+
+    /// Returns a buffer command builder used to write the rectangular
+    /// `region` at `host_origin` within `data`, laid out as an array of
+    /// `host_dims`, to `buffer_origin` within this buffer.
+    ///
+    /// Pitches are computed as for `::read_rect`.
+    ///
+    /// Call `.enq()` to enqueue the command.
+    pub fn write_rect<'b, B, H, R, D>(&'b self, buffer_origin: B, host_origin: H, region: R,
+            host_dims: D, data: &'b [T]) -> BufferCmd<'b, T>
+            where B: Into<ImageOrigin>, H: Into<ImageOrigin>, R: Into<ImageRegion>,
+                D: Into<ImageRegion>
+    {
+        let (buffer_row_pitch, buffer_slc_pitch) = self.rect_pitches();
+        let (host_row_pitch, host_slc_pitch) = rect_pitches(host_dims.into());
+
+        self.cmd().write(data).rect(buffer_origin, host_origin, region, buffer_row_pitch,
+            buffer_slc_pitch, host_row_pitch, host_slc_pitch)
+    }
+
+    /// Returns a buffer command builder used to copy the rectangular
+    /// `region` at `src_origin` within this buffer to `dst_origin` within
+    /// `dst_buffer`.
+    ///
+    /// The pitches of each buffer are computed from its dimensions (see
+    /// `::read_rect`).
+    ///
+    /// Call `.enq()` to enqueue the command.
+    pub fn copy_rect<'b, S, D, R>(&'b self, dst_buffer: &'b Buffer<T>, src_origin: S,
+            dst_origin: D, region: R) -> BufferCmd<'b, T>
+            where S: Into<ImageOrigin>, D: Into<ImageOrigin>, R: Into<ImageRegion>
+    {
+        let (src_row_pitch, src_slc_pitch) = self.rect_pitches();
+        let (dst_row_pitch, dst_slc_pitch) = dst_buffer.rect_pitches();
+
+        self.cmd().copy(dst_buffer, 0, 0).rect(src_origin, dst_origin, region, src_row_pitch,
+            src_slc_pitch, dst_row_pitch, dst_slc_pitch)
+    }
+
+    /// Returns the row and slice pitches, in elements, of this buffer's
+    /// dimensions.
+    fn rect_pitches(&self) -> (usize, usize) {
+        rect_pitches(self.dims.to_lens().unwrap_or([self.len, 1, 1]).into())
+    }
+
+    /// Returns a buffer command builder used to write without blocking,
+    /// taking ownership of `data` until the write completes (see
+    /// `BufferCmd::write_owned`).
+    ///
+    /// Call `.enq()` to enqueue the command.
+    pub fn write_owned<'b>(&'b self, data: Vec<T>) -> BufferCmd<'b, T> {
+        self.cmd().write_owned(data)
+    }
+
+    /// Reads the entire buffer (blocking) into a newly allocated `Vec`.
+    ///
+    /// ```rust,no_run
+    /// # fn main() { run().unwrap() }
+    /// # fn run() -> ocl::Result<()> {
+    /// # let pro_que = try!(ocl::ProQue::builder().src("").dims([1024]).build());
+    /// # let buffer = try!(pro_que.create_buffer::<f32>());
+    /// let vec: Vec<f32> = try!(buffer.read_to_vec());
+    /// assert_eq!(vec.len(), buffer.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Shorthand for `.cmd().read_to_vec()`. Use `::cmd` to read only part
+    /// of the buffer or to use another queue (see `BufferCmd::read_to_vec`).
+    pub fn read_to_vec(&self) -> OclResult<Vec<T>> {
+        self.cmd().read_to_vec()
+    }
+
+    /// Resizes `vec` to the length of the buffer then reads the entire
+    /// buffer into it (blocking).
+    ///
+    /// ```rust,no_run
+    /// # fn main() { run().unwrap() }
+    /// # fn run() -> ocl::Result<()> {
+    /// # let pro_que = try!(ocl::ProQue::builder().src("").dims([1024]).build());
+    /// # let buffer = try!(pro_que.create_buffer::<f32>());
+    /// let mut vec = Vec::new();
+    /// try!(buffer.read_into(&mut vec));
+    /// assert_eq!(vec.len(), buffer.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Shorthand for `.cmd().read_into(vec)` (see `BufferCmd::read_into`).
+    pub fn read_into(&self, vec: &mut Vec<T>) -> OclResult<()> {
+        self.cmd().read_into(vec)
+    }
+
+    /// Writes all of `data` to the buffer (blocking). The length of `data`
+    /// must equal the length of the buffer.
+    ///
+    /// ```rust,no_run
+    /// # fn main() { run().unwrap() }
+    /// # fn run() -> ocl::Result<()> {
+    /// # let pro_que = try!(ocl::ProQue::builder().src("").dims([1024]).build());
+    /// # let buffer = try!(pro_que.create_buffer::<f32>());
+    /// let data = vec![1.0f32; buffer.len()];
+    /// try!(buffer.write_all(&data));
+    /// assert!(buffer.write_all(&data[1..]).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Shorthand for `.cmd().write_all(data)`. Use `::cmd` to write only
+    /// part of the buffer or to use another queue (see
+    /// `BufferCmd::write_all`).
+    pub fn write_all(&self, data: &[T]) -> OclResult<()> {
+        self.cmd().write_all(data)
+    }
+
+    /// Enqueues a fill of `len` elements (or of the rest of the buffer if
+    /// `None`), beginning at the element, `offset`, with `value`.
+    ///
+    /// The pattern is copied when enqueued, so the fill need not have
+    /// completed when this returns. On devices or platforms which do not
+    /// support OpenCL 1.2, the fill is performed with a blocking write (see
+    /// `BufferCmd::enq`).
+    ///
+    /// Shorthand for `.cmd().fill(&[value], len).offset(offset).enq()`. Use
+    /// `::cmd` to fill with a longer pattern, wait on or create events, or
+    /// to use another queue.
+    pub fn fill(&self, value: T, offset: usize, len: Option<usize>) -> OclResult<()> {
+        self.cmd().fill(&[value], len).offset(offset).enq()
+    }
+
+    /// Maps `len` elements (or the rest of the buffer if `None`), beginning
+    /// at the element, `offset`, into host memory, once each event in
+    /// `wait_list` has completed.
+    ///
+    /// The map is enqueued on the default queue of the buffer (or of its
+    /// context) without blocking. The returned guard blocks until it has
+    /// completed when first dereferenced and unmaps the region when dropped
+    /// (see `MappedMem`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the region is empty or extends beyond the end of
+    /// the buffer.
+    pub fn map(&self, flags: MapFlags, offset: usize, len: Option<usize>,
+            wait_list: Option<&ClWaitList>) -> OclResult<MappedMem<T>>
+    {
+        let len = len.unwrap_or(self.len.saturating_sub(offset));
+
+        if len == 0 || offset > self.len || len > self.len - offset {
+            return OclError::err(format!("ocl::Buffer::map: Invalid region (offset: {}, len: \
+                {}). The region must be non-empty and lie within the buffer (len: {}).", offset,
+                len, self.len));
+        }
+
+        let queue = try!(resolve_queue("ocl::Buffer::map", None, self.queue.as_ref(),
+            self.context.as_ref())).into_owned();
+
+        if flags.intersects(core::MAP_WRITE | core::MAP_WRITE_INVALIDATE_REGION) {
+            self.mark_init();
+        }
+
+        MappedMem::map_buffer(queue, &self.obj_core, flags, offset, len, wait_list)
+    }
+
+    /// Returns a view of the elements within `range`, used to read, write,
+    /// copy, fill, or pass to a kernel only that part of the buffer (see
+    /// `BufferView`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `range` is reversed or extends beyond the end of
+    /// the buffer.
+    pub fn view(&self, range: Range<usize>) -> OclResult<BufferView<T>> {
+        BufferView::new(self, range)
+    }
+
+    /// Returns a new sub-buffer of the `len` elements beginning at the
+    /// element, `origin`, sharing this buffer's memory (no copy is made).
+    ///
+    /// Sub-buffers may be passed to kernels as any other buffer, allowing
+    /// one large allocation to be partitioned into several logical buffers.
+    /// Empty `flags` inherit the access flags of this buffer. The sub-buffer
+    /// keeps this buffer's memory object alive and uses the same default
+    /// queue.
+    ///
+    /// Unlike a view (see `::view`), `origin`, in bytes, must be aligned to
+    /// the `CL_DEVICE_MEM_BASE_ADDR_ALIGN` of a device within the context
+    /// (see `DeviceLimits::mem_base_addr_align_bytes`). Reading or writing a
+    /// buffer and its sub-buffers (or overlapping sub-buffers) concurrently
+    /// is undefined.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error, without calling into the OpenCL library, if `len`
+    /// is zero, the region extends beyond the end of this buffer, or this
+    /// buffer is itself a sub-buffer, and an error from the library if the
+    /// sub-buffer can not be created (e.g. when `origin` is misaligned).
+    pub fn create_sub_buffer(&self, flags: MemFlags, origin: usize, len: usize)
+            -> OclResult<Buffer<T>>
+    {
+        if self.parent.is_some() {
+            return OclError::err("ocl::Buffer::create_sub_buffer: Sub-buffers may not be \
+                created from other sub-buffers.");
+        }
+        if len == 0 {
+            return OclError::err("ocl::Buffer::create_sub_buffer: Sub-buffers may not be \
+                empty.");
+        }
+        match origin.checked_add(len) {
+            Some(end) if end <= self.len => (),
+            _ => return OclError::err(format!("ocl::Buffer::create_sub_buffer: The region \
+                (origin: {}, len: {}) extends beyond the end of the buffer (len: {}).", origin,
+                len, self.len)),
+        }
+
+        let region = try!(BufferRegion::from_elements::<T>(origin, len));
+        let obj_core = try!(core::create_sub_buffer(&self.obj_core, flags, &region));
+
+        Ok(Buffer {
+            obj_core: obj_core,
+            queue: self.queue.clone(),
+            context: self.context.clone(),
+            dims: SpatialDims::One(len),
+            len: len,
+            uninit: self.uninit.clone(),
+            swap_bytes: self.swap_bytes,
+            flags: if flags.is_empty() { self.flags } else { flags },
+            transfer_mode: self.transfer_mode,
+            parent: Some(self.obj_core.clone()),
+            _data: PhantomData,
+        })
+    }
+
+    /// Returns the memory object of the buffer this sub-buffer was created
+    /// from, or `None` if this is not a sub-buffer (see
+    /// `::create_sub_buffer`).
+    pub fn parent_core_as_ref(&self) -> Option<&MemCore> {
+        self.parent.as_ref()
+    }
+
     /// Returns the length of the Buffer.
     #[inline]
     pub fn len(&self) -> usize {
@@ -499,6 +1722,24 @@ impl<T: OclPrm> Buffer<T> {
         core::get_mem_object_info(&self.obj_core, info_kind)
     }
 
+    /// Returns the size of the underlying memory object in bytes.
+    pub fn size_bytes(&self) -> OclResult<usize> {
+        match self.mem_info(MemInfo::Size) {
+            MemInfoResult::Size(size) => Ok(size),
+            MemInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Buffer::size_bytes: Unexpected 'MemInfoResult' variant."),
+        }
+    }
+
+    /// Returns the flags the underlying memory object was created with.
+    pub fn flags(&self) -> OclResult<MemFlags> {
+        match self.mem_info(MemInfo::Flags) {
+            MemInfoResult::Flags(flags) => Ok(flags),
+            MemInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Buffer::flags: Unexpected 'MemInfoResult' variant."),
+        }
+    }
+
     /// Changes the default queue used by this Buffer for reads and writes, etc.
     ///
     /// Returns a ref for chaining i.e.:
@@ -512,13 +1753,55 @@ impl<T: OclPrm> Buffer<T> {
         // assert!(queue.device == self.queue.device);
         // [/FIXME]
 
-        self.queue = queue.clone();
+        self.queue = Some(queue.clone());
         self
     }
 
-    /// Returns a reference to the default queue.
-    pub fn default_queue(&self) -> &Queue {
-        &self.queue
+    /// Enables or disables byte swapping of the elements of reads, writes,
+    /// and fills when the endianness of the device (`CL_DEVICE_ENDIAN_LITTLE`)
+    /// differs from that of the host.
+    ///
+    /// Has no effect (and no overhead) when the endianness of the two
+    /// matches. Requires `T::swap_bytes` to be implemented (it is for all
+    /// scalar and vector types). Data accessed by kernels is never swapped.
+    pub fn set_swap_bytes<'a>(&'a mut self, swap_bytes: bool) -> &'a mut Buffer<T> {
+        self.swap_bytes = swap_bytes;
+        self
+    }
+
+    /// Returns true if byte swapping is enabled (see `::set_swap_bytes`).
+    pub fn swap_bytes(&self) -> bool {
+        self.swap_bytes
+    }
+
+    /// Sets the method used to transfer data for reads and writes (see
+    /// `TransferMode`). Defaults to `TransferMode::Auto`.
+    ///
+    /// Use `BufferCmd::transfer_mode` to override this for a single command.
+    pub fn set_transfer_mode<'a>(&'a mut self, mode: TransferMode) -> &'a mut Buffer<T> {
+        self.transfer_mode = mode;
+        self
+    }
+
+    /// Returns the method used to transfer data for reads and writes (see
+    /// `::set_transfer_mode`).
+    pub fn transfer_mode(&self) -> TransferMode {
+        self.transfer_mode
+    }
+
+    /// Returns a reference to the default queue, or `None` if the buffer was
+    /// created within a context (see `BufferBuilder::build_in_context`) and
+    /// none has been set since.
+    pub fn default_queue(&self) -> Option<&Queue> {
+        self.queue.as_ref()
+    }
+
+    /// Returns the queue used by commands which do not specify one: the
+    /// default queue of the buffer or, failing that, that of its context
+    /// (see the `Queue` docs).
+    pub fn resolve_queue(&self) -> OclResult<Queue> {
+        resolve_queue("ocl::Buffer::resolve_queue", None, self.queue.as_ref(),
+            self.context.as_ref()).map(|queue| queue.into_owned())
     }
 
     /// Returns a reference to the core pointer wrapper, usable by functions in
@@ -527,6 +1810,24 @@ impl<T: OclPrm> Buffer<T> {
         &self.obj_core
     }
 
+    /// Registers `callback` to be called once the underlying memory object
+    /// has been destroyed, after every clone of this buffer has been dropped
+    /// and any commands using it have completed.
+    ///
+    /// `callback` is run on a thread belonging to the OpenCL implementation.
+    /// Panics within it are caught and reported rather than propagated.
+    pub fn on_destroy<F>(&self, callback: F) -> OclResult<()>
+            where F: FnOnce() + Send + 'static
+    {
+        core::set_mem_object_destructor_callback(&self.obj_core, callback)
+    }
+
+    /// Marks the buffer as having been written to (by a kernel, for example),
+    /// suppressing the uninitialized read warning.
+    pub(crate) fn mark_init(&self) {
+        self.uninit.store(false, Ordering::SeqCst);
+    }
+
     /// Formats memory info.
     fn fmt_mem_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Buffer Mem")
@@ -537,12 +1838,82 @@ impl<T: OclPrm> Buffer<T> {
             .field("MapCount", &self.mem_info(MemInfo::MapCount))
             .field("ReferenceCount", &self.mem_info(MemInfo::ReferenceCount))
             .field("Context", &self.mem_info(MemInfo::Context))
-            .field("AssociatedMemobject", &self.mem_info(MemInfo::AssociatedMemobject))
+            .field("AssociatedMemObject", &self.mem_info(MemInfo::AssociatedMemObject))
             .field("Offset", &self.mem_info(MemInfo::Offset))
             .finish()
     }
 }
 
+#[cfg(feature = "f16")]
+impl Buffer<::half::f16> {
+    /// Reads the entire buffer (blocking) and returns its contents converted
+    /// to single-precision floats.
+    ///
+    /// Intended for debugging and verification.
+    pub fn read_as_f32(&self) -> OclResult<Vec<f32>> {
+        let mut vec = vec![::half::f16::default(); self.len];
+        try!(self.read(&mut vec).enq());
+        Ok(vec.iter().map(|v| v.to_f32()).collect())
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<T: OclPrm> Buffer<T> {
+    /// Creates a new one-dimensional buffer within the context of `queue`
+    /// (its default queue) containing the elements of `array` in logical
+    /// (row-major) order.
+    ///
+    /// ```rust,no_run
+    /// # extern crate ocl;
+    /// # extern crate ndarray;
+    /// # fn main() { run().unwrap() }
+    /// # fn run() -> ocl::Result<()> {
+    /// # let pro_que = try!(ocl::ProQue::builder().src("").dims([1]).build());
+    /// let array = ndarray::Array2::<f32>::from_elem((16, 8), 1.0);
+    /// let buffer = try!(ocl::Buffer::from_array(pro_que.queue(), &array));
+    /// assert_eq!(try!(buffer.read_to_array((16, 8))), array);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Arrays not in standard layout (transposed or strided views, for
+    /// example) are first copied into a contiguous array.
+    pub fn from_array<S, D>(queue: &Queue, array: &::ndarray::ArrayBase<S, D>)
+            -> OclResult<Buffer<T>>
+            where S: ::ndarray::Data<Elem = T>, D: ::ndarray::Dimension
+    {
+        let array = array.as_standard_layout();
+        let data = array.as_slice().expect("ocl::Buffer::from_array: Array not contiguous.");
+
+        Buffer::builder()
+            .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+            .dims([data.len()])
+            .build_with_data(queue, data)
+    }
+
+    /// Reads the entire buffer (blocking) into a new array of the given
+    /// `shape`, in logical (row-major) order.
+    ///
+    /// Returns an error if the number of elements in `shape` differs from
+    /// the length of the buffer.
+    pub fn read_to_array<Sh>(&self, shape: Sh) -> OclResult<::ndarray::Array<T, Sh::Dim>>
+            where Sh: ::ndarray::ShapeBuilder
+    {
+        let shape = shape.into_shape();
+        if shape.size() != self.len {
+            return OclError::err(format!("ocl::Buffer::read_to_array(): The number of elements \
+                in the shape ({:?}: {}) does not match the length of the buffer ({}).",
+                ::ndarray::Dimension::slice(shape.raw_dim()), shape.size(), self.len));
+        }
+
+        let vec = try!(self.read_to_vec());
+        match ::ndarray::Array::from_shape_vec(shape, vec) {
+            Ok(array) => Ok(array),
+            Err(err) => OclError::err(format!("ocl::Buffer::read_to_array(): {}", err)),
+        }
+    }
+}
+
 impl<T: OclPrm> Deref for Buffer<T> {
     type Target = MemCore;
 
@@ -563,3 +1934,21 @@ impl<T: OclPrm> std::fmt::Display for Buffer<T> {
     }
 }
 
+/// Shows the element type, length, size in bytes, dimensions, flags, and
+/// transfer mode, all cached when the buffer was created (no calls are made
+/// to the implementation), along with the memory object. The alternate form
+/// (`{:#?}`) adds info queried about the memory object (see `core::Mem`).
+impl<T: OclPrm> std::fmt::Debug for Buffer<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Buffer")
+            .field("type", &format_args!("{}", util::short_type_name::<T>()))
+            .field("len", &self.len)
+            .field("size_bytes", &(self.len * mem::size_of::<T>()))
+            .field("dims", &self.dims)
+            .field("flags", &self.flags)
+            .field("transfer_mode", &self.transfer_mode)
+            .field("mem", &self.obj_core)
+            .finish()
+    }
+}
+
@@ -0,0 +1,225 @@
+//! A range of elements within a buffer.
+
+use std;
+use std::mem;
+use std::ops::Range;
+use core::{self, OclPrm, Mem as MemCore, MemFlags, BufferRegion};
+use error::{Error as OclError, Result as OclResult};
+use standard::{Buffer, BufferCmd};
+
+/// A range of elements within a buffer, used to read, write, copy, fill, or
+/// pass to a kernel only that part of it.
+///
+/// Unlike a sub-buffer, a view may begin at any element: commands simply
+/// apply its offset, and every command (and further view) is checked to lie
+/// within it. Offsets given to commands on a view (see `::cmd`) are
+/// relative to its start.
+///
+/// A view holds a handle to its buffer (see `Buffer::view`), keeping it
+/// alive.
+///
+/// ## Kernels
+///
+/// A view is passed to a kernel as two arguments (see `Kernel::arg_view`):
+/// a buffer and a `uint` offset, in elements, to be added to each index.
+/// When the start of the view is suitably aligned for a sub-buffer (see
+/// `::is_aligned`), a sub-buffer covering only the view is passed along with
+/// an offset of zero, otherwise the whole buffer is passed along with the
+/// offset of the view:
+///
+/// ```text
+/// __kernel void add(__global float* buf, uint buf_offset, float val) {
+///     buf[buf_offset + get_global_id(0)] += val;
+/// }
+/// ```
+///
+/// ## Examples
+///
+/// ```text
+/// let view = buffer.view(256..512).unwrap();
+/// view.write(&data).enq().unwrap();
+///
+/// let (head, tail) = view.split_at(128).unwrap();
+/// tail.cmd().fill(&[0.0], None).enq().unwrap();
+/// head.cmd().copy_to_view(&other_view).enq().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct BufferView<T: OclPrm> {
+    buffer: Buffer<T>,
+    offset: usize,
+    len: usize,
+}
+
+impl<T: OclPrm> BufferView<T> {
+    /// Returns a view of the elements of `buffer` within `range`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `range` is reversed or extends beyond the end of
+    /// the buffer.
+    pub fn new(buffer: &Buffer<T>, range: Range<usize>) -> OclResult<BufferView<T>> {
+        try!(check_range("ocl::BufferView::new", &range, buffer.len()));
+
+        Ok(BufferView {
+            buffer: buffer.clone(),
+            offset: range.start,
+            len: range.end - range.start,
+        })
+    }
+
+    /// Returns a view of the elements within `range`, relative to the start
+    /// of this view.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `range` is reversed or extends beyond the end of
+    /// this view.
+    pub fn view(&self, range: Range<usize>) -> OclResult<BufferView<T>> {
+        try!(check_range("ocl::BufferView::view", &range, self.len));
+
+        Ok(BufferView {
+            buffer: self.buffer.clone(),
+            offset: self.offset + range.start,
+            len: range.end - range.start,
+        })
+    }
+
+    /// Divides this view into two at `mid`, the first containing the
+    /// elements before it and the second the rest.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `mid` is beyond the end of this view.
+    pub fn split_at(&self, mid: usize) -> OclResult<(BufferView<T>, BufferView<T>)> {
+        if mid > self.len {
+            return OclError::err(format!("ocl::BufferView::split_at: Index ({}) out of range \
+                for a view of length {}.", mid, self.len));
+        }
+
+        Ok((try!(self.view(0..mid)), try!(self.view(mid..self.len))))
+    }
+
+    /// Returns a buffer command builder restricted to this view.
+    ///
+    /// Offsets (see `BufferCmd::offset`) are relative to the start of the
+    /// view, fills without a length fill the rest of the view, and whole
+    /// buffer reads and writes (such as `BufferCmd::read_to_vec`) cover the
+    /// rest of the view. Commands extending beyond the view, as well as
+    /// rectangular commands and copies to images, return an error when
+    /// enqueued.
+    pub fn cmd<'b>(&'b self) -> BufferCmd<'b, T> {
+        self.buffer.cmd().within(self.offset..self.offset + self.len)
+    }
+
+    /// Returns a buffer command builder used to read from this view.
+    pub fn read<'b>(&'b self, data: &'b mut [T]) -> BufferCmd<'b, T> {
+        self.cmd().read(data)
+    }
+
+    /// Returns a buffer command builder used to write to this view.
+    pub fn write<'b>(&'b self, data: &'b [T]) -> BufferCmd<'b, T> {
+        self.cmd().write(data)
+    }
+
+    /// Reads the entire view (blocking) into a newly allocated `Vec`.
+    pub fn read_to_vec(&self) -> OclResult<Vec<T>> {
+        self.cmd().read_to_vec()
+    }
+
+    /// Returns the buffer this is a view of.
+    pub fn buffer(&self) -> &Buffer<T> {
+        &self.buffer
+    }
+
+    /// Returns the offset, in elements, of the start of this view within
+    /// its buffer.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the number of elements within this view.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if this view contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the range of elements of the buffer within this view.
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.len
+    }
+
+    /// Returns true if a sub-buffer covering this view can be created on the
+    /// device of the buffer's queue (see `Buffer::resolve_queue`): the device
+    /// must support OpenCL 1.1 and the start of the view must be aligned to
+    /// its `DeviceInfo::MemBaseAddrAlign`.
+    pub fn is_aligned(&self) -> bool {
+        match self.buffer.resolve_queue() {
+            Ok(queue) => {
+                let align = queue.device_limits().mem_base_addr_align_bytes();
+                queue.device_version() >= (1, 1) && align != 0 &&
+                    (self.offset * mem::size_of::<T>()) % align == 0
+            },
+            Err(_) => false,
+        }
+    }
+
+    /// Creates a sub-buffer covering this view, inheriting the access flags
+    /// of the buffer.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if this view is empty or if the sub-buffer can not
+    /// be created, as when the view is not aligned (see `::is_aligned`).
+    pub fn create_sub_buffer(&self) -> OclResult<MemCore> {
+        if self.is_empty() {
+            return OclError::err("ocl::BufferView::create_sub_buffer: Sub-buffers may not be \
+                empty.");
+        }
+
+        core::create_sub_buffer(self.buffer.core_as_ref(), MemFlags::empty(), &BufferRegion {
+            origin: self.offset * mem::size_of::<T>(),
+            size: self.len * mem::size_of::<T>(),
+        })
+    }
+
+    /// Returns the memory object and offset passed to a kernel for this view
+    /// (see the type docs), creating a sub-buffer if aligned.
+    pub(crate) fn kernel_args(&self) -> OclResult<(MemCore, u32)> {
+        if self.offset != 0 && !self.is_empty() && self.is_aligned() {
+            if let Ok(sub_buffer) = self.create_sub_buffer() {
+                return Ok((sub_buffer, 0));
+            }
+        }
+
+        if self.offset > std::u32::MAX as usize {
+            return OclError::err(format!("ocl::BufferView: The offset of the view ({}) is too \
+                large to be passed to a kernel as a 'uint'.", self.offset));
+        }
+
+        Ok((self.buffer.core_as_ref().clone(), self.offset as u32))
+    }
+}
+
+impl<T: OclPrm> std::fmt::Debug for BufferView<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("BufferView")
+            .field("buffer", &self.buffer.core_as_ref())
+            .field("offset", &self.offset)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// Returns an error if `range` is reversed or extends beyond `len`.
+fn check_range(fn_name: &str, range: &Range<usize>, len: usize) -> OclResult<()> {
+    if range.start > range.end || range.end > len {
+        OclError::err(format!("{}: Range ({:?}) out of range for a length of {}.", fn_name,
+            range, len))
+    } else {
+        Ok(())
+    }
+}
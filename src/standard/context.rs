@@ -5,12 +5,14 @@
 
 // use formatting::MT;
 use std;
-use std::ops::{Deref, DerefMut};
+use std::ops::Deref;
+use std::sync::{Arc, Weak, Mutex};
 use core::{self, Context as ContextCore, ContextProperties, ContextInfo, ContextInfoResult, 
     DeviceInfo, DeviceInfoResult, PlatformId as PlatformIdCore, PlatformInfo, PlatformInfoResult, 
     CreateContextCallbackFn, UserDataPtr};
+use cl_h::cl_device_id;
 use error::{Result as OclResult, Error as OclError};
-use standard::{Platform, Device, DeviceSpecifier};
+use standard::{Platform, Device, DeviceSpecifier, DeviceLimits, Queue};
 
 
 /// A builder for `Context`.
@@ -20,6 +22,7 @@ pub struct ContextBuilder {
     properties: Option<ContextProperties>,
     platform: Option<Platform>,
     device_spec: Option<DeviceSpecifier>,
+    notify: Option<Arc<Fn(&str) + Send + Sync>>,
 }
 
 impl ContextBuilder {
@@ -42,6 +45,7 @@ impl ContextBuilder {
             properties: None,
             platform: None,
             device_spec: None,
+            notify: None,
         }
     }
 
@@ -58,13 +62,13 @@ impl ContextBuilder {
             None => {
                 let platform = match self.platform {
                     Some(ref plat) => plat.clone(),
-                    None => Platform::default(),
+                    None => try!(Platform::try_default()),
                 };
                 Some(ContextProperties::new().platform::<PlatformIdCore>(platform.into()))
             },
         };
 
-        Context::new(properties, self.device_spec.clone(), None, None)
+        Context::create(properties, self.device_spec.clone(), self.notify.clone())
     }
 
     /// Specifies a platform.
@@ -99,6 +103,10 @@ impl ContextBuilder {
     /// See [`DeviceSpecifier`](/ocl/ocl/enum.DeviceSpecifier.html) for actually
     /// useful documentation.
     ///
+    /// If not specified, the devices returned by `core::default_devices` are
+    /// used (all devices unless narrowed by the `OCL_DEVICE_TYPE` and
+    /// `OCL_DEVICE_INDEX` environment variables).
+    ///
     /// ## Panics
     ///
     /// Panics if any devices have already been specified.
@@ -111,6 +119,20 @@ impl ContextBuilder {
         self
     }
 
+    /// Specifies a closure to be called with a description of each error
+    /// reported by the implementation for the lifetime of the context.
+    ///
+    /// The closure may be called from any thread, including concurrently,
+    /// and panics within it are caught and reported rather than propagated.
+    /// It is never dropped unless creation fails (see
+    /// `core::create_context_notify`).
+    pub fn notify<'a, F>(&'a mut self, notify: F) -> &'a mut ContextBuilder
+            where F: Fn(&str) + Send + Sync + 'static
+    {
+        self.notify = Some(Arc::new(notify));
+        self
+    }
+
     // // [FIXME: Add these]
     //
     // pub fn device_idx_round_robin
@@ -125,14 +147,34 @@ impl ContextBuilder {
 /// Thread safety and destruction for any enclosed pointers are all handled automatically. 
 /// Clone, store, and share between threads to your heart's content.
 ///
+/// The limits of each device (see `DeviceLimits`) are queried once, upon
+/// creation, and shared with every queue, buffer, and image created using
+/// the context.
+///
+/// ## Sharing
+///
+/// A `Context` is a handle to shared state (the context object along with
+/// its platform, devices, and device limits) and cloning one only
+/// increments a reference count. The context object is released once the
+/// last handle (and every queue, program, and memory object using it) is
+/// dropped. Use `::downgrade` for a handle which does not keep it alive.
+///
 /// [TODO]: Consider removing contained copies of the device id list and
 /// platform id. Can be easily ascertained via the API.
 /// 
 #[derive(Debug, Clone)]
 pub struct Context {
+    inner: Arc<ContextInner>,
+}
+
+/// The state shared by each clone of a `Context`.
+#[derive(Debug)]
+struct ContextInner {
     obj_core: ContextCore,
     platform: Option<Platform>,
     devices: Vec<Device>,
+    device_limits: Vec<Arc<DeviceLimits>>,
+    default_queue: Mutex<Option<Queue>>,
 }
 
 impl Context {
@@ -163,44 +205,83 @@ impl Context {
     ///
     /// ## Panics
     ///
-    /// [TEMPORARY] Passing a `Some` variant for `pfn_notify` or `user_data` is 
-    /// not yet supported.
+    /// Passing a `Some` variant for `pfn_notify` or `user_data` is not
+    /// supported. Use `ContextBuilder::notify` instead.
     ///
     pub fn new(properties: Option<ContextProperties>, device_spec: Option<DeviceSpecifier>, 
                 pfn_notify: Option<CreateContextCallbackFn>, user_data: Option<UserDataPtr>) 
             -> OclResult<Context> 
     {
         assert!(pfn_notify.is_none() && user_data.is_none(), 
-            "Context creation callbacks not yet implemented.");
+            "Raw context creation callbacks are not supported. Use 'ContextBuilder::notify'.");
 
+        Context::create(properties, device_spec, None)
+    }
+
+    /// Creates a context, passing any errors reported by the implementation
+    /// to `notify`.
+    fn create(properties: Option<ContextProperties>, device_spec: Option<DeviceSpecifier>,
+            notify: Option<Arc<Fn(&str) + Send + Sync>>) -> OclResult<Context>
+    {
         let platform: Option<Platform> = match properties {
             Some(ref props) => props.get_platform().clone().map(|p| Platform::new(p)),
             None => None,
         };
 
-        let device_spec = match device_spec {
-            Some(ds) => ds,
-            None => DeviceSpecifier::All,
+        let device_list = match device_spec {
+            Some(ds) => try!(ds.to_device_list(platform.clone())),
+            None => {
+                let platform = match platform {
+                    Some(ref p) => p.clone(),
+                    None => try!(Platform::try_default()),
+                };
+                Device::list_from_core(try!(core::default_devices(platform.as_core())))
+            },
         };
 
-        let device_list = try!(device_spec.to_device_list(platform.clone()));
+        let obj_core = match notify {
+            Some(notify) => try!(core::create_context_notify(&properties, &device_list,
+                move |errinfo: &str| notify(errinfo))),
+            None => try!(core::create_context(&properties, &device_list, None, None)),
+        };
 
-        let obj_core = try!(core::create_context(&properties, &device_list, pfn_notify, user_data));
+        let mut device_limits = Vec::with_capacity(device_list.len());
+        for device in device_list.iter() {
+            device_limits.push(Arc::new(try!(DeviceLimits::new(device))));
+        }
 
         Ok(Context {
-            obj_core: obj_core,
-            platform: platform,
-            devices: device_list,
+            inner: Arc::new(ContextInner {
+                obj_core: obj_core,
+                platform: platform,
+                devices: device_list,
+                device_limits: device_limits,
+                default_queue: Mutex::new(None),
+            }),
         })
     }
 
+    /// Returns a weak handle to this context which does not keep it alive.
+    pub fn downgrade(&self) -> WeakContext {
+        WeakContext { inner: Arc::downgrade(&self.inner) }
+    }
+
+    /// Returns the number of `Context` handles (clones) sharing this context.
+    ///
+    /// Handles held by queues and other objects created from this context
+    /// are not counted (see `ContextInfo::ReferenceCount` for the reference
+    /// count of the context object itself).
+    pub fn handle_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+
     /// Resolves a list of zero-based device indices into a list of Devices.
     ///
     /// If any index is out of bounds it will wrap around zero (%) to the next
     /// valid device index.
     ///
     pub fn resolve_wrapping_device_idxs(&self, idxs: &[usize]) -> Vec<Device> {
-        Device::resolve_idxs_wrap(idxs, &self.devices)
+        Device::resolve_idxs_wrap(idxs, &self.inner.devices)
     }
 
     /// Returns a device by its ordinal count within this context.
@@ -224,7 +305,7 @@ impl Context {
     /// Returns info about the device indexed by `index` associated with this
     /// context.
     pub fn device_info(&self, index: usize, info_kind: DeviceInfo) -> DeviceInfoResult {
-        let device = match self.devices.get(index) {
+        let device = match self.inner.devices.get(index) {
             Some(d) => d,
             None => {
                 return DeviceInfoResult::Error(Box::new(
@@ -239,13 +320,36 @@ impl Context {
         core::get_device_info(device, info_kind)
     }
 
+    /// Returns the cached limits of the device indexed by `index` associated
+    /// with this context.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `index` is out of bounds.
+    pub fn device_limits(&self, index: usize) -> OclResult<&DeviceLimits> {
+        match self.inner.device_limits.get(index) {
+            Some(limits) => Ok(limits),
+            None => OclError::err(format!("ocl::Context::device_limits: Invalid device index \
+                ({}, of {} devices).", index, self.inner.devices.len())),
+        }
+    }
+
+    /// Returns a shared reference to the cached limits of `device`,
+    /// querying them if `device` is not associated with this context.
+    pub(crate) fn limits_of(&self, device: &Device) -> OclResult<Arc<DeviceLimits>> {
+        match self.inner.devices.iter().position(|d| d.as_core() == device.as_core()) {
+            Some(idx) => Ok(self.inner.device_limits[idx].clone()),
+            None => DeviceLimits::new(device).map(Arc::new),
+        }
+    }
+
     /// Returns info about the context. 
     pub fn info(&self, info_kind: ContextInfo) -> ContextInfoResult {
-        // match core::get_context_info(&self.obj_core, info_kind) {
+        // match core::get_context_info(&self.inner.obj_core, info_kind) {
         //     Ok(pi) => pi,
         //     Err(err) => ContextInfoResult::Error(Box::new(err)),
         // }
-        core::get_context_info(&self.obj_core, info_kind)
+        core::get_context_info(&self.inner.obj_core, info_kind)
     }
 
     // /// Returns a string containing a formatted list of context properties.
@@ -256,17 +360,82 @@ impl Context {
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     pub fn core_as_ref(&self) -> &ContextCore {
-        &self.obj_core
+        &self.inner.obj_core
     }
 
     /// Returns the list of devices associated with this context.
     pub fn devices(&self) -> &[Device] {
-        &self.devices[..]
+        &self.inner.devices[..]
+    }
+
+    /// Returns the raw `cl_device_id`s of the devices associated with this
+    /// context, without copying them.
+    ///
+    /// Root device ids are not reference counted and remain valid for the
+    /// life of the process.
+    pub fn devices_raw(&self) -> &[cl_device_id] {
+        // `Device` and `DeviceIdCore` are both `repr(C)` wrappers around a
+        // `cl_device_id`:
+        unsafe {
+            std::slice::from_raw_parts(self.inner.devices.as_ptr() as *const cl_device_id,
+                self.inner.devices.len())
+        }
     }
 
     /// Returns the platform this context is associated with.
     pub fn platform(&self) -> Option<Platform> {
-        self.platform.clone()
+        self.inner.platform.clone()
+    }
+
+    /// Sets the default queue, used by commands on buffers, images, and
+    /// kernels created from this context which have no default queue of
+    /// their own (see the `Queue` docs for the resolution order).
+    ///
+    /// ## Errors
+    ///
+    /// `queue` must be associated with this context.
+    pub fn set_default_queue(&self, queue: &Queue) -> OclResult<()> {
+        if queue.context_core_as_ref() != &self.inner.obj_core {
+            return OclError::err("ocl::Context::set_default_queue: The queue is not associated \
+                with this context.");
+        }
+        *self.lock_default_queue() = Some(queue.clone());
+        Ok(())
+    }
+
+    /// Returns the default queue or `None` if none has been set or created
+    /// (see `::set_default_queue` and `::get_or_create_default_queue`).
+    pub fn default_queue(&self) -> Option<Queue> {
+        self.lock_default_queue().clone()
+    }
+
+    /// Returns the default queue, first creating one on the first device
+    /// associated with this context and setting it as the default if none
+    /// has been set.
+    pub fn get_or_create_default_queue(&self) -> OclResult<Queue> {
+        let mut default_queue = self.lock_default_queue();
+
+        if let Some(ref queue) = *default_queue {
+            return Ok(queue.clone());
+        }
+
+        let device = match self.inner.devices.first() {
+            Some(device) => device.clone(),
+            None => return OclError::err("ocl::Context::get_or_create_default_queue: The \
+                context has no devices."),
+        };
+        let queue = try!(Queue::new(self, device));
+        *default_queue = Some(queue.clone());
+        Ok(queue)
+    }
+
+    /// Locks the default queue, ignoring poisoning (the value is always
+    /// consistent).
+    fn lock_default_queue(&self) -> ::std::sync::MutexGuard<'_, Option<Queue>> {
+        match self.inner.default_queue.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
     }
 
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -279,9 +448,30 @@ impl Context {
     }
 }
 
+/// Formats the platform name followed by the name of each device, e.g.:
+/// `NVIDIA CUDA: GeForce GTX 1080, GeForce GTX 1070`, with the raw handle
+/// in brackets in place of any name which can not be queried.
+///
+/// Use the alternate flag (`{:#}`) to list every property instead.
 impl std::fmt::Display for Context {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        self.fmt_info(f)
+        if f.alternate() { return self.fmt_info(f); }
+
+        let devices: Vec<String> = self.inner.devices.iter().map(|device| {
+            match device.info(DeviceInfo::Name) {
+                DeviceInfoResult::Name(name) => name,
+                _ => format!("[{:?}]", device.as_core()),
+            }
+        }).collect();
+
+        if let Some(ref platform) = self.inner.platform {
+            match platform.info(PlatformInfo::Name) {
+                PlatformInfoResult::Name(name) => try!(write!(f, "{}: ", name)),
+                _ => try!(write!(f, "[{:?}]: ", platform.as_core())),
+            }
+        }
+
+        f.write_str(&devices.join(", "))
     }
 }
 
@@ -289,13 +479,24 @@ impl Deref for Context {
     type Target = ContextCore;
 
     fn deref(&self) -> &ContextCore {
-        &self.obj_core
+        &self.inner.obj_core
     }
 }
 
-impl DerefMut for Context {
-    fn deref_mut(&mut self) -> &mut ContextCore {
-        &mut self.obj_core
+/// A weak handle to a `Context` which does not keep the context alive.
+///
+/// Useful for caches which should not extend the lifetime of a context.
+/// Create with `Context::downgrade`.
+#[derive(Debug, Clone)]
+pub struct WeakContext {
+    inner: Weak<ContextInner>,
+}
+
+impl WeakContext {
+    /// Returns a handle to the context or `None` if every `Context` handle
+    /// has been dropped.
+    pub fn upgrade(&self) -> Option<Context> {
+        self.inner.upgrade().map(|inner| Context { inner: inner })
     }
 }
 
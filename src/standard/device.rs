@@ -4,11 +4,11 @@
 use std;
 use std::ops::{Deref, DerefMut};
 use std::convert::Into;
-use std::error::Error;
 // use std::borrow::Borrow;
 use error::{Error as OclError, Result as OclResult};
-use standard::Platform;
-use core::{self, DeviceId as DeviceIdCore, DeviceType, DeviceInfo, DeviceInfoResult, ClDeviceIdPtr};
+use standard::{Platform, DeviceInfoSource, BenchWorkload, BenchReport};
+use core::{self, DeviceId as DeviceIdCore, DeviceType, DeviceInfo, DeviceInfoResult, ClDeviceIdPtr,
+    DeviceExecCapabilities};
 use util;
 
 const DEBUG_PRINT: bool = false;
@@ -80,12 +80,12 @@ impl DeviceSpecifier {
     pub fn to_device_list(&self, platform: Option<Platform>) -> OclResult<Vec<Device>> {
         let platform = match platform {
             Some(p) => p.clone(),
-            None => Platform::default(),
+            None => try!(Platform::try_default()),
         };
 
         Ok(match self {
             &DeviceSpecifier::All => {
-                Device::list_all(&platform)
+                try!(Device::list_all(&platform))
             },
             &DeviceSpecifier::First => {
                 try!(Device::list_select(&platform, None, &vec![0]))
@@ -117,13 +117,13 @@ impl DeviceSpecifier {
                 //             DeviceSpecifier::Indices: Device index out of range.");
                 //         device_list_all[idx].clone()
                 //     } ).collect()
-                Device::list_select_wrap(&platform, None, idx_list)
+                try!(Device::list_select_wrap(&platform, None, idx_list))
             },
             &DeviceSpecifier::TypeFlags(flags) => {
                 // Device::list_from_core(try!(
                 //     core::get_device_ids(platform_id_core.clone(), Some(flags))
                 // ))
-                Device::list(&platform, Some(flags))
+                try!(Device::list(&platform, Some(flags)))
             },
         })
     }
@@ -193,15 +193,29 @@ impl From<DeviceType> for DeviceSpecifier {
 
 
 /// A device identifier.
+#[repr(C)]
 #[derive(Clone, Copy, Debug)]
 pub struct Device(DeviceIdCore);
 
 impl Device {
-    /// Returns the first available device on a platform
-    pub fn first(platform: Platform) -> Device {
-        let first_core = core::get_device_ids(&platform, None, None)
-            .expect("ocl::Device::first: Error retrieving device list");
-        Device(first_core[0])
+    /// Returns the first available device on a platform.
+    pub fn first(platform: Platform) -> OclResult<Device> {
+        let first_core = try!(core::get_device_ids(&platform, None, None));
+
+        match first_core.first() {
+            Some(&device) => Ok(Device(device)),
+            None => OclError::err("ocl::Device::first: No devices found on the platform."),
+        }
+    }
+
+    /// Returns the first available device on a platform.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the platform has no devices or the device list can not be
+    /// retrieved. Use `::first` to handle the error instead.
+    pub fn first_unwrap(platform: Platform) -> Device {
+        Device::first(platform).expect("ocl::Device::first_unwrap")
     }
 
     /// Returns a `DeviceSpecifier` useful for precisely specifying a set
@@ -249,26 +263,46 @@ impl Device {
     /// Setting `device_types` to `None` will return a list of all avaliable
     /// devices for `platform` regardless of type.
     ///
-    pub fn list(platform: &Platform, device_types: Option<DeviceType>) -> Vec<Device> {
-        let list_core = core::get_device_ids(platform.as_core(), device_types, None)
-            .expect("Device::list: Error retrieving device list");
+    pub fn list(platform: &Platform, device_types: Option<DeviceType>) -> OclResult<Vec<Device>> {
+        let list_core = try!(core::get_device_ids(platform.as_core(), device_types, None));
         let list = list_core.into_iter().map(|pr| Device(pr) ).collect();
         if DEBUG_PRINT { println!("Devices::list(): device_types: {:?} -> list: {:?}", 
             device_types, list); }
-        list
+        Ok(list)
+    }
+
+    /// Returns a list of all devices avaliable for a given platform which
+    /// optionally match the flags set in the bitfield, `device_types`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the device list can not be retrieved. Use `::list` to
+    /// handle the error instead.
+    pub fn list_unwrap(platform: &Platform, device_types: Option<DeviceType>) -> Vec<Device> {
+        Device::list(platform, device_types).expect("ocl::Device::list_unwrap")
     }
 
     /// Returns a list of all devices avaliable for a given `platform`.
     ///
     /// Equivalent to `::list(platform, None)`.
     ///
-    pub fn list_all(platform: &Platform) -> Vec<Device> {
+    pub fn list_all(platform: &Platform) -> OclResult<Vec<Device>> {
         // let list_core = core::get_device_ids(Some(platform.as_core()), None)
         //     .expect("Device::list_all: Error retrieving device list");        
         // list_core.into_iter().map(|pr| Device(pr) ).collect()
         Self::list(platform, None)
     }
 
+    /// Returns a list of all devices avaliable for a given `platform`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the device list can not be retrieved. Use `::list_all` to
+    /// handle the error instead.
+    pub fn list_all_unwrap(platform: &Platform) -> Vec<Device> {
+        Self::list_all(platform).expect("ocl::Device::list_all_unwrap")
+    }
+
     /// Returns a list of devices filtered by type then selected using a
     /// list of indices.
     ///
@@ -279,7 +313,7 @@ impl Device {
     pub fn list_select(platform: &Platform, device_types: Option<DeviceType>,
             idxs: &[usize]) -> OclResult<Vec<Device>>
     {
-        Self::resolve_idxs(idxs, &Self::list(platform, device_types))
+        Self::resolve_idxs(idxs, &try!(Self::list(platform, device_types)))
     }
 
     /// Returns a list of devices filtered by type then selected using a
@@ -287,13 +321,24 @@ impl Device {
     ///
     /// Wraps indices around (`%`) so that every index is valid.
     ///
+    /// # Errors
+    ///
+    /// The platform must have at least one device matching `device_types`.
+    ///
     pub fn list_select_wrap(platform: &Platform, device_types: Option<DeviceType>,
-            idxs: &[usize]) -> Vec<Device> 
+            idxs: &[usize]) -> OclResult<Vec<Device>>
     {
-        let list = Self::resolve_idxs_wrap(idxs, &Self::list(platform, device_types));
+        let devices = try!(Self::list(platform, device_types));
+
+        if devices.is_empty() {
+            return OclError::err(format!("ocl::Device::list_select_wrap: No devices found \
+                (device_types: {:?}).", device_types));
+        }
+
+        let list = Self::resolve_idxs_wrap(idxs, &devices);
         if DEBUG_PRINT { println!("Devices::list_select_wrap(): device_types: {:?} \
             -> list: {:?}", device_types, list); }
-        list
+        Ok(list)
     }
 
     // /// Creates a new `Device` from a `DeviceIdCore`.
@@ -329,11 +374,148 @@ impl Device {
     }
 
     /// Returns the maximum workgroup size.
-    pub fn max_wg_size(&self) -> usize {
-        match self.info(DeviceInfo::MaxWorkGroupSize) {
-            DeviceInfoResult::MaxWorkGroupSize(s) => s,
-            DeviceInfoResult::Error(err) => panic!("ocl::Device::max_wg_size: {}", err.description()),
-            _ => panic!("ocl::Device::max_wg_size: Unexpected 'DeviceInfoResult' variant."),
+    pub fn max_wg_size(&self) -> OclResult<usize> {
+        DeviceInfoSource::max_wg_size(self)
+    }
+
+    /// Returns the maximum number of work item dimensions.
+    pub fn max_wi_dims(&self) -> OclResult<u32> {
+        DeviceInfoSource::max_wi_dims(self)
+    }
+
+    /// Returns the maximum number of work items for each dimension of a
+    /// work group.
+    pub fn max_wi_sizes(&self) -> OclResult<Vec<usize>> {
+        DeviceInfoSource::max_wi_sizes(self)
+    }
+
+    /// Returns true if the device is little endian.
+    pub fn endian_little(&self) -> OclResult<bool> {
+        DeviceInfoSource::endian_little(self)
+    }
+
+    /// Returns true if the device and the host share a unified memory
+    /// subsystem (as is typical of integrated GPUs).
+    ///
+    /// Deprecated as of OpenCL 2.0 (though typically still available).
+    pub fn host_unified_memory(&self) -> OclResult<bool> {
+        DeviceInfoSource::host_unified_memory(self)
+    }
+
+    /// Returns the number of parallel compute units.
+    pub fn max_compute_units(&self) -> OclResult<u32> {
+        DeviceInfoSource::max_compute_units(self)
+    }
+
+    /// Returns the size of global memory in bytes.
+    pub fn global_mem_size(&self) -> OclResult<u64> {
+        DeviceInfoSource::global_mem_size(self)
+    }
+
+    /// Returns the execution capabilities of the device.
+    pub fn execution_capabilities(&self) -> OclResult<DeviceExecCapabilities> {
+        DeviceInfoSource::execution_capabilities(self)
+    }
+
+    /// Returns the OpenCL version supported by the device as a string of the
+    /// form: `OpenCL<space><major_version.minor_version><space><vendor-specific
+    /// information>`.
+    pub fn version(&self) -> OclResult<String> {
+        DeviceInfoSource::version(self)
+    }
+
+    /// Returns the OpenCL version supported by the device as a
+    /// `(major, minor)` pair, e.g. `(1, 2)`.
+    pub fn version_number(&self) -> OclResult<(u32, u32)> {
+        DeviceInfoSource::version_number(self)
+    }
+
+    /// Returns the space-separated list of extensions supported by the
+    /// device.
+    pub fn extensions(&self) -> OclResult<String> {
+        DeviceInfoSource::extensions(self)
+    }
+
+    /// Returns true if the extension named `extension` (e.g. `cl_khr_fp16`)
+    /// is supported by the device.
+    pub fn has_extension(&self, extension: &str) -> OclResult<bool> {
+        DeviceInfoSource::has_extension(self, extension)
+    }
+
+    /// Times `workload` on each of `candidates` and returns the fastest
+    /// along with a report of every candidate's timings.
+    ///
+    /// Each candidate gets its own context and profiling queue. The
+    /// workload's kernel is built and enqueued a few times to warm up, then
+    /// timed (by its profiling counters) over several more runs. Candidates
+    /// on which any of this fails are excluded (their errors are kept in the
+    /// report) rather than aborting the benchmark.
+    ///
+    /// "Fastest" depends on the work: an integrated GPU sharing host memory
+    /// may well beat a discrete one on bandwidth-bound work, for example, so
+    /// choose the workload most like the real one.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `candidates` is empty or no candidate completed
+    /// the benchmark (the error includes the report).
+    pub fn benchmark_select(workload: BenchWorkload, candidates: &[Device])
+            -> OclResult<(Device, BenchReport)>
+    {
+        if candidates.is_empty() {
+            return OclError::err("ocl::Device::benchmark_select: No candidate devices \
+                specified.");
+        }
+
+        let report = BenchReport::run(workload, candidates);
+
+        match report.winner() {
+            Some(device) => Ok((device, report)),
+            None => OclError::err(format!("ocl::Device::benchmark_select: No candidate device \
+                completed the benchmark:\n{}", report)),
+        }
+    }
+
+    /// Returns a `(device_timestamp, host_timestamp)` pair, in nanoseconds,
+    /// sampled at (nearly) the same moment.
+    ///
+    /// The device timestamp is on the clock used for event profiling, making
+    /// this useful to place profiled commands on a host timeline (see
+    /// `TimelineTranslator`).
+    ///
+    /// Returns a `VersionUnsupported` error unless both the device and its
+    /// platform support OpenCL 2.1 or later.
+    pub fn device_and_host_timer(&self) -> OclResult<(u64, u64)> {
+        try!(self.require_version("ocl::Device::device_and_host_timer", (2, 1)));
+        core::get_device_and_host_timer(&self.0)
+    }
+
+    /// Returns the current value of the host clock, in nanoseconds, as used
+    /// by `::device_and_host_timer`.
+    ///
+    /// Returns a `VersionUnsupported` error unless both the device and its
+    /// platform support OpenCL 2.1 or later.
+    pub fn host_timer(&self) -> OclResult<u64> {
+        try!(self.require_version("ocl::Device::host_timer", (2, 1)));
+        core::get_host_timer(&self.0)
+    }
+
+    /// Returns a `VersionUnsupported` error for `op` if either the device or
+    /// its platform supports an OpenCL version older than `required`.
+    fn require_version(&self, op: &'static str, required: (u32, u32)) -> OclResult<()> {
+        let device_version = try!(self.version_number());
+        let platform_version = match self.info(DeviceInfo::Platform) {
+            DeviceInfoResult::Platform(platform) => try!(Platform::new(platform).version_number()),
+            DeviceInfoResult::Error(err) => return Err(*err),
+            res => return OclError::err(format!("{}: Unexpected device info result: {:?}", op,
+                res)),
+        };
+
+        let found = std::cmp::min(device_version, platform_version);
+        if found < required {
+            OclError::err_version_unsupported(op, required, found)
+        } else {
+            Ok(())
         }
     }
 
@@ -346,7 +528,8 @@ impl Device {
         core::get_device_info(&self.0, info_kind)
     }
 
-    /// Returns a string containing a formatted list of device properties.
+    /// Returns a one line summary of the device (see the `Display`
+    /// implementation).
     pub fn to_string(&self) -> String {
         self.clone().into()
     }
@@ -452,9 +635,19 @@ impl Into<DeviceIdCore> for Device {
     }
 }
 
+/// Formats a one line summary of the device, e.g.: `GeForce GTX 1080 (NVIDIA
+/// Corporation, OpenCL 1.2, 20 CUs, 7.9 GiB)`, or, if its name can not be
+/// queried, its raw handle in brackets.
+///
+/// Use the alternate flag (`{:#}`) to list every property instead.
 impl std::fmt::Display for Device {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        self.fmt_info(f)
+        if f.alternate() { return self.fmt_info(f); }
+
+        match DeviceInfoSource::summary(self) {
+            Ok(summary) => f.write_str(&summary),
+            Err(_) => write!(f, "[{:?}]", self.0),
+        }
     }
 }
 
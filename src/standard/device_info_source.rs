@@ -0,0 +1,188 @@
+//! The boundary between device-dependent logic and `clGetDeviceInfo`.
+
+use core::{self, DeviceInfo, DeviceInfoResult, DeviceExecCapabilities};
+use error::{Error as OclError, Result as OclResult};
+use standard::Device;
+use util;
+
+/// A source of device information.
+///
+/// Logic which depends only upon what a device reports (its limits, version,
+/// and extensions) is written against this trait rather than calling
+/// `clGetDeviceInfo` directly, so that it can be tested deterministically
+/// with canned snapshots (see `tests::fixtures`) as well as with whatever
+/// hardware is present. `Device` is the only implementation outside of
+/// tests.
+pub(crate) trait DeviceInfoSource {
+    /// Returns the raw bytes of the value of `request`.
+    fn info_bytes(&self, request: DeviceInfo) -> OclResult<Vec<u8>>;
+
+    /// Returns the value of `request`.
+    fn info(&self, request: DeviceInfo) -> DeviceInfoResult {
+        DeviceInfoResult::from_bytes(request, self.info_bytes(request))
+    }
+
+    /// Returns the maximum workgroup size.
+    fn max_wg_size(&self) -> OclResult<usize> {
+        match self.info(DeviceInfo::MaxWorkGroupSize) {
+            DeviceInfoResult::MaxWorkGroupSize(s) => Ok(s),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Device::max_wg_size: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns the maximum number of work item dimensions.
+    fn max_wi_dims(&self) -> OclResult<u32> {
+        match self.info(DeviceInfo::MaxWorkItemDimensions) {
+            DeviceInfoResult::MaxWorkItemDimensions(d) => Ok(d),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Device::max_wi_dims: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns the maximum number of work items for each dimension of a
+    /// work group.
+    fn max_wi_sizes(&self) -> OclResult<Vec<usize>> {
+        match self.info(DeviceInfo::MaxWorkItemSizes) {
+            DeviceInfoResult::MaxWorkItemSizes(s) => Ok(s),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Device::max_wi_sizes: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns true if the device is little endian.
+    fn endian_little(&self) -> OclResult<bool> {
+        match self.info(DeviceInfo::EndianLittle) {
+            DeviceInfoResult::EndianLittle(l) => Ok(l),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Device::endian_little: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns true if the device and the host share a unified memory
+    /// subsystem.
+    fn host_unified_memory(&self) -> OclResult<bool> {
+        match self.info(DeviceInfo::HostUnifiedMemory) {
+            DeviceInfoResult::HostUnifiedMemory(unified) => Ok(unified),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Device::host_unified_memory: Unexpected 'DeviceInfoResult' \
+                variant."),
+        }
+    }
+
+    /// Returns the number of parallel compute units.
+    fn max_compute_units(&self) -> OclResult<u32> {
+        unsafe { util::bytes_into::<u32>(try!(self.info_bytes(DeviceInfo::MaxComputeUnits))) }
+    }
+
+    /// Returns the size of global memory in bytes.
+    fn global_mem_size(&self) -> OclResult<u64> {
+        unsafe { util::bytes_into::<u64>(try!(self.info_bytes(DeviceInfo::GlobalMemSize))) }
+    }
+
+    /// Returns the execution capabilities of the device.
+    fn execution_capabilities(&self) -> OclResult<DeviceExecCapabilities> {
+        match self.info(DeviceInfo::ExecutionCapabilities) {
+            DeviceInfoResult::ExecutionCapabilities(c) => Ok(c),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Device::execution_capabilities: Unexpected \
+                'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns the OpenCL version string of the device.
+    fn version(&self) -> OclResult<String> {
+        match self.info(DeviceInfo::Version) {
+            DeviceInfoResult::Version(v) => Ok(v),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Device::version: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns the OpenCL version of the device as a `(major, minor)` pair.
+    fn version_number(&self) -> OclResult<(u32, u32)> {
+        let version = try!(self.version());
+
+        super::parse_cl_version(&version)
+            .ok_or(OclError::new(format!("ocl::Device::version_number: Unable to parse the \
+                device version string: '{}'.", version)))
+    }
+
+    /// Returns the space-separated list of extensions supported by the
+    /// device.
+    fn extensions(&self) -> OclResult<String> {
+        match self.info(DeviceInfo::Extensions) {
+            DeviceInfoResult::Extensions(e) => Ok(e),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Device::extensions: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns true if the extension named `extension` is supported by the
+    /// device.
+    fn has_extension(&self, extension: &str) -> OclResult<bool> {
+        self.extensions().map(|e| e.split_whitespace().any(|ext| ext == extension))
+    }
+
+    /// Returns a one line summary of the device, e.g.: `GeForce GTX 1080
+    /// (NVIDIA Corporation, OpenCL 1.2, 20 CUs, 7.9 GiB)`.
+    ///
+    /// Only the name is required, anything else which can not be queried is
+    /// left out.
+    fn summary(&self) -> OclResult<String> {
+        let name = match self.info(DeviceInfo::Name) {
+            DeviceInfoResult::Name(name) => name,
+            DeviceInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::Device::summary: Unexpected 'DeviceInfoResult' variant."),
+        };
+
+        let mut details = Vec::with_capacity(4);
+
+        match self.info_bytes(DeviceInfo::Vendor).map(util::string_from_info_bytes) {
+            Ok(ref vendor) if !vendor.is_empty() => details.push(vendor.clone()),
+            _ => (),
+        }
+        match self.version_number() {
+            Ok((major, minor)) => details.push(format!("OpenCL {}.{}", major, minor)),
+            Err(_) => if let Ok(version) = self.version() { details.push(version) },
+        }
+        if let Ok(units) = self.max_compute_units() {
+            details.push(format!("{} CUs", units));
+        }
+        if let Ok(size) = self.global_mem_size() {
+            details.push(fmt_mem_size(size));
+        }
+
+        if details.is_empty() {
+            Ok(name)
+        } else {
+            Ok(format!("{} ({})", name, details.join(", ")))
+        }
+    }
+}
+
+/// Formats `bytes` in the largest binary unit of which there is at least
+/// one, to one decimal place (dropped if zero), e.g.: `7.9 GiB`, `16 KiB`.
+pub(crate) fn fmt_mem_size(bytes: u64) -> String {
+    const UNITS: [&'static str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    let size = format!("{:.1}", size);
+    format!("{} {}", size.trim_end_matches(".0"), UNITS[unit])
+}
+
+impl DeviceInfoSource for Device {
+    fn info_bytes(&self, request: DeviceInfo) -> OclResult<Vec<u8>> {
+        core::get_device_info_bytes(self, request)
+    }
+
+    fn info(&self, request: DeviceInfo) -> DeviceInfoResult {
+        core::get_device_info(self, request)
+    }
+}
@@ -0,0 +1,148 @@
+//! Cached device limits used for validation.
+
+use core::{DeviceInfo, MemObjectType, ImageDescriptor};
+use error::{Error as OclError, Result as OclResult};
+use standard::DeviceInfoSource;
+use util;
+
+/// Limits of a device which are needed to validate the creation and use of
+/// buffers, images, and kernels.
+///
+/// Queried once per device when a `Context` is created and shared (behind an
+/// `Arc`) with every `Queue`, `Buffer`, and `Image` using that device, so
+/// that validation never requires further calls to `clGetDeviceInfo`.
+///
+/// Obtain with `Context::device_limits` or `Queue::device_limits`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeviceLimits {
+    mem_base_addr_align: u32,
+    max_mem_alloc_size: u64,
+    max_wg_size: usize,
+    image_support: bool,
+    image2d_max_width: usize,
+    image2d_max_height: usize,
+    image3d_max_width: usize,
+    image3d_max_height: usize,
+    image3d_max_depth: usize,
+    host_unified_memory: bool,
+}
+
+impl DeviceLimits {
+    /// Queries each limit of `device` (a `Device` or, in tests, a fixture).
+    ///
+    /// The host unified memory flag (deprecated as of OpenCL 2.0) is treated
+    /// as false if the device does not report it.
+    pub(crate) fn new<S: DeviceInfoSource + ?Sized>(device: &S) -> OclResult<DeviceLimits> {
+        let max_wg_size = try!(device.max_wg_size());
+        let host_unified_memory = device.host_unified_memory().unwrap_or(false);
+
+        Ok(DeviceLimits {
+            mem_base_addr_align: try!(query(device, DeviceInfo::MemBaseAddrAlign)),
+            max_mem_alloc_size: try!(query(device, DeviceInfo::MaxMemAllocSize)),
+            max_wg_size: max_wg_size,
+            image_support: try!(query::<u32, _>(device, DeviceInfo::ImageSupport)) != 0,
+            image2d_max_width: try!(query(device, DeviceInfo::Image2dMaxWidth)),
+            image2d_max_height: try!(query(device, DeviceInfo::Image2dMaxHeight)),
+            image3d_max_width: try!(query(device, DeviceInfo::Image3dMaxWidth)),
+            image3d_max_height: try!(query(device, DeviceInfo::Image3dMaxHeight)),
+            image3d_max_depth: try!(query(device, DeviceInfo::Image3dMaxDepth)),
+            host_unified_memory: host_unified_memory,
+        })
+    }
+
+    /// Returns the alignment, in bits, of the base address of any memory
+    /// object (`DeviceInfo::MemBaseAddrAlign`).
+    pub fn mem_base_addr_align(&self) -> u32 {
+        self.mem_base_addr_align
+    }
+
+    /// Returns the alignment, in bytes, required of sub-buffer origins.
+    pub fn mem_base_addr_align_bytes(&self) -> usize {
+        self.mem_base_addr_align as usize / 8
+    }
+
+    /// Returns the maximum size, in bytes, of a single memory object
+    /// allocation (`DeviceInfo::MaxMemAllocSize`).
+    pub fn max_mem_alloc_size(&self) -> u64 {
+        self.max_mem_alloc_size
+    }
+
+    /// Returns the maximum work group size (`DeviceInfo::MaxWorkGroupSize`).
+    pub fn max_wg_size(&self) -> usize {
+        self.max_wg_size
+    }
+
+    /// Returns true if the device supports images.
+    pub fn image_support(&self) -> bool {
+        self.image_support
+    }
+
+    /// Returns the maximum `[width, height]` of a 2D image.
+    pub fn image2d_max_dims(&self) -> [usize; 2] {
+        [self.image2d_max_width, self.image2d_max_height]
+    }
+
+    /// Returns the maximum `[width, height, depth]` of a 3D image.
+    pub fn image3d_max_dims(&self) -> [usize; 3] {
+        [self.image3d_max_width, self.image3d_max_height, self.image3d_max_depth]
+    }
+
+    /// Returns true if the device and the host share a unified memory
+    /// subsystem.
+    pub fn host_unified_memory(&self) -> bool {
+        self.host_unified_memory
+    }
+
+    /// Returns an error if an allocation of `size` bytes exceeds the maximum
+    /// allocation size.
+    pub(crate) fn check_alloc_size(&self, fn_name: &'static str, size: usize) -> OclResult<()> {
+        if size as u64 > self.max_mem_alloc_size {
+            return OclError::err(format!("{}: The requested size ({} bytes) exceeds the maximum \
+                allocation size of the device ({} bytes).", fn_name, size,
+                self.max_mem_alloc_size));
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the device does not support images or if the
+    /// dimensions in `image_desc` exceed the maximums for its image type.
+    ///
+    /// Image buffers (limited by `DeviceInfo::ImageMaxBufferSize`) are not
+    /// checked.
+    pub(crate) fn check_image_desc(&self, fn_name: &'static str, image_desc: &ImageDescriptor)
+            -> OclResult<()>
+    {
+        if !self.image_support {
+            return OclError::err(format!("{}: The device does not support images.", fn_name));
+        }
+
+        let (dims, max_dims): (&[usize], &[usize]) = match image_desc.image_type {
+            MemObjectType::Image1d | MemObjectType::Image1dArray => {
+                (&[image_desc.image_width], &[self.image2d_max_width])
+            },
+            MemObjectType::Image2d | MemObjectType::Image2dArray => {
+                (&[image_desc.image_width, image_desc.image_height],
+                    &[self.image2d_max_width, self.image2d_max_height])
+            },
+            MemObjectType::Image3d => {
+                (&[image_desc.image_width, image_desc.image_height, image_desc.image_depth],
+                    &[self.image3d_max_width, self.image3d_max_height, self.image3d_max_depth])
+            },
+            _ => return Ok(()),
+        };
+
+        if dims.iter().zip(max_dims.iter()).any(|(&d, &max)| d > max) {
+            return OclError::err(format!("{}: The image dimensions ({:?}) exceed the maximum \
+                dimensions of the device for an image of type '{:?}' ({:?}).", fn_name, dims,
+                image_desc.image_type, max_dims));
+        }
+        Ok(())
+    }
+}
+
+/// Queries a scalar device info value which has no dedicated
+/// `DeviceInfoResult` variant.
+fn query<T, S: DeviceInfoSource + ?Sized>(device: &S, request: DeviceInfo) -> OclResult<T> {
+    unsafe { util::bytes_into::<T>(try!(device.info_bytes(request))) }
+}
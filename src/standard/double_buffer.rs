@@ -0,0 +1,235 @@
+//! A pair of device buffers used to overlap computation with transfers.
+
+use std;
+use core::{self, OclPrm};
+use error::{Error as OclError, Result as OclResult};
+use standard::{Queue, Buffer, Event};
+
+/// A pair of device buffers used to process a stream of host data in chunks,
+/// overlapping the computation on each chunk with the transfers of its
+/// neighbors.
+///
+/// While the kernel (or other work) for chunk N runs on the compute queue,
+/// chunk N + 1 is written and the result of chunk N - 1 is read back on the
+/// transfer queue, the buffers alternating between chunks ("ping-pong").
+/// Commands are chained with events so the host only blocks to hand each
+/// result to the output sink, waiting on chunk N - 2 while chunk N - 1 is
+/// being processed, and at the end of the stream.
+///
+/// Using the same queue for both transfers and computation is valid but
+/// prevents any overlap unless it is out-of-order.
+///
+/// Elements are never byte swapped (see `Buffer::swap_bytes`).
+///
+/// ## Examples
+///
+/// ```text
+/// let mut dbuf = DoubleBuffer::<f32>::new(&transfer_queue, &compute_queue, 1 << 16).unwrap();
+///
+/// dbuf.run(data.chunks(1 << 16), |buffer, len, wait, new| {
+///     kernel.set_arg_buf_named("buf", Some(buffer)).unwrap();
+///     kernel.cmd().queue(&compute_queue).gws(len).ewait(wait).enew(new).enq()
+/// }, |result| output.extend_from_slice(result)).unwrap();
+/// ```
+pub struct DoubleBuffer<T: OclPrm> {
+    transfer_queue: Queue,
+    compute_queue: Queue,
+    chunk_len: usize,
+    buffers: [Buffer<T>; 2],
+    outputs: [Vec<T>; 2],
+}
+
+impl<T: OclPrm> DoubleBuffer<T> {
+    /// Returns a new pair of buffers, each `chunk_len` elements long, which
+    /// will be written to and read from using `transfer_queue` and
+    /// processed using `compute_queue`.
+    ///
+    /// The buffers use `compute_queue` as their default queue.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `chunk_len` is zero.
+    pub fn new(transfer_queue: &Queue, compute_queue: &Queue, chunk_len: usize)
+            -> OclResult<DoubleBuffer<T>>
+    {
+        if chunk_len == 0 {
+            return OclError::err("ocl::DoubleBuffer::new: The chunk length must be non-zero.");
+        }
+
+        let buffers = [
+            try!(Buffer::<T>::new(compute_queue, Some(core::MEM_READ_WRITE), [chunk_len], None)),
+            try!(Buffer::<T>::new(compute_queue, Some(core::MEM_READ_WRITE), [chunk_len], None)),
+        ];
+
+        Ok(DoubleBuffer {
+            transfer_queue: transfer_queue.clone(),
+            compute_queue: compute_queue.clone(),
+            chunk_len: chunk_len,
+            buffers: buffers,
+            outputs: [vec![T::default(); chunk_len], vec![T::default(); chunk_len]],
+        })
+    }
+
+    /// Processes each chunk of `input` in turn, passing each result to
+    /// `sink` in order.
+    ///
+    /// For each chunk, `enqueue` is called with the buffer containing it
+    /// (only the first `len` elements of which are valid), `len`, an event
+    /// to wait on before accessing the buffer, and an empty event which must
+    /// be associated with the last command using the buffer (such as with
+    /// `KernelCmd::ewait` and `KernelCmd::enew`). The contents of the buffer
+    /// once that command completes are the result passed to `sink`.
+    ///
+    /// Chunks may be up to `chunk_len` elements long. A final partial chunk
+    /// (or any other short or empty chunk) produces a result of the same
+    /// length. An empty `input` calls neither `enqueue` nor `sink`.
+    ///
+    /// Blocks until every result has been passed to `sink`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if a chunk is longer than `chunk_len`, if `enqueue`
+    /// returns an error or does not associate its event with a command, or
+    /// if a transfer fails. Both queues are finished before returning an
+    /// error.
+    pub fn run<I, F, S>(&mut self, input: I, mut enqueue: F, mut sink: S) -> OclResult<()>
+            where I: IntoIterator, I::Item: AsRef<[T]>,
+                F: FnMut(&Buffer<T>, usize, &Event, &mut Event) -> OclResult<()>,
+                S: FnMut(&[T])
+    {
+        // Each input chunk is held until the read of its result completes
+        // (implying that its write has completed):
+        let mut inputs: [Option<I::Item>; 2] = [None, None];
+        let result = self.run_chunks(input, &mut inputs, &mut enqueue, &mut sink);
+
+        if result.is_err() {
+            // Ensure that no transfer still refers to `inputs` or `outputs`:
+            core::finish(&self.transfer_queue).ok();
+            core::finish(&self.compute_queue).ok();
+        }
+
+        result
+    }
+
+    /// Returns the length of each buffer (the maximum chunk length).
+    pub fn chunk_len(&self) -> usize {
+        self.chunk_len
+    }
+
+    /// Returns the queue used for transfers.
+    pub fn transfer_queue(&self) -> &Queue {
+        &self.transfer_queue
+    }
+
+    /// Returns the queue intended for computation (the buffers' default
+    /// queue).
+    pub fn compute_queue(&self) -> &Queue {
+        &self.compute_queue
+    }
+
+    /// Processes `input`, storing each chunk in `inputs` until its result
+    /// has been passed to `sink`.
+    fn run_chunks<I, F, S>(&mut self, input: I, inputs: &mut [Option<I::Item>; 2],
+            enqueue: &mut F, sink: &mut S) -> OclResult<()>
+            where I: IntoIterator, I::Item: AsRef<[T]>,
+                F: FnMut(&Buffer<T>, usize, &Event, &mut Event) -> OclResult<()>,
+                S: FnMut(&[T])
+    {
+        let mut lens = [0usize; 2];
+        let mut compute_events = [Event::empty(), Event::empty()];
+        let mut read_events = [Event::empty(), Event::empty()];
+        let mut count = 0;
+
+        for (n, chunk) in input.into_iter().enumerate() {
+            let (k, prev) = (n % 2, (n + 1) % 2);
+
+            // Hand over the result of chunk N - 2, freeing its buffer:
+            if n >= 2 {
+                try!(self.finish_chunk(k, lens[k], &mut inputs[k], &mut read_events[k], sink));
+            }
+
+            let len = chunk.as_ref().len();
+
+            if len > self.chunk_len {
+                return OclError::err(format!("ocl::DoubleBuffer::run: The length of chunk [{}] \
+                    ({}) exceeds the chunk length ({}).", n, len, self.chunk_len));
+            }
+
+            lens[k] = len;
+            inputs[k] = Some(chunk);
+            compute_events[k] = Event::empty();
+
+            if len > 0 {
+                let mut write_event = Event::empty();
+
+                try!(core::enqueue_write_buffer(&self.transfer_queue, &self.buffers[k], false, 0,
+                    inputs[k].as_ref().unwrap().as_ref(), None, Some(&mut write_event)));
+
+                try!(enqueue(&self.buffers[k], len, &write_event, &mut compute_events[k]));
+
+                if compute_events[k].is_empty() {
+                    return OclError::err(format!("ocl::DoubleBuffer::run: The event for chunk \
+                        [{}] was not associated with a command.", n));
+                }
+            }
+
+            // Read the result of chunk N - 1 (enqueued after the write of
+            // chunk N so that the write is not held up by its computation):
+            if n >= 1 {
+                try!(self.enqueue_read(prev, lens[prev], &compute_events[prev],
+                    &mut read_events[prev]));
+            }
+
+            count = n + 1;
+        }
+
+        if count == 0 { return Ok(()); }
+
+        let last = (count - 1) % 2;
+        try!(self.enqueue_read(last, lens[last], &compute_events[last], &mut read_events[last]));
+
+        if count >= 2 {
+            let k = count % 2;
+            try!(self.finish_chunk(k, lens[k], &mut inputs[k], &mut read_events[k], sink));
+        }
+
+        self.finish_chunk(last, lens[last], &mut inputs[last], &mut read_events[last], sink)
+    }
+
+    /// Enqueues a read of the first `len` elements of buffer `k` into its
+    /// output, waiting on `compute_event`.
+    fn enqueue_read(&mut self, k: usize, len: usize, compute_event: &Event,
+            read_event: &mut Event) -> OclResult<()>
+    {
+        if len == 0 { return Ok(()); }
+
+        unsafe {
+            core::enqueue_read_buffer(&self.transfer_queue, &self.buffers[k], false, 0,
+                &mut self.outputs[k][..len], Some(compute_event), Some(read_event))
+        }
+    }
+
+    /// Waits for the read of buffer `k` to complete, passes the result to
+    /// `sink`, and releases the input chunk.
+    fn finish_chunk<C, S>(&self, k: usize, len: usize, input: &mut Option<C>,
+            read_event: &mut Event, sink: &mut S) -> OclResult<()>
+            where S: FnMut(&[T])
+    {
+        if !read_event.is_empty() {
+            try!(read_event.wait());
+        }
+
+        sink(&self.outputs[k][..len]);
+        *input = None;
+        *read_event = Event::empty();
+        Ok(())
+    }
+}
+
+impl<T: OclPrm> std::fmt::Debug for DoubleBuffer<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("DoubleBuffer")
+            .field("chunk_len", &self.chunk_len)
+            .finish()
+    }
+}
@@ -1,13 +1,42 @@
 //! An OpenCL event.
 
 use std;
+use std::cmp;
+use std::sync::{Arc, Mutex, Condvar, PoisonError};
+use std::time::{Duration, Instant};
 use std::ops::{Deref, DerefMut};
 use std::convert::Into;
 use libc::c_void;
 use cl_h;
 use error::{Error as OclError, Result as OclResult};
 use core::{self, Event as EventCore, EventInfo, EventInfoResult, ProfilingInfo, ProfilingInfoResult,
-    ClEventPtrNew, ClWaitList, EventList as EventListCore, CommandExecutionStatus, EventCallbackFn};
+    ClEventPtrNew, ClWaitList, EventList as EventListCore, CommandExecutionStatus, CommandType,
+    EventCallbackFn};
+
+/// The interval at which the status of an event is polled while waiting
+/// with a timeout, if a completion callback could not be registered.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The interval at which the status of an event is polled while waiting
+/// with a timeout, in case its completion callback is never called.
+const CALLBACK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The outcome of waiting for an event (or events) with a timeout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitOutcome {
+    /// The command(s) completed.
+    Complete,
+    /// The timeout elapsed before the command(s) completed. The command(s)
+    /// may still complete (or may never do so).
+    TimedOut,
+}
+
+impl WaitOutcome {
+    /// Returns true if the command(s) completed.
+    pub fn is_complete(&self) -> bool {
+        *self == WaitOutcome::Complete
+    }
+}
 
 /// An event representing a command or user created event.
 #[derive(Clone, Debug)]
@@ -29,13 +58,166 @@ impl Event {
         Event(Some(event_core))
     }
 
+    /// Creates a new `Event` from a raw `cl_event`, incrementing its
+    /// reference count. The caller keeps its own reference and remains
+    /// responsible for releasing it.
+    ///
+    /// Returns an error if `ptr` is null.
+    pub unsafe fn from_raw_retained(ptr: cl_h::cl_event) -> OclResult<Event> {
+        EventCore::from_raw_retained(ptr).map(|core| Event(Some(core)))
+    }
+
+    /// Creates a new `Event` from a raw `cl_event`, taking over the caller's
+    /// reference, which is released when the event is dropped. The caller
+    /// must not release it.
+    ///
+    /// Returns an error if `ptr` is null.
+    pub unsafe fn from_raw_unretained(ptr: cl_h::cl_event) -> OclResult<Event> {
+        EventCore::from_raw_unretained(ptr).map(|core| Event(Some(core)))
+    }
+
+    /// Returns the raw `cl_event` without incrementing its reference count,
+    /// or a null pointer if this event is empty. Do not store it beyond the
+    /// life of this event unless first retained.
+    pub unsafe fn as_ptr(&self) -> cl_h::cl_event {
+        match self.0 {
+            Some(ref core) => core.as_ptr(),
+            None => std::ptr::null_mut(),
+        }
+    }
+
     /// Waits for all events in list to complete before returning.
     ///
     /// Similar in function to `Queue::finish()`.
     ///
     pub fn wait(&self) -> OclResult<()> {
         assert!(!self.is_empty(), "ocl::Event::wait(): {}", self.err_empty());
-        core::wait_for_event(self.0.as_ref().unwrap())
+        core::wait_for_events(std::slice::from_ref(self.0.as_ref().unwrap()))
+    }
+
+    /// Waits for the command associated with the event to complete for at
+    /// most `timeout`, returning whether or not it did.
+    ///
+    /// The command queue of the event (if any) is flushed first. A completion
+    /// callback is then registered to wake the waiting thread, with the
+    /// status of the event polled periodically as well in case the callback
+    /// is unsupported or never called.
+    ///
+    /// OpenCL provides no way to cancel a command once enqueued, so the
+    /// timeout is purely observational: a command which never completes
+    /// (such as a kernel stuck in an infinite loop) continues to occupy its
+    /// device and queue, and the context may need to be abandoned.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the command terminated abnormally or its status
+    /// could not be queried.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if this event is empty.
+    pub fn wait_timeout(&self, timeout: Duration) -> OclResult<WaitOutcome> {
+        assert!(!self.is_empty(), "ocl::Event::wait_timeout(): {}", self.err_empty());
+        self.wait_until(Instant::now() + timeout)
+    }
+
+    /// Waits for the command associated with the event to complete until
+    /// `deadline` (see `::wait_timeout`).
+    fn wait_until(&self, deadline: Instant) -> OclResult<WaitOutcome> {
+        let core = self.0.as_ref().unwrap();
+
+        // Commands which have not been submitted to their device would
+        // otherwise never complete:
+        match self.info(EventInfo::CommandQueue) {
+            EventInfoResult::CommandQueue(Some(queue)) => try!(core::flush(&queue)),
+            EventInfoResult::CommandQueue(None) => (),
+            EventInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::Event::wait_timeout: Unexpected 'EventInfoResult' \
+                variant."),
+        }
+
+        let signal = Arc::new((Mutex::new(None), Condvar::new()));
+        let callback_signal = signal.clone();
+
+        let has_callback = core::set_event_callback_fn(core, CommandExecutionStatus::Complete,
+            move |status| {
+                let &(ref result, ref cvar) = &*callback_signal;
+                *result.lock().unwrap_or_else(PoisonError::into_inner) = Some(status.map(|_| ()));
+                cvar.notify_all();
+            }).is_ok();
+
+        let poll_interval = if has_callback { CALLBACK_POLL_INTERVAL } else { POLL_INTERVAL };
+        let &(ref result, ref cvar) = &*signal;
+
+        loop {
+            if try!(self.status()) == CommandExecutionStatus::Complete {
+                return Ok(WaitOutcome::Complete);
+            }
+
+            let now = Instant::now();
+            if now >= deadline { return Ok(WaitOutcome::TimedOut); }
+
+            let guard = result.lock().unwrap_or_else(PoisonError::into_inner);
+            let mut guard = match *guard {
+                Some(_) => guard,
+                None => cvar.wait_timeout(guard, cmp::min(deadline - now, poll_interval))
+                    .unwrap_or_else(PoisonError::into_inner).0,
+            };
+
+            if let Some(status) = guard.take() {
+                return status.map(|_| WaitOutcome::Complete);
+            }
+        }
+    }
+
+    /// Keeps `payload` alive until the command associated with the event
+    /// completes (or terminates abnormally), dropping it afterwards on a
+    /// thread belonging to the OpenCL implementation.
+    ///
+    /// Used to hold memory objects and host data which must outlive an
+    /// in-flight command regardless of whether or not this event is kept.
+    ///
+    /// ## Errors
+    ///
+    /// If the callback can not be registered, the command is waited on
+    /// before `payload` is dropped and the error returned.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if this event is empty.
+    pub fn retain_until_complete<P: Send + 'static>(&self, payload: P) -> OclResult<()> {
+        assert!(!self.is_empty(), "ocl::Event::retain_until_complete(): {}", self.err_empty());
+        let payload = Arc::new(Mutex::new(Some(payload)));
+        let callback_payload = payload.clone();
+
+        let result = self.on_complete(move |_| {
+            if let Ok(mut payload) = callback_payload.lock() { payload.take(); }
+        });
+
+        if result.is_err() {
+            core::wait_for_events(std::slice::from_ref(self.0.as_ref().unwrap())).ok();
+            drop(payload);
+        }
+        result
+    }
+
+    /// Registers `callback` to be called once the command associated with
+    /// the event completes, receiving an error if it terminated abnormally.
+    ///
+    /// `callback` is run on a thread belonging to the OpenCL implementation
+    /// (possibly before this method returns) and must not block on other
+    /// commands. Panics within it are caught and reported rather than
+    /// propagated.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if this event is empty.
+    pub fn on_complete<F>(&self, callback: F) -> OclResult<()>
+            where F: FnOnce(OclResult<()>) + Send + 'static
+    {
+        assert!(!self.is_empty(), "ocl::Event::on_complete(): {}", self.err_empty());
+        core::set_event_callback_fn(self.0.as_ref().unwrap(), CommandExecutionStatus::Complete,
+            move |status| callback(status.map(|_| ())))
     }
 
     /// Returns info about the event. 
@@ -52,6 +234,27 @@ impl Event {
         }
     }
 
+    /// Returns the execution status of the command associated with the
+    /// event.
+    ///
+    /// Returns an error if the command terminated abnormally.
+    pub fn status(&self) -> OclResult<CommandExecutionStatus> {
+        match self.info(EventInfo::CommandExecutionStatus) {
+            EventInfoResult::CommandExecutionStatus(status) => Ok(status),
+            EventInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Event::status: Unexpected 'EventInfoResult' variant."),
+        }
+    }
+
+    /// Returns the type of the command associated with the event.
+    pub fn command_type(&self) -> OclResult<CommandType> {
+        match self.info(EventInfo::CommandType) {
+            EventInfoResult::CommandType(ty) => Ok(ty),
+            EventInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Event::command_type: Unexpected 'EventInfoResult' variant."),
+        }
+    }
+
     /// Returns info about the event. 
     pub fn profiling_info(&self, info_kind: ProfilingInfo) -> ProfilingInfoResult {
         match self.0 {
@@ -175,6 +378,10 @@ unsafe impl ClWaitList for Event {
 /// For access to individual events use `get_clone` and `last_clone` then
 /// either store or discard the result.
 ///
+/// For interop, `as_ptr` and `len` (through `Deref`) expose the list's
+/// contiguous `cl_event`s without copying them. See also
+/// `::from_raw_retained` and `::from_raw_unretained`.
+///
 // [FIXME] TODO: impl Index.
 #[derive(Debug, Clone)]
 pub struct EventList {
@@ -189,6 +396,24 @@ impl EventList {
         }
     }
 
+    /// Creates a new list from raw `cl_event`s, incrementing the reference
+    /// count of each. The caller keeps its own references and remains
+    /// responsible for releasing them.
+    ///
+    /// Returns an error if any is null.
+    pub unsafe fn from_raw_retained(ptrs: &[cl_h::cl_event]) -> OclResult<EventList> {
+        EventListCore::from_raw_retained(ptrs).map(|core| EventList { event_list_core: core })
+    }
+
+    /// Creates a new list from raw `cl_event`s, taking over the caller's
+    /// reference to each, which is released when the list is dropped. The
+    /// caller must not release them.
+    ///
+    /// Returns an error if any is null.
+    pub unsafe fn from_raw_unretained(ptrs: &[cl_h::cl_event]) -> OclResult<EventList> {
+        EventListCore::from_raw_unretained(ptrs).map(|core| EventList { event_list_core: core })
+    }
+
     // pub fn push(&mut self, event: Event) {
     //     self.events.push(event);
     // }
@@ -270,11 +495,33 @@ impl EventList {
 
     /// Waits for all events in list to complete.
     pub fn wait(&self) -> OclResult<()> {
-        if self.event_list_core.len() > 0 {
-            core::wait_for_events(self.event_list_core.count(), &self.event_list_core)
-        } else {
-            Ok(())
+        core::wait_for_event_list(&self.event_list_core)
+    }
+
+    /// Waits for all events in the list to complete for at most `timeout`
+    /// in total, returning whether or not they did.
+    ///
+    /// As with `Event::wait_timeout`, the timeout is purely observational:
+    /// commands still running when it elapses are not cancelled.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if any command terminated abnormally or the status
+    /// of any event could not be queried.
+    pub fn wait_all_timeout(&self, timeout: Duration) -> OclResult<WaitOutcome> {
+        let deadline = Instant::now() + timeout;
+
+        for idx in 0..self.event_list_core.len() {
+            let event = match self.event_list_core.get_clone(idx) {
+                Some(event) => unsafe { Event::from_core(try!(event)) },
+                None => break,
+            };
+
+            if try!(event.wait_until(deadline)) == WaitOutcome::TimedOut {
+                return Ok(WaitOutcome::TimedOut);
+            }
         }
+        Ok(WaitOutcome::Complete)
     }
 }
 
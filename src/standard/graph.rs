@@ -0,0 +1,313 @@
+//! Commands enqueued in dependency order with generated wait lists.
+
+use std;
+use std::collections::HashMap;
+use core::{ClWaitList, Event as EventCore};
+use error::{Error as OclError, Result as OclResult};
+use standard::{Queue, Event};
+
+/// The function called to enqueue the command of a node.
+type EnqueueFn<'a> = Box<FnMut(&Queue, &ClWaitList, &mut Event) -> OclResult<()> + 'a>;
+
+/// A node: a named command and the names of the nodes it depends upon.
+struct Node<'a> {
+    name: String,
+    deps: Vec<String>,
+    enqueue: EnqueueFn<'a>,
+}
+
+/// The order in which nodes are enqueued and the indexes of the
+/// dependencies of each.
+struct Plan {
+    order: Vec<usize>,
+    deps: Vec<Vec<usize>>,
+}
+
+/// The events of the commands enqueued by a graph (see `Graph::enqueue`).
+#[derive(Debug)]
+pub struct GraphEvents {
+    indexes: HashMap<String, usize>,
+    events: Vec<Event>,
+    completion: Event,
+}
+
+impl GraphEvents {
+    fn new() -> GraphEvents {
+        GraphEvents {
+            indexes: HashMap::new(),
+            events: Vec::new(),
+            completion: Event::empty(),
+        }
+    }
+
+    /// Returns the event of the command enqueued by the node named `name`.
+    pub fn get(&self, name: &str) -> Option<&Event> {
+        self.indexes.get(name).map(|&idx| &self.events[idx])
+    }
+
+    /// Returns an event which completes once every command of the graph
+    /// has completed.
+    pub fn completion(&self) -> &Event {
+        &self.completion
+    }
+
+    /// Blocks until every command of the graph has completed.
+    pub fn wait(&self) -> OclResult<()> {
+        self.completion.wait()
+    }
+
+    /// Returns an iterator over the name and event of each node, in the
+    /// order in which they were added.
+    pub fn iter<'e>(&'e self) -> Box<Iterator<Item=(&'e str, &'e Event)> + 'e> {
+        let mut names: Vec<(&str, usize)> = self.indexes.iter()
+            .map(|(name, &idx)| (name.as_str(), idx)).collect();
+        names.sort_by_key(|&(_, idx)| idx);
+
+        Box::new(names.into_iter().map(move |(name, idx)| (name, &self.events[idx])))
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+}
+
+/// A set of commands, each enqueued only after the commands it depends upon.
+///
+/// Each node is a named function which enqueues a command, given a list of
+/// events to wait for (the events of its dependencies) and an empty event to
+/// be filled in with the event of the new command. Dependencies are
+/// declared by name and may refer to nodes added later.
+///
+/// `::enqueue` sorts the nodes (in the order they were added where
+/// dependencies allow), then enqueues each node's command, followed by a
+/// marker which completes with all of them. The sorted order and the
+/// wait lists are kept, so a graph may be enqueued repeatedly (each frame,
+/// for example) without being rebuilt.
+///
+/// ## Examples
+///
+/// ```text
+/// let mut graph = Graph::new();
+///
+/// graph.add("upload", &[], |_, wait, new| {
+///     buffer.write(&input).ewait(wait).enew(new).enq()
+/// });
+/// graph.add("blur", &["upload"], |q, wait, new| {
+///     blur.cmd().queue(q).ewait(wait).enew(new).enq()
+/// });
+/// graph.add("edges", &["upload"], |q, wait, new| {
+///     edges.cmd().queue(q).ewait(wait).enew(new).enq()
+/// });
+/// graph.add("combine", &["blur", "edges"], |q, wait, new| {
+///     combine.cmd().queue(q).ewait(wait).enew(new).enq()
+/// });
+///
+/// for _ in 0..frames {
+///     let events = graph.enqueue(&queue).unwrap();
+///     events.wait().unwrap();
+/// }
+/// ```
+pub struct Graph<'a> {
+    nodes: Vec<Node<'a>>,
+    plan: Option<Plan>,
+    wait_lists: Vec<Vec<EventCore>>,
+    all_events: Vec<EventCore>,
+    events: GraphEvents,
+}
+
+impl<'a> Graph<'a> {
+    /// Returns a new, empty, graph.
+    pub fn new() -> Graph<'a> {
+        Graph {
+            nodes: Vec::new(),
+            plan: None,
+            wait_lists: Vec::new(),
+            all_events: Vec::new(),
+            events: GraphEvents::new(),
+        }
+    }
+
+    /// Adds a node named `name` which depends upon the nodes named in
+    /// `deps`.
+    ///
+    /// `enqueue` is called with the queue passed to `::enqueue`, the events
+    /// of the commands of each dependency, and an empty event which must be
+    /// used as the new event of the command it enqueues (as with `enew`).
+    ///
+    /// Duplicate names and unknown dependencies are reported by `::enqueue`.
+    pub fn add<F>(&mut self, name: &str, deps: &[&str], enqueue: F) -> &mut Graph<'a>
+            where F: FnMut(&Queue, &ClWaitList, &mut Event) -> OclResult<()> + 'a
+    {
+        self.nodes.push(Node {
+            name: name.to_owned(),
+            deps: deps.iter().map(|&dep| dep.to_owned()).collect(),
+            enqueue: Box::new(enqueue),
+        });
+        self.plan = None;
+        self
+    }
+
+    /// Enqueues the command of every node, each waiting for the commands of
+    /// its dependencies, and returns their events along with an aggregate
+    /// completion event.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error, before enqueuing anything, if a name is used by
+    /// more than one node, a dependency names no node, or the dependencies
+    /// form a cycle (named in the error, as in `a -> b -> a`, where each
+    /// node depends upon the next).
+    ///
+    /// Returns an error if a node's function returns an error or does not
+    /// fill in its event, in which case the commands of preceding nodes
+    /// remain enqueued.
+    pub fn enqueue(&mut self, queue: &Queue) -> OclResult<&GraphEvents> {
+        if self.plan.is_none() {
+            self.plan = Some(try!(self.make_plan()));
+        }
+
+        let plan = self.plan.as_ref().unwrap();
+        self.all_events.clear();
+        for event in self.events.events.iter_mut() {
+            *event = Event::empty();
+        }
+        self.events.completion = Event::empty();
+
+        for &idx in plan.order.iter() {
+            let wait_list = &mut self.wait_lists[idx];
+            wait_list.clear();
+            for &dep_idx in plan.deps[idx].iter() {
+                if let Some(event) = self.events.events[dep_idx].core_as_ref() {
+                    wait_list.push(event.clone());
+                }
+            }
+
+            let node = &mut self.nodes[idx];
+            let event = &mut self.events.events[idx];
+            try!((node.enqueue)(queue, &*wait_list, event));
+
+            match event.core_as_ref() {
+                Some(event) => self.all_events.push(event.clone()),
+                None => return OclError::err(format!("ocl::Graph::enqueue: The node '{}' did not \
+                    set its new event.", node.name)),
+            }
+        }
+
+        try!(queue.enqueue_marker(Some(&self.all_events as &ClWaitList),
+            Some(&mut self.events.completion)));
+        Ok(&self.events)
+    }
+
+    /// Returns the events of the most recent call to `::enqueue`.
+    pub fn events(&self) -> &GraphEvents {
+        &self.events
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if no nodes have been added.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Resolves dependencies and sorts the nodes, resizing the per-node
+    /// storage to match.
+    fn make_plan(&mut self) -> OclResult<Plan> {
+        let mut indexes = HashMap::with_capacity(self.nodes.len());
+        for (idx, node) in self.nodes.iter().enumerate() {
+            if indexes.insert(node.name.clone(), idx).is_some() {
+                return OclError::err(format!("ocl::Graph::enqueue: More than one node is named \
+                    '{}'.", node.name));
+            }
+        }
+
+        let mut deps = Vec::with_capacity(self.nodes.len());
+        for node in self.nodes.iter() {
+            let mut node_deps = Vec::with_capacity(node.deps.len());
+            for dep in node.deps.iter() {
+                match indexes.get(dep) {
+                    Some(&dep_idx) => node_deps.push(dep_idx),
+                    None => return OclError::err(format!("ocl::Graph::enqueue: The node '{}' \
+                        depends on '{}', which does not exist.", node.name, dep)),
+                }
+            }
+            deps.push(node_deps);
+        }
+
+        let order = try!(sort(&self.nodes, &deps));
+
+        self.wait_lists.resize(self.nodes.len(), Vec::new());
+        self.events.events.resize(self.nodes.len(), Event::empty());
+        self.events.indexes = indexes;
+
+        Ok(Plan { order: order, deps: deps })
+    }
+}
+
+impl<'a> Default for Graph<'a> {
+    fn default() -> Graph<'a> {
+        Graph::new()
+    }
+}
+
+impl<'a> std::fmt::Debug for Graph<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.nodes.iter().map(|node| (&node.name, &node.deps)))
+            .finish()
+    }
+}
+
+/// Returns the indexes of `nodes` sorted such that each follows its
+/// dependencies, otherwise in index order, or an error naming a cycle.
+fn sort(nodes: &[Node], deps: &[Vec<usize>]) -> OclResult<Vec<usize>> {
+    let mut dependents = vec![Vec::new(); nodes.len()];
+    let mut remaining: Vec<usize> = deps.iter().map(|d| d.len()).collect();
+    for (idx, node_deps) in deps.iter().enumerate() {
+        for &dep_idx in node_deps.iter() {
+            dependents[dep_idx].push(idx);
+        }
+    }
+
+    let mut order = Vec::with_capacity(nodes.len());
+    let mut sorted = vec![false; nodes.len()];
+
+    // Repeatedly take the first node whose dependencies have all been
+    // sorted, keeping nodes in the order they were added where possible:
+    while order.len() < nodes.len() {
+        let next = (0..nodes.len()).find(|&idx| !sorted[idx] && remaining[idx] == 0);
+
+        match next {
+            Some(idx) => {
+                sorted[idx] = true;
+                order.push(idx);
+                for &dependent in dependents[idx].iter() {
+                    remaining[dependent] -= 1;
+                }
+            },
+            None => {
+                // Every unsorted node has an unsorted dependency. Follow them
+                // until one repeats:
+                let mut path = vec![(0..nodes.len()).find(|&idx| !sorted[idx]).unwrap()];
+                loop {
+                    let last = *path.last().unwrap();
+                    let dep = *deps[last].iter().find(|&&dep| !sorted[dep]).unwrap();
+
+                    if let Some(start) = path.iter().position(|&idx| idx == dep) {
+                        let cycle: Vec<&str> = path[start..].iter().chain(Some(&dep))
+                            .map(|&idx| nodes[idx].name.as_str()).collect();
+                        return OclError::err(format!("ocl::Graph::enqueue: Dependency cycle: {}.",
+                            cycle.join(" -> ")));
+                    }
+                    path.push(dep);
+                }
+            },
+        }
+    }
+
+    Ok(order)
+}
@@ -7,10 +7,12 @@ use std::ops::{Deref, DerefMut};
 use std::marker::PhantomData;
 use std::convert::Into;
 use error::{Error as OclError, Result as OclResult};
-use core::{self, OclPrm, Mem as MemCore, MemFlags, MemObjectType, ImageFormat, ImageDescriptor, 
-    ImageInfo, ImageInfoResult, MemInfo, MemInfoResult, ClEventPtrNew, ClWaitList, 
+use core::{self, OclPrm, Mem as MemCore, MemFlags, MapFlags, MemObjectType, ImageFormat,
+    ImageDescriptor, ImageInfo, ImageInfoResult, MemInfo, MemInfoResult, ClEventPtrNew, ClWaitList, 
     ImageChannelOrder, ImageChannelDataType};
-use standard::{Context, Queue, MemLen, SpatialDims};
+use standard::{self, Context, Queue, MemLen, SpatialDims, ImageOrigin, ImageRegion,
+    MappedMem, resolve_queue};
+use util;
 
 
 /// A builder for `Image`. 
@@ -18,6 +20,7 @@ pub struct ImageBuilder<S: OclPrm> {
     flags: MemFlags,
     image_format: ImageFormat,
     image_desc: ImageDescriptor, 
+    image_type: Option<MemObjectType>,
     _pixel: PhantomData<S>,
     // image_data: Option<&'a [S]>,
 }
@@ -42,12 +45,18 @@ impl<S: OclPrm> ImageBuilder<S> {
     /// }
     /// ```
     ///
+    /// The channel data type defaults to `ImageChannelDataType::HalfFloat`
+    /// instead when the pixel component type, `S`, is `ocl::f16`.
+    ///
     /// * Descriptor (stores everything else - width, height, pitch, etc.):
     ///
     /// ```text
     /// ImageDescriptor::new(MemObjectType::Image1d, 0, 0, 0, 0, 0, 0, None)
     /// ```
     ///
+    /// The image type is inferred from the dimensions and array size unless
+    /// set (see `::image_type`).
+    ///
     /// ## Reference
     ///
     /// See the [official SDK documentation] for more information.
@@ -56,10 +65,17 @@ impl<S: OclPrm> ImageBuilder<S> {
     /// 
     /// [official SDK docs]: https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clCreateImage.html
     pub fn new() -> ImageBuilder<S> {
+        let mut image_format = ImageFormat::new_rgba();
+
+        if standard::is_f16::<S>() {
+            image_format.channel_data_type = ImageChannelDataType::HalfFloat;
+        }
+
         ImageBuilder { 
             flags: core::MEM_READ_WRITE,
-            image_format: ImageFormat::new_rgba(),
+            image_format: image_format,
             image_desc: ImageDescriptor::new(MemObjectType::Image1d, 0, 0, 0, 0, 0, 0, None),
+            image_type: None,
             _pixel: PhantomData,
             // image_data: None,
         }
@@ -67,9 +83,14 @@ impl<S: OclPrm> ImageBuilder<S> {
 
     /// Builds with no host side image data memory specified and returns a 
     /// new `Image`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a `DimsUnspecified` error if no dimensions have been set with
+    /// `::dims` (or `::image_desc`).
     pub fn build(&self, queue: &Queue) -> OclResult<Image<S>> {
-        Image::new(queue, self.flags, self.image_format.clone(), self.image_desc.clone(), 
-            None)
+        let image_desc = try!(self.checked_desc("ocl::ImageBuilder::build"));
+        Image::new(queue, self.flags, self.image_format.clone(), image_desc, None)
     }
 
     /// Builds with the host side image data specified by `image_data`
@@ -81,9 +102,36 @@ impl<S: OclPrm> ImageBuilder<S> {
     /// Also used with the `ocl::MEM_USE_HOST_PTR` and `ocl::ALLOC_HOST_PTR`
     /// flags. See the [official SDK docs] for more info.
     ///
+    /// Returns a `DimsUnspecified` error if no dimensions have been set.
+    ///
     /// [official SDK docs]: https://www.khronos.org/registry/cl/sdk/1.2/docs/man/xhtml/clCreateImage.html
     pub fn build_with_data(&self, queue: &Queue, image_data: &[S]) -> OclResult<Image<S>> {
-        Image::new(queue, self.flags, self.image_format.clone(), self.image_desc.clone(), 
+        let image_desc = try!(self.checked_desc("ocl::ImageBuilder::build_with_data"));
+        Image::new(queue, self.flags, self.image_format.clone(), image_desc, Some(image_data))
+    }
+
+    /// Builds within `context` and returns a new `Image` without a default
+    /// queue of its own. Commands use the default queue of the context
+    /// unless another is specified (see the `Queue` docs).
+    ///
+    /// Returns a `DimsUnspecified` error if no dimensions have been set.
+    pub fn build_in_context(&self, context: &Context) -> OclResult<Image<S>> {
+        let image_desc = try!(self.checked_desc("ocl::ImageBuilder::build_in_context"));
+        Image::create(None, Some(context), self.flags, self.image_format.clone(), image_desc,
+            None)
+    }
+
+    /// Builds within `context` with the host side image data specified by
+    /// `image_data` and returns a new `Image` without a default queue of its
+    /// own (see `::build_with_data` and `::build_in_context`).
+    ///
+    /// Returns a `DimsUnspecified` error if no dimensions have been set.
+    pub fn build_in_context_with_data(&self, context: &Context, image_data: &[S])
+            -> OclResult<Image<S>>
+    {
+        let image_desc = try!(self.checked_desc(
+            "ocl::ImageBuilder::build_in_context_with_data"));
+        Image::create(None, Some(context), self.flags, self.image_format.clone(), image_desc,
             Some(image_data))
     }
 
@@ -103,8 +151,13 @@ impl<S: OclPrm> ImageBuilder<S> {
     /// Describes the image type and must be either `Image1d`, `Image1dBuffer`,
     /// `Image1dArray`, `Image2d`, `Image2dArray`, or `Image3d`.
     ///
+    /// If not set, the type is inferred from the dimensions, array size, and
+    /// buffer (see `ImageDescriptor::checked`), which are checked against
+    /// the type when building either way.
+    ///
     pub fn image_type<'a>(&'a mut self, image_type: MemObjectType) -> &'a mut ImageBuilder<S> {
         self.image_desc.image_type = image_type;
+        self.image_type = Some(image_type);
         self
     }
 
@@ -142,10 +195,11 @@ impl<S: OclPrm> ImageBuilder<S> {
     /// * To set the dimensions of a 3d image use:
     ///   `SpatialDims::Three(width, height, depth)`.
     ///
+    /// Passing unspecified dimensions (`SpatialDims::Unspecified`) clears any
+    /// previously set dimensions, causing `::build` to return an error.
+    ///
     pub fn dims<'a, D: MemLen>(&'a mut self, dims: D) -> &'a mut ImageBuilder<S> {
-        let dims = dims.to_lens();
-        // let size = dims.to_lens().expect(&format!("ocl::ImageBuilder::dims(): Invalid image \
-     //        dimensions: {:?}", dims));
+        let dims = dims.to_lens().unwrap_or([0, 0, 0]);
         self.image_desc.image_width = dims[0];
         self.image_desc.image_height = dims[1];
         self.image_desc.image_depth = dims[2];
@@ -281,9 +335,26 @@ impl<S: OclPrm> ImageBuilder<S> {
     /// Setting this overwrites any previously set type, dimensions, array size, pitch, etc.
     /// 
     pub unsafe fn image_desc<'a>(&'a mut self, image_desc: ImageDescriptor) -> &'a mut ImageBuilder<S> {
+        self.image_type = Some(image_desc.image_type);
         self.image_desc = image_desc;
         self
     }
+
+    /// Returns the descriptor to create the image with, checked (and
+    /// completed) by `ImageDescriptor::checked`.
+    fn checked_desc(&self, fn_name: &'static str) -> OclResult<ImageDescriptor> {
+        if self.image_desc.image_width == 0 {
+            return OclError::err_dims_unspecified(fn_name);
+        }
+
+        let desc = &self.image_desc;
+        let mut checked = try!(ImageDescriptor::checked(self.image_type,
+            [desc.image_width, desc.image_height, desc.image_depth], desc.image_array_size,
+            desc.buffer.clone()));
+        checked.image_row_pitch = desc.image_row_pitch;
+        checked.image_slice_pitch = desc.image_slice_pitch;
+        Ok(checked)
+    }
 }
  
 
@@ -329,38 +400,49 @@ impl<'b, E: 'b> ImageCmdKind<'b, E> {
 /// image.cmd().read_async(&dst_vec).enew(&empty_event).enq().unwrap();
 ///
 /// ```
-///
-/// [FIXME]: Fills not yet implemented.
 #[allow(dead_code)]
 pub struct ImageCmd<'b, E: 'b + OclPrm> {
-    queue: &'b Queue,
+    queue: Option<&'b Queue>,
+    default_queue: Option<&'b Queue>,
+    context: Option<&'b Context>,
     obj_core: &'b MemCore,
     block: bool,
     lock_block: bool,
-    origin: [usize; 3],
-    region: [usize; 3],
+    origin: ImageOrigin,
+    region: ImageRegion,
     row_pitch: usize,
     slc_pitch: usize,
     kind: ImageCmdKind<'b, E>,
     ewait: Option<&'b ClWaitList>,
     enew: Option<&'b mut ClEventPtrNew>,
-    mem_dims: [usize; 3],
+    mem_dims: ImageRegion,
+    copy_dst_dims: Option<ImageRegion>,
 }
 
 /// [UNSTABLE]: All methods still in a state of adjustifulsomeness.
 impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
     /// Returns a new image command builder associated with with the
-    /// memory object `obj_core` along with a default `queue` and `to_len` 
-    /// (the length of the device side image).
-    pub fn new(queue: &'b Queue, obj_core: &'b MemCore, dims: [usize; 3]) 
+    /// memory object `obj_core` along with a default `queue` and `dims`
+    /// (the size of the device side image, see `Image::region`).
+    pub fn new<D: Into<ImageRegion>>(queue: &'b Queue, obj_core: &'b MemCore, dims: D)
             -> ImageCmd<'b, E>
+    {
+        ImageCmd::with_defaults(Some(queue), None, obj_core, dims.into())
+    }
+
+    /// Returns a new image command builder using the default queue of
+    /// either the image or, failing that, `context` (see the `Queue` docs).
+    fn with_defaults(default_queue: Option<&'b Queue>, context: Option<&'b Context>,
+            obj_core: &'b MemCore, dims: ImageRegion) -> ImageCmd<'b, E>
     {
         ImageCmd {
-            queue: queue,
+            queue: None,
+            default_queue: default_queue,
+            context: context,
             obj_core: obj_core,
             block: true,
             lock_block: false,
-            origin: [0, 0, 0],
+            origin: ImageOrigin::default(),
             region: dims,
             row_pitch: 0,
             slc_pitch: 0,
@@ -368,12 +450,14 @@ impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
             ewait: None,
             enew: None,
             mem_dims: dims,
+            copy_dst_dims: None,
         }
     }
 
-    /// Specifies a queue to use for this call only.
+    /// Specifies a queue to use for this call only, overriding the default
+    /// queues of the image and its context (see the `Queue` docs).
     pub fn queue(mut self, queue: &'b Queue) -> ImageCmd<'b, E> {
-        self.queue = queue;
+        self.queue = Some(queue);
         self
     }
 
@@ -398,13 +482,8 @@ impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
     /// Sets the three dimensional offset, the origin point, for an operation.
     /// 
     /// Defaults to [0, 0, 0] if not set.
-    ///
-    /// ## Panics
-    ///
-    /// The 'shape' may not have already been set to rectangular by the 
-    /// `::rect` function.
-    pub fn origin(mut self, origin: [usize; 3]) -> ImageCmd<'b, E> {
-        self.origin = origin;
+    pub fn origin<O: Into<ImageOrigin>>(mut self, origin: O) -> ImageCmd<'b, E> {
+        self.origin = origin.into();
         self
     }
 
@@ -413,14 +492,11 @@ impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
     /// Defaults to the full region size of the image(s) as defined when first
     /// created if not set.
     ///
-    /// ## Panics [TENATIVE]
-    ///
-    /// Panics if the region is out of range on any of the three dimensions.
-    ///
-    /// [FIXME]: Probably delay checking this until enq().
-    ///
-    pub fn region(mut self, region: [usize; 3]) -> ImageCmd<'b, E> {
-        self.region = region;
+    /// The region must lie within the image when placed at the origin (see
+    /// `ImageRegion::fits_within`), otherwise an error is returned when the
+    /// command is enqueued.
+    pub fn region<R: Into<ImageRegion>>(mut self, region: R) -> ImageCmd<'b, E> {
+        self.region = region.into();
         self
     }
 
@@ -508,19 +584,25 @@ impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
     ///
     /// ## Errors
     ///
-    /// If this is a rectangular copy, `dst_origin` and `len` must be zero.
+    /// The region must lie within `dst_image` when placed at `dst_origin`
+    /// and, when copying within one image, the source and destination may
+    /// not overlap (see `ImageRegion::overlaps`), otherwise an error is
+    /// returned when the command is enqueued.
     ///
     /// ## Panics
     ///
     /// The command operation kind must not have already been specified
     ///
-    pub fn copy(mut self, dst_image: &'b Image<E>, dst_origin: [usize; 3]) -> ImageCmd<'b, E> {
+    pub fn copy<O: Into<ImageOrigin>>(mut self, dst_image: &'b Image<E>, dst_origin: O)
+            -> ImageCmd<'b, E>
+    {
         assert!(self.kind.is_unspec(), "ocl::ImageCmd::copy(): Operation kind \
             already set for this command.");
         self.kind = ImageCmdKind::Copy { 
             dst_image: dst_image.core_as_ref(),
-            dst_origin: dst_origin,
+            dst_origin: dst_origin.into().to_array(),
         }; 
+        self.copy_dst_dims = Some(dst_image.region());
         self
     }
 
@@ -541,6 +623,9 @@ impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
 
     /// Specifies that this command will be a fill.
     ///
+    /// `color` must have four 32-bit components (see
+    /// `core::enqueue_fill_image`). Fills require OpenCL 1.2 or later.
+    ///
     /// If `.block(..)` has been set it will be ignored.
     ///
     /// ## Panics
@@ -583,23 +668,58 @@ impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
 
     /// Enqueues this command.
     ///
+    /// Returns an error if no queue has been specified for the command and
+    /// neither the image nor its context has a default queue.
+    ///
     /// TODO: FOR COPY, FILL, AND COPYTOBUFFER -- ENSURE PITCHES ARE BOTH UNSET.
     pub fn enq(self) -> OclResult<()> {
+        let queue = try!(resolve_queue("ocl::ImageCmd::enq", self.queue, self.default_queue,
+            self.context));
+
+        if !self.kind.is_unspec() {
+            try!(self.region.check_within("ocl::ImageCmd::enq", self.mem_dims, self.origin));
+        }
+
+        let origin = self.origin.to_array();
+        let region = self.region.to_array();
+
         match self.kind {
             ImageCmdKind::Read { data } => { 
                 // try!(check_len(self.to_len, data.len(), offset));
-                unsafe { core::enqueue_read_image(self.queue, self.obj_core, self.block, 
-                    self.origin, self.region, self.row_pitch, self.slc_pitch, data, self.ewait, 
+                unsafe { core::enqueue_read_image(&queue, self.obj_core, self.block, 
+                    origin, region, self.row_pitch, self.slc_pitch, data, self.ewait, 
                     self.enew) }
             },
             ImageCmdKind::Write { data } => {
-                core::enqueue_write_image(self.queue, self.obj_core, self.block, 
-                    self.origin, self.region, self.row_pitch, self.slc_pitch, data, self.ewait, 
+                core::enqueue_write_image(&queue, self.obj_core, self.block, 
+                    origin, region, self.row_pitch, self.slc_pitch, data, self.ewait, 
                     self.enew)
             },
             ImageCmdKind::Copy { dst_image, dst_origin } => {
-                core::enqueue_copy_image::<E>(self.queue, self.obj_core, dst_image, self.origin,
-                    dst_origin, self.region, self.ewait, self.enew)
+                if let Some(dst_dims) = self.copy_dst_dims {
+                    try!(self.region.check_within("ocl::ImageCmd::enq", dst_dims,
+                        dst_origin.into()));
+                }
+                if unsafe { dst_image.as_ptr() == self.obj_core.as_ptr() } &&
+                    self.region.overlaps(self.origin, dst_origin)
+                {
+                    return OclError::err(format!("ocl::ImageCmd::enq: The source ({:?}) and \
+                        destination ({:?}) of a copy within one image overlap.", origin,
+                        dst_origin));
+                }
+
+                core::enqueue_copy_image::<E>(&queue, self.obj_core, dst_image, origin,
+                    dst_origin, region, self.ewait, self.enew)
+            },
+            ImageCmdKind::Fill { color } => {
+                let version = std::cmp::min(queue.device_version(), queue.platform_version());
+                if version < (1, 2) {
+                    return OclError::err_version_unsupported("ocl::ImageCmd::enq (fill)",
+                        (1, 2), version);
+                }
+
+                core::enqueue_fill_image(&queue, self.obj_core, color, origin, region,
+                    self.ewait, self.enew)
             },
             ImageCmdKind::Unspecified => return OclError::err("ocl::ImageCmd::enq(): No operation \
                 specified. Use '.read(...)', 'write(...)', etc. before calling '.enq()'."),
@@ -609,15 +729,40 @@ impl<'b, E: 'b + OclPrm> ImageCmd<'b, E> {
 }
 
 
+/// Returns the region covering the whole of an image described by `desc`.
+fn image_region(desc: &ImageDescriptor) -> ImageRegion {
+    let height = std::cmp::max(desc.image_height, 1);
+    let depth = std::cmp::max(desc.image_depth, 1);
+    let layers = std::cmp::max(desc.image_array_size, 1);
+
+    match desc.image_type {
+        MemObjectType::Image1d | MemObjectType::Image1dBuffer => ImageRegion::d1(desc.image_width),
+        MemObjectType::Image1dArray => ImageRegion::d1_array(desc.image_width, layers),
+        MemObjectType::Image2d => ImageRegion::d2(desc.image_width, height),
+        MemObjectType::Image2dArray => ImageRegion::d2_array(desc.image_width, height, layers),
+        _ => ImageRegion::d3(desc.image_width, height, depth),
+    }
+}
+
+
 /// A section of device memory which represents one or many images.
 ///
 /// Use `::builder` for an easy way to create. [UNIMPLEMENTED]
 ///
-#[derive(Clone, Debug)]
+/// Commands are enqueued on the default queue of the image or, for images
+/// created with `ImageBuilder::build_in_context`, that of its context,
+/// unless another is specified (see the `Queue` docs).
+///
+#[derive(Clone)]
 pub struct Image<E: OclPrm> {
     obj_core: MemCore,
-    queue: Queue,
+    queue: Option<Queue>,
+    context: Option<Context>,
     dims: SpatialDims,
+    region: ImageRegion,
+    image_type: MemObjectType,
+    format: ImageFormat,
+    flags: MemFlags,
     pixel_element_len: usize,
     _pixel: PhantomData<E>
 }
@@ -638,11 +783,42 @@ impl<E: OclPrm> Image<E> {
     /// Returns a new `Image`.
     ///
     /// Prefer `::builder` to create a new image.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the device does not support images or if the
+    /// dimensions of the image exceed the maximums of the device.
     pub fn new(queue: &Queue, flags: MemFlags, image_format: ImageFormat,
             image_desc: ImageDescriptor, image_data: Option<&[E]>) -> OclResult<Image<E>>
     {
+        Image::create(Some(queue), None, flags, image_format, image_desc, image_data)
+    }
+
+    /// Creates a new image within the context of `queue`, if given, which
+    /// becomes its default queue. Otherwise creates it within `context`,
+    /// using its default queue.
+    fn create(queue: Option<&Queue>, context: Option<&Context>, flags: MemFlags,
+            image_format: ImageFormat, image_desc: ImageDescriptor, image_data: Option<&[E]>)
+            -> OclResult<Image<E>>
+    {
+        let context_core = match (queue, context) {
+            (Some(queue), _) => {
+                try!(queue.device_limits().check_image_desc("ocl::Image::new", &image_desc));
+                queue.context_core_as_ref()
+            },
+            (None, Some(context)) => {
+                for idx in 0..context.devices().len() {
+                    try!(try!(context.device_limits(idx)).check_image_desc("ocl::Image::new",
+                        &image_desc));
+                }
+                context.core_as_ref()
+            },
+            (None, None) => return OclError::err("ocl::Image::new: A queue or context must be \
+                specified."),
+        };
+
         let obj_core = unsafe { try!(core::create_image(
-            queue.context_core_as_ref(),
+            context_core,
             flags,
             &image_format,
             &image_desc,
@@ -660,8 +836,13 @@ impl<E: OclPrm> Image<E> {
 
         let new_img = Image {
             obj_core: obj_core,
-            queue: queue.clone(),
+            queue: queue.cloned(),
+            context: context.cloned(),
             dims: dims,
+            region: image_region(&image_desc),
+            image_type: image_desc.image_type,
+            format: image_format,
+            flags: flags,
             pixel_element_len: pixel_element_len,
             _pixel: PhantomData,
         };
@@ -674,8 +855,8 @@ impl<E: OclPrm> Image<E> {
     /// Run `.enq()` to enqueue the command.
     ///
     pub fn cmd<'b>(&'b self) -> ImageCmd<'b, E> {
-        ImageCmd::new(&self.queue, &self.obj_core, 
-            self.dims.to_lens().expect("ocl::Image::cmd"))
+        ImageCmd::with_defaults(self.queue.as_ref(), self.context.as_ref(), &self.obj_core,
+            self.region)
     }
 
     /// Returns an image command builder set to read.
@@ -694,6 +875,51 @@ impl<E: OclPrm> Image<E> {
         self.cmd().write(data)
     }
 
+    /// Returns an image command builder set to fill with `color`, which
+    /// must have four 32-bit components (see `ImageCmd::fill`).
+    ///
+    /// Fills the whole image unless an origin and region are set. Run
+    /// `.enq()` to enqueue the command.
+    ///
+    pub fn fill<'b>(&'b self, color: &'b [E]) -> ImageCmd<'b, E> {
+        self.cmd().fill(color)
+    }
+
+    /// Maps the rectangular `region` at `origin` into host memory, once
+    /// each event in `wait_list` has completed.
+    ///
+    /// The map is enqueued on the default queue of the image (or of its
+    /// context) without blocking. The returned guard blocks until it has
+    /// completed when first dereferenced and unmaps the region when dropped
+    /// (see `MappedMem`).
+    ///
+    /// Rows (and slices) of the mapped region may be padded. Index it using
+    /// `MappedMem::row_pitch` and `MappedMem::slc_pitch`, which are in
+    /// elements: the first element of the pixel at `[x, y, z]` (relative to
+    /// `origin`) is at `z * slc_pitch + y * row_pitch + x * pixel_len`,
+    /// where `pixel_len` is the number of elements per pixel. For one
+    /// dimensional image arrays, the layer, `y`, is instead multiplied by
+    /// the slice pitch.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the region is empty or does not lie within the
+    /// image.
+    pub fn map<O, R>(&self, flags: MapFlags, origin: O, region: R,
+            wait_list: Option<&ClWaitList>) -> OclResult<MappedMem<E>>
+            where O: Into<ImageOrigin>, R: Into<ImageRegion>
+    {
+        let origin = origin.into();
+        let region = region.into();
+        try!(region.check_within("ocl::Image::map", self.region, origin));
+
+        let queue = try!(resolve_queue("ocl::Image::map", None, self.queue.as_ref(),
+            self.context.as_ref())).into_owned();
+
+        MappedMem::map_image(queue, &self.obj_core, flags, origin.to_array(), region.to_array(),
+            self.pixel_element_len, self.image_type == MemObjectType::Image1dArray, wait_list)
+    }
+
     /// Changes the default queue.
     ///
     /// Returns a ref for chaining i.e.:
@@ -708,13 +934,23 @@ impl<E: OclPrm> Image<E> {
     ///
     pub fn set_default_queue<'a>(&'a mut self, queue: &Queue) -> &'a mut Image<E> {
         // self.command_queue_obj_core = queue.core_as_ref().clone();
-        self.queue = queue.clone();
+        self.queue = Some(queue.clone());
         self
     }
 
-    /// Returns a reference to the default queue.
-    pub fn default_queue(&self) -> &Queue {
-        &self.queue
+    /// Returns a reference to the default queue, or `None` if the image was
+    /// created within a context (see `ImageBuilder::build_in_context`) and
+    /// none has been set since.
+    pub fn default_queue(&self) -> Option<&Queue> {
+        self.queue.as_ref()
+    }
+
+    /// Returns the queue used by commands which do not specify one: the
+    /// default queue of the image or, failing that, that of its context
+    /// (see the `Queue` docs).
+    pub fn resolve_queue(&self) -> OclResult<Queue> {
+        resolve_queue("ocl::Image::resolve_queue", None, self.queue.as_ref(),
+            self.context.as_ref()).map(|queue| queue.into_owned())
     }
 
     /// Returns this image's dimensions.
@@ -722,6 +958,30 @@ impl<E: OclPrm> Image<E> {
         &self.dims
     }
 
+    /// Returns the region covering the whole image, as used by commands.
+    ///
+    /// Unlike `::dims`, this counts the images of an image array in place of
+    /// the dimension following the last image dimension (see
+    /// `ImageRegion::d1_array` and `ImageRegion::d2_array`).
+    pub fn region(&self) -> ImageRegion {
+        self.region
+    }
+
+    /// Returns the type of this image (`MemObjectType::Image2d`, etc.).
+    pub fn image_type(&self) -> MemObjectType {
+        self.image_type
+    }
+
+    /// Returns the format this image was created with.
+    pub fn format(&self) -> &ImageFormat {
+        &self.format
+    }
+
+    /// Returns the flags this image was created with.
+    pub fn flags(&self) -> MemFlags {
+        self.flags
+    }
+
     /// Returns the total number of pixels in this image.
     pub fn pixel_count(&self) -> usize {
         self.dims.to_len()
@@ -761,6 +1021,18 @@ impl<E: OclPrm> Image<E> {
         &self.obj_core
     }
 
+    /// Registers `callback` to be called once the underlying memory object
+    /// has been destroyed, after every clone of this image has been dropped
+    /// and any commands using it have completed.
+    ///
+    /// `callback` is run on a thread belonging to the OpenCL implementation.
+    /// Panics within it are caught and reported rather than propagated.
+    pub fn on_destroy<F>(&self, callback: F) -> OclResult<()>
+            where F: FnOnce() + Send + 'static
+    {
+        core::set_mem_object_destructor_callback(&self.obj_core, callback)
+    }
+
     /// Format image info.
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Image")
@@ -787,12 +1059,64 @@ impl<E: OclPrm> Image<E> {
             .field("MapCount", &self.mem_info(MemInfo::MapCount))
             .field("ReferenceCount", &self.mem_info(MemInfo::ReferenceCount))
             .field("Context", &self.mem_info(MemInfo::Context))
-            .field("AssociatedMemobject", &self.mem_info(MemInfo::AssociatedMemobject))
+            .field("AssociatedMemObject", &self.mem_info(MemInfo::AssociatedMemObject))
             .field("Offset", &self.mem_info(MemInfo::Offset))
             .finish()
     }
 }
 
+#[cfg(feature = "ndarray")]
+impl Image<u8> {
+    /// Creates a new 2d image within the context of `queue` (its default
+    /// queue) from an array of shape `(height, width, channels)`, where
+    /// `channels` is 1, 2, or 4 (`R`, `Rg`, or `Rgba`), with a channel data
+    /// type of `UnormInt8`.
+    ///
+    /// Arrays not in standard layout are first copied into a contiguous
+    /// array.
+    pub fn from_array<S>(queue: &Queue, array: &::ndarray::ArrayBase<S, ::ndarray::Ix3>)
+            -> OclResult<Image<u8>>
+            where S: ::ndarray::Data<Elem = u8>
+    {
+        let (height, width, channels) = array.dim();
+        let channel_order = match channels {
+            1 => ImageChannelOrder::R,
+            2 => ImageChannelOrder::Rg,
+            4 => ImageChannelOrder::Rgba,
+            _ => return OclError::err(format!("ocl::Image::from_array: Unsupported number of \
+                channels ({}), must be 1, 2, or 4.", channels)),
+        };
+
+        let array = array.as_standard_layout();
+        let data = array.as_slice().expect("ocl::Image::from_array: Array not contiguous.");
+        Image::from_pixels(queue, channel_order, width, height, data)
+    }
+
+    /// Creates a new 2d `Rgba` image within the context of `queue` (its
+    /// default queue) from an array of pixels of shape `(height, width)`,
+    /// with a channel data type of `UnormInt8`.
+    pub fn from_rgba_array<S>(queue: &Queue, array: &::ndarray::ArrayBase<S, ::ndarray::Ix2>)
+            -> OclResult<Image<u8>>
+            where S: ::ndarray::Data<Elem = [u8; 4]>
+    {
+        let (height, width) = array.dim();
+        let data: Vec<u8> = array.iter().flat_map(|px| px.iter().cloned()).collect();
+        Image::from_pixels(queue, ImageChannelOrder::Rgba, width, height, &data)
+    }
+
+    fn from_pixels(queue: &Queue, channel_order: ImageChannelOrder, width: usize,
+            height: usize, data: &[u8]) -> OclResult<Image<u8>>
+    {
+        Image::builder()
+            .channel_order(channel_order)
+            .channel_data_type(ImageChannelDataType::UnormInt8)
+            .image_type(MemObjectType::Image2d)
+            .dims([width, height])
+            .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+            .build_with_data(queue, data)
+    }
+}
+
 impl<E: OclPrm> std::fmt::Display for Image<E> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         try!(self.fmt_info(f));
@@ -801,6 +1125,25 @@ impl<E: OclPrm> std::fmt::Display for Image<E> {
     }
 }
 
+/// Shows the pixel element type, image type, dimensions, format, flags, and
+/// pixel element length, all cached when the image was created (no calls
+/// are made to the implementation), along with the memory object. The
+/// alternate form (`{:#?}`) adds info queried about the memory object (see
+/// `core::Mem`).
+impl<E: OclPrm> std::fmt::Debug for Image<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Image")
+            .field("type", &format_args!("{}", util::short_type_name::<E>()))
+            .field("image_type", &self.image_type)
+            .field("dims", &self.dims)
+            .field("format", &self.format)
+            .field("flags", &self.flags)
+            .field("pixel_element_len", &self.pixel_element_len)
+            .field("mem", &self.obj_core)
+            .finish()
+    }
+}
+
 impl<E: OclPrm> Deref for Image<E> {
     type Target = MemCore;
 
@@ -0,0 +1,264 @@
+//! Three dimensional origins and regions used by image and rectangular
+//! commands.
+
+use std::ops::Index;
+use error::{Error as OclError, Result as OclResult};
+use standard::SpatialDims;
+
+/// The origin, `[x, y, z]`, of an image or rectangular command, in pixels
+/// (or elements).
+///
+/// For image arrays, the coordinate following the last image dimension is
+/// the index of the first image within the array (see `::d1_array` and
+/// `::d2_array`).
+///
+/// Unused coordinates are zero. May be created from an `[x, y, z]` array, a
+/// tuple or array of one to three coordinates, or a `SpatialDims` (an
+/// unspecified one giving `[0, 0, 0]`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct ImageOrigin([usize; 3]);
+
+impl ImageOrigin {
+    /// Returns the origin `[x, 0, 0]` within a 1D image.
+    pub fn d1(x: usize) -> ImageOrigin {
+        ImageOrigin([x, 0, 0])
+    }
+
+    /// Returns the origin `[x, y, 0]` within a 2D image.
+    pub fn d2(x: usize, y: usize) -> ImageOrigin {
+        ImageOrigin([x, y, 0])
+    }
+
+    /// Returns the origin `[x, y, z]` within a 3D image.
+    pub fn d3(x: usize, y: usize, z: usize) -> ImageOrigin {
+        ImageOrigin([x, y, z])
+    }
+
+    /// Returns the origin `[x, layer, 0]` within a 1D image array, `layer`
+    /// being the index of an image within the array.
+    pub fn d1_array(x: usize, layer: usize) -> ImageOrigin {
+        ImageOrigin([x, layer, 0])
+    }
+
+    /// Returns the origin `[x, y, layer]` within a 2D image array, `layer`
+    /// being the index of an image within the array.
+    pub fn d2_array(x: usize, y: usize, layer: usize) -> ImageOrigin {
+        ImageOrigin([x, y, layer])
+    }
+
+    /// Returns the coordinates as an `[x, y, z]` array.
+    pub fn to_array(&self) -> [usize; 3] {
+        self.0
+    }
+}
+
+impl Index<usize> for ImageOrigin {
+    type Output = usize;
+
+    fn index(&self, idx: usize) -> &usize {
+        &self.0[idx]
+    }
+}
+
+impl From<[usize; 3]> for ImageOrigin {
+    fn from(coords: [usize; 3]) -> ImageOrigin {
+        ImageOrigin(coords)
+    }
+}
+
+impl From<[usize; 2]> for ImageOrigin {
+    fn from(coords: [usize; 2]) -> ImageOrigin {
+        ImageOrigin::d2(coords[0], coords[1])
+    }
+}
+
+impl From<[usize; 1]> for ImageOrigin {
+    fn from(coords: [usize; 1]) -> ImageOrigin {
+        ImageOrigin::d1(coords[0])
+    }
+}
+
+impl From<(usize, usize, usize)> for ImageOrigin {
+    fn from(coords: (usize, usize, usize)) -> ImageOrigin {
+        ImageOrigin::d3(coords.0, coords.1, coords.2)
+    }
+}
+
+impl From<(usize, usize)> for ImageOrigin {
+    fn from(coords: (usize, usize)) -> ImageOrigin {
+        ImageOrigin::d2(coords.0, coords.1)
+    }
+}
+
+impl From<usize> for ImageOrigin {
+    fn from(x: usize) -> ImageOrigin {
+        ImageOrigin::d1(x)
+    }
+}
+
+impl From<SpatialDims> for ImageOrigin {
+    fn from(dims: SpatialDims) -> ImageOrigin {
+        ImageOrigin(dims.to_offset().unwrap_or([0, 0, 0]))
+    }
+}
+
+impl From<ImageOrigin> for [usize; 3] {
+    fn from(origin: ImageOrigin) -> [usize; 3] {
+        origin.0
+    }
+}
+
+
+/// The size, `[width, height, depth]`, of the region of an image or
+/// rectangular command, in pixels (or elements).
+///
+/// For image arrays, the size following the last image dimension is the
+/// number of images within the array (see `::d1_array` and `::d2_array`).
+///
+/// Unused sizes are one. May be created from a `[width, height, depth]`
+/// array, a tuple or array of one to three sizes, or a `SpatialDims` (an
+/// unspecified one giving an empty region, which is invalid to any command).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageRegion([usize; 3]);
+
+impl ImageRegion {
+    /// Returns the region `[width, 1, 1]` of a 1D image.
+    pub fn d1(width: usize) -> ImageRegion {
+        ImageRegion([width, 1, 1])
+    }
+
+    /// Returns the region `[width, height, 1]` of a 2D image.
+    pub fn d2(width: usize, height: usize) -> ImageRegion {
+        ImageRegion([width, height, 1])
+    }
+
+    /// Returns the region `[width, height, depth]` of a 3D image.
+    pub fn d3(width: usize, height: usize, depth: usize) -> ImageRegion {
+        ImageRegion([width, height, depth])
+    }
+
+    /// Returns the region `[width, layers, 1]` of a 1D image array, `layers`
+    /// being a number of images within the array.
+    pub fn d1_array(width: usize, layers: usize) -> ImageRegion {
+        ImageRegion([width, layers, 1])
+    }
+
+    /// Returns the region `[width, height, layers]` of a 2D image array,
+    /// `layers` being a number of images within the array.
+    pub fn d2_array(width: usize, height: usize, layers: usize) -> ImageRegion {
+        ImageRegion([width, height, layers])
+    }
+
+    /// Returns the sizes as a `[width, height, depth]` array.
+    pub fn to_array(&self) -> [usize; 3] {
+        self.0
+    }
+
+    /// Returns the number of pixels (or elements) within the region.
+    pub fn to_len(&self) -> usize {
+        self.0[0] * self.0[1] * self.0[2]
+    }
+
+    /// Returns true if any size is zero.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(|&s| s == 0)
+    }
+
+    /// Returns true if this region, placed at `origin`, lies entirely within
+    /// an image (or rectangle) of size `dims`.
+    ///
+    /// Empty regions never fit.
+    pub fn fits_within<D, O>(&self, dims: D, origin: O) -> bool
+            where D: Into<ImageRegion>, O: Into<ImageOrigin>
+    {
+        let dims = dims.into();
+        let origin = origin.into();
+
+        !self.is_empty() && (0..3).all(|i| {
+            origin[i].checked_add(self.0[i]).map(|end| end <= dims.0[i]).unwrap_or(false)
+        })
+    }
+
+    /// Returns true if this region overlaps itself when placed at both `a`
+    /// and `b`, as when copying within one image.
+    pub fn overlaps<A, B>(&self, a: A, b: B) -> bool
+            where A: Into<ImageOrigin>, B: Into<ImageOrigin>
+    {
+        let a = a.into();
+        let b = b.into();
+
+        !self.is_empty() && (0..3).all(|i| {
+            let (lo, hi) = if a[i] <= b[i] { (a[i], b[i]) } else { (b[i], a[i]) };
+            hi - lo < self.0[i]
+        })
+    }
+
+    /// Returns an error if this region, placed at `origin`, does not lie
+    /// entirely within `dims` (see `::fits_within`).
+    pub(crate) fn check_within(&self, fn_name: &str, dims: ImageRegion, origin: ImageOrigin)
+            -> OclResult<()>
+    {
+        if self.fits_within(dims, origin) {
+            Ok(())
+        } else {
+            OclError::err(format!("{}: Region ({:?}) at origin ({:?}) is empty or out of range \
+                for dimensions ({:?}).", fn_name, self.0, origin.0, dims.0))
+        }
+    }
+}
+
+impl Index<usize> for ImageRegion {
+    type Output = usize;
+
+    fn index(&self, idx: usize) -> &usize {
+        &self.0[idx]
+    }
+}
+
+impl From<[usize; 3]> for ImageRegion {
+    fn from(sizes: [usize; 3]) -> ImageRegion {
+        ImageRegion(sizes)
+    }
+}
+
+impl From<[usize; 2]> for ImageRegion {
+    fn from(sizes: [usize; 2]) -> ImageRegion {
+        ImageRegion::d2(sizes[0], sizes[1])
+    }
+}
+
+impl From<[usize; 1]> for ImageRegion {
+    fn from(sizes: [usize; 1]) -> ImageRegion {
+        ImageRegion::d1(sizes[0])
+    }
+}
+
+impl From<(usize, usize, usize)> for ImageRegion {
+    fn from(sizes: (usize, usize, usize)) -> ImageRegion {
+        ImageRegion::d3(sizes.0, sizes.1, sizes.2)
+    }
+}
+
+impl From<(usize, usize)> for ImageRegion {
+    fn from(sizes: (usize, usize)) -> ImageRegion {
+        ImageRegion::d2(sizes.0, sizes.1)
+    }
+}
+
+impl From<usize> for ImageRegion {
+    fn from(width: usize) -> ImageRegion {
+        ImageRegion::d1(width)
+    }
+}
+
+impl From<SpatialDims> for ImageRegion {
+    fn from(dims: SpatialDims) -> ImageRegion {
+        ImageRegion(dims.to_lens().unwrap_or([0, 0, 0]))
+    }
+}
+
+impl From<ImageRegion> for [usize; 3] {
+    fn from(region: ImageRegion) -> [usize; 3] {
+        region.0
+    }
+}
@@ -3,61 +3,325 @@
 
 
 use std;
+use std::mem;
+use std::slice;
+use std::marker::PhantomData;
 use std::convert::Into;
 use std::collections::HashMap;
-use core::{self, OclPrm, Kernel as KernelCore, CommandQueue as CommandQueueCore, Mem as MemCore,
+use std::sync::atomic::{AtomicBool, Ordering};
+use core::{self, OclPrm, Kernel as KernelCore, Mem as MemCore,
     KernelArg, KernelInfo, KernelInfoResult, KernelArgInfo, KernelArgInfoResult, 
-    KernelWorkGroupInfo, KernelWorkGroupInfoResult, ClEventPtrNew, ClWaitList};
+    KernelWorkGroupInfo, KernelWorkGroupInfoResult, ClEventPtrNew, ClWaitList,
+    WorkSizes, DeviceId as DeviceIdCore, Sampler as SamplerCore};
 use error::{Result as OclResult, Error as OclError};
-use standard::{SpatialDims, Buffer, Image, Program, Queue, WorkDims, Sampler, Device};
+use standard::{self, SpatialDims, Buffer, BufferView, Image, Program, Queue, WorkDims, Sampler,
+    Device, Event, EventList, SharedKernel, Context, resolve_queue};
 
 const PRINT_DEBUG: bool = false;
 
+/// Checks a set of work dimensions against the limits of a device and kernel.
+///
+/// Every offending component is listed in the returned error, along with the
+/// limit it exceeds, rather than stopping at the first one.
+///
+/// `max_wi_sizes` is the `DeviceInfo::MaxWorkItemSizes` list for the device
+/// (its length being the maximum number of dimensions), `max_wg_size` is
+/// `DeviceInfo::MaxWorkGroupSize`, `kernel_wg_size` is
+/// `KernelWorkGroupInfo::WorkGroupSize` for the kernel on that device and
+/// `device_version` is the device's OpenCL version. Global work sizes need
+/// only be evenly divisible by local work sizes below OpenCL 2.0, which
+/// allows non-uniform work groups.
+///
+pub(crate) fn check_work_dims(dims: &WorkSizes, max_wi_sizes: &[usize], max_wg_size: usize,
+            kernel_wg_size: Option<usize>, device_version: (u32, u32)) -> OclResult<()>
+{
+    let dim_count = dims.dim_count() as usize;
+    let gws = dims.global_work_size();
+    let lws = dims.local_work_size();
+    let mut errs: Vec<String> = Vec::new();
+
+    if dim_count > max_wi_sizes.len() {
+        errs.push(format!("work dimension count ({}) exceeds the device maximum ({})",
+            dim_count, max_wi_sizes.len()));
+    }
+
+    for d in 0..dim_count {
+        if gws[d] == 0 {
+            errs.push(format!("global work size [{}] is zero", d));
+        }
+    }
+
+    if let Some(lws) = lws {
+        // `None` if the product overflows, exceeding any limit:
+        let mut wg_size = Some(1usize);
+
+        for d in 0..dim_count {
+            wg_size = wg_size.and_then(|s| s.checked_mul(lws[d]));
+
+            if let Some(&max) = max_wi_sizes.get(d) {
+                if lws[d] > max {
+                    errs.push(format!("local work size [{}] ({}) exceeds the device maximum \
+                        work item size for that dimension ({})", d, lws[d], max));
+                }
+            }
+
+            if lws[d] == 0 {
+                errs.push(format!("local work size [{}] is zero", d));
+            } else if device_version < (2, 0) && gws[d] % lws[d] != 0 {
+                errs.push(format!("global work size [{}] ({}) is not evenly divisible by \
+                    local work size [{}] ({})", d, gws[d], d, lws[d]));
+            }
+        }
+
+        let wg_size_str = match wg_size {
+            Some(s) => s.to_string(),
+            None => String::from("overflows usize"),
+        };
+
+        if wg_size.map_or(true, |s| s > max_wg_size) {
+            errs.push(format!("work group size ({}) exceeds the device maximum work group \
+                size ({})", wg_size_str, max_wg_size));
+        }
+
+        if let Some(kernel_wg_size) = kernel_wg_size {
+            if wg_size.map_or(true, |s| s > kernel_wg_size) {
+                errs.push(format!("work group size ({}) exceeds the maximum work group \
+                    size for this kernel ({})", wg_size_str, kernel_wg_size));
+            }
+        }
+    }
+
+    if errs.is_empty() {
+        Ok(())
+    } else {
+        OclError::err(format!("ocl::KernelCmd::enq: Invalid work dimensions (gws: {:?}, \
+            lws: {:?}): {}.", &gws[..dim_count], lws.map(|l| &l[..dim_count]), errs.join("; ")))
+    }
+}
+
+/// The default for `Kernel::cache_args` (see `Kernel::set_cache_args_default`).
+static CACHE_ARGS_DEFAULT: AtomicBool = AtomicBool::new(true);
+
+/// The value last passed to `clSetKernelArg` for an argument, used to skip
+/// redundant calls.
+///
+/// Memory objects and samplers are identified by handle. The handle can not
+/// be reused while cached because the kernel holds a reference to the memory
+/// object (see `Kernel::mem_args`) and the cache one to the sampler.
+#[derive(Debug)]
+enum CachedArg {
+    Mem(usize),
+    Sampler(SamplerCore),
+    Null,
+    Bytes(Vec<u8>),
+    Local(usize),
+}
+
+impl CachedArg {
+    /// Returns the raw bytes of a scalar or vector argument.
+    fn value_bytes<'a, T: OclPrm>(arg: &'a KernelArg<T>) -> Option<&'a [u8]> {
+        match *arg {
+            KernelArg::Scalar(ref scalar) => Some(unsafe {
+                slice::from_raw_parts(scalar as *const T as *const u8, mem::size_of::<T>())
+            }),
+            KernelArg::Vector(vector) => Some(unsafe {
+                slice::from_raw_parts(vector.as_ptr() as *const u8,
+                    vector.len() * mem::size_of::<T>())
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns true if setting `arg` would repeat the cached value.
+    fn matches<T: OclPrm>(&self, arg: &KernelArg<T>) -> bool {
+        match (self, arg) {
+            (&CachedArg::Mem(ptr), &KernelArg::Mem(mem)) => {
+                ptr == unsafe { mem.as_ptr() } as usize
+            },
+            (&CachedArg::Sampler(ref cached), &KernelArg::Sampler(sampler)) => unsafe {
+                cached.as_ptr() == sampler.as_ptr()
+            },
+            (&CachedArg::Null, &KernelArg::MemNull) |
+                (&CachedArg::Null, &KernelArg::SamplerNull) => true,
+            (&CachedArg::Bytes(ref bytes), _) => {
+                CachedArg::value_bytes(arg).map_or(false, |value| &bytes[..] == value)
+            },
+            (&CachedArg::Local(size), &KernelArg::Local(&len)) => {
+                size == len * mem::size_of::<T>()
+            },
+            _ => false,
+        }
+    }
+
+    /// Stores `arg` in `cached`, reusing its byte buffer where possible.
+    ///
+    /// Unsafe pointer arguments are never cached.
+    fn store<T: OclPrm>(cached: &mut Option<CachedArg>, arg: &KernelArg<T>) {
+        if let Some(value) = CachedArg::value_bytes(arg) {
+            if let Some(CachedArg::Bytes(ref mut bytes)) = *cached {
+                bytes.clear();
+                bytes.extend_from_slice(value);
+                return;
+            }
+        }
+
+        *cached = match *arg {
+            KernelArg::Mem(mem) => Some(CachedArg::Mem(unsafe { mem.as_ptr() } as usize)),
+            KernelArg::Sampler(sampler) => Some(CachedArg::Sampler(sampler.clone())),
+            KernelArg::MemNull | KernelArg::SamplerNull => Some(CachedArg::Null),
+            KernelArg::Scalar(_) | KernelArg::Vector(_) => {
+                CachedArg::value_bytes(arg).map(|value| CachedArg::Bytes(value.to_vec()))
+            },
+            KernelArg::Local(&len) => Some(CachedArg::Local(len * mem::size_of::<T>())),
+            KernelArg::UnsafePointer { .. } => None,
+        };
+    }
+}
+
+/// Returns `err` with the index of the failed launch within a batch of
+/// `count` launches added to its description.
+///
+/// `Status` errors remain `Status` errors.
+fn batch_launch_err(err: OclError, index: usize, count: usize) -> OclError {
+    match err {
+        OclError::Status { status, fn_name, fn_info, .. } => {
+            match OclError::err_status::<(), _>(status as i32, fn_name,
+                format!("{} batch_launch={}/{}", fn_info, index, count))
+            {
+                Err(err) => err,
+                Ok(_) => OclError::new(format!("ocl::KernelCmd::enq: Batch launch [{}] (of {}) \
+                    failed.", index, count)),
+            }
+        },
+        err => OclError::new(format!("ocl::KernelCmd::enq: Batch launch [{}] (of {}) failed: \
+            {}", index, count, err)),
+    }
+}
+
 /// A kernel command builder used to queue a kernel with a mix of default
 /// and optionally specified arguments.
 pub struct KernelCmd<'k> {
-    queue: &'k CommandQueueCore,
+    queue: Option<&'k Queue>,
+    default_queue: Option<&'k Queue>,
+    context: Option<&'k Context>,
     kernel: &'k KernelCore,
+    kernel_name: &'k str,
+    kernel_wg_sizes: &'k [(DeviceIdCore, usize)],
+    mem_args: &'k [Option<MemCore>],
     gwo: SpatialDims,
     gws: SpatialDims,
     lws: SpatialDims,
     wait_list: Option<&'k ClWaitList>,
     dest_list: Option<&'k mut ClEventPtrNew>,
+    validate_dims: bool,
+    task: bool,
+    batch: Option<&'k [WorkSizes]>,
+    batch_events: Option<&'k mut EventList>,
+    retain_mems: bool,
 }
 
 /// [UNSTABLE]: All methods still being tuned.
 impl<'k> KernelCmd<'k> {
-    /// Specifies a queue to use for this call only.
-    pub fn queue<Q: AsRef<CommandQueueCore>>(mut self, queue: &'k Q) -> KernelCmd<'k> {
-        self.queue = queue.as_ref();
+    /// Specifies a queue to use for this call only, overriding the default
+    /// queues of the kernel and its context (see the `Queue` docs).
+    ///
+    /// Work sizes are validated against the limits of the device associated
+    /// with this queue (see `::validate_dims`).
+    #[inline]
+    pub fn queue(mut self, queue: &'k Queue) -> KernelCmd<'k> {
+        self.queue = Some(queue);
         self
     }
 
     /// Specifies a global work offset for this call only.
+    #[inline]
      pub fn gwo<D: Into<SpatialDims>>(mut self, gwo: D) -> KernelCmd<'k> {
         self.gwo = gwo.into();
         self
     }
 
     /// Specifies a global work size for this call only.
+    #[inline]
     pub fn gws<D: Into<SpatialDims>>(mut self, gws: D) -> KernelCmd<'k> {
         self.gws = gws.into();
         self
     }
 
     /// Specifies a local work size for this call only.
+    #[inline]
     pub fn lws<D: Into<SpatialDims>>(mut self, lws: D) -> KernelCmd<'k> {
         self.lws = lws.into();
         self
     }
 
+    /// Specifies whether or not the work sizes will be validated against the
+    /// limits of the queue's device and the kernel before enqueuing, for this
+    /// call only.
+    ///
+    /// Defaults to the kernel's setting (see `Kernel::validate_dims`).
+    pub fn validate_dims(mut self, validate: bool) -> KernelCmd<'k> {
+        self.validate_dims = validate;
+        self
+    }
+
+    /// Specifies that the kernel will be enqueued as a task, executing a
+    /// single work-item, for this call only.
+    ///
+    /// Any global work offset and global or local work sizes are ignored. On
+    /// devices supporting OpenCL 2.0 or later, where `clEnqueueTask` is
+    /// deprecated, an NDRange launch of one work-item is used instead.
+    pub fn task(mut self) -> KernelCmd<'k> {
+        self.task = true;
+        self
+    }
+
+    /// Specifies that the kernel will be enqueued once for each set of work
+    /// dimensions in `launches`, in order, for this call only.
+    ///
+    /// Any global work offset and global or local work sizes are ignored.
+    /// Launches may use differing numbers of dimensions. The wait list (see
+    /// `::ewait`) applies to every launch and a new event (see `::enew`) is
+    /// associated with the last launch. Use `::enew_batch` to collect the
+    /// event of every launch instead.
+    ///
+    /// See `::enq` for details about errors.
+    pub fn batch(mut self, launches: &'k [WorkSizes]) -> KernelCmd<'k> {
+        self.batch = Some(launches);
+        self
+    }
+
+    /// Specifies a list to which the event of each launch of a batch (see
+    /// `::batch`) will be appended.
+    ///
+    /// May not be used along with `::enew`.
+    pub fn enew_batch(mut self, events: &'k mut EventList) -> KernelCmd<'k> {
+        self.batch_events = Some(events);
+        self
+    }
+
+    /// Specifies whether or not the memory objects set as arguments are kept
+    /// alive until the command (every launch, for a batch) completes, for
+    /// this call only.
+    ///
+    /// Not all implementations retain the arguments of in-flight commands
+    /// internally, so dropping the kernel and the last reference to a buffer
+    /// it uses right after enqueuing can otherwise release the buffer too
+    /// early. A marker is enqueued after the command to do so (see
+    /// `Queue::retain_until_complete`). Disabled by default.
+    pub fn retain_mems(mut self, retain: bool) -> KernelCmd<'k> {
+        self.retain_mems = retain;
+        self
+    }
+
     /// Specifies the list of events to wait on before the command will run.
+    #[inline]
     pub fn ewait(mut self, wait_list: &'k ClWaitList) -> KernelCmd<'k> {
         self.wait_list = Some(wait_list);
         self
     }
 
     /// Specifies a list of events to wait on before the command will run.
+    #[inline]
     pub fn ewait_opt(mut self, wait_list: Option<&'k ClWaitList>) -> KernelCmd<'k> {
         self.wait_list = wait_list;
         self
@@ -65,6 +329,7 @@ impl<'k> KernelCmd<'k> {
 
     /// Specifies the destination list or empty event for a new, optionally 
     /// created event associated with this command.
+    #[inline]
     pub fn enew(mut self, new_event_dest: &'k mut ClEventPtrNew) -> KernelCmd<'k> {
         self.dest_list = Some(new_event_dest);
         self
@@ -72,28 +337,194 @@ impl<'k> KernelCmd<'k> {
 
     /// Specifies a destination list for a new, optionally created event
     /// associated with this command.
+    #[inline]
     pub fn enew_opt(mut self, new_event_list: Option<&'k mut ClEventPtrNew>) -> KernelCmd<'k> {
         self.dest_list = new_event_list;
         self
     }
 
     /// Enqueues this kernel command.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if no queue has been specified for the command and
+    /// neither the kernel nor its context has a default queue.
+    ///
+    /// Unless enqueuing as a task (see `::task`), a global work size must
+    /// have been specified, either as a default for
+    /// the kernel or with `::gws`, otherwise a `DimsUnspecified` error is
+    /// returned.
+    ///
+    /// If dimension validation is enabled, any work size exceeding the limits
+    /// of the queue's device (`DeviceInfo::MaxWorkItemSizes` and
+    /// `DeviceInfo::MaxWorkGroupSize`) or of the kernel
+    /// (`KernelWorkGroupInfo::WorkGroupSize`) will cause an error listing
+    /// each offending component and the limit it exceeds.
+    ///
+    /// ### Batches
+    ///
+    /// The work dimensions of every launch in a batch (see `::batch`) are
+    /// validated before any is enqueued, so an invalid launch causes nothing
+    /// to be enqueued. Otherwise, enqueuing stops at the first launch which
+    /// fails, leaving earlier launches enqueued. In either case the error
+    /// identifies the index of the failed launch. An empty batch enqueues
+    /// nothing (and is an error if a new event was requested with `::enew`).
     pub fn enq(self) -> OclResult<()> {
-        let dim_count = self.gws.dim_count();
+        let queue = try!(resolve_queue("ocl::KernelCmd::enq", self.queue, self.default_queue,
+            self.context));
+
+        if self.retain_mems {
+            let mems: Vec<MemCore> = self.mem_args.iter().filter_map(|m| m.clone()).collect();
+            try!(self.enq_on(&queue));
+            return queue.retain_until_complete(mems);
+        }
+
+        self.enq_on(&queue)
+    }
+
+    /// Enqueues this kernel command on `queue`.
+    fn enq_on(self, queue: &Queue) -> OclResult<()> {
+        if self.task {
+            if self.batch.is_some() {
+                return OclError::err("ocl::KernelCmd::enq: A command may not be both a task \
+                    and a batch.");
+            }
+            return self.enq_task(queue);
+        }
+
+        if let Some(launches) = self.batch {
+            return self.enq_batch(queue, launches);
+        }
+
+        if self.batch_events.is_some() {
+            return OclError::err("ocl::KernelCmd::enq: '::enew_batch' may only be used along \
+                with '::batch'.");
+        }
 
         let gws = match self.gws.to_work_size() {
             Some(gws) => gws,
-            None => return OclError::err("ocl::KernelCmd::enqueue: Global Work Size ('gws') \
-                cannot be left unspecified. Set a default for the kernel or pass a valid parameter."),
+            None => return Err(OclError::DimsUnspecified {
+                op: "ocl::KernelCmd::enq (global work size)",
+                desc: format!("ocl::KernelCmd::enq: No global work size has been specified for \
+                    kernel '{}'. Set a default with 'Kernel::gws' or 'Kernel::set_default_gws' \
+                    (or set dimensions on the 'ProQue' used to create it) or specify one for \
+                    this call with 'KernelCmd::gws'.", self.kernel_name),
+            }),
         };
+        let lws = self.lws.to_work_size();
+        let gwo = self.gwo.to_work_offset();
+
+        let dims = try!(WorkSizes::new(&gws[..self.gws.dim_count() as usize],
+            lws.as_ref().map(|lws| &lws[..self.lws.dim_count() as usize]),
+            gwo.as_ref().map(|gwo| &gwo[..self.gwo.dim_count() as usize])));
+
+        if self.validate_dims {
+            let kernel_wg_size = try!(self.kernel_wg_size(queue,
+                dims.local_work_size().is_some()));
+
+            try!(check_work_dims(&dims, queue.device_max_wi_sizes(),
+                queue.device_max_wg_size(), kernel_wg_size, queue.device_version()));
+        }
+
+        if PRINT_DEBUG { 
+            println!("Enqueuing kernel: '{}'...", self.kernel_name);
+        }
+
+        core::enqueue_kernel_dims(queue, self.kernel, &dims, self.wait_list, self.dest_list)
+    }
+
+    /// Returns the maximum work group size for the kernel on the queue's
+    /// device if `lws_specified` is true (it is only needed to validate a
+    /// local work size).
+    ///
+    /// The size recorded upon kernel creation is used unless the queue's
+    /// device is not associated with the kernel's program.
+    fn kernel_wg_size(&self, queue: &Queue, lws_specified: bool) -> OclResult<Option<usize>> {
+        if !lws_specified { return Ok(None); }
+
+        let device = queue.device().as_core();
+
+        if let Some(&(_, size)) = self.kernel_wg_sizes.iter().find(|&&(ref d, _)| d == device) {
+            return Ok(Some(size));
+        }
+
+        match core::get_kernel_work_group_info(self.kernel, queue.device(),
+                KernelWorkGroupInfo::WorkGroupSize) {
+            KernelWorkGroupInfoResult::WorkGroupSize(s) => Ok(Some(s)),
+            KernelWorkGroupInfoResult::Error(err) => Err(*err),
+            _ => Ok(None),
+        }
+    }
+
+    /// Enqueues this kernel command once for each of `launches`.
+    fn enq_batch(self, queue: &Queue, launches: &[WorkSizes]) -> OclResult<()> {
+        if self.dest_list.is_some() && self.batch_events.is_some() {
+            return OclError::err("ocl::KernelCmd::enq: '::enew' and '::enew_batch' may not \
+                both be used.");
+        }
+
+        let count = launches.len();
+
+        if count == 0 {
+            return match self.dest_list {
+                Some(_) => OclError::err("ocl::KernelCmd::enq: Unable to create a new event \
+                    for an empty batch."),
+                None => Ok(()),
+            };
+        }
+
+        if self.validate_dims {
+            let kernel_wg_size = try!(self.kernel_wg_size(queue, launches.iter()
+                .any(|dims| dims.local_work_size().is_some())));
+
+            for (i, dims) in launches.iter().enumerate() {
+                try!(check_work_dims(dims, queue.device_max_wi_sizes(),
+                    queue.device_max_wg_size(), kernel_wg_size, queue.device_version())
+                    .map_err(|err| batch_launch_err(err, i, count)));
+            }
+        }
+
+        if PRINT_DEBUG { 
+            println!("Enqueuing kernel batch ({} launches): '{}'...", count, self.kernel_name);
+        }
+
+        match self.batch_events {
+            Some(events) => {
+                for (i, dims) in launches.iter().enumerate() {
+                    try!(core::enqueue_kernel_dims(queue, self.kernel, dims, self.wait_list,
+                        Some(&mut *events)).map_err(|err| batch_launch_err(err, i, count)));
+                }
+            },
+            None => {
+                let last = count - 1;
+
+                for (i, dims) in launches[..last].iter().enumerate() {
+                    try!(core::enqueue_kernel_dims(queue, self.kernel, dims, self.wait_list,
+                        None).map_err(|err| batch_launch_err(err, i, count)));
+                }
+
+                try!(core::enqueue_kernel_dims(queue, self.kernel, &launches[last],
+                    self.wait_list, self.dest_list)
+                    .map_err(|err| batch_launch_err(err, last, count)));
+            },
+        }
+
+        Ok(())
+    }
 
+    /// Enqueues this kernel command as a single work-item task.
+    fn enq_task(self, queue: &Queue) -> OclResult<()> {
         if PRINT_DEBUG { 
-            println!("Enqueuing kernel: '{}'...", 
-                core::get_kernel_info(self.kernel, KernelInfo::FunctionName));
+            println!("Enqueuing kernel task: '{}'...", self.kernel_name);
         }
 
-        core::enqueue_kernel(self.queue, self.kernel, dim_count, self.gwo.to_work_offset(), 
-            &gws, self.lws.to_work_size(), self.wait_list, self.dest_list)
+        if queue.device_version() >= (2, 0) {
+            let dims = try!(WorkSizes::new(&[1, 1, 1], Some(&[1, 1, 1]), None));
+            core::enqueue_kernel_dims(queue, self.kernel, &dims, self.wait_list,
+                self.dest_list)
+        } else {
+            core::enqueue_task(queue, self.kernel, self.wait_list, self.dest_list)
+        }
     }
 }
 
@@ -110,21 +541,81 @@ impl<'k> KernelCmd<'k> {
 ///
 /// ## Thread Safety
 ///
-/// Now handled automatically.
+/// `Send` but not `Sync`: a kernel may be moved to another thread but not
+/// shared, since `clSetKernelArg` may not be called concurrently on the same
+/// kernel object. Create an identical kernel for each thread instead or use
+/// `::into_shared`. The underlying `core::Kernel` is therefore only
+/// reachable through the `unsafe` `::core_as_ref`.
+///
+/// ```compile_fail
+/// # fn f(kernel: ocl::Kernel) {
+/// let copy: ocl::core::Kernel = (*kernel).clone();
+/// # }
+/// ```
+///
+/// ## Work Size Validation
+///
+/// When enabled (the default for debug builds), work sizes are checked
+/// against the limits of the queue's device and of the kernel itself before
+/// each enqueue. Toggle with `::validate_dims`.
+///
+/// ## Metadata
+///
+/// The function name, number of arguments, and maximum work group size on
+/// each device associated with the program are queried once, upon creation,
+/// so that enqueuing makes no calls to the driver other than the enqueue
+/// itself.
+///
+/// ## Double-Precision Verification
+///
+/// When enabled with `::verify_fp64`, binding a double-precision argument
+/// (an `f64` or `ClDouble*` scalar or vector, or a buffer of either) on a
+/// device which does not support `cl_khr_fp64` returns an error describing
+/// the problem rather than failing later during the build or at run time.
+/// Disabled by default.
+///
+/// ## Argument Caching
+///
+/// The last value set for each argument is recorded and setting the same
+/// value again (an identical scalar or vector, or the same buffer, image, or
+/// sampler) does not call the driver. This makes it cheap to set every
+/// argument before each enqueue even when only one has changed. Toggle with
+/// `::cache_args` or, for new kernels, `Kernel::set_cache_args_default`.
+///
+/// Arguments set without going through this `Kernel` (such as with
+/// `core::set_kernel_arg` on the `core::Kernel` it dereferences to) are not
+/// seen by the cache. Use `::set_arg_force` or `::clear_arg_cache` after
+/// doing so. Each new `Kernel` (including those created after rebuilding a
+/// program) starts with an empty cache.
+///
+/// ## Panics
+///
+/// The builder-style argument methods (`::arg_buf`, `::arg_scl`, etc.) panic
+/// if the argument can not be set. This only happens when the arguments do
+/// not match the kernel's signature (too many arguments or a mismatched
+/// size), which is a programmer error. Driver errors during creation and
+/// enqueuing are returned as errors.
 ///
 /// TODO: Add more details, examples, etc.
-/// TODO: Add information about panics and errors.
 /// TODO: Finish arg info formatting.
 #[derive(Debug)]
 pub struct Kernel {
     obj_core: KernelCore,
+    name: String,
+    num_args: u32,
+    wg_sizes: Vec<(DeviceIdCore, usize)>,
     named_args: HashMap<&'static str, u32>,
     mem_args: Vec<Option<MemCore>>,
+    arg_cache: Vec<Option<CachedArg>>,
     arg_count: u32,
-    queue: Queue,
+    queue: Option<Queue>,
+    context: Option<Context>,
     gwo: SpatialDims,
     gws: SpatialDims,
     lws: SpatialDims,
+    validate_dims: bool,
+    verify_fp64: bool,
+    cache_args: bool,
 }
 
 // ######### IMPLEMENT THIS #########
@@ -149,21 +640,75 @@ impl Kernel {
     pub fn new<S: Into<String>, >(name: S, program: &Program, queue: &Queue, 
             ) -> OclResult<Kernel>
     {
-        let name = name.into();
+        Kernel::create(name.into(), program, Some(queue), None)
+    }
+
+    /// Returns a new kernel without a default queue of its own.
+    ///
+    /// Commands are enqueued on the default queue of `context` unless
+    /// another is specified (see the `Queue` docs).
+    pub fn new_in_context<S: Into<String>>(name: S, program: &Program, context: &Context)
+            -> OclResult<Kernel>
+    {
+        Kernel::create(name.into(), program, None, Some(context))
+    }
+
+    /// Returns a new kernel using `queue` as its default queue or, if
+    /// `None`, the default queue of `context`.
+    fn create(name: String, program: &Program, queue: Option<&Queue>,
+            context: Option<&Context>) -> OclResult<Kernel>
+    {
         let obj_core = try!(core::create_kernel(program, &name));
 
+        let num_args = match core::get_kernel_info(&obj_core, KernelInfo::NumArgs) {
+            KernelInfoResult::NumArgs(num) => num,
+            KernelInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::Kernel::new: Unexpected 'KernelInfoResult' variant."),
+        };
+
+        let mut wg_sizes = Vec::with_capacity(program.devices().len());
+
+        for device in program.devices() {
+            match core::get_kernel_work_group_info(&obj_core, device,
+                    KernelWorkGroupInfo::WorkGroupSize) {
+                KernelWorkGroupInfoResult::WorkGroupSize(size) => {
+                    wg_sizes.push((device.as_core().clone(), size))
+                },
+                KernelWorkGroupInfoResult::Error(err) => return Err(*err),
+                _ => (),
+            }
+        }
+
         Ok(Kernel {
             obj_core: obj_core,
+            name: name,
+            num_args: num_args,
+            wg_sizes: wg_sizes,
             named_args: HashMap::with_capacity(5),
             arg_count: 0,
             mem_args: Vec::with_capacity(16),
-            queue: queue.clone(),
+            arg_cache: Vec::with_capacity(16),
+            queue: queue.cloned(),
+            context: context.cloned(),
             gwo: SpatialDims::Unspecified,
             gws: SpatialDims::Unspecified,
             lws: SpatialDims::Unspecified,
+            validate_dims: cfg!(debug_assertions),
+            verify_fp64: false,
+            cache_args: CACHE_ARGS_DEFAULT.load(Ordering::Relaxed),
         })
     }
 
+    /// Sets whether or not kernels created after this call will skip
+    /// setting arguments to the value they already have (see
+    /// `::cache_args`).
+    ///
+    /// Enabled by default. Applies process-wide and does not affect
+    /// existing kernels.
+    pub fn set_cache_args_default(cache: bool) {
+        CACHE_ARGS_DEFAULT.store(cache, Ordering::Relaxed);
+    }
+
     /// Sets the default global work offset (builder-style).
     ///
     /// Used when enqueuing kernel commands. Superseded if specified while
@@ -191,6 +736,57 @@ impl Kernel {
         self
     }
 
+    /// Sets whether or not work sizes will be validated against device and
+    /// kernel limits before enqueuing (builder-style).
+    ///
+    /// Enabled by default for debug builds and disabled for release builds.
+    /// Validation costs one kernel info query per enqueue when a local work
+    /// size is specified.
+    pub fn validate_dims(mut self, validate: bool) -> Kernel {
+        self.validate_dims = validate;
+        self
+    }
+
+    /// Sets whether or not double-precision arguments will be checked against
+    /// the extensions supported by the queue's device as they are bound
+    /// (builder-style).
+    ///
+    /// Only affects arguments bound after this is called. Disabled by
+    /// default. The device extensions are cached by the queue so the check
+    /// is cheap.
+    pub fn verify_fp64(mut self, verify: bool) -> Kernel {
+        self.verify_fp64 = verify;
+        self
+    }
+
+    /// Sets whether or not setting an argument to the value it already has
+    /// will be skipped rather than passed to the driver (builder-style).
+    ///
+    /// Enabled by default unless changed with
+    /// `Kernel::set_cache_args_default`. See the type-level documentation
+    /// for details.
+    pub fn cache_args(mut self, cache: bool) -> Kernel {
+        self.set_cache_args(cache);
+        self
+    }
+
+    /// Sets whether or not setting an argument to the value it already has
+    /// will be skipped rather than passed to the driver.
+    ///
+    /// Disabling the cache also clears it.
+    pub fn set_cache_args(&mut self, cache: bool) {
+        self.cache_args = cache;
+        if !cache { self.clear_arg_cache(); }
+    }
+
+    /// Forgets the last value set for every argument so that each is passed
+    /// to the driver the next time it is set.
+    pub fn clear_arg_cache(&mut self) {
+        for cached in self.arg_cache.iter_mut() {
+            *cached = None;
+        }
+    }
+
     /// Adds a new argument to the kernel specifying the buffer object represented
     /// by 'buffer' (builder-style). Argument is added to the bottom of the argument 
     /// order.
@@ -199,6 +795,27 @@ impl Kernel {
         self
     }
 
+    /// Adds two new arguments to the kernel specifying the elements of the
+    /// buffer within `view` (builder-style): a buffer followed by a `uint`
+    /// offset, in elements, to be added to each index. Arguments are added
+    /// to the bottom of the argument order.
+    ///
+    /// A sub-buffer covering only the view is passed, along with an offset
+    /// of zero, if the start of the view is aligned (see
+    /// `BufferView::is_aligned`). Otherwise the whole buffer is passed along
+    /// with the offset of the view. Either way, the kernel accesses element
+    /// `i` of the view as `buf[buf_offset + i]`:
+    ///
+    /// ```text
+    /// __kernel void add(__global float* buf, uint buf_offset, float val) {
+    ///     buf[buf_offset + get_global_id(0)] += val;
+    /// }
+    /// ```
+    pub fn arg_view<T: OclPrm>(mut self, view: &BufferView<T>) -> Kernel {
+        self.new_arg_view(view);
+        self
+    }
+
     /// Adds a new argument to the kernel specifying the image object represented
     /// by 'image' (builder-style). Argument is added to the bottom of the argument 
     /// order.
@@ -239,6 +856,50 @@ impl Kernel {
         self
     }
 
+    /// Adds a new argument specifying `val`, which may be any of the kinds
+    /// of value accepted by the methods above (builder-style). Argument is
+    /// added to the bottom of the argument order.
+    ///
+    /// Accepts (see `ArgVal`):
+    ///
+    /// * `&Buffer<T>`, `&Image<P>`, and `&Sampler`, or `None` within an
+    ///   `Option` of any of them for a null argument.
+    /// * Scalars and vector types (`f32`, `ClFloat4`, etc.) by value.
+    /// * `Local::<T>::new(len)` for a local variable of `len` elements.
+    ///
+    /// Slices and `Vec`s are not accepted: pass the components of a vector
+    /// type with `::arg_vec` (or pass a value such as `ClFloat4`) and
+    /// arrays within a `Buffer`.
+    ///
+    /// ```rust,no_run
+    /// # use ocl::{ProQue, Local};
+    /// # use ocl::vectors::ClFloat4;
+    /// # fn main() { example().unwrap(); }
+    /// # fn example() -> ocl::Result<()> {
+    /// # let pro_que = try!(ProQue::builder().src("").dims([64]).build());
+    /// let buffer = try!(pro_que.create_buffer::<f32>());
+    ///
+    /// let kernel = try!(pro_que.create_kernel("scale")).gws([64])
+    ///     .arg(&buffer)
+    ///     .arg(3.5f32)
+    ///     .arg(ClFloat4([1.0, 2.0, 3.0, 4.0]))
+    ///     .arg(Local::<f32>::new(16))
+    ///     .arg(None::<&ocl::Buffer<f32>>);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// ```rust,compile_fail
+    /// # use ocl::ProQue;
+    /// # let pro_que = ProQue::builder().src("").build().unwrap();
+    /// let values = vec![1.0f32, 2.0];
+    /// // Use `::arg_vec` or a `Buffer`:
+    /// let kernel = pro_que.create_kernel("scale").unwrap().arg(&values);
+    /// ```
+    pub fn arg<A: ArgVal>(mut self, val: A) -> Kernel {
+        self.new_arg_val(val);
+        self
+    }
+
     /// Adds a new named argument (in order) specifying the value: `scalar` 
     /// (builder-style).
     ///
@@ -289,6 +950,17 @@ impl Kernel {
         self
     }    
 
+    /// Adds a new named argument specifying `val` (builder-style). Argument
+    /// is added to the bottom of the argument order.
+    ///
+    /// Accepts the same values as `::arg`. Named arguments can be easily
+    /// modified later using the `::set_arg_..._named` methods.
+    pub fn arg_named<A: ArgVal>(mut self, name: &'static str, val: A) -> Kernel {
+        let arg_idx = self.new_arg_val(val);
+        self.named_args.insert(name, arg_idx);
+        self
+    }
+
     /// Modifies the kernel argument named: `name`.
     ///
     /// ## Panics [FIXME]
@@ -324,6 +996,7 @@ impl Kernel {
         let arg_idx = try!(self.resolve_named_arg_idx(name));
         match buffer_opt {
             Some(buffer) => {
+                buffer.mark_init();
                 self.set_arg::<T>(arg_idx, KernelArg::Mem(buffer))
             },
             None => {
@@ -351,6 +1024,21 @@ impl Kernel {
         }.and(Ok(self))
     }
 
+    /// Modifies the kernel argument named: `name`, passing it to the driver
+    /// even if it has not changed since it was last set (see `::cache_args`).
+    ///
+    /// Use after modifying the argument by other means (such as with
+    /// `core::set_kernel_arg`). Buffers passed with `KernelArg::Mem` are not
+    /// marked as initialized (see `::set_arg_buf_named`).
+    pub fn set_arg_force<'a, T: OclPrm>(&'a mut self, name: &'static str, arg: KernelArg<T>)
+            -> OclResult<&'a mut Kernel>
+    {
+        let arg_idx = try!(self.resolve_named_arg_idx(name));
+        self.arg_cache[arg_idx as usize] = None;
+        self.set_arg::<T>(arg_idx, arg)
+            .and(Ok(self))
+    }
+
     /// Sets the value of a named sampler argument.
     ///
     /// ## Panics [FIXME]
@@ -362,18 +1050,40 @@ impl Kernel {
         unimplemented!();
     }
 
+    /// Returns a `SharedKernel` which may be shared among threads, setting
+    /// arguments and enqueuing behind a lock.
+    ///
+    /// See the `SharedKernel` docs for the performance implications.
+    pub fn into_shared(self) -> SharedKernel {
+        SharedKernel::new(self)
+    }
+
     /// Returns a command builder which is used to chain parameters of an
     /// 'enqueue' command together.
+    #[inline]
     pub fn cmd<'k>(&'k self) -> KernelCmd<'k> {
-        KernelCmd { queue: &self.queue, kernel: &self.obj_core, 
-            gwo: self.gwo.clone(), gws: self.gws.clone(), lws: self.lws.clone(), 
-            wait_list: None, dest_list: None }
+        KernelCmd { queue: None, default_queue: self.queue.as_ref(),
+            context: self.context.as_ref(), kernel: &self.obj_core, kernel_name: &self.name,
+            kernel_wg_sizes: &self.wg_sizes, gwo: self.gwo.clone(), gws: self.gws.clone(), lws: self.lws.clone(), 
+            wait_list: None, dest_list: None, validate_dims: self.validate_dims,
+            task: false, batch: None, batch_events: None, mem_args: &self.mem_args,
+            retain_mems: false }
     }
 
-    /// Enqueues this kernel on the default queue and returns the result.
+    /// Enqueues this kernel on the default queue using the default work
+    /// offset and sizes and returns the result.
+    ///
+    /// Shorthand for `.cmd().enq()`. The defaults in effect when this is
+    /// called are used (see `::set_default_gws`, etc.).
     ///
-    /// Shorthand for `.cmd().enq()`
+    /// ## Errors
     ///
+    /// Returns a `DimsUnspecified` error if no default global work size has
+    /// been set (with `::gws`, `::set_default_gws`, or by creating the kernel
+    /// with `ProQue::create_kernel` after setting the `ProQue` dimensions).
+    /// See `KernelCmd::enq` for other errors.
+    ///
+    #[inline]
     pub fn enq<'k>(&'k self) -> OclResult<()> {
         // core::enqueue_kernel::<EventList>(&self.queue, &self.obj_core,
         //     self.gws.dim_count(), self.gwo.to_work_offset(), &self.gws.to_lens().unwrap(), self.lws.to_work_size(), 
@@ -381,6 +1091,37 @@ impl Kernel {
         self.cmd().enq()
     }
 
+    /// Enqueues this kernel on the default queue using the default work
+    /// offset and sizes and returns an event which completes along with it.
+    ///
+    /// Shorthand for `.cmd().enew(&mut event).enq()`. See `::enq`.
+    ///
+    pub fn enq_event(&self) -> OclResult<Event> {
+        let mut event = Event::empty();
+        try!(self.cmd().enew(&mut event).enq());
+        Ok(event)
+    }
+
+    /// Enqueues this kernel on the default queue once for each set of work
+    /// dimensions in `launches` and returns the result.
+    ///
+    /// Shorthand for `.cmd().batch(launches).enq()`. See `KernelCmd::batch`.
+    ///
+    #[inline]
+    pub fn enq_batch<'k>(&'k self, launches: &'k [WorkSizes]) -> OclResult<()> {
+        self.cmd().batch(launches).enq()
+    }
+
+    /// Enqueues this kernel on the default queue as a task, executing a
+    /// single work-item, and returns the result.
+    ///
+    /// Shorthand for `.cmd().task().enq()`
+    ///
+    #[inline]
+    pub fn enq_task<'k>(&'k self) -> OclResult<()> {
+        self.cmd().task().enq()
+    }
+
     /// Changes the default queue.
     ///
     /// Returns a ref for chaining i.e.:
@@ -399,13 +1140,69 @@ impl Kernel {
     ///
     pub fn set_default_queue<'a>(&'a mut self, queue: &Queue) -> OclResult<&'a mut Kernel> {
         // self.command_queue_obj_core = queue.core_as_ref().clone();
-        self.queue = queue.clone();
+        self.queue = Some(queue.clone());
         Ok(self)
     }
 
-    /// Returns the default `core::CommandQueue` for this kernel.
-    pub fn default_queue(&self) -> &Queue {
-        &self.queue
+    /// Changes the default global work offset.
+    ///
+    /// Used by subsequent calls to enqueue (see `::gwo`).
+    pub fn set_default_gwo<D: Into<SpatialDims>>(&mut self, gwo: D) {
+        self.gwo = gwo.into();
+    }
+
+    /// Changes the default global work size.
+    ///
+    /// Used by subsequent calls to enqueue (see `::gws`).
+    pub fn set_default_gws<D: Into<SpatialDims>>(&mut self, gws: D) {
+        self.gws = gws.into();
+    }
+
+    /// Changes the default local work size.
+    ///
+    /// Used by subsequent calls to enqueue (see `::lws`).
+    pub fn set_default_lws<D: Into<SpatialDims>>(&mut self, lws: D) {
+        self.lws = lws.into();
+    }
+
+    /// Returns the default queue for this kernel, or `None` if it was
+    /// created with `::new_in_context` and none has been set since.
+    pub fn default_queue(&self) -> Option<&Queue> {
+        self.queue.as_ref()
+    }
+
+    /// Returns the queue used by commands which do not specify one: the
+    /// default queue of the kernel or, failing that, that of its context
+    /// (see the `Queue` docs).
+    pub fn resolve_queue(&self) -> OclResult<Queue> {
+        resolve_queue("ocl::Kernel::resolve_queue", None, self.queue.as_ref(),
+            self.context.as_ref()).map(|queue| queue.into_owned())
+    }
+
+    /// Returns the device on which this kernel would run when enqueued
+    /// without specifying a queue, or the first device of its context if
+    /// no queue is available.
+    fn default_device(&self) -> Option<Device> {
+        match self.resolve_queue() {
+            Ok(queue) => Some(*queue.device()),
+            Err(_) => self.context.as_ref().and_then(|c| c.devices().first().cloned()),
+        }
+    }
+
+    /// Returns the first device this kernel may be enqueued on which does
+    /// not support `extension`: the device of the default queue or, if
+    /// none is available, any device of the kernel's context.
+    fn device_lacking(&self, extension: &str) -> Option<Device> {
+        match self.resolve_queue() {
+            Ok(queue) => {
+                if queue.device_has_extension(extension) { None } else { Some(*queue.device()) }
+            },
+            Err(_) => self.context.as_ref().and_then(|context| {
+                context.devices().iter()
+                    .find(|d| !d.has_extension(extension).unwrap_or(false))
+                    .cloned()
+            }),
+        }
     }
 
     /// Returns the default global work offset.
@@ -431,7 +1228,15 @@ impl Kernel {
 
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
-    pub fn core_as_ref(&self) -> &KernelCore {
+    ///
+    /// ## Safety
+    ///
+    /// The returned `core::Kernel` must not be cloned (nor its pointer
+    /// otherwise retained) and then used to set arguments on another thread
+    /// than the one this kernel is used from: `Kernel` is `Send` on the basis
+    /// that `clSetKernelArg` is never called concurrently on its kernel
+    /// object.
+    pub unsafe fn core_as_ref(&self) -> &KernelCore {
         &self.obj_core
     }
 
@@ -464,13 +1269,28 @@ impl Kernel {
         core::get_kernel_work_group_info(&self.obj_core, device, info_kind)
     }
 
+    /// Returns the name of the kernel function (cached).
     pub fn name(&self) -> String {
-        core::get_kernel_info(&self.obj_core, KernelInfo::FunctionName).into()
+        self.name.clone()
+    }
+
+    /// Returns the number of arguments declared by the kernel function (as
+    /// opposed to `::arg_count`, the number specified so far) (cached).
+    pub fn num_args(&self) -> OclResult<u32> {
+        Ok(self.num_args)
+    }
+
+    /// Returns the maximum work group size of the kernel on `device` as
+    /// recorded upon creation, or `None` if `device` is not associated with
+    /// the kernel's program.
+    pub fn max_wg_size(&self, device: &Device) -> Option<usize> {
+        self.wg_sizes.iter().find(|&&(ref d, _)| d == device.as_core()).map(|&(_, size)| size)
     }
 
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Kernel")
             .field("FunctionName", &self.info(KernelInfo::FunctionName))
+            .field("NumArgs", &self.info(KernelInfo::NumArgs))
             .field("ReferenceCount", &self.info(KernelInfo::ReferenceCount))
             .field("Context", &self.info(KernelInfo::Context))
             .field("Program", &self.info(KernelInfo::Program))
@@ -515,6 +1335,7 @@ impl Kernel {
     fn new_arg_buf<T: OclPrm>(&mut self, buffer_opt: Option<&Buffer<T>>) -> u32 {        
         match buffer_opt {
             Some(buffer) => {
+                buffer.mark_init();
                 self.new_arg::<T>(KernelArg::Mem(buffer))
             },
             None => {
@@ -523,6 +1344,16 @@ impl Kernel {
         }
     }
 
+    /// Non-builder-style version of `::arg_view()`. Returns the index of the
+    /// buffer argument.
+    fn new_arg_view<T: OclPrm>(&mut self, view: &BufferView<T>) -> u32 {
+        let (mem, offset) = view.kernel_args().expect("Kernel::arg_view()");
+        view.buffer().mark_init();
+        let arg_idx = self.new_arg::<T>(KernelArg::Mem(&mem));
+        self.new_arg::<u32>(KernelArg::Scalar(offset));
+        arg_idx
+    }
+
     /// Non-builder-style version of `::arg_img()`.
     fn new_arg_img<P: OclPrm>(&mut self, image_opt: Option<&Image<P>>) -> u32 {        
         match image_opt {
@@ -574,12 +1405,19 @@ impl Kernel {
 
     /// Adds a new argument to the kernel and returns the index.
     fn new_arg<T: OclPrm>(&mut self, arg: KernelArg<T>) -> u32 {
+        self.new_arg_val(arg)
+    }
+
+    /// Non-builder-style version of `::arg()`.
+    fn new_arg_val<A: ArgVal>(&mut self, val: A) -> u32 {
         let arg_idx = self.arg_count;        
 
-        // Push an empty `mem_arg` to the list just to make room.
+        // Push an empty `mem_arg` (and cache entry) to the list just to make
+        // room.
         self.mem_args.push(None);
+        self.arg_cache.push(None);
 
-        self.set_arg(arg_idx, arg).expect("Kernel::new_arg()");        
+        val.set_on(self, arg_idx).expect("Kernel::new_arg()");        
 
         self.arg_count += 1;
         debug_assert!(self.arg_count as usize == self.mem_args.len());
@@ -588,6 +1426,44 @@ impl Kernel {
 
     /// Sets an argument.
     fn set_arg<T: OclPrm>(&mut self, arg_idx: u32, arg: KernelArg<T>) -> OclResult<()> {
+        // Half-precision values may only be passed directly to kernels on
+        // devices supporting `cl_khr_fp16` (memory objects are unaffected).
+        match arg {
+            KernelArg::Scalar(_) | KernelArg::Vector(_) if standard::is_f16::<T>() => {
+                if let Some(device) = self.device_lacking("cl_khr_fp16") {
+                    return OclError::err(format!("ocl::Kernel::set_arg: Unable to set \
+                        argument [{}] of kernel '{}': half-precision ('f16') kernel arguments \
+                        require the 'cl_khr_fp16' extension which is not supported by the \
+                        device ('{}').", arg_idx, self.name(), device.name()));
+                }
+            },
+            _ => (),
+        }
+
+        if self.verify_fp64 && standard::is_f64::<T>() {
+            match arg {
+                KernelArg::Scalar(_) | KernelArg::Vector(_) | KernelArg::Mem(_) |
+                        KernelArg::MemNull => {
+                    if let Some(device) = self.device_lacking("cl_khr_fp64") {
+                        return OclError::err(format!("ocl::Kernel::set_arg: Unable to set \
+                            argument [{}] of kernel '{}': double-precision ('f64') values \
+                            require the 'cl_khr_fp64' extension which is not supported by the \
+                            device ('{}'). Use single-precision ('f32') values instead, \
+                            optionally building the program with \
+                            '-cl-fp32-correctly-rounded-divide-sqrt' for improved accuracy.",
+                            arg_idx, self.name(), device.name()));
+                    }
+                },
+                _ => (),
+            }
+        }
+
+        let cached = &mut self.arg_cache[arg_idx as usize];
+
+        if self.cache_args && cached.as_ref().map_or(false, |c| c.matches(&arg)) {
+            return Ok(());
+        }
+
         // If the `KernelArg` is a `Mem` variant, clone the `MemCore` it
         // refers to, store it in `self.mem_args`, and create a new
         // `KernelArg::Mem` refering to the locally stored copy. This prevents
@@ -607,32 +1483,135 @@ impl Kernel {
             },
         };        
 
-        core::set_kernel_arg::<T>(&self.obj_core, arg_idx, arg)
+        if self.cache_args { CachedArg::store(cached, &arg); }
+
+        let result = core::set_kernel_arg::<T>(&self.obj_core, arg_idx, arg);
+        if result.is_err() { *cached = None; }
+        result
     }
 }
 
 
 
-impl std::fmt::Display for Kernel {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        try!(self.fmt_info(f));
-        try!(write!(f, " "));
-        self.fmt_wg_info(f, &self.queue.device())
+/// A local variable argument of `len` elements of type `T`, allocated for
+/// each work group (see `Kernel::arg`).
+#[derive(Clone, Copy, Debug)]
+pub struct Local<T: OclPrm> {
+    len: usize,
+    _ty: PhantomData<T>,
+}
+
+impl<T: OclPrm> Local<T> {
+    /// Returns a new local variable argument of `len` elements.
+    pub fn new(len: usize) -> Local<T> {
+        Local { len: len, _ty: PhantomData }
+    }
+
+    /// Returns the length in elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// A value which may be passed as a kernel argument using `Kernel::arg` or
+/// `Kernel::arg_named`.
+///
+/// Implemented for references to buffers, images, and samplers (and for
+/// `Option`s of them, `None` being a null argument), for scalars and vector
+/// types by value, for `Local`, and for `KernelArg` itself. Buffers are
+/// marked as initialized, as with `Kernel::arg_buf`.
+pub trait ArgVal {
+    /// Sets argument `arg_idx` of `kernel` to this value.
+    #[doc(hidden)]
+    fn set_on(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()>;
+}
+
+impl<T: OclPrm> ArgVal for T {
+    fn set_on(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        kernel.set_arg::<T>(arg_idx, KernelArg::Scalar(self))
     }
 }
 
-use std::ops::{Deref, DerefMut};
+impl<'a, T: OclPrm> ArgVal for KernelArg<'a, T> {
+    fn set_on(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        kernel.set_arg::<T>(arg_idx, self)
+    }
+}
 
-impl Deref for Kernel {
-    type Target = KernelCore;
+impl<T: OclPrm> ArgVal for Local<T> {
+    fn set_on(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        kernel.set_arg::<T>(arg_idx, KernelArg::Local(&self.len))
+    }
+}
 
-    fn deref(&self) -> &KernelCore {
-        &self.obj_core
+impl<'a, T: OclPrm> ArgVal for &'a Buffer<T> {
+    fn set_on(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        self.mark_init();
+        kernel.set_arg::<T>(arg_idx, KernelArg::Mem(self))
     }
 }
 
-impl DerefMut for Kernel {
-    fn deref_mut(&mut self) -> &mut KernelCore {
-        &mut self.obj_core
+impl<'a, T: OclPrm> ArgVal for Option<&'a Buffer<T>> {
+    fn set_on(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        match self {
+            Some(buffer) => buffer.set_on(kernel, arg_idx),
+            None => kernel.set_arg::<T>(arg_idx, KernelArg::MemNull),
+        }
+    }
+}
+
+impl<'a, P: OclPrm> ArgVal for &'a Image<P> {
+    fn set_on(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        // Type is ignored:
+        kernel.set_arg::<u8>(arg_idx, KernelArg::Mem(self))
+    }
+}
+
+impl<'a, P: OclPrm> ArgVal for Option<&'a Image<P>> {
+    fn set_on(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        match self {
+            Some(image) => image.set_on(kernel, arg_idx),
+            None => kernel.set_arg::<u8>(arg_idx, KernelArg::MemNull),
+        }
     }
 }
+
+impl<'a> ArgVal for &'a Sampler {
+    fn set_on(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        kernel.set_arg::<u8>(arg_idx, KernelArg::Sampler(self))
+    }
+}
+
+impl<'a> ArgVal for Option<&'a Sampler> {
+    fn set_on(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        match self {
+            Some(sampler) => sampler.set_on(kernel, arg_idx),
+            None => kernel.set_arg::<u8>(arg_idx, KernelArg::SamplerNull),
+        }
+    }
+}
+
+
+
+// Each `Kernel` creates (and is the only owner of) its `cl_kernel`, which is
+// not exposed other than by the `unsafe` `::core_as_ref`, and arguments are
+// only set through `&mut self`, so `clSetKernelArg` is never called
+// concurrently on it (OpenCL 1.2 specification, Appendix A.2). Not `Sync`
+// (`core::Kernel` is not).
+unsafe impl Send for Kernel {}
+
+impl std::fmt::Display for Kernel {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        try!(self.fmt_info(f));
+        match self.default_device() {
+            Some(device) => {
+                try!(write!(f, " "));
+                self.fmt_wg_info(f, &device)
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+
+
@@ -0,0 +1,214 @@
+//! Host access to mapped buffer and image memory.
+
+use std;
+use std::cell::Cell;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+use libc::c_void;
+use core::{self, OclPrm, Mem as MemCore, MapFlags, ClWaitList};
+use error::{Error as OclError, Result as OclResult};
+use standard::{Queue, Event};
+
+/// A region of a buffer or image mapped into host memory.
+///
+/// Created with `Buffer::map` or `Image::map`. Maps are enqueued without
+/// blocking. The mapped elements may be accessed through `Deref` and
+/// `DerefMut` (as a slice) which, the first time, block until the map has
+/// completed. Call `::wait` beforehand to handle any error rather than
+/// panicking.
+///
+/// The region is unmapped when the guard is dropped. Use `::unmap` to
+/// obtain an event which completes once it has been.
+///
+/// While mapped, the region must not be used by commands on the device.
+/// Elements are not byte swapped (see `BufferBuilder::swap_bytes`).
+///
+/// ## Examples
+///
+/// ```text
+/// let mut mapped = buffer.map(flags::MAP_WRITE, 0, None, None).unwrap();
+/// for val in mapped.iter_mut() { *val = 1.0; }
+/// mapped.unmap().unwrap().wait().unwrap();
+/// ```
+pub struct MappedMem<T: OclPrm> {
+    queue: Queue,
+    mem: MemCore,
+    ptr: *mut T,
+    len: usize,
+    row_pitch: usize,
+    slc_pitch: usize,
+    map_event: Cell<Option<Event>>,
+    unmapped: bool,
+}
+
+impl<T: OclPrm> MappedMem<T> {
+    /// Enqueues a map of the `len` elements of `mem` beginning at the
+    /// element, `offset`, waiting on `wait_list`.
+    ///
+    /// The region must lie within `mem` (checked by the caller).
+    pub(crate) fn map_buffer(queue: Queue, mem: &MemCore, flags: MapFlags, offset: usize,
+            len: usize, wait_list: Option<&ClWaitList>) -> OclResult<MappedMem<T>>
+    {
+        let mut map_event = Event::empty();
+        let ptr = try!(unsafe { core::enqueue_map_buffer::<T>(&queue, mem, false, flags, offset,
+            len, wait_list, Some(&mut map_event)) });
+
+        Ok(MappedMem::new(queue, mem, ptr, len, 0, 0, map_event))
+    }
+
+    /// Enqueues a map of the rectangular `region` of the image, `mem`, at
+    /// `origin`, waiting on `wait_list`.
+    ///
+    /// `pixel_len` is the number of elements per pixel. `layered_rows` is
+    /// true for one dimensional image arrays, whose rows are separated by
+    /// the slice pitch. The region must lie within `mem` (checked by the
+    /// caller) and no dimension of it may be zero.
+    pub(crate) fn map_image(queue: Queue, mem: &MemCore, flags: MapFlags, origin: [usize; 3],
+            region: [usize; 3], pixel_len: usize, layered_rows: bool,
+            wait_list: Option<&ClWaitList>) -> OclResult<MappedMem<T>>
+    {
+        if region.iter().any(|&dim| dim == 0) {
+            return OclError::err(format!("ocl::Image::map: No dimension of the region ({:?}) \
+                may be zero.", region));
+        }
+
+        let mut map_event = Event::empty();
+        let (ptr, row_pitch_bytes, slc_pitch_bytes) = try!(unsafe { core::enqueue_map_image(
+            &queue, mem, false, flags, origin, region, wait_list, Some(&mut map_event)) });
+
+        // Wraps before checking pitches so that the region is unmapped on
+        // error:
+        let mut mapped = MappedMem::new(queue, mem, ptr, 0, 0, 0, map_event);
+        let ele_size = std::mem::size_of::<T>();
+
+        if row_pitch_bytes % ele_size != 0 || slc_pitch_bytes % ele_size != 0 {
+            return OclError::err(format!("ocl::Image::map: The row pitch ({}) and slice pitch \
+                ({}) of the mapped region are not multiples of the element size ({}).",
+                row_pitch_bytes, slc_pitch_bytes, ele_size));
+        }
+
+        mapped.row_pitch = row_pitch_bytes / ele_size;
+        mapped.slc_pitch = slc_pitch_bytes / ele_size;
+
+        // Elements from the first to the last pixel of the region:
+        let row_len = region[0] * pixel_len;
+        mapped.len = if layered_rows {
+            mapped.slc_pitch * (region[1] - 1) + row_len
+        } else {
+            mapped.slc_pitch * (region[2] - 1) + mapped.row_pitch * (region[1] - 1) + row_len
+        };
+
+        Ok(mapped)
+    }
+
+    /// Wraps the pointer, `ptr`, mapped by the command of `map_event`.
+    fn new(queue: Queue, mem: &MemCore, ptr: *mut c_void, len: usize, row_pitch: usize,
+            slc_pitch: usize, map_event: Event) -> MappedMem<T>
+    {
+        MappedMem {
+            queue: queue,
+            mem: mem.clone(),
+            ptr: ptr as *mut T,
+            len: len,
+            row_pitch: row_pitch,
+            slc_pitch: slc_pitch,
+            map_event: Cell::new(Some(map_event)),
+            unmapped: false,
+        }
+    }
+
+    /// Blocks until the map has completed.
+    pub fn wait(&self) -> OclResult<()> {
+        match self.map_event.take() {
+            Some(event) => {
+                let res = event.wait();
+                if res.is_err() { self.map_event.set(Some(event)); }
+                res
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the number of mapped elements.
+    ///
+    /// For images, this spans from the first to the last pixel of the
+    /// mapped region, including any padding between rows and slices.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of elements between the beginning of each row of
+    /// a mapped image region (zero for buffers).
+    pub fn row_pitch(&self) -> usize {
+        self.row_pitch
+    }
+
+    /// Returns the number of elements between the beginning of each slice
+    /// (or, for one dimensional image arrays, each row) of a mapped image
+    /// region (zero for buffers and two dimensional images).
+    pub fn slc_pitch(&self) -> usize {
+        self.slc_pitch
+    }
+
+    /// Enqueues the unmap, returning an event which completes once it has.
+    pub fn unmap(mut self) -> OclResult<Event> {
+        let mut unmap_event = Event::empty();
+        try!(self.wait());
+        // Left for `Drop` to retry should the enqueue fail:
+        try!(core::enqueue_unmap_mem_object(&self.queue, &self.mem, self.ptr as *mut c_void,
+            None, Some(&mut unmap_event)));
+        self.unmapped = true;
+        Ok(unmap_event)
+    }
+}
+
+impl<T: OclPrm> Deref for MappedMem<T> {
+    type Target = [T];
+
+    /// Blocks until the map has completed, the first time.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if waiting for the map fails (see `::wait`).
+    fn deref(&self) -> &[T] {
+        if let Err(err) = self.wait() {
+            panic!("ocl::MappedMem: Error waiting for the map to complete: {}", err);
+        }
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl<T: OclPrm> DerefMut for MappedMem<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        if let Err(err) = self.wait() {
+            panic!("ocl::MappedMem: Error waiting for the map to complete: {}", err);
+        }
+        unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl<T: OclPrm> Drop for MappedMem<T> {
+    /// Enqueues the unmap (unless already done with `::unmap`).
+    fn drop(&mut self) {
+        if !self.unmapped {
+            // The map must complete before its pointer may be unmapped:
+            self.wait().ok();
+            core::enqueue_unmap_mem_object(&self.queue, &self.mem, self.ptr as *mut c_void,
+                None, None).ok();
+        }
+    }
+}
+
+impl<T: OclPrm> std::fmt::Debug for MappedMem<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("MappedMem")
+            .field("len", &self.len)
+            .field("row_pitch", &self.row_pitch)
+            .field("slc_pitch", &self.slc_pitch)
+            .finish()
+    }
+}
+
+// The mapped pointer is only accessed through the guard, which is not
+// `Sync`.
+unsafe impl<T: OclPrm> Send for MappedMem<T> {}
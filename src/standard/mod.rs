@@ -1,18 +1,47 @@
 //! `ocl` standard types.
 
+/// Declares a crate-private function, `$name(platform: &Platform) ->
+/// OclResult<$fn_ty>`, which returns the extension function `$ext_name`
+/// as a typed function pointer.
+///
+/// The address is looked up once per platform and cached (see
+/// `ExtensionFnCache`). An `ExtensionUnavailable` error is returned if the
+/// implementation does not provide the function.
+#[allow(unused_macros)]
+macro_rules! extension_fn {
+    ($name:ident, $ext_name:expr, $fn_ty:ty) => {
+        #[allow(dead_code)]
+        pub(crate) fn $name(platform: &::standard::Platform)
+                -> ::error::Result<$fn_ty>
+        {
+            static CACHE: ::standard::ExtensionFnCache =
+                ::standard::ExtensionFnCache::new($ext_name);
+
+            CACHE.get(platform).map(|ptr| unsafe {
+                ::std::mem::transmute::<*mut ::libc::c_void, $fn_ty>(ptr)
+            })
+        }
+    };
+}
+
 mod platform;
 mod device;
+mod device_limits;
+mod device_info_source;
 // mod device_specifier;
 // mod context_builder;
 mod context;
 // mod program_builder;
 mod program;
 mod kernel;
+mod shared_kernel;
 mod queue;
 mod buffer;
+mod buffer_view;
 // mod buffer_cmd;
 // mod image_builder;
 mod image;
+mod image_region;
 // mod image_cmd;
 mod sampler;
 // mod pro_que_builder;
@@ -20,29 +49,60 @@ mod pro_que;
 mod event;
 // mod event_list;
 mod spatial_dims;
+mod staging_pool;
+mod mapped_mem;
+mod double_buffer;
+mod scope;
+mod graph;
+mod multi;
+mod benchmark;
+mod timeline;
+mod report;
 // mod work_dims;
 
 // #[cfg(not(release))] pub use self::buffer::tests::BufferTest;
 pub use self::platform::Platform;
+#[allow(unused_imports)] pub(crate) use self::platform::ExtensionFnCache;
 pub use self::device::{Device, DeviceSpecifier};
+pub use self::device_limits::DeviceLimits;
+pub(crate) use self::device_info_source::DeviceInfoSource;
 // pub use self::device_specifier::DeviceSpecifier;
 // pub use self::context_builder::ContextBuilder;
-pub use self::context::{Context, ContextBuilder};
+pub use self::context::{Context, ContextBuilder, WeakContext};
 // pub use self::program_builder::{ProgramBuilder, BuildOpt};
-pub use self::program::{Program, ProgramBuilder, BuildOpt};
-pub use self::queue::Queue;
-pub use self::kernel::{Kernel, KernelCmd};
-pub use self::buffer::{Buffer, BufferCmd, BufferCmdKind, BufferCmdDataShape};
+pub use self::program::{Program, ProgramBuilder, BuildOpt, DefineValue, DefineStrategy,
+    WeakProgram, BinaryDump};
+pub use self::queue::{Queue, WeakQueue};
+pub(crate) use self::queue::resolve_queue;
+pub use self::kernel::{Kernel, KernelCmd, ArgVal, Local};
+#[allow(unused_imports)] pub(crate) use self::kernel::check_work_dims;
+#[allow(unused_imports)] pub(crate) use self::device_info_source::fmt_mem_size;
+pub use self::shared_kernel::SharedKernel;
+pub use self::buffer::{Buffer, BufferBuilder, BufferCmd, BufferCmdKind, BufferCmdDataShape,
+    TransferMode};
+pub use self::buffer_view::BufferView;
 // pub use self::buffer_cmd::{BufferCmd, BufferCmdKind, BufferCmdDataShape};
 // pub use self::image_builder::ImageBuilder;
 pub use self::image::{Image, ImageCmd, ImageCmdKind, ImageBuilder};
+pub use self::image_region::{ImageOrigin, ImageRegion};
 // pub use self::image_cmd::{ImageCmd, ImageCmdKind};
 pub use self::sampler::Sampler;
 // pub use self::pro_que_builder::ProQueBuilder;
 pub use self::pro_que::{ProQue, ProQueBuilder};
-pub use self::event::{Event, EventList};
+pub use self::event::{Event, EventList, WaitOutcome};
 // pub use self::event_list::EventList;
-pub use self::spatial_dims::SpatialDims;
+pub use self::spatial_dims::{SpatialDims, SpatialDimsCoords};
+pub use self::staging_pool::StagingPool;
+pub use self::mapped_mem::MappedMem;
+pub use self::double_buffer::DoubleBuffer;
+pub use self::scope::{scope, Scope};
+pub use self::graph::{Graph, GraphEvents};
+pub use self::multi::run_partitioned;
+pub use self::benchmark::{BenchWorkload, BenchReport, BenchEntry};
+pub use self::timeline::{TimelineTranslator, EventSpans};
+#[cfg(test)] pub(crate) use self::multi::partition;
+pub use self::report::report;
+#[allow(unused_imports)] pub(crate) use self::report::push_device_report;
 pub use self::traits::{MemLen, WorkDims};
 
 
@@ -52,6 +112,93 @@ pub use self::traits::{MemLen, WorkDims};
 
 // pub const INFO_FORMAT_MULTILINE: bool = false;
 
+//=============================================================================
+//================================ FUNCTIONS ==================================
+//=============================================================================
+
+/// Parses the `<major>.<minor>` portion of an OpenCL version string such as
+/// `OpenCL 1.2 <platform-specific information>`.
+fn parse_cl_version(version: &str) -> Option<(u32, u32)> {
+    version.split_whitespace().nth(1)
+        .and_then(|num| {
+            let mut parts = num.split('.').map(|p| p.parse::<u32>());
+            match (parts.next(), parts.next()) {
+                (Some(Ok(major)), Some(Ok(minor))) => Some((major, minor)),
+                _ => None,
+            }
+        })
+}
+
+/// Returns true if `T` is the half-precision float type (`ocl::f16`).
+#[cfg(feature = "f16")]
+fn is_f16<T: 'static>() -> bool {
+    ::std::any::TypeId::of::<T>() == ::std::any::TypeId::of::<::half::f16>()
+}
+
+/// Returns true if `T` is the half-precision float type (`ocl::f16`).
+#[cfg(not(feature = "f16"))]
+fn is_f16<T: 'static>() -> bool {
+    false
+}
+
+/// Returns true if `T` is a double-precision float type (`f64`, one of the
+/// `ClDouble` vector types, or `Complex<f64>`).
+fn is_f64<T: 'static>() -> bool {
+    use std::any::TypeId;
+    use core::{ClDouble2, ClDouble3, ClDouble4, ClDouble8, ClDouble16};
+
+    let id = TypeId::of::<T>();
+    id == TypeId::of::<f64>() || id == TypeId::of::<ClDouble2>() ||
+        id == TypeId::of::<ClDouble3>() || id == TypeId::of::<ClDouble4>() ||
+        id == TypeId::of::<ClDouble8>() || id == TypeId::of::<ClDouble16>() ||
+        is_complex_f64::<T>()
+}
+
+/// Returns true if `T` is `Complex<f64>`.
+#[cfg(feature = "complex")]
+fn is_complex_f64<T: 'static>() -> bool {
+    ::std::any::TypeId::of::<T>() == ::std::any::TypeId::of::<::num::complex::Complex<f64>>()
+}
+
+/// Returns true if `T` is `Complex<f64>`.
+#[cfg(not(feature = "complex"))]
+fn is_complex_f64<T: 'static>() -> bool {
+    false
+}
+
+//=============================================================================
+//============================ THREAD SAFETY CHECKS ===========================
+//=============================================================================
+
+// Every type may be sent between threads. All but `Kernel` (which sets
+// arguments through `&mut self` but whose `core::Kernel` is not `Sync`) may
+// also be shared (`SharedKernel` wraps a `Kernel` in a lock for that). See "Thread Safety" in the `core::types::abs` docs.
+const _: fn() = || {
+    use util::{assert_send, assert_sync, NotSync};
+
+    assert_send::<Platform>(); assert_sync::<Platform>();
+    assert_send::<Device>(); assert_sync::<Device>();
+    assert_send::<Context>(); assert_sync::<Context>();
+    assert_send::<Queue>(); assert_sync::<Queue>();
+    assert_send::<Program>(); assert_sync::<Program>();
+    assert_send::<WeakContext>(); assert_sync::<WeakContext>();
+    assert_send::<WeakQueue>(); assert_sync::<WeakQueue>();
+    assert_send::<WeakProgram>(); assert_sync::<WeakProgram>();
+    assert_send::<Buffer<f32>>(); assert_sync::<Buffer<f32>>();
+    assert_send::<Image<u8>>(); assert_sync::<Image<u8>>();
+    assert_send::<Sampler>(); assert_sync::<Sampler>();
+    assert_send::<Event>(); assert_sync::<Event>();
+    assert_send::<EventList>(); assert_sync::<EventList>();
+    assert_send::<ProQue>(); assert_sync::<ProQue>();
+    assert_send::<StagingPool>(); assert_sync::<StagingPool>();
+    assert_send::<MappedMem<f32>>();
+    assert_send::<DoubleBuffer<f32>>(); assert_sync::<DoubleBuffer<f32>>();
+
+    assert_send::<Kernel>();
+    let _ = <Kernel as NotSync<_>>::check;
+    assert_send::<SharedKernel>(); assert_sync::<SharedKernel>();
+};
+
 //=============================================================================
 //================================= TRAITS ====================================
 //=============================================================================
@@ -60,7 +207,7 @@ mod traits {
     use std::fmt::Debug;
     // use std::convert::Into;
     use num::{Num, ToPrimitive};
-    // use error::{Result as OclResult};
+    use error::{Result as OclResult};
     use super::{SpatialDims};
     use super::spatial_dims::to_usize;
 
@@ -98,14 +245,15 @@ mod traits {
         /// Returns the length of a volumue of memory padded to the next
         /// multiple of `incr`.
         fn to_len_padded(&self, incr: usize) -> usize;
-        /// Returns the exact lengths of each dimension of a volume of memory.
-        fn to_lens(&self) -> [usize; 3];
+        /// Returns the exact lengths of each dimension of a volume of memory
+        /// or an error if no dimensions have been specified.
+        fn to_lens(&self) -> OclResult<[usize; 3]>;
     }
 
     impl<'a, D> MemLen for &'a D where D: MemLen {
         fn to_len(&self) -> usize { (*self).to_len() }
         fn to_len_padded(&self, incr: usize) -> usize { (*self).to_len_padded(incr) }
-        fn to_lens(&self) -> [usize; 3] { (*self).to_lens() }
+        fn to_lens(&self) -> OclResult<[usize; 3]> { (*self).to_lens() }
     }
 
     impl<D> MemLen for (D, ) where D: Num + ToPrimitive + Debug + Copy {
@@ -115,7 +263,7 @@ mod traits {
         fn to_len_padded(&self, incr: usize) -> usize {
             SpatialDims::One(to_usize(self.0)).to_len_padded(incr)
         }
-        fn to_lens(&self) -> [usize; 3] { [to_usize(self.0), 1, 1] }
+        fn to_lens(&self) -> OclResult<[usize; 3]> { Ok([to_usize(self.0), 1, 1]) }
     }
 
     impl<D> MemLen for [D; 1] where D: Num + ToPrimitive + Debug + Copy {
@@ -125,7 +273,7 @@ mod traits {
         fn to_len_padded(&self, incr: usize) -> usize {
             SpatialDims::One(to_usize(self[0])).to_len_padded(incr)
         }
-        fn to_lens(&self) -> [usize; 3] { [to_usize(self[0]), 1, 1] }
+        fn to_lens(&self) -> OclResult<[usize; 3]> { Ok([to_usize(self[0]), 1, 1]) }
     }
 
     impl<D> MemLen for (D, D) where D: Num + ToPrimitive + Debug + Copy {
@@ -135,7 +283,7 @@ mod traits {
         fn to_len_padded(&self, incr: usize) -> usize {
             SpatialDims::Two(to_usize(self.0), to_usize(self.1)).to_len_padded(incr)
         }
-        fn to_lens(&self) -> [usize; 3] { [to_usize(self.0), to_usize(self.1), 1] }
+        fn to_lens(&self) -> OclResult<[usize; 3]> { Ok([to_usize(self.0), to_usize(self.1), 1]) }
     }
 
     impl<D> MemLen for [D; 2] where D: Num + ToPrimitive + Debug + Copy {
@@ -145,7 +293,7 @@ mod traits {
         fn to_len_padded(&self, incr: usize) -> usize {
             SpatialDims::Two(to_usize(self[0]), to_usize(self[1])).to_len_padded(incr)
         }
-        fn to_lens(&self) -> [usize; 3] { [to_usize(self[0]), to_usize(self[1]), 1] }
+        fn to_lens(&self) -> OclResult<[usize; 3]> { Ok([to_usize(self[0]), to_usize(self[1]), 1]) }
     }
 
     impl<'a, D> MemLen for (D, D, D) where D: Num + ToPrimitive + Debug + Copy {
@@ -157,7 +305,9 @@ mod traits {
             SpatialDims::Three(to_usize(self.0), to_usize(self.1), to_usize(self.2))
                 .to_len_padded(incr)
         }
-        fn to_lens(&self) -> [usize; 3] { [to_usize(self.0), to_usize(self.1), to_usize(self.2)] }
+        fn to_lens(&self) -> OclResult<[usize; 3]> {
+            Ok([to_usize(self.0), to_usize(self.1), to_usize(self.2)])
+        }
     }
 
     impl<'a, D> MemLen for [D; 3] where D: Num + ToPrimitive + Debug + Copy {
@@ -169,7 +319,9 @@ mod traits {
             SpatialDims::Three(to_usize(self[0]), to_usize(self[1]), to_usize(self[2]))
                 .to_len_padded(incr)
         }
-        fn to_lens(&self) -> [usize; 3] { [to_usize(self[0]), to_usize(self[1]), to_usize(self[2])] }
+        fn to_lens(&self) -> OclResult<[usize; 3]> {
+            Ok([to_usize(self[0]), to_usize(self[1]), to_usize(self[2])])
+        }
     }   
 }
 
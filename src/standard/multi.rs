@@ -0,0 +1,167 @@
+//! Dispatching one job across several devices.
+
+use std::ops::Range;
+use core::{self, OclPrm, DeviceInfo, DeviceInfoResult};
+use error::{Error as OclError, Result as OclResult};
+use standard::{Platform, Device, DeviceSpecifier, Context, Queue, Buffer, Event, scope};
+
+/// Splits `data` into one partition per device, proportional to the number
+/// of compute units of each, runs `f` on every partition concurrently, and
+/// returns the contents of each partition's buffer afterwards, in order.
+///
+/// For each partition, a buffer containing the partition's elements is
+/// created on an in-order queue for its device and `f` is called with that
+/// queue, the buffer, and the range of `data` the partition covers. `f`
+/// enqueues the work for the partition (e.g. a kernel processing the buffer
+/// in place) on the queue, returning the event of the last command. The
+/// buffer is then read back once that event completes.
+///
+/// Devices sharing a platform share a context. Devices with too few
+/// compute units to receive any elements (possible when `data` is shorter
+/// than the number of devices) are left out and `f` is not called for them.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// # fn main() { run().unwrap() }
+/// # fn run() -> ocl::Result<()> {
+/// # let devices = try!(ocl::Device::list_all(&ocl::Platform::default()));
+/// # let data = vec![1.0f32; 1 << 20];
+/// let src = "__kernel void double_it(__global float* buf) {
+///     buf[get_global_id(0)] *= 2.0f;
+/// }";
+///
+/// let result = try!(ocl::multi::run_partitioned(&devices, &data, |queue, buffer, _range| {
+///     let program = try!(ocl::Program::builder().src(src).build_for_queue(queue));
+///     let kernel = try!(ocl::Kernel::new("double_it", &program, queue))
+///         .gws([buffer.len()]).arg_buf(buffer);
+///     let mut event = ocl::Event::empty();
+///     try!(kernel.cmd().enew(&mut event).enq());
+///     Ok(event)
+/// }));
+/// assert_eq!(result.len(), data.len());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ## Errors
+///
+/// Returns an error if `devices` or `data` is empty, or the first error
+/// encountered creating contexts, queues, or buffers, returned by `f`, or
+/// while waiting for any partition. Every partition enqueued so far is
+/// waited on before returning in any case.
+pub fn run_partitioned<T, F>(devices: &[Device], data: &[T], f: F) -> OclResult<Vec<T>>
+        where T: OclPrm, F: Fn(&Queue, &Buffer<T>, Range<usize>) -> OclResult<Event>
+{
+    if devices.is_empty() {
+        return OclError::err("ocl::multi::run_partitioned: No devices specified.");
+    }
+    if data.is_empty() {
+        return OclError::err("ocl::multi::run_partitioned: No data specified.");
+    }
+
+    let mut weights = Vec::with_capacity(devices.len());
+    for device in devices {
+        weights.push(try!(device.max_compute_units()));
+    }
+
+    let queues = try!(queues(devices));
+    let mut partitions = Vec::with_capacity(devices.len());
+
+    for (queue, range) in queues.into_iter().zip(partition(&weights, data.len())) {
+        if range.start == range.end { continue; }
+
+        let buffer = try!(Buffer::builder()
+            .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+            .dims([range.end - range.start])
+            .build_with_data(&queue, &data[range.clone()]));
+        partitions.push((queue, buffer, range));
+    }
+
+    let mut result = vec![T::default(); data.len()];
+    {
+        // Split in order, one disjoint chunk per (non-empty) partition:
+        let mut chunks = Vec::with_capacity(partitions.len());
+        let mut rest = &mut result[..];
+        for &(_, _, ref range) in partitions.iter() {
+            let (chunk, tail) = { rest }.split_at_mut(range.end - range.start);
+            chunks.push(chunk);
+            rest = tail;
+        }
+
+        try!(scope(|s| {
+            for (&(ref queue, ref buffer, ref range), chunk) in partitions.iter()
+                    .zip(chunks.into_iter())
+            {
+                let event = try!(f(queue, buffer, range.clone()));
+                try!(s.read_async_after(buffer, chunk, &event));
+            }
+            Ok(())
+        }));
+    }
+
+    Ok(result)
+}
+
+/// Returns the half-open ranges dividing `len` elements proportionally to
+/// `weights`, in order, with a weight of zero counted as one.
+///
+/// The ranges cover `0..len` exactly, some possibly empty.
+pub(crate) fn partition(weights: &[u32], len: usize) -> Vec<Range<usize>> {
+    let total: u64 = weights.iter().map(|&w| w.max(1) as u64).sum();
+    let mut cumulative = 0u64;
+    let mut start = 0;
+
+    weights.iter().map(|&w| {
+        cumulative += w.max(1) as u64;
+        let end = (len as u64 * cumulative / total) as usize;
+        let range = start..end;
+        start = end;
+        range
+    }).collect()
+}
+
+/// Creates one queue per device, within a context shared by all devices of
+/// the same platform.
+fn queues(devices: &[Device]) -> OclResult<Vec<Queue>> {
+    let mut platforms = Vec::with_capacity(devices.len());
+    for &device in devices {
+        platforms.push(try!(device_platform(device)));
+    }
+
+    let mut contexts: Vec<(Platform, Context)> = Vec::new();
+    let mut queues = Vec::with_capacity(devices.len());
+
+    for (&device, platform) in devices.iter().zip(platforms.iter()) {
+        let idx = match contexts.iter().position(|&(ref p, _)| p.as_core() == platform.as_core()) {
+            Some(idx) => idx,
+            None => {
+                let same_platform = devices.iter().zip(platforms.iter())
+                    .filter(|&(_, p)| p.as_core() == platform.as_core())
+                    .map(|(&d, _)| d)
+                    .collect();
+
+                let context = try!(Context::builder()
+                    .platform(*platform)
+                    .devices(DeviceSpecifier::List(same_platform))
+                    .build());
+                contexts.push((*platform, context));
+                contexts.len() - 1
+            },
+        };
+
+        queues.push(try!(Queue::new(&contexts[idx].1, device)));
+    }
+
+    Ok(queues)
+}
+
+/// Returns the platform of `device`.
+fn device_platform(device: Device) -> OclResult<Platform> {
+    match device.info(DeviceInfo::Platform) {
+        DeviceInfoResult::Platform(platform) => Ok(Platform::new(platform)),
+        DeviceInfoResult::Error(err) => Err(*err),
+        res => OclError::err(format!("ocl::multi::run_partitioned: Unexpected device info \
+            result: {:?}", res)),
+    }
+}
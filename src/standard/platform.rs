@@ -8,7 +8,10 @@
 use std;
 use std::ops::{Deref, DerefMut};
 use std::convert::Into;
+use std::sync::Mutex;
+use libc::c_void;
 use core::{self, PlatformId as PlatformIdCore, PlatformInfo, PlatformInfoResult, ClPlatformIdPtr};
+use error::{Error as OclError, Result as OclResult};
 
 #[derive(Clone, Copy, Debug)]
 /// A platform identifier.
@@ -16,11 +19,35 @@ pub struct Platform(PlatformIdCore);
 
 impl Platform {
     /// Returns a list of all platforms avaliable on the host machine.
-    pub fn list() -> Vec<Platform> {
-        let list_core = core::get_platform_ids()
-            .expect("Platform::list: Error retrieving platform list");
+    pub fn list() -> OclResult<Vec<Platform>> {
+        let list_core = try!(core::get_platform_ids());
+        Ok(list_core.into_iter().map(|pr| Platform::new(pr) ).collect())
+    }
 
-        list_core.into_iter().map(|pr| Platform::new(pr) ).collect()
+    /// Returns a list of all platforms avaliable on the host machine.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the platform list can not be retrieved. Use `::list` to
+    /// handle the error instead.
+    pub fn list_unwrap() -> Vec<Platform> {
+        Platform::list().expect("ocl::Platform::list_unwrap")
+    }
+
+    /// Returns the default platform (the first platform or the platform
+    /// selected by index with the `OCL_PLATFORM_INDEX` environment
+    /// variable).
+    ///
+    /// Use this instead of `Platform::default()` when no platform being
+    /// available should not be fatal.
+    ///
+    /// ## Errors
+    ///
+    /// Returns `Error::NoPlatforms` if no platforms are available. It
+    /// displays the library loaded, the drivers registered, and suggestions
+    /// of what to try (see `ocl::diagnostics`).
+    pub fn try_default() -> OclResult<Platform> {
+        core::default_platform().map(Platform::new)
     }
 
     // /// Returns the first available platform on the host machine.
@@ -104,6 +131,60 @@ impl Platform {
         core::get_platform_info(Some(self.0.clone()), PlatformInfo::Version).into()
     }
 
+    /// Returns the `(major, minor)` OpenCL version supported by the
+    /// platform, parsed from the string returned by `::version`.
+    pub fn version_number(&self) -> OclResult<(u32, u32)> {
+        let version = self.version();
+
+        super::parse_cl_version(&version)
+            .ok_or(OclError::new(format!("ocl::Platform::version_number: Unable to parse the \
+                platform version string: '{}'.", version)))
+    }
+
+    /// Returns the address of the extension function named `name` for this
+    /// platform.
+    ///
+    /// Uses `clGetExtensionFunctionAddressForPlatform` or, on OpenCL 1.1
+    /// platforms, the depricated `clGetExtensionFunctionAddress`.
+    ///
+    /// The returned pointer must be cast to a function pointer type matching
+    /// the definition in the appropriate extension specification. A non-null
+    /// address does not guarantee that the extension is supported by every
+    /// device (see `::extensions` and `Device::has_extension`).
+    ///
+    /// Returns an `ExtensionUnavailable` error if the function can not be
+    /// found.
+    pub fn extension_fn(&self, name: &str) -> OclResult<*mut c_void> {
+        let pre_1_2 = match self.version_number() {
+            Ok(version) => version < (1, 2),
+            Err(_) => false,
+        };
+
+        unsafe {
+            if pre_1_2 {
+                core::get_extension_function_address(name)
+            } else {
+                core::get_extension_function_address_for_platform(&self.0, name)
+            }
+        }
+    }
+
+    /// Allows the implementation to release the resources allocated by the
+    /// compiler for this platform.
+    ///
+    /// Useful for long-running processes which build all of their programs
+    /// at startup. Programs may still be built afterwards, the compiler will
+    /// simply be reloaded.
+    ///
+    /// Uses `clUnloadPlatformCompiler` or, on OpenCL 1.1 platforms, the
+    /// depricated `clUnloadCompiler`.
+    pub fn unload_compiler(&self) -> OclResult<()> {
+        match self.version_number() {
+            Ok(version) if version < (1, 2) => core::unload_compiler(),
+            _ => core::unload_platform_compiler(&self.0),
+        }
+    }
+
     /// Returns the platform name as a string.
     pub fn name(&self) -> String {
         // match core::get_platform_info(Some(self.0.clone()), PlatformInfo::Name) {
@@ -154,6 +235,69 @@ impl Platform {
 
 unsafe impl ClPlatformIdPtr for Platform {}
 
+
+/// A thread-safe, per-platform cache of the address of a single extension
+/// function. Used by the `extension_fn!` macro.
+///
+/// Failed lookups are cached as well so that an unavailable function is only
+/// ever looked up once per platform.
+#[allow(dead_code)]
+pub(crate) struct ExtensionFnCache {
+    name: &'static str,
+    // `(platform pointer, function address)` pairs. An address of zero
+    // indicates that the function is unavailable on that platform.
+    fns: Mutex<Vec<(usize, usize)>>,
+}
+
+#[allow(dead_code)]
+impl ExtensionFnCache {
+    /// Returns a new, empty cache for the extension function `name`.
+    pub const fn new(name: &'static str) -> ExtensionFnCache {
+        ExtensionFnCache { name: name, fns: Mutex::new(Vec::new()) }
+    }
+
+    /// Returns the cached address of the extension function for `platform`,
+    /// looking it up first if necessary.
+    pub fn get(&self, platform: &Platform) -> OclResult<*mut c_void> {
+        let key = unsafe { platform.as_core().as_ptr() as usize };
+        self.get_or_lookup(key, || platform.extension_fn(self.name))
+    }
+
+    /// Returns the cached address for the platform identified by `key` or
+    /// calls `lookup` and caches the result.
+    pub fn get_or_lookup<F>(&self, key: usize, lookup: F) -> OclResult<*mut c_void>
+            where F: FnOnce() -> OclResult<*mut c_void>
+    {
+        let mut fns = match self.fns.lock() {
+            Ok(fns) => fns,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let addr = match fns.iter().find(|&&(k, _)| k == key) {
+            Some(&(_, addr)) => addr,
+            None => {
+                let addr = match lookup() {
+                    Ok(ptr) => ptr as usize,
+                    Err(OclError::ExtensionUnavailable(_)) => 0,
+                    Err(err) => return Err(err),
+                };
+                fns.push((key, addr));
+                addr
+            },
+        };
+
+        if addr == 0 {
+            Err(OclError::ExtensionUnavailable(self.name.to_owned()))
+        } else {
+            Ok(addr as *mut c_void)
+        }
+    }
+}
+
+/// ## Panics
+///
+/// `Platform::default()` panics if no platform is available. Use
+/// `Platform::try_default` to handle the error instead.
 impl Default for Platform {
     fn default() -> Platform {
         // let list_core = core::get_platform_ids()
@@ -162,8 +306,7 @@ impl Default for Platform {
         // // let first_idx = list_core.len() - 1;
         // let first_idx = 0;
 
-        let dflt_plat_core = core::default_platform().expect("Platform::default()");
-        Platform::new(dflt_plat_core)
+        Platform::try_default().expect("ocl::Platform::default")
     }
 }
 
@@ -179,9 +322,22 @@ impl Into<PlatformIdCore> for Platform {
     }
 }
 
+/// Formats the platform name and version, e.g.: `NVIDIA CUDA (OpenCL 1.2
+/// CUDA 9.0.282)`, or, if its name can not be queried, its raw handle in
+/// brackets.
+///
+/// Use the alternate flag (`{:#}`) to list every property instead.
 impl std::fmt::Display for Platform {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        self.fmt_info(f)
+        if f.alternate() { return self.fmt_info(f); }
+
+        match (self.info(PlatformInfo::Name), self.info(PlatformInfo::Version)) {
+            (PlatformInfoResult::Name(name), PlatformInfoResult::Version(version)) => {
+                write!(f, "{} ({})", name, version)
+            },
+            (PlatformInfoResult::Name(name), _) => f.write_str(&name),
+            _ => write!(f, "[{:?}]", self.0),
+        }
     }
 }
 
@@ -1,9 +1,11 @@
 //! A convenient wrapper for `Program` and `Queue`.
 
+use std::cmp;
 use std::convert::Into;
 use std::ops::Deref;
 use error::{Result as OclResult, Error as OclError};
-use core::OclPrm;
+use core::{self, OclPrm, KernelWorkGroupInfo, KernelWorkGroupInfoResult};
+use util;
 use standard::{Platform, Device, Context, ProgramBuilder, Program, Queue, Kernel, Buffer,
     MemLen, SpatialDims, WorkDims, DeviceSpecifier};
 
@@ -72,9 +74,9 @@ impl ProQueBuilder {
             None => match &self.context {
                 &Some(ref context) => match context.platform() {
                     Some(platform) => platform,
-                    None => Platform::default(),
+                    None => try!(Platform::try_default()),
                 },
-                &None => Platform::default(),
+                &None => try!(Platform::try_default()),
             },
         };
 
@@ -92,7 +94,7 @@ impl ProQueBuilder {
                         'Queue' separately for multi-device configurations.", device_list.len()));
                 }
             },
-            None => Device::first(platform),
+            None => Device::list_from_core(try!(core::default_devices(platform.as_core())))[0],
         };
 
         if DEBUG_PRINT { println!("ProQue::build(): device: {:?}", device); }
@@ -154,7 +156,10 @@ impl ProQueBuilder {
     /// Sets a device or devices to be used and returns a `ProQueBuilder`
     /// reference.
     ///
-    /// Must specify only a single device.
+    /// Must specify only a single device. If not specified, the first device
+    /// returned by `core::default_devices` is used (which the
+    /// `OCL_DEVICE_TYPE` and `OCL_DEVICE_INDEX` environment variables can
+    /// select).
     ///
     pub fn device<'p, D: Into<DeviceSpecifier>>(&'p mut self, device_spec: D) 
             -> &'p mut ProQueBuilder
@@ -293,6 +298,10 @@ impl ProQue {
     }
 
     /// Creates a kernel with pre-assigned dimensions.
+    ///
+    /// If no dimensions have been set for this `ProQue`, the kernel will have
+    /// no default global work size and enqueuing it without specifying one
+    /// will return a `DimsUnspecified` error.
     pub fn create_kernel(&self, name: &str) -> OclResult<Kernel> {
         let kernel = try!(Kernel::new(name.to_string(), &self.program, &self.queue));
 
@@ -313,7 +322,10 @@ impl ProQue {
     /// buffer using `Buffer::new()`.
     ///
     pub fn create_buffer<T: OclPrm>(&self) -> OclResult<Buffer<T>> {
-        let dims = try!(self.dims_result());
+        let dims = match self.dims {
+            Some(ref dims) => dims,
+            None => return OclError::err_dims_unspecified("ocl::ProQue::create_buffer"),
+        };
         Buffer::<T>::new(&self.queue, None, &dims, None)
     }
 
@@ -323,11 +335,76 @@ impl ProQue {
     }
 
     /// Returns the maximum workgroup size supported by the device associated
-    /// with this `ProQue`.
+    /// with this `ProQue` (cached).
+    ///
+    /// Individual kernels may be limited further (see `::kernel_max_wg_size`).
+    pub fn max_wg_size(&self) -> OclResult<usize> {
+        Ok(self.queue.device_max_wg_size())
+    }
+
+    /// Returns the maximum workgroup size of the kernel named `name` on the
+    /// device associated with this `ProQue`.
     ///
-    /// [UNSTABLE]: Evaluate usefulness.
-    pub fn max_wg_size(&self) -> usize {
-        self.queue.device().max_wg_size()
+    /// Depends on the resources (registers, local memory, etc.) used by the
+    /// kernel and never exceeds `::max_wg_size`.
+    pub fn kernel_max_wg_size(&self, name: &str) -> OclResult<usize> {
+        let kernel = try!(Kernel::new(name, &self.program, &self.queue));
+
+        match kernel.wg_info(self.queue.device(), KernelWorkGroupInfo::WorkGroupSize) {
+            KernelWorkGroupInfoResult::WorkGroupSize(size) => Ok(size),
+            KernelWorkGroupInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::ProQue::kernel_max_wg_size: Unexpected kernel work group \
+                info result."),
+        }
+    }
+
+    /// Returns a one dimensional `(global_work_size, local_work_size)` pair
+    /// suitable for launching the kernel named `name` over `total_items`
+    /// work items.
+    ///
+    /// The local work size is the largest multiple of the kernel's preferred
+    /// work group size multiple within the limits of both the kernel and the
+    /// device (and no larger than needed for `total_items`). The global work
+    /// size is `total_items` padded up to a multiple of the local work size,
+    /// so the kernel must ignore work items beyond `total_items` (e.g. `if
+    /// (get_global_id(0) >= total_items) return;`).
+    pub fn suggest_launch(&self, name: &str, total_items: usize)
+            -> OclResult<(SpatialDims, SpatialDims)>
+    {
+        if total_items == 0 {
+            return OclError::err("ocl::ProQue::suggest_launch: 'total_items' must be non-zero.");
+        }
+
+        let kernel = try!(Kernel::new(name, &self.program, &self.queue));
+        let device = self.queue.device();
+
+        let kernel_limit = match kernel.wg_info(device, KernelWorkGroupInfo::WorkGroupSize) {
+            KernelWorkGroupInfoResult::WorkGroupSize(size) => size,
+            KernelWorkGroupInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err("ocl::ProQue::suggest_launch: Unexpected kernel work \
+                group info result."),
+        };
+
+        let multiple = match kernel.wg_info(device,
+                KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple) {
+            KernelWorkGroupInfoResult::PreferredWorkGroupSizeMultiple(m) if m > 0 => m,
+            // Unavailable before OpenCL 1.1:
+            _ => 1,
+        };
+
+        let mut limit = cmp::min(kernel_limit, self.queue.device_max_wg_size());
+        if let Some(&max_wi_size) = self.queue.device_max_wi_sizes().first() {
+            limit = cmp::min(limit, max_wi_size);
+        }
+        let limit = cmp::max(limit, 1);
+
+        let lws = match (limit / multiple) * multiple {
+            0 => limit,
+            lws => cmp::min(lws, try!(util::padded_len(total_items, multiple))),
+        };
+        let gws = try!(util::padded_len(total_items, lws));
+
+        Ok((SpatialDims::One(gws), SpatialDims::One(lws)))
     }
 
     /// Returns a reference to the queue associated with this ProQue.
@@ -353,42 +430,46 @@ impl ProQue {
         self.dims_result().expect(DIMS_ERR_MSG)
     }
 
-    /// Returns the current `dims` or an error.
+    /// Returns the current `dims` or a `DimsUnspecified` error.
     ///
     /// [UNSTABLE]: Evaluate which 'dims' method to keep. Leaning towards the
     /// above, panicing version at the moment.
     pub fn dims_result(&self) -> OclResult<&SpatialDims> {
         match self.dims {
             Some(ref dims) => Ok(dims),
-            None => OclError::err(DIMS_ERR_MSG),
+            None => OclError::err_dims_unspecified("ocl::ProQue::dims_result"),
         }
     }
 }
 
+/// A `ProQue` without dimensions behaves as `SpatialDims::Unspecified`.
 impl MemLen for ProQue {
     fn to_len(&self) -> usize {
-        self.dims().to_len()
+        self.dims.unwrap_or(SpatialDims::Unspecified).to_len()
     }
     fn to_len_padded(&self, incr: usize) -> usize {
-        self.dims().to_len_padded(incr)
+        self.dims.unwrap_or(SpatialDims::Unspecified).to_len_padded(incr)
     }
-    fn to_lens(&self) -> [usize; 3] { 
-        self.dims_result().expect("ocl::ProQue::to_lens()")
-            .to_lens().expect("ocl::ProQue::to_lens()")
+    fn to_lens(&self) -> OclResult<[usize; 3]> { 
+        match self.dims {
+            Some(ref dims) => dims.to_lens(),
+            None => OclError::err_dims_unspecified("ocl::ProQue::to_lens"),
+        }
     }
 }
 
+/// A `ProQue` without dimensions behaves as `SpatialDims::Unspecified`.
 impl WorkDims for ProQue {
     fn dim_count(&self) -> u32 {
-        self.dims_result().expect("ProQue::dim_count").dim_count()
+        self.dims.unwrap_or(SpatialDims::Unspecified).dim_count()
     }
 
     fn to_work_size(&self) -> Option<[usize; 3]> {
-        self.dims_result().expect("ProQue::to_work_size").to_work_size()
+        self.dims.and_then(|d| d.to_work_size())
     }
 
     fn to_work_offset(&self) -> Option<[usize; 3]> {
-        self.dims_result().expect("ProQue::to_work_offset").to_work_offset()
+        self.dims.and_then(|d| d.to_work_offset())
     }
 }
 
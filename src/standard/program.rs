@@ -1,17 +1,19 @@
 //! An OpenCL program.
 use std;
-use std::ops::{Deref, DerefMut};
+use std::ops::Deref;
+use std::sync::{Arc, Weak};
 use std::ffi::CString;
 use std::io::Read;
-use std::fs::File;
-use std::path::PathBuf;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::collections::HashSet;
 use std::convert::Into;
 
 use error::{Result as OclResult, Error as OclError};
 use core::{self, Program as ProgramCore, Context as ContextCore,
-    ProgramInfo, ProgramInfoResult, ProgramBuildInfo, ProgramBuildInfoResult};
-use standard::{Context, Device, DeviceSpecifier};
+    ProgramInfo, ProgramInfoResult, ProgramBuildInfo, ProgramBuildInfoResult, ProgramBinaryType};
+use standard::{Context, Device, DeviceSpecifier, Queue};
 
 
 /// A build option used by ProgramBuilder.
@@ -27,6 +29,7 @@ use standard::{Context, Device, DeviceSpecifier};
 #[derive(Clone, Debug)]
 pub enum BuildOpt {
     CmplrDefine { ident: String, val: String },
+    Define { ident: String, val: DefineValue },
     CmplrInclDir { path: String },
     CmplrOther(String),
     IncludeDefine { ident: String, val: String },
@@ -35,11 +38,11 @@ pub enum BuildOpt {
 }
 
 impl BuildOpt {
-    /// Returns a `BuildOpt::CmplrDefine`.
-    pub fn cmplr_def<S: Into<String>>(ident: S, val: i32) -> BuildOpt {
-        BuildOpt::CmplrDefine {
+    /// Returns a `BuildOpt::Define`.
+    pub fn cmplr_def<S: Into<String>, V: Into<DefineValue>>(ident: S, val: V) -> BuildOpt {
+        BuildOpt::Define {
             ident: ident.into(),
-            val: val.to_string(),
+            val: val.into(),
         }
     }
 
@@ -53,6 +56,157 @@ impl BuildOpt {
 }
 
 
+/// The value of a macro definition added with `ProgramBuilder::cmplr_def`.
+///
+/// Converted from integers (and `bool`), floats, strings, and `()` (a flag
+/// with no value). Each is rendered as an OpenCL C literal of the matching
+/// type (see `::to_literal`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum DefineValue {
+    /// An integer, suffixed with `L` if it does not fit in an `int`.
+    Int(i64),
+    /// A single-precision float, suffixed with `f`.
+    Float(f32),
+    /// A double-precision float (requires `cl_khr_fp64` to be used).
+    Double(f64),
+    /// A string literal.
+    Str(String),
+    /// A flag, defined without a value (`#ifdef` only).
+    Flag,
+}
+
+impl DefineValue {
+    /// Returns the OpenCL C literal for this value, or `None` for a flag.
+    ///
+    /// Floats are written with enough digits to round-trip exactly, using
+    /// `INFINITY` and `NAN` (`HUGE_VAL` for doubles) where required. Strings
+    /// are quoted with any character other than printable ASCII (including
+    /// spaces), a quote, or a backslash written as an octal escape so that
+    /// the literal contains no whitespace.
+    pub fn to_literal(&self) -> Option<String> {
+        match *self {
+            DefineValue::Int(val) => Some(if val == i64::min_value() {
+                    format!("({}L - 1)", i64::min_value() + 1)
+                } else if val < i32::min_value() as i64 || val > i32::max_value() as i64 {
+                    format!("{}L", val)
+                } else {
+                    val.to_string()
+                }),
+            DefineValue::Float(val) => Some(if val.is_nan() {
+                    "NAN".to_owned()
+                } else if val.is_infinite() {
+                    if val > 0.0 { "INFINITY".to_owned() } else { "(-INFINITY)".to_owned() }
+                } else {
+                    format!("{:?}f", val)
+                }),
+            DefineValue::Double(val) => Some(if val.is_nan() {
+                    "((double)NAN)".to_owned()
+                } else if val.is_infinite() {
+                    if val > 0.0 { "HUGE_VAL".to_owned() } else { "(-HUGE_VAL)".to_owned() }
+                } else {
+                    format!("{:?}", val)
+                }),
+            DefineValue::Str(ref val) => {
+                let mut literal = String::with_capacity(val.len() + 2);
+                literal.push('"');
+                for &byte in val.as_bytes() {
+                    match byte {
+                        b'"' => literal.push_str("\\\""),
+                        b'\\' => literal.push_str("\\\\"),
+                        0x21..=0x7e => literal.push(byte as char),
+                        _ => literal.push_str(&format!("\\{:03o}", byte)),
+                    }
+                }
+                literal.push('"');
+                Some(literal)
+            },
+            DefineValue::Flag => None,
+        }
+    }
+}
+
+macro_rules! impl_define_value_from_int {
+    ($($ty:ty),+) => ($(
+        impl From<$ty> for DefineValue {
+            fn from(val: $ty) -> DefineValue {
+                DefineValue::Int(val as i64)
+            }
+        }
+    )+)
+}
+
+impl_define_value_from_int!(i8, u8, i16, u16, i32, u32, i64);
+
+impl From<bool> for DefineValue {
+    fn from(val: bool) -> DefineValue {
+        DefineValue::Int(val as i64)
+    }
+}
+
+impl From<f32> for DefineValue {
+    fn from(val: f32) -> DefineValue {
+        DefineValue::Float(val)
+    }
+}
+
+impl From<f64> for DefineValue {
+    fn from(val: f64) -> DefineValue {
+        DefineValue::Double(val)
+    }
+}
+
+impl<'s> From<&'s str> for DefineValue {
+    fn from(val: &'s str) -> DefineValue {
+        DefineValue::Str(val.to_owned())
+    }
+}
+
+impl From<String> for DefineValue {
+    fn from(val: String) -> DefineValue {
+        DefineValue::Str(val)
+    }
+}
+
+impl From<()> for DefineValue {
+    fn from(_: ()) -> DefineValue {
+        DefineValue::Flag
+    }
+}
+
+
+/// Where the definitions added with `ProgramBuilder::cmplr_def` are placed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DefineStrategy {
+    /// Strings are placed in the source preamble and everything else in the
+    /// compiler options (the default).
+    Auto,
+    /// Everything is placed in the compiler options, as `-D NAME=value`.
+    ///
+    /// Some implementations strip or mangle quotes within compiler options
+    /// so string definitions may not be received intact.
+    BuildOptions,
+    /// Everything is placed in the source preamble, as `#define NAME value`.
+    Preamble,
+}
+
+impl DefineStrategy {
+    /// Returns true if `val` is placed in the source preamble.
+    fn use_preamble(&self, val: &DefineValue) -> bool {
+        match *self {
+            DefineStrategy::Auto => if let DefineValue::Str(_) = *val { true } else { false },
+            DefineStrategy::BuildOptions => false,
+            DefineStrategy::Preamble => true,
+        }
+    }
+}
+
+impl Default for DefineStrategy {
+    fn default() -> DefineStrategy {
+        DefineStrategy::Auto
+    }
+}
+
+
 /// A builder for `Program`.
 ///
 // [SOMEDAY TODO]: Keep track of line number range for each string and print 
@@ -63,6 +217,7 @@ pub struct ProgramBuilder {
     options: Vec<BuildOpt>,
     src_files: Vec<PathBuf>,
     device_spec: Option<DeviceSpecifier>,
+    define_strategy: DefineStrategy,
 }
 
 impl ProgramBuilder {
@@ -72,6 +227,7 @@ impl ProgramBuilder {
             options: Vec::with_capacity(64),
             src_files: Vec::with_capacity(16),
             device_spec: None,
+            define_strategy: DefineStrategy::Auto,
         }
     }
 
@@ -99,18 +255,47 @@ impl ProgramBuilder {
             &device_list[..])
     }
 
-    /// Adds a build option containing a compiler command line definition.
-    /// Formatted as `-D {name}={val}`.
+    /// Returns a new program built for the device of `queue`, within the
+    /// context `queue` belongs to, ignoring any devices specified with
+    /// `::devices`.
+    ///
+    /// Useful where only a queue is at hand (see
+    /// `ocl::multi::run_partitioned`).
+    pub fn build_for_queue(&self, queue: &Queue) -> OclResult<Program> {
+        Program::new(
+            try!(self.get_src_strings().map_err(|e| e.to_string())),
+            try!(self.get_compiler_options().map_err(|e| e.to_string())),
+            queue.context_core_as_ref(),
+            &[*queue.device()])
+    }
+
+    /// Adds a macro definition, formatted as `-D {name}={val}` within the
+    /// compiler options or as `#define {name} {val}` at the beginning of the
+    /// source, depending on the strategy (see `::define_strategy`).
+    ///
+    /// `val` may be an integer, `bool` (`1` or `0`), `f32` (e.g. `3.0f`),
+    /// `f64`, string (quoted and escaped), or `()` for a flag with no value
+    /// (see `DefineValue`).
     ///
     /// ## Example
     ///
-    /// `...cmplr_def("MAX_ITERS", 500)...`
+    /// `...cmplr_def("MAX_ITERS", 500).cmplr_def("SCALE", 0.5f32)
+    /// .cmplr_def("USE_FAST_PATH", ())...`
     ///
-    pub fn cmplr_def<S: Into<String>>(mut self, name: S, val: i32) -> ProgramBuilder {
+    pub fn cmplr_def<S: Into<String>, V: Into<DefineValue>>(mut self, name: S, val: V)
+            -> ProgramBuilder
+    {
         self.options.push(BuildOpt::cmplr_def(name, val));
         self
     }
 
+    /// Sets where definitions added with `::cmplr_def` are placed. Defaults
+    /// to `DefineStrategy::Auto`.
+    pub fn define_strategy(mut self, strategy: DefineStrategy) -> ProgramBuilder {
+        self.define_strategy = strategy;
+        self
+    }
+
     /// Adds a build option containing a raw compiler command line parameter. 
     /// Formatted as `{}` (exact text).
     ///
@@ -130,10 +315,11 @@ impl ProgramBuilder {
     }
 
     /// Adds the contents of a file to the program.
+    ///
+    /// The file is read when the program is built. A file which does not exist
+    /// or can not be read will cause the build to return an error.
     pub fn src_file<P: Into<PathBuf>>(mut self, file_path: P) -> ProgramBuilder {
         let file_path = file_path.into();
-        assert!(file_path.is_file(), "ProgramBuilder::src_file(): Source file error: \
-            '{}' does not exist.", file_path.display());
         self.src_files.push(file_path);
         self
     }   
@@ -178,6 +364,15 @@ impl ProgramBuilder {
                     opts.push(format!("-D{}={}", ident, val))
                 },
 
+                &BuildOpt::Define { ref ident, ref val } => {
+                    if !self.define_strategy.use_preamble(val) {
+                        match val.to_literal() {
+                            Some(literal) => opts.push(format!("-D{}={}", ident, literal)),
+                            None => opts.push(format!("-D{}", ident)),
+                        }
+                    }
+                },
+
                 &BuildOpt::CmplrInclDir { ref path } => {
                     opts.push(format!("-I{}", path))
                 },
@@ -215,7 +410,9 @@ impl ProgramBuilder {
             if src_file_history.contains(srcpath) { continue; }
             src_file_history.insert(srcpath.clone());
 
-            let mut src_file_handle = try!(File::open(srcpath));
+            let mut src_file_handle = try!(File::open(srcpath).map_err(|err| OclError::new(
+                format!("ocl::ProgramBuilder::get_src_strings: Unable to open source file \
+                '{}': {}", srcpath.display(), err))));
 
             try!(src_file_handle.read_to_end(&mut src_bytes));
             src_bytes.shrink_to_fit();
@@ -242,6 +439,15 @@ impl ProgramBuilder {
                     strings.push(try!(CString::new(format!("#define {}  {}\n", ident, val)
                         .into_bytes())));
                 },
+                &BuildOpt::Define { ref ident, ref val } => {
+                    if self.define_strategy.use_preamble(val) {
+                        let define = match val.to_literal() {
+                            Some(literal) => format!("#define {}  {}\n", ident, literal),
+                            None => format!("#define {}\n", ident),
+                        };
+                        strings.push(try!(CString::new(define.into_bytes())));
+                    }
+                },
                 &BuildOpt::IncludeRaw(ref text) => {
                     strings.push(try!(CString::new(text.clone().into_bytes())));
                 },
@@ -284,8 +490,19 @@ impl ProgramBuilder {
 /// Handled automatically. Feel free to store, clone, and share among threads
 /// as you please.
 ///
+/// ## Sharing
+///
+/// Cloning a `Program` only increments a reference count. Use `::downgrade`
+/// for a handle which does not keep the program alive.
+///
 #[derive(Clone, Debug)]
 pub struct Program {
+    inner: Arc<ProgramInner>,
+}
+
+/// The state shared by each clone of a `Program`.
+#[derive(Debug)]
+struct ProgramInner {
     obj_core: ProgramCore,
     devices: Vec<Device>,
 }
@@ -305,43 +522,109 @@ impl Program {
                 device_ids: &[Device]) -> OclResult<Program>
     {
         let obj_core = try!(core::create_build_program(context_obj_core, &src_strings, &cmplr_opts, 
-             device_ids).map_err(|e| e.to_string()));
+             device_ids));
 
         Ok(Program {
-            obj_core: obj_core,
-            devices: Vec::from(device_ids),
+            inner: Arc::new(ProgramInner {
+                obj_core: obj_core,
+                devices: Vec::from(device_ids),
+            }),
         })
     }
 
+    /// Returns a weak handle to this program which does not keep it alive.
+    pub fn downgrade(&self) -> WeakProgram {
+        WeakProgram { inner: Arc::downgrade(&self.inner) }
+    }
+
+    /// Returns the number of `Program` handles (clones) sharing this
+    /// program.
+    pub fn handle_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     pub fn core_as_ref(&self) -> &ProgramCore {
-        &self.obj_core
+        &self.inner.obj_core
     }
 
     /// Returns the list of devices associated with this program.
     pub fn devices(&self) -> &[Device] {
-        &self.devices
+        &self.inner.devices
     }
 
     /// Returns info about this program.
     pub fn info(&self, info_kind: ProgramInfo) -> ProgramInfoResult {
-        // match core::get_program_info(&self.obj_core, info_kind) {
+        // match core::get_program_info(&self.inner.obj_core, info_kind) {
         //     Ok(res) => res,
         //     Err(err) => ProgramInfoResult::Error(Box::new(err)),
         // }        
-        core::get_program_info(&self.obj_core, info_kind)
+        core::get_program_info(&self.inner.obj_core, info_kind)
     }
 
     /// Returns info about this program's build.
     ///
     /// TODO: Check that device is valid.
     pub fn build_info(&self, device: Device, info_kind: ProgramBuildInfo) -> ProgramBuildInfoResult {
-        // match core::get_program_build_info(&self.obj_core, &device, info_kind) {
+        // match core::get_program_build_info(&self.inner.obj_core, &device, info_kind) {
         //     Ok(res) => res,
         //     Err(err) => ProgramBuildInfoResult::Error(Box::new(err)),
         // }        
-        core::get_program_build_info(&self.obj_core, &device, info_kind)
+        core::get_program_build_info(&self.inner.obj_core, &device, info_kind)
+    }
+
+    /// Writes the binary (PTX, ISA, ELF, etc., depending on the platform) of
+    /// this program for each of its devices to a file within `dir` for
+    /// offline inspection, creating `dir` if necessary.
+    ///
+    /// Each file is named after its (sanitized) device name with an
+    /// extension guessed from the binary's contents and type: `.ptx` for text
+    /// beginning with `//` (NVIDIA), `.bin` for ELF and other executables,
+    /// `.obj` and `.lib` for compiled objects and libraries. Devices without
+    /// a binary (not built for) are skipped and listed in the returned
+    /// `BinaryDump`, which otherwise dereferences to the paths written.
+    pub fn dump_binaries(&self, dir: &Path) -> OclResult<BinaryDump> {
+        let binaries = try!(core::get_program_binaries(&self.inner.obj_core));
+
+        try!(fs::create_dir_all(dir).map_err(|err| OclError::new(format!(
+            "ocl::Program::dump_binaries: Unable to create directory '{}': {}", dir.display(),
+            err))));
+
+        let mut dump = BinaryDump { files: Vec::with_capacity(binaries.len()),
+            skipped: Vec::new() };
+
+        for (device, binary) in Device::list_from_core(binaries.iter().map(|&(d, _)| d).collect())
+                .into_iter().zip(binaries.iter().map(|&(_, ref b)| b))
+        {
+            if binary.is_empty() {
+                dump.skipped.push(device);
+                continue;
+            }
+
+            let binary_type = match self.build_info(device, ProgramBuildInfo::BinaryType) {
+                ProgramBuildInfoResult::BinaryType(ty) => Some(ty),
+                _ => None,
+            };
+
+            let stem = sanitize_file_name(&device.name());
+            let ext = binary_extension(binary, binary_type);
+            let mut path = dir.join(format!("{}.{}", stem, ext));
+            let mut idx = 1;
+
+            while dump.files.contains(&path) {
+                path = dir.join(format!("{}_{}.{}", stem, idx, ext));
+                idx += 1;
+            }
+
+            try!(File::create(&path).and_then(|mut file| file.write_all(binary))
+                .map_err(|err| OclError::new(format!("ocl::Program::dump_binaries: Unable to \
+                    write '{}': {}", path.display(), err))));
+
+            dump.files.push(path);
+        }
+
+        Ok(dump)
     }
 
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -370,12 +653,90 @@ impl Deref for Program {
     type Target = ProgramCore;
 
     fn deref(&self) -> &ProgramCore {
-        &self.obj_core
+        &self.inner.obj_core
     }
 }
 
-impl DerefMut for Program {
-    fn deref_mut(&mut self) -> &mut ProgramCore {
-        &mut self.obj_core
+/// The files written by `Program::dump_binaries`.
+///
+/// Dereferences to the paths of the files written, one per device with a
+/// binary.
+#[derive(Debug, Clone)]
+pub struct BinaryDump {
+    files: Vec<PathBuf>,
+    skipped: Vec<Device>,
+}
+
+impl BinaryDump {
+    /// Returns the paths of the files written.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Returns the devices skipped because the program had no binary for
+    /// them (e.g. was not built for them).
+    pub fn skipped(&self) -> &[Device] {
+        &self.skipped
+    }
+}
+
+impl Deref for BinaryDump {
+    type Target = [PathBuf];
+
+    fn deref(&self) -> &[PathBuf] {
+        &self.files
+    }
+}
+
+/// Returns `name` with every run of characters other than ASCII letters,
+/// digits, `-` and `.` replaced by a single `_`.
+fn sanitize_file_name(name: &str) -> String {
+    let mut sanitized = String::with_capacity(name.len());
+
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+            sanitized.push(c);
+        } else if !sanitized.ends_with('_') {
+            sanitized.push('_');
+        }
+    }
+
+    let sanitized = sanitized.trim_matches('_');
+    if sanitized.is_empty() { "device".to_owned() } else { sanitized.to_owned() }
+}
+
+/// Returns a file extension for a program binary, sniffed from its contents
+/// where possible and otherwise guessed from its type.
+fn binary_extension(binary: &[u8], binary_type: Option<ProgramBinaryType>) -> &'static str {
+    if binary.starts_with(b"\x7fELF") {
+        return "bin";
+    }
+
+    let text_start = binary.iter().position(|b| !b" \t\r\n".contains(b)).unwrap_or(0);
+    if binary[text_start..].starts_with(b"//") {
+        return "ptx";
+    }
+
+    match binary_type {
+        Some(ProgramBinaryType::CompiledObject) => "obj",
+        Some(ProgramBinaryType::Library) => "lib",
+        _ => "bin",
+    }
+}
+
+
+/// A weak handle to a `Program` which does not keep the program alive.
+///
+/// Create with `Program::downgrade`.
+#[derive(Debug, Clone)]
+pub struct WeakProgram {
+    inner: Weak<ProgramInner>,
+}
+
+impl WeakProgram {
+    /// Returns a handle to the program or `None` if every `Program` handle
+    /// has been dropped.
+    pub fn upgrade(&self) -> Option<Program> {
+        self.inner.upgrade().map(|inner| Program { inner: inner })
     }
 }
@@ -1,11 +1,15 @@
 //! An OpenCL command queue.
 
 use std;
-use std::ops::{Deref, DerefMut};
-use error::{Result as OclResult};
+use std::borrow::Cow;
+use std::ops::Deref;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use error::{Error as OclError, Result as OclResult};
 use core::{self, CommandQueue as CommandQueueCore, Context as ContextCore,
-    CommandQueueInfo, CommandQueueInfoResult};
-use standard::{Context, Device};
+    CommandQueueInfo, CommandQueueInfoResult, CommandQueueProperties, Mem as MemCore, ClWaitList,
+    ClEventPtrNew};
+use standard::{Context, Device, DeviceLimits, Event, WaitOutcome};
 
 /// A command queue which manages all actions taken on kernels, buffers, and
 /// images.
@@ -14,51 +18,282 @@ use standard::{Context, Device};
 ///
 /// Underlying queue object is destroyed automatically.
 ///
+/// ## Device Limits
+///
+/// The work item sizes, version, endianness, and extensions of the
+/// associated device are queried once, upon creation, and cached for use
+/// when validating kernel launches and arguments. The platform version is
+/// cached as well. All other limits (see `DeviceLimits`) are shared with the
+/// context.
+///
+/// ## Sharing
+///
+/// A `Queue` is a handle to shared state and cloning one only increments a
+/// reference count. The queue object is released once the last handle (and
+/// every buffer, image, and kernel using it) is dropped. Use `::downgrade`
+/// for a handle which does not keep it alive.
+///
+/// ## Default Queues
+///
+/// Commands on buffers, images, and kernels are enqueued on the first queue
+/// found, in order, among:
+///
+/// 1. The queue specified for the command (`BufferCmd::queue`,
+///    `ImageCmd::queue`, or `KernelCmd::queue`).
+/// 2. The default queue of the object (set upon creation with a queue or
+///    with `::set_default_queue`).
+/// 3. The default queue of the context the object was created from, for
+///    objects created with a context rather than a queue (see
+///    `Context::set_default_queue`). Changes to it are picked up by
+///    subsequent commands.
+///
+/// If none is found the command returns an error.
+///
 // TODO: Implement a constructor which accepts a DeviceIdCore.
 #[derive(Clone, Debug)]
 pub struct Queue {
+    inner: Arc<QueueInner>,
+}
+
+/// The state shared by each clone of a `Queue`.
+#[derive(Debug)]
+struct QueueInner {
     obj_core: CommandQueueCore,
     context_obj_core: ContextCore,
     device: Device,
+    limits: Arc<DeviceLimits>,
+    max_wi_sizes: Vec<usize>,
+    version: (u32, u32),
+    platform_version: (u32, u32),
+    endian_little: bool,
+    extensions: String,
 }
 
 impl Queue {
     /// Returns a new Queue on the device specified by `device`. 
     pub fn new(context: &Context, device: Device) -> OclResult<Queue> {
         let obj_core = try!(core::create_command_queue(context, &device));
+        let limits = try!(context.limits_of(&device));
+        let max_wi_sizes = try!(device.max_wi_sizes());
+        let version = try!(device.version_number());
+        let platform_version = match context.platform() {
+            Some(platform) => try!(platform.version_number()),
+            None => version,
+        };
+        let endian_little = try!(device.endian_little());
+        let extensions = try!(device.extensions());
 
         Ok(Queue {
-            obj_core: obj_core,
-            context_obj_core: context.core_as_ref().clone(),
-            device: device, 
+            inner: Arc::new(QueueInner {
+                obj_core: obj_core,
+                context_obj_core: context.core_as_ref().clone(),
+                device: device, 
+                limits: limits,
+                max_wi_sizes: max_wi_sizes,
+                version: version,
+                platform_version: platform_version,
+                endian_little: endian_little,
+                extensions: extensions,
+            }),
         })
     }
 
+    /// Returns a weak handle to this queue which does not keep it alive.
+    pub fn downgrade(&self) -> WeakQueue {
+        WeakQueue { inner: Arc::downgrade(&self.inner) }
+    }
+
+    /// Returns the number of `Queue` handles (clones) sharing this queue,
+    /// including those held by buffers, images, and kernels.
+    pub fn handle_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+
     /// Blocks until all commands in this queue have completed before returning.
-    pub fn finish(&self) {
-        core::finish(&self.obj_core).unwrap();
+    pub fn finish(&self) -> OclResult<()> {
+        core::finish(&self.inner.obj_core)
+    }
+
+    /// Waits for all commands enqueued on this queue so far to complete for
+    /// at most `timeout`, returning whether or not they did.
+    ///
+    /// A marker is enqueued and waited on (see `Event::wait_timeout`). The
+    /// timeout is purely observational: commands still running when it
+    /// elapses are not cancelled.
+    pub fn finish_timeout(&self, timeout: Duration) -> OclResult<WaitOutcome> {
+        let mut marker = Event::empty();
+        try!(self.enqueue_marker(None, Some(&mut marker)));
+        marker.wait_timeout(timeout)
     }
 
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     pub fn core_as_ref(&self) -> &CommandQueueCore {
-        &self.obj_core
+        &self.inner.obj_core
     }
 
     /// Returns a reference to the core pointer wrapper of the context
     /// associated with this queue, usable by functions in the `core` module.
     pub fn context_core_as_ref(&self) -> &ContextCore {
-        &self.context_obj_core
+        &self.inner.context_obj_core
     }
 
     /// Returns the OpenCL device associated with this queue.
     pub fn device(&self) -> &Device {
-        &self.device
+        &self.inner.device
+    }
+
+    /// Returns the maximum work group size of the associated device (cached).
+    pub fn device_max_wg_size(&self) -> usize {
+        self.inner.limits.max_wg_size()
+    }
+
+    /// Returns the maximum number of work items for each dimension of a work
+    /// group on the associated device (cached).
+    ///
+    /// The length of the returned slice is the maximum number of work item
+    /// dimensions supported by the device.
+    pub fn device_max_wi_sizes(&self) -> &[usize] {
+        &self.inner.max_wi_sizes
+    }
+
+    /// Returns the OpenCL version supported by the associated device as a
+    /// `(major, minor)` pair (cached).
+    pub fn device_version(&self) -> (u32, u32) {
+        self.inner.version
+    }
+
+    /// Returns the OpenCL version supported by the associated platform as a
+    /// `(major, minor)` pair (cached).
+    ///
+    /// Falls back to the device version if the context was created without
+    /// a platform.
+    pub fn platform_version(&self) -> (u32, u32) {
+        self.inner.platform_version
+    }
+
+    /// Returns the cached limits of the associated device.
+    pub fn device_limits(&self) -> &DeviceLimits {
+        &self.inner.limits
+    }
+
+    /// Returns true if the associated device is little endian (cached).
+    pub fn device_endian_little(&self) -> bool {
+        self.inner.endian_little
+    }
+
+    /// Returns true if the byte order of the associated device differs from
+    /// that of the host (cached).
+    pub fn device_endian_mismatch(&self) -> bool {
+        self.inner.endian_little != cfg!(target_endian = "little")
+    }
+
+    /// Returns true if the associated device shares a unified memory
+    /// subsystem with the host (cached).
+    ///
+    /// Treated as false if the device does not report it.
+    pub fn device_host_unified_memory(&self) -> bool {
+        self.inner.limits.host_unified_memory()
+    }
+
+    /// Returns true if the associated device supports the extension named
+    /// `extension` (cached).
+    pub fn device_has_extension(&self, extension: &str) -> bool {
+        self.inner.extensions.split_whitespace().any(|ext| ext == extension)
+    }
+
+    /// Enqueues a closure to be run on the host, in order with the other
+    /// commands in this queue, without blocking the calling thread.
+    ///
+    /// The closure receives the contents of each of the memory objects in
+    /// `mems` as a mutable byte slice (in the same order). Panics within it
+    /// are caught and printed rather than propagated.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the device does not support native kernels (see
+    /// `Device::execution_capabilities`). Typically only CPU devices do.
+    ///
+    /// ## Examples
+    ///
+    /// ```text
+    /// queue.enqueue_native(&[buffer.core_as_ref()], None, None, |mems| {
+    ///     for byte in mems[0].iter_mut() { *byte += 1; }
+    /// }).unwrap();
+    /// ```
+    pub fn enqueue_native<F>(&self, mems: &[&MemCore], ewait: Option<&ClWaitList>,
+            enew: Option<&mut ClEventPtrNew>, func: F) -> OclResult<()>
+            where F: FnOnce(&mut [&mut [u8]]) + Send + 'static
+    {
+        if !try!(self.inner.device.execution_capabilities()).contains(core::EXEC_NATIVE_KERNEL) {
+            return OclError::err(format!("ocl::Queue::enqueue_native: The device, '{}', does \
+                not support native kernels.", self.inner.device.name()));
+        }
+
+        core::enqueue_native(&self.inner.obj_core, mems, ewait, enew, func)
+    }
+
+    /// Enqueues a marker command which completes once each event in `ewait`
+    /// (or, if none are specified, all previously enqueued commands) has
+    /// completed.
+    ///
+    /// The deprecated OpenCL 1.1 commands are used to emulate this on older
+    /// platforms (see `core::enqueue_marker_compat`).
+    pub fn enqueue_marker(&self, ewait: Option<&ClWaitList>, enew: Option<&mut ClEventPtrNew>)
+            -> OclResult<()>
+    {
+        core::enqueue_marker_compat(&self.inner.obj_core, ewait, enew, self.inner.platform_version)
+    }
+
+    /// Enqueues a barrier command which prevents subsequently enqueued
+    /// commands from starting until each event in `ewait` (or, if none are
+    /// specified, all previously enqueued commands) has completed.
+    ///
+    /// The deprecated OpenCL 1.1 commands are used to emulate this on older
+    /// platforms (see `core::enqueue_barrier_compat`).
+    pub fn enqueue_barrier(&self, ewait: Option<&ClWaitList>, enew: Option<&mut ClEventPtrNew>)
+            -> OclResult<()>
+    {
+        core::enqueue_barrier_compat(&self.inner.obj_core, ewait, enew, self.inner.platform_version)
+    }
+
+    /// Keeps `payload` alive until every command enqueued on this queue so
+    /// far has completed, then drops it.
+    ///
+    /// A marker is enqueued and `payload` attached to its event (see
+    /// `Event::retain_until_complete`). Use this to keep memory objects or
+    /// host data referenced by non-blocking commands alive when the commands
+    /// were enqueued with borrowed data.
+    ///
+    /// ## Errors
+    ///
+    /// If the marker can not be enqueued, the queue is finished before
+    /// `payload` is dropped and the error returned.
+    pub fn retain_until_complete<P: Send + 'static>(&self, payload: P) -> OclResult<()> {
+        let mut marker = Event::empty();
+
+        if let Err(err) = self.enqueue_marker(None, Some(&mut marker)) {
+            core::finish(&self.inner.obj_core).ok();
+            drop(payload);
+            return Err(err);
+        }
+
+        marker.retain_until_complete(payload)
     }
 
     /// Returns info about this queue.
     pub fn info(&self, info_kind: CommandQueueInfo) -> CommandQueueInfoResult {   
-        core::get_command_queue_info(&self.obj_core, info_kind)
+        core::get_command_queue_info(&self.inner.obj_core, info_kind)
+    }
+
+    /// Returns the properties this queue was created with.
+    pub fn properties(&self) -> OclResult<CommandQueueProperties> {
+        match self.info(CommandQueueInfo::Properties) {
+            CommandQueueInfoResult::Properties(props) => Ok(props),
+            CommandQueueInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Queue::properties: Unexpected 'CommandQueueInfoResult' \
+                variant."),
+        }
     }
 
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -79,7 +314,7 @@ impl std::fmt::Display for Queue {
 
 impl AsRef<CommandQueueCore> for Queue {
     fn as_ref(&self) -> &CommandQueueCore {
-        &self.obj_core
+        &self.inner.obj_core
     }
 }
 
@@ -87,12 +322,45 @@ impl Deref for Queue {
     type Target = CommandQueueCore;
 
     fn deref(&self) -> &CommandQueueCore {
-        &self.obj_core
+        &self.inner.obj_core
+    }
+}
+
+/// Returns the queue a command is enqueued on: the queue specified for the
+/// command, otherwise the default queue of the object, otherwise the default
+/// queue of the context the object was created from (see the `Queue` docs).
+pub(crate) fn resolve_queue<'q>(op: &'static str, command: Option<&'q Queue>,
+        object: Option<&'q Queue>, context: Option<&Context>) -> OclResult<Cow<'q, Queue>>
+{
+    if let Some(queue) = command.or(object) {
+        return Ok(Cow::Borrowed(queue));
+    }
+
+    if let Some(queue) = context.and_then(|context| context.default_queue()) {
+        return Ok(Cow::Owned(queue));
     }
+
+    OclError::err(format!("{}: No queue available: none was specified for the command (with \
+        '::queue'), the object has no default queue (see '::set_default_queue'), and {} (see \
+        'Context::set_default_queue').", op, if context.is_some() {
+            "its context has no default queue"
+        } else {
+            "it was not created from a context"
+        }))
+}
+
+/// A weak handle to a `Queue` which does not keep the queue alive.
+///
+/// Create with `Queue::downgrade`.
+#[derive(Debug, Clone)]
+pub struct WeakQueue {
+    inner: Weak<QueueInner>,
 }
 
-impl DerefMut for Queue {
-    fn deref_mut(&mut self) -> &mut CommandQueueCore {
-        &mut self.obj_core
+impl WeakQueue {
+    /// Returns a handle to the queue or `None` if every `Queue` handle has
+    /// been dropped.
+    pub fn upgrade(&self) -> Option<Queue> {
+        self.inner.upgrade().map(|inner| Queue { inner: inner })
     }
 }
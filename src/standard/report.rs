@@ -0,0 +1,167 @@
+//! A plain text report of every platform and device, for bug reports.
+
+use std::fmt::Display;
+use core::{self, DeviceInfo, DeviceType, PlatformInfo};
+use error::Result as OclResult;
+use standard::{Platform, Device, DeviceInfoSource, fmt_mem_size};
+use util;
+
+/// Extensions listed (when supported) in each device section.
+const KEY_EXTENSIONS: &'static [&'static str] = &[
+    "cl_khr_fp64",
+    "cl_khr_fp16",
+    "cl_khr_global_int32_base_atomics",
+    "cl_khr_local_int32_base_atomics",
+    "cl_khr_int64_base_atomics",
+    "cl_khr_byte_addressable_store",
+    "cl_khr_3d_image_writes",
+    "cl_khr_image2d_from_buffer",
+    "cl_khr_gl_sharing",
+    "cl_khr_subgroups",
+    "cl_khr_il_program",
+    "cl_khr_spir",
+];
+
+/// Appends a `name: value` line, or `name: <error: ...>` if the value could
+/// not be queried.
+fn push_field<T: Display>(report: &mut String, indent: &str, name: &str, value: OclResult<T>) {
+    match value {
+        Ok(value) => report.push_str(&format!("{}{}: {}\n", indent, name, value)),
+        Err(err) => report.push_str(&format!("{}{}: <error: {}>\n", indent, name, err)),
+    }
+}
+
+fn platform_string(platform: &Platform, request: PlatformInfo) -> OclResult<String> {
+    core::get_platform_info_bytes(Some(*platform.as_core()), request)
+        .map(util::string_from_info_bytes)
+}
+
+fn device_string<S: DeviceInfoSource + ?Sized>(device: &S, request: DeviceInfo)
+        -> OclResult<String>
+{
+    device.info_bytes(request).map(util::string_from_info_bytes)
+}
+
+fn device_scalar<T, S: DeviceInfoSource + ?Sized>(device: &S, request: DeviceInfo)
+        -> OclResult<T>
+{
+    unsafe { util::bytes_into::<T>(try!(device.info_bytes(request))) }
+}
+
+fn device_mem_size<S: DeviceInfoSource + ?Sized>(device: &S, request: DeviceInfo)
+        -> OclResult<String>
+{
+    device_scalar::<u64, _>(device, request)
+        .map(|bytes| format!("{} ({} bytes)", fmt_mem_size(bytes), bytes))
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+/// Returns the names of each type in `device_type`, e.g. `GPU`.
+fn fmt_device_type(device_type: DeviceType) -> String {
+    let names: Vec<&str> = [(core::DEVICE_TYPE_DEFAULT, "DEFAULT"), (core::DEVICE_TYPE_CPU, "CPU"),
+            (core::DEVICE_TYPE_GPU, "GPU"), (core::DEVICE_TYPE_ACCELERATOR, "ACCELERATOR"),
+            (core::DEVICE_TYPE_CUSTOM, "CUSTOM")].iter()
+        .filter(|&&(flag, _)| device_type.contains(flag))
+        .map(|&(_, name)| name)
+        .collect();
+
+    if names.is_empty() { format!("{:?}", device_type) } else { names.join(" | ") }
+}
+
+/// Appends the section describing `device` to `report`, one field per line,
+/// each indented by `indent`.
+pub(crate) fn push_device_report<S>(report: &mut String, indent: &str, device: &S)
+        where S: DeviceInfoSource + ?Sized
+{
+    push_field(report, indent, "Type", device_scalar::<u64, _>(device, DeviceInfo::Type)
+        .map(|bits| fmt_device_type(DeviceType::from_bits_truncate(bits))));
+    push_field(report, indent, "Vendor", device_string(device, DeviceInfo::Vendor));
+    push_field(report, indent, "Version", device.version());
+    push_field(report, indent, "Driver version", device_string(device, DeviceInfo::DriverVersion));
+    push_field(report, indent, "OpenCL C version",
+        device_string(device, DeviceInfo::OpenclCVersion));
+    push_field(report, indent, "Compute units", device.max_compute_units());
+    push_field(report, indent, "Max clock frequency",
+        device_scalar::<u32, _>(device, DeviceInfo::MaxClockFrequency)
+            .map(|mhz| format!("{} MHz", mhz)));
+    push_field(report, indent, "Global memory", device_mem_size(device, DeviceInfo::GlobalMemSize));
+    push_field(report, indent, "Max allocation",
+        device_mem_size(device, DeviceInfo::MaxMemAllocSize));
+    push_field(report, indent, "Local memory", device_mem_size(device, DeviceInfo::LocalMemSize));
+    push_field(report, indent, "Max work group size", device.max_wg_size());
+    push_field(report, indent, "Max work item sizes", device.max_wi_sizes()
+        .map(|sizes| format!("{:?}", sizes)));
+    push_field(report, indent, "Image support",
+        device_scalar::<u32, _>(device, DeviceInfo::ImageSupport).map(|s| yes_no(s != 0)));
+    push_field(report, indent, "fp64", device.has_extension("cl_khr_fp64").map(yes_no));
+    push_field(report, indent, "fp16", device.has_extension("cl_khr_fp16").map(yes_no));
+    push_field(report, indent, "Key extensions", device.extensions().map(|e| {
+        let supported: Vec<&str> = KEY_EXTENSIONS.iter().cloned()
+            .filter(|&key| e.split_whitespace().any(|ext| ext == key))
+            .collect();
+        if supported.is_empty() { "(none)".to_owned() } else { supported.join(" ") }
+    }));
+}
+
+/// Returns a plain text report describing every platform and device
+/// (names, versions, memory sizes, limits, and key extensions), suitable for
+/// inclusion in a bug report.
+///
+/// Any value which can not be queried is shown as `<error: ...>` in place
+/// of the value.
+///
+/// See also `selection_report`, which describes the default selection.
+pub fn report() -> String {
+    let mut report = format!("OpenCL report (ocl {}):\n", env!("CARGO_PKG_VERSION"));
+
+    let platforms = match Platform::list() {
+        Ok(platforms) => platforms,
+        Err(err) => {
+            report.push_str(&format!("Platforms: <error: {}>\n", err));
+            return report;
+        },
+    };
+
+    if platforms.is_empty() {
+        report.push_str("Platforms: (none)\n");
+    }
+
+    for (platform_idx, platform) in platforms.iter().enumerate() {
+        match platform_string(platform, PlatformInfo::Name) {
+            Ok(name) => report.push_str(&format!("Platform [{}]: {}\n", platform_idx, name)),
+            Err(err) => report.push_str(&format!("Platform [{}]: <error: {}>\n", platform_idx,
+                err)),
+        }
+        push_field(&mut report, "    ", "Vendor", platform_string(platform, PlatformInfo::Vendor));
+        push_field(&mut report, "    ", "Version",
+            platform_string(platform, PlatformInfo::Version));
+        push_field(&mut report, "    ", "Profile",
+            platform_string(platform, PlatformInfo::Profile));
+        push_field(&mut report, "    ", "Extensions",
+            platform_string(platform, PlatformInfo::Extensions)
+                .map(|e| if e.trim().is_empty() { "(none)".to_owned() } else { e }));
+
+        let devices = match core::get_device_ids(platform.as_core(), Some(core::DEVICE_TYPE_ALL),
+                None) {
+            Ok(devices) => Device::list_from_core(devices),
+            Err(err) => {
+                report.push_str(&format!("    Devices: <error: {}>\n", err));
+                continue;
+            },
+        };
+
+        for (device_idx, device) in devices.iter().enumerate() {
+            match device_string(device, DeviceInfo::Name) {
+                Ok(name) => report.push_str(&format!("    Device [{}]: {}\n", device_idx, name)),
+                Err(err) => report.push_str(&format!("    Device [{}]: <error: {}>\n",
+                    device_idx, err)),
+            }
+            push_device_report(&mut report, "        ", device);
+        }
+    }
+
+    report
+}
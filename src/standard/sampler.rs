@@ -2,7 +2,7 @@
 
 use std;
 use std::ops::{Deref, DerefMut};
-use error::{Result as OclResult};
+use error::{Error as OclError, Result as OclResult};
 use core::{self, Sampler as SamplerCore, AddressingMode, FilterMode, SamplerInfo, SamplerInfoResult};
 use standard::Context;
 
@@ -64,6 +64,36 @@ impl Sampler {
         core::get_sampler_info(&self.0, info_kind)
     }
 
+    /// Returns true if the sampler uses normalized coordinates.
+    pub fn normalized_coords(&self) -> OclResult<bool> {
+        match self.info(SamplerInfo::NormalizedCoords) {
+            SamplerInfoResult::NormalizedCoords(norm) => Ok(norm),
+            SamplerInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Sampler::normalized_coords: Unexpected \
+                'SamplerInfoResult' variant."),
+        }
+    }
+
+    /// Returns the addressing mode of the sampler.
+    pub fn addressing_mode(&self) -> OclResult<AddressingMode> {
+        match self.info(SamplerInfo::AddressingMode) {
+            SamplerInfoResult::AddressingMode(mode) => Ok(mode),
+            SamplerInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Sampler::addressing_mode: Unexpected \
+                'SamplerInfoResult' variant."),
+        }
+    }
+
+    /// Returns the filter mode of the sampler.
+    pub fn filter_mode(&self) -> OclResult<FilterMode> {
+        match self.info(SamplerInfo::FilterMode) {
+            SamplerInfoResult::FilterMode(mode) => Ok(mode),
+            SamplerInfoResult::Error(err) => Err(*err),
+            _ => OclError::err("ocl::Sampler::filter_mode: Unexpected \
+                'SamplerInfoResult' variant."),
+        }
+    }
+
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Sampler")
             .field("ReferenceCount", &self.info(SamplerInfo::ReferenceCount))
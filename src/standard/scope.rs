@@ -0,0 +1,176 @@
+//! Scoped asynchronous commands which borrow host memory.
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use core::{self, OclPrm};
+use error::Result as OclResult;
+use standard::{Queue, Buffer, Kernel, Event};
+
+/// Runs `f`, within which non-blocking reads and writes may borrow host
+/// memory, returning only once every command enqueued through the `Scope`
+/// has completed.
+///
+/// Borrowed host memory must outlive the call to `scope` (a local variable
+/// of `f` can not be used), guaranteeing that it remains valid until the
+/// commands using it complete. This holds even if `f` returns an error or
+/// panics.
+///
+/// If waiting for a command fails, each queue used within the scope is
+/// finished before the error is returned. An error returned by `f` takes
+/// precedence over one encountered while waiting.
+///
+/// ## Examples
+///
+/// ```text
+/// let mut result = vec![0.0f32; buffer.len()];
+///
+/// ocl::scope(|s| {
+///     try!(s.write_async(&buffer, &src));
+///     try!(s.enq_kernel(&kernel));
+///     try!(s.read_async(&buffer, &mut result));
+///     Ok(())
+/// }).unwrap();
+///
+/// // `result` is ready.
+/// ```
+///
+/// Host memory which does not outlive the scope is rejected:
+///
+/// ```compile_fail
+/// # fn main() {}
+/// # fn f(buffer: ocl::Buffer<f32>) {
+/// ocl::scope(|s| {
+///     let mut local = vec![0.0f32; buffer.len()];
+///     try!(s.read_async(&buffer, &mut local));
+///     Ok(())
+/// }).unwrap();
+/// # }
+/// ```
+pub fn scope<'a, F, R>(f: F) -> OclResult<R>
+        where F: FnOnce(&mut Scope<'a>) -> OclResult<R>
+{
+    let mut scope = Scope {
+        events: Vec::new(),
+        queues: Vec::new(),
+        _borrows: PhantomData,
+    };
+
+    // Should `f` panic, `scope` waits when dropped during unwinding:
+    let result = f(&mut scope);
+    let wait_result = scope.wait_all();
+    let value = try!(result);
+    try!(wait_result);
+    Ok(value)
+}
+
+/// Enqueues non-blocking commands which borrow host memory for at least the
+/// lifetime `'a`, the duration of a call to `scope`.
+///
+/// Each method returns the event associated with the command it enqueues,
+/// which may be used to order other commands after it (commands enqueued on
+/// the same in-order queue complete in order regardless). All events are
+/// available from `::events`.
+pub struct Scope<'a> {
+    events: Vec<Event>,
+    queues: Vec<Queue>,
+    // Invariant in `'a` so that borrows can not be moved between scopes:
+    _borrows: PhantomData<Cell<&'a mut ()>>,
+}
+
+impl<'a> Scope<'a> {
+    /// Enqueues a non-blocking write of `data` to the beginning of `buffer`
+    /// on its default queue.
+    pub fn write_async<T: OclPrm>(&mut self, buffer: &Buffer<T>, data: &'a [T])
+            -> OclResult<Event>
+    {
+        let queue = try!(buffer.resolve_queue());
+        let mut event = Event::empty();
+        try!(buffer.cmd().queue(&queue).write(data).block(false).enew(&mut event).enq());
+        Ok(self.register(&queue, event))
+    }
+
+    /// Enqueues a non-blocking read from the beginning of `buffer` into
+    /// `data` on its default queue.
+    ///
+    /// Byte swapped reads (see `Buffer::swap_bytes`) return an error, as
+    /// with `BufferCmd::read_async`.
+    pub fn read_async<T: OclPrm>(&mut self, buffer: &Buffer<T>, data: &'a mut [T])
+            -> OclResult<Event>
+    {
+        let queue = try!(buffer.resolve_queue());
+        let mut event = Event::empty();
+        // The read is waited on before `data` can be accessed again:
+        try!(unsafe { buffer.cmd().queue(&queue).read_async(data).enew(&mut event).enq() });
+        Ok(self.register(&queue, event))
+    }
+
+    /// Enqueues a non-blocking read from the beginning of `buffer` into
+    /// `data` on its default queue, once `event` has completed (see
+    /// `::read_async`).
+    pub fn read_async_after<T: OclPrm>(&mut self, buffer: &Buffer<T>, data: &'a mut [T],
+            event: &Event) -> OclResult<Event>
+    {
+        let queue = try!(buffer.resolve_queue());
+        let mut read_event = Event::empty();
+        // The read is waited on before `data` can be accessed again:
+        try!(unsafe { buffer.cmd().queue(&queue).read_async(data).ewait(event)
+            .enew(&mut read_event).enq() });
+        Ok(self.register(&queue, read_event))
+    }
+
+    /// Enqueues `kernel` on its default queue (or that of its context) with its
+    /// default work sizes.
+    pub fn enq_kernel(&mut self, kernel: &Kernel) -> OclResult<Event> {
+        let queue = try!(kernel.resolve_queue());
+        let mut event = Event::empty();
+        try!(kernel.cmd().queue(&queue).enew(&mut event).enq());
+        Ok(self.register(&queue, event))
+    }
+
+    /// Returns the events of every command enqueued so far, in order.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Records `event`, and the queue it was enqueued on, to be waited on
+    /// before the scope ends.
+    fn register(&mut self, queue: &Queue, event: Event) -> Event {
+        let queue_ptr = unsafe { queue.core_as_ref().as_ptr() };
+
+        if !self.queues.iter().any(|q| unsafe { q.core_as_ref().as_ptr() } == queue_ptr) {
+            self.queues.push(queue.clone());
+        }
+
+        self.events.push(event.clone());
+        event
+    }
+
+    /// Waits for every command to complete, finishing each queue should a
+    /// wait fail.
+    fn wait_all(&mut self) -> OclResult<()> {
+        let mut result = Ok(());
+
+        for event in self.events.iter() {
+            if let Err(err) = event.wait() {
+                result = Err(err);
+                break;
+            }
+        }
+
+        if result.is_err() {
+            for queue in self.queues.iter() {
+                core::finish(queue).ok();
+            }
+        }
+
+        self.events.clear();
+        self.queues.clear();
+        result
+    }
+}
+
+impl<'a> Drop for Scope<'a> {
+    fn drop(&mut self) {
+        self.wait_all().ok();
+    }
+}
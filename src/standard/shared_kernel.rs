@@ -0,0 +1,81 @@
+//! A kernel which may be shared among threads.
+
+use std::sync::{Mutex, MutexGuard};
+use standard::Kernel;
+
+/// A kernel which may be shared among threads (it is `Sync`), created with
+/// `Kernel::into_shared`.
+///
+/// Setting arguments and enqueuing are serialized by an internal lock. Each
+/// thread locks the kernel (with `::lock` or `::with`), sets the arguments
+/// it needs, and enqueues before releasing it, so no other thread can change
+/// an argument in between.
+///
+/// ## Performance
+///
+/// Threads wait on each other for the whole of each set-and-enqueue (and,
+/// for blocking enqueues, for the command itself). Argument caching (see
+/// `Kernel::cache_args`) means that every argument a thread relies on must
+/// be set each time it holds the lock, but only those changed by another
+/// thread are passed to the driver. When threads launch frequently, an
+/// identical kernel for each thread (created from the same program) avoids
+/// the contention entirely and is usually preferable. `Kernel` itself
+/// remains lock-free.
+///
+/// ## Examples
+///
+/// ```text
+/// let kernel = Arc::new(proque.create_kernel("add").unwrap()
+///     .arg_buf(&buffer)
+///     .arg_scl_named::<f32>("addend", None)
+///     .into_shared());
+///
+/// // On any thread:
+/// kernel.with(|k| {
+///     try!(k.set_arg_scl_named("addend", 5.0f32));
+///     k.enq()
+/// }).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SharedKernel {
+    kernel: Mutex<Kernel>,
+}
+
+impl SharedKernel {
+    /// Returns a new shared kernel.
+    pub fn new(kernel: Kernel) -> SharedKernel {
+        SharedKernel { kernel: Mutex::new(kernel) }
+    }
+
+    /// Locks the kernel, blocking until any other thread using it has
+    /// finished, and returns a guard through which arguments may be set and
+    /// the kernel enqueued.
+    ///
+    /// The lock is held until the guard is dropped.
+    pub fn lock(&self) -> MutexGuard<'_, Kernel> {
+        // Arguments set by a thread which panicked are simply overwritten by
+        // the next:
+        match self.kernel.lock() {
+            Ok(kernel) => kernel,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Locks the kernel and calls `f` with it, returning the result.
+    ///
+    /// Set all arguments and enqueue within `f` so that they are applied
+    /// together.
+    pub fn with<R, F>(&self, f: F) -> R
+            where F: FnOnce(&mut Kernel) -> R
+    {
+        f(&mut *self.lock())
+    }
+
+    /// Returns the kernel, consuming this `SharedKernel`.
+    pub fn into_inner(self) -> Kernel {
+        match self.kernel.into_inner() {
+            Ok(kernel) => kernel,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
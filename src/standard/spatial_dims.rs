@@ -24,6 +24,7 @@ use util;
 /// at any time. This is still a work in progress.
 ///
 #[derive(Clone, Debug, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SpatialDims {
     Unspecified,
     One     (usize),
@@ -77,7 +78,7 @@ impl SpatialDims {
     /// Returns a 3D size or an error if unspecified.
     pub fn to_lens(&self) -> OclResult<[usize; 3]> {
         match self {
-            &SpatialDims::Unspecified => Err(OclError::UnspecifiedDimensions),
+            &SpatialDims::Unspecified => OclError::err_dims_unspecified("ocl::SpatialDims::to_lens"),
             &SpatialDims::One(x) => Ok([x, 1, 1]),
             &SpatialDims::Two(x, y) => Ok([x, y, 1]),
             &SpatialDims::Three(x, y, z) => Ok([x, y, z]),
@@ -87,7 +88,7 @@ impl SpatialDims {
     /// Returns a 3D offset or an error if unspecified.
     pub fn to_offset(&self) -> OclResult<[usize; 3]> {
         match self {
-            &SpatialDims::Unspecified => Err(OclError::UnspecifiedDimensions),
+            &SpatialDims::Unspecified => OclError::err_dims_unspecified("ocl::SpatialDims::to_offset"),
             &SpatialDims::One(x) => Ok([x, 0, 0]),
             &SpatialDims::Two(x, y) => Ok([x, y, 0]),
             &SpatialDims::Three(x, y, z) => Ok([x, y, z]),
@@ -113,15 +114,105 @@ impl SpatialDims {
 
     /// Takes the length and rounds it up to the nearest `incr` or an error.
     pub fn try_to_padded_len(&self, incr: usize) -> OclResult<usize> {
-        Ok(util::padded_len(self.to_len(), incr))
+        util::padded_len(self.to_len(), incr)
     }
 
     /// Returns `true` if this `SpatialDims` is an `Unspecified` variant.
     pub fn is_unspecified(&self) -> bool {
         if let &SpatialDims::Unspecified = self { true } else { false }
     }
+
+    /// Returns the flat (linear) index of the element at `coords` or `None`
+    /// if `coords` are out of bounds or no dimensions have been specified.
+    ///
+    /// ## Ordering
+    ///
+    /// Coordinates are given as `[x, y, z]` where `x` corresponds to
+    /// dimension 0 (`get_global_id(0)` within a kernel), `y` to dimension 1,
+    /// and `z` to dimension 2. The first dimension, `x`, varies fastest:
+    ///
+    /// `index = (z * dims[1] * dims[0]) + (y * dims[0]) + x`
+    ///
+    /// which matches the usual linearization within a kernel, i.e.:
+    /// `get_global_id(0) + get_global_id(1) * get_global_size(0) + ...`.
+    ///
+    /// Coordinates for dimensions beyond those specified must be zero.
+    ///
+    pub fn to_index(&self, coords: [usize; 3]) -> Option<usize> {
+        let lens = match self.to_lens() {
+            Ok(lens) => lens,
+            Err(_) => return None,
+        };
+
+        if coords[0] >= lens[0] || coords[1] >= lens[1] || coords[2] >= lens[2] {
+            return None;
+        }
+
+        Some((coords[2] * lens[1] * lens[0]) + (coords[1] * lens[0]) + coords[0])
+    }
+
+    /// Returns the `[x, y, z]` coordinates of the element at the flat index,
+    /// `index`, or `None` if `index` is out of bounds or no dimensions have
+    /// been specified.
+    ///
+    /// The inverse of `::to_index`. See `::to_index` for ordering details.
+    ///
+    pub fn to_coords(&self, index: usize) -> Option<[usize; 3]> {
+        let lens = match self.to_lens() {
+            Ok(lens) => lens,
+            Err(_) => return None,
+        };
+
+        if index >= lens[0] * lens[1] * lens[2] {
+            return None;
+        }
+
+        let slice_len = lens[0] * lens[1];
+        Some([index % lens[0], (index % slice_len) / lens[0], index / slice_len])
+    }
+
+    /// Returns an iterator over the `[x, y, z]` coordinates of every element,
+    /// in flat index order (`x` varying fastest).
+    ///
+    /// The iterator will be empty if no dimensions have been specified.
+    ///
+    pub fn coords_iter(&self) -> SpatialDimsCoords {
+        SpatialDimsCoords { dims: self.clone(), index: 0, len: self.to_len() }
+    }
 }
 
+
+/// An iterator over the coordinates of every element within a `SpatialDims`.
+///
+/// Created by `SpatialDims::coords_iter`.
+///
+#[derive(Clone, Debug)]
+pub struct SpatialDimsCoords {
+    dims: SpatialDims,
+    index: usize,
+    len: usize,
+}
+
+impl Iterator for SpatialDimsCoords {
+    type Item = [usize; 3];
+
+    fn next(&mut self) -> Option<[usize; 3]> {
+        if self.index < self.len {
+            self.index += 1;
+            self.dims.to_coords(self.index - 1)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for SpatialDimsCoords {}
+
 impl MemLen for SpatialDims {
     fn to_len_padded(&self, incr: usize) -> usize {
         self.try_to_padded_len(incr).expect("ocl::SpatialDims::to_len_padded()")
@@ -131,8 +222,8 @@ impl MemLen for SpatialDims {
         self.to_len()
     }
     
-    fn to_lens(&self) -> [usize; 3] { 
-        self.to_lens().expect("SpatialDims::<MemLen>::to_lens()")
+    fn to_lens(&self) -> OclResult<[usize; 3]> { 
+        self.to_lens()
     }
 }
 
@@ -0,0 +1,313 @@
+//! A pool of pinned staging buffers used for chunked transfers.
+
+use std;
+use std::mem;
+use std::slice;
+use std::sync::Mutex;
+use libc::c_void;
+use core::{self, OclPrm, Mem as MemCore, Event as EventCore, ClWaitList};
+use error::{Error as OclError, Result as OclResult};
+use util;
+use standard::{Queue, Buffer};
+use super::buffer::swap_slice;
+
+/// A persistently mapped staging buffer along with the event of the last
+/// transfer to use it.
+struct StagingChunk {
+    mem: MemCore,
+    ptr: *mut c_void,
+    event: Option<EventCore>,
+}
+
+impl StagingChunk {
+    /// Blocks until the last transfer using this chunk has completed.
+    fn wait(&mut self) -> OclResult<()> {
+        match self.event.take() {
+            Some(event) => core::wait_for_event(&event),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the first `len` elements of the mapped memory.
+    ///
+    /// The caller must ensure that no transfer using this chunk is in
+    /// progress and that `len` elements fit within the chunk.
+    unsafe fn as_slice<T: OclPrm>(&self, len: usize) -> &[T] {
+        slice::from_raw_parts(self.ptr as *const T, len)
+    }
+
+    /// Returns the first `len` elements of the mapped memory, mutably.
+    ///
+    /// The caller must ensure that no transfer using this chunk is in
+    /// progress and that `len` elements fit within the chunk.
+    unsafe fn as_slice_mut<T: OclPrm>(&mut self, len: usize) -> &mut [T] {
+        slice::from_raw_parts_mut(self.ptr as *mut T, len)
+    }
+}
+
+// The mapped pointer is only ever accessed while holding the pool's lock.
+unsafe impl Send for StagingChunk {}
+
+
+/// A pool of pinned (`MEM_ALLOC_HOST_PTR`), persistently mapped staging
+/// buffers used to transfer data between host memory and device buffers in
+/// chunks.
+///
+/// Each transfer is split into chunks of up to `chunk_size` bytes which are
+/// pipelined through the pool: the host copies one chunk into (or out of)
+/// a staging buffer while the transfers of the others are in progress. This
+/// avoids the driver allocating a temporary bounce buffer for each call when
+/// transferring from pageable host memory.
+///
+/// Transfers are enqueued on the pool's queue and block until complete. Only
+/// one transfer runs at a time; concurrent calls from other threads wait
+/// their turn.
+///
+/// ## Examples
+///
+/// ```text
+/// let pool = StagingPool::new(&queue, 1 << 20, 4).unwrap();
+/// pool.write(&buffer, &vec).unwrap();
+/// pool.read(&buffer, &mut vec).unwrap();
+/// ```
+pub struct StagingPool {
+    queue: Queue,
+    chunk_size: usize,
+    chunks: Mutex<Vec<StagingChunk>>,
+}
+
+impl StagingPool {
+    /// Returns a new pool of `chunk_count` staging buffers, each
+    /// `chunk_size` bytes in size, mapped using `queue`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if either `chunk_size` or `chunk_count` is zero.
+    pub fn new(queue: &Queue, chunk_size: usize, chunk_count: usize) -> OclResult<StagingPool> {
+        if chunk_size == 0 || chunk_count == 0 {
+            return OclError::err(format!("ocl::StagingPool::new: The chunk size ({}) and \
+                chunk count ({}) must both be non-zero.", chunk_size, chunk_count));
+        }
+
+        let mut chunks = Vec::with_capacity(chunk_count);
+
+        for _ in 0..chunk_count {
+            let mem = try!(unsafe { core::create_buffer::<u8>(queue.context_core_as_ref(),
+                core::MEM_READ_WRITE | core::MEM_ALLOC_HOST_PTR, chunk_size, None) });
+
+            let ptr = try!(unsafe { core::enqueue_map_buffer::<u8>(queue, &mem, true,
+                core::MAP_READ | core::MAP_WRITE, 0, chunk_size, None, None) });
+
+            chunks.push(StagingChunk { mem: mem, ptr: ptr, event: None });
+        }
+
+        Ok(StagingPool {
+            queue: queue.clone(),
+            chunk_size: chunk_size,
+            chunks: Mutex::new(chunks),
+        })
+    }
+
+    /// Writes `data` to the beginning of `buffer` through the pool.
+    ///
+    /// Blocks until the write is complete. Commands using `buffer` enqueued
+    /// on other queues must have completed beforehand.
+    ///
+    /// If byte swapping is enabled for `buffer` (see `Buffer::swap_bytes`),
+    /// elements are swapped within the staging buffers.
+    pub fn write<T: OclPrm>(&self, buffer: &Buffer<T>, data: &[T]) -> OclResult<()> {
+        let swap = buffer.swap_bytes() && self.queue.device_endian_mismatch();
+        buffer.mark_init();
+        self.write_mem(buffer.core_as_ref(), buffer.len(), swap, 0, data, self.chunk_size, None)
+    }
+
+    /// Writes `data` to `mem` (`mem_len` elements long), starting at
+    /// `offset`, through the pool in chunks of at most `chunk_size` bytes
+    /// (or the pool's chunk size if smaller), each waiting on `wait_list`.
+    ///
+    /// Blocks until the write is complete.
+    pub(crate) fn write_mem<T: OclPrm>(&self, mem: &MemCore, mem_len: usize, swap: bool,
+            offset: usize, data: &[T], chunk_size: usize, wait_list: Option<&ClWaitList>)
+            -> OclResult<()>
+    {
+        let mut chunks = self.lock();
+        let chunk_len = try!(self.chunk_len::<T>("write", &chunks, chunk_size, mem_len,
+            offset, data.len()));
+        let chunk_count = chunks.len();
+
+        for (i, src) in data.chunks(chunk_len).enumerate() {
+            let chunk = &mut chunks[i % chunk_count];
+            try!(chunk.wait());
+
+            let mut event = unsafe { EventCore::null() };
+
+            {
+                let staged = unsafe { chunk.as_slice_mut::<T>(src.len()) };
+                staged.copy_from_slice(src);
+                if swap { try!(swap_slice(staged)); }
+
+                try!(core::enqueue_write_buffer(&self.queue, mem, false,
+                    offset + i * chunk_len, staged, wait_list, Some(&mut event)));
+            }
+
+            chunk.event = Some(event);
+        }
+
+        for chunk in chunks.iter_mut() {
+            try!(chunk.wait());
+        }
+
+        Ok(())
+    }
+
+    /// Reads from the beginning of `buffer` into `data` through the pool.
+    ///
+    /// Blocks until the read is complete. Commands using `buffer` enqueued
+    /// on other queues must have completed beforehand.
+    ///
+    /// If byte swapping is enabled for `buffer` (see `Buffer::swap_bytes`),
+    /// elements are swapped after being copied into `data`.
+    pub fn read<T: OclPrm>(&self, buffer: &Buffer<T>, data: &mut [T]) -> OclResult<()> {
+        let swap = buffer.swap_bytes() && self.queue.device_endian_mismatch();
+        self.read_mem(buffer.core_as_ref(), buffer.len(), swap, 0, data, self.chunk_size, None)
+    }
+
+    /// Reads from `mem` (`mem_len` elements long), starting at `offset`,
+    /// into `data` through the pool in chunks of at most `chunk_size` bytes
+    /// (or the pool's chunk size if smaller), each waiting on `wait_list`.
+    ///
+    /// Blocks until the read is complete.
+    pub(crate) fn read_mem<T: OclPrm>(&self, mem: &MemCore, mem_len: usize, swap: bool,
+            offset: usize, data: &mut [T], chunk_size: usize, wait_list: Option<&ClWaitList>)
+            -> OclResult<()>
+    {
+        let mut chunks = self.lock();
+        let chunk_len = try!(self.chunk_len::<T>("read", &chunks, chunk_size, mem_len,
+            offset, data.len()));
+        let chunk_count = chunks.len();
+        let data_len = data.len();
+        let total_chunks = (data_len + chunk_len - 1) / chunk_len;
+
+        // Start as many reads as there are staging buffers:
+        for i in 0..std::cmp::min(total_chunks, chunk_count) {
+            try!(self.enqueue_read_chunk::<T>(&mut chunks[i], mem, offset + i * chunk_len,
+                std::cmp::min(chunk_len, data_len - i * chunk_len), wait_list));
+        }
+
+        // Copy out each chunk as its read completes, reusing its staging
+        // buffer for the next pending read:
+        for (i, dst) in data.chunks_mut(chunk_len).enumerate() {
+            let chunk = &mut chunks[i % chunk_count];
+            try!(chunk.wait());
+
+            dst.copy_from_slice(unsafe { chunk.as_slice::<T>(dst.len()) });
+            if swap { try!(swap_slice(dst)); }
+
+            let next = i + chunk_count;
+
+            if next < total_chunks {
+                try!(self.enqueue_read_chunk::<T>(chunk, mem, offset + next * chunk_len,
+                    std::cmp::min(chunk_len, data_len - next * chunk_len), wait_list));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the size of each staging buffer in bytes.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the number of staging buffers in the pool.
+    pub fn chunk_count(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns the queue used for transfers.
+    pub fn queue(&self) -> &Queue {
+        &self.queue
+    }
+
+    /// Enqueues a read of `len` elements of `mem`, starting at `offset`,
+    /// into the staging buffer of `chunk`.
+    fn enqueue_read_chunk<T: OclPrm>(&self, chunk: &mut StagingChunk, mem: &MemCore,
+            offset: usize, len: usize, wait_list: Option<&ClWaitList>) -> OclResult<()>
+    {
+        let mut event = unsafe { EventCore::null() };
+
+        unsafe {
+            let staged = chunk.as_slice_mut::<T>(len);
+            try!(core::enqueue_read_buffer(&self.queue, mem, false, offset,
+                staged, wait_list, Some(&mut event)));
+        }
+
+        chunk.event = Some(event);
+        Ok(())
+    }
+
+    /// Returns the number of elements of `T` which fit within a chunk of at
+    /// most `chunk_size` bytes after verifying that a transfer of `data_len`
+    /// elements to or from a buffer of `mem_len` elements, starting at
+    /// `offset`, is valid.
+    fn chunk_len<T: OclPrm>(&self, op: &str, chunks: &[StagingChunk], chunk_size: usize,
+            mem_len: usize, offset: usize, data_len: usize) -> OclResult<usize>
+    {
+        let chunk_size = std::cmp::min(chunk_size, self.chunk_size);
+        let chunk_len = chunk_size / mem::size_of::<T>();
+
+        if chunk_len == 0 {
+            return OclError::err(format!("ocl::StagingPool::{}: The chunk size ({} bytes) is \
+                smaller than the size of a single element ({} bytes).", op, chunk_size,
+                mem::size_of::<T>()));
+        }
+
+        if offset > mem_len || data_len > mem_len - offset {
+            return OclError::err(format!("ocl::StagingPool::{}: The data length ({}) exceeds \
+                the buffer length ({}) less the offset ({}).", op, data_len, mem_len, offset));
+        }
+
+        for chunk in chunks.iter() {
+            if !try!(util::is_aligned(chunk.ptr as usize, mem::align_of::<T>())) {
+                return OclError::err(format!("ocl::StagingPool::{}: A staging buffer was \
+                    mapped at an address ({:?}) not aligned for the element type.", op,
+                    chunk.ptr));
+            }
+        }
+
+        Ok(chunk_len)
+    }
+
+    /// Locks and returns the list of staging buffers.
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<StagingChunk>> {
+        // Chunks are left in a consistent state if a transfer panics (any
+        // outstanding event is waited on before the chunk is reused):
+        match self.chunks.lock() {
+            Ok(chunks) => chunks,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl Drop for StagingPool {
+    /// Waits for any outstanding transfers then unmaps each staging buffer.
+    fn drop(&mut self) {
+        let mut chunks = self.lock();
+
+        for chunk in chunks.iter_mut() {
+            chunk.wait().ok();
+            core::enqueue_unmap_mem_object(&self.queue, &chunk.mem, chunk.ptr, None, None).ok();
+        }
+
+        core::finish(&self.queue).ok();
+    }
+}
+
+impl std::fmt::Debug for StagingPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("StagingPool")
+            .field("chunk_size", &self.chunk_size)
+            .field("chunk_count", &self.chunk_count())
+            .finish()
+    }
+}
@@ -0,0 +1,163 @@
+//! Translation of device timestamps (as used by event profiling) to the host
+//! clock.
+
+use core::{ProfilingInfo, ProfilingInfoResult};
+use error::{Error as OclError, Result as OclResult};
+use standard::{Device, Event};
+use util;
+
+/// Converts device timestamps, such as the event profiling counters, into
+/// host clock nanoseconds (as returned by `Device::host_timer`), for example
+/// to place commands on a host-side tracing timeline.
+///
+/// Built from one or more `(device_timestamp, host_timestamp)` sample pairs
+/// (see `Device::device_and_host_timer`). With a single sample, timestamps
+/// are simply offset. With more, they are interpolated between the nearest
+/// samples (and extrapolated beyond the first and last), correcting for
+/// drift between the two clocks. Call `::resample` periodically (e.g. once
+/// per frame or batch) to keep the correction accurate over long runs.
+///
+/// Requires OpenCL 2.1 or later to sample (samples taken elsewhere may be
+/// supplied with `::from_samples`).
+#[derive(Debug, Clone)]
+pub struct TimelineTranslator {
+    device: Option<Device>,
+    samples: Vec<(u64, u64)>,
+}
+
+impl TimelineTranslator {
+    /// Returns a new translator for `device`, initialized with one sample.
+    pub fn new(device: Device) -> OclResult<TimelineTranslator> {
+        let sample = try!(device.device_and_host_timer());
+
+        Ok(TimelineTranslator {
+            device: Some(device),
+            samples: vec![sample],
+        })
+    }
+
+    /// Returns a new translator from previously taken `(device_timestamp,
+    /// host_timestamp)` samples, which may be in any order.
+    ///
+    /// The translator can not `::resample`.
+    pub fn from_samples(samples: &[(u64, u64)]) -> OclResult<TimelineTranslator> {
+        if samples.is_empty() {
+            return OclError::err("ocl::TimelineTranslator::from_samples: At least one sample \
+                is required.");
+        }
+
+        let mut translator = TimelineTranslator { device: None, samples: Vec::new() };
+        for &sample in samples {
+            translator.insert(sample);
+        }
+        Ok(translator)
+    }
+
+    /// Takes another sample from the device, improving the drift correction
+    /// of timestamps near (and after) now.
+    pub fn resample(&mut self) -> OclResult<()> {
+        let sample = match self.device {
+            Some(ref device) => try!(device.device_and_host_timer()),
+            None => return OclError::err("ocl::TimelineTranslator::resample: This translator \
+                was created from samples and has no device to sample."),
+        };
+
+        self.insert(sample);
+        Ok(())
+    }
+
+    /// Returns the `(device_timestamp, host_timestamp)` samples in order of
+    /// device timestamp.
+    pub fn samples(&self) -> &[(u64, u64)] {
+        &self.samples
+    }
+
+    /// Returns the host clock time, in nanoseconds, corresponding to the
+    /// device clock time, `device_ns`.
+    pub fn to_host(&self, device_ns: u64) -> u64 {
+        // The sample at or before `device_ns`, clamped so that there is a
+        // following sample to interpolate (or extrapolate) towards:
+        let idx = if self.samples.len() == 1 {
+            0
+        } else {
+            match self.samples.iter().position(|&(d, _)| d > device_ns) {
+                Some(0) => 0,
+                Some(idx) => idx - 1,
+                None => self.samples.len() - 2,
+            }
+        };
+
+        let (d0, h0) = self.samples[idx];
+        let offset = device_ns as i128 - d0 as i128;
+
+        let host = match self.samples.get(idx + 1) {
+            Some(&(d1, h1)) => {
+                h0 as i128 + offset * (h1 as i128 - h0 as i128) / (d1 as i128 - d0 as i128)
+            },
+            None => h0 as i128 + offset,
+        };
+
+        if host < 0 {
+            0
+        } else if host > u64::max_value() as i128 {
+            u64::max_value()
+        } else {
+            host as u64
+        }
+    }
+
+    /// Returns the queued, submit, start, and end profiling counters of
+    /// `event`, which must have completed, translated to host clock time.
+    pub fn event_spans(&self, event: &Event) -> OclResult<EventSpans> {
+        Ok(EventSpans {
+            queued: self.to_host(try!(timestamp(event, ProfilingInfo::Queued))),
+            submit: self.to_host(try!(timestamp(event, ProfilingInfo::Submit))),
+            start: self.to_host(try!(timestamp(event, ProfilingInfo::Start))),
+            end: self.to_host(try!(timestamp(event, ProfilingInfo::End))),
+        })
+    }
+
+    /// Adds `sample`, keeping samples in order of device timestamp and
+    /// replacing any with the same device timestamp.
+    fn insert(&mut self, sample: (u64, u64)) {
+        match self.samples.binary_search_by_key(&sample.0, |&(d, _)| d) {
+            Ok(idx) => self.samples[idx] = sample,
+            Err(idx) => self.samples.insert(idx, sample),
+        }
+    }
+}
+
+/// The profiling counters of an event in host clock nanoseconds (see
+/// `TimelineTranslator::event_spans`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventSpans {
+    pub queued: u64,
+    pub submit: u64,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl EventSpans {
+    /// Returns the time spent in each stage of the command as a `(name,
+    /// begin, end)` span: `"queued"` (queued until submitted), `"submitted"`
+    /// (submitted until started), and `"running"` (started until ended).
+    pub fn spans(&self) -> [(&'static str, u64, u64); 3] {
+        [
+            ("queued", self.queued, self.submit),
+            ("submitted", self.submit, self.start),
+            ("running", self.start, self.end),
+        ]
+    }
+}
+
+/// Returns a profiling counter of `event`, in nanoseconds.
+fn timestamp(event: &Event, request: ProfilingInfo) -> OclResult<u64> {
+    match event.profiling_info(request) {
+        ProfilingInfoResult::TemporaryPlaceholderVariant(bytes) => unsafe {
+            util::bytes_to::<u64>(&bytes)
+        },
+        ProfilingInfoResult::Error(err) => Err(*err),
+        _ => OclError::err("ocl::TimelineTranslator::event_spans: Unexpected profiling info \
+            result."),
+    }
+}
@@ -0,0 +1,601 @@
+//! A stand-in for the OpenCL library (the `stub` feature).
+//!
+//! **This is not an OpenCL implementation.** It provides one platform with
+//! one (CPU) device and just enough of the API (creation, retain, release,
+//! and info queries, answered with canned values) for contexts, queues,
+//! buffers, programs, and user events to be created and dropped, allowing
+//! the crate (and crates which depend on it) to be built, linked, and unit
+//! tested on machines without an OpenCL driver.
+//!
+//! Nothing is ever executed. Every other function, including all enqueues
+//! and program builds, returns an error code without doing anything and
+//! records its name so that `core` functions return
+//! `Error::StubBackend(name)`.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::mem;
+use std::ptr;
+use std::slice;
+use std::sync::Mutex;
+use libc::{c_void, c_char, size_t};
+use cl_h::*;
+use error::Error as OclError;
+
+const PLATFORM: usize = 0x10;
+const DEVICE: usize = 0x20;
+
+const PLATFORM_NAME: &'static str = "ocl stub platform (not conformant)";
+const VERSION: &'static str = "OpenCL 1.2 ocl-stub";
+
+thread_local! {
+    static UNSUPPORTED: Cell<Option<&'static str>> = Cell::new(None);
+}
+
+/// The implementation of the functions provided (see `cl_h::ClApi`).
+pub struct Stub;
+
+enum Kind {
+    Context { properties: Vec<cl_context_properties> },
+    Queue { context: usize, properties: cl_bitfield },
+    Mem { context: usize, flags: cl_bitfield, size: usize, host_ptr: usize },
+    Program { context: usize, source: Vec<u8> },
+    Event { context: usize, status: cl_int },
+}
+
+struct Object {
+    ref_count: cl_uint,
+    kind: Kind,
+}
+
+struct Objects {
+    next_handle: usize,
+    live: HashMap<usize, Object>,
+}
+
+static OBJECTS: Mutex<Option<Objects>> = Mutex::new(None);
+
+/// Calls `f` with the table of live objects.
+fn with_objects<R, F: FnOnce(&mut Objects) -> R>(f: F) -> R {
+    let mut objects = match OBJECTS.lock() {
+        Ok(objects) => objects,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    if objects.is_none() {
+        *objects = Some(Objects { next_handle: 0x1000, live: HashMap::new() });
+    }
+
+    f(objects.as_mut().unwrap())
+}
+
+/// Returns the value returned by (and records the name of) a function
+/// which the stub does not provide.
+pub fn unsupported<R: ::cl_h::Unavailable>(fn_name: &'static str) -> R {
+    UNSUPPORTED.with(|u| u.set(Some(fn_name)));
+    R::unavailable()
+}
+
+/// Returns (and clears) the error for the most recent call on this thread
+/// to a function which the stub does not provide, if it has not already
+/// been reported.
+pub fn take_error() -> Option<OclError> {
+    UNSUPPORTED.with(|u| u.replace(None)).map(|fn_name| OclError::StubBackend(fn_name))
+}
+
+fn status(status: Status) -> cl_int {
+    status as cl_int
+}
+
+/// Sets `*errcode_ret` (if non-null) and returns `handle`.
+unsafe fn created(handle: usize, errcode: cl_int, errcode_ret: *mut cl_int) -> *mut c_void {
+    if !errcode_ret.is_null() { *errcode_ret = errcode; }
+    handle as *mut c_void
+}
+
+/// Adds an object, retaining the context it belongs to (if any), and
+/// returns its handle.
+fn create(context: Option<usize>, kind: Kind) -> Result<usize, cl_int> {
+    with_objects(|objs| {
+        if let Some(context) = context {
+            match objs.live.get_mut(&context) {
+                Some(&mut Object { ref mut ref_count, kind: Kind::Context { .. } }) => *ref_count += 1,
+                _ => return Err(status(Status::CL_INVALID_CONTEXT)),
+            }
+        }
+
+        let handle = objs.next_handle;
+        objs.next_handle += 0x10;
+        objs.live.insert(handle, Object { ref_count: 1, kind: kind });
+        Ok(handle)
+    })
+}
+
+/// Returns the handle of the context an object belongs to, if any.
+fn context_of(kind: &Kind) -> Option<usize> {
+    match *kind {
+        Kind::Context { .. } => None,
+        Kind::Queue { context, .. } | Kind::Mem { context, .. } |
+            Kind::Program { context, .. } | Kind::Event { context, .. } => Some(context),
+    }
+}
+
+/// Retains or releases `handle` (which must be an object for which `is_kind`
+/// returns true), destroying it (and releasing its context) once its
+/// reference count reaches zero.
+fn retain_release<K>(handle: *mut c_void, is_kind: K, invalid: Status, retain: bool) -> cl_int
+        where K: Fn(&Kind) -> bool
+{
+    let handle = handle as usize;
+
+    with_objects(|objs| {
+        let destroy = match objs.live.get_mut(&handle) {
+            Some(obj) => {
+                if !is_kind(&obj.kind) { return status(invalid); }
+                if retain { obj.ref_count += 1; } else { obj.ref_count -= 1; }
+                obj.ref_count == 0
+            },
+            None => return status(invalid),
+        };
+
+        if destroy {
+            let obj = objs.live.remove(&handle).unwrap();
+
+            if let Some(context) = context_of(&obj.kind) {
+                let destroy_context = match objs.live.get_mut(&context) {
+                    Some(ctx) => { ctx.ref_count -= 1; ctx.ref_count == 0 },
+                    None => false,
+                };
+                if destroy_context { objs.live.remove(&context); }
+            }
+        }
+
+        status(Status::CL_SUCCESS)
+    })
+}
+
+/// Writes `value` as the result of an info query.
+unsafe fn info<T: Copy>(value: &[T], param_value_size: size_t, param_value: *mut c_void,
+        param_value_size_ret: *mut size_t) -> cl_int
+{
+    let size = value.len() * mem::size_of::<T>();
+
+    if !param_value.is_null() {
+        if param_value_size < size { return status(Status::CL_INVALID_VALUE); }
+        ptr::copy_nonoverlapping(value.as_ptr() as *const u8, param_value as *mut u8, size);
+    }
+
+    if !param_value_size_ret.is_null() { *param_value_size_ret = size; }
+    status(Status::CL_SUCCESS)
+}
+
+/// Writes `value` as a nul-terminated string result of an info query.
+unsafe fn info_str(value: &str, param_value_size: size_t, param_value: *mut c_void,
+        param_value_size_ret: *mut size_t) -> cl_int
+{
+    let mut bytes = value.as_bytes().to_vec();
+    bytes.push(0);
+    info(&bytes, param_value_size, param_value, param_value_size_ret)
+}
+
+/// Looks up the object `handle`, returning `invalid` if it does not exist,
+/// and calls `f` with it.
+fn query<F>(handle: *mut c_void, invalid: Status, f: F) -> cl_int
+        where F: FnOnce(&Object) -> cl_int
+{
+    with_objects(|objs| match objs.live.get(&(handle as usize)) {
+        Some(obj) => f(obj),
+        None => status(invalid),
+    })
+}
+
+#[allow(non_snake_case)]
+impl ClApi for Stub {
+    unsafe fn clGetPlatformIDs(num_entries: cl_uint, platforms: *mut cl_platform_id,
+            num_platforms: *mut cl_uint) -> cl_int
+    {
+        if !platforms.is_null() {
+            if num_entries == 0 { return status(Status::CL_INVALID_VALUE); }
+            *platforms = PLATFORM as cl_platform_id;
+        }
+        if !num_platforms.is_null() { *num_platforms = 1; }
+        status(Status::CL_SUCCESS)
+    }
+
+    unsafe fn clGetPlatformInfo(platform: cl_platform_id, param_name: cl_platform_info,
+            param_value_size: size_t, param_value: *mut c_void, param_value_size_ret: *mut size_t)
+            -> cl_int
+    {
+        if platform as usize != PLATFORM { return status(Status::CL_INVALID_PLATFORM); }
+
+        let value = match param_name {
+            CL_PLATFORM_PROFILE => "FULL_PROFILE",
+            CL_PLATFORM_VERSION => VERSION,
+            CL_PLATFORM_NAME => PLATFORM_NAME,
+            CL_PLATFORM_VENDOR => "ocl",
+            CL_PLATFORM_EXTENSIONS => "",
+            _ => return status(Status::CL_INVALID_VALUE),
+        };
+        info_str(value, param_value_size, param_value, param_value_size_ret)
+    }
+
+    unsafe fn clGetDeviceIDs(platform: cl_platform_id, device_type: cl_device_type,
+            num_entries: cl_uint, devices: *mut cl_device_id, num_devices: *mut cl_uint) -> cl_int
+    {
+        if platform as usize != PLATFORM { return status(Status::CL_INVALID_PLATFORM); }
+
+        if device_type & (CL_DEVICE_TYPE_CPU | CL_DEVICE_TYPE_DEFAULT) == 0 {
+            return status(Status::CL_DEVICE_NOT_FOUND);
+        }
+
+        if !devices.is_null() {
+            if num_entries == 0 { return status(Status::CL_INVALID_VALUE); }
+            *devices = DEVICE as cl_device_id;
+        }
+        if !num_devices.is_null() { *num_devices = 1; }
+        status(Status::CL_SUCCESS)
+    }
+
+    unsafe fn clGetDeviceInfo(device: cl_device_id, param_name: cl_device_info,
+            param_value_size: size_t, param_value: *mut c_void, param_value_size_ret: *mut size_t)
+            -> cl_int
+    {
+        if device as usize != DEVICE { return status(Status::CL_INVALID_DEVICE); }
+
+        let (s, p, r) = (param_value_size, param_value, param_value_size_ret);
+
+        match param_name {
+            CL_DEVICE_NAME => info_str("ocl stub device (not conformant)", s, p, r),
+            CL_DEVICE_VENDOR => info_str("ocl", s, p, r),
+            CL_DEVICE_VERSION => info_str(VERSION, s, p, r),
+            CL_DRIVER_VERSION => info_str(env!("CARGO_PKG_VERSION"), s, p, r),
+            CL_DEVICE_PROFILE => info_str("FULL_PROFILE", s, p, r),
+            CL_DEVICE_OPENCL_C_VERSION => info_str("OpenCL C 1.2", s, p, r),
+            CL_DEVICE_EXTENSIONS | CL_DEVICE_BUILT_IN_KERNELS => info_str("", s, p, r),
+            CL_DEVICE_TYPE => info(&[CL_DEVICE_TYPE_CPU], s, p, r),
+            CL_DEVICE_PLATFORM => info(&[PLATFORM], s, p, r),
+            CL_DEVICE_PARENT_DEVICE => info(&[0usize], s, p, r),
+            CL_DEVICE_VENDOR_ID | CL_DEVICE_IMAGE_SUPPORT | CL_DEVICE_COMPILER_AVAILABLE |
+                CL_DEVICE_LINKER_AVAILABLE | CL_DEVICE_ERROR_CORRECTION_SUPPORT |
+                CL_DEVICE_HOST_UNIFIED_MEMORY | CL_DEVICE_PARTITION_MAX_SUB_DEVICES |
+                CL_DEVICE_MAX_READ_IMAGE_ARGS | CL_DEVICE_MAX_WRITE_IMAGE_ARGS |
+                CL_DEVICE_MAX_SAMPLERS => info(&[0 as cl_uint], s, p, r),
+            CL_DEVICE_MAX_COMPUTE_UNITS | CL_DEVICE_ENDIAN_LITTLE | CL_DEVICE_AVAILABLE |
+                CL_DEVICE_REFERENCE_COUNT | CL_DEVICE_PREFERRED_INTEROP_USER_SYNC |
+                CL_DEVICE_PREFERRED_VECTOR_WIDTH_CHAR | CL_DEVICE_PREFERRED_VECTOR_WIDTH_SHORT |
+                CL_DEVICE_PREFERRED_VECTOR_WIDTH_INT | CL_DEVICE_PREFERRED_VECTOR_WIDTH_LONG |
+                CL_DEVICE_PREFERRED_VECTOR_WIDTH_FLOAT | CL_DEVICE_PREFERRED_VECTOR_WIDTH_DOUBLE |
+                CL_DEVICE_PREFERRED_VECTOR_WIDTH_HALF | CL_DEVICE_NATIVE_VECTOR_WIDTH_CHAR |
+                CL_DEVICE_NATIVE_VECTOR_WIDTH_SHORT | CL_DEVICE_NATIVE_VECTOR_WIDTH_INT |
+                CL_DEVICE_NATIVE_VECTOR_WIDTH_LONG | CL_DEVICE_NATIVE_VECTOR_WIDTH_FLOAT |
+                CL_DEVICE_NATIVE_VECTOR_WIDTH_DOUBLE | CL_DEVICE_NATIVE_VECTOR_WIDTH_HALF |
+                CL_DEVICE_MAX_CLOCK_FREQUENCY => info(&[1 as cl_uint], s, p, r),
+            CL_DEVICE_MAX_WORK_ITEM_DIMENSIONS => info(&[3 as cl_uint], s, p, r),
+            CL_DEVICE_ADDRESS_BITS => info(&[mem::size_of::<usize>() as cl_uint * 8], s, p, r),
+            CL_DEVICE_MEM_BASE_ADDR_ALIGN => info(&[1024 as cl_uint], s, p, r),
+            CL_DEVICE_MIN_DATA_TYPE_ALIGN_SIZE | CL_DEVICE_GLOBAL_MEM_CACHELINE_SIZE =>
+                info(&[128 as cl_uint], s, p, r),
+            CL_DEVICE_MAX_CONSTANT_ARGS => info(&[8 as cl_uint], s, p, r),
+            CL_DEVICE_GLOBAL_MEM_CACHE_TYPE => info(&[CL_READ_WRITE_CACHE], s, p, r),
+            CL_DEVICE_LOCAL_MEM_TYPE => info(&[CL_GLOBAL], s, p, r),
+            CL_DEVICE_MAX_MEM_ALLOC_SIZE | CL_DEVICE_MAX_CONSTANT_BUFFER_SIZE =>
+                info(&[1u64 << 30], s, p, r),
+            CL_DEVICE_GLOBAL_MEM_SIZE => info(&[1u64 << 32], s, p, r),
+            CL_DEVICE_GLOBAL_MEM_CACHE_SIZE | CL_DEVICE_LOCAL_MEM_SIZE =>
+                info(&[1u64 << 15], s, p, r),
+            CL_DEVICE_SINGLE_FP_CONFIG => info(&[CL_FP_INF_NAN | CL_FP_ROUND_TO_NEAREST], s, p, r),
+            CL_DEVICE_DOUBLE_FP_CONFIG | CL_DEVICE_HALF_FP_CONFIG |
+                CL_DEVICE_PARTITION_AFFINITY_DOMAIN => info(&[0 as cl_bitfield], s, p, r),
+            CL_DEVICE_EXECUTION_CAPABILITIES => info(&[CL_EXEC_KERNEL], s, p, r),
+            CL_DEVICE_QUEUE_PROPERTIES => info(&[CL_QUEUE_PROFILING_ENABLE], s, p, r),
+            CL_DEVICE_MAX_WORK_GROUP_SIZE => info(&[1024usize], s, p, r),
+            CL_DEVICE_MAX_WORK_ITEM_SIZES => info(&[1024usize, 1024, 64], s, p, r),
+            CL_DEVICE_MAX_PARAMETER_SIZE => info(&[1024usize], s, p, r),
+            CL_DEVICE_PROFILING_TIMER_RESOLUTION => info(&[1usize], s, p, r),
+            CL_DEVICE_PRINTF_BUFFER_SIZE => info(&[1usize << 20], s, p, r),
+            CL_DEVICE_IMAGE2D_MAX_WIDTH | CL_DEVICE_IMAGE2D_MAX_HEIGHT |
+                CL_DEVICE_IMAGE3D_MAX_WIDTH | CL_DEVICE_IMAGE3D_MAX_HEIGHT |
+                CL_DEVICE_IMAGE3D_MAX_DEPTH | CL_DEVICE_IMAGE_MAX_BUFFER_SIZE |
+                CL_DEVICE_IMAGE_MAX_ARRAY_SIZE => info(&[0usize], s, p, r),
+            CL_DEVICE_PARTITION_PROPERTIES | CL_DEVICE_PARTITION_TYPE =>
+                info(&[0 as cl_device_partition_property], s, p, r),
+            _ => status(Status::CL_INVALID_VALUE),
+        }
+    }
+
+    unsafe fn clRetainDevice(device: cl_device_id) -> cl_int {
+        if device as usize == DEVICE { status(Status::CL_SUCCESS) }
+            else { status(Status::CL_INVALID_DEVICE) }
+    }
+
+    unsafe fn clReleaseDevice(device: cl_device_id) -> cl_int {
+        Stub::clRetainDevice(device)
+    }
+
+    unsafe fn clCreateContext(properties: *const cl_context_properties, num_devices: cl_uint,
+            devices: *const cl_device_id,
+            _pfn_notify: Option<extern "C" fn (*const c_char, *const c_void, size_t, *mut c_void)>,
+            _user_data: *mut c_void, errcode_ret: *mut cl_int) -> cl_context
+    {
+        if num_devices == 0 || devices.is_null() {
+            return created(0, status(Status::CL_INVALID_VALUE), errcode_ret);
+        }
+
+        if slice::from_raw_parts(devices, num_devices as usize).iter()
+                .any(|&d| d as usize != DEVICE)
+        {
+            return created(0, status(Status::CL_INVALID_DEVICE), errcode_ret);
+        }
+
+        let mut props = Vec::new();
+        if !properties.is_null() {
+            let mut i = 0;
+            while *properties.offset(i) != 0 {
+                props.push(*properties.offset(i));
+                i += 1;
+            }
+            props.push(0);
+        }
+
+        match create(None, Kind::Context { properties: props }) {
+            Ok(handle) => created(handle, status(Status::CL_SUCCESS), errcode_ret),
+            Err(errcode) => created(0, errcode, errcode_ret),
+        }
+    }
+
+    unsafe fn clCreateContextFromType(properties: *mut cl_context_properties,
+            device_type: cl_device_type,
+            _pfn_notify: Option<extern "C" fn (*mut c_char, *mut c_void, size_t, *mut c_void)>,
+            user_data: *mut c_void, errcode_ret: *mut cl_int) -> cl_context
+    {
+        if device_type & (CL_DEVICE_TYPE_CPU | CL_DEVICE_TYPE_DEFAULT) == 0 {
+            return created(0, status(Status::CL_DEVICE_NOT_FOUND), errcode_ret);
+        }
+
+        let device = DEVICE as cl_device_id;
+        Stub::clCreateContext(properties, 1, &device, None, user_data, errcode_ret)
+    }
+
+    unsafe fn clRetainContext(context: cl_context) -> cl_int {
+        retain_release(context, |k| match *k { Kind::Context { .. } => true, _ => false },
+            Status::CL_INVALID_CONTEXT, true)
+    }
+
+    unsafe fn clReleaseContext(context: cl_context) -> cl_int {
+        retain_release(context, |k| match *k { Kind::Context { .. } => true, _ => false },
+            Status::CL_INVALID_CONTEXT, false)
+    }
+
+    unsafe fn clGetContextInfo(context: cl_context, param_name: cl_context_info,
+            param_value_size: size_t, param_value: *mut c_void, param_value_size_ret: *mut size_t)
+            -> cl_int
+    {
+        let (s, p, r) = (param_value_size, param_value, param_value_size_ret);
+
+        query(context, Status::CL_INVALID_CONTEXT, |obj| match obj.kind {
+            Kind::Context { ref properties } => match param_name {
+                CL_CONTEXT_REFERENCE_COUNT => info(&[obj.ref_count], s, p, r),
+                CL_CONTEXT_NUM_DEVICES => info(&[1 as cl_uint], s, p, r),
+                CL_CONTEXT_DEVICES => info(&[DEVICE], s, p, r),
+                CL_CONTEXT_PROPERTIES => info(properties, s, p, r),
+                _ => status(Status::CL_INVALID_VALUE),
+            },
+            _ => status(Status::CL_INVALID_CONTEXT),
+        })
+    }
+
+    unsafe fn clCreateCommandQueue(context: cl_context, device: cl_device_id,
+            properties: cl_command_queue_properties, errcode_ret: *mut cl_int) -> cl_command_queue
+    {
+        if device as usize != DEVICE {
+            return created(0, status(Status::CL_INVALID_DEVICE), errcode_ret);
+        }
+
+        let kind = Kind::Queue { context: context as usize, properties: properties };
+
+        match create(Some(context as usize), kind) {
+            Ok(handle) => created(handle, status(Status::CL_SUCCESS), errcode_ret),
+            Err(errcode) => created(0, errcode, errcode_ret),
+        }
+    }
+
+    unsafe fn clRetainCommandQueue(command_queue: cl_command_queue) -> cl_int {
+        retain_release(command_queue, |k| match *k { Kind::Queue { .. } => true, _ => false },
+            Status::CL_INVALID_COMMAND_QUEUE, true)
+    }
+
+    unsafe fn clReleaseCommandQueue(command_queue: cl_command_queue) -> cl_int {
+        retain_release(command_queue, |k| match *k { Kind::Queue { .. } => true, _ => false },
+            Status::CL_INVALID_COMMAND_QUEUE, false)
+    }
+
+    unsafe fn clGetCommandQueueInfo(command_queue: cl_command_queue,
+            param_name: cl_command_queue_info, param_value_size: size_t, param_value: *mut c_void,
+            param_value_size_ret: *mut size_t) -> cl_int
+    {
+        let (s, p, r) = (param_value_size, param_value, param_value_size_ret);
+
+        query(command_queue, Status::CL_INVALID_COMMAND_QUEUE, |obj| match obj.kind {
+            Kind::Queue { context, properties } => match param_name {
+                CL_QUEUE_CONTEXT => info(&[context], s, p, r),
+                CL_QUEUE_DEVICE => info(&[DEVICE], s, p, r),
+                CL_QUEUE_REFERENCE_COUNT => info(&[obj.ref_count], s, p, r),
+                CL_QUEUE_PROPERTIES => info(&[properties], s, p, r),
+                _ => status(Status::CL_INVALID_VALUE),
+            },
+            _ => status(Status::CL_INVALID_COMMAND_QUEUE),
+        })
+    }
+
+    unsafe fn clFlush(command_queue: cl_command_queue) -> cl_int {
+        // Nothing is ever enqueued:
+        query(command_queue, Status::CL_INVALID_COMMAND_QUEUE, |_| status(Status::CL_SUCCESS))
+    }
+
+    unsafe fn clFinish(command_queue: cl_command_queue) -> cl_int {
+        Stub::clFlush(command_queue)
+    }
+
+    unsafe fn clCreateBuffer(context: cl_context, flags: cl_mem_flags, size: size_t,
+            host_ptr: *mut c_void, errcode_ret: *mut cl_int) -> cl_mem
+    {
+        if size == 0 || size as u64 > 1u64 << 30 {
+            return created(0, status(Status::CL_INVALID_BUFFER_SIZE), errcode_ret);
+        }
+
+        let needs_host_ptr = flags & (CL_MEM_USE_HOST_PTR | CL_MEM_COPY_HOST_PTR) != 0;
+        if needs_host_ptr == host_ptr.is_null() {
+            return created(0, status(Status::CL_INVALID_HOST_PTR), errcode_ret);
+        }
+
+        // The contents of the buffer are never stored:
+        let host_ptr = if flags & CL_MEM_USE_HOST_PTR != 0 { host_ptr as usize } else { 0 };
+        let kind = Kind::Mem { context: context as usize, flags: flags, size: size,
+            host_ptr: host_ptr };
+
+        match create(Some(context as usize), kind) {
+            Ok(handle) => created(handle, status(Status::CL_SUCCESS), errcode_ret),
+            Err(errcode) => created(0, errcode, errcode_ret),
+        }
+    }
+
+    unsafe fn clRetainMemObject(memobj: cl_mem) -> cl_int {
+        retain_release(memobj, |k| match *k { Kind::Mem { .. } => true, _ => false },
+            Status::CL_INVALID_MEM_OBJECT, true)
+    }
+
+    unsafe fn clReleaseMemObject(memobj: cl_mem) -> cl_int {
+        retain_release(memobj, |k| match *k { Kind::Mem { .. } => true, _ => false },
+            Status::CL_INVALID_MEM_OBJECT, false)
+    }
+
+    unsafe fn clGetMemObjectInfo(memobj: cl_mem, param_name: cl_mem_info, param_value_size: size_t,
+            param_value: *mut c_void, param_value_size_ret: *mut size_t) -> cl_int
+    {
+        let (s, p, r) = (param_value_size, param_value, param_value_size_ret);
+
+        query(memobj, Status::CL_INVALID_MEM_OBJECT, |obj| match obj.kind {
+            Kind::Mem { context, flags, size, host_ptr } => match param_name {
+                CL_MEM_TYPE => info(&[CL_MEM_OBJECT_BUFFER], s, p, r),
+                CL_MEM_FLAGS => info(&[flags], s, p, r),
+                CL_MEM_SIZE => info(&[size], s, p, r),
+                CL_MEM_HOST_PTR => info(&[host_ptr], s, p, r),
+                CL_MEM_MAP_COUNT => info(&[0 as cl_uint], s, p, r),
+                CL_MEM_REFERENCE_COUNT => info(&[obj.ref_count], s, p, r),
+                CL_MEM_CONTEXT => info(&[context], s, p, r),
+                CL_MEM_ASSOCIATED_MEMOBJECT | CL_MEM_OFFSET => info(&[0usize], s, p, r),
+                _ => status(Status::CL_INVALID_VALUE),
+            },
+            _ => status(Status::CL_INVALID_MEM_OBJECT),
+        })
+    }
+
+    unsafe fn clCreateProgramWithSource(context: cl_context, count: cl_uint,
+            strings: *const *const c_char, lengths: *const size_t, errcode_ret: *mut cl_int)
+            -> cl_program
+    {
+        if count == 0 || strings.is_null() {
+            return created(0, status(Status::CL_INVALID_VALUE), errcode_ret);
+        }
+
+        let mut source = Vec::new();
+        for i in 0..count as isize {
+            let string = *strings.offset(i) as *const u8;
+            let len = if lengths.is_null() || *lengths.offset(i) == 0 {
+                (0..).take_while(|&j| *string.offset(j) != 0).count()
+            } else {
+                *lengths.offset(i)
+            };
+            source.extend_from_slice(slice::from_raw_parts(string, len));
+        }
+
+        match create(Some(context as usize), Kind::Program { context: context as usize,
+            source: source })
+        {
+            Ok(handle) => created(handle, status(Status::CL_SUCCESS), errcode_ret),
+            Err(errcode) => created(0, errcode, errcode_ret),
+        }
+    }
+
+    unsafe fn clRetainProgram(program: cl_program) -> cl_int {
+        retain_release(program, |k| match *k { Kind::Program { .. } => true, _ => false },
+            Status::CL_INVALID_PROGRAM, true)
+    }
+
+    unsafe fn clReleaseProgram(program: cl_program) -> cl_int {
+        retain_release(program, |k| match *k { Kind::Program { .. } => true, _ => false },
+            Status::CL_INVALID_PROGRAM, false)
+    }
+
+    unsafe fn clGetProgramInfo(program: cl_program, param_name: cl_program_info,
+            param_value_size: size_t, param_value: *mut c_void, param_value_size_ret: *mut size_t)
+            -> cl_int
+    {
+        let (s, p, r) = (param_value_size, param_value, param_value_size_ret);
+
+        query(program, Status::CL_INVALID_PROGRAM, |obj| match obj.kind {
+            Kind::Program { context, ref source } => match param_name {
+                CL_PROGRAM_REFERENCE_COUNT => info(&[obj.ref_count], s, p, r),
+                CL_PROGRAM_CONTEXT => info(&[context], s, p, r),
+                CL_PROGRAM_NUM_DEVICES => info(&[1 as cl_uint], s, p, r),
+                CL_PROGRAM_DEVICES => info(&[DEVICE], s, p, r),
+                CL_PROGRAM_SOURCE => {
+                    let mut source = source.clone();
+                    source.push(0);
+                    info(&source, s, p, r)
+                },
+                _ => status(Status::CL_INVALID_VALUE),
+            },
+            _ => status(Status::CL_INVALID_PROGRAM),
+        })
+    }
+
+    unsafe fn clCreateUserEvent(context: cl_context, errcode_ret: *mut cl_int) -> cl_event {
+        let kind = Kind::Event { context: context as usize, status: CL_SUBMITTED };
+
+        match create(Some(context as usize), kind) {
+            Ok(handle) => created(handle, status(Status::CL_SUCCESS), errcode_ret),
+            Err(errcode) => created(0, errcode, errcode_ret),
+        }
+    }
+
+    unsafe fn clRetainEvent(event: cl_event) -> cl_int {
+        retain_release(event, |k| match *k { Kind::Event { .. } => true, _ => false },
+            Status::CL_INVALID_EVENT, true)
+    }
+
+    unsafe fn clReleaseEvent(event: cl_event) -> cl_int {
+        retain_release(event, |k| match *k { Kind::Event { .. } => true, _ => false },
+            Status::CL_INVALID_EVENT, false)
+    }
+
+    unsafe fn clSetUserEventStatus(event: cl_event, execution_status: cl_int) -> cl_int {
+        with_objects(|objs| match objs.live.get_mut(&(event as usize)) {
+            Some(&mut Object { kind: Kind::Event { ref mut status, .. }, .. }) => {
+                if *status != CL_SUBMITTED { return self::status(Status::CL_INVALID_OPERATION); }
+                if execution_status > CL_COMPLETE { return self::status(Status::CL_INVALID_VALUE); }
+                *status = execution_status;
+                self::status(Status::CL_SUCCESS)
+            },
+            _ => self::status(Status::CL_INVALID_EVENT),
+        })
+    }
+
+    unsafe fn clGetEventInfo(event: cl_event, param_name: cl_event_info, param_value_size: size_t,
+            param_value: *mut c_void, param_value_size_ret: *mut size_t) -> cl_int
+    {
+        let (s, p, r) = (param_value_size, param_value, param_value_size_ret);
+
+        query(event, Status::CL_INVALID_EVENT, |obj| match obj.kind {
+            Kind::Event { context, status: event_status } => match param_name {
+                CL_EVENT_COMMAND_QUEUE => info(&[0usize], s, p, r),
+                CL_EVENT_CONTEXT => info(&[context], s, p, r),
+                CL_EVENT_COMMAND_TYPE => info(&[CL_COMMAND_USER], s, p, r),
+                CL_EVENT_COMMAND_EXECUTION_STATUS => info(&[event_status], s, p, r),
+                CL_EVENT_REFERENCE_COUNT => info(&[obj.ref_count], s, p, r),
+                _ => status(Status::CL_INVALID_VALUE),
+            },
+            _ => status(Status::CL_INVALID_EVENT),
+        })
+    }
+}
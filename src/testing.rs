@@ -0,0 +1,796 @@
+//! Helpers for running tests on every available device (the `testing`
+//! feature).
+//!
+//! ```rust,ignore
+//! ocl::testing::for_each_device(|queue| {
+//!     let buffer = try!(ocl::Buffer::<f32>::new(queue, None, [1024], None));
+//!     buffer.cmd().fill(1.0, None).enq()
+//! });
+//! ```
+//!
+//! The platforms and devices are narrowed by the same environment variables
+//! as the default selection (`OCL_PLATFORM_INDEX`, `OCL_DEVICE_TYPE`, and
+//! `OCL_DEVICE_INDEX`, see `core::default_devices`).
+//!
+//! Kernel results can be checked with `assert_buffers_eq` and
+//! `assert_buffers_close`, which compare a buffer against another buffer or
+//! host data and report the first few mismatches:
+//!
+//! ```rust,ignore
+//! ocl::testing::assert_buffers_close(&expected[..], &buffer, 1e-6, 1e-5);
+//! ```
+//!
+//! To track down numeric divergence, a `VerifiedKernel` runs a kernel along
+//! with a host implementation of it, comparing the output buffers of each:
+//!
+//! ```rust,ignore
+//! let kernel = ocl::testing::VerifiedKernel::new(kernel, |args| {
+//!     let input = args.buffer::<f32>(0).unwrap();
+//!     let factor = args.scalar::<f32>(2).unwrap();
+//!     vec![ExpectedBuffer::new(1, input.iter().map(|&v| v * factor).collect())]
+//! }).unwrap().arg_buf(&input).arg_buf(&output).arg_scl(1.5f32);
+//!
+//! kernel.enq().unwrap().assert();
+//! ```
+
+use std::fmt::{self, Debug};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use core::{self, OclPrm};
+use error::{Error as OclError, Result as OclResult};
+use standard::{Platform, Device, Context, Queue, Buffer, Image, Kernel};
+
+/// The maximum number of mismatching elements listed in a comparison
+/// report.
+pub const MISMATCHES_REPORTED: usize = 10;
+
+/// The outcome of running a closure on one device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The closure returned `Ok`.
+    Passed,
+    /// The closure returned an error or panicked.
+    Failed(String),
+    /// The closure was not run because the device (or platform) could not be
+    /// initialized.
+    Skipped(String),
+}
+
+/// The outcome of running a closure on a device, with the names of the
+/// device and its platform.
+#[derive(Clone, Debug)]
+pub struct DeviceRun {
+    pub platform: String,
+    pub device: String,
+    pub outcome: Outcome,
+}
+
+impl DeviceRun {
+    fn new(platform: String, device: String, outcome: Outcome) -> DeviceRun {
+        DeviceRun { platform: platform, device: device, outcome: outcome }
+    }
+}
+
+impl fmt::Display for DeviceRun {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.outcome {
+            Outcome::Passed => write!(f, "[pass] {} / {}", self.platform, self.device),
+            Outcome::Failed(ref reason) => write!(f, "[FAIL] {} / {}: {}", self.platform,
+                self.device, reason),
+            Outcome::Skipped(ref reason) => write!(f, "[skip] {} / {}: {}", self.platform,
+                self.device, reason),
+        }
+    }
+}
+
+/// Returns the message of a caught panic.
+fn panic_message(payload: &Box<Any + Send>) -> String {
+    match payload.downcast_ref::<&'static str>() {
+        Some(msg) => msg.to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(msg) => msg.clone(),
+            None => "(non-string panic payload)".to_owned(),
+        },
+    }
+}
+
+/// Runs `f` on `device` with a new context and queue.
+fn run_on_device<F>(platform: Platform, device: Device, f: &F) -> Outcome
+        where F: Fn(&Queue) -> OclResult<()>
+{
+    let context = match Context::builder().platform(platform).devices(device).build() {
+        Ok(context) => context,
+        Err(err) => return Outcome::Skipped(format!("Unable to create a context: {}", err)),
+    };
+
+    let queue = match Queue::new(&context, device) {
+        Ok(queue) => queue,
+        Err(err) => return Outcome::Skipped(format!("Unable to create a queue: {}", err)),
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| f(&queue))) {
+        Ok(Ok(())) => Outcome::Passed,
+        Ok(Err(err)) => Outcome::Failed(err.to_string()),
+        Err(payload) => Outcome::Failed(format!("Panicked: {}", panic_message(&payload))),
+    }
+}
+
+/// Runs `f` with a new (profiling, in-order) queue on each available device
+/// in turn and returns the outcome for each.
+///
+/// Devices for which a context or queue can not be created are skipped, as
+/// are platforms with no (matching) devices. Panics within `f` are caught
+/// and count as failures. Nothing is returned if no platforms are available
+/// at all.
+///
+/// An invalid `OCL_PLATFORM_INDEX` or `OCL_DEVICE_TYPE` is reported as a
+/// single failure.
+pub fn run_on_each_device<F>(f: F) -> Vec<DeviceRun>
+        where F: Fn(&Queue) -> OclResult<()>
+{
+    let mut runs = Vec::new();
+
+    match core::get_platform_ids() {
+        Ok(ref platforms) if !platforms.is_empty() => (),
+        _ => return runs,
+    }
+
+    let selected = core::selected_platforms()
+        .and_then(|platforms| core::default_device_type().map(|_| platforms));
+
+    let platforms = match selected {
+        Ok(platforms) => platforms,
+        Err(err) => {
+            runs.push(DeviceRun::new("(selection)".to_owned(), "(none)".to_owned(),
+                Outcome::Failed(err.to_string())));
+            return runs;
+        },
+    };
+
+    for platform in platforms.into_iter().map(Platform::new) {
+        let devices = match core::default_devices(platform.as_core()) {
+            Ok(devices) => Device::list_from_core(devices),
+            Err(err) => {
+                runs.push(DeviceRun::new(platform.name(), "(none)".to_owned(),
+                    Outcome::Skipped(err.to_string())));
+                continue;
+            },
+        };
+
+        for device in devices {
+            let outcome = run_on_device(platform.clone(), device, &f);
+            runs.push(DeviceRun::new(platform.name(), device.name(), outcome));
+        }
+    }
+
+    runs
+}
+
+/// Returns a summary of `runs`, one line per device followed by the totals.
+pub fn summary(runs: &[DeviceRun]) -> String {
+    let count = |pass: bool, fail: bool| runs.iter().filter(|run| match run.outcome {
+        Outcome::Passed => pass,
+        Outcome::Failed(_) => fail,
+        Outcome::Skipped(_) => !pass && !fail,
+    }).count();
+
+    let mut summary = String::new();
+    for run in runs {
+        summary.push_str(&format!("{}\n", run));
+    }
+    summary.push_str(&format!("{} passed, {} failed, {} skipped", count(true, false),
+        count(false, true), count(false, false)));
+    summary
+}
+
+/// Runs `f` with a new (profiling, in-order) queue on each available device
+/// (see `run_on_each_device`), prints a summary, and returns the outcome for
+/// each device.
+///
+/// ## Panics
+///
+/// Panics with the summary if `f` failed on any device.
+pub fn for_each_device<F>(f: F) -> Vec<DeviceRun>
+        where F: Fn(&Queue) -> OclResult<()>
+{
+    let runs = run_on_each_device(f);
+
+    if runs.is_empty() {
+        println!("ocl::testing::for_each_device: No platforms found. Skipping.");
+        return runs;
+    }
+
+    let summary = summary(&runs);
+
+    if runs.iter().any(|run| match run.outcome { Outcome::Failed(_) => true, _ => false }) {
+        panic!("ocl::testing::for_each_device: Failed on one or more devices:\n{}", summary);
+    }
+
+    println!("{}", summary);
+    runs
+}
+
+
+//=============================================================================
+//============================ BUFFER COMPARISON ==============================
+//=============================================================================
+
+/// The expected side of a buffer comparison: another `Buffer` or host data.
+pub trait Expected<T: OclPrm> {
+    /// Returns the expected values, reading them from the device if
+    /// necessary.
+    fn expected_values(&self) -> OclResult<Vec<T>>;
+}
+
+impl<T: OclPrm> Expected<T> for Buffer<T> {
+    fn expected_values(&self) -> OclResult<Vec<T>> {
+        read(self)
+    }
+}
+
+impl<T: OclPrm> Expected<T> for [T] {
+    fn expected_values(&self) -> OclResult<Vec<T>> {
+        Ok(self.to_vec())
+    }
+}
+
+impl<T: OclPrm> Expected<T> for Vec<T> {
+    fn expected_values(&self) -> OclResult<Vec<T>> {
+        Ok(self.clone())
+    }
+}
+
+/// Reads the contents of `buffer` (mapping it instead if its transfer mode
+/// calls for it).
+fn read<T: OclPrm>(buffer: &Buffer<T>) -> OclResult<Vec<T>> {
+    let mut vec = vec![T::default(); buffer.len()];
+    try!(buffer.read(&mut vec).enq());
+    Ok(vec)
+}
+
+/// How close two floating point values must be to be considered equal.
+///
+/// Values are close if they are equal (including infinities of the same
+/// sign) or if the absolute difference between them is within either the
+/// absolute tolerance or the relative tolerance multiplied by the larger
+/// magnitude. NaN is never close to anything unless `nan_eq` is set, in
+/// which case NaN is close to NaN (only).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    pub abs: f64,
+    pub rel: f64,
+    pub nan_eq: bool,
+}
+
+impl Tolerance {
+    /// Returns a new tolerance under which NaN is never close to anything.
+    pub fn new(abs: f64, rel: f64) -> Tolerance {
+        Tolerance { abs: abs, rel: rel, nan_eq: false }
+    }
+
+    /// Sets whether NaN is close to NaN.
+    pub fn nan_eq(mut self, nan_eq: bool) -> Tolerance {
+        self.nan_eq = nan_eq;
+        self
+    }
+
+    /// Returns true if `actual` is close enough to `expected`.
+    pub fn is_close(&self, expected: f64, actual: f64) -> bool {
+        if expected.is_nan() || actual.is_nan() {
+            return self.nan_eq && expected.is_nan() && actual.is_nan();
+        }
+        if expected == actual { return true; }
+        if expected.is_infinite() || actual.is_infinite() { return false; }
+
+        let diff = (expected - actual).abs();
+        diff <= self.abs || diff <= self.rel * expected.abs().max(actual.abs())
+    }
+}
+
+impl fmt::Display for Tolerance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "abs: {:e}, rel: {:e}, NaN == NaN: {}", self.abs, self.rel, self.nan_eq)
+    }
+}
+
+/// An element which differs between the expected and actual values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch<T> {
+    pub index: usize,
+    pub expected: T,
+    pub actual: T,
+}
+
+/// The result of comparing two sequences of values element by element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison<T> {
+    /// The first `MISMATCHES_REPORTED` mismatching elements.
+    pub mismatches: Vec<Mismatch<T>>,
+    /// The total number of mismatching elements.
+    pub mismatch_count: usize,
+    /// The number of elements compared.
+    pub len: usize,
+}
+
+impl<T: Copy + Debug> Comparison<T> {
+    /// Compares `expected` and `actual` (which must be the same length)
+    /// using `eq`.
+    pub fn new<F>(expected: &[T], actual: &[T], eq: F) -> Comparison<T>
+            where F: Fn(T, T) -> bool
+    {
+        assert_eq!(expected.len(), actual.len(), "ocl::testing::Comparison::new: Lengths differ.");
+        let mut mismatches = Vec::new();
+        let mut mismatch_count = 0;
+
+        for (index, (&e, &a)) in expected.iter().zip(actual.iter()).enumerate() {
+            if !eq(e, a) {
+                if mismatches.len() < MISMATCHES_REPORTED {
+                    mismatches.push(Mismatch { index: index, expected: e, actual: a });
+                }
+                mismatch_count += 1;
+            }
+        }
+
+        Comparison { mismatches: mismatches, mismatch_count: mismatch_count, len: expected.len() }
+    }
+
+    /// Returns true if every element matched.
+    pub fn is_match(&self) -> bool {
+        self.mismatch_count == 0
+    }
+
+    /// Returns a description of the mismatches, listing the first
+    /// `MISMATCHES_REPORTED` with their expected and actual values.
+    /// `criterion` describes how the elements were compared.
+    pub fn report(&self, criterion: &str) -> String {
+        let mut report = format!("{} of {} elements differ ({}):", self.mismatch_count,
+            self.len, criterion);
+        for m in self.mismatches.iter() {
+            report.push_str(&format!("\n    [{}]: expected: {:?}, actual: {:?}", m.index,
+                m.expected, m.actual));
+        }
+        if self.mismatch_count > self.mismatches.len() {
+            report.push_str(&format!("\n    ... and {} more",
+                self.mismatch_count - self.mismatches.len()));
+        }
+        report
+    }
+}
+
+/// Reads `expected` and `actual` and compares them with `eq`, panicking
+/// with a report on any difference.
+fn assert_buffer<T, E, F>(fn_name: &str, expected: &E, actual: &Buffer<T>, criterion: &str, eq: F)
+        where T: OclPrm, E: Expected<T> + ?Sized, F: Fn(T, T) -> bool
+{
+    let expected = expected.expected_values()
+        .unwrap_or_else(|err| panic!("{}: Unable to read the expected values: {}", fn_name, err));
+    let actual = read(actual)
+        .unwrap_or_else(|err| panic!("{}: Unable to read the actual values: {}", fn_name, err));
+
+    if expected.len() != actual.len() {
+        panic!("{}: Lengths differ (expected: {}, actual: {}).", fn_name, expected.len(),
+            actual.len());
+    }
+
+    let comparison = Comparison::new(&expected, &actual, eq);
+    if !comparison.is_match() {
+        panic!("{}: {}", fn_name, comparison.report(criterion));
+    }
+}
+
+/// Asserts that `actual` contains exactly the values of `expected` (another
+/// buffer or a host slice or `Vec`).
+///
+/// ## Panics
+///
+/// Panics if either can not be read, if their lengths differ, or if any
+/// element differs, listing the first `MISMATCHES_REPORTED` differing
+/// elements and the total count.
+pub fn assert_buffers_eq<T, E>(expected: &E, actual: &Buffer<T>)
+        where T: OclPrm, E: Expected<T> + ?Sized
+{
+    assert_buffer("ocl::testing::assert_buffers_eq", expected, actual, "exact", |e, a| e == a)
+}
+
+/// Asserts that each value of `actual` is within `abs_tol` or `rel_tol` of
+/// the corresponding value of `expected` (another buffer or a host slice or
+/// `Vec`). NaN is never considered close (use `assert_buffers_close_with`
+/// for NaN == NaN).
+///
+/// ## Panics
+///
+/// As with `assert_buffers_eq`.
+pub fn assert_buffers_close<T, E>(expected: &E, actual: &Buffer<T>, abs_tol: f64, rel_tol: f64)
+        where T: OclPrm + Into<f64>, E: Expected<T> + ?Sized
+{
+    assert_buffers_close_with(expected, actual, Tolerance::new(abs_tol, rel_tol))
+}
+
+/// Asserts that each value of `actual` is close to the corresponding value
+/// of `expected` according to `tolerance`.
+///
+/// ## Panics
+///
+/// As with `assert_buffers_eq`.
+pub fn assert_buffers_close_with<T, E>(expected: &E, actual: &Buffer<T>, tolerance: Tolerance)
+        where T: OclPrm + Into<f64>, E: Expected<T> + ?Sized
+{
+    assert_buffer("ocl::testing::assert_buffers_close", expected, actual,
+        &tolerance.to_string(), |e, a| tolerance.is_close(e.into(), a.into()))
+}
+
+
+//=============================================================================
+//============================ KERNEL VERIFICATION ============================
+//=============================================================================
+
+/// The value of a kernel argument captured before launch.
+enum ArgValue {
+    /// The contents (`Vec<T>`) of a buffer.
+    Buffer(Box<Any>),
+    /// The pixel elements (`Vec<E>`) of an image.
+    Image(Box<Any>),
+    /// A scalar (`T`).
+    Scalar(Box<Any>),
+    /// The components (`Vec<T>`) of a vector.
+    Vector(Box<Any>),
+    /// The length of a local variable.
+    Local,
+}
+
+impl ArgValue {
+    fn kind(&self) -> &'static str {
+        match *self {
+            ArgValue::Buffer(_) => "buffer",
+            ArgValue::Image(_) => "image",
+            ArgValue::Scalar(_) => "scalar",
+            ArgValue::Vector(_) => "vector",
+            ArgValue::Local => "local",
+        }
+    }
+}
+
+/// A kernel argument set through a `VerifiedKernel`.
+struct VerifiedArg {
+    /// Captures the value of the argument using the given queue.
+    capture: Box<Fn(&Queue) -> OclResult<ArgValue>>,
+    /// The `Buffer<T>`, for buffer arguments.
+    buffer: Option<Box<Any>>,
+}
+
+/// Host copies of the arguments of a `VerifiedKernel`, captured just before
+/// it is enqueued and passed to its host implementation.
+///
+/// Arguments are accessed by index, requesting the same type they were set
+/// with. Local variables have no value.
+pub struct KernelArgsSnapshot {
+    args: Vec<ArgValue>,
+}
+
+impl KernelArgsSnapshot {
+    /// Returns the contents of the buffer argument `arg_idx`.
+    pub fn buffer<T: OclPrm>(&self, arg_idx: u32) -> OclResult<&[T]> {
+        self.get::<Vec<T>>("buffer", arg_idx).map(|vec| &vec[..])
+    }
+
+    /// Returns the pixel elements of the image argument `arg_idx`.
+    pub fn image<E: OclPrm>(&self, arg_idx: u32) -> OclResult<&[E]> {
+        self.get::<Vec<E>>("image", arg_idx).map(|vec| &vec[..])
+    }
+
+    /// Returns the value of the scalar argument `arg_idx`.
+    pub fn scalar<T: OclPrm>(&self, arg_idx: u32) -> OclResult<T> {
+        self.get::<T>("scalar", arg_idx).map(|&val| val)
+    }
+
+    /// Returns the components of the vector argument `arg_idx`.
+    pub fn vector<T: OclPrm>(&self, arg_idx: u32) -> OclResult<&[T]> {
+        self.get::<Vec<T>>("vector", arg_idx).map(|vec| &vec[..])
+    }
+
+    /// Returns the number of arguments.
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Returns the value of argument `arg_idx` if it is of kind `kind` and
+    /// type `V`.
+    fn get<V: 'static>(&self, kind: &str, arg_idx: u32) -> OclResult<&V> {
+        let arg = match self.args.get(arg_idx as usize) {
+            Some(arg) => arg,
+            None => return OclError::err(format!("ocl::testing::KernelArgsSnapshot::{}: \
+                Argument index [{}] out of range ({} arguments).", kind, arg_idx,
+                self.args.len())),
+        };
+
+        let value = match *arg {
+            ArgValue::Buffer(ref val) | ArgValue::Image(ref val) | ArgValue::Scalar(ref val) |
+                ArgValue::Vector(ref val) if arg.kind() == kind => val.downcast_ref::<V>(),
+            _ => None,
+        };
+
+        match value {
+            Some(value) => Ok(value),
+            None => OclError::err(format!("ocl::testing::KernelArgsSnapshot::{}: Argument [{}] \
+                is not a {} of the requested type (it is a {}).", kind, arg_idx, kind,
+                arg.kind())),
+        }
+    }
+}
+
+/// The values expected in an output buffer of a `VerifiedKernel`, returned
+/// by its host implementation.
+pub struct ExpectedBuffer {
+    arg_idx: u32,
+    verify: Box<Fn(&Any, &Queue, Tolerance) -> OclResult<BufferVerification>>,
+}
+
+impl ExpectedBuffer {
+    /// Expects the buffer argument `arg_idx` to contain `values`, compared
+    /// using the tolerance of the kernel (see `VerifiedKernel::tolerance`).
+    pub fn new<T: OclPrm + Into<f64>>(arg_idx: u32, values: Vec<T>) -> ExpectedBuffer {
+        ExpectedBuffer::with_eq(arg_idx, values, |tolerance, e: T, a: T| {
+            tolerance.is_close(e.into(), a.into())
+        }, |tolerance| tolerance.to_string())
+    }
+
+    /// Expects the buffer argument `arg_idx` to contain exactly `values`.
+    pub fn exact<T: OclPrm>(arg_idx: u32, values: Vec<T>) -> ExpectedBuffer {
+        ExpectedBuffer::with_eq(arg_idx, values, |_, e: T, a: T| e == a,
+            |_| "exact".to_owned())
+    }
+
+    /// Returns the index of the buffer argument.
+    pub fn arg_idx(&self) -> u32 {
+        self.arg_idx
+    }
+
+    fn with_eq<T, F, C>(arg_idx: u32, expected: Vec<T>, eq: F, criterion: C) -> ExpectedBuffer
+            where T: OclPrm, F: Fn(Tolerance, T, T) -> bool + 'static,
+                C: Fn(Tolerance) -> String + 'static
+    {
+        let verify = move |buffer: &Any, queue: &Queue, tolerance: Tolerance| {
+            let buffer = match buffer.downcast_ref::<Buffer<T>>() {
+                Some(buffer) => buffer,
+                None => return OclError::err(format!("ocl::testing::VerifiedKernel::enq: The \
+                    expected values for argument [{}] are not of the type of its buffer.",
+                    arg_idx)),
+            };
+
+            let mut actual = vec![T::default(); buffer.len()];
+            try!(buffer.read(&mut actual).queue(queue).enq());
+
+            let report = if expected.len() != actual.len() {
+                Some(format!("Lengths differ (expected: {}, actual: {}).", expected.len(),
+                    actual.len()))
+            } else {
+                let comparison = Comparison::new(&expected, &actual, |e, a| eq(tolerance, e, a));
+                if comparison.is_match() { None } else {
+                    Some(comparison.report(&criterion(tolerance)))
+                }
+            };
+
+            Ok(BufferVerification { arg_idx: arg_idx, report: report })
+        };
+
+        ExpectedBuffer { arg_idx: arg_idx, verify: Box::new(verify) }
+    }
+}
+
+/// The outcome of comparing one output buffer of a `VerifiedKernel`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BufferVerification {
+    /// The index of the buffer argument.
+    pub arg_idx: u32,
+    /// A description of the mismatches (see `Comparison::report`), if any.
+    pub report: Option<String>,
+}
+
+/// The outcome of enqueuing a `VerifiedKernel`: the comparison of each
+/// output buffer with the values expected by the host implementation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verification {
+    pub buffers: Vec<BufferVerification>,
+}
+
+impl Verification {
+    /// Returns true if every output buffer matched.
+    pub fn is_match(&self) -> bool {
+        self.buffers.iter().all(|buffer| buffer.report.is_none())
+    }
+
+    /// Returns a description of each mismatching output buffer.
+    pub fn report(&self) -> String {
+        let mismatched: Vec<_> = self.buffers.iter()
+            .filter_map(|buffer| buffer.report.as_ref().map(|report| (buffer.arg_idx, report)))
+            .collect();
+
+        if mismatched.is_empty() {
+            return format!("All {} output buffers match.", self.buffers.len());
+        }
+
+        let mut report = format!("{} of {} output buffers differ:", mismatched.len(),
+            self.buffers.len());
+        for (arg_idx, buffer_report) in mismatched {
+            report.push_str(&format!("\n  argument [{}]: {}", arg_idx,
+                buffer_report.replace("\n", "\n  ")));
+        }
+        report
+    }
+
+    /// Panics with the report if any output buffer did not match.
+    pub fn assert(&self) {
+        if !self.is_match() {
+            panic!("ocl::testing::Verification::assert: {}", self.report());
+        }
+    }
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.report())
+    }
+}
+
+/// A kernel which is verified against a host implementation each time it is
+/// enqueued, for debugging numeric divergence.
+///
+/// Arguments are set through the wrapper (rather than on the `Kernel`) so
+/// that their values can be captured: when enqueued, the contents of each
+/// buffer and image argument are read (blocking) and, along with the values
+/// of scalar and vector arguments, passed to the host implementation while
+/// the kernel runs. The host implementation returns the values expected in
+/// each output buffer (see `ExpectedBuffer`), which are then compared with
+/// the contents of the buffers once the kernel completes.
+///
+/// Every transfer uses the queue of the kernel (see `Kernel::resolve_queue`)
+/// and blocks, so commands writing to the arguments on other queues must
+/// have completed beforehand. The overhead is considerable: use only for
+/// debugging and testing.
+pub struct VerifiedKernel {
+    kernel: Kernel,
+    args: Vec<VerifiedArg>,
+    host: Box<Fn(&KernelArgsSnapshot) -> Vec<ExpectedBuffer>>,
+    tolerance: Tolerance,
+}
+
+impl VerifiedKernel {
+    /// Wraps `kernel`, whose arguments must not yet have been set, to be
+    /// verified against the host implementation `host`.
+    ///
+    /// Floating point outputs are compared exactly unless a tolerance is
+    /// set with `::tolerance`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if any arguments of `kernel` have been set.
+    pub fn new<F>(kernel: Kernel, host: F) -> OclResult<VerifiedKernel>
+            where F: Fn(&KernelArgsSnapshot) -> Vec<ExpectedBuffer> + 'static
+    {
+        if kernel.arg_count() != 0 {
+            return OclError::err(format!("ocl::testing::VerifiedKernel::new: The arguments of \
+                kernel '{}' must be set through the 'VerifiedKernel' ({} already set).",
+                kernel.name(), kernel.arg_count()));
+        }
+
+        Ok(VerifiedKernel {
+            kernel: kernel,
+            args: Vec::new(),
+            host: Box::new(host),
+            tolerance: Tolerance::new(0.0, 0.0),
+        })
+    }
+
+    /// Adds a buffer argument (see `Kernel::arg_buf`).
+    pub fn arg_buf<T: OclPrm>(mut self, buffer: &Buffer<T>) -> VerifiedKernel {
+        self.kernel = self.kernel.arg_buf(buffer);
+        let captured = buffer.clone();
+
+        self.args.push(VerifiedArg {
+            capture: Box::new(move |queue| {
+                let mut vec = vec![T::default(); captured.len()];
+                try!(captured.read(&mut vec).queue(queue).enq());
+                Ok(ArgValue::Buffer(Box::new(vec)))
+            }),
+            buffer: Some(Box::new(buffer.clone())),
+        });
+        self
+    }
+
+    /// Adds an image argument (see `Kernel::arg_img`).
+    pub fn arg_img<E: OclPrm>(mut self, image: &Image<E>) -> VerifiedKernel {
+        self.kernel = self.kernel.arg_img(image);
+        let captured = image.clone();
+
+        self.args.push(VerifiedArg {
+            capture: Box::new(move |queue| {
+                let mut vec = vec![E::default(); captured.element_count()];
+                try!(captured.read(&mut vec).queue(queue).enq());
+                Ok(ArgValue::Image(Box::new(vec)))
+            }),
+            buffer: None,
+        });
+        self
+    }
+
+    /// Adds a scalar argument (see `Kernel::arg_scl`).
+    pub fn arg_scl<T: OclPrm>(mut self, scalar: T) -> VerifiedKernel {
+        self.kernel = self.kernel.arg_scl(scalar);
+        self.args.push(VerifiedArg {
+            capture: Box::new(move |_| Ok(ArgValue::Scalar(Box::new(scalar)))),
+            buffer: None,
+        });
+        self
+    }
+
+    /// Adds a vector argument (see `Kernel::arg_vec`).
+    pub fn arg_vec<T: OclPrm>(mut self, vector: &[T]) -> VerifiedKernel {
+        self.kernel = self.kernel.arg_vec(vector);
+        let captured = vector.to_vec();
+
+        self.args.push(VerifiedArg {
+            capture: Box::new(move |_| Ok(ArgValue::Vector(Box::new(captured.clone())))),
+            buffer: None,
+        });
+        self
+    }
+
+    /// Adds a local variable argument (see `Kernel::arg_loc`).
+    pub fn arg_loc<T: OclPrm>(mut self, length: usize) -> VerifiedKernel {
+        self.kernel = self.kernel.arg_loc::<T>(length);
+        self.args.push(VerifiedArg {
+            capture: Box::new(|_| Ok(ArgValue::Local)),
+            buffer: None,
+        });
+        self
+    }
+
+    /// Sets the tolerance used to compare the values of output buffers
+    /// expected with `ExpectedBuffer::new`.
+    pub fn tolerance(mut self, tolerance: Tolerance) -> VerifiedKernel {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Returns the wrapped kernel.
+    pub fn kernel(&self) -> &Kernel {
+        &self.kernel
+    }
+
+    /// Captures the arguments, enqueues the kernel, runs the host
+    /// implementation, and compares the output buffers it returned values
+    /// for, blocking until complete.
+    ///
+    /// Mismatches are reported in the returned `Verification` rather than
+    /// as an error (see `Verification::assert`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the kernel can not be enqueued, any argument can
+    /// not be read, or the host implementation returned values for an
+    /// argument which is not a buffer of the same type.
+    pub fn enq(&self) -> OclResult<Verification> {
+        let queue = try!(self.kernel.resolve_queue());
+
+        let mut args = Vec::with_capacity(self.args.len());
+        for arg in self.args.iter() {
+            args.push(try!((arg.capture)(&queue)));
+        }
+        let snapshot = KernelArgsSnapshot { args: args };
+
+        try!(self.kernel.cmd().queue(&queue).enq());
+        let expected = (self.host)(&snapshot);
+
+        let mut buffers = Vec::with_capacity(expected.len());
+        for expected in expected {
+            let buffer = match self.args.get(expected.arg_idx as usize) {
+                Some(&VerifiedArg { buffer: Some(ref buffer), .. }) => buffer,
+                _ => return OclError::err(format!("ocl::testing::VerifiedKernel::enq: Argument \
+                    [{}] of kernel '{}' is not a buffer.", expected.arg_idx, self.kernel.name())),
+            };
+            buffers.push(try!((expected.verify)(&**buffer, &queue, self.tolerance)));
+        }
+
+        Ok(Verification { buffers: buffers })
+    }
+}
@@ -0,0 +1,87 @@
+//! Tests the padding and alignment helpers in `util`.
+
+use std::usize;
+use util::{padded_len, align_up, is_aligned};
+
+#[test]
+fn padded_len_values() {
+    assert_eq!(padded_len(0, 1).unwrap(), 0);
+    assert_eq!(padded_len(0, 64).unwrap(), 0);
+    assert_eq!(padded_len(1, 64).unwrap(), 64);
+    assert_eq!(padded_len(64, 64).unwrap(), 64);
+    assert_eq!(padded_len(65, 64).unwrap(), 128);
+    assert_eq!(padded_len(10, 3).unwrap(), 12);
+    assert_eq!(padded_len(12, 3).unwrap(), 12);
+    assert_eq!(padded_len(7, 1).unwrap(), 7);
+
+    assert!(padded_len(0, 0).is_err());
+    assert!(padded_len(10, 0).is_err());
+}
+
+#[test]
+fn padded_len_overflow() {
+    assert_eq!(padded_len(usize::MAX, 1).unwrap(), usize::MAX);
+    assert_eq!(padded_len(usize::MAX, usize::MAX).unwrap(), usize::MAX);
+    assert!(padded_len(usize::MAX, 2).is_err());
+    assert_eq!(padded_len(usize::MAX - 1, 3).unwrap(), usize::MAX);
+    assert!(padded_len(usize::MAX, 7).is_err());
+    assert!(padded_len(1, usize::MAX - 1).is_ok());
+    assert!(padded_len(usize::MAX, usize::MAX - 1).is_err());
+}
+
+#[test]
+fn align_up_values() {
+    // Power-of-two alignments:
+    assert_eq!(align_up(0, 1).unwrap(), 0);
+    assert_eq!(align_up(0, 256).unwrap(), 0);
+    assert_eq!(align_up(1, 256).unwrap(), 256);
+    assert_eq!(align_up(255, 256).unwrap(), 256);
+    assert_eq!(align_up(256, 256).unwrap(), 256);
+    assert_eq!(align_up(257, 256).unwrap(), 512);
+
+    // Other alignments:
+    assert_eq!(align_up(1, 3).unwrap(), 3);
+    assert_eq!(align_up(100, 24).unwrap(), 120);
+    assert_eq!(align_up(120, 24).unwrap(), 120);
+
+    assert!(align_up(0, 0).is_err());
+    assert!(align_up(5, 0).is_err());
+
+    // Both paths agree:
+    for value in 0..300 {
+        for &align in &[1, 2, 4, 8, 64, 128] {
+            assert_eq!(align_up(value, align).unwrap(), padded_len(value, align).unwrap());
+        }
+    }
+}
+
+#[test]
+fn align_up_overflow() {
+    let top_bit = 1 << (usize::MAX.count_ones() - 1);
+    assert_eq!(align_up(usize::MAX, 1).unwrap(), usize::MAX);
+    assert_eq!(align_up(top_bit, top_bit).unwrap(), top_bit);
+    assert_eq!(align_up(1, top_bit).unwrap(), top_bit);
+    assert!(align_up(top_bit + 1, top_bit).is_err());
+    assert!(align_up(usize::MAX, 2).is_err());
+    assert!(align_up(usize::MAX - 2, 4).is_err());
+    assert_eq!(align_up(usize::MAX, 3).unwrap(), usize::MAX);
+    assert!(align_up(usize::MAX, 7).is_err());
+    assert_eq!(align_up(usize::MAX, usize::MAX).unwrap(), usize::MAX);
+}
+
+#[test]
+fn is_aligned_values() {
+    assert!(is_aligned(0, 1).unwrap());
+    assert!(is_aligned(0, 256).unwrap());
+    assert!(is_aligned(512, 256).unwrap());
+    assert!(!is_aligned(513, 256).unwrap());
+    assert!(is_aligned(9, 3).unwrap());
+    assert!(!is_aligned(10, 3).unwrap());
+    assert!(is_aligned(usize::MAX, 1).unwrap());
+    assert!(is_aligned(usize::MAX, usize::MAX).unwrap());
+    assert!(!is_aligned(usize::MAX, 2).unwrap());
+    assert!(!is_aligned(usize::MAX - 1, usize::MAX).unwrap());
+
+    assert!(is_aligned(0, 0).is_err());
+    assert!(is_aligned(8, 0).is_err());
+}
@@ -0,0 +1,77 @@
+//! Tests round-tripping `ndarray` arrays through buffers and images.
+
+use ndarray::{Array, Array2, Array3, Axis, Slice};
+use standard::{ProQue, Buffer, Image};
+use enums::{ImageChannelOrder, ImageInfo, ImageInfoResult};
+
+fn pro_que() -> ProQue {
+    ProQue::builder().src("__kernel void dummy() {}").dims([1]).build().unwrap()
+}
+
+#[test]
+fn buffer_round_trip() {
+    let pro_que = pro_que();
+
+    let array_2d = Array::from_shape_fn((16, 24), |(y, x)| (y * 24 + x) as f32 * 0.5);
+    let buffer = Buffer::from_array(pro_que.queue(), &array_2d).unwrap();
+    assert_eq!(buffer.len(), 16 * 24);
+    assert_eq!(buffer.read_to_array((16, 24)).unwrap(), array_2d);
+
+    let array_3d: Array3<i32> = Array::from_shape_fn((4, 8, 12), |(z, y, x)| {
+        (z * 1000 + y * 100 + x) as i32
+    });
+    let buffer = Buffer::from_array(pro_que.queue(), &array_3d).unwrap();
+    assert_eq!(buffer.read_to_array((4, 8, 12)).unwrap(), array_3d);
+
+    // Mismatched shapes are rejected before reading:
+    assert!(buffer.read_to_array((4, 8, 11)).is_err());
+}
+
+#[test]
+fn buffer_non_standard_layout() {
+    let pro_que = pro_que();
+    let array = Array::from_shape_fn((16, 24), |(y, x)| (y * 24 + x) as u32);
+
+    // Transposed:
+    let buffer = Buffer::from_array(pro_que.queue(), &array.t()).unwrap();
+    assert_eq!(buffer.read_to_array((24, 16)).unwrap(), array.t());
+
+    // Strided:
+    let strided = array.slice_axis(Axis(1), Slice::new(0, None, 3));
+    let buffer = Buffer::from_array(pro_que.queue(), &strided).unwrap();
+    assert_eq!(buffer.read_to_array((16, 8)).unwrap(), strided);
+}
+
+#[test]
+fn image_round_trip() {
+    let pro_que = pro_que();
+    let (width, height) = (32, 16);
+
+    let array = Array3::from_shape_fn((height, width, 4), |(y, x, c)| (y * 7 + x * 3 + c) as u8);
+    let image = Image::from_array(pro_que.queue(), &array).unwrap();
+    assert_eq!(image.dims().to_lens().unwrap(), [width, height, 1]);
+
+    let mut vec = vec![0u8; image.element_count()];
+    image.read(&mut vec).enq().unwrap();
+    assert_eq!(Array3::from_shape_vec((height, width, 4), vec).unwrap(), array);
+
+    // One channel:
+    let single = array.index_axis(Axis(2), 0).insert_axis(Axis(2));
+    let image = Image::from_array(pro_que.queue(), &single).unwrap();
+    match image.info(ImageInfo::Format) {
+        ImageInfoResult::Format(format) => {
+            assert_eq!(format.channel_order, ImageChannelOrder::R)
+        },
+        _ => panic!("Unexpected 'ImageInfoResult' variant."),
+    }
+    assert!(Image::from_array(pro_que.queue(), &Array3::<u8>::zeros((4, 4, 3))).is_err());
+
+    // Pixel arrays:
+    let pixels = Array2::from_shape_fn((height, width), |(y, x)| {
+        [array[(y, x, 0)], array[(y, x, 1)], array[(y, x, 2)], array[(y, x, 3)]]
+    });
+    let image = Image::from_rgba_array(pro_que.queue(), &pixels).unwrap();
+    let mut vec = vec![0u8; image.element_count()];
+    image.read(&mut vec).enq().unwrap();
+    assert_eq!(Array3::from_shape_vec((height, width, 4), vec).unwrap(), array);
+}
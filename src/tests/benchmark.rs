@@ -0,0 +1,51 @@
+//! Tests selecting a device by micro-benchmark with
+//! `Device::benchmark_select`.
+
+use std::time::Duration;
+use standard::{Platform, Device, BenchWorkload};
+
+fn all_devices() -> Vec<Device> {
+    Platform::list().unwrap().iter().flat_map(|p| Device::list_all(p).unwrap()).collect()
+}
+
+#[test]
+fn bandwidth() {
+    let devices = all_devices();
+    let (winner, report) = Device::benchmark_select(BenchWorkload::MemoryBandwidth, &devices)
+        .unwrap();
+
+    assert_eq!(report.entries().len(), devices.len());
+    assert_eq!(report.winner().map(|d| *d.as_core()), Some(*winner.as_core()));
+
+    let entry = report.entries().iter()
+        .find(|e| e.device().as_core() == winner.as_core()).unwrap();
+    assert!(!entry.timings().unwrap().is_empty());
+    let best = entry.mean().unwrap();
+    assert!(best > Duration::from_secs(0));
+
+    // Every completed candidate is at least as slow as the winner:
+    for entry in report.entries() {
+        if let Some(mean) = entry.mean() {
+            assert!(mean >= best);
+        }
+    }
+
+    assert!(report.to_string().contains("(winner)"));
+}
+
+#[test]
+fn custom_failures_excluded() {
+    let devices = all_devices();
+    let workload = BenchWorkload::Custom {
+        src: "__kernel void broken(__global float* buf) { undeclared(); }".to_owned(),
+        name: "broken".to_owned(),
+        dims: [64].into(),
+    };
+
+    // Every device fails to build the kernel, so none can be selected, but
+    // each is listed in the error:
+    let err = Device::benchmark_select(workload, &devices).unwrap_err();
+    assert!(err.to_string().contains("excluded"), "{}", err);
+
+    assert!(Device::benchmark_select(BenchWorkload::Fp32Throughput, &[]).is_err());
+}
@@ -0,0 +1,72 @@
+//! Tests reads and writes split into chunks with `BufferCmd::chunk_size`.
+
+use standard::{ProQue, Buffer, Event, StagingPool, TransferMode};
+
+// Neither a multiple of the element size nor a divisor of the buffer length:
+const CHUNK_SIZE: usize = 1003;
+const CHUNK_LEN: usize = CHUNK_SIZE / 4;
+const BUFFER_LEN: usize = CHUNK_LEN * 9 + 17;
+const OFFSET: usize = 31;
+
+fn check_round_trip(proque: &ProQue, pool: Option<&StagingPool>, mode: TransferMode) {
+    let buffer = Buffer::<u32>::new(proque.queue(), None, [BUFFER_LEN], None).unwrap();
+    let len = BUFFER_LEN - OFFSET - 5;
+    let src: Vec<u32> = (0..len as u32).map(|v| v.wrapping_mul(2654435761)).collect();
+
+    let mut cmd = buffer.cmd().write(&src).offset(OFFSET).chunk_size(CHUNK_SIZE)
+        .transfer_mode(mode);
+    if let Some(pool) = pool { cmd = cmd.staging_pool(pool); }
+    cmd.enq().unwrap();
+
+    // Compare with a direct read (everything outside the range must be
+    // untouched):
+    let mut direct = vec![0u32; BUFFER_LEN];
+    buffer.read(&mut direct).enq().unwrap();
+    assert!(direct[..OFFSET].iter().all(|&v| v == 0));
+    assert_eq!(&direct[OFFSET..OFFSET + len], &src[..]);
+    assert!(direct[OFFSET + len..].iter().all(|&v| v == 0));
+
+    // Read back in chunks:
+    let mut chunked = vec![0u32; len];
+    let mut cmd = buffer.cmd().read(&mut chunked).offset(OFFSET).chunk_size(CHUNK_SIZE)
+        .transfer_mode(mode);
+    if let Some(pool) = pool { cmd = cmd.staging_pool(pool); }
+    cmd.enq().unwrap();
+    assert_eq!(chunked, src);
+}
+
+#[test]
+fn buffer_chunked() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([BUFFER_LEN])
+        .build().unwrap();
+
+    check_round_trip(&proque, None, TransferMode::Copy);
+    check_round_trip(&proque, None, TransferMode::Map);
+
+    let pool = StagingPool::new(proque.queue(), 512, 3).unwrap();
+    check_round_trip(&proque, Some(&pool), TransferMode::Copy);
+
+    // Non-blocking, completing via the aggregate event:
+    let buffer = Buffer::<u32>::new(proque.queue(), None, [BUFFER_LEN], None).unwrap();
+    let src: Vec<u32> = (0..BUFFER_LEN as u32).collect();
+    let mut write_event = Event::empty();
+    buffer.cmd().write(&src).block(false).chunk_size(CHUNK_SIZE).enew(&mut write_event)
+        .enq().unwrap();
+
+    let mut dst = vec![0u32; BUFFER_LEN];
+    let mut read_event = Event::empty();
+    unsafe {
+        buffer.cmd().read_async(&mut dst).chunk_size(CHUNK_SIZE).ewait(&write_event)
+            .enew(&mut read_event).enq().unwrap();
+    }
+    read_event.wait().unwrap();
+    assert_eq!(dst, src);
+
+    // Invalid usage:
+    let mut small = vec![0u32; 4];
+    assert!(buffer.cmd().read(&mut small).chunk_size(3).enq().is_err());
+    assert!(buffer.cmd().read(&mut small).staging_pool(&pool).enq().is_err());
+    assert!(buffer.cmd().fill(&[1], None).chunk_size(CHUNK_SIZE).enq().is_err());
+}
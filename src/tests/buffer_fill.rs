@@ -1,5 +1,6 @@
 // extern crate ocl;
-use standard::ProQue;
+use core;
+use standard::{ProQue, Context, Queue, Buffer};
 
 const DATASET_SIZE: usize = 2 << 20;
 
@@ -36,4 +37,47 @@ fn fill() {
     for &ele in vec.iter() {
         assert_eq!(ele, 10.0f32);
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn fill_validation() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let buffer = Buffer::<u8>::builder().dims([256]).uninit().build(&queue).unwrap();
+
+    // Each is refused before calling into the library:
+    let empty: &[u8] = &[];
+    assert!(core::enqueue_fill_buffer(queue.core_as_ref(), buffer.core_as_ref(), empty, 0, 256,
+        None, None).is_err());
+    assert!(core::enqueue_fill_buffer(queue.core_as_ref(), buffer.core_as_ref(), &[0u8; 3], 0,
+        255, None, None).is_err());
+    assert!(core::enqueue_fill_buffer(queue.core_as_ref(), buffer.core_as_ref(), &[0u8; 256], 0,
+        256, None, None).is_err());
+    assert!(core::enqueue_fill_buffer(queue.core_as_ref(), buffer.core_as_ref(), &[0u8; 4], 2,
+        4, None, None).is_err());
+    assert!(core::enqueue_fill_buffer(queue.core_as_ref(), buffer.core_as_ref(), &[0u8; 4], 0,
+        6, None, None).is_err());
+
+    assert!(core::enqueue_fill_image(queue.core_as_ref(), buffer.core_as_ref(), &[0.0f32; 3],
+        [0, 0, 0], [1, 1, 1], None, None).is_err());
+    assert!(core::enqueue_fill_image(queue.core_as_ref(), buffer.core_as_ref(), &[0u8; 16],
+        [0, 0, 0], [1, 1, 1], None, None).is_err());
+
+    assert!(buffer.fill(1, 200, Some(57)).is_err());
+}
+
+#[test]
+fn fill_values() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let buffer = Buffer::<u32>::builder().dims([64]).build(&queue).unwrap();
+
+    buffer.fill(7, 0, None).unwrap();
+    buffer.fill(9, 16, Some(8)).unwrap();
+    buffer.cmd().fill(&[1, 2], Some(8)).offset(32).enq().unwrap();
+
+    let mut expected = vec![7u32; 64];
+    for val in expected[16..24].iter_mut() { *val = 9; }
+    for (idx, val) in expected[32..40].iter_mut().enumerate() { *val = idx as u32 % 2 + 1; }
+    assert_eq!(buffer.read_to_vec().unwrap(), expected);
+}
@@ -0,0 +1,55 @@
+//! Tests the initial contents of newly created buffers.
+
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global int* buffer, int addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+const LEN: usize = 4096;
+
+#[test]
+fn buffer_init_zeroed() {
+    let pro_que = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let mut vec = vec![-1i32; LEN];
+
+    let buffer = Buffer::<i32>::new(pro_que.queue(), None, [LEN], None).unwrap();
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 0));
+
+    let buffer = Buffer::<i32>::builder().dims([LEN]).build(pro_que.queue()).unwrap();
+    let mut vec = vec![-1i32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 0));
+}
+
+#[test]
+fn buffer_init_fill_val() {
+    let pro_que = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let buffer = Buffer::<i32>::builder().dims([LEN]).fill_val(7).build(pro_que.queue()).unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(3i32);
+    kernel.enq().unwrap();
+
+    let mut vec = vec![0i32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 10));
+}
+
+#[test]
+fn buffer_init_uninit() {
+    let pro_que = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let buffer = Buffer::<i32>::builder().dims([LEN]).uninit().build(pro_que.queue()).unwrap();
+    let vec_src: Vec<i32> = (0..LEN as i32).collect();
+    buffer.write(&vec_src).enq().unwrap();
+
+    let mut vec = vec![0i32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(vec, vec_src);
+
+    assert!(Buffer::<i32>::builder().uninit().build(pro_que.queue()).is_err());
+}
@@ -0,0 +1,93 @@
+//! Tests the whole-buffer reads and writes: `read_to_vec`, `read_into`, and
+//! `write_all`.
+
+use core;
+use standard::{Buffer, Queue};
+use error::Result as OclResult;
+use testing;
+
+const LEN: usize = 64;
+
+fn new_buffer(queue: &Queue) -> OclResult<Buffer<i32>> {
+    let data: Vec<i32> = (0..LEN as i32).collect();
+    Buffer::builder()
+        .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+        .dims([LEN])
+        .build_with_data(queue, &data)
+}
+
+#[test]
+fn read_to_vec() {
+    testing::for_each_device(|queue| {
+        let buffer = try!(new_buffer(queue));
+        assert_eq!(try!(buffer.read_to_vec()), (0..LEN as i32).collect::<Vec<_>>());
+
+        let tail = try!(buffer.cmd().queue(queue).offset(LEN - 4).read_to_vec());
+        assert_eq!(tail, vec![60, 61, 62, 63]);
+        Ok(())
+    });
+}
+
+#[test]
+fn read_into_resizes() {
+    testing::for_each_device(|queue| {
+        let buffer = try!(new_buffer(queue));
+
+        // Grows:
+        let mut vec = vec![-1; 3];
+        try!(buffer.read_into(&mut vec));
+        assert_eq!(vec, (0..LEN as i32).collect::<Vec<_>>());
+
+        // Shrinks, keeping the allocation:
+        let mut vec = vec![-1; LEN * 4];
+        let capacity = vec.capacity();
+        try!(buffer.cmd().offset(LEN - 2).read_into(&mut vec));
+        assert_eq!(vec, vec![62, 63]);
+        assert_eq!(vec.capacity(), capacity);
+        Ok(())
+    });
+}
+
+#[test]
+fn write_all() {
+    testing::for_each_device(|queue| {
+        let buffer = try!(new_buffer(queue));
+        try!(buffer.write_all(&[7; LEN]));
+        assert_eq!(try!(buffer.read_to_vec()), vec![7; LEN]);
+
+        try!(buffer.cmd().offset(LEN / 2).write_all(&[9; LEN / 2]));
+        let vec = try!(buffer.read_to_vec());
+        assert!(vec[..LEN / 2].iter().all(|&v| v == 7) && vec[LEN / 2..].iter().all(|&v| v == 9));
+        Ok(())
+    });
+}
+
+#[test]
+fn length_errors() {
+    testing::for_each_device(|queue| {
+        let buffer = try!(new_buffer(queue));
+
+        let err = buffer.write_all(&[0; LEN - 1]).unwrap_err().to_string();
+        assert!(err.contains("length of the data (63) does not match the length of the \
+            buffer (64)"), "{}", err);
+
+        let err = buffer.cmd().offset(16).write_all(&[0; LEN]).unwrap_err().to_string();
+        assert!(err.contains("length of the data (64) does not match the length of the \
+            buffer after the offset (48)"), "{}", err);
+
+        let err = buffer.cmd().offset(LEN).read_to_vec().unwrap_err().to_string();
+        assert!(err.contains("read_to_vec(): Offset out of range (offset: 64, buffer length: \
+            64)"), "{}", err);
+
+        // The destination is left untouched on error:
+        let mut vec = vec![-1; 3];
+        assert!(buffer.cmd().offset(LEN + 1).read_into(&mut vec).is_err());
+        assert_eq!(vec, vec![-1; 3]);
+
+        assert!(buffer.cmd().rect([0; 3], [0; 3], [8, 1, 1], 0, 0, 0, 0).read_into(&mut vec)
+            .is_err());
+        assert!(buffer.cmd().rect([0; 3], [0; 3], [8, 1, 1], 0, 0, 0, 0).read_to_vec().is_err());
+        assert_eq!(vec, vec![-1; 3]);
+        Ok(())
+    });
+}
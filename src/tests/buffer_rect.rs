@@ -0,0 +1,103 @@
+//! Tests rectangular buffer reads, writes, and copies with
+//! `Buffer::read_rect`, `::write_rect`, and `::copy_rect`.
+
+use standard::{Context, Queue, Buffer, Event};
+
+/// The dimensions of the host grid and of the tile within it.
+const GRID: [usize; 2] = [16, 12];
+const TILE: [usize; 2] = [8, 6];
+const TILE_ORIGIN: [usize; 2] = [5, 3];
+
+fn grid() -> Vec<u32> {
+    (0..(GRID[0] * GRID[1]) as u32).collect()
+}
+
+#[test]
+fn validation() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let buffer = Buffer::<u32>::builder().dims(TILE).uninit().build(&queue).unwrap();
+    let data = grid();
+
+    let err = |res: ::error::Result<()>| res.unwrap_err().to_string();
+
+    // Empty region:
+    assert!(err(buffer.write_rect([0, 0], TILE_ORIGIN, [0, 6], GRID, &data).enq())
+        .contains("must not be empty"));
+    // Beyond the end of the host slice and of the buffer:
+    assert!(err(buffer.write_rect([0, 0], [8, 8], TILE, GRID, &data).enq())
+        .contains("extends beyond the end of the host slice"));
+    assert!(err(buffer.write_rect([1, 0], TILE_ORIGIN, TILE, GRID, &data).enq())
+        .contains("extends beyond the end of the buffer"));
+    assert!(err(buffer.write_rect([0, 0], TILE_ORIGIN, TILE, GRID, &data[..100]).enq())
+        .contains("host slice"));
+
+    // Pitches given directly, too small or not a multiple of the row pitch:
+    assert!(err(buffer.cmd().write(&data).rect([0, 0], [0, 0], TILE, 4, 0, 0, 0).enq())
+        .contains("row pitch (4) is less than the region width (8)"));
+    assert!(err(buffer.cmd().write(&data).rect([0, 0], [0, 0], TILE, 0, 0, 16, 100).enq())
+        .contains("slice pitch (100)"));
+    assert!(err(buffer.cmd().write(&data).rect([0, 0], [0, 0], TILE, 0, 0, 16, 80).enq())
+        .contains("slice pitch (80)"));
+
+    // A copy too large for its destination:
+    let small = Buffer::<u32>::builder().dims([4, 4]).uninit().build(&queue).unwrap();
+    assert!(err(buffer.copy_rect(&small, [0, 0], [0, 0], [4, 5]).enq())
+        .contains("destination buffer"));
+}
+
+#[test]
+fn round_trip() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let buffer = Buffer::<u32>::builder().dims(TILE).fill_val(0).build(&queue).unwrap();
+    let data = grid();
+
+    // Out of the larger host grid into the whole buffer:
+    let mut write_event = Event::empty();
+    buffer.write_rect([0, 0], TILE_ORIGIN, TILE, GRID, &data).enew(&mut write_event).enq()
+        .unwrap();
+
+    let tile = buffer.read_to_vec().unwrap();
+    for y in 0..TILE[1] {
+        for x in 0..TILE[0] {
+            assert_eq!(tile[y * TILE[0] + x],
+                data[(y + TILE_ORIGIN[1]) * GRID[0] + x + TILE_ORIGIN[0]]);
+        }
+    }
+
+    // And back into a zeroed grid, after the write:
+    let mut result = vec![0u32; data.len()];
+    buffer.read_rect([0, 0], TILE_ORIGIN, TILE, GRID, &mut result).ewait(&write_event).enq()
+        .unwrap();
+
+    for y in 0..GRID[1] {
+        for x in 0..GRID[0] {
+            let in_tile = x >= TILE_ORIGIN[0] && x < TILE_ORIGIN[0] + TILE[0] &&
+                y >= TILE_ORIGIN[1] && y < TILE_ORIGIN[1] + TILE[1];
+            assert_eq!(result[y * GRID[0] + x], if in_tile { data[y * GRID[0] + x] } else { 0 });
+        }
+    }
+
+    // Zero pitches are computed from the region:
+    let mut packed = vec![0u32; TILE[0] * TILE[1]];
+    buffer.cmd().read(&mut packed).rect([0, 0], [0, 0], TILE, 0, 0, 0, 0).enq().unwrap();
+    assert_eq!(packed, tile);
+
+    // The lower right quarter of the tile to the upper left of another:
+    let other = Buffer::<u32>::builder().dims(TILE).fill_val(0).build(&queue).unwrap();
+    buffer.copy_rect(&other, [TILE[0] / 2, TILE[1] / 2], [0, 0], [TILE[0] / 2, TILE[1] / 2])
+        .enq().unwrap();
+
+    let copied = other.read_to_vec().unwrap();
+    for y in 0..TILE[1] {
+        for x in 0..TILE[0] {
+            let expected = if x < TILE[0] / 2 && y < TILE[1] / 2 {
+                tile[(y + TILE[1] / 2) * TILE[0] + x + TILE[0] / 2]
+            } else {
+                0
+            };
+            assert_eq!(copied[y * TILE[0] + x], expected);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Tests reading, writing, copying, filling, and passing to kernels only the
+//! part of a buffer within a `BufferView`.
+
+use core;
+use standard::{Context, Queue, Buffer, ProQue};
+
+const LEN: usize = 1 << 12;
+
+static SRC: &'static str = r#"
+    __kernel void add(__global uint* buf, uint buf_offset, uint val) {
+        buf[buf_offset + get_global_id(0)] += val;
+    }
+"#;
+
+fn proque() -> ProQue {
+    ProQue::builder().src(SRC).dims([LEN]).build().unwrap()
+}
+
+/// Asserts that the elements of `vec` within `range` equal `inside` and
+/// that all others equal `outside`.
+fn assert_range(vec: &[u32], range: ::std::ops::Range<usize>, inside: u32, outside: u32) {
+    for (idx, &val) in vec.iter().enumerate() {
+        let expected = if idx >= range.start && idx < range.end { inside } else { outside };
+        assert_eq!(val, expected, "idx: {}", idx);
+    }
+}
+
+#[test]
+fn view_bounds() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let data: Vec<u32> = (0..16).collect();
+    let buffer = Buffer::<u32>::builder()
+        .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+        .dims([16])
+        .build_with_data(&queue, &data).unwrap();
+
+    assert!(buffer.view(4..17).is_err());
+    assert!(buffer.view(8..4).is_err());
+    assert!(buffer.view(16..16).unwrap().is_empty());
+
+    let view = buffer.view(4..12).unwrap();
+    assert_eq!((view.offset(), view.len(), view.range()), (4, 8, 4..12));
+
+    let sub_view = view.view(2..6).unwrap();
+    assert_eq!(sub_view.range(), 6..10);
+    assert!(view.view(2..9).is_err());
+
+    let (head, tail) = view.split_at(3).unwrap();
+    assert_eq!((head.range(), tail.range()), (4..7, 7..12));
+    assert!(view.split_at(9).is_err());
+
+    // Commands extending beyond the view are rejected before reaching the
+    // implementation:
+    let err = view.write(&data[..9]).enq().unwrap_err();
+    assert!(err.to_string().contains("exceeds"), "{}", err);
+    let err = view.cmd().offset(6).write(&data[..4]).enq().unwrap_err();
+    assert!(err.to_string().contains("exceeds"), "{}", err);
+    let err = view.cmd().offset(8).read_to_vec().unwrap_err();
+    assert!(err.to_string().contains("out of range"), "{}", err);
+    let err = view.cmd().offset(1).fill(&[0], Some(8)).enq().unwrap_err();
+    assert!(err.to_string().contains("exceeds"), "{}", err);
+    let err = sub_view.cmd().copy_to_view(&view).enq().unwrap_err();
+    assert!(err.to_string().contains("exceeds"), "{}", err);
+    let err = view.cmd().rect([0; 3], [0; 3], [1; 3], 0, 0, 0, 0).write(&data[..1]).enq()
+        .unwrap_err();
+    assert!(err.to_string().contains("Rectangular"), "{}", err);
+
+    // Copy destinations are checked as well:
+    let err = buffer.cmd().copy(&buffer, 10, 8).enq().unwrap_err();
+    assert!(err.to_string().contains("exceeds"), "{}", err);
+}
+
+#[test]
+fn view_read_write() {
+    let proque = proque();
+    let buffer = proque.create_buffer::<u32>().unwrap();
+    let view = buffer.view(100..300).unwrap();
+
+    view.write(&vec![7u32; 200]).enq().unwrap();
+    assert_range(&buffer.read_to_vec().unwrap(), 100..300, 7, 0);
+
+    // Offsets are relative to the view:
+    view.cmd().offset(150).write(&vec![9u32; 50]).enq().unwrap();
+    assert_range(&buffer.read_to_vec().unwrap()[250..], 0..50, 9, 0);
+
+    let mut partial = vec![0u32; 100];
+    view.cmd().offset(100).read(&mut partial).enq().unwrap();
+    assert_range(&partial, 0..50, 7, 9);
+
+    let vec = view.read_to_vec().unwrap();
+    assert_eq!(vec.len(), 200);
+    assert_range(&vec, 0..150, 7, 9);
+}
+
+#[test]
+fn view_copy_fill() {
+    let proque = proque();
+    let src = proque.create_buffer::<u32>().unwrap();
+    let dst = proque.create_buffer::<u32>().unwrap();
+
+    src.cmd().fill(&[5], None).enq().unwrap();
+
+    let src_view = src.view(1000..1500).unwrap();
+    let dst_view = dst.view(3..503).unwrap();
+    src_view.cmd().copy_to_view(&dst_view).enq().unwrap();
+    proque.queue().finish().unwrap();
+    assert_range(&dst.read_to_vec().unwrap(), 3..503, 5, 0);
+
+    // Copies to a plain buffer offset relative to the source view:
+    src_view.cmd().offset(100).copy(&dst, 2000, 10).enq().unwrap();
+    proque.queue().finish().unwrap();
+    assert_range(&dst.read_to_vec().unwrap()[2000..], 0..10, 5, 0);
+
+    // Fills without a length fill the rest of the view only:
+    let fill_view = src.view(64..128).unwrap();
+    fill_view.cmd().offset(32).fill(&[8], None).enq().unwrap();
+    proque.queue().finish().unwrap();
+    assert_range(&src.read_to_vec().unwrap(), 96..128, 8, 5);
+}
+
+#[test]
+fn view_kernel_arg() {
+    let proque = proque();
+    let buffer = proque.create_buffer::<u32>().unwrap();
+    let align = proque.queue().device_limits().mem_base_addr_align_bytes() / 4;
+
+    // Unaligned, passed as the whole buffer along with the offset:
+    let view = buffer.view(3..103).unwrap();
+    assert!(!view.is_aligned() || align <= 1);
+    let kernel = proque.create_kernel("add").unwrap()
+        .gws([view.len()])
+        .arg_view(&view)
+        .arg_scl(1u32);
+    kernel.enq().unwrap();
+    assert_range(&buffer.read_to_vec().unwrap(), 3..103, 1, 0);
+
+    // Aligned, passed as a sub-buffer:
+    if align > 0 && 2 * align <= LEN {
+        let view = buffer.view(align..2 * align).unwrap();
+        assert!(view.is_aligned());
+        let kernel = proque.create_kernel("add").unwrap()
+            .gws([view.len()])
+            .arg_view(&view)
+            .arg_scl(10u32);
+        kernel.enq().unwrap();
+
+        let vec = buffer.read_to_vec().unwrap();
+        for (idx, &val) in vec.iter().enumerate() {
+            let expected = if idx >= 3 && idx < 103 { 1 } else { 0 } +
+                if idx >= align && idx < 2 * align { 10 } else { 0 };
+            assert_eq!(val, expected, "idx: {}", idx);
+        }
+    }
+}
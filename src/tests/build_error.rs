@@ -15,3 +15,28 @@ fn bad_kernel_variable_names() {
 
     let ocl_pq = ProQue::builder().src(kernel).build().unwrap();
 }
+
+#[test]
+fn program_build_error_logs() {
+    use error::Error as OclError;
+    use enums::BuildStatus;
+
+    let kernel = r#"
+        kernel void multiply(global float* buffer, float coeff) {
+            buffer[get_global_id(0)] *= not_a_variable;
+        }
+    "#;
+
+    match ProQue::builder().src(kernel).build() {
+        Err(OclError::ProgramBuild(ref err)) => {
+            assert_eq!(err.logs().len(), 1);
+            let (ref device_name, status, ref log) = err.logs()[0];
+            assert!(!device_name.is_empty());
+            assert_eq!(status, BuildStatus::Error);
+            assert!(log.contains("not_a_variable"));
+            assert_eq!(err.first_log(), Some(log.as_str()));
+        },
+        Err(err) => panic!("Expected a 'ProgramBuild' error, found: '{}'.", err),
+        Ok(_) => panic!("Building invalid source should fail."),
+    }
+}
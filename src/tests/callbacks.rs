@@ -0,0 +1,284 @@
+//! Drives the callback trampolines directly (as an implementation would)
+//! with closures which capture state, panic, and register further callbacks
+//! re-entrantly, checking that each closure runs at most once and is dropped
+//! exactly once.
+
+use std::mem;
+use std::ptr;
+use std::ffi::CString;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use libc::c_void;
+use cl_h::cl_event;
+use core::{self, CommandExecutionStatus};
+use core::callbacks::{register_once, register_persistent, event_trampoline,
+    mem_destructor_trampoline, build_program_trampoline, native_trampoline,
+    context_notify_trampoline};
+use error::{Error as OclError, Result as OclResult};
+
+type EventResult = OclResult<CommandExecutionStatus>;
+type EventTrampoline = extern "C" fn(cl_event, i32, *mut c_void);
+
+/// Returns a closure which records each status it receives in `log` and
+/// holds a reference to `state` (so that `Arc::strong_count` reveals whether
+/// it has been dropped).
+fn event_closure(log: &Arc<Mutex<Vec<EventResult>>>, state: &Arc<()>)
+        -> impl FnOnce(EventResult) + Send + 'static
+{
+    let log = log.clone();
+    let state = state.clone();
+    move |status| {
+        let _state = state;
+        log.lock().unwrap().push(status);
+    }
+}
+
+/// Returns the event trampoline for the type of `f`.
+fn event_trampoline_of<F>(_: &F) -> EventTrampoline
+        where F: FnOnce(EventResult) + Send + 'static
+{
+    event_trampoline::<F>
+}
+
+/// Registers `f`, returning the `user_data` pointer to invoke it with.
+fn register_deferred<F: Send + 'static>(f: F) -> *mut c_void {
+    let mut user_data = ptr::null_mut();
+    register_once(f, |ud| { user_data = ud; Ok(()) }).unwrap();
+    user_data
+}
+
+#[test]
+fn event_invoked_after_registration() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let state = Arc::new(());
+    let f = event_closure(&log, &state);
+    let trampoline = event_trampoline_of(&f);
+
+    let user_data = register_deferred(f);
+    assert_eq!(Arc::strong_count(&state), 2);
+
+    trampoline(ptr::null_mut(), 0, user_data);
+    assert_eq!(Arc::strong_count(&state), 1);
+
+    let log = log.lock().unwrap();
+    assert_eq!(log.len(), 1);
+    assert_eq!(*log[0].as_ref().unwrap(), CommandExecutionStatus::Complete);
+}
+
+#[test]
+fn event_invoked_during_registration() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let state = Arc::new(());
+    let f = event_closure(&log, &state);
+    let trampoline = event_trampoline_of(&f);
+
+    register_once(f, |user_data| {
+        trampoline(ptr::null_mut(), 0, user_data);
+        Ok(())
+    }).unwrap();
+
+    assert_eq!(Arc::strong_count(&state), 1);
+    assert_eq!(log.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn event_invoked_during_failed_registration() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let state = Arc::new(());
+    let f = event_closure(&log, &state);
+    let trampoline = event_trampoline_of(&f);
+
+    let result = register_once(f, |user_data| {
+        trampoline(ptr::null_mut(), 0, user_data);
+        OclError::err("Registration failed.")
+    });
+
+    assert!(result.is_err());
+    assert_eq!(Arc::strong_count(&state), 1);
+    assert_eq!(log.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn event_registration_failed() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let state = Arc::new(());
+    let f = event_closure(&log, &state);
+
+    let result = register_once(f, |_| OclError::err("Registration failed."));
+
+    assert!(result.is_err());
+    assert_eq!(Arc::strong_count(&state), 1);
+    assert_eq!(log.lock().unwrap().len(), 0);
+}
+
+#[test]
+fn event_abnormal_termination() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let state = Arc::new(());
+    let f = event_closure(&log, &state);
+    let trampoline = event_trampoline_of(&f);
+
+    let user_data = register_deferred(f);
+    // CL_OUT_OF_RESOURCES:
+    trampoline(ptr::null_mut(), -5, user_data);
+
+    assert_eq!(Arc::strong_count(&state), 1);
+    assert!(log.lock().unwrap()[0].is_err());
+}
+
+#[test]
+fn event_closure_panics() {
+    let state = Arc::new(());
+    let captured = state.clone();
+    let f = move |_: EventResult| {
+        let _captured = captured;
+        panic!("Panic within an event callback.");
+    };
+    let trampoline = event_trampoline_of(&f);
+
+    let user_data = register_deferred(f);
+    let panic_count = core::callback_panic_count();
+    // Returns normally rather than unwinding:
+    trampoline(ptr::null_mut(), 0, user_data);
+
+    assert_eq!(Arc::strong_count(&state), 1);
+    // (Other tests may panic concurrently.)
+    assert!(core::callback_panic_count() > panic_count);
+}
+
+#[test]
+fn event_reentrant() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let state = Arc::new(());
+
+    let inner_count = count.clone();
+    let inner_state = state.clone();
+    let outer = move |_: EventResult| {
+        let outer_count = inner_count.clone();
+        let inner = move |_: EventResult| {
+            let _state = inner_state;
+            inner_count.fetch_add(1, Ordering::SeqCst);
+        };
+        let trampoline = event_trampoline_of(&inner);
+
+        // Registers (and is invoked for) another callback from within this
+        // one, as would be the case for a callback which enqueues a command:
+        register_once(inner, |user_data| {
+            trampoline(ptr::null_mut(), 0, user_data);
+            Ok(())
+        }).unwrap();
+
+        outer_count.fetch_add(1, Ordering::SeqCst);
+    };
+    let trampoline = event_trampoline_of(&outer);
+
+    register_once(outer, |user_data| {
+        trampoline(ptr::null_mut(), 0, user_data);
+        Ok(())
+    }).unwrap();
+
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    assert_eq!(Arc::strong_count(&state), 1);
+}
+
+#[test]
+fn mem_destructor() {
+    fn trampoline_of<F: FnOnce() + Send + 'static>(_: &F)
+            -> extern "C" fn(::cl_h::cl_mem, *mut c_void)
+    {
+        mem_destructor_trampoline::<F>
+    }
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let captured = count.clone();
+    let f = move || { captured.fetch_add(1, Ordering::SeqCst); };
+    let trampoline = trampoline_of(&f);
+
+    trampoline(ptr::null_mut(), register_deferred(f));
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    assert_eq!(Arc::strong_count(&count), 1);
+
+    let captured = count.clone();
+    let f = move || { let _captured = captured; panic!("Panic within a destructor callback."); };
+    let trampoline = trampoline_of(&f);
+
+    trampoline(ptr::null_mut(), register_deferred(f));
+    assert_eq!(Arc::strong_count(&count), 1);
+}
+
+#[test]
+fn build_program() {
+    fn trampoline_of<F: FnOnce() + Send + 'static>(_: &F)
+            -> extern "C" fn(::cl_h::cl_program, *mut c_void)
+    {
+        build_program_trampoline::<F>
+    }
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let captured = count.clone();
+    let f = move || { captured.fetch_add(1, Ordering::SeqCst); };
+    let trampoline = trampoline_of(&f);
+
+    register_once(f, |user_data| {
+        trampoline(ptr::null_mut(), user_data);
+        Ok(())
+    }).unwrap();
+
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+    assert_eq!(Arc::strong_count(&count), 1);
+}
+
+#[test]
+fn native() {
+    fn trampoline_of<F: FnOnce(&mut [&mut [u8]]) + Send + 'static>(_: &F)
+            -> extern "C" fn(*mut c_void)
+    {
+        native_trampoline::<F>
+    }
+
+    let mut contents = vec![1u8; 16];
+    let f = |mems: &mut [&mut [u8]]| {
+        for byte in mems[0].iter_mut() { *byte += 1; }
+    };
+    let trampoline = trampoline_of(&f);
+
+    // Laid out as by `core::enqueue_native` after substitution of the
+    // memory object pointer by the implementation:
+    let mut words: Vec<usize> = vec![register_deferred(f) as usize, 1,
+        contents.as_mut_ptr() as usize, contents.len()];
+    trampoline(words.as_mut_ptr() as *mut c_void);
+    mem::drop(words);
+
+    assert!(contents.iter().all(|&b| b == 2));
+}
+
+#[test]
+fn context_notify() {
+    fn trampoline_of<F: Fn(&str) + Send + Sync + 'static>(_: &F)
+            -> extern "C" fn(*const ::libc::c_char, *const c_void, ::libc::size_t, *mut c_void)
+    {
+        context_notify_trampoline::<F>
+    }
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let captured = log.clone();
+    let f = move |errinfo: &str| {
+        if errinfo.is_empty() { panic!("Panic within a context notify callback."); }
+        captured.lock().unwrap().push(errinfo.to_owned());
+    };
+    let trampoline = trampoline_of(&f);
+    let errinfo = CString::new("Out of host memory.").unwrap();
+
+    // Invoked repeatedly, including with a panic in between, then dropped
+    // upon failure:
+    let result = register_persistent(f, |user_data| {
+        trampoline(errinfo.as_ptr(), ptr::null(), 0, user_data);
+        trampoline(ptr::null(), ptr::null(), 0, user_data);
+        trampoline(errinfo.as_ptr(), ptr::null(), 0, user_data);
+        OclError::err("Context creation failed.")
+    });
+
+    assert!(result.is_err());
+    assert_eq!(*log.lock().unwrap(), vec!["Out of host memory.", "Out of host memory."]);
+    assert_eq!(Arc::strong_count(&log), 1);
+}
@@ -0,0 +1,63 @@
+//! Tests `util::ToClBytes` and the byte representation of context
+//! properties.
+
+use libc::c_void;
+use util::ToClBytes;
+use cl_h;
+use core::{ContextProperties, PlatformId};
+
+/// Returns `bytes` in little-endian order, reversed if the host is big
+/// endian.
+fn ne(mut bytes: Vec<u8>) -> Vec<u8> {
+    if cfg!(target_endian = "big") { bytes.reverse(); }
+    bytes
+}
+
+#[test]
+fn to_cl_bytes_u32() {
+    assert_eq!(0x01020304u32.to_cl_bytes(), ne(vec![4, 3, 2, 1]));
+    assert_eq!((cl_h::CL_TRUE as cl_h::cl_bool).to_cl_bytes(), ne(vec![1, 0, 0, 0]));
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn to_cl_bytes_usize_ptr() {
+    assert_eq!(0x0102030405060708usize.to_cl_bytes(), ne(vec![8, 7, 6, 5, 4, 3, 2, 1]));
+    assert_eq!((0x0102030405060708usize as *mut c_void).to_cl_bytes(),
+        ne(vec![8, 7, 6, 5, 4, 3, 2, 1]));
+    assert_eq!((0x0102usize as *const u8).to_cl_bytes(), ne(vec![2, 1, 0, 0, 0, 0, 0, 0]));
+}
+
+#[cfg(target_pointer_width = "32")]
+#[test]
+fn to_cl_bytes_usize_ptr() {
+    assert_eq!(0x01020304usize.to_cl_bytes(), ne(vec![4, 3, 2, 1]));
+    assert_eq!((0x01020304usize as *mut c_void).to_cl_bytes(), ne(vec![4, 3, 2, 1]));
+    assert_eq!((0x0102usize as *const u8).to_cl_bytes(), ne(vec![2, 1, 0, 0]));
+}
+
+#[test]
+fn context_properties_to_bytes() {
+    use std::mem;
+
+    let ptr = 0x1234usize as *mut c_void;
+    let props = ContextProperties::new()
+        .platform(unsafe { PlatformId::from_fresh_ptr(ptr) })
+        .interop_user_sync(true);
+
+    // Kind, 32 bits of padding, value, 32 bits of padding (per property),
+    // then a terminating `size_t` zero:
+    let mut expected = Vec::new();
+    expected.extend_from_slice(&cl_h::CL_CONTEXT_PLATFORM.to_ne_bytes());
+    expected.extend_from_slice(&[0; 4]);
+    expected.extend_from_slice(&(ptr as usize).to_ne_bytes());
+    expected.extend_from_slice(&[0; 4]);
+    expected.extend_from_slice(&cl_h::CL_CONTEXT_INTEROP_USER_SYNC.to_ne_bytes());
+    expected.extend_from_slice(&[0; 4]);
+    expected.extend_from_slice(&cl_h::CL_TRUE.to_ne_bytes());
+    expected.extend_from_slice(&[0; 4]);
+    expected.extend_from_slice(&[0; 8][..mem::size_of::<usize>()]);
+
+    assert_eq!(props.to_bytes(), expected);
+    assert_eq!(ContextProperties::new().to_bytes(), vec![0; mem::size_of::<usize>()]);
+}
@@ -0,0 +1,114 @@
+//! Tests `Complex<f32>` and `Complex<f64>` buffers and kernel arguments,
+//! which correspond to `float2` and `double2` on the device.
+
+use std::mem;
+use num::complex::Complex;
+use core::{OclPrm, ClFloat2, ClDouble2};
+use standard::ProQue;
+
+const LEN: usize = 1024;
+
+static SRC: &'static str = r#"
+    __kernel void mul_f32(__global float2* buffer, float2 factor) {
+        uint idx = get_global_id(0);
+        float2 z = buffer[idx];
+        buffer[idx] = (float2)(z.x * factor.x - z.y * factor.y,
+            z.x * factor.y + z.y * factor.x);
+    }
+"#;
+
+static SRC_F64: &'static str = r#"
+    #pragma OPENCL EXTENSION cl_khr_fp64 : enable
+
+    __kernel void mul_f64(__global double2* buffer, double2 factor) {
+        uint idx = get_global_id(0);
+        double2 z = buffer[idx];
+        buffer[idx] = (double2)(z.x * factor.x - z.y * factor.y,
+            z.x * factor.y + z.y * factor.x);
+    }
+"#;
+
+fn vec_init_f32() -> Vec<Complex<f32>> {
+    (0..LEN).map(|i| Complex::new(i as f32 * 0.5, 1.0 - i as f32 * 0.25)).collect()
+}
+
+#[test]
+fn complex_layout() {
+    assert_eq!(mem::size_of::<Complex<f32>>(), mem::size_of::<ClFloat2>());
+    assert_eq!(mem::size_of::<Complex<f64>>(), mem::size_of::<ClDouble2>());
+
+    let z = Complex::new(1.0f32, 2.0);
+    let vec: ClFloat2 = unsafe { mem::transmute(z) };
+    assert_eq!(vec, ClFloat2([1.0, 2.0]));
+
+    let z = Complex::new(1.0f64, 0.0);
+    assert_eq!(z.swap_bytes().unwrap(),
+        Complex::new(f64::from_bits(0x000000000000f03f), 0.0));
+    assert_eq!(z.swap_bytes().unwrap().swap_bytes().unwrap(), z);
+}
+
+#[test]
+fn complex_buffer() {
+    let pro_que = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let buffer = pro_que.create_buffer::<Complex<f32>>().unwrap();
+    assert!(buffer.read_to_vec().unwrap().iter().all(|&z| z == Complex::new(0.0, 0.0)));
+
+    let fill = Complex::new(3.0, -4.0);
+    buffer.cmd().fill(&[fill], None).enq().unwrap();
+    assert!(buffer.read_to_vec().unwrap().iter().all(|&z| z == fill));
+
+    let vec = vec_init_f32();
+    buffer.write(&vec).enq().unwrap();
+    assert_eq!(buffer.read_to_vec().unwrap(), vec);
+}
+
+#[test]
+fn complex_kernel() {
+    let pro_que = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let buffer = pro_que.create_buffer::<Complex<f32>>().unwrap();
+    let vec = vec_init_f32();
+    buffer.write(&vec).enq().unwrap();
+
+    let factor = Complex::new(0.5f32, 2.0);
+    let kernel = pro_que.create_kernel("mul_f32").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(factor);
+    kernel.enq().unwrap();
+
+    for (i, (z, &orig)) in buffer.read_to_vec().unwrap().into_iter().zip(vec.iter()).enumerate() {
+        let expected = orig * factor;
+        assert!((z - expected).norm() <= 1e-4 * expected.norm().max(1.0),
+            "buffer[{}]: {} (expected: {})", i, z, expected);
+    }
+}
+
+#[test]
+fn complex_kernel_f64() {
+    let pro_que = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let factor = Complex::new(0.5f64, 2.0);
+
+    if !pro_que.queue().device_has_extension("cl_khr_fp64") {
+        let mut kernel = pro_que.create_kernel("mul_f32").unwrap()
+            .arg_buf_named::<Complex<f32>>("buffer", None)
+            .arg_scl_named::<ClFloat2>("factor", None);
+        assert!(kernel.set_arg_scl_named("factor", factor).is_err());
+        return;
+    }
+
+    let pro_que = ProQue::builder().src(SRC_F64).dims([LEN]).build().unwrap();
+    let vec: Vec<Complex<f64>> = (0..LEN)
+        .map(|i| Complex::new(i as f64 * 0.5, 1.0 - i as f64 * 0.25)).collect();
+    let buffer = pro_que.create_buffer::<Complex<f64>>().unwrap();
+    buffer.write(&vec).enq().unwrap();
+
+    let kernel = pro_que.create_kernel("mul_f64").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(factor);
+    kernel.enq().unwrap();
+
+    for (i, (z, &orig)) in buffer.read_to_vec().unwrap().into_iter().zip(vec.iter()).enumerate() {
+        let expected = orig * factor;
+        assert!((z - expected).norm() <= 1e-10 * expected.norm().max(1.0),
+            "buffer[{}]: {} (expected: {})", i, z, expected);
+    }
+}
@@ -32,7 +32,7 @@ fn concurrent() {
 		let thread_name = format!("[thread_{}]", i);
 
 		let th = thread::Builder::new().name(thread_name.clone()).spawn(move || {
-			let platforms = Platform::list();
+			let platforms = Platform::list().unwrap();
 		}).expect(&format!("Error creating {}", &thread_name));
 
 		threads.push(th);
@@ -54,7 +54,7 @@ fn main_from_example() {
 	let dims = [data_set_size];
 	let mut threads = Vec::new();
 
-	let platforms = Platform::list();
+	let platforms = Platform::list().unwrap();
 
 	println!("Looping through avaliable platforms ({}):", platforms.len());
 
@@ -63,7 +63,7 @@ fn main_from_example() {
     	let platform = &platforms[p_idx];
     	println!("Platform[{}]: {} ({})", p_idx, platform.name(), platform.vendor());
 
-    	let devices = Device::list_all(platform);
+    	let devices = Device::list_all(platform).unwrap();
 
     	// Loop through each device:
     	for device_idx in 0..devices.len() {
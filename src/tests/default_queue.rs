@@ -0,0 +1,135 @@
+//! Tests the resolution of the queue used by a command: the queue specified
+//! for the command, then the default queue of the object, then that of its
+//! context.
+
+use core::{self, EventInfo, EventInfoResult};
+use standard::{Context, Queue, Buffer, Program, Kernel, Event};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global int* buffer, int addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+const LEN: usize = 64;
+
+fn same_queue(a: &Queue, b: &Queue) -> bool {
+    unsafe { a.core_as_ref().as_ptr() == b.core_as_ref().as_ptr() }
+}
+
+fn new_queues(context: &Context) -> (Queue, Queue) {
+    let device = context.devices()[0];
+    (Queue::new(context, device).unwrap(), Queue::new(context, device).unwrap())
+}
+
+fn new_buffer(context: &Context) -> Buffer<i32> {
+    Buffer::builder()
+        .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+        .dims([LEN])
+        .build_in_context_with_data(context, &[0; LEN])
+        .unwrap()
+}
+
+fn assert_enqueued_on(event: &Event, queue: &Queue) {
+    match core::get_event_info(event.core_as_ref().unwrap(), EventInfo::CommandQueue) {
+        EventInfoResult::CommandQueue(Some(q)) => assert_eq!(unsafe { q.as_ptr() },
+            unsafe { queue.core_as_ref().as_ptr() }),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn context_default_queue() {
+    let context = Context::builder().build().unwrap();
+    assert!(context.default_queue().is_none());
+
+    let (queue, _) = new_queues(&context);
+    context.set_default_queue(&queue).unwrap();
+    assert!(same_queue(&context.default_queue().unwrap(), &queue));
+
+    // Queues of other contexts are rejected:
+    let other_context = Context::builder().build().unwrap();
+    let (other_queue, _) = new_queues(&other_context);
+    assert!(context.set_default_queue(&other_queue).is_err());
+    assert!(same_queue(&context.default_queue().unwrap(), &queue));
+
+    // Created once, on first use:
+    let created = other_context.get_or_create_default_queue().unwrap();
+    assert!(same_queue(&other_context.get_or_create_default_queue().unwrap(), &created));
+    assert!(same_queue(&other_context.default_queue().unwrap(), &created));
+}
+
+/// Checks each resolution level without enqueuing anything.
+#[test]
+fn resolution_order() {
+    let context = Context::builder().build().unwrap();
+    let (context_queue, object_queue) = new_queues(&context);
+    let mut buffer = new_buffer(&context);
+
+    // Nothing to resolve:
+    assert!(buffer.default_queue().is_none());
+    let err = buffer.resolve_queue().unwrap_err().to_string();
+    assert!(err.contains("none was specified for the command") &&
+        err.contains("the object has no default queue") &&
+        err.contains("its context has no default queue"), "{}", err);
+    let err = buffer.cmd().read(&mut [0; LEN]).enq().unwrap_err().to_string();
+    assert!(err.starts_with("ocl::BufferCmd::enq: No queue available"), "{}", err);
+
+    // Context:
+    context.set_default_queue(&context_queue).unwrap();
+    assert!(same_queue(&buffer.resolve_queue().unwrap(), &context_queue));
+
+    // Object:
+    buffer.set_default_queue(&object_queue);
+    assert!(same_queue(&buffer.resolve_queue().unwrap(), &object_queue));
+    assert!(same_queue(buffer.default_queue().unwrap(), &object_queue));
+
+    // Buffers created with a queue never use the context default:
+    let buffer = Buffer::builder()
+        .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+        .dims([LEN])
+        .build_with_data(&object_queue, &[0i32; LEN])
+        .unwrap();
+    assert!(same_queue(&buffer.resolve_queue().unwrap(), &object_queue));
+}
+
+/// Checks which queue each command is actually enqueued on.
+#[test]
+fn commands_use_resolved_queue() {
+    let context = Context::builder().build().unwrap();
+    let (context_queue, command_queue) = new_queues(&context);
+    let buffer = new_buffer(&context);
+
+    let program = Program::builder().src(SRC).devices(context.devices()[0])
+        .build(&context).unwrap();
+    let kernel = Kernel::new_in_context("add", &program, &context).unwrap()
+        .gws([LEN])
+        .arg_buf(&buffer)
+        .arg_scl(1i32);
+    assert!(kernel.default_queue().is_none());
+    assert!(kernel.enq().is_err());
+
+    context.set_default_queue(&context_queue).unwrap();
+
+    let mut event = Event::empty();
+    kernel.cmd().enew(&mut event).enq().unwrap();
+    assert_enqueued_on(&event, &context_queue);
+
+    // Overridden for one command:
+    let mut event = Event::empty();
+    kernel.cmd().queue(&command_queue).enew(&mut event).enq().unwrap();
+    assert_enqueued_on(&event, &command_queue);
+    event.wait().unwrap();
+
+    let mut event = Event::empty();
+    let mut vec = vec![0; LEN];
+    buffer.cmd().read(&mut vec).enew(&mut event).enq().unwrap();
+    assert_enqueued_on(&event, &context_queue);
+    assert_eq!(vec, vec![2; LEN]);
+
+    let mut event = Event::empty();
+    buffer.cmd().queue(&command_queue).write(&vec![5; LEN]).enew(&mut event).enq().unwrap();
+    assert_enqueued_on(&event, &command_queue);
+    command_queue.finish().unwrap();
+    assert_eq!(buffer.read_to_vec().unwrap(), vec![5; LEN]);
+}
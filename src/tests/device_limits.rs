@@ -0,0 +1,68 @@
+//! Tests that device limits are queried once per device and shared.
+
+use core::{self, DeviceInfo, ImageFormat, ImageDescriptor, MemObjectType, ImageChannelOrder,
+    ImageChannelDataType};
+use flags;
+use standard::{Context, Queue, Buffer, Image};
+
+const LIMIT_REQUESTS: [DeviceInfo; 10] = [DeviceInfo::MemBaseAddrAlign,
+    DeviceInfo::MaxMemAllocSize, DeviceInfo::MaxWorkGroupSize, DeviceInfo::ImageSupport,
+    DeviceInfo::Image2dMaxWidth, DeviceInfo::Image2dMaxHeight, DeviceInfo::Image3dMaxWidth,
+    DeviceInfo::Image3dMaxHeight, DeviceInfo::Image3dMaxDepth, DeviceInfo::HostUnifiedMemory];
+
+#[test]
+fn device_limits_queried_once() {
+    let context = Context::builder().build().unwrap();
+
+    // Each field is queried exactly once per device upon context creation:
+    for device in context.devices() {
+        for &request in LIMIT_REQUESTS.iter() {
+            assert_eq!(core::device_info_query_count(device, request), 1, "{:?}", request);
+        }
+    }
+
+    for (idx, device) in context.devices().iter().enumerate() {
+        let limits = context.device_limits(idx).unwrap();
+        assert_eq!(limits.max_wg_size(), device.max_wg_size().unwrap());
+        assert!(limits.max_mem_alloc_size() > 0);
+        assert_eq!(limits.mem_base_addr_align_bytes() * 8, limits.mem_base_addr_align() as usize);
+
+        let queue = Queue::new(&context, device.clone()).unwrap();
+        assert_eq!(queue.device_limits(), limits);
+        assert_eq!(queue.device_max_wg_size(), limits.max_wg_size());
+
+        for _ in 0..32 {
+            Buffer::<f32>::new(&queue, None, [256], None).unwrap();
+        }
+
+        if limits.image_support() {
+            let image_format = ImageFormat::new(ImageChannelOrder::Rgba,
+                ImageChannelDataType::UnsignedInt8);
+            for _ in 0..8 {
+                let image_desc = ImageDescriptor::new(MemObjectType::Image2d, 16, 16, 1, 0, 0,
+                    0, None);
+                Image::<u8>::new(&queue, flags::MEM_READ_WRITE, image_format.clone(),
+                    image_desc, None).unwrap();
+            }
+
+            // Exceeding the maximum dimensions fails without calling the driver:
+            let [max_w, max_h] = limits.image2d_max_dims();
+            let image_desc = ImageDescriptor::new(MemObjectType::Image2d, max_w + 1, max_h, 1,
+                0, 0, 0, None);
+            assert!(Image::<u8>::new(&queue, flags::MEM_READ_WRITE, image_format, image_desc,
+                None).is_err());
+        }
+
+        // Exceeding the maximum allocation size fails without calling the driver:
+        let too_long = (limits.max_mem_alloc_size() / 4 + 1) as usize;
+        assert!(Buffer::<f32>::new(&queue, None, [too_long], None).is_err());
+
+        // `Device::max_wg_size` above is the only repeated query:
+        for &request in LIMIT_REQUESTS.iter() {
+            let expected = if request == DeviceInfo::MaxWorkGroupSize { 2 } else { 1 };
+            assert_eq!(core::device_info_query_count(device, request), expected, "{:?}", request);
+        }
+    }
+
+    assert!(context.device_limits(context.devices().len()).is_err());
+}
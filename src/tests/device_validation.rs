@@ -0,0 +1,210 @@
+//! Validation of work dimensions, allocations, and images against device
+//! limits, and filtering of devices by version and extension, using the
+//! device snapshots in `tests::fixtures`.
+
+use core::{DeviceInfo, ImageDescriptor, MemObjectType, WorkSizes};
+use standard::{DeviceLimits, DeviceInfoSource, check_work_dims};
+use tests::fixtures::{self, DeviceFixture};
+
+fn dims(gws: &[usize], lws: Option<&[usize]>) -> WorkSizes {
+    WorkSizes::new(gws, lws, None).unwrap()
+}
+
+fn err_msg(result: ::error::Result<()>) -> String {
+    format!("{}", result.err().expect("expected a validation error"))
+}
+
+/// Validates `dims` as a launch on `device` would.
+fn check_dims(device: &DeviceFixture, dims: &WorkSizes, kernel_wg_size: Option<usize>)
+        -> ::error::Result<()>
+{
+    let limits = DeviceLimits::new(device).unwrap();
+    check_work_dims(dims, &device.max_wi_sizes().unwrap(), limits.max_wg_size(), kernel_wg_size,
+        device.version_number().unwrap())
+}
+
+fn image_desc(image_type: MemObjectType, width: usize, height: usize, depth: usize)
+        -> ImageDescriptor
+{
+    ImageDescriptor::new(image_type, width, height, depth, 0, 0, 0, None)
+}
+
+#[test]
+fn work_dims_within_limits() {
+    let nvidia = fixtures::nvidia_discrete();
+    assert!(check_dims(&nvidia, &dims(&[2048, 64, 64], Some(&[16, 16, 4])), Some(1024)).is_ok());
+    assert!(check_dims(&fixtures::intel_integrated(), &dims(&[1 << 20], None), None).is_ok());
+}
+
+#[test]
+fn work_dims_too_many_dims() {
+    let device = fixtures::nvidia_discrete()
+        .with(DeviceInfo::MaxWorkItemDimensions, 2u32)
+        .with_sizes(DeviceInfo::MaxWorkItemSizes, &[1024, 1024]);
+    let msg = err_msg(check_dims(&device, &dims(&[8, 8, 8], None), None));
+    assert!(msg.contains("work dimension count (3) exceeds the device maximum (2)"), "{}", msg);
+}
+
+#[test]
+fn work_dims_wi_size_exceeded() {
+    let msg = err_msg(check_dims(&fixtures::nvidia_discrete(),
+        &dims(&[64, 64, 128], Some(&[1, 1, 128])), None));
+    assert!(msg.contains("local work size [2] (128) exceeds the device maximum work item \
+        size for that dimension (64)"), "{}", msg);
+    assert!(!msg.contains("local work size [0]"), "{}", msg);
+}
+
+#[test]
+fn work_dims_wg_size_exceeded() {
+    let msg = err_msg(check_dims(&fixtures::intel_integrated(),
+        &dims(&[64, 64], Some(&[32, 32])), None));
+    assert!(msg.contains("work group size (1024) exceeds the device maximum work group \
+        size (256)"), "{}", msg);
+
+    // The same launch is valid on the other devices:
+    assert!(check_dims(&fixtures::nvidia_discrete(), &dims(&[64, 64], Some(&[32, 32])), None)
+        .is_ok());
+    assert!(check_dims(&fixtures::pocl_cpu(), &dims(&[64, 64], Some(&[32, 32])), None).is_ok());
+}
+
+#[test]
+fn work_dims_kernel_wg_size_exceeded() {
+    let msg = err_msg(check_dims(&fixtures::nvidia_discrete(),
+        &dims(&[64, 64], Some(&[16, 16])), Some(128)));
+    assert!(msg.contains("work group size (256) exceeds the maximum work group size for \
+        this kernel (128)"), "{}", msg);
+    assert!(!msg.contains("device maximum work group size"), "{}", msg);
+}
+
+#[test]
+fn work_dims_indivisible_and_zero() {
+    let msg = err_msg(check_dims(&fixtures::nvidia_discrete(),
+        &dims(&[100, 0], Some(&[8, 0])), None));
+    assert!(msg.contains("global work size [0] (100) is not evenly divisible by local \
+        work size [0] (8)"), "{}", msg);
+    assert!(msg.contains("global work size [1] is zero"), "{}", msg);
+    assert!(msg.contains("local work size [1] is zero"), "{}", msg);
+}
+
+#[test]
+fn work_dims_indivisible_non_uniform() {
+    // Non-uniform work groups are allowed as of OpenCL 2.0:
+    assert!(check_dims(&fixtures::intel_integrated(), &dims(&[100], Some(&[8])), None).is_ok());
+
+    let device = fixtures::intel_integrated().with_str(DeviceInfo::Version, "OpenCL 1.2 NEO");
+    let msg = err_msg(check_dims(&device, &dims(&[100], Some(&[8])), None));
+    assert!(msg.contains("global work size [0] (100) is not evenly divisible by local \
+        work size [0] (8)"), "{}", msg);
+}
+
+#[test]
+fn work_dims_wg_size_overflow() {
+    let device = fixtures::nvidia_discrete()
+        .with_sizes(DeviceInfo::MaxWorkItemSizes, &[usize::max_value(); 3]);
+    let lws = [1 << 16, 1 << 16, usize::max_value()];
+    let msg = err_msg(check_dims(&device, &dims(&lws, Some(&lws)), Some(1024)));
+    assert!(msg.contains("work group size (overflows usize) exceeds the device maximum work \
+        group size (1024)"), "{}", msg);
+    assert!(msg.contains("work group size (overflows usize) exceeds the maximum work group \
+        size for this kernel (1024)"), "{}", msg);
+}
+
+#[test]
+fn limits() {
+    let limits = DeviceLimits::new(&fixtures::nvidia_discrete()).unwrap();
+    assert_eq!(limits.max_wg_size(), 1024);
+    assert_eq!(limits.mem_base_addr_align_bytes(), 512);
+    assert_eq!(limits.image2d_max_dims(), [16384, 32768]);
+    assert!(!limits.host_unified_memory());
+
+    assert!(DeviceLimits::new(&fixtures::intel_integrated()).unwrap().host_unified_memory());
+
+    // Not reported, so treated as false:
+    assert!(!DeviceLimits::new(&fixtures::pocl_cpu()).unwrap().host_unified_memory());
+
+    // Any other missing limit is an error:
+    assert!(DeviceLimits::new(&fixtures::pocl_cpu().without(DeviceInfo::MaxMemAllocSize))
+        .is_err());
+}
+
+#[test]
+fn alloc_size() {
+    let limits = DeviceLimits::new(&fixtures::nvidia_discrete()).unwrap();
+    assert!(limits.check_alloc_size("alloc_size", 2147287040).is_ok());
+
+    let msg = err_msg(limits.check_alloc_size("alloc_size", 2147287041));
+    assert!(msg.contains("exceeds the maximum allocation size of the device (2147287040 bytes)"),
+        "{}", msg);
+
+    // Fits on the others:
+    for device in &[fixtures::intel_integrated(), fixtures::pocl_cpu()] {
+        assert!(DeviceLimits::new(device).unwrap().check_alloc_size("alloc_size", 2147287041)
+            .is_ok());
+    }
+}
+
+#[test]
+fn image_dims() {
+    let pocl = DeviceLimits::new(&fixtures::pocl_cpu()).unwrap();
+    let intel = DeviceLimits::new(&fixtures::intel_integrated()).unwrap();
+
+    let desc = image_desc(MemObjectType::Image2d, 8192, 8192, 1);
+    assert!(pocl.check_image_desc("image_dims", &desc).is_ok());
+
+    let desc = image_desc(MemObjectType::Image2d, 8193, 16, 1);
+    let msg = err_msg(pocl.check_image_desc("image_dims", &desc));
+    assert!(msg.contains("exceed the maximum dimensions of the device"), "{}", msg);
+    assert!(intel.check_image_desc("image_dims", &desc).is_ok());
+
+    let desc = image_desc(MemObjectType::Image3d, 16, 16, 4096);
+    assert!(pocl.check_image_desc("image_dims", &desc).is_err());
+    assert!(intel.check_image_desc("image_dims", &desc).is_err());
+    assert!(DeviceLimits::new(&fixtures::nvidia_discrete()).unwrap()
+        .check_image_desc("image_dims", &desc).is_ok());
+
+    // Image buffers are not checked:
+    let desc = image_desc(MemObjectType::Image1dBuffer, 1 << 30, 1, 1);
+    assert!(pocl.check_image_desc("image_dims", &desc).is_ok());
+
+    let no_images = DeviceLimits::new(&fixtures::pocl_cpu().with(DeviceInfo::ImageSupport, 0u32))
+        .unwrap();
+    let desc = image_desc(MemObjectType::Image2d, 16, 16, 1);
+    let msg = err_msg(no_images.check_image_desc("image_dims", &desc));
+    assert!(msg.contains("does not support images"), "{}", msg);
+}
+
+#[test]
+fn filter_by_version() {
+    let at_least = |major: u32, minor: u32| -> Vec<&'static str> {
+        fixtures::all().into_iter()
+            .filter(|d| d.version_number().unwrap() >= (major, minor))
+            .map(|d| d.name())
+            .collect()
+    };
+
+    assert_eq!(at_least(1, 2).len(), 3);
+    assert_eq!(at_least(2, 0), vec!["Intel(R) HD Graphics 620"]);
+    assert!(at_least(2, 2).is_empty());
+
+    let unparsable = fixtures::pocl_cpu().with_str(DeviceInfo::Version, "OpenCL");
+    assert!(unparsable.version_number().unwrap_err().to_string()
+        .contains("Unable to parse the device version string: 'OpenCL'"));
+}
+
+#[test]
+fn filter_by_extension() {
+    let supporting = |extension: &str| -> Vec<&'static str> {
+        fixtures::all().into_iter()
+            .filter(|d| d.has_extension(extension).unwrap())
+            .map(|d| d.name())
+            .collect()
+    };
+
+    assert_eq!(supporting("cl_khr_fp64").len(), 3);
+    assert_eq!(supporting("cl_khr_fp16"), vec!["Intel(R) HD Graphics 620"]);
+    assert_eq!(supporting("cl_nv_device_attribute_query"), vec!["GeForce GTX 1080"]);
+
+    // Whole names only:
+    assert!(supporting("cl_khr_fp").is_empty());
+    assert!(supporting("cl_khr_3d_image").is_empty());
+}
@@ -0,0 +1,89 @@
+//! Tests the formatting of `Diagnostics` (and `Error::NoPlatforms`) using
+//! made-up installations, and the listing of `.icd` files.
+
+use std::env;
+use std::fs;
+use error::Error as OclError;
+use util::{self, Diagnostics};
+
+fn diagnostics(library_path: Option<&str>, icd_files: Option<&[&str]>) -> Diagnostics {
+    Diagnostics {
+        library: String::from("linked at build time"),
+        library_path: library_path.map(String::from),
+        icd_vendors_dir: Some(String::from("/etc/OpenCL/vendors")),
+        icd_files: icd_files.map(|files| files.iter().map(|&f| String::from(f)).collect()),
+        env_vars: vec![(String::from("OCL_ICD_VENDORS"), None),
+            (String::from("OCL_PLATFORM_INDEX"), Some(String::from("1")))],
+    }
+}
+
+#[test]
+#[cfg(not(feature = "stub"))]
+fn empty_vendors_dir() {
+    let diag = diagnostics(Some("/usr/lib/libOpenCL.so.1"), Some(&[]));
+    let report = diag.to_string();
+    assert!(report.starts_with("OpenCL library: linked at build time\n\
+        Library path: /usr/lib/libOpenCL.so.1\n\
+        ICD vendors directory: /etc/OpenCL/vendors (empty)\n\
+        Environment: OCL_ICD_VENDORS=(unset) OCL_PLATFORM_INDEX=1\n\
+        Hint: No drivers are registered ('/etc/OpenCL/vendors' is empty)."), "{}", report);
+    assert!(report.contains("OCL_ICD_FILENAMES"), "{}", report);
+    assert_eq!(diag.hints().len(), 1);
+
+    let report = diagnostics(Some("/usr/lib/libOpenCL.so.1"), None).to_string();
+    assert!(report.contains("ICD vendors directory: /etc/OpenCL/vendors (missing)\n"));
+    assert!(report.contains("('/etc/OpenCL/vendors' is missing)"), "{}", report);
+}
+
+#[test]
+#[cfg(not(feature = "stub"))]
+fn registered_drivers() {
+    let report = diagnostics(Some("/usr/lib/libOpenCL.so.1"),
+        Some(&["intel.icd", "nvidia.icd"])).to_string();
+    assert!(report.contains("ICD vendors directory: /etc/OpenCL/vendors (intel.icd, \
+        nvidia.icd)\n"), "{}", report);
+    assert!(report.contains("Hint: Drivers are registered in '/etc/OpenCL/vendors' but none \
+        reported a platform."), "{}", report);
+}
+
+#[test]
+#[cfg(not(feature = "stub"))]
+fn library_not_loaded() {
+    let diag = diagnostics(None, Some(&["pocl.icd"]));
+    let hints = diag.hints();
+    assert_eq!(hints.len(), 2);
+    assert!(hints[0].starts_with("Install an OpenCL ICD loader"), "{}", hints[0]);
+
+    let report = diag.to_string();
+    assert!(report.contains("Library path: none (not loaded)\n"), "{}", report);
+
+    // No vendors directory (macOS and Windows):
+    let diag = Diagnostics { icd_vendors_dir: None, icd_files: None, .. diag };
+    let report = diag.to_string();
+    assert!(!report.contains("ICD vendors directory"), "{}", report);
+    assert!(report.ends_with("Hint: Install the OpenCL driver for your device."), "{}", report);
+}
+
+#[test]
+fn no_platforms_error() {
+    let diag = diagnostics(Some("/usr/lib/libOpenCL.so.1"), Some(&[]));
+    let err = OclError::NoPlatforms(diag.clone());
+    assert_eq!(err.to_string(), format!("No OpenCL platforms found.\n{}", diag));
+    assert_eq!(format!("{:?}", err), err.to_string());
+}
+
+#[test]
+fn icd_files() {
+    let dir = env::temp_dir().join(format!("ocl_icd_files_{}", ::std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    assert_eq!(util::icd_files(&dir), Some(vec![]));
+
+    for name in ["nvidia.icd", "intel.icd", "README"].iter() {
+        fs::write(dir.join(name), "libfake.so\n").unwrap();
+    }
+    assert_eq!(util::icd_files(&dir), Some(vec![String::from("intel.icd"),
+        String::from("nvidia.icd")]));
+
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(util::icd_files(&dir), None);
+}
@@ -0,0 +1,48 @@
+//! Tests that each consumer of dimensions returns a `DimsUnspecified` error
+//! naming the operation when no dimensions have been set.
+
+use error::{Error as OclError, Result as OclResult};
+use standard::{ProQue, Buffer, Image, SpatialDims, MemLen};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+fn assert_dims_unspecified<T>(result: OclResult<T>, expected_op: &str) {
+    match result {
+        Err(OclError::DimsUnspecified { op, .. }) => assert_eq!(op, expected_op),
+        Err(err) => panic!("Expected a 'DimsUnspecified' error, found: '{}'.", err),
+        Ok(_) => panic!("Expected a 'DimsUnspecified' error for '{}'.", expected_op),
+    }
+}
+
+#[test]
+fn spatial_dims_unspecified() {
+    assert_dims_unspecified(SpatialDims::Unspecified.to_lens(), "ocl::SpatialDims::to_lens");
+    assert_dims_unspecified(SpatialDims::Unspecified.to_offset(), "ocl::SpatialDims::to_offset");
+    assert_dims_unspecified(MemLen::to_lens(&SpatialDims::Unspecified), "ocl::SpatialDims::to_lens");
+}
+
+#[test]
+fn consumers_dims_unspecified() {
+    let pro_que = ProQue::builder().src(SRC).build().unwrap();
+
+    assert_dims_unspecified(pro_que.dims_result(), "ocl::ProQue::dims_result");
+    assert_dims_unspecified(pro_que.create_buffer::<f32>(), "ocl::ProQue::create_buffer");
+    assert_dims_unspecified(MemLen::to_lens(&pro_que), "ocl::ProQue::to_lens");
+
+    assert_dims_unspecified(Buffer::<f32>::new(pro_que.queue(), None, SpatialDims::Unspecified,
+        None), "ocl::Buffer::new");
+
+    assert_dims_unspecified(Image::<u8>::builder().build(pro_que.queue()),
+        "ocl::ImageBuilder::build");
+    assert_dims_unspecified(Image::<u8>::builder().dims(SpatialDims::Unspecified)
+        .build_with_data(pro_que.queue(), &[0u8; 4]), "ocl::ImageBuilder::build_with_data");
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&Buffer::<f32>::new(pro_que.queue(), None, [64], None).unwrap())
+        .arg_scl(10.0f32);
+    assert_dims_unspecified(kernel.enq(), "ocl::KernelCmd::enq (global work size)");
+}
@@ -0,0 +1,78 @@
+//! Tests the one line `Display` summaries of platforms, devices, and
+//! contexts (and the full property listing of the alternate form).
+
+use core::DeviceInfo;
+use standard::{Platform, Device, Context, DeviceInfoSource, fmt_mem_size};
+use tests::fixtures;
+
+#[test]
+fn mem_size() {
+    assert_eq!(fmt_mem_size(0), "0 B");
+    assert_eq!(fmt_mem_size(1023), "1023 B");
+    assert_eq!(fmt_mem_size(1024), "1 KiB");
+    assert_eq!(fmt_mem_size(1536), "1.5 KiB");
+    assert_eq!(fmt_mem_size(64 << 20), "64 MiB");
+    assert_eq!(fmt_mem_size(10 << 30), "10 GiB");
+    assert_eq!(fmt_mem_size(8508145664), "7.9 GiB");
+    assert_eq!(fmt_mem_size(3 << 40), "3 TiB");
+    assert_eq!(fmt_mem_size(5 << 50), "5120 TiB");
+}
+
+#[test]
+fn device_summary() {
+    assert_eq!(fixtures::nvidia_discrete().summary().unwrap(),
+        "GeForce GTX 1080 (NVIDIA Corporation, OpenCL 1.2, 20 CUs, 7.9 GiB)");
+    assert_eq!(fixtures::intel_integrated().summary().unwrap(),
+        "Intel(R) HD Graphics 620 (Intel(R) Corporation, OpenCL 2.1, 24 CUs, 12.4 GiB)");
+    assert_eq!(fixtures::pocl_cpu().summary().unwrap(),
+        "pthread-Intel(R) Core(TM) i7-7500U CPU @ 2.70GHz (GenuineIntel, OpenCL 1.2, 4 CUs, \
+        6 GiB)");
+
+    // Anything but the name may be missing:
+    let partial = fixtures::nvidia_discrete()
+        .without(DeviceInfo::Vendor)
+        .without(DeviceInfo::GlobalMemSize)
+        .with_str(DeviceInfo::Version, "OpenCL");
+    assert_eq!(partial.summary().unwrap(), "GeForce GTX 1080 (OpenCL, 20 CUs)");
+
+    let bare = fixtures::pocl_cpu().without(DeviceInfo::Vendor).without(DeviceInfo::Version)
+        .without(DeviceInfo::MaxComputeUnits).without(DeviceInfo::GlobalMemSize);
+    assert_eq!(bare.summary().unwrap(), bare.name());
+
+    assert!(fixtures::pocl_cpu().without(DeviceInfo::Name).summary().is_err());
+}
+
+#[test]
+fn display_local() {
+    for platform in Platform::list().unwrap_or(Vec::new()) {
+        let summary = platform.to_string();
+        println!("{}", summary);
+        assert_eq!(summary, format!("{} ({})", platform.name(), platform.version()));
+        assert!(format!("{:#}", platform).contains("Extensions"));
+
+        for device in Device::list_all(&platform).unwrap_or(Vec::new()) {
+            let summary = format!("{}", device);
+            println!("    {}", summary);
+            assert!(summary.starts_with(&device.name()), "{}", summary);
+            let (major, minor) = device.version_number().unwrap();
+            assert!(summary.contains(&format!(", OpenCL {}.{}, ", major, minor)), "{}", summary);
+            assert!(summary.contains(&format!("{} CUs", device.max_compute_units().unwrap())),
+                "{}", summary);
+            assert!(summary.ends_with(&format!("{})", fmt_mem_size(device.global_mem_size()
+                .unwrap()))), "{}", summary);
+            assert!(!summary.contains('\n'));
+            assert!(format!("{:#}", device).contains("MaxComputeUnits"));
+
+            // Debug remains the raw handle:
+            assert!(format!("{:?}", device).starts_with("Device(DeviceId("));
+
+            let context = match Context::builder().platform(platform).devices(device).build() {
+                Ok(context) => context,
+                Err(_) => continue,
+            };
+            assert_eq!(format!("{}", context), format!("{}: {}", platform.name(),
+                device.name()));
+            assert!(format!("{:#}", context).contains("ReferenceCount"));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Tests overlapped chunk processing using a `DoubleBuffer`.
+
+use standard::{ProQue, Queue, Buffer, Event, DoubleBuffer};
+
+static SRC: &'static str = r#"
+    __kernel void double_it(__global uint* buf) {
+        buf[get_global_id(0)] *= 2;
+    }
+"#;
+
+const CHUNK_LEN: usize = 1 << 12;
+
+/// Processes `chunks` with a doubling kernel, returning each result.
+fn process(proque: &ProQue, transfer_queue: &Queue, chunks: &[Vec<u32>]) -> Vec<Vec<u32>> {
+    let compute_queue = proque.queue();
+    let mut dbuf = DoubleBuffer::<u32>::new(transfer_queue, compute_queue, CHUNK_LEN).unwrap();
+    assert_eq!(dbuf.chunk_len(), CHUNK_LEN);
+
+    let mut kernel = proque.create_kernel("double_it").unwrap()
+        .arg_buf_named::<u32>("buf", None);
+    let mut results = Vec::new();
+
+    dbuf.run(chunks, |buffer: &Buffer<u32>, len, wait: &Event, new: &mut Event| {
+        kernel.set_arg_buf_named("buf", Some(buffer)).unwrap();
+        kernel.cmd().queue(compute_queue).gws([len]).ewait(wait).enew(new).enq()
+    }, |result: &[u32]| results.push(result.to_vec())).unwrap();
+
+    results
+}
+
+#[test]
+fn double_buffer() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([CHUNK_LEN])
+        .build().unwrap();
+
+    let transfer_queue = Queue::new(&proque.context(), proque.queue().device().clone()).unwrap();
+
+    // Many full chunks and a final partial chunk:
+    let data: Vec<u32> = (0..(CHUNK_LEN * 9 + 123) as u32).collect();
+    let chunks: Vec<Vec<u32>> = data.chunks(CHUNK_LEN).map(|c| c.to_vec()).collect();
+    let expected: Vec<u32> = data.iter().map(|&v| v * 2).collect();
+
+    for queue in &[&transfer_queue, proque.queue()] {
+        let results = process(&proque, queue, &chunks);
+        assert_eq!(results.len(), chunks.len());
+        assert_eq!(results.iter().flat_map(|r| r.iter().cloned()).collect::<Vec<_>>(), expected);
+    }
+
+    // One and two chunks:
+    for count in 1..3 {
+        let results = process(&proque, &transfer_queue, &chunks[..count]);
+        assert_eq!(results.len(), count);
+        assert_eq!(results.concat(), &expected[..CHUNK_LEN * count]);
+    }
+
+    // An empty chunk among others:
+    let mixed = vec![chunks[0].clone(), Vec::new(), chunks[9].clone()];
+    let results = process(&proque, &transfer_queue, &mixed);
+    assert_eq!(results[0], &expected[..CHUNK_LEN]);
+    assert!(results[1].is_empty());
+    assert_eq!(results[2], &expected[CHUNK_LEN * 9..]);
+
+    // An empty input:
+    assert!(process(&proque, &transfer_queue, &[]).is_empty());
+}
+
+#[test]
+fn double_buffer_errors() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([CHUNK_LEN])
+        .build().unwrap();
+
+    assert!(DoubleBuffer::<u32>::new(proque.queue(), proque.queue(), 0).is_err());
+
+    let mut dbuf = DoubleBuffer::<u32>::new(proque.queue(), proque.queue(), 16).unwrap();
+    let mut sunk = 0;
+
+    // A chunk which is too long:
+    let err = dbuf.run(&[vec![0u32; 17]], |_, _, _, _| Ok(()), |_| sunk += 1).unwrap_err();
+    assert!(err.to_string().contains("chunk [0] (17)"), "{}", err);
+
+    // No command associated with the new event:
+    let err = dbuf.run(&[vec![0u32; 16]], |_, _, _, _| Ok(()), |_| sunk += 1).unwrap_err();
+    assert!(err.to_string().contains("not associated"), "{}", err);
+    assert_eq!(sunk, 0);
+}
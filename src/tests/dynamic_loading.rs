@@ -0,0 +1,98 @@
+//! Loading of the OpenCL library at runtime, with the library present (if
+//! installed) and, by re-running this test binary with `OCL_LIBRARY` set to
+//! a library which does not exist, absent.
+
+use std::env;
+use std::process::Command;
+use core;
+use loader::{self, Symbol, LIBRARY_VAR};
+use error::Error as OclError;
+use standard::Platform;
+
+const ABSENT_LIBRARY: &'static str = "libOclTestAbsentLibrary.so";
+
+#[test]
+fn library_present() {
+    if !core::is_opencl_available() {
+        println!("No OpenCL library found. Skipping.");
+        return;
+    }
+
+    // Any error is now one returned by the library itself:
+    match core::get_platform_ids() {
+        Err(OclError::LibraryNotLoaded) => panic!("LibraryNotLoaded despite being available."),
+        _ => (),
+    }
+}
+
+#[test]
+fn symbol_missing() {
+    static BOGUS: Symbol = Symbol::new("clOclTestBogus", "clOclTestBogus\0");
+    assert!(BOGUS.addr().is_none());
+
+    match loader::take_error() {
+        Some(OclError::LibraryNotLoaded) => assert!(!core::is_opencl_available()),
+        Some(OclError::VersionUnsupported { op, required, .. }) => {
+            assert!(core::is_opencl_available());
+            assert_eq!(op, "clOclTestBogus");
+            assert_eq!(required, (1, 0));
+        },
+        Some(err) => panic!("Unexpected error for a missing symbol: {:?}", err),
+        None => panic!("No error recorded for a missing symbol."),
+    }
+
+    // The error is reported only once:
+    assert!(loader::take_error().is_none());
+}
+
+#[test]
+fn symbol_missing_not_sticky() {
+    static BOGUS: Symbol = Symbol::new("clOclTestBogusNotSticky", "clOclTestBogusNotSticky\0");
+    static GET_PLATFORM_IDS: Symbol = Symbol::new("clGetPlatformIDs", "clGetPlatformIDs\0");
+
+    if !core::is_opencl_available() {
+        println!("No OpenCL library found. Skipping.");
+        return;
+    }
+
+    // An unconsumed error is cleared by the next call:
+    assert!(BOGUS.addr().is_none());
+    assert!(GET_PLATFORM_IDS.addr().is_some());
+    assert!(loader::take_error().is_none());
+}
+
+/// Run (only) by `library_absent` with `OCL_LIBRARY` set to a library which
+/// does not exist.
+#[test]
+fn library_absent_child() {
+    if env::var(LIBRARY_VAR).ok().as_ref().map(|s| s.as_str()) != Some(ABSENT_LIBRARY) {
+        return;
+    }
+
+    assert!(!core::is_opencl_available());
+
+    match core::get_platform_ids() {
+        Err(OclError::LibraryNotLoaded) => (),
+        other => panic!("Expected LibraryNotLoaded, got: {:?}", other),
+    }
+
+    match Platform::list() {
+        Err(OclError::LibraryNotLoaded) => (),
+        other => panic!("Expected LibraryNotLoaded, got: {:?}", other),
+    }
+}
+
+#[test]
+fn library_absent() {
+    let output = Command::new(env::current_exe().unwrap())
+        .arg("tests::dynamic_loading::library_absent_child")
+        .arg("--exact")
+        .arg("--nocapture")
+        .env(LIBRARY_VAR, ABSENT_LIBRARY)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{}{}", String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("1 passed"));
+}
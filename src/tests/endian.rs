@@ -0,0 +1,91 @@
+//! Tests byte swapping for transfers to and from devices of differing
+//! endianness.
+//!
+//! The device path only actually swaps anything when run on a device whose
+//! endianness differs from the host (`CL_DEVICE_ENDIAN_LITTLE`). Elsewhere
+//! `buffer_swap_bytes_round_trip` just verifies that enabling it changes
+//! nothing.
+
+use core::{OclPrm, ClUchar4, ClShort3, ClInt2, ClUlong2, ClFloat4, ClDouble2};
+use standard::{ProQue, Buffer};
+
+const LEN: usize = 1024;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+struct Pair {
+    a: u32,
+    b: u32,
+}
+
+impl_ocl_prm!(unsafe Pair => 8);
+
+/// Swaps `val` and checks the result against `expected` and that swapping
+/// again restores the original.
+fn check_swap<T: OclPrm>(val: T, expected: T) {
+    let swapped = val.swap_bytes().unwrap();
+    assert_eq!(swapped, expected);
+    assert_eq!(swapped.swap_bytes().unwrap(), val);
+}
+
+#[test]
+fn swap_bytes_scalars() {
+    check_swap(0x12u8, 0x12u8);
+    check_swap(-0x12i8, -0x12i8);
+    check_swap(0x1234u16, 0x3412u16);
+    check_swap(0x1234i16, 0x3412i16);
+    check_swap(0x12345678u32, 0x78563412u32);
+    check_swap(0x12345678i32, 0x78563412i32);
+    check_swap(0x0102030405060708u64, 0x0807060504030201u64);
+    check_swap(0x0102030405060708i64, 0x0807060504030201i64);
+    check_swap(1.0f32, f32::from_bits(0x0000803f));
+    check_swap(1.0f64, f64::from_bits(0x000000000000f03f));
+}
+
+#[cfg(feature = "f16")]
+#[test]
+fn swap_bytes_f16() {
+    use half::f16;
+    check_swap(f16::from_f32(1.0), f16::from_bits(0x003c));
+}
+
+#[test]
+fn swap_bytes_vectors() {
+    check_swap(ClUchar4([1, 2, 3, 4]), ClUchar4([1, 2, 3, 4]));
+    check_swap(ClShort3([0x0102, 0x0304, 0x0506]), ClShort3([0x0201, 0x0403, 0x0605]));
+    check_swap(ClInt2([0x01020304, -1]), ClInt2([0x04030201, -1]));
+    check_swap(ClUlong2([0x0102030405060708, 0]), ClUlong2([0x0807060504030201, 0]));
+    check_swap(ClFloat4([1.0, 0.0, 1.0, 0.0]),
+        ClFloat4([f32::from_bits(0x0000803f), 0.0, f32::from_bits(0x0000803f), 0.0]));
+    check_swap(ClDouble2([1.0, 0.0]), ClDouble2([f64::from_bits(0x000000000000f03f), 0.0]));
+}
+
+#[test]
+fn swap_bytes_user_type_unsupported() {
+    assert_eq!(Pair { a: 1, b: 2 }.swap_bytes(), None);
+}
+
+#[test]
+fn buffer_swap_bytes_round_trip() {
+    let pro_que = ProQue::builder().src("__kernel void nop() {}").dims([LEN]).build().unwrap();
+    let vec_src: Vec<u32> = (0..LEN as u32).map(|i| i * 0x01010101).collect();
+
+    let buffer = Buffer::<u32>::builder().dims([LEN]).swap_bytes(true)
+        .build_with_data(pro_que.queue(), &vec_src).unwrap();
+    assert!(buffer.swap_bytes());
+
+    let mut vec = vec![0u32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(vec, vec_src);
+
+    buffer.cmd().fill(&[0x01020304], None).enq().unwrap();
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 0x01020304));
+
+    // Bytes are reversed on the device only when endianness differs:
+    let mut buffer = buffer;
+    buffer.set_swap_bytes(false);
+    buffer.read(&mut vec).enq().unwrap();
+    let expected = if pro_que.queue().device_endian_mismatch() { 0x04030201 } else { 0x01020304 };
+    assert!(vec.iter().all(|&v| v == expected));
+}
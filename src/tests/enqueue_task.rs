@@ -0,0 +1,69 @@
+//! Tests single work-item kernel launches (`Kernel::enq_task` and
+//! `core::enqueue_task`).
+
+use core;
+use standard::{ProQue, Buffer, Event};
+
+static SRC: &'static str = r#"
+    __kernel void write_one(__global uint* buf, uint val) {
+        buf[get_global_id(0)] = val + get_global_size(0) - 1;
+    }
+"#;
+
+const LEN: usize = 16;
+
+#[test]
+fn enqueue_task() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    let buffer = Buffer::<u32>::new(proque.queue(), None, [LEN], None).unwrap();
+    let mut kernel = proque.create_kernel("write_one").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl_named("val", Some(7u32));
+
+    // Only the first element is written despite the kernel's default global
+    // work size:
+    kernel.enq_task().unwrap();
+
+    let mut vec = vec![0u32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(vec[0], 7);
+    assert!(vec[1..].iter().all(|&v| v == 0));
+
+    // Task mode on the command builder, with an event:
+    kernel.set_arg_scl_named("val", 11u32).unwrap();
+    let mut event = Event::empty();
+    kernel.cmd().gws([LEN]).task().enew(&mut event).enq().unwrap();
+    event.wait().unwrap();
+
+    buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(vec[0], 11);
+    assert!(vec[1..].iter().all(|&v| v == 0));
+}
+
+#[test]
+fn enqueue_task_core() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    // `clEnqueueTask` is deprecated (though typically still available) as of
+    // OpenCL 2.0:
+    if proque.queue().device_version() >= (2, 0) { return; }
+
+    let buffer = Buffer::<u32>::new(proque.queue(), None, [LEN], None).unwrap();
+    let kernel = proque.create_kernel("write_one").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(42u32);
+
+    core::enqueue_task(proque.queue(), unsafe { kernel.core_as_ref() }, None, None).unwrap();
+
+    let mut vec = vec![0u32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(vec[0], 42);
+    assert!(vec[1..].iter().all(|&v| v == 0));
+}
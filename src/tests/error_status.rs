@@ -0,0 +1,138 @@
+//! Tests that errors returned by the OpenCL API can be matched by their
+//! typed `Status` rather than by their message text.
+
+use core;
+use flags;
+use error::Error as OclError;
+use enums::Status;
+use standard::ProQue;
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn error_status_typed() {
+    let pro_que = ProQue::builder().src(SRC).build().unwrap();
+
+    let result = unsafe { core::create_buffer::<f32>(pro_que.context(), flags::MEM_READ_WRITE,
+        0, None) };
+
+    match result {
+        Err(OclError::Status { status: Status::CL_INVALID_BUFFER_SIZE, fn_name, .. }) => {
+            assert_eq!(fn_name, "clCreateBuffer");
+        },
+        Err(err) => panic!("Expected a 'CL_INVALID_BUFFER_SIZE' status error, found: '{}'.", err),
+        Ok(_) => panic!("Creating a zero-length buffer should fail."),
+    }
+}
+
+#[test]
+fn error_status_unknown_code() {
+    match OclError::err_status::<(), _>(-9999, "clFoo", "") {
+        Err(OclError::String(desc)) => assert!(desc.contains("-9999")),
+        other => panic!("Expected a 'String' error for an unknown code, found: '{:?}'.", other),
+    }
+}
+
+#[test]
+fn error_status_call_info() {
+    let pro_que = ProQue::builder().src(SRC).dims([1024]).build().unwrap();
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    // Write past the end of the buffer:
+    let data = vec![0.0f32; 1024];
+    let err = core::enqueue_write_buffer(pro_que.queue().core_as_ref(), buffer.core_as_ref(),
+        true, 2048, &data, None, None).unwrap_err();
+
+    let msg = format!("{}", err);
+    assert!(msg.starts_with("clEnqueueWriteBuffer("), "{}", msg);
+    assert!(msg.contains("offset=8192 size=4096 buffer_len=4096"), "{}", msg);
+    assert!(msg.contains("CL_INVALID_VALUE"), "{}", msg);
+
+    // Create a kernel which does not exist:
+    let err = core::create_kernel(pro_que.program().core_as_ref(), "nonexistent").unwrap_err();
+
+    let msg = format!("{}", err);
+    assert!(msg.starts_with("clCreateKernel(name=nonexistent): "), "{}", msg);
+}
+
+#[test]
+#[cfg(not(feature="panic_on_error"))]
+fn error_status_allocation_failure() {
+    let pro_que = ProQue::builder().src(SRC).build().unwrap();
+
+    // Request an absurd amount of memory (~2^61 bytes):
+    let len = ::std::usize::MAX / 8;
+    let result = unsafe { core::create_buffer::<f32>(pro_que.context(), flags::MEM_READ_WRITE,
+        len, None) };
+
+    match result {
+        Err(OclError::Status { fn_name, .. }) => assert_eq!(fn_name, "clCreateBuffer"),
+        Err(err) => panic!("Expected a status error, found: '{}'.", err),
+        Ok(_) => panic!("Allocating an absurdly large buffer should fail."),
+    }
+}
+
+fn status_err(status: Status) -> OclError {
+    OclError::err_status::<(), _>(status as i32, "clFoo", "").unwrap_err()
+}
+
+#[test]
+fn error_status_predicates() {
+    let err = status_err(Status::CL_INVALID_ARG_VALUE);
+    assert_eq!(err.status(), Some(Status::CL_INVALID_ARG_VALUE));
+    assert!(err.is_status(Status::CL_INVALID_ARG_VALUE));
+    assert!(!err.is_status(Status::CL_INVALID_VALUE));
+    assert!(err.is_invalid_arg_value());
+    assert!(!err.is_out_of_memory());
+
+    for status in vec![Status::CL_OUT_OF_HOST_MEMORY, Status::CL_OUT_OF_RESOURCES,
+            Status::CL_MEM_OBJECT_ALLOCATION_FAILURE]
+    {
+        let err = status_err(status.clone());
+        assert!(err.is_out_of_memory(), "{:?}", status);
+        assert!(!err.is_invalid_arg_value(), "{:?}", status);
+    }
+
+    let err = OclError::new("not a status");
+    assert_eq!(err.status(), None);
+    assert!(!err.is_status(Status::CL_SUCCESS));
+    assert!(!err.is_out_of_memory());
+}
+
+#[test]
+fn error_status_descriptions() {
+    use num::FromPrimitive;
+
+    let mut variant_count = 0;
+
+    for code in -1100..1 {
+        if let Some(status) = Status::from_i32(code) {
+            let desc = status.description();
+            assert!(desc.len() > 0 && desc.ends_with('.'), "{:?}: '{}'", status, desc);
+            variant_count += 1;
+        }
+    }
+
+    assert_eq!(variant_count, 80);
+}
+
+#[test]
+#[cfg(not(feature="panic_on_error"))]
+fn error_status_release_fn_name() {
+    // Make sure the platform is initialized before passing an invalid handle:
+    core::default_platform().unwrap();
+
+    let program = unsafe { core::Program::from_fresh_ptr(::std::ptr::null_mut()) };
+    let result = unsafe { core::release_program(&program) };
+    ::std::mem::forget(program);
+
+    match result {
+        Err(OclError::Status { fn_name, .. }) => assert_eq!(fn_name, "clReleaseProgram"),
+        Err(err) => panic!("Expected a status error, found: '{}'.", err),
+        Ok(_) => panic!("Releasing a null program should fail."),
+    }
+}
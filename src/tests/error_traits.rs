@@ -0,0 +1,59 @@
+//! Tests the standard error traits implemented by `ocl::Error`.
+
+use std;
+use std::error::Error as StdError;
+use std::ffi::CString;
+use error::{Error as OclError, Result as OclResult};
+
+fn assert_send_sync_static<T: Send + Sync + 'static>() {}
+
+fn open_nonexistent() -> OclResult<std::fs::File> {
+    let file = std::fs::File::open("/nonexistent/ocl/kernel.cl")?;
+    Ok(file)
+}
+
+#[test]
+fn error_send_sync() {
+    assert_send_sync_static::<OclError>();
+
+    let handle = std::thread::spawn(|| -> OclResult<()> { OclError::err("thread") });
+    assert!(handle.join().unwrap().is_err());
+}
+
+#[test]
+fn error_source_io() {
+    let err = open_nonexistent().unwrap_err();
+
+    match err {
+        OclError::Io(_) => (),
+        _ => panic!("Expected an 'Io' variant, found: '{:?}'.", err),
+    }
+
+    let io_err = err.source().and_then(|src| src.downcast_ref::<std::io::Error>())
+        .expect("'Io' errors should have an 'io::Error' source");
+    assert_eq!(io_err.kind(), std::io::ErrorKind::NotFound);
+    assert_eq!(format!("{}", err), format!("{}", io_err));
+}
+
+#[test]
+fn error_source_other() {
+    let nul_err: OclError = CString::new(vec![b'a', 0, b'b']).unwrap_err().into();
+    assert!(nul_err.source().unwrap().is::<std::ffi::NulError>());
+
+    let utf8_err: OclError = String::from_utf8(vec![0xff, 0xfe]).unwrap_err().into();
+    assert!(utf8_err.source().unwrap().is::<std::string::FromUtf8Error>());
+
+    assert!(OclError::new("no source").source().is_none());
+    assert!(OclError::dims_unspecified("op").source().is_none());
+}
+
+#[test]
+fn error_into_boxed() {
+    fn app() -> std::result::Result<(), Box<StdError + Send + Sync>> {
+        try!(open_nonexistent());
+        Ok(())
+    }
+
+    let err = app().unwrap_err();
+    assert!(err.downcast_ref::<OclError>().is_some());
+}
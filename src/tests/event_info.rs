@@ -0,0 +1,66 @@
+//! Tests event info queries through both `Event` and `core::get_event_info`.
+
+use core;
+use standard::{ProQue, Event};
+use enums::{EventInfo, EventInfoResult, CommandType, CommandExecutionStatus};
+
+#[test]
+fn event_info_user() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([64])
+        .build().unwrap();
+
+    let event = core::create_user_event(proque.context().core_as_ref()).unwrap();
+
+    match core::get_event_info(&event, EventInfo::CommandType) {
+        EventInfoResult::CommandType(ty) => assert_eq!(ty, CommandType::User),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    match core::get_event_info(&event, EventInfo::CommandQueue) {
+        EventInfoResult::CommandQueue(queue) => assert!(queue.is_none()),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    match core::get_event_info(&event, EventInfo::Context) {
+        EventInfoResult::Context(context) => assert_eq!(unsafe { context.as_ptr() },
+            unsafe { proque.context().core_as_ref().as_ptr() }),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    match core::get_event_info(&event, EventInfo::CommandExecutionStatus) {
+        EventInfoResult::CommandExecutionStatus(status) => {
+            assert_eq!(status, CommandExecutionStatus::Submitted)
+        },
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    core::set_user_event_status(&event, CommandExecutionStatus::Complete).unwrap();
+
+    let event = unsafe { Event::from_core(event) };
+    assert_eq!(event.command_type().unwrap(), CommandType::User);
+    assert_eq!(event.status().unwrap(), CommandExecutionStatus::Complete);
+}
+
+#[test]
+fn event_info_kernel() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([64])
+        .build().unwrap();
+
+    let kernel = proque.create_kernel("nop").unwrap();
+    let mut event = Event::empty();
+    kernel.cmd().enew(&mut event).enq().unwrap();
+    event.wait().unwrap();
+
+    assert_eq!(event.command_type().unwrap(), CommandType::NdrangeKernel);
+    assert_eq!(event.status().unwrap(), CommandExecutionStatus::Complete);
+
+    match core::get_event_info(event.core_as_ref().unwrap(), EventInfo::CommandQueue) {
+        EventInfoResult::CommandQueue(Some(queue)) => assert_eq!(unsafe { queue.as_ptr() },
+            unsafe { proque.queue().core_as_ref().as_ptr() }),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+}
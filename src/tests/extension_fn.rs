@@ -0,0 +1,89 @@
+//! Tests extension function lookup and caching.
+
+use std::cell::Cell;
+use libc::c_void;
+use error::Error as OclError;
+use standard::{Platform, ExtensionFnCache};
+
+const BOGUS_NAME: &'static str = "clBogusExtensionFunctionNameOCL";
+
+type IcdGetPlatformIdsFn = extern "C" fn(u32, *mut *mut c_void, *mut u32) -> i32;
+
+extension_fn!(bogus_fn, BOGUS_NAME, extern "C" fn());
+extension_fn!(icd_get_platform_ids, "clIcdGetPlatformIDsKHR", IcdGetPlatformIdsFn);
+
+#[test]
+fn extension_fn_cache() {
+    let cache = ExtensionFnCache::new("clSomeFunction");
+    let lookups = Cell::new(0);
+    let lookup = || { lookups.set(lookups.get() + 1); Ok(0x1000 as *mut c_void) };
+
+    for _ in 0..3 {
+        assert_eq!(cache.get_or_lookup(1, &lookup).unwrap(), 0x1000 as *mut c_void);
+    }
+    assert_eq!(lookups.get(), 1);
+
+    // A different platform is looked up separately:
+    cache.get_or_lookup(2, &lookup).unwrap();
+    assert_eq!(lookups.get(), 2);
+
+    // Unavailable functions are cached as well:
+    let failures = Cell::new(0);
+    let fail = || {
+        failures.set(failures.get() + 1);
+        Err(OclError::ExtensionUnavailable("clSomeFunction".to_owned()))
+    };
+
+    for _ in 0..3 {
+        match cache.get_or_lookup(3, &fail) {
+            Err(OclError::ExtensionUnavailable(name)) => assert_eq!(name, "clSomeFunction"),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+    assert_eq!(failures.get(), 1);
+
+    // Other errors are not cached:
+    let errors = Cell::new(0);
+    let error = || { errors.set(errors.get() + 1); Err(OclError::from("lookup error")) };
+    assert!(cache.get_or_lookup(4, &error).is_err());
+    assert!(cache.get_or_lookup(4, &error).is_err());
+    assert_eq!(errors.get(), 2);
+}
+
+#[test]
+fn extension_fn_bogus_name() {
+    for platform in Platform::list().unwrap() {
+        match platform.extension_fn(BOGUS_NAME) {
+            Err(OclError::ExtensionUnavailable(name)) => assert_eq!(name, BOGUS_NAME),
+            Ok(_) => panic!("Bogus extension function found."),
+            Err(err) => panic!("Unexpected error: {}", err),
+        }
+
+        for _ in 0..2 {
+            match bogus_fn(&platform) {
+                Err(OclError::ExtensionUnavailable(name)) => assert_eq!(name, BOGUS_NAME),
+                Ok(_) => panic!("Bogus extension function found."),
+                Err(err) => panic!("Unexpected error: {}", err),
+            }
+        }
+    }
+}
+
+#[test]
+fn extension_fn_icd_get_platform_ids() {
+    for platform in Platform::list().unwrap() {
+        if !platform.extensions().split_whitespace().any(|ext| ext == "cl_khr_icd") {
+            continue;
+        }
+
+        let get_ids = match icd_get_platform_ids(&platform) {
+            Ok(f) => f,
+            Err(OclError::ExtensionUnavailable(_)) => continue,
+            Err(err) => panic!("{}", err),
+        };
+
+        let mut count = 0u32;
+        assert_eq!(get_ids(0, 0 as *mut _, &mut count), 0);
+        assert!(count > 0);
+    }
+}
@@ -0,0 +1,48 @@
+//! Tests that driver and user input failures in the high-level interfaces
+//! are returned as errors rather than panicking.
+
+use standard::{ProQue, Platform, Device, DeviceSpecifier, Kernel, Buffer, ProgramBuilder};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn fallible_bad_kernel_name() {
+    let pro_que = ProQue::builder().src(SRC).dims([1024]).build().unwrap();
+    assert!(Kernel::new("not_a_kernel", pro_que.program(), pro_que.queue()).is_err());
+    assert!(pro_que.create_kernel("not_a_kernel").is_err());
+}
+
+#[test]
+fn fallible_zero_size_buffer() {
+    let pro_que = ProQue::builder().src(SRC).dims([1024]).build().unwrap();
+    assert!(Buffer::<f32>::new(pro_que.queue(), None, [0], None).is_err());
+}
+
+#[test]
+fn fallible_invalid_device() {
+    let platform = Platform::try_default().unwrap();
+    let device_count = Device::list_all(&platform).unwrap().len();
+
+    assert!(DeviceSpecifier::Indices(vec![device_count]).to_device_list(Some(platform))
+        .is_err());
+    assert!(ProQue::builder().src(SRC).device(DeviceSpecifier::Indices(vec![device_count]))
+        .build().is_err());
+}
+
+#[test]
+fn fallible_missing_src_file() {
+    let program_builder = ProgramBuilder::new().src_file("/nonexistent/ocl/kernel.cl");
+    assert!(program_builder.get_src_strings().is_err());
+}
+
+#[test]
+fn fallible_queue_and_device() {
+    let pro_que = ProQue::builder().src(SRC).dims([1024]).build().unwrap();
+    pro_que.queue().finish().unwrap();
+    assert!(pro_que.max_wg_size().unwrap() > 0);
+    assert!(Device::first(Platform::try_default().unwrap()).is_ok());
+}
@@ -0,0 +1,179 @@
+//! Canned device info snapshots for testing device-dependent logic (see
+//! `standard::DeviceInfoSource`) deterministically, regardless of the
+//! hardware present.
+//!
+//! The values are those reported by real devices of each kind (abridged
+//! extension lists aside).
+
+use std::mem;
+use std::slice;
+use core::{self, DeviceInfo};
+use cl_h::Status;
+use error::{Error as OclError, Result as OclResult};
+use standard::DeviceInfoSource;
+
+/// A snapshot of the info reported by a device.
+#[derive(Clone, Debug)]
+pub struct DeviceFixture {
+    name: &'static str,
+    values: Vec<(DeviceInfo, Vec<u8>)>,
+}
+
+impl DeviceFixture {
+    fn new(name: &'static str) -> DeviceFixture {
+        DeviceFixture { name: name, values: Vec::new() }.with_str(DeviceInfo::Name, name)
+    }
+
+    /// Sets (or replaces) the raw bytes reported for `request`.
+    pub fn with_bytes(mut self, request: DeviceInfo, bytes: Vec<u8>) -> DeviceFixture {
+        self.values.retain(|&(r, _)| r != request);
+        self.values.push((request, bytes));
+        self
+    }
+
+    /// Sets the scalar value reported for `request`.
+    pub fn with<T: Copy>(self, request: DeviceInfo, value: T) -> DeviceFixture {
+        let bytes = unsafe { slice::from_raw_parts(&value as *const T as *const u8,
+            mem::size_of::<T>()) }.to_vec();
+        self.with_bytes(request, bytes)
+    }
+
+    /// Sets the string value reported for `request`.
+    pub fn with_str(self, request: DeviceInfo, value: &str) -> DeviceFixture {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.push(0);
+        self.with_bytes(request, bytes)
+    }
+
+    /// Sets the list of sizes reported for `request`.
+    pub fn with_sizes(self, request: DeviceInfo, sizes: &[usize]) -> DeviceFixture {
+        let bytes = unsafe { slice::from_raw_parts(sizes.as_ptr() as *const u8,
+            sizes.len() * mem::size_of::<usize>()) }.to_vec();
+        self.with_bytes(request, bytes)
+    }
+
+    /// Removes the value reported for `request` (as for a device which does
+    /// not support the query).
+    pub fn without(mut self, request: DeviceInfo) -> DeviceFixture {
+        self.values.retain(|&(r, _)| r != request);
+        self
+    }
+
+    /// Returns the device name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl DeviceInfoSource for DeviceFixture {
+    fn info_bytes(&self, request: DeviceInfo) -> OclResult<Vec<u8>> {
+        match self.values.iter().find(|&&(r, _)| r == request) {
+            Some(&(_, ref bytes)) => Ok(bytes.clone()),
+            None => OclError::err_status(Status::CL_INVALID_VALUE as i32, "clGetDeviceInfo",
+                format!("fixture '{}' has no value for {:?}", self.name, request)),
+        }
+    }
+}
+
+/// A discrete NVIDIA GPU (OpenCL 1.2, CUDA).
+pub fn nvidia_discrete() -> DeviceFixture {
+    DeviceFixture::new("GeForce GTX 1080")
+        .with_str(DeviceInfo::Vendor, "NVIDIA Corporation")
+        .with(DeviceInfo::Type, core::DEVICE_TYPE_GPU.bits())
+        .with_str(DeviceInfo::Version, "OpenCL 1.2 CUDA")
+        .with_str(DeviceInfo::Extensions, "cl_khr_global_int32_base_atomics \
+            cl_khr_local_int32_base_atomics cl_khr_fp64 cl_khr_byte_addressable_store \
+            cl_khr_icd cl_khr_gl_sharing cl_nv_compiler_options cl_nv_device_attribute_query")
+        .with(DeviceInfo::MaxComputeUnits, 20u32)
+        .with(DeviceInfo::GlobalMemSize, 8508145664u64)
+        .with(DeviceInfo::MaxWorkGroupSize, 1024usize)
+        .with(DeviceInfo::MaxWorkItemDimensions, 3u32)
+        .with_sizes(DeviceInfo::MaxWorkItemSizes, &[1024, 1024, 64])
+        .with(DeviceInfo::EndianLittle, 1u32)
+        .with(DeviceInfo::ExecutionCapabilities, core::EXEC_KERNEL.bits())
+        .with(DeviceInfo::MemBaseAddrAlign, 4096u32)
+        .with(DeviceInfo::MaxMemAllocSize, 2147287040u64)
+        .with(DeviceInfo::ImageSupport, 1u32)
+        .with(DeviceInfo::Image2dMaxWidth, 16384usize)
+        .with(DeviceInfo::Image2dMaxHeight, 32768usize)
+        .with(DeviceInfo::Image3dMaxWidth, 16384usize)
+        .with(DeviceInfo::Image3dMaxHeight, 16384usize)
+        .with(DeviceInfo::Image3dMaxDepth, 16384usize)
+        .with(DeviceInfo::HostUnifiedMemory, 0u32)
+}
+
+/// An integrated Intel GPU (OpenCL 2.1, NEO).
+pub fn intel_integrated() -> DeviceFixture {
+    DeviceFixture::new("Intel(R) HD Graphics 620")
+        .with_str(DeviceInfo::Vendor, "Intel(R) Corporation")
+        .with(DeviceInfo::Type, core::DEVICE_TYPE_GPU.bits())
+        .with_str(DeviceInfo::Version, "OpenCL 2.1 NEO")
+        .with_str(DeviceInfo::Extensions, "cl_khr_3d_image_writes cl_khr_byte_addressable_store \
+            cl_khr_fp16 cl_khr_global_int32_base_atomics cl_khr_icd cl_khr_fp64 \
+            cl_khr_subgroups cl_intel_subgroups")
+        .with(DeviceInfo::MaxComputeUnits, 24u32)
+        .with(DeviceInfo::GlobalMemSize, 13316907008u64)
+        .with(DeviceInfo::MaxWorkGroupSize, 256usize)
+        .with(DeviceInfo::MaxWorkItemDimensions, 3u32)
+        .with_sizes(DeviceInfo::MaxWorkItemSizes, &[256, 256, 256])
+        .with(DeviceInfo::EndianLittle, 1u32)
+        .with(DeviceInfo::ExecutionCapabilities, core::EXEC_KERNEL.bits())
+        .with(DeviceInfo::MemBaseAddrAlign, 1024u32)
+        .with(DeviceInfo::MaxMemAllocSize, 4294959104u64)
+        .with(DeviceInfo::ImageSupport, 1u32)
+        .with(DeviceInfo::Image2dMaxWidth, 16384usize)
+        .with(DeviceInfo::Image2dMaxHeight, 16384usize)
+        .with(DeviceInfo::Image3dMaxWidth, 16384usize)
+        .with(DeviceInfo::Image3dMaxHeight, 16384usize)
+        .with(DeviceInfo::Image3dMaxDepth, 2048usize)
+        .with(DeviceInfo::HostUnifiedMemory, 1u32)
+}
+
+/// A CPU device under pocl (OpenCL 1.2), which, like some other CPU
+/// implementations, does not report unified host memory.
+pub fn pocl_cpu() -> DeviceFixture {
+    DeviceFixture::new("pthread-Intel(R) Core(TM) i7-7500U CPU @ 2.70GHz")
+        .with_str(DeviceInfo::Vendor, "GenuineIntel")
+        .with(DeviceInfo::Type, core::DEVICE_TYPE_CPU.bits())
+        .with_str(DeviceInfo::Version, "OpenCL 1.2 pocl 1.1 None+Asserts, LLVM 6.0.0")
+        .with_str(DeviceInfo::Extensions, "cl_khr_byte_addressable_store \
+            cl_khr_global_int32_base_atomics cl_khr_local_int32_base_atomics \
+            cl_khr_3d_image_writes cl_khr_fp64 cl_khr_int64_base_atomics")
+        .with(DeviceInfo::MaxComputeUnits, 4u32)
+        .with(DeviceInfo::GlobalMemSize, 6442450944u64)
+        .with(DeviceInfo::MaxWorkGroupSize, 4096usize)
+        .with(DeviceInfo::MaxWorkItemDimensions, 3u32)
+        .with_sizes(DeviceInfo::MaxWorkItemSizes, &[4096, 4096, 4096])
+        .with(DeviceInfo::EndianLittle, 1u32)
+        .with(DeviceInfo::ExecutionCapabilities,
+            (core::EXEC_KERNEL | core::EXEC_NATIVE_KERNEL).bits())
+        .with(DeviceInfo::MemBaseAddrAlign, 1024u32)
+        .with(DeviceInfo::MaxMemAllocSize, 4294967296u64)
+        .with(DeviceInfo::ImageSupport, 1u32)
+        .with(DeviceInfo::Image2dMaxWidth, 8192usize)
+        .with(DeviceInfo::Image2dMaxHeight, 8192usize)
+        .with(DeviceInfo::Image3dMaxWidth, 2048usize)
+        .with(DeviceInfo::Image3dMaxHeight, 2048usize)
+        .with(DeviceInfo::Image3dMaxDepth, 2048usize)
+}
+
+/// Returns each fixture.
+pub fn all() -> Vec<DeviceFixture> {
+    vec![nvidia_discrete(), intel_integrated(), pocl_cpu()]
+}
+
+#[test]
+fn fixtures_parse() {
+    for fixture in all() {
+        assert_eq!(fixture.info(DeviceInfo::Name).to_string(), fixture.name());
+        assert_eq!(fixture.max_wi_sizes().unwrap().len(),
+            fixture.max_wi_dims().unwrap() as usize);
+        assert!(fixture.max_wg_size().unwrap() > 0);
+        assert!(fixture.endian_little().unwrap());
+        assert!(fixture.execution_capabilities().unwrap().contains(core::EXEC_KERNEL));
+        assert!(fixture.has_extension("cl_khr_byte_addressable_store").unwrap());
+    }
+
+    let err = pocl_cpu().host_unified_memory().unwrap_err();
+    assert!(err.is_status(Status::CL_INVALID_VALUE));
+}
@@ -0,0 +1,57 @@
+//! Tests `Kernel::verify_fp64` on devices with and without `cl_khr_fp64`.
+
+use standard::ProQue;
+use vectors::ClDouble2;
+
+static SRC_FP64: &'static str = r#"
+    #pragma OPENCL EXTENSION cl_khr_fp64 : enable
+
+    __kernel void add(__global double* buffer, double addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+static SRC_FP32: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn fp64_verify() {
+    let pro_que = ProQue::builder().src(SRC_FP32).dims([1024]).build().unwrap();
+
+    if pro_que.queue().device_has_extension("cl_khr_fp64") {
+        let pro_que = ProQue::builder().src(SRC_FP64).dims([1024]).build().unwrap();
+        let buffer = pro_que.create_buffer::<f64>().unwrap();
+
+        let kernel = pro_que.create_kernel("add").unwrap()
+            .verify_fp64(true)
+            .arg_buf(&buffer)
+            .arg_scl(5.0f64);
+        kernel.enq().unwrap();
+
+        let mut vec = vec![0.0f64; 1024];
+        buffer.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&v| v == 5.0));
+    } else {
+        let buffer = pro_que.create_buffer::<f64>().unwrap();
+        let mut kernel = pro_que.create_kernel("add").unwrap()
+            .verify_fp64(true)
+            .arg_buf_named::<f32>("buf", None)
+            .arg_scl_named::<f32>("addend", None);
+
+        let err = kernel.set_arg_scl_named("addend", 5.0f64).err().unwrap();
+        assert!(err.to_string().contains("cl_khr_fp64"));
+        assert!(kernel.set_arg_vec_named("addend", &[ClDouble2::default()]).is_err());
+        assert!(kernel.set_arg_buf_named("buf", Some(&buffer)).is_err());
+    }
+
+    // Single-precision arguments are never affected:
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .verify_fp64(true)
+        .arg_buf(&buffer)
+        .arg_scl(5.0f32);
+    kernel.enq().unwrap();
+}
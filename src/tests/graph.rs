@@ -0,0 +1,132 @@
+//! Tests enqueuing commands with declared dependencies using a `Graph`.
+
+use core::ProfilingInfo;
+use util;
+use standard::{ProQue, Context, Queue, Buffer, Event, Graph};
+use enums::ProfilingInfoResult;
+
+static SRC: &'static str = r#"
+    __kernel void scale(__global const uint* src, __global uint* dst, uint factor) {
+        uint idx = get_global_id(0);
+        dst[idx] = src[idx] * factor;
+    }
+
+    __kernel void sum(__global const uint* a, __global const uint* b, __global uint* dst) {
+        uint idx = get_global_id(0);
+        dst[idx] = a[idx] + b[idx];
+    }
+"#;
+
+const LEN: usize = 1 << 14;
+
+/// Returns the value of a profiling counter of `event`.
+fn timestamp(event: &Event, info: ProfilingInfo) -> u64 {
+    match event.profiling_info(info) {
+        ProfilingInfoResult::TemporaryPlaceholderVariant(bytes) => unsafe {
+            util::bytes_to::<u64>(&bytes).unwrap()
+        },
+        res => panic!("Unable to query profiling info: {}", res),
+    }
+}
+
+#[test]
+fn diamond() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    let src: Buffer<u32> = proque.create_buffer().unwrap();
+    let left: Buffer<u32> = proque.create_buffer().unwrap();
+    let right: Buffer<u32> = proque.create_buffer().unwrap();
+    let dst: Buffer<u32> = proque.create_buffer().unwrap();
+
+    let scale_left = proque.create_kernel("scale").unwrap()
+        .arg_buf(&src).arg_buf(&left).arg_scl(2u32);
+    let scale_right = proque.create_kernel("scale").unwrap()
+        .arg_buf(&src).arg_buf(&right).arg_scl(3u32);
+    let sum = proque.create_kernel("sum").unwrap()
+        .arg_buf(&left).arg_buf(&right).arg_buf(&dst);
+
+    let input: Vec<u32> = (0..LEN as u32).collect();
+    let mut graph = Graph::new();
+
+    // Added out of order to exercise sorting:
+    graph.add("sum", &["left", "right"], |q, wait, new| {
+        sum.cmd().queue(q).ewait(wait).enew(new).enq()
+    });
+    graph.add("left", &["upload"], |q, wait, new| {
+        scale_left.cmd().queue(q).ewait(wait).enew(new).enq()
+    });
+    graph.add("right", &["upload"], |q, wait, new| {
+        scale_right.cmd().queue(q).ewait(wait).enew(new).enq()
+    });
+    graph.add("upload", &[], |q, wait, new| {
+        src.cmd().queue(q).write(&input).ewait(wait).enew(new).enq()
+    });
+
+    // Enqueued repeatedly, as each frame would be:
+    for _ in 0..3 {
+        {
+            let events = graph.enqueue(proque.queue()).unwrap();
+            events.wait().unwrap();
+            assert_eq!(events.len(), 4);
+
+            let start = |name| timestamp(events.get(name).unwrap(), ProfilingInfo::Start);
+            let end = |name| timestamp(events.get(name).unwrap(), ProfilingInfo::End);
+
+            assert!(start("left") >= end("upload"));
+            assert!(start("right") >= end("upload"));
+            assert!(start("sum") >= end("left"));
+            assert!(start("sum") >= end("right"));
+
+            let names: Vec<&str> = events.iter().map(|(name, _)| name).collect();
+            assert_eq!(names, vec!["sum", "left", "right", "upload"]);
+        }
+
+        let mut result = vec![0u32; LEN];
+        dst.read(&mut result).enq().unwrap();
+
+        for (idx, &val) in result.iter().enumerate() {
+            assert_eq!(val, idx as u32 * 5);
+        }
+    }
+}
+
+#[test]
+fn cycle() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let mut graph = Graph::new();
+
+    graph.add("upload", &[], |_, _, _| panic!("Enqueued a node of a cyclic graph."));
+    graph.add("a", &["upload", "c"], |_, _, _| panic!("Enqueued a node of a cyclic graph."));
+    graph.add("b", &["a"], |_, _, _| panic!("Enqueued a node of a cyclic graph."));
+    graph.add("c", &["b"], |_, _, _| panic!("Enqueued a node of a cyclic graph."));
+
+    let err = graph.enqueue(&queue).unwrap_err();
+    assert!(err.to_string().contains("a -> c -> b -> a"), "{}", err);
+}
+
+#[test]
+fn invalid_deps() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+
+    let mut graph = Graph::new();
+    graph.add("a", &["missing"], |_, _, _| panic!("Enqueued a node of an invalid graph."));
+    let err = graph.enqueue(&queue).unwrap_err();
+    assert!(err.to_string().contains("'missing'"), "{}", err);
+
+    let mut graph = Graph::new();
+    graph.add("a", &[], |_, _, _| panic!("Enqueued a node of an invalid graph."));
+    graph.add("a", &[], |_, _, _| panic!("Enqueued a node of an invalid graph."));
+    let err = graph.enqueue(&queue).unwrap_err();
+    assert!(err.to_string().contains("More than one node"), "{}", err);
+
+    // Self-dependency:
+    let mut graph = Graph::new();
+    graph.add("a", &["a"], |_, _, _| panic!("Enqueued a node of an invalid graph."));
+    let err = graph.enqueue(&queue).unwrap_err();
+    assert!(err.to_string().contains("a -> a"), "{}", err);
+}
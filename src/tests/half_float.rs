@@ -0,0 +1,81 @@
+//! Tests `f16` buffers, images and (where supported) kernel arguments.
+
+use half::f16;
+use standard::{ProQue, Image};
+use enums::{ImageChannelDataType, MemObjectType, ImageInfo, ImageInfoResult};
+
+const LEN: usize = 1024;
+const TOLERANCE: f32 = 0.01;
+
+static SRC: &'static str = r#"
+    #pragma OPENCL EXTENSION cl_khr_fp16 : enable
+
+    __kernel void scale(__global half* buffer, half factor) {
+        uint idx = get_global_id(0);
+        buffer[idx] = buffer[idx] * factor;
+    }
+"#;
+
+static SRC_NO_EXT: &'static str = r#"
+    __kernel void copy(__global ushort* src, __global ushort* dst) {
+        uint idx = get_global_id(0);
+        dst[idx] = src[idx];
+    }
+"#;
+
+fn approx_eq(a: f32, b: f32) -> bool {
+    (a - b).abs() <= TOLERANCE * b.abs().max(1.0)
+}
+
+fn vec_init() -> Vec<f16> {
+    (0..LEN).map(|i| f16::from_f32(i as f32 * 0.125)).collect()
+}
+
+#[test]
+fn half_float_buffer() {
+    let pro_que = ProQue::builder().src(SRC_NO_EXT).dims([LEN]).build().unwrap();
+    let buffer = pro_que.create_buffer::<f16>().unwrap();
+    assert!(buffer.read_as_f32().unwrap().iter().all(|&v| v == 0.0));
+
+    buffer.write(&vec_init()).enq().unwrap();
+
+    for (i, v) in buffer.read_as_f32().unwrap().into_iter().enumerate() {
+        assert!(approx_eq(v, i as f32 * 0.125), "buffer[{}]: {}", i, v);
+    }
+
+    let image = Image::<f16>::builder().image_type(MemObjectType::Image2d).dims([16, 16])
+        .build(pro_que.queue()).unwrap();
+    match image.info(ImageInfo::Format) {
+        ImageInfoResult::Format(format) => {
+            assert_eq!(format.channel_data_type, ImageChannelDataType::HalfFloat)
+        },
+        _ => panic!("Unexpected 'ImageInfoResult' variant."),
+    }
+}
+
+#[test]
+fn half_float_kernel() {
+    let pro_que = ProQue::builder().src(SRC_NO_EXT).dims([LEN]).build().unwrap();
+    let factor = f16::from_f32(2.0);
+
+    if !pro_que.queue().device_has_extension("cl_khr_fp16") {
+        let mut kernel = pro_que.create_kernel("copy").unwrap()
+            .arg_buf_named::<u16>("src", None)
+            .arg_buf_named::<u16>("dst", None);
+        assert!(kernel.set_arg_scl_named("src", factor).is_err());
+        return;
+    }
+
+    let pro_que = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let buffer = pro_que.create_buffer::<f16>().unwrap();
+    buffer.write(&vec_init()).enq().unwrap();
+
+    let kernel = pro_que.create_kernel("scale").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(factor);
+    kernel.enq().unwrap();
+
+    for (i, v) in buffer.read_as_f32().unwrap().into_iter().enumerate() {
+        assert!(approx_eq(v, i as f32 * 0.125 * 2.0), "buffer[{}]: {}", i, v);
+    }
+}
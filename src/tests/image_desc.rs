@@ -0,0 +1,138 @@
+//! Tests checking image descriptors against their image type with
+//! `ImageDescriptor::checked` and `ImageBuilder`.
+
+use core::{ImageDescriptor, MemObjectType};
+use error::Result as OclResult;
+use standard::{Context, Queue, Buffer, Image};
+
+/// Returns the error message of `result`, which must be an error.
+fn err_msg(result: OclResult<ImageDescriptor>) -> String {
+    match result {
+        Ok(desc) => panic!("Expected an error, got: {:?}", desc),
+        Err(err) => err.to_string(),
+    }
+}
+
+fn check(image_type: MemObjectType, dims: [usize; 3], array_size: usize)
+        -> OclResult<ImageDescriptor>
+{
+    ImageDescriptor::checked(Some(image_type), dims, array_size, None)
+}
+
+fn assert_err(image_type: MemObjectType, dims: [usize; 3], array_size: usize, msg: &str) {
+    let err = err_msg(check(image_type, dims, array_size));
+    assert!(err.ends_with(msg), "{:?} {:?} {}: {}", image_type, dims, array_size, err);
+}
+
+#[test]
+fn invalid() {
+    use self::MemObjectType::*;
+
+    assert_err(Buffer, [16, 1, 1], 0, "'Buffer' is not an image type.");
+
+    for &ty in [Image1d, Image1dBuffer, Image1dArray, Image2d, Image2dArray, Image3d].iter() {
+        let err = err_msg(check(ty, [0, 1, 1], 1));
+        assert!(err.contains("require a width of at least 1."), "{}", err);
+    }
+
+    assert_err(Image1d, [16, 4, 1], 0,
+        "1D images (Image1d) require a height of 1 (or 0), found 4.");
+    assert_err(Image1d, [16, 1, 4], 0,
+        "1D images (Image1d) require a depth of 1 (or 0), found 4.");
+    assert_err(Image1d, [16, 1, 1], 4,
+        "1D images (Image1d) may not have an array size greater than 1 (only image arrays \
+        may), found 4.");
+    assert_err(Image1dBuffer, [16, 4, 1], 0,
+        "1D image buffers (Image1dBuffer) require a height of 1 (or 0), found 4.");
+    assert_err(Image1dBuffer, [16, 1, 1], 0,
+        "1D image buffers (Image1dBuffer) require a buffer.");
+
+    assert_err(Image1dArray, [16, 1, 1], 0,
+        "1D image arrays (Image1dArray) require an array size of at least 1, given either as \
+        the array size or as the dimension following the image dimensions.");
+    assert_err(Image1dArray, [16, 4, 1], 3,
+        "1D image arrays (Image1dArray) require the array size (3) and the dimension \
+        following the image dimensions (4) to agree when both are given.");
+    assert_err(Image1dArray, [16, 1, 4], 2,
+        "1D image arrays (Image1dArray) require a depth of 1 (or 0), found 4.");
+
+    assert_err(Image2d, [16, 0, 1], 0,
+        "2D images (Image2d) require a height of at least 1.");
+    assert_err(Image2d, [16, 16, 4], 0,
+        "2D images (Image2d) require a depth of 1 (or 0), found 4.");
+    assert_err(Image2d, [16, 16, 1], 2,
+        "2D images (Image2d) may not have an array size greater than 1 (only image arrays \
+        may), found 2.");
+
+    assert_err(Image2dArray, [16, 16, 1], 0,
+        "2D image arrays (Image2dArray) require an array size of at least 1, given either as \
+        the array size or as the dimension following the image dimensions.");
+    assert_err(Image2dArray, [16, 0, 1], 2,
+        "2D image arrays (Image2dArray) require a height of at least 1.");
+    assert_err(Image2dArray, [16, 16, 4], 3,
+        "2D image arrays (Image2dArray) require the array size (3) and the dimension \
+        following the image dimensions (4) to agree when both are given.");
+
+    assert_err(Image3d, [16, 16, 0], 0,
+        "3D images (Image3d) require a depth of at least 1.");
+    assert_err(Image3d, [16, 0, 16], 0,
+        "3D images (Image3d) require a height of at least 1.");
+    assert_err(Image3d, [16, 16, 16], 2,
+        "3D images (Image3d) may not have an array size greater than 1 (only image arrays \
+        may), found 2.");
+}
+
+#[test]
+fn inferred() {
+    let desc = ImageDescriptor::checked(None, [16, 1, 1], 0, None).unwrap();
+    assert_eq!(desc.image_type, MemObjectType::Image1d);
+
+    let desc = ImageDescriptor::checked(None, [16, 8, 1], 0, None).unwrap();
+    assert_eq!(desc.image_type, MemObjectType::Image2d);
+    assert_eq!((desc.image_width, desc.image_height, desc.image_depth), (16, 8, 1));
+
+    let desc = ImageDescriptor::checked(None, [16, 8, 4], 0, None).unwrap();
+    assert_eq!(desc.image_type, MemObjectType::Image3d);
+
+    let desc = ImageDescriptor::checked(None, [16, 1, 1], 3, None).unwrap();
+    assert_eq!(desc.image_type, MemObjectType::Image1dArray);
+    assert_eq!(desc.image_array_size, 3);
+
+    let desc = ImageDescriptor::checked(None, [16, 8, 1], 3, None).unwrap();
+    assert_eq!(desc.image_type, MemObjectType::Image2dArray);
+
+    // Array sizes given as the dimension following the image dimensions:
+    let desc = check(MemObjectType::Image1dArray, [16, 5, 1], 0).unwrap();
+    assert_eq!((desc.image_height, desc.image_array_size), (1, 5));
+    let desc = check(MemObjectType::Image2dArray, [16, 8, 5], 0).unwrap();
+    assert_eq!((desc.image_height, desc.image_depth, desc.image_array_size), (8, 1, 5));
+    let desc = check(MemObjectType::Image2dArray, [16, 8, 5], 5).unwrap();
+    assert_eq!(desc.image_array_size, 5);
+
+    // Unused dimensions:
+    let desc = check(MemObjectType::Image1d, [16, 0, 0], 1).unwrap();
+    assert_eq!((desc.image_height, desc.image_depth, desc.image_array_size), (1, 1, 0));
+}
+
+#[test]
+fn buffers() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let buffer = Buffer::<u8>::builder().dims([64]).uninit().build(&queue).unwrap();
+    let mem = buffer.core_as_ref().clone();
+
+    let desc = ImageDescriptor::checked(None, [16, 1, 1], 0, Some(mem.clone())).unwrap();
+    assert_eq!(desc.image_type, MemObjectType::Image1dBuffer);
+
+    let err = err_msg(ImageDescriptor::checked(Some(MemObjectType::Image2d), [16, 16, 1], 0,
+        Some(mem)));
+    assert!(err.ends_with("2D images (Image2d) may not have a buffer (only 1D image buffers \
+        may)."), "{}", err);
+
+    // Through the builder, before creating anything:
+    let err = Image::<u8>::builder()
+        .image_type(MemObjectType::Image2dArray)
+        .dims([16, 16])
+        .build(&queue).unwrap_err().to_string();
+    assert!(err.contains("require an array size of at least 1"), "{}", err);
+}
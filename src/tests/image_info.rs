@@ -0,0 +1,108 @@
+//! Tests image info queries (`core::get_image_info`) and, by extension,
+//! `ImageFormat::pixel_bytes`.
+
+use core::{self, ContextProperties, ImageFormat, ImageDescriptor};
+use flags;
+use enums::{ImageInfo, ImageInfoResult, ImageChannelOrder, ImageChannelDataType,
+    MemObjectType};
+
+const WIDTH: usize = 16;
+const HEIGHT: usize = 8;
+
+#[test]
+fn image_info_core() {
+    let platform_id = core::default_platform().unwrap();
+    let device_ids = core::get_device_ids(&platform_id, None, None).unwrap();
+    let device_id = device_ids[0];
+    let context_properties = ContextProperties::new().platform(platform_id);
+    let context = core::create_context(&Some(context_properties),
+        &[device_id], None, None).unwrap();
+
+    let supported = core::get_supported_image_formats(&context, flags::MEM_READ_WRITE,
+        MemObjectType::Image2d).unwrap();
+
+    let formats = [
+        (ImageChannelOrder::Rgba, ImageChannelDataType::UnormInt8),
+        (ImageChannelOrder::Rgba, ImageChannelDataType::SignedInt16),
+        (ImageChannelOrder::Rgba, ImageChannelDataType::Float),
+        (ImageChannelOrder::Bgra, ImageChannelDataType::UnormInt8),
+        (ImageChannelOrder::R, ImageChannelDataType::Float),
+        (ImageChannelOrder::R, ImageChannelDataType::UnsignedInt8),
+        (ImageChannelOrder::Rg, ImageChannelDataType::HalfFloat),
+        (ImageChannelOrder::Intensity, ImageChannelDataType::Float),
+        (ImageChannelOrder::Luminance, ImageChannelDataType::UnormInt16),
+        (ImageChannelOrder::Rgb, ImageChannelDataType::UnormShort565),
+    ];
+
+    for &(order, data_type) in formats.iter() {
+        if !supported.iter().any(|f| f.channel_order == order &&
+            f.channel_data_type == data_type)
+        {
+            continue;
+        }
+
+        let format = ImageFormat::new(order, data_type);
+        let desc = ImageDescriptor::new(MemObjectType::Image2d, WIDTH, HEIGHT, 1, 0, 0, 0, None);
+        let image = unsafe { core::create_image::<u8>(&context, flags::MEM_READ_WRITE,
+            &format, &desc, None).unwrap() };
+
+        match core::get_image_info(&image, ImageInfo::Format) {
+            ImageInfoResult::Format(f) => {
+                assert_eq!(f.channel_order, order);
+                assert_eq!(f.channel_data_type, data_type);
+            },
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_image_info(&image, ImageInfo::ElementSize) {
+            ImageInfoResult::ElementSize(s) => assert_eq!(s, format.pixel_bytes(),
+                "Element size mismatch for {:?}.", format),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_image_info(&image, ImageInfo::RowPitch) {
+            ImageInfoResult::RowPitch(p) => assert!(p >= WIDTH * format.pixel_bytes()),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_image_info(&image, ImageInfo::SlicePitch) {
+            ImageInfoResult::SlicePitch(p) => assert_eq!(p, 0),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_image_info(&image, ImageInfo::Width) {
+            ImageInfoResult::Width(w) => assert_eq!(w, WIDTH),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_image_info(&image, ImageInfo::Height) {
+            ImageInfoResult::Height(h) => assert_eq!(h, HEIGHT),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_image_info(&image, ImageInfo::Depth) {
+            ImageInfoResult::Depth(d) => assert_eq!(d, 0),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_image_info(&image, ImageInfo::ArraySize) {
+            ImageInfoResult::ArraySize(s) => assert_eq!(s, 0),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_image_info(&image, ImageInfo::Buffer) {
+            ImageInfoResult::Buffer(b) => assert!(b.is_none()),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_image_info(&image, ImageInfo::NumMipLevels) {
+            ImageInfoResult::NumMipLevels(n) => assert_eq!(n, 0),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_image_info(&image, ImageInfo::NumSamples) {
+            ImageInfoResult::NumSamples(n) => assert_eq!(n, 0),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Tests `core::enqueue_map_image` and `core::enqueue_unmap_mem_object`.
+
+use std::slice;
+use core::{self, ContextProperties, ImageFormat, ImageDescriptor};
+use flags;
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+const WIDTH: usize = 16;
+const HEIGHT: usize = 8;
+const PIXEL_SIZE: usize = 4;
+
+/// Returns the expected value of the channel `c` of the pixel at `(x, y)`.
+fn channel_val(x: usize, y: usize, c: usize) -> u8 {
+    ((y * WIDTH + x) * PIXEL_SIZE + c) as u8
+}
+
+#[test]
+fn image_map_core() {
+    let platform_id = core::default_platform().unwrap();
+    let device_ids = core::get_device_ids(&platform_id, None, None).unwrap();
+    let device_id = device_ids[0];
+    let context_properties = ContextProperties::new().platform(platform_id);
+    let context = core::create_context(&Some(context_properties),
+        &[device_id], None, None).unwrap();
+    let queue = core::create_command_queue(&context, &device_id).unwrap();
+
+    let data: Vec<u8> = (0..HEIGHT).flat_map(|y| (0..WIDTH).flat_map(move |x|
+        (0..PIXEL_SIZE).map(move |c| channel_val(x, y, c)))).collect();
+
+    let format = ImageFormat::new(ImageChannelOrder::Rgba, ImageChannelDataType::UnsignedInt8);
+    let desc = ImageDescriptor::new(MemObjectType::Image2d, WIDTH, HEIGHT, 1, 0, 0, 0, None);
+    let image = unsafe { core::create_image(&context, flags::MEM_READ_WRITE |
+        flags::MEM_COPY_HOST_PTR, &format, &desc, Some(&data)).unwrap() };
+
+    // Map the whole image and a sub-region, checking contents directly:
+    for &(origin, region) in &[([0, 0, 0], [WIDTH, HEIGHT, 1]), ([3, 2, 0], [5, 4, 1])] {
+        let (ptr, row_pitch, _) = unsafe { core::enqueue_map_image(&queue, &image, true,
+            flags::MAP_READ, origin, region, None, None).unwrap() };
+
+        assert!(row_pitch >= region[0] * PIXEL_SIZE);
+
+        let mapped = unsafe { slice::from_raw_parts(ptr as *const u8,
+            row_pitch * (region[1] - 1) + region[0] * PIXEL_SIZE) };
+
+        for y in 0..region[1] {
+            for x in 0..region[0] {
+                for c in 0..PIXEL_SIZE {
+                    assert_eq!(mapped[y * row_pitch + x * PIXEL_SIZE + c],
+                        channel_val(origin[0] + x, origin[1] + y, c));
+                }
+            }
+        }
+
+        core::enqueue_unmap_mem_object(&queue, &image, ptr, None, None).unwrap();
+    }
+
+    // Zero-sized regions are rejected:
+    assert!(unsafe { core::enqueue_map_image(&queue, &image, true, flags::MAP_READ,
+        [0, 0, 0], [WIDTH, 0, 1], None, None) }.is_err());
+
+    core::finish(&queue).unwrap();
+}
@@ -1,12 +1,12 @@
 //! Tests various image operations.
 //!
-//! TODO: Test fill and copy to buffer.
+//! TODO: Test copy to buffer.
 //!
 //! Runs both the core function and the 'standard' method call for each.
 
 use core;
 use flags;
-use standard::{ProQue, Image, Sampler};
+use standard::{ProQue, Context, Queue, Image, Sampler};
 use enums::{AddressingMode, FilterMode, ImageChannelOrder, ImageChannelDataType, MemObjectType};
 use tests;
 
@@ -141,7 +141,7 @@ fn image_ops() {
             &mut vec, None, None).unwrap(); }
 
         // Just to make sure read is complete:
-        proque.queue().finish();
+        proque.queue().finish().unwrap();
 
         // Verify:
         tests::verify_vec_rect(origin, region, cur_val, old_val, 
@@ -163,7 +163,7 @@ fn image_ops() {
             &mut vec, None, None).unwrap(); }
 
         // Just to make sure read is complete:
-        proque.queue().finish();
+        proque.queue().finish().unwrap();
 
         // Verify:
         tests::verify_vec_rect(origin, region, cur_val, old_val, 
@@ -206,4 +206,34 @@ fn image_ops() {
     }
 
     println!("{} total test runs complete.\n", ttl_runs);
-}
\ No newline at end of file
+}
+
+#[test]
+fn image_fill() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+
+    let image = Image::<i32>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::SignedInt32)
+        .image_type(MemObjectType::Image2d)
+        .dims([8, 8])
+        .build(&queue).unwrap();
+
+    assert!(image.fill(&[1, 2, 3]).enq().is_err());
+
+    image.fill(&[1, 2, 3, 4]).enq().unwrap();
+    image.fill(&[5, 6, 7, 8]).origin([2, 2, 0]).region([4, 4, 1]).enq().unwrap();
+
+    let mut vec = vec![0i32; 8 * 8 * 4];
+    image.read(&mut vec).enq().unwrap();
+
+    for y in 0..8 {
+        for x in 0..8 {
+            let inner = x >= 2 && x < 6 && y >= 2 && y < 6;
+            let expected = if inner { [5, 6, 7, 8] } else { [1, 2, 3, 4] };
+            let idx = (y * 8 + x) * 4;
+            assert_eq!(&vec[idx..idx + 4], &expected[..], "pixel ({}, {})", x, y);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Tests `ImageOrigin` and `ImageRegion` and their use by image and
+//! rectangular buffer commands.
+
+use flags;
+use standard::{Context, Queue, Buffer, Image, ImageOrigin, ImageRegion, SpatialDims};
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+const WIDTH: usize = 8;
+const HEIGHT: usize = 4;
+const LAYERS: usize = 3;
+
+#[test]
+fn constructors() {
+    assert_eq!(ImageOrigin::d1(5).to_array(), [5, 0, 0]);
+    assert_eq!(ImageOrigin::d2(5, 6).to_array(), [5, 6, 0]);
+    assert_eq!(ImageOrigin::d1_array(5, 2).to_array(), [5, 2, 0]);
+    assert_eq!(ImageOrigin::d2_array(5, 6, 2).to_array(), [5, 6, 2]);
+    assert_eq!(ImageOrigin::from((1, 2)), ImageOrigin::d2(1, 2));
+    assert_eq!(ImageOrigin::from([1, 2, 3]), ImageOrigin::d3(1, 2, 3));
+    assert_eq!(ImageOrigin::from(SpatialDims::Two(1, 2)), ImageOrigin::d2(1, 2));
+    assert_eq!(ImageOrigin::from(SpatialDims::Unspecified), ImageOrigin::default());
+
+    assert_eq!(ImageRegion::d1(5).to_array(), [5, 1, 1]);
+    assert_eq!(ImageRegion::d2(5, 6).to_array(), [5, 6, 1]);
+    assert_eq!(ImageRegion::d1_array(5, 2).to_array(), [5, 2, 1]);
+    assert_eq!(ImageRegion::d2_array(5, 6, 2).to_array(), [5, 6, 2]);
+    assert_eq!(ImageRegion::from(7), ImageRegion::d1(7));
+    assert_eq!(ImageRegion::from((1, 2, 3)), ImageRegion::d3(1, 2, 3));
+    assert_eq!(ImageRegion::from(SpatialDims::Two(4, 5)), ImageRegion::d2(4, 5));
+    assert!(ImageRegion::from(SpatialDims::Unspecified).is_empty());
+    assert_eq!(ImageRegion::d3(2, 3, 4).to_len(), 24);
+}
+
+#[test]
+fn fits_within() {
+    let dims = ImageRegion::d2(WIDTH, HEIGHT);
+
+    assert!(dims.fits_within(dims, ImageOrigin::default()));
+    assert!(ImageRegion::d2(2, 2).fits_within(dims, (6, 2)));
+    assert!(!ImageRegion::d2(2, 2).fits_within(dims, (7, 2)));
+    assert!(!ImageRegion::d2(2, 2).fits_within(dims, (6, 3)));
+    assert!(!ImageRegion::d3(2, 2, 2).fits_within(dims, (0, 0)));
+    assert!(!ImageRegion::d2(0, 2).fits_within(dims, (0, 0)));
+    assert!(!ImageRegion::d2(2, 2).fits_within(dims, (::std::usize::MAX, 0)));
+
+    // Layers of an array:
+    let array_dims = ImageRegion::d2_array(WIDTH, HEIGHT, LAYERS);
+    assert!(ImageRegion::d2_array(WIDTH, HEIGHT, 1).fits_within(array_dims,
+        ImageOrigin::d2_array(0, 0, LAYERS - 1)));
+    assert!(!ImageRegion::d2_array(WIDTH, HEIGHT, 2).fits_within(array_dims,
+        ImageOrigin::d2_array(0, 0, LAYERS - 1)));
+}
+
+#[test]
+fn overlaps() {
+    let region = ImageRegion::d2(4, 4);
+
+    assert!(region.overlaps((0, 0), (0, 0)));
+    assert!(region.overlaps((0, 0), (3, 3)));
+    assert!(region.overlaps((3, 3), (0, 0)));
+    assert!(!region.overlaps((0, 0), (4, 0)));
+    assert!(!region.overlaps((0, 0), (0, 4)));
+    assert!(!ImageRegion::d3(4, 4, 1).overlaps((0, 0, 0), (0, 0, 1)));
+    assert!(!ImageRegion::d2(0, 4).overlaps((0, 0), (0, 0)));
+}
+
+fn image_array(queue: &Queue, data: &[u32]) -> Image<u32> {
+    Image::<u32>::builder()
+        .channel_order(ImageChannelOrder::R)
+        .channel_data_type(ImageChannelDataType::UnsignedInt32)
+        .image_type(MemObjectType::Image2dArray)
+        .dims([WIDTH, HEIGHT])
+        .array_size(LAYERS)
+        .flags(flags::MEM_READ_WRITE | flags::MEM_COPY_HOST_PTR)
+        .build_with_data(queue, data).unwrap()
+}
+
+#[test]
+fn image_cmd_validation() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let image = image_array(&queue, &vec![0u32; WIDTH * HEIGHT * LAYERS]);
+
+    assert_eq!(image.region(), ImageRegion::d2_array(WIDTH, HEIGHT, LAYERS));
+
+    // Beyond the last layer:
+    let mut layer = vec![0u32; WIDTH * HEIGHT];
+    assert!(image.cmd().read(&mut layer).origin(ImageOrigin::d2_array(0, 0, LAYERS))
+        .region(ImageRegion::d2_array(WIDTH, HEIGHT, 1)).enq().is_err());
+
+    // Overlapping copy within one image:
+    assert!(image.cmd().copy(&image, ImageOrigin::d2_array(2, 0, 0))
+        .region(ImageRegion::d2_array(4, HEIGHT, 1)).enq().is_err());
+}
+
+#[test]
+fn image_cmd_layers() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let data: Vec<u32> = (0..(WIDTH * HEIGHT * LAYERS) as u32).collect();
+    let image = image_array(&queue, &data);
+
+    // Each layer alone:
+    for l in 0..LAYERS {
+        let mut layer = vec![0u32; WIDTH * HEIGHT];
+        image.cmd().read(&mut layer).origin(ImageOrigin::d2_array(0, 0, l))
+            .region(ImageRegion::d2_array(WIDTH, HEIGHT, 1)).enq().unwrap();
+        assert_eq!(&layer[..], &data[l * WIDTH * HEIGHT..(l + 1) * WIDTH * HEIGHT]);
+    }
+
+    // Copy the first half of each row of the first layer to the second
+    // half of the last layer:
+    image.cmd().copy(&image, ImageOrigin::d2_array(WIDTH / 2, 0, LAYERS - 1))
+        .region(ImageRegion::d2_array(WIDTH / 2, HEIGHT, 1)).enq().unwrap();
+
+    let mut result = vec![0u32; data.len()];
+    image.read(&mut result).enq().unwrap();
+
+    let last = (LAYERS - 1) * WIDTH * HEIGHT;
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let expected = if x >= WIDTH / 2 { data[y * WIDTH + x - WIDTH / 2] }
+                else { data[last + y * WIDTH + x] };
+            assert_eq!(result[last + y * WIDTH + x], expected);
+        }
+    }
+}
+
+#[test]
+fn buffer_rect() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let data: Vec<u32> = (0..(WIDTH * HEIGHT) as u32).collect();
+    let buffer = Buffer::builder()
+        .flags(flags::MEM_READ_WRITE | flags::MEM_COPY_HOST_PTR)
+        .dims([WIDTH * HEIGHT])
+        .build_with_data(&queue, &data).unwrap();
+
+    // The lower right quarter into the upper left of a zeroed rectangle:
+    let mut result = vec![0u32; WIDTH * HEIGHT];
+    buffer.cmd().read(&mut result)
+        .rect(ImageOrigin::d2(WIDTH / 2, HEIGHT / 2), ImageOrigin::default(),
+            ImageRegion::d2(WIDTH / 2, HEIGHT / 2), WIDTH, WIDTH * HEIGHT, WIDTH,
+            WIDTH * HEIGHT)
+        .enq().unwrap();
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let expected = if x < WIDTH / 2 && y < HEIGHT / 2 {
+                data[(y + HEIGHT / 2) * WIDTH + x + WIDTH / 2]
+            } else {
+                0
+            };
+            assert_eq!(result[y * WIDTH + x], expected);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Tests the checked conversion of info query result bytes.
+
+use std::mem;
+use util;
+use core::{self, DeviceInfo, DeviceInfoResult, PlatformInfo, PlatformInfoResult, KernelInfo,
+    InfoBytes, INFO_BYTES_INLINE_MAX};
+use standard::ProQue;
+
+#[test]
+fn bytes_into_sizes() {
+    let exact = 0x01020304u32.to_ne_bytes().to_vec();
+    assert_eq!(unsafe { util::bytes_into::<u32>(exact.clone()) }.unwrap(), 0x01020304);
+    assert_eq!(unsafe { util::bytes_to::<u32>(&exact) }.unwrap(), 0x01020304);
+
+    let short = vec![1u8, 2, 3];
+    let err = unsafe { util::bytes_into::<u32>(short) }.unwrap_err();
+    assert!(err.to_string().contains("expected: 4 bytes, actual: 3 bytes"));
+    assert!(unsafe { util::bytes_into::<u32>(Vec::new()) }.is_err());
+
+    let long = vec![0u8; 5];
+    assert!(unsafe { util::bytes_into::<u32>(long) }.is_err());
+
+    // A `size_t` result on this host is exactly `usize` wide:
+    let size = vec![0xffu8; mem::size_of::<usize>()];
+    assert_eq!(unsafe { util::bytes_into::<usize>(size) }.unwrap(), usize::max_value());
+    assert!(unsafe { util::bytes_into::<u64>(vec![0u8; 4]) }.is_err());
+}
+
+#[test]
+fn bytes_into_vec_sizes() {
+    let mut exact = Vec::new();
+    for val in &[1u32, 2, 3] {
+        exact.extend_from_slice(&val.to_ne_bytes());
+    }
+    assert_eq!(unsafe { util::bytes_into_vec::<u32>(exact.clone()) }.unwrap(), vec![1, 2, 3]);
+    assert_eq!(unsafe { util::bytes_to_vec::<u32>(&exact) }.unwrap(), vec![1, 2, 3]);
+    assert!(unsafe { util::bytes_into_vec::<u32>(Vec::new()) }.unwrap().is_empty());
+
+    let short = exact[..11].to_vec();
+    let err = unsafe { util::bytes_into_vec::<u32>(short) }.unwrap_err();
+    assert!(err.to_string().contains("expected a multiple of 4 bytes, actual: 11 bytes"));
+
+    let mut long = exact.clone();
+    long.push(0);
+    assert!(unsafe { util::bytes_into_vec::<u32>(long) }.is_err());
+
+    // Unaligned source bytes:
+    assert_eq!(unsafe { util::bytes_to_vec::<u32>(&[0u8; 9][1..]) }.unwrap(), vec![0, 0]);
+}
+
+#[test]
+fn string_from_info_bytes() {
+    // Trailing padding:
+    assert_eq!(util::string_from_info_bytes(b"Device\0".to_vec()), "Device");
+    assert_eq!(util::string_from_info_bytes(b"Device\0\0\0".to_vec()), "Device");
+    assert_eq!(util::string_from_info_bytes(b"Device".to_vec()), "Device");
+    assert_eq!(util::string_from_info_bytes(Vec::new()), "");
+    assert_eq!(util::string_from_info_bytes(vec![0]), "");
+    assert_eq!(util::string_from_info_bytes(vec![0; 16]), "");
+
+    // Embedded NULs and other control characters:
+    assert_eq!(util::string_from_info_bytes(b"cl_khr_fp64\0 cl_khr_fp16\0".to_vec()),
+        "cl_khr_fp64 cl_khr_fp16");
+    assert_eq!(util::string_from_info_bytes(b"a\x01b\x1bc\x07\0".to_vec()), "abc");
+    assert_eq!(util::string_from_info_bytes(b"line 1\r\n\tline 2\n\0".to_vec()),
+        "line 1\r\n\tline 2\n");
+
+    // Invalid UTF-8:
+    assert_eq!(util::string_from_info_bytes(vec![b'a', 0xff, b'b', 0]), "a\u{fffd}b");
+    assert_eq!(util::string_from_info_bytes(vec![0xe2, 0x82, 0]), "\u{fffd}");
+    assert_eq!(util::string_from_info_bytes("warning: \u{2018}x\u{2019}\0".as_bytes().to_vec()),
+        "warning: \u{2018}x\u{2019}");
+}
+
+#[test]
+fn info_result_from_short_bytes() {
+    match DeviceInfoResult::from_bytes(DeviceInfo::MaxWorkGroupSize, Ok(vec![1, 2])) {
+        DeviceInfoResult::Error(_) => (),
+        r => panic!("Unexpected result: {:?}", r),
+    }
+
+    match DeviceInfoResult::from_bytes(DeviceInfo::MaxWorkItemSizes, Ok(vec![0; 3])) {
+        DeviceInfoResult::Error(_) => (),
+        r => panic!("Unexpected result: {:?}", r),
+    }
+
+    match DeviceInfoResult::from_bytes(DeviceInfo::EndianLittle, Ok(1u32.to_ne_bytes().to_vec())) {
+        DeviceInfoResult::EndianLittle(little) => assert!(little),
+        r => panic!("Unexpected result: {:?}", r),
+    }
+
+    match PlatformInfoResult::from_bytes(PlatformInfo::Name, Ok(Vec::new())) {
+        PlatformInfoResult::Name(ref name) => assert_eq!(name, ""),
+        r => panic!("Unexpected result: {:?}", r),
+    }
+}
+
+#[test]
+fn info_bytes_storage() {
+    let small = InfoBytes::zeroed(4);
+    assert!(small.is_inline());
+    assert_eq!(&small[..], &[0u8; 4]);
+    assert!(InfoBytes::zeroed(0).is_inline());
+    assert!(InfoBytes::zeroed(INFO_BYTES_INLINE_MAX).is_inline());
+
+    let large = InfoBytes::zeroed(INFO_BYTES_INLINE_MAX + 1);
+    assert!(!large.is_inline());
+    assert_eq!(large.len(), INFO_BYTES_INLINE_MAX + 1);
+
+    let mut bytes = InfoBytes::zeroed(3);
+    bytes.copy_from_slice(b"abc");
+    assert_eq!(bytes.into_vec(), b"abc".to_vec());
+
+    core::set_info_bytes_inline_threshold(0);
+    assert!(!InfoBytes::zeroed(4).is_inline());
+    assert!(InfoBytes::zeroed(0).is_inline());
+    core::set_info_bytes_inline_threshold(INFO_BYTES_INLINE_MAX);
+}
+
+/// Queries device, platform, and kernel info with results stored both
+/// inline and (by forcing the fallback) on the heap.
+#[test]
+fn info_bytes_inline_and_heap_results_match() {
+    let proque = ProQue::builder()
+        .src("__kernel void add(__global float* buf, float val) {}")
+        .dims([64])
+        .build().unwrap();
+    let device = proque.queue().device().clone();
+    let platform = core::default_platform().unwrap();
+    let kernel = proque.create_kernel("add").unwrap();
+
+    let device_requests = [DeviceInfo::MaxWorkGroupSize, DeviceInfo::MaxWorkItemDimensions,
+        DeviceInfo::MaxWorkItemSizes, DeviceInfo::Name, DeviceInfo::Version,
+        DeviceInfo::Extensions, DeviceInfo::EndianLittle, DeviceInfo::HostUnifiedMemory,
+        DeviceInfo::ExecutionCapabilities, DeviceInfo::MaxComputeUnits];
+    let platform_requests = [PlatformInfo::Profile, PlatformInfo::Version, PlatformInfo::Name,
+        PlatformInfo::Vendor, PlatformInfo::Extensions];
+    let kernel_requests = [KernelInfo::FunctionName, KernelInfo::NumArgs];
+
+    let query = || {
+        let device_results: Vec<(Vec<u8>, String)> = device_requests.iter().map(|&r| {
+            (core::get_device_info_bytes(&device, r).unwrap(),
+                core::get_device_info(&device, r).to_string())
+        }).collect();
+        let platform_results: Vec<(Vec<u8>, String)> = platform_requests.iter().map(|&r| {
+            (core::get_platform_info_bytes(Some(platform.clone()), r).unwrap(),
+                core::get_platform_info(Some(platform.clone()), r).to_string())
+        }).collect();
+        let kernel_results: Vec<(Vec<u8>, String)> = kernel_requests.iter().map(|&r| {
+            (core::get_kernel_info_bytes(unsafe { kernel.core_as_ref() }, r).unwrap(),
+                core::get_kernel_info(unsafe { kernel.core_as_ref() }, r).to_string())
+        }).collect();
+        (device_results, platform_results, kernel_results)
+    };
+
+    let inline = query();
+    core::set_info_bytes_inline_threshold(0);
+    let heap = query();
+    core::set_info_bytes_inline_threshold(INFO_BYTES_INLINE_MAX);
+
+    assert!(inline == heap);
+    assert!(inline.0.iter().all(|&(ref bytes, _)| !bytes.is_empty()));
+}
@@ -0,0 +1,138 @@
+//! Tests the size handshake of info queries (querying the result size, then
+//! the result) against a simulated driver which reports inconsistent sizes.
+
+use std::cell::{Cell, RefCell};
+use std::ptr;
+use libc::{size_t, c_void};
+use cl_h::{cl_int, Status};
+use core::{self, INFO_QUERY_ATTEMPTS, INFO_RESULT_SIZE_MAX};
+use error::Result as OclResult;
+
+/// A simulated `clGet*Info` function.
+///
+/// The result is `value`. Size queries report the next entry of `sizes`
+/// (the last is repeated) and value queries report `size_ret` (or the size
+/// of the result) as the size written. Values are never written beyond the
+/// size passed, as with a driver which reports sizes inconsistently but
+/// otherwise behaves.
+struct Driver {
+    value: Vec<u8>,
+    sizes: Vec<size_t>,
+    size_ret: Option<size_t>,
+    size_queries: Cell<usize>,
+    value_queries: RefCell<Vec<size_t>>,
+}
+
+impl Driver {
+    fn new(value: &[u8]) -> Driver {
+        Driver {
+            value: value.to_vec(),
+            sizes: vec![value.len()],
+            size_ret: None,
+            size_queries: Cell::new(0),
+            value_queries: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn sizes(mut self, sizes: &[size_t]) -> Driver {
+        self.sizes = sizes.to_vec();
+        self
+    }
+
+    fn size_ret(mut self, size_ret: size_t) -> Driver {
+        self.size_ret = Some(size_ret);
+        self
+    }
+
+    fn query(&self, size: size_t, value: *mut c_void, size_ret: *mut size_t) -> cl_int {
+        if value.is_null() {
+            let idx = self.size_queries.get();
+            self.size_queries.set(idx + 1);
+            let reported = self.sizes[if idx < self.sizes.len() { idx } else { self.sizes.len() - 1 }];
+            if !size_ret.is_null() { unsafe { *size_ret = reported; } }
+            return Status::CL_SUCCESS as cl_int;
+        }
+
+        self.value_queries.borrow_mut().push(size);
+        if size < self.value.len() {
+            return Status::CL_INVALID_VALUE as cl_int;
+        }
+
+        unsafe { ptr::copy_nonoverlapping(self.value.as_ptr(), value as *mut u8,
+            self.value.len()); }
+        if !size_ret.is_null() {
+            unsafe { *size_ret = self.size_ret.unwrap_or(self.value.len()); }
+        }
+        Status::CL_SUCCESS as cl_int
+    }
+
+    fn info_bytes(&self) -> OclResult<Vec<u8>> {
+        core::get_info_bytes("clGetDeviceInfo", || "request=Simulated".to_owned(),
+            |size, value, size_ret| self.query(size, value, size_ret)).map(|b| b.into_vec())
+    }
+}
+
+#[test]
+fn consistent_sizes() {
+    let driver = Driver::new(b"OpenCL 1.2\0");
+    assert_eq!(driver.info_bytes().unwrap(), b"OpenCL 1.2\0".to_vec());
+    assert_eq!(driver.size_queries.get(), 1);
+    assert_eq!(*driver.value_queries.borrow(), vec![11]);
+
+    let driver = Driver::new(b"");
+    assert!(driver.info_bytes().unwrap().is_empty());
+    assert!(driver.value_queries.borrow().is_empty());
+}
+
+#[test]
+fn result_grew() {
+    // Reported too small once, then correctly:
+    let driver = Driver::new(b"cl_khr_fp64 cl_khr_fp16\0").sizes(&[12, 24]);
+    assert_eq!(driver.info_bytes().unwrap(), b"cl_khr_fp64 cl_khr_fp16\0".to_vec());
+    assert_eq!(*driver.value_queries.borrow(), vec![12, 24]);
+
+    // Always reported too small (but growing):
+    let driver = Driver::new(&[7u8; 100]).sizes(&[10, 20, 30, 40, 50]);
+    let err = driver.info_bytes().unwrap_err();
+    assert!(err.is_status(Status::CL_INVALID_VALUE), "{}", err);
+    assert_eq!(driver.value_queries.borrow().len(), INFO_QUERY_ATTEMPTS);
+    assert_eq!(*driver.value_queries.borrow(), vec![10, 20, 30]);
+
+    // Reported too small without growing is not retried:
+    let driver = Driver::new(&[7u8; 100]).sizes(&[10]);
+    assert!(driver.info_bytes().unwrap_err().is_status(Status::CL_INVALID_VALUE));
+    assert_eq!(*driver.value_queries.borrow(), vec![10]);
+}
+
+#[test]
+fn size_ret_inconsistent() {
+    // More written than the capacity passed:
+    let driver = Driver::new(&[1u8; 8]).size_ret(4096);
+    let err = driver.info_bytes().unwrap_err();
+    assert!(err.to_string().contains("The reported result size (4096 bytes) exceeds the size \
+        of the result buffer (8 bytes) (request=Simulated)"), "{}", err);
+
+    // Less written than the capacity passed (the result shrank):
+    let driver = Driver::new(b"abc\0").sizes(&[16]).size_ret(4);
+    assert_eq!(driver.info_bytes().unwrap(), b"abc\0".to_vec());
+    assert_eq!(*driver.value_queries.borrow(), vec![16]);
+
+    let driver = Driver::new(&[9u8; 200]).sizes(&[300]).size_ret(200);
+    assert_eq!(driver.info_bytes().unwrap(), vec![9u8; 200]);
+}
+
+#[test]
+fn size_unreasonable() {
+    for &size in &[INFO_RESULT_SIZE_MAX + 1, size_t::max_value()] {
+        let driver = Driver::new(b"x\0").sizes(&[size]);
+        let err = driver.info_bytes().unwrap_err();
+        assert!(err.to_string().contains(&format!("The reported result size ({} bytes) exceeds \
+            the maximum", size)), "{}", err);
+        assert!(driver.value_queries.borrow().is_empty());
+    }
+
+    // Growing beyond the maximum on a retry:
+    let driver = Driver::new(&[0u8; 64]).sizes(&[32, size_t::max_value()]);
+    assert!(driver.info_bytes().is_err());
+    assert_eq!(*driver.value_queries.borrow(), vec![32]);
+}
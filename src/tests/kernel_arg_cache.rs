@@ -0,0 +1,148 @@
+//! Tests that kernels see every argument update when unchanged arguments
+//! are skipped (`Kernel::cache_args`).
+
+use core::{self, KernelArg};
+use enums::{SamplerInfo, SamplerInfoResult};
+use standard::{ProQue, Buffer, Kernel, Sampler};
+
+static SRC: &'static str = r#"
+    __kernel void write_val(__global uint* buf, uint val, uint2 pair) {
+        buf[get_global_id(0)] = val + pair.x + pair.y;
+    }
+"#;
+
+const LEN: usize = 16;
+
+fn check(kernel: &Kernel, buffer: &Buffer<u32>, expected: u32) {
+    kernel.enq().unwrap();
+    let mut vec = vec![0u32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == expected), "expected: {}, actual: {:?}", expected, vec);
+}
+
+fn kernel_arg_cache(cache: bool) {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    let buffer_a = Buffer::<u32>::new(proque.queue(), None, [LEN], None).unwrap();
+    let buffer_b = Buffer::<u32>::new(proque.queue(), None, [LEN], None).unwrap();
+
+    let mut kernel = proque.create_kernel("write_val").unwrap()
+        .cache_args(cache)
+        .arg_buf_named("buf", Some(&buffer_a))
+        .arg_scl_named("val", Some(1u32))
+        .arg_vec_named("pair", Some(&[0u32, 0][..]));
+
+    check(&kernel, &buffer_a, 1);
+
+    // Alternating and repeated scalar values:
+    for &val in &[2u32, 1, 1, 2, 2, 1, 5] {
+        kernel.set_arg_scl_named("val", val).unwrap();
+        check(&kernel, &buffer_a, val);
+    }
+
+    // Vectors:
+    for &pair in &[[10u32, 0], [10, 0], [0, 10], [3, 4], [0, 10]] {
+        kernel.set_arg_vec_named("pair", &pair).unwrap();
+        check(&kernel, &buffer_a, 5 + pair[0] + pair[1]);
+    }
+
+    // Alternating buffers:
+    for (i, &use_b) in [true, false, false, true, true, false].iter().enumerate() {
+        let buffer = if use_b { &buffer_b } else { &buffer_a };
+        kernel.set_arg_scl_named("val", i as u32).unwrap();
+        kernel.set_arg_buf_named("buf", Some(buffer)).unwrap();
+        check(&kernel, buffer, i as u32 + 10);
+    }
+}
+
+#[test]
+fn kernel_arg_cache_enabled() {
+    kernel_arg_cache(true);
+}
+
+#[test]
+fn kernel_arg_cache_disabled() {
+    kernel_arg_cache(false);
+}
+
+#[test]
+fn kernel_arg_cache_bypassed() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    let buffer = Buffer::<u32>::new(proque.queue(), None, [LEN], None).unwrap();
+    let mut kernel = proque.create_kernel("write_val").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl_named("val", Some(7u32))
+        .arg_vec(&[0u32, 0]);
+
+    // Set the argument behind the cache's back:
+    core::set_kernel_arg(unsafe { kernel.core_as_ref() }, 1, KernelArg::Scalar(9u32)).unwrap();
+    check(&kernel, &buffer, 9);
+
+    // The cached value is unchanged so nothing is set:
+    kernel.set_arg_scl_named("val", 7u32).unwrap();
+    check(&kernel, &buffer, 9);
+
+    // Forcing passes the value to the driver regardless:
+    kernel.set_arg_force("val", KernelArg::Scalar(7u32)).unwrap();
+    check(&kernel, &buffer, 7);
+
+    // As does clearing the cache:
+    core::set_kernel_arg(unsafe { kernel.core_as_ref() }, 1, KernelArg::Scalar(9u32)).unwrap();
+    kernel.clear_arg_cache();
+    kernel.set_arg_scl_named("val", 7u32).unwrap();
+    check(&kernel, &buffer, 7);
+
+    // Or disabling it:
+    core::set_kernel_arg(unsafe { kernel.core_as_ref() }, 1, KernelArg::Scalar(9u32)).unwrap();
+    kernel.set_cache_args(false);
+    kernel.set_arg_scl_named("val", 7u32).unwrap();
+    check(&kernel, &buffer, 7);
+}
+
+fn sampler_ref_count(sampler: &Sampler) -> u32 {
+    match sampler.info(SamplerInfo::ReferenceCount) {
+        SamplerInfoResult::ReferenceCount(count) => count,
+        res => panic!("Unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn kernel_arg_cache_sampler() {
+    let proque = ProQue::builder()
+        .src("__kernel void smp(sampler_t s, __global uint* buf) {}")
+        .dims([LEN])
+        .build().unwrap();
+
+    let buffer = Buffer::<u32>::new(proque.queue(), None, [LEN], None).unwrap();
+    let sampler = Sampler::with_defaults(proque.context()).unwrap();
+    let ref_count = sampler_ref_count(&sampler);
+
+    // The cached sampler is retained (so that its handle can not be reused
+    // while cached) until replaced:
+    let mut kernel = proque.create_kernel("smp").unwrap()
+        .arg_smp_named("s", Some(&sampler))
+        .arg_buf(&buffer);
+    assert_eq!(sampler_ref_count(&sampler), ref_count + 1);
+
+    kernel.set_arg_force("s", KernelArg::<u8>::SamplerNull).unwrap();
+    assert_eq!(sampler_ref_count(&sampler), ref_count);
+
+    kernel.set_arg_force("s", KernelArg::<u8>::Sampler(&sampler)).unwrap();
+    assert_eq!(sampler_ref_count(&sampler), ref_count + 1);
+    drop(kernel);
+    assert_eq!(sampler_ref_count(&sampler), ref_count);
+
+    // Nothing is retained without caching:
+    let _kernel = proque.create_kernel("smp").unwrap()
+        .cache_args(false)
+        .arg_smp_named("s", Some(&sampler))
+        .arg_buf(&buffer);
+    assert_eq!(sampler_ref_count(&sampler), ref_count);
+}
@@ -0,0 +1,72 @@
+//! Tests that `Kernel::arg` and `Kernel::arg_named` accept each kind of
+//! value (`ArgVal`) and set it as the matching typed method would.
+
+use core;
+use standard::{ProQue, Buffer, Image, Sampler, Kernel, Local};
+use core::{KernelArg, ClFloat4};
+
+static SRC: &'static str = r#"
+    __kernel void args(__global float4* out, float scale, float4 offset,
+            __local float* scratch, __global float* maybe_null, int named)
+    {
+        uint i = get_global_id(0);
+        scratch[get_local_id(0)] = scale;
+        barrier(CLK_LOCAL_MEM_FENCE);
+
+        out[i] = (float4)(scratch[get_local_id(0)]) * (float)(i) + offset + (float)(named);
+
+        if (maybe_null != 0) {
+            maybe_null[i] = 1.0f;
+        }
+    }
+"#;
+
+const LEN: usize = 64;
+
+/// Only compiled, ensuring each remaining form is accepted.
+#[allow(dead_code)]
+fn accepted_forms(kernel: Kernel, image: &Image<u8>, sampler: &Sampler) -> Kernel {
+    kernel
+        .arg(image)
+        .arg(None::<&Image<u8>>)
+        .arg(Some(image))
+        .arg(sampler)
+        .arg(None::<&Sampler>)
+        .arg(Some(sampler))
+        .arg(KernelArg::Scalar(1u32))
+        .arg_named("image", image)
+}
+
+#[test]
+fn arg_forms() {
+    let pro_que = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let out = pro_que.create_buffer::<ClFloat4>().unwrap();
+    let flags = Buffer::<f32>::builder()
+        .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+        .dims([LEN])
+        .build_with_data(pro_que.queue(), &[0.0; LEN])
+        .unwrap();
+
+    let mut kernel = pro_que.create_kernel("args").unwrap()
+        .lws([16])
+        .arg(&out)
+        .arg(2.0f32)
+        .arg(ClFloat4([0.0, 0.25, 0.5, 0.75]))
+        .arg(Local::<f32>::new(16))
+        .arg_named("maybe_null", None::<&Buffer<f32>>)
+        .arg_named("named", 0i32);
+    kernel.enq().unwrap();
+
+    let vec = out.read_to_vec().unwrap();
+    assert_eq!(vec[3], ClFloat4([6.0, 6.25, 6.5, 6.75]));
+    assert!(flags.read_to_vec().unwrap().iter().all(|&v| v == 0.0));
+
+    // Named arguments set with `::arg_named` are modified as usual:
+    kernel.set_arg_buf_named("maybe_null", Some(&flags)).unwrap();
+    kernel.set_arg_scl_named("named", 10i32).unwrap();
+    kernel.enq().unwrap();
+
+    let vec = out.read_to_vec().unwrap();
+    assert_eq!(vec[3], ClFloat4([16.0, 16.25, 16.5, 16.75]));
+    assert!(flags.read_to_vec().unwrap().iter().all(|&v| v == 1.0));
+}
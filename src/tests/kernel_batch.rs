@@ -0,0 +1,110 @@
+//! Tests batched kernel launches (`KernelCmd::batch` and `Kernel::enq_batch`).
+
+use core::WorkSizes;
+use standard::{ProQue, Buffer, Event, EventList};
+
+static SRC: &'static str = r#"
+    __kernel void add_one(__global uint* buf) {
+        uint idx = get_global_id(0) + get_global_id(1) * get_global_size(0);
+        buf[idx] += 1;
+    }
+"#;
+
+const LEN: usize = 64;
+
+fn read(buffer: &Buffer<u32>) -> Vec<u32> {
+    let mut vec = vec![0u32; LEN];
+    buffer.read(&mut vec).enq().unwrap();
+    vec
+}
+
+#[test]
+fn kernel_batch() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    let buffer = Buffer::<u32>::new(proque.queue(), None, [LEN], None).unwrap();
+    let kernel = proque.create_kernel("add_one").unwrap()
+        .arg_buf(&buffer);
+
+    // Mixed dimensions: the whole buffer (1D and 2D), the second half (with
+    // an offset) and the first quarter (with a local work size):
+    let launches = [
+        WorkSizes::new(&[LEN], None, None).unwrap(),
+        WorkSizes::new(&[LEN / 4, 4], None, None).unwrap(),
+        WorkSizes::new(&[LEN / 2], None, Some(&[LEN / 2])).unwrap(),
+        WorkSizes::new(&[LEN / 4], Some(&[LEN / 16]), None).unwrap(),
+    ];
+
+    kernel.enq_batch(&launches).unwrap();
+
+    let expected: Vec<u32> = (0..LEN).map(|i| {
+        2 + (i >= LEN / 2) as u32 + (i < LEN / 4) as u32
+    }).collect();
+    assert_eq!(read(&buffer), expected);
+
+    // An event for the last launch only:
+    let mut event = Event::empty();
+    kernel.cmd().batch(&launches).enew(&mut event).enq().unwrap();
+    event.wait().unwrap();
+    assert_eq!(read(&buffer), expected.iter().map(|&v| v * 2).collect::<Vec<_>>());
+
+    // An event for each launch, waiting on a prior event:
+    let mut events = EventList::new();
+    kernel.cmd().batch(&launches).ewait(&event).enew_batch(&mut events).enq().unwrap();
+    assert_eq!(events.len(), launches.len());
+    events.wait().unwrap();
+    assert_eq!(read(&buffer), expected.iter().map(|&v| v * 3).collect::<Vec<_>>());
+
+    // An empty batch enqueues nothing:
+    kernel.enq_batch(&[]).unwrap();
+    assert_eq!(read(&buffer), expected.iter().map(|&v| v * 3).collect::<Vec<_>>());
+}
+
+#[test]
+fn kernel_batch_errors() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    let buffer = Buffer::<u32>::new(proque.queue(), None, [LEN], None).unwrap();
+    let kernel = proque.create_kernel("add_one").unwrap()
+        .arg_buf(&buffer);
+
+    // Larger than any work group the device supports:
+    let too_big = proque.queue().device_max_wg_size() * 2;
+
+    let launches = [
+        WorkSizes::new(&[LEN], None, None).unwrap(),
+        WorkSizes::new(&[LEN], None, None).unwrap(),
+        WorkSizes::new(&[too_big], Some(&[too_big]), None).unwrap(),
+    ];
+
+    // The invalid launch is identified and nothing is enqueued:
+    let err = kernel.cmd().batch(&launches).validate_dims(true).enq().unwrap_err();
+    assert!(format!("{}", err).contains("Batch launch [2] (of 3)"), "{}", err);
+    assert!(read(&buffer).iter().all(|&v| v == 0));
+
+    // Likewise for a single launch:
+    let err = kernel.cmd().gws([too_big]).lws([too_big]).validate_dims(true).enq().unwrap_err();
+    assert!(format!("{}", err).contains("exceeds the device maximum work group size"), "{}", err);
+
+    // A new event for an empty batch:
+    let mut event = Event::empty();
+    assert!(kernel.cmd().batch(&[]).enew(&mut event).enq().is_err());
+
+    // Both kinds of new event:
+    let mut events = EventList::new();
+    assert!(kernel.cmd().batch(&launches[..1]).enew(&mut event).enew_batch(&mut events)
+        .enq().is_err());
+
+    // A batch of tasks:
+    assert!(kernel.cmd().batch(&launches[..1]).task().enq().is_err());
+
+    // A list of batch events without a batch:
+    assert!(kernel.cmd().enew_batch(&mut events).enq().is_err());
+    assert!(read(&buffer).iter().all(|&v| v == 0));
+}
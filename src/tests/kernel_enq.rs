@@ -0,0 +1,69 @@
+//! Tests that `Kernel::enq` uses the kernel's stored defaults.
+
+use error::Error as OclError;
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void add_id(__global int* buffer, int addend) {
+        buffer[get_global_id(0)] += addend + (int)get_global_id(0);
+    }
+"#;
+
+const LEN: usize = 1024;
+
+/// Runs the kernel once using the defaults and once fully specified, then
+/// compares the results.
+#[test]
+fn kernel_enq_defaults() {
+    let pro_que = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let defaults = pro_que.create_buffer::<i32>().unwrap();
+    let specified = pro_que.create_buffer::<i32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add_id").unwrap()
+        .gwo([16]).lws([16])
+        .gws([LEN - 16])
+        .arg_buf(&defaults)
+        .arg_scl(5i32);
+    kernel.enq().unwrap();
+
+    let kernel = pro_que.create_kernel("add_id").unwrap()
+        .arg_buf(&specified)
+        .arg_scl(5i32);
+    kernel.cmd().queue(pro_que.queue()).gwo([16]).gws([LEN - 16]).lws([16]).enq().unwrap();
+
+    let (defaults, specified) = (defaults.read_to_vec().unwrap(),
+        specified.read_to_vec().unwrap());
+    assert_eq!(defaults, specified);
+    assert!(defaults[..16].iter().all(|&v| v == 0));
+    assert_eq!(defaults[16], 21);
+}
+
+#[test]
+fn kernel_enq_later_defaults() {
+    let pro_que = ProQue::builder().src(SRC).build().unwrap();
+    let buffer = Buffer::<i32>::new(pro_que.queue(), None, [LEN], None).unwrap();
+    let mut kernel = pro_que.create_kernel("add_id").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(0i32);
+
+    match kernel.enq() {
+        Err(OclError::DimsUnspecified { op, desc }) => {
+            assert_eq!(op, "ocl::KernelCmd::enq (global work size)");
+            assert!(desc.contains("'add_id'") && desc.contains("Kernel::set_default_gws"),
+                "{}", desc);
+        },
+        other => panic!("Expected a 'DimsUnspecified' error, found: {:?}", other),
+    }
+
+    kernel.set_default_gws([LEN / 2]);
+    kernel.enq().unwrap();
+    assert_eq!(kernel.get_gws().to_lens().unwrap(), [LEN / 2, 1, 1]);
+
+    kernel.set_default_gwo([LEN / 2]);
+    kernel.set_default_lws([8]);
+    let event = kernel.enq_event().unwrap();
+    event.wait().unwrap();
+
+    let vec = buffer.read_to_vec().unwrap();
+    assert!(vec.iter().enumerate().all(|(i, &v)| v == i as i32));
+}
@@ -0,0 +1,68 @@
+//! Tests kernel info queries through both `Kernel` and `core::get_kernel_info`.
+
+use core;
+use standard::ProQue;
+use enums::{KernelInfo, KernelInfoResult, KernelWorkGroupInfo, KernelWorkGroupInfoResult};
+
+static SRC: &'static str = r#"
+    __kernel void no_args() {}
+
+    __kernel void three_args(__global float* buf, float val, uint len) {
+        if (get_global_id(0) < len) {
+            buf[get_global_id(0)] = val;
+        }
+    }
+"#;
+
+#[test]
+fn kernel_info() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([64])
+        .build().unwrap();
+
+    for &(name, num_args) in &[("no_args", 0), ("three_args", 3)] {
+        let kernel = proque.create_kernel(name).unwrap();
+
+        assert_eq!(kernel.name(), name);
+        assert_eq!(kernel.num_args().unwrap(), num_args);
+
+        // Work group sizes recorded upon creation:
+        for device in proque.program().devices() {
+            match kernel.wg_info(device, KernelWorkGroupInfo::WorkGroupSize) {
+                KernelWorkGroupInfoResult::WorkGroupSize(size) => {
+                    assert_eq!(kernel.max_wg_size(device), Some(size))
+                },
+                res => panic!("Unexpected result: {:?}", res),
+            }
+        }
+
+        match core::get_kernel_info(unsafe { kernel.core_as_ref() }, KernelInfo::FunctionName) {
+            KernelInfoResult::FunctionName(n) => assert_eq!(n, name),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_kernel_info(unsafe { kernel.core_as_ref() }, KernelInfo::NumArgs) {
+            KernelInfoResult::NumArgs(n) => assert_eq!(n, num_args),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_kernel_info(unsafe { kernel.core_as_ref() }, KernelInfo::ReferenceCount) {
+            KernelInfoResult::ReferenceCount(count) => assert!(count > 0),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        // Retained handles (dropped at the end of each iteration):
+        match core::get_kernel_info(unsafe { kernel.core_as_ref() }, KernelInfo::Context) {
+            KernelInfoResult::Context(context) => assert_eq!(unsafe { context.as_ptr() },
+                unsafe { proque.context().core_as_ref().as_ptr() }),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_kernel_info(unsafe { kernel.core_as_ref() }, KernelInfo::Program) {
+            KernelInfoResult::Program(program) => assert_eq!(unsafe { program.as_ptr() },
+                unsafe { proque.program().core_as_ref().as_ptr() }),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+    }
+}
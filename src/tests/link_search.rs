@@ -0,0 +1,98 @@
+//! The build script's library search order (given made-up environments) and
+//! `util::link_info`.
+
+use std::collections::HashMap;
+use util;
+
+#[path = "../../build/search.rs"]
+#[allow(dead_code)]
+mod search;
+
+use self::search::{search_dirs, link_directives};
+
+fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+    vars.iter().map(|&(k, v)| (k.to_owned(), v.to_owned())).collect()
+}
+
+#[test]
+fn linux() {
+    let e = env(&[("CARGO_CFG_TARGET_OS", "linux"), ("OCL_ROOT", "/ignored")]);
+    assert!(search_dirs(&e, None).is_empty());
+    assert_eq!(search_dirs(&e, Some("/usr/lib64\n")), vec!["/usr/lib64"]);
+    assert_eq!(link_directives(&e, None), vec!["cargo:rustc-link-lib=OpenCL"]);
+
+    // `OPENCL_LIB_DIR` precedes the pkg-config hint:
+    let e = env(&[("CARGO_CFG_TARGET_OS", "linux"), ("OPENCL_LIB_DIR", "/opt/cl/lib")]);
+    assert_eq!(search_dirs(&e, Some("/usr/lib64")), vec!["/opt/cl/lib", "/usr/lib64"]);
+    assert_eq!(search_dirs(&e, Some("/opt/cl/lib")), vec!["/opt/cl/lib"]);
+    assert_eq!(link_directives(&e, None), vec!["cargo:rustc-link-search=native=/opt/cl/lib",
+        "cargo:rustc-link-lib=OpenCL"]);
+}
+
+#[test]
+fn macos() {
+    let e = env(&[("CARGO_CFG_TARGET_OS", "macos")]);
+    assert!(search_dirs(&e, Some("/usr/local/lib")).is_empty());
+    assert_eq!(link_directives(&e, None), vec!["cargo:rustc-link-lib=framework=OpenCL"]);
+
+    let e = env(&[("CARGO_CFG_TARGET_OS", "macos"), ("OPENCL_LIB_DIR", "/Library/Frameworks")]);
+    assert_eq!(link_directives(&e, None), vec!["cargo:rustc-link-search=native=/Library/Frameworks",
+        "cargo:rustc-link-lib=framework=OpenCL"]);
+}
+
+#[test]
+fn windows() {
+    let e = env(&[
+        ("CARGO_CFG_TARGET_OS", "windows"),
+        ("CARGO_CFG_TARGET_ARCH", "x86_64"),
+        ("INTELOCLSDKROOT", "C:/Intel"),
+        ("AMDAPPSDKROOT", "C:/AMD"),
+        ("CUDA_LIB_PATH", "C:/CudaLib"),
+        ("CUDA_PATH_V9_2", "C:/CUDA/v9.2"),
+        ("CUDA_PATH_V11_2", "C:/CUDA/v11.2"),
+        ("CUDA_PATH", "C:/CUDA/v11.2"),
+        ("OCL_ROOT", "C:/OCL_SDK_Light"),
+        ("OPENCL_LIB_DIR", "C:/OpenCL/lib"),
+    ]);
+
+    assert_eq!(search_dirs(&e, Some("/ignored")), vec![
+        "C:/OpenCL/lib",
+        "C:/OCL_SDK_Light/lib/x86_64",
+        "C:/CUDA/v11.2/lib/x64",
+        "C:/CUDA/v9.2/lib/x64",
+        "C:/CudaLib",
+        "C:/AMD/lib/x86_64",
+        "C:/Intel/lib/x64",
+    ]);
+
+    let e = env(&[("CARGO_CFG_TARGET_OS", "windows"), ("CARGO_CFG_TARGET_ARCH", "x86"),
+        ("OCL_ROOT", "C:/OCL_SDK_Light"), ("CUDA_PATH_V10_0", "C:/CUDA/v10.0")]);
+    assert_eq!(search_dirs(&e, None), vec!["C:/OCL_SDK_Light/lib/x86", "C:/CUDA/v10.0/lib/Win32"]);
+
+    // No SDK found:
+    let e = env(&[("CARGO_CFG_TARGET_OS", "windows"), ("OCL_ROOT", "")]);
+    let lines = link_directives(&e, None);
+    assert!(lines[0].starts_with("cargo:warning="));
+    assert_eq!(lines[1], "cargo:rustc-link-lib=OpenCL");
+}
+
+#[test]
+fn dynamic_loading() {
+    let e = env(&[("CARGO_CFG_TARGET_OS", "linux"), ("OPENCL_LIB_DIR", "/opt/cl/lib"),
+        ("CARGO_FEATURE_DYNAMIC_LOADING", "1")]);
+    assert!(link_directives(&e, Some("/usr/lib64")).is_empty());
+}
+
+#[test]
+fn stub() {
+    let e = env(&[("CARGO_CFG_TARGET_OS", "macos"), ("CARGO_FEATURE_STUB", "1")]);
+    assert!(link_directives(&e, None).is_empty());
+}
+
+#[test]
+fn link_info() {
+    let info = util::link_info();
+    println!("{}", info);
+    assert!(info.starts_with("OpenCL library: "));
+    assert!(info.contains("\nLibrary path: "));
+}
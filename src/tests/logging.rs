@@ -0,0 +1,139 @@
+//! Tests the records logged for enqueued commands and program builds with
+//! the `logging` feature, using a logger which captures them.
+
+use std::ffi::CString;
+use std::sync::{Mutex, Once};
+use std::thread::{self, ThreadId};
+use log::{self, Log, Metadata, Record, Level, LevelFilter};
+use core;
+use standard::{Context, Buffer, Event};
+
+/// A captured record.
+struct Captured {
+    thread: ThreadId,
+    target: String,
+    level: Level,
+    message: String,
+}
+
+struct CapturingLogger {
+    records: Mutex<Vec<Captured>>,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.records.lock().unwrap().push(Captured {
+            thread: thread::current().id(),
+            target: record.target().to_owned(),
+            level: record.level(),
+            message: record.args().to_string(),
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger { records: Mutex::new(Vec::new()) };
+
+/// Held while capturing, as the maximum level is changed by some tests.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+/// Installs the capturing logger (once per process) and returns the records
+/// logged by the current thread while running `f`.
+fn capture<F: FnOnce()>(f: F) -> Vec<Captured> {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+    });
+    let _serial = SERIAL.lock().unwrap_or_else(|err| err.into_inner());
+
+    let thread = thread::current().id();
+    LOGGER.records.lock().unwrap().retain(|r| r.thread != thread);
+    f();
+    let mut records = LOGGER.records.lock().unwrap();
+    let (mine, others) = records.drain(..).partition(|r| r.thread == thread);
+    *records = others;
+    mine
+}
+
+/// Returns the value of the `key=value` field named `key` in `message`.
+fn field<'m>(message: &'m str, key: &str) -> Option<&'m str> {
+    let prefix = format!("{}=", key);
+    message.split(' ').find(|f| f.starts_with(&prefix)).map(|f| &f[prefix.len()..])
+}
+
+#[test]
+fn enqueue_record() {
+    let context = Context::builder().build().unwrap();
+    let queue = core::create_command_queue(&context, &context.devices()[0]).unwrap();
+    let buffer = Buffer::<u32>::builder()
+        .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+        .dims([64])
+        .build_in_context_with_data(&context, &[0; 64])
+        .unwrap();
+
+    let mut data = vec![0u32; 16];
+    let mut event = Event::empty();
+    let records = capture(|| {
+        unsafe { core::enqueue_read_buffer(&queue, &buffer, true, 8, &mut data, None,
+            Some(&mut event)).ok(); }
+    });
+
+    let records: Vec<_> = records.iter().filter(|r| r.target == "ocl::core::enqueue").collect();
+    assert_eq!(records.len(), 1);
+    let record = records[0];
+    assert_eq!(record.level, Level::Trace);
+
+    let msg = &record.message;
+    assert!(msg.starts_with("clEnqueueReadBuffer "), "{}", msg);
+    assert_eq!(field(msg, "queue"), Some(&format!("{:?}", unsafe { queue.as_ptr() })[..]));
+    assert_eq!(field(msg, "device"),
+        Some(&format!("{:?}", unsafe { context.devices()[0].as_ptr() })[..]));
+    assert_eq!(field(msg, "block"), Some("true"));
+    assert_eq!(field(msg, "offset"), Some("32"));
+    assert_eq!(field(msg, "size"), Some("64"));
+    assert_eq!(field(msg, "wait_list_len"), Some("0"));
+    assert!(field(msg, "event").is_some(), "{}", msg);
+    assert!(field(msg, "status").is_some(), "{}", msg);
+}
+
+#[test]
+fn build_record() {
+    let context = Context::builder().build().unwrap();
+    let src = CString::new("__kernel void nop() {}").unwrap();
+    let program = core::create_program_with_source(&context, &[src]).unwrap();
+    let options = CString::new("-cl-fast-relaxed-math").unwrap();
+
+    let records = capture(|| {
+        core::build_program(&program, context.devices(), &options, None, None).ok();
+    });
+
+    let records: Vec<_> = records.iter().filter(|r| r.target == "ocl::core::build").collect();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].level, Level::Debug);
+
+    let msg = &records[0].message;
+    assert!(msg.starts_with("clBuildProgram "), "{}", msg);
+    assert_eq!(field(msg, "program"), Some(&format!("{:?}", unsafe { program.as_ptr() })[..]));
+    assert_eq!(field(msg, "device_count"), Some("1"));
+    assert_eq!(field(msg, "options"), Some("\"-cl-fast-relaxed-math\""));
+    assert!(field(msg, "duration").is_some() && field(msg, "log_len").is_some(), "{}", msg);
+}
+
+#[test]
+fn disabled_level() {
+    let context = Context::builder().build().unwrap();
+    let queue = core::create_command_queue(&context, &context.devices()[0]).unwrap();
+
+    let records = capture(|| {
+        log::set_max_level(LevelFilter::Debug);
+        core::enqueue_barrier(&queue).ok();
+        log::set_max_level(LevelFilter::Trace);
+    });
+    assert!(records.iter().all(|r| r.target != "ocl::core::enqueue"));
+}
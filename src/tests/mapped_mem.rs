@@ -0,0 +1,125 @@
+//! Tests `Buffer::map`, `Image::map`, and `MappedMem`.
+
+use flags;
+use standard::{Context, Queue, Buffer, Image, Event, MappedMem};
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+const WIDTH: usize = 16;
+const HEIGHT: usize = 8;
+const PIXEL_LEN: usize = 4;
+
+#[test]
+fn invalid_regions() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let buffer = Buffer::<f32>::builder().dims([64]).uninit().build(&queue).unwrap();
+
+    // Each is refused before calling into the library:
+    for &(offset, len) in [(0, Some(0)), (64, None), (0, Some(65)), (60, Some(8)),
+        (::std::usize::MAX, Some(2))].iter()
+    {
+        let err = buffer.map(flags::MAP_READ, offset, len, None).unwrap_err();
+        assert!(err.to_string().starts_with("ocl::Buffer::map:"), "{}", err);
+    }
+}
+
+#[test]
+fn map_buffer() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let data: Vec<u32> = (0..64).collect();
+    let buffer = Buffer::builder()
+        .flags(flags::MEM_READ_WRITE | flags::MEM_COPY_HOST_PTR)
+        .dims([64])
+        .build_with_data(&queue, &data).unwrap();
+
+    // Waits on a prior command before mapping:
+    let sevens = vec![7u32; 8];
+    let mut write_event = Event::empty();
+    buffer.cmd().write(&sevens).offset(8).block(false).enew(&mut write_event)
+        .enq().unwrap();
+
+    let mut mapped = buffer.map(flags::MAP_READ | flags::MAP_WRITE, 8, Some(16),
+        Some(&write_event)).unwrap();
+    mapped.wait().unwrap();
+    assert_eq!(mapped.len(), 16);
+    assert_eq!(mapped.row_pitch(), 0);
+    assert_eq!(&mapped[..8], &[7u32; 8]);
+    assert_eq!(&mapped[8..], &data[16..24]);
+
+    for val in mapped.iter_mut() { *val = 0; }
+    mapped.unmap().unwrap().wait().unwrap();
+
+    let mut expected = data.clone();
+    for val in expected[8..24].iter_mut() { *val = 0; }
+    assert_eq!(buffer.read_to_vec().unwrap(), expected);
+
+    // Unmapped when dropped:
+    {
+        let mut mapped = buffer.map(flags::MAP_WRITE, 60, None, None).unwrap();
+        assert_eq!(mapped.len(), 4);
+        for val in mapped.iter_mut() { *val = 1; }
+    }
+
+    for val in expected[60..].iter_mut() { *val = 1; }
+    assert_eq!(buffer.read_to_vec().unwrap(), expected);
+}
+
+#[test]
+fn map_image() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+
+    let data: Vec<u8> = (0..WIDTH * HEIGHT * PIXEL_LEN).map(|i| i as u8).collect();
+    let image = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnsignedInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims([WIDTH, HEIGHT])
+        .flags(flags::MEM_READ_WRITE | flags::MEM_COPY_HOST_PTR)
+        .build_with_data(&queue, &data).unwrap();
+
+    assert!(image.map(flags::MAP_READ, [12, 0, 0], [5, 1, 1], None).is_err());
+    assert!(image.map(flags::MAP_READ, [0, 0, 0], [0, 1, 1], None).is_err());
+
+    // Empty regions are refused by the guard itself, too:
+    for &region in &[[0, 1, 1], [1, 0, 1], [1, 1, 0]] {
+        let err = MappedMem::<u8>::map_image(queue.clone(), image.core_as_ref(),
+            flags::MAP_READ, [0, 0, 0], region, PIXEL_LEN, false, None).unwrap_err();
+        assert!(err.to_string().starts_with("ocl::Image::map:"), "{}", err);
+    }
+
+    let (origin, region) = ([3, 2, 0], [5, 4, 1]);
+    {
+        let mut mapped = image.map(flags::MAP_READ | flags::MAP_WRITE, origin, region, None)
+            .unwrap();
+        let row_pitch = mapped.row_pitch();
+        assert!(row_pitch >= region[0] * PIXEL_LEN);
+        assert_eq!(mapped.len(), row_pitch * (region[1] - 1) + region[0] * PIXEL_LEN);
+
+        for y in 0..region[1] {
+            for x in 0..region[0] {
+                for c in 0..PIXEL_LEN {
+                    let idx = y * row_pitch + x * PIXEL_LEN + c;
+                    let src_idx = ((origin[1] + y) * WIDTH + origin[0] + x) * PIXEL_LEN + c;
+                    assert_eq!(mapped[idx], data[src_idx]);
+                    mapped[idx] = 0;
+                }
+            }
+        }
+    }
+
+    let mut vec = vec![0u8; data.len()];
+    image.read(&mut vec).enq().unwrap();
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let inner = x >= origin[0] && x < origin[0] + region[0] &&
+                y >= origin[1] && y < origin[1] + region[1];
+            for c in 0..PIXEL_LEN {
+                let idx = (y * WIDTH + x) * PIXEL_LEN + c;
+                assert_eq!(vec[idx], if inner { 0 } else { data[idx] }, "pixel ({}, {})", x, y);
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Tests the ordering of marker and barrier commands enqueued through the
+//! version-dispatching compat functions, using both the OpenCL 1.2 and the
+//! emulated 1.1 paths.
+
+use core::{self, CommandExecutionStatus};
+use standard::{ProQue, Buffer, Event};
+
+static SRC: &'static str = r#"
+    __kernel void set(__global uint* buf, uint val) {
+        buf[get_global_id(0)] = val;
+    }
+"#;
+
+const LEN: usize = 64;
+
+#[test]
+fn marker_compat() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    for &version in &[(1, 1), proque.queue().platform_version()] {
+        let gate = core::create_user_event(proque.context().core_as_ref()).unwrap();
+        let mut marker = Event::empty();
+
+        core::enqueue_marker_compat(proque.queue(), Some(&gate), Some(&mut marker),
+            version).unwrap();
+        assert!(marker.status().unwrap() != CommandExecutionStatus::Complete,
+            "version: {:?}", version);
+
+        core::set_user_event_status(&gate, CommandExecutionStatus::Complete).unwrap();
+        marker.wait().unwrap();
+        assert_eq!(marker.status().unwrap(), CommandExecutionStatus::Complete);
+
+        // Without a wait list or new event:
+        core::enqueue_marker_compat(proque.queue(), None, None, version).unwrap();
+        proque.queue().finish().unwrap();
+    }
+}
+
+#[test]
+fn barrier_compat() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    let buffer = Buffer::<u32>::new(proque.queue(), None, [LEN], None).unwrap();
+    let mut kernel = proque.create_kernel("set").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl_named("val", Some(0u32));
+
+    for (i, &version) in [(1, 1), proque.queue().platform_version()].iter().enumerate() {
+        let val = i as u32 + 1;
+        kernel.set_arg_scl_named("val", val).unwrap();
+
+        let gate = core::create_user_event(proque.context().core_as_ref()).unwrap();
+        let mut barrier = Event::empty();
+        let mut kernel_event = Event::empty();
+
+        core::enqueue_barrier_compat(proque.queue(), Some(&gate), Some(&mut barrier),
+            version).unwrap();
+        kernel.cmd().enew(&mut kernel_event).enq().unwrap();
+        core::flush(proque.queue()).unwrap();
+
+        assert!(barrier.status().unwrap() != CommandExecutionStatus::Complete,
+            "version: {:?}", version);
+        assert!(kernel_event.status().unwrap() != CommandExecutionStatus::Complete,
+            "version: {:?}", version);
+
+        core::set_user_event_status(&gate, CommandExecutionStatus::Complete).unwrap();
+        kernel_event.wait().unwrap();
+        assert_eq!(barrier.status().unwrap(), CommandExecutionStatus::Complete);
+
+        let mut vec = vec![0u32; LEN];
+        buffer.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&v| v == val));
+
+        // Through the queue, without a wait list:
+        proque.queue().enqueue_barrier(None, None).unwrap();
+        proque.queue().enqueue_marker(None, Some(&mut barrier)).unwrap();
+        barrier.wait().unwrap();
+    }
+}
@@ -0,0 +1,82 @@
+//! Tests the `Debug` output of buffers, images, and memory objects.
+
+use core::{self, ClFloat4, Mem};
+use standard::{Buffer, Image, TransferMode};
+use util;
+use testing;
+
+#[test]
+fn short_type_name() {
+    assert_eq!(util::short_type_name::<f32>(), "f32");
+    assert_eq!(util::short_type_name::<ClFloat4>(), "ClFloat4");
+    assert_eq!(util::short_type_name::<Vec<Mem>>(), "Vec<Mem>");
+    assert_eq!(util::short_type_name::<(u8, [ClFloat4; 4])>(), "(u8, [ClFloat4; 4])");
+    assert_eq!(util::short_type_name::<Option<&::std::sync::Arc<str>>>(), "Option<&Arc<str>>");
+}
+
+#[test]
+fn buffer_debug() {
+    testing::for_each_device(|queue| {
+        let buffer = try!(Buffer::<f32>::builder()
+            .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+            .dims([1024])
+            .build_with_data(queue, &[0.0; 1024]));
+        let debug = format!("{:?}", buffer);
+        assert!(debug.starts_with("Buffer { type: f32, len: 1024, size_bytes: 4096, dims: "),
+            "{}", debug);
+        assert!(debug.contains("flags: MEM_READ_WRITE | MEM_COPY_HOST_PTR"), "{}", debug);
+        assert!(debug.contains("transfer_mode: Auto"), "{}", debug);
+        assert!(debug.contains(", mem: Mem(0x"), "{}", debug);
+        assert!(!debug.contains("map_count"), "{}", debug);
+
+        let vectors = try!(Buffer::<ClFloat4>::builder()
+            .flags(core::MEM_READ_ONLY | core::MEM_ALLOC_HOST_PTR | core::MEM_COPY_HOST_PTR)
+            .dims([4, 4])
+            .transfer_mode(TransferMode::Map)
+            .build_with_data(queue, &[ClFloat4::default(); 16]));
+        let debug = format!("{:?}", vectors);
+        assert!(debug.starts_with("Buffer { type: ClFloat4, len: 16, size_bytes: 256, "),
+            "{}", debug);
+        assert!(debug.contains("MEM_READ_ONLY") && debug.contains("MEM_ALLOC_HOST_PTR"),
+            "{}", debug);
+        assert!(debug.contains("transfer_mode: Map"), "{}", debug);
+
+        // Queried info:
+        let alternate = format!("{:#?}", buffer);
+        assert!(alternate.contains("map_count: 0,"), "{}", alternate);
+        assert!(alternate.contains("size: 4096,"), "{}", alternate);
+        assert!(alternate.contains("type: Buffer,"), "{}", alternate);
+        assert!(alternate.contains("associated_mem_object: None,"), "{}", alternate);
+
+        // A clone outlives the original:
+        let original = format!("{:?}", buffer);
+        let clone = buffer.clone();
+        drop(buffer);
+        assert_eq!(format!("{:?}", clone), original);
+        assert!(format!("{:#?}", clone).contains("reference_count: 1,"));
+        Ok(())
+    });
+}
+
+#[test]
+fn image_debug() {
+    testing::for_each_device(|queue| {
+        if !queue.device_limits().image_support() { return Ok(()); }
+
+        let image = try!(Image::<u8>::builder()
+            .dims([64, 32])
+            .flags(core::MEM_READ_ONLY)
+            .build(queue));
+        let debug = format!("{:?}", image);
+        assert!(debug.starts_with("Image { type: u8, image_type: Image2d, dims: "), "{}", debug);
+        assert!(debug.contains("format: ImageFormat { channel_order: Rgba, channel_data_type: \
+            UnormInt8 }"), "{}", debug);
+        assert!(debug.contains("flags: MEM_READ_ONLY, pixel_element_len: 4, mem: Mem(0x"),
+            "{}", debug);
+
+        let alternate = format!("{:#?}", image);
+        assert!(alternate.contains("type: Image2d,"), "{}", alternate);
+        assert!(alternate.contains("map_count: 0,"), "{}", alternate);
+        Ok(())
+    });
+}
@@ -0,0 +1,84 @@
+//! Tests memory object info queries (`core::get_mem_object_info`).
+
+use core::{self, BufferRegion};
+use flags;
+use util;
+use standard::{ProQue, Buffer, Image};
+use enums::{MemInfo, MemInfoResult, DeviceInfo, MemObjectType, ImageChannelOrder,
+    ImageChannelDataType};
+
+const LEN: usize = 1 << 12;
+
+#[test]
+fn mem_info() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([LEN])
+        .build().unwrap();
+
+    let buffer = Buffer::<f32>::new(proque.queue(), Some(flags::MEM_READ_WRITE),
+        [LEN], None).unwrap();
+
+    // Size and flags:
+    assert_eq!(buffer.size_bytes().unwrap(), LEN * 4);
+    assert!(buffer.flags().unwrap().contains(flags::MEM_READ_WRITE));
+
+    match core::get_mem_object_info(buffer.core_as_ref(), MemInfo::Type) {
+        MemInfoResult::Type(ty) => assert_eq!(ty, MemObjectType::Buffer),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    match core::get_mem_object_info(buffer.core_as_ref(), MemInfo::AssociatedMemObject) {
+        MemInfoResult::AssociatedMemObject(parent) => assert!(parent.is_none()),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    // Context handles are retained (dropping them must not invalidate the
+    // context):
+    for _ in 0..3 {
+        match core::get_mem_object_info(buffer.core_as_ref(), MemInfo::Context) {
+            MemInfoResult::Context(context) => {
+                assert_eq!(unsafe { context.as_ptr() },
+                    unsafe { proque.context().core_as_ref().as_ptr() });
+            },
+            res => panic!("Unexpected result: {:?}", res),
+        }
+    }
+
+    // Sub-buffer offset and parent:
+    let align_bytes = unsafe { util::bytes_into::<u32>(core::get_device_info_bytes(
+        proque.queue().device(), DeviceInfo::MemBaseAddrAlign).unwrap()).unwrap() } as usize / 8;
+    let region = BufferRegion { origin: align_bytes, size: align_bytes };
+    let sub_buffer = core::create_sub_buffer(buffer.core_as_ref(), flags::MEM_READ_WRITE,
+        &region).unwrap();
+
+    match core::get_mem_object_info(&sub_buffer, MemInfo::Offset) {
+        MemInfoResult::Offset(offset) => assert_eq!(offset, align_bytes),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    match core::get_mem_object_info(&sub_buffer, MemInfo::Size) {
+        MemInfoResult::Size(size) => assert_eq!(size, align_bytes),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    match core::get_mem_object_info(&sub_buffer, MemInfo::AssociatedMemObject) {
+        MemInfoResult::AssociatedMemObject(Some(parent)) => {
+            assert_eq!(unsafe { parent.as_ptr() }, unsafe { buffer.core_as_ref().as_ptr() });
+        },
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    // Images:
+    let image = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnsignedInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims((16, 16))
+        .build(proque.queue()).unwrap();
+
+    match core::get_mem_object_info(image.core_as_ref(), MemInfo::Type) {
+        MemInfoResult::Type(ty) => assert_eq!(ty, MemObjectType::Image2d),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+}
@@ -7,18 +7,100 @@
 //!
 //!
 
+pub mod align;
+pub mod benchmark;
 pub mod build_error;
+pub mod callbacks;
+pub mod buffer_chunked;
 pub mod buffer_copy;
 pub mod buffer_ops_rect;
+pub mod buffer_rect;
+pub mod buffer_read_write;
+pub mod buffer_view;
+pub mod image_desc;
+pub mod image_info;
+pub mod image_map;
 pub mod image_ops;
+pub mod image_region;
+pub mod info_bytes;
+pub mod info_handshake;
 pub mod buffer_fill;
+pub mod buffer_init;
+pub mod cl_bytes;
 pub mod clear_completed;
+pub mod device_limits;
+pub mod diagnostics;
+pub mod device_validation;
+pub mod display;
 pub mod concurrent; 
+pub mod default_queue;
+pub mod multi_device;
+pub mod dims_unspecified;
+pub mod double_buffer;
+#[cfg(feature = "dynamic-loading")] pub mod dynamic_loading;
+pub mod endian;
+pub mod enqueue_task;
+pub mod error_status;
+pub mod error_traits;
+pub mod event_info;
+pub mod extension_fn;
+pub mod fallible;
+pub mod fixtures;
+pub mod fp64;
+pub mod graph;
+#[cfg(feature = "f16")] pub mod half_float;
+#[cfg(feature = "complex")] pub mod complex;
+#[cfg(feature = "ndarray")] pub mod array_interop;
+#[cfg(feature = "serde")] pub mod serde_types;
+#[cfg(feature = "logging")] pub mod logging;
+pub mod kernel_arg_cache;
+pub mod kernel_arg_val;
 pub mod kernel_arg_ptr;
+pub mod kernel_batch;
+pub mod kernel_enq;
+pub mod kernel_info;
+pub mod link_search;
+pub mod mapped_mem;
+pub mod marker_barrier;
+pub mod mem_debug;
+pub mod mem_info;
+pub mod native_kernel;
+pub mod retain_in_flight;
+pub mod retain_release;
+pub mod retry;
+pub mod sampler_info;
+pub mod scope;
+pub mod selection;
+pub mod shared_kernel;
+pub mod pixel_bytes;
+pub mod pro_que_launch;
+pub mod program_binaries;
+pub mod program_build_info;
+pub mod program_defines;
+pub mod queue_info;
+pub mod raw_handles;
+pub mod report;
+pub mod spatial_dims;
+pub mod staging_pool;
+pub mod sub_buffer;
+#[cfg(feature = "stub")] pub mod stub;
+pub mod testing;
+pub mod thread_safety;
+pub mod timeline;
+pub mod transfer_mode;
+pub mod unload_compiler;
+pub mod user_prm;
+pub mod vec_cast;
+pub mod vector_types;
+pub mod weak_handles;
+pub mod wait_list_ptrs;
+pub mod wait_for_events;
+pub mod wait_timeout;
+pub mod work_sizes;
 
 use rand::{self, Rng};
 use error::{Error as OclError, Result as OclResult};
-use core::OclPrm;
+use core::OclScl;
 
 const PRINT_ITERS_MAX: i32 = 3;
 const PRINT_SLICES_MAX: usize = 16;
@@ -50,7 +132,7 @@ fn within_region(coords: [usize; 3], region_ofs: [usize; 3], region_size: [usize
     within
 }
 
-fn verify_vec_rect<T: OclPrm>(origin: [usize; 3], region: [usize; 3], in_region_val: T, 
+fn verify_vec_rect<T: OclScl>(origin: [usize; 3], region: [usize; 3], in_region_val: T, 
             out_region_val: T, vec_dims: [usize; 3], ele_per_coord: usize, vec: &[T], 
             ttl_runs: i32, print: bool) -> OclResult<()>
 {
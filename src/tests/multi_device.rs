@@ -0,0 +1,75 @@
+//! Tests dispatching one job across several devices with
+//! `multi::run_partitioned`.
+
+use std::ops::Range;
+use standard::{Platform, Device, Queue, Buffer, Program, Kernel, Event, partition};
+use multi;
+
+static SRC: &'static str = r#"
+    __kernel void double_it(__global int* buffer, uint offset) {
+        uint idx = get_global_id(0);
+        buffer[idx] = buffer[idx] * 2 + (int)(offset + idx) % 3;
+    }
+"#;
+
+const LEN: usize = 1 << 16 | 7;
+
+fn all_devices() -> Vec<Device> {
+    Platform::list().unwrap().iter().flat_map(|p| Device::list_all(p).unwrap()).collect()
+}
+
+fn sequential(data: &[i32]) -> Vec<i32> {
+    data.iter().enumerate().map(|(i, &v)| v * 2 + (i % 3) as i32).collect()
+}
+
+fn run(devices: &[Device], data: &[i32]) -> Vec<i32> {
+    multi::run_partitioned(devices, data, |queue, buffer, range: Range<usize>| {
+        assert_eq!(buffer.len(), range.end - range.start);
+        let program = try!(Program::builder().src(SRC).build_for_queue(queue));
+        let kernel = try!(Kernel::new("double_it", &program, queue))
+            .gws([buffer.len()])
+            .arg_buf(buffer)
+            .arg_scl(range.start as u32);
+        let mut event = Event::empty();
+        try!(kernel.cmd().enew(&mut event).enq());
+        Ok(event)
+    }).unwrap()
+}
+
+fn no_work(_: &Queue, _: &Buffer<i32>, _: Range<usize>) -> ::Result<Event> {
+    panic!("Nothing to run.");
+}
+
+#[test]
+fn partition_ranges() {
+    assert_eq!(partition(&[4, 1, 3], 80), vec![0..40, 40..50, 50..80]);
+    assert_eq!(partition(&[1, 1, 1], 7), vec![0..2, 2..4, 4..7]);
+    assert_eq!(partition(&[8], 5), vec![0..5]);
+
+    // Zero weights count as one:
+    assert_eq!(partition(&[0, 0], 10), vec![0..5, 5..10]);
+
+    // Fewer elements than partitions:
+    assert_eq!(partition(&[1, 1, 1, 1], 2), vec![0..0, 0..1, 1..1, 1..2]);
+}
+
+#[test]
+fn empty_input() {
+    let data = [1i32; 4];
+    assert!(multi::run_partitioned(&[], &data, no_work).is_err());
+    assert!(multi::run_partitioned(&all_devices(), &[], no_work).is_err());
+}
+
+#[test]
+fn doubling() {
+    let data: Vec<i32> = (0..LEN as i32).map(|i| i - 500).collect();
+    let expected = sequential(&data);
+    let devices = all_devices();
+
+    assert_eq!(run(&devices, &data), expected);
+    assert_eq!(run(&devices[..1], &data), expected);
+
+    // Shorter than the number of partitions:
+    let devices: Vec<Device> = devices.iter().cycle().take(4).cloned().collect();
+    assert_eq!(run(&devices, &data[..3]), expected[..3].to_vec());
+}
@@ -0,0 +1,56 @@
+//! Tests enqueuing host closures with `Queue::enqueue_native` on each
+//! device.
+//!
+//! Only devices supporting native kernels (usually CPU devices) run them,
+//! the others must return an error.
+
+use core;
+use standard::{Queue, Buffer, Event};
+use error::Result as OclResult;
+use testing;
+
+const LEN: usize = 256;
+
+fn supports_native(queue: &Queue) -> OclResult<bool> {
+    queue.device().execution_capabilities().map(|caps| caps.contains(core::EXEC_NATIVE_KERNEL))
+}
+
+#[test]
+fn native_kernel_mutate_buffer() {
+    testing::for_each_device(|queue| {
+        if !try!(supports_native(queue)) { return Ok(()); }
+
+        let src: Vec<u32> = (0..LEN as u32).collect();
+        let buffer = try!(Buffer::<u32>::new(queue,
+            Some(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR), [LEN], Some(&src)));
+
+        let mut event = Event::empty();
+        try!(queue.enqueue_native(&[buffer.core_as_ref()], None, Some(&mut event), |mems| {
+            assert_eq!(mems.len(), 1);
+            assert_eq!(mems[0].len(), LEN * 4);
+            for chunk in mems[0].chunks_mut(4) {
+                let mut val = [0u8; 4];
+                val.copy_from_slice(chunk);
+                chunk.copy_from_slice(&(u32::from_ne_bytes(val) * 2).to_ne_bytes());
+            }
+        }));
+
+        let mut vec = vec![0u32; LEN];
+        try!(buffer.cmd().read(&mut vec).ewait(&event).enq());
+        assert!(vec.iter().enumerate().all(|(i, &v)| v == i as u32 * 2));
+
+        // A panicking closure must not bring down the process:
+        try!(queue.enqueue_native(&[], None, None, |_| panic!("native kernel panic")));
+        queue.finish()
+    });
+}
+
+#[test]
+fn native_kernel_unsupported() {
+    testing::for_each_device(|queue| {
+        if !try!(supports_native(queue)) {
+            assert!(queue.enqueue_native(&[], None, None, |_| ()).is_err());
+        }
+        Ok(())
+    });
+}
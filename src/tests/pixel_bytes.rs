@@ -0,0 +1,22 @@
+//! Checks the `ImageFormat::pixel_bytes` table against the element size
+//! reported by each device for every format it supports (formats unknown to
+//! the table are skipped).
+
+use core::ImageFormat;
+use testing;
+
+#[test]
+fn pixel_bytes_match_element_size() {
+    testing::for_each_device(|queue| {
+        if !queue.device_limits().image_support() {
+            println!("{}: Images not supported. Skipping.", queue.device().name());
+            return Ok(());
+        }
+
+        let mismatches = try!(ImageFormat::verify_pixel_bytes(queue.context_core_as_ref()));
+        let list: Vec<String> = mismatches.iter().map(|m| m.to_string()).collect();
+        assert!(mismatches.is_empty(), "{}: 'ImageFormat::pixel_bytes' disagrees with the \
+            implementation for:\n{}", queue.device().name(), list.join("\n"));
+        Ok(())
+    });
+}
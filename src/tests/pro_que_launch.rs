@@ -0,0 +1,82 @@
+//! Tests the work group limit and launch size helpers of `ProQue`.
+
+use standard::{ProQue, Buffer, SpatialDims};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buf, uint len) {
+        uint idx = get_global_id(0);
+        if (idx < len) {
+            buf[idx] += 1.0f;
+        }
+    }
+
+    __kernel void heavy(__global float* buf, uint len) {
+        __local float shared[1024];
+        float priv[64];
+        uint idx = get_global_id(0);
+        uint lid = get_local_id(0);
+
+        for (uint i = 0; i < 64; i++) {
+            priv[i] = (float)(idx + i);
+        }
+
+        shared[lid % 1024] = priv[idx % 64];
+        barrier(CLK_LOCAL_MEM_FENCE);
+
+        if (idx < len) {
+            float sum = 0.0f;
+            for (uint i = 0; i < 64; i++) {
+                sum += priv[i] * shared[(lid + i) % 1024];
+            }
+            buf[idx] = sum;
+        }
+    }
+"#;
+
+#[test]
+fn suggest_launch() {
+    let pro_que = ProQue::builder().src(SRC).dims([1]).build().unwrap();
+    let device_limit = pro_que.max_wg_size().unwrap();
+    assert!(device_limit > 0);
+
+    for &name in ["add", "heavy"].iter() {
+        let kernel_limit = pro_que.kernel_max_wg_size(name).unwrap();
+        assert!(kernel_limit > 0 && kernel_limit <= device_limit, "{}", name);
+
+        for &total in [1, 7, 64, 1000, 65537].iter() {
+            let (gws, lws) = pro_que.suggest_launch(name, total).unwrap();
+            let (gws, lws) = match (gws, lws) {
+                (SpatialDims::One(g), SpatialDims::One(l)) => (g, l),
+                dims => panic!("Unexpected dimensions: {:?}", dims),
+            };
+
+            assert!(lws > 0 && lws <= kernel_limit && lws <= device_limit,
+                "{}: {}: lws: {}", name, total, lws);
+            assert_eq!(gws % lws, 0, "{}: {}: gws: {}, lws: {}", name, total, gws, lws);
+            assert!(gws >= total && gws - total < lws, "{}: {}: gws: {}", name, total, gws);
+        }
+    }
+
+    assert!(pro_que.suggest_launch("add", 0).is_err());
+    assert!(pro_que.kernel_max_wg_size("missing").is_err());
+}
+
+#[test]
+fn suggested_launch_enqueues() {
+    let len = 1000;
+    let pro_que = ProQue::builder().src(SRC).dims([len]).build().unwrap();
+    let buffer = Buffer::<f32>::builder().dims([len]).fill_val(1.0).build(pro_que.queue())
+        .unwrap();
+    let (gws, lws) = pro_que.suggest_launch("add", len).unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .gws(gws)
+        .lws(lws)
+        .arg_buf(&buffer)
+        .arg_scl(len as u32);
+    kernel.enq().unwrap();
+
+    let mut result = vec![0.0f32; len];
+    buffer.read(&mut result).enq().unwrap();
+    assert!(result.iter().all(|&v| v == 2.0));
+}
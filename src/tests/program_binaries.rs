@@ -0,0 +1,51 @@
+//! Tests dumping program binaries with `Program::dump_binaries`.
+
+use std::env;
+use std::fs;
+use standard::{Context, Program};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buf, float val) {
+        buf[get_global_id(0)] += val;
+    }
+"#;
+
+#[test]
+fn dump_binaries() {
+    let context = Context::builder().build().unwrap();
+    let program = Program::builder().src(SRC).devices(context.devices()[0])
+        .build(&context).unwrap();
+
+    // The directory (and its parent) is created as needed:
+    let dir = env::temp_dir().join(format!("ocl_program_binaries_{}", ::std::process::id()))
+        .join("dump");
+    let dump = program.dump_binaries(&dir).unwrap();
+
+    assert_eq!(dump.len() + dump.skipped().len(), program.devices().len());
+    assert!(!dump.is_empty());
+
+    for path in dump.iter() {
+        assert_eq!(path.parent(), Some(dir.as_path()));
+        assert!(path.extension().is_some(), "{}", path.display());
+        assert!(fs::metadata(path).unwrap().len() > 0, "{}", path.display());
+    }
+
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn dump_binaries_unwritable() {
+    let context = Context::builder().build().unwrap();
+    let program = Program::builder().src(SRC).devices(context.devices()[0])
+        .build(&context).unwrap();
+
+    // A file in place of the directory:
+    let file = env::temp_dir().join(format!("ocl_program_binaries_file_{}",
+        ::std::process::id()));
+    fs::write(&file, "").unwrap();
+
+    let err = program.dump_binaries(&file).unwrap_err().to_string();
+    assert!(err.contains(&file.display().to_string()), "{}", err);
+
+    fs::remove_file(&file).unwrap();
+}
@@ -0,0 +1,55 @@
+//! Tests program build info queries (`core::get_program_build_info`).
+
+use std::ffi::CString;
+use core::{self, ContextProperties};
+use enums::{ProgramBuildInfo, ProgramBuildInfoResult, BuildStatus, ProgramBinaryType};
+
+static SRC: &'static str = "__kernel void nop() {}";
+static SRC_BAD: &'static str = "__kernel void nop() { this is not opencl c }";
+
+fn build_status(program: &core::Program, device: &core::DeviceId) -> BuildStatus {
+    match core::get_program_build_info(program, device, ProgramBuildInfo::BuildStatus) {
+        ProgramBuildInfoResult::BuildStatus(status) => status,
+        res => panic!("Unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn program_build_info() {
+    let platform_id = core::default_platform().unwrap();
+    let device_ids = core::get_device_ids(&platform_id, None, None).unwrap();
+    let device_id = device_ids[0];
+    let context_properties = ContextProperties::new().platform(platform_id);
+    let context = core::create_context(&Some(context_properties),
+        &[device_id], None, None).unwrap();
+
+    // Successful build:
+    let program = core::create_program_with_source(&context,
+        &[CString::new(SRC).unwrap()]).unwrap();
+    assert_eq!(build_status(&program, &device_id), BuildStatus::None);
+
+    core::build_program(&program, &[device_id], &CString::new("-D OCL_TEST=1").unwrap(),
+        None, None).unwrap();
+    assert_eq!(build_status(&program, &device_id), BuildStatus::Success);
+
+    match core::get_program_build_info(&program, &device_id, ProgramBuildInfo::BuildOptions) {
+        ProgramBuildInfoResult::BuildOptions(opts) => assert!(opts.contains("-D OCL_TEST=1")),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    match core::get_program_build_info(&program, &device_id, ProgramBuildInfo::BinaryType) {
+        ProgramBuildInfoResult::BinaryType(ty) => assert_eq!(ty, ProgramBinaryType::Executable),
+        // Not available prior to OpenCL 1.2:
+        ProgramBuildInfoResult::Error(_) => (),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    // Failing build:
+    let program_bad = core::create_program_with_source(&context,
+        &[CString::new(SRC_BAD).unwrap()]).unwrap();
+    assert_eq!(build_status(&program_bad, &device_id), BuildStatus::None);
+
+    assert!(core::build_program(&program_bad, &[device_id], &CString::new("").unwrap(),
+        None, None).is_err());
+    assert_eq!(build_status(&program_bad, &device_id), BuildStatus::Error);
+}
@@ -0,0 +1,157 @@
+//! Tests the rendering of macro definitions added with
+//! `ProgramBuilder::cmplr_def` and that kernels receive them intact.
+
+use std::{f32, f64};
+use error::Result as OclResult;
+use standard::{Buffer, Kernel, Program, ProgramBuilder, DefineValue, DefineStrategy, Queue};
+use testing;
+
+const STR_VAL: &'static str = "say \"hi\"\\ \t\n\u{e9}";
+
+static SRC: &'static str = r#"
+    #if INT_VAL != -42
+        #error "INT_VAL"
+    #endif
+
+    #if BIG_VAL != 5000000000L
+        #error "BIG_VAL"
+    #endif
+
+    #if MIN_VAL != (-9223372036854775807L - 1) || MIN_VAL >= 0
+        #error "MIN_VAL"
+    #endif
+
+    #if !TRUE_VAL || FALSE_VAL
+        #error "TRUE_VAL, FALSE_VAL"
+    #endif
+
+    #ifndef FLAG
+        #error "FLAG"
+    #endif
+
+    #ifdef STR_VAL
+        __constant char str_val[] = STR_VAL;
+    #endif
+
+    __kernel void defines(__global float* floats, __global char* str) {
+        floats[0] = FLOAT_VAL;
+        floats[1] = FLOAT_MAX;
+        floats[2] = FLOAT_INF;
+
+        #ifdef STR_VAL
+            for (uint i = 0; i < sizeof(str_val); i++) {
+                str[i] = str_val[i];
+            }
+        #endif
+    }
+"#;
+
+fn builder(strategy: DefineStrategy, include_str: bool) -> ProgramBuilder {
+    let builder = Program::builder()
+        .src(SRC)
+        .define_strategy(strategy)
+        .cmplr_def("INT_VAL", -42)
+        .cmplr_def("BIG_VAL", 5000000000i64)
+        .cmplr_def("MIN_VAL", i64::min_value())
+        .cmplr_def("TRUE_VAL", true)
+        .cmplr_def("FALSE_VAL", false)
+        .cmplr_def("FLAG", ())
+        .cmplr_def("FLOAT_VAL", 0.1f32)
+        .cmplr_def("FLOAT_MAX", f32::MAX)
+        .cmplr_def("FLOAT_INF", f32::NEG_INFINITY);
+
+    if include_str { builder.cmplr_def("STR_VAL", STR_VAL) } else { builder }
+}
+
+fn src_string(builder: &ProgramBuilder) -> String {
+    builder.get_src_strings().unwrap().into_iter()
+        .map(|s| s.into_string().unwrap())
+        .collect()
+}
+
+#[test]
+fn define_literals() {
+    assert_eq!(DefineValue::from(500).to_literal().unwrap(), "500");
+    assert_eq!(DefineValue::from(-2147483648i64).to_literal().unwrap(), "-2147483648");
+    assert_eq!(DefineValue::from(2147483648u32).to_literal().unwrap(), "2147483648L");
+    assert_eq!(DefineValue::from(i64::min_value()).to_literal().unwrap(),
+        "(-9223372036854775807L - 1)");
+    assert_eq!(DefineValue::from(true), DefineValue::Int(1));
+
+    assert_eq!(DefineValue::from(3.0f32).to_literal().unwrap(), "3.0f");
+    assert_eq!(DefineValue::from(0.1f32).to_literal().unwrap(), "0.1f");
+    assert_eq!(DefineValue::from(-1.5e-7f32).to_literal().unwrap(), "-1.5e-7f");
+    assert_eq!(DefineValue::from(f32::INFINITY).to_literal().unwrap(), "INFINITY");
+    assert_eq!(DefineValue::from(f32::NAN).to_literal().unwrap(), "NAN");
+    assert_eq!(DefineValue::from(0.1f64).to_literal().unwrap(), "0.1");
+    assert_eq!(DefineValue::from(1e300f64).to_literal().unwrap(), "1e300");
+    assert_eq!(DefineValue::from(f64::NEG_INFINITY).to_literal().unwrap(), "(-HUGE_VAL)");
+
+    assert_eq!(DefineValue::from(STR_VAL).to_literal().unwrap(),
+        r#""say\040\"hi\"\\\040\011\012\303\251""#);
+    assert_eq!(DefineValue::from(String::new()).to_literal().unwrap(), r#""""#);
+    assert_eq!(DefineValue::from(()).to_literal(), None);
+}
+
+#[test]
+fn define_strategies() {
+    let auto = builder(DefineStrategy::Auto, true);
+    let opts = auto.get_compiler_options().unwrap().into_string().unwrap();
+    assert!(opts.contains(" -DINT_VAL=-42 -DBIG_VAL=5000000000L "), "{}", opts);
+    assert!(opts.contains(" -DFLAG -DFLOAT_VAL=0.1f "), "{}", opts);
+    assert!(opts.ends_with(" -DFLOAT_INF=(-INFINITY)"), "{}", opts);
+    assert!(!opts.contains("STR_VAL"), "{}", opts);
+    let src = src_string(&auto);
+    assert!(src.contains("#define STR_VAL  \"say\\040"), "{}", src);
+    assert!(!src.contains("#define INT_VAL"), "{}", src);
+
+    let options = builder(DefineStrategy::BuildOptions, true);
+    let opts = options.get_compiler_options().unwrap().into_string().unwrap();
+    assert!(opts.contains(" -DSTR_VAL=\"say\\040"), "{}", opts);
+    assert!(!src_string(&options).contains("#define"));
+
+    let preamble = builder(DefineStrategy::Preamble, true);
+    assert_eq!(preamble.get_compiler_options().unwrap().into_string().unwrap().trim(), "");
+    let src = src_string(&preamble);
+    assert!(src.contains("#define INT_VAL  -42\n"), "{}", src);
+    assert!(src.contains("#define FLAG\n"), "{}", src);
+    assert!(src.find("#define FLOAT_INF").unwrap() < src.find("__kernel").unwrap());
+}
+
+fn round_trip(queue: &Queue, builder: &ProgramBuilder, include_str: bool) -> OclResult<()> {
+    let program = try!(Program::new(try!(builder.get_src_strings()),
+        try!(builder.get_compiler_options()), queue.context_core_as_ref(), &[*queue.device()]));
+
+    let floats = try!(Buffer::<f32>::new(queue, None, [3], None));
+    let str_len = STR_VAL.len() + 1;
+    let str_buf = try!(Buffer::<u8>::new(queue, None, [str_len], None));
+
+    let kernel = try!(Kernel::new("defines", &program, queue))
+        .gws([1])
+        .arg_buf(&floats)
+        .arg_buf(&str_buf);
+    try!(kernel.enq());
+
+    let floats = try!(floats.read_to_vec());
+    assert_eq!(floats[0].to_bits(), 0.1f32.to_bits());
+    assert_eq!(floats[1], f32::MAX);
+    assert_eq!(floats[2], f32::NEG_INFINITY);
+
+    if include_str {
+        let mut expected = STR_VAL.as_bytes().to_vec();
+        expected.push(0);
+        assert_eq!(try!(str_buf.read_to_vec()), expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn define_round_trip() {
+    testing::for_each_device(|queue| {
+        try!(round_trip(queue, &builder(DefineStrategy::Auto, true), true));
+        try!(round_trip(queue, &builder(DefineStrategy::Preamble, true), true));
+        // Quoting within compiler options is not portable (see
+        // `DefineStrategy::BuildOptions`):
+        round_trip(queue, &builder(DefineStrategy::BuildOptions, false), false)
+    });
+}
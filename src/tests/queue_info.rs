@@ -0,0 +1,46 @@
+//! Tests command queue info queries through both `Queue` and
+//! `core::get_command_queue_info`.
+
+use core;
+use flags;
+use standard::{Context, Queue};
+use enums::{CommandQueueInfo, CommandQueueInfoResult};
+
+#[test]
+fn queue_info() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let queue = Queue::new(&context, device).unwrap();
+
+    // Queues are always created with profiling enabled:
+    let props = queue.properties().unwrap();
+    assert!(props.contains(flags::QUEUE_PROFILING_ENABLE));
+    assert!(!props.contains(flags::QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE));
+
+    match core::get_command_queue_info(queue.core_as_ref(), CommandQueueInfo::Properties) {
+        CommandQueueInfoResult::Properties(p) => assert_eq!(p, props),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    match core::get_command_queue_info(queue.core_as_ref(), CommandQueueInfo::Device) {
+        CommandQueueInfoResult::Device(d) => assert_eq!(d, *queue.device().as_core()),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    // The context handle is retained (dropping it must not invalidate the
+    // context):
+    for _ in 0..3 {
+        match core::get_command_queue_info(queue.core_as_ref(), CommandQueueInfo::Context) {
+            CommandQueueInfoResult::Context(c) => assert_eq!(unsafe { c.as_ptr() },
+                unsafe { context.core_as_ref().as_ptr() }),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+    }
+
+    match core::get_command_queue_info(queue.core_as_ref(), CommandQueueInfo::ReferenceCount) {
+        CommandQueueInfoResult::ReferenceCount(count) => assert!(count > 0),
+        res => panic!("Unexpected result: {:?}", res),
+    }
+
+    queue.finish().unwrap();
+}
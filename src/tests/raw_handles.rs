@@ -0,0 +1,111 @@
+//! Tests round-tripping objects through raw pointers (`as_ptr`,
+//! `from_raw_retained`, and `from_raw_unretained`) and that reference counts
+//! follow the ownership rules of each.
+
+use std::slice;
+use cl_h::cl_event;
+use core::{self, Event as EventCore, CommandQueue as CommandQueueCore, EventInfo,
+    EventInfoResult, CommandQueueInfo, CommandQueueInfoResult};
+use standard::{Context, Queue, Event, EventList};
+
+fn event_ref_count(event: &EventCore) -> u32 {
+    match core::get_event_info(event, EventInfo::ReferenceCount) {
+        EventInfoResult::ReferenceCount(c) => c,
+        res => panic!("{:?}", res),
+    }
+}
+
+fn queue_ref_count(queue: &CommandQueueCore) -> u32 {
+    match core::get_command_queue_info(queue, CommandQueueInfo::ReferenceCount) {
+        CommandQueueInfoResult::ReferenceCount(c) => c,
+        res => panic!("{:?}", res),
+    }
+}
+
+fn user_events(context: &Context, count: usize) -> Vec<EventCore> {
+    (0..count).map(|_| core::create_user_event(context).unwrap()).collect()
+}
+
+#[test]
+fn event_round_trip() {
+    let context = Context::builder().build().unwrap();
+    let event = core::create_user_event(&context).unwrap();
+    let ref_count = event_ref_count(&event);
+    let ptr = unsafe { event.as_ptr() };
+
+    // Retained: both references are released independently.
+    let retained = unsafe { Event::from_raw_retained(ptr).unwrap() };
+    assert_eq!(unsafe { retained.as_ptr() }, ptr);
+    assert_eq!(event_ref_count(&event), ref_count + 1);
+    drop(retained);
+    assert_eq!(event_ref_count(&event), ref_count);
+
+    // Unretained: takes over a reference retained on its behalf.
+    unsafe { core::retain_event(&event).unwrap(); }
+    assert_eq!(event_ref_count(&event), ref_count + 1);
+    let unretained = unsafe { Event::from_raw_unretained(ptr).unwrap() };
+    assert_eq!(event_ref_count(&event), ref_count + 1);
+    drop(unretained);
+    assert_eq!(event_ref_count(&event), ref_count);
+
+    // Null pointers:
+    assert!(unsafe { Event::from_raw_retained(0 as cl_event) }.is_err());
+    assert!(unsafe { Event::from_raw_unretained(0 as cl_event) }.is_err());
+    assert!(unsafe { Event::empty().as_ptr() }.is_null());
+}
+
+#[test]
+fn event_list_round_trip() {
+    let context = Context::builder().build().unwrap();
+    let events = user_events(&context, 3);
+    let ref_counts: Vec<u32> = events.iter().map(event_ref_count).collect();
+    let ptrs: Vec<cl_event> = events.iter().map(|ev| unsafe { ev.as_ptr() }).collect();
+
+    let list = unsafe { EventList::from_raw_retained(&ptrs).unwrap() };
+    assert_eq!(list.len(), 3);
+    assert_eq!(unsafe { slice::from_raw_parts(list.as_ptr(), list.len()) }, &ptrs[..]);
+    assert!(events.iter().zip(ref_counts.iter()).all(|(ev, &rc)| event_ref_count(ev) == rc + 1));
+
+    // Clones retain each event once more:
+    let clone = list.clone();
+    assert!(events.iter().zip(ref_counts.iter()).all(|(ev, &rc)| event_ref_count(ev) == rc + 2));
+    drop(clone);
+    drop(list);
+    assert!(events.iter().zip(ref_counts.iter()).all(|(ev, &rc)| event_ref_count(ev) == rc));
+
+    // Unretained lists release references retained on their behalf:
+    for ev in events.iter() { unsafe { core::retain_event(ev).unwrap(); } }
+    let list = unsafe { EventList::from_raw_unretained(&ptrs).unwrap() };
+    assert!(events.iter().zip(ref_counts.iter()).all(|(ev, &rc)| event_ref_count(ev) == rc + 1));
+    drop(list);
+    assert!(events.iter().zip(ref_counts.iter()).all(|(ev, &rc)| event_ref_count(ev) == rc));
+
+    // A null anywhere retains nothing:
+    let with_null = [ptrs[0], 0 as cl_event, ptrs[2]];
+    assert!(unsafe { EventList::from_raw_retained(&with_null) }.is_err());
+    assert!(unsafe { EventList::from_raw_unretained(&with_null) }.is_err());
+    assert!(events.iter().zip(ref_counts.iter()).all(|(ev, &rc)| event_ref_count(ev) == rc));
+
+    assert!(unsafe { EventList::new().as_ptr() }.is_null());
+}
+
+#[test]
+fn other_handles() {
+    let context = Context::builder().build().unwrap();
+    let raw_devices: Vec<_> = context.devices().iter().map(|d| unsafe { d.as_ptr() }).collect();
+    assert_eq!(context.devices_raw(), &raw_devices[..]);
+
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let ref_count = queue_ref_count(&queue);
+    let ptr = unsafe { queue.as_ptr() };
+
+    let retained = unsafe { CommandQueueCore::from_raw_retained(ptr).unwrap() };
+    assert_eq!(queue_ref_count(&queue), ref_count + 1);
+    drop(retained);
+    assert_eq!(queue_ref_count(&queue), ref_count);
+
+    unsafe { core::retain_command_queue(&queue).unwrap(); }
+    let unretained = unsafe { CommandQueueCore::from_raw_unretained(ptr).unwrap() };
+    drop(unretained);
+    assert_eq!(queue_ref_count(&queue), ref_count);
+}
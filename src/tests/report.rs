@@ -0,0 +1,76 @@
+//! Tests the platform and device report.
+
+use core::DeviceInfo;
+use standard::{self, push_device_report};
+use tests::fixtures;
+
+fn device_report(device: &fixtures::DeviceFixture) -> String {
+    let mut report = String::new();
+    push_device_report(&mut report, "  ", device);
+    report
+}
+
+#[test]
+fn device_section() {
+    let report = device_report(&fixtures::nvidia_discrete());
+    let lines: Vec<&str> = report.lines().collect();
+
+    assert_eq!(lines[0], "  Type: GPU");
+    assert_eq!(lines[1], "  Vendor: NVIDIA Corporation");
+    assert_eq!(lines[2], "  Version: OpenCL 1.2 CUDA");
+    assert!(report.contains("  Compute units: 20\n"), "{}", report);
+    assert!(report.contains("  Global memory: 7.9 GiB (8508145664 bytes)\n"), "{}", report);
+    assert!(report.contains("  Max allocation: 2 GiB (2147287040 bytes)\n"), "{}", report);
+    assert!(report.contains("  Max work group size: 1024\n"), "{}", report);
+    assert!(report.contains("  Max work item sizes: [1024, 1024, 64]\n"), "{}", report);
+    assert!(report.contains("  Image support: yes\n"), "{}", report);
+    assert!(report.contains("  fp64: yes\n  fp16: no\n"), "{}", report);
+    assert!(report.ends_with("  Key extensions: cl_khr_fp64 cl_khr_global_int32_base_atomics \
+        cl_khr_local_int32_base_atomics cl_khr_byte_addressable_store cl_khr_gl_sharing\n"),
+        "{}", report);
+
+    let report = device_report(&fixtures::intel_integrated());
+    assert!(report.contains("  fp64: yes\n  fp16: yes\n"), "{}", report);
+
+    let report = device_report(&fixtures::pocl_cpu().with(DeviceInfo::Type,
+        (::core::DEVICE_TYPE_CPU | ::core::DEVICE_TYPE_DEFAULT).bits()));
+    assert!(report.starts_with("  Type: DEFAULT | CPU\n"), "{}", report);
+}
+
+#[test]
+fn device_section_query_failures() {
+    // Values not in the snapshot (driver version, clock, etc.) fail to be
+    // queried, as do the removed values:
+    let device = fixtures::pocl_cpu()
+        .without(DeviceInfo::Extensions)
+        .with_bytes(DeviceInfo::MaxWorkItemSizes, vec![0; 3]);
+    let report = device_report(&device);
+
+    assert!(report.contains("  Driver version: <error: "), "{}", report);
+    assert!(report.contains("  Max clock frequency: <error: "), "{}", report);
+    assert!(report.contains("  Max work item sizes: <error: "), "{}", report);
+    assert!(report.contains("  fp64: <error: "), "{}", report);
+    assert!(report.contains("  Key extensions: <error: "), "{}", report);
+
+    // Every other field is still present:
+    assert_eq!(report.lines().count(), 16);
+    assert!(report.contains("  Compute units: 4\n"), "{}", report);
+    assert!(report.contains("  Image support: yes\n"), "{}", report);
+}
+
+#[test]
+fn report() {
+    let report = standard::report();
+    println!("{}", report);
+    assert!(report.starts_with("OpenCL report (ocl "));
+
+    if ::core::get_platform_ids().map(|p| p.is_empty()).unwrap_or(true) {
+        println!("No platforms found.");
+        return;
+    }
+
+    assert!(report.contains("\nPlatform [0]: "), "{}", report);
+    assert!(report.contains("\n    Version: OpenCL "), "{}", report);
+    assert!(report.contains("\n    Device [0]: "), "{}", report);
+    assert!(report.contains("\n        Max work group size: "), "{}", report);
+}
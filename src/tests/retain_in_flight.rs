@@ -0,0 +1,76 @@
+//! Drops buffers, kernels, and host data immediately after enqueuing
+//! non-blocking commands which use them, relying on the commands to keep
+//! them alive until complete.
+//!
+//! Best run under valgrind or a sanitizer where available.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use standard::{ProQue, Buffer, Event};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global uint* src, __global uint* dst) {
+        uint idx = get_global_id(0);
+        dst[idx] = src[idx] + 1;
+    }
+"#;
+
+const LEN: usize = 1 << 14;
+const ITERS: usize = 200;
+
+#[test]
+fn retain_in_flight() {
+    let proque = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let result_buffer = proque.create_buffer::<u32>().unwrap();
+
+    for i in 0..ITERS {
+        let src_buffer = Buffer::<u32>::builder().dims([LEN]).uninit()
+            .build(proque.queue()).unwrap();
+        let dst_buffer = Buffer::<u32>::builder().dims([LEN]).uninit()
+            .build(proque.queue()).unwrap();
+
+        src_buffer.write_owned(vec![i as u32; LEN]).enq().unwrap();
+
+        let kernel = proque.create_kernel("add").unwrap()
+            .arg_buf(&src_buffer)
+            .arg_buf(&dst_buffer);
+        kernel.cmd().retain_mems(true).enq().unwrap();
+        drop(kernel);
+
+        dst_buffer.cmd().copy(&result_buffer, 0, LEN).block(false).retain_mem(true)
+            .enq().unwrap();
+    }
+
+    proque.queue().finish().unwrap();
+
+    let mut result = vec![0u32; LEN];
+    result_buffer.read(&mut result).enq().unwrap();
+
+    for &val in result.iter() {
+        assert_eq!(val, ITERS as u32);
+    }
+}
+
+#[test]
+fn retain_until_complete() {
+    let proque = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let buffer = proque.create_buffer::<u32>().unwrap();
+    let payload = Arc::new(());
+
+    let mut event = Event::empty();
+    buffer.write_owned(vec![5; LEN]).enew(&mut event).enq().unwrap();
+    event.retain_until_complete(payload.clone()).unwrap();
+    proque.queue().retain_until_complete(payload.clone()).unwrap();
+    drop(event);
+
+    proque.queue().finish().unwrap();
+
+    // Callbacks may run shortly after the commands are reported complete:
+    for _ in 0..500 {
+        if Arc::strong_count(&payload) == 1 { break; }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert_eq!(Arc::strong_count(&payload), 1);
+}
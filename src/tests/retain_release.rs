@@ -0,0 +1,101 @@
+//! Tests that `core::retain_*` and `core::release_*` calls are balanced for
+//! each object type, using the reference counts reported by info queries.
+
+use core;
+use standard::{ProQue, Buffer};
+use enums::{ContextInfo, ContextInfoResult, CommandQueueInfo, CommandQueueInfoResult, MemInfo,
+    MemInfoResult, ProgramInfo, ProgramInfoResult, KernelInfo, KernelInfoResult, EventInfo,
+    EventInfoResult, SamplerInfo, SamplerInfoResult, AddressingMode, FilterMode};
+
+/// Asserts that `retain` increments and `release` decrements the count
+/// returned by `count`.
+fn check_round_trip<C, R, L>(name: &str, count: C, retain: R, release: L)
+        where C: Fn() -> u32, R: FnOnce(), L: FnOnce()
+{
+    let initial = count();
+    retain();
+    assert_eq!(count(), initial + 1, "{}: retain", name);
+    release();
+    assert_eq!(count(), initial, "{}: release", name);
+}
+
+#[test]
+fn retain_release() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([64])
+        .build().unwrap();
+
+    let context = proque.context().core_as_ref();
+    check_round_trip("context",
+        || match core::get_context_info(context, ContextInfo::ReferenceCount) {
+            ContextInfoResult::ReferenceCount(c) => c,
+            res => panic!("{:?}", res),
+        },
+        || unsafe { core::retain_context(context).unwrap() },
+        || unsafe { core::release_context(context).unwrap() });
+
+    let queue = proque.queue().core_as_ref();
+    check_round_trip("queue",
+        || match core::get_command_queue_info(queue, CommandQueueInfo::ReferenceCount) {
+            CommandQueueInfoResult::ReferenceCount(c) => c,
+            res => panic!("{:?}", res),
+        },
+        || unsafe { core::retain_command_queue(queue).unwrap() },
+        || unsafe { core::release_command_queue(queue).unwrap() });
+
+    let buffer = Buffer::<f32>::new(proque.queue(), None, [64], None).unwrap();
+    let mem = buffer.core_as_ref();
+    check_round_trip("mem",
+        || match core::get_mem_object_info(mem, MemInfo::ReferenceCount) {
+            MemInfoResult::ReferenceCount(c) => c,
+            res => panic!("{:?}", res),
+        },
+        || unsafe { core::retain_mem_object(mem).unwrap() },
+        || unsafe { core::release_mem_object(mem).unwrap() });
+
+    let program = proque.program().core_as_ref();
+    check_round_trip("program",
+        || match core::get_program_info(program, ProgramInfo::ReferenceCount) {
+            ProgramInfoResult::ReferenceCount(c) => c,
+            res => panic!("{:?}", res),
+        },
+        || unsafe { core::retain_program(program).unwrap() },
+        || unsafe { core::release_program(program).unwrap() });
+
+    let kernel_obj = proque.create_kernel("nop").unwrap();
+    let kernel = unsafe { kernel_obj.core_as_ref() };
+    check_round_trip("kernel",
+        || match core::get_kernel_info(kernel, KernelInfo::ReferenceCount) {
+            KernelInfoResult::ReferenceCount(c) => c,
+            res => panic!("{:?}", res),
+        },
+        || unsafe { core::retain_kernel(kernel).unwrap() },
+        || unsafe { core::release_kernel(kernel).unwrap() });
+
+    let event = core::create_user_event(context).unwrap();
+    check_round_trip("event",
+        || match core::get_event_info(&event, EventInfo::ReferenceCount) {
+            EventInfoResult::ReferenceCount(c) => c,
+            res => panic!("{:?}", res),
+        },
+        || unsafe { core::retain_event(&event).unwrap() },
+        || unsafe { core::release_event(&event).unwrap() });
+
+    let sampler = core::create_sampler(context, false, AddressingMode::None,
+        FilterMode::Nearest).unwrap();
+    check_round_trip("sampler",
+        || match core::get_sampler_info(&sampler, SamplerInfo::ReferenceCount) {
+            SamplerInfoResult::ReferenceCount(c) => c,
+            res => panic!("{:?}", res),
+        },
+        || unsafe { core::retain_sampler(&sampler).unwrap() },
+        || unsafe { core::release_sampler(&sampler).unwrap() });
+
+    // Root-level devices are unaffected but the calls must succeed (1.2+):
+    if proque.queue().device_version() >= (1, 2) {
+        let device = proque.queue().device().as_core();
+        unsafe { core::retain_device(device).unwrap(); }
+        unsafe { core::release_device(device).unwrap(); }
+    }
+}
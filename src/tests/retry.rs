@@ -0,0 +1,105 @@
+//! Tests the classification of retryable errors and `RetryPolicy`.
+
+use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use error::{Error as OclError, Result as OclResult, RetryPolicy};
+use enums::Status;
+
+fn status_err<T>(status: Status) -> OclResult<T> {
+    Err(OclError::err_status::<(), _>(status as i32, "clFoo", "").unwrap_err())
+}
+
+fn policy() -> RetryPolicy {
+    RetryPolicy::new().max_attempts(4).initial_delay(Duration::from_millis(0))
+}
+
+#[test]
+fn retry_classification() {
+    assert!(Status::CL_OUT_OF_RESOURCES.is_retryable());
+    assert!(Status::CL_MEM_OBJECT_ALLOCATION_FAILURE.is_retryable());
+    assert!(Status::CL_OUT_OF_HOST_MEMORY.is_retryable());
+    assert!(!Status::CL_INVALID_ARG_VALUE.is_retryable());
+    assert!(!Status::CL_INVALID_KERNEL_ARGS.is_retryable());
+
+    assert!(status_err::<()>(Status::CL_OUT_OF_RESOURCES).unwrap_err().is_retryable());
+    assert!(!status_err::<()>(Status::CL_INVALID_VALUE).unwrap_err().is_retryable());
+    assert!(!OclError::new("string error").is_retryable());
+}
+
+#[test]
+fn retry_transient_then_success() {
+    let mut attempts = Vec::new();
+
+    let result = policy().run(|attempt| {
+        attempts.push(attempt);
+        if attempt < 2 { status_err(Status::CL_MEM_OBJECT_ALLOCATION_FAILURE) } else { Ok(attempt) }
+    });
+
+    assert_eq!(result.unwrap(), 2);
+    assert_eq!(attempts, vec![0, 1, 2]);
+}
+
+#[test]
+fn retry_fatal_not_retried() {
+    let mut calls = 0;
+
+    let result: OclResult<()> = policy().run(|_| {
+        calls += 1;
+        status_err(Status::CL_INVALID_ARG_VALUE)
+    });
+
+    assert!(result.unwrap_err().is_invalid_arg_value());
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn retry_exhausted() {
+    let mut calls = 0;
+
+    let result: OclResult<()> = policy().run(|_| {
+        calls += 1;
+        status_err(Status::CL_OUT_OF_RESOURCES)
+    });
+
+    assert!(result.unwrap_err().is_status(Status::CL_OUT_OF_RESOURCES));
+    assert_eq!(calls, 4);
+}
+
+#[test]
+fn retry_custom_classifier() {
+    let classified = Arc::new(AtomicUsize::new(0));
+    let classified_clone = classified.clone();
+
+    let policy = policy().classifier(move |err| {
+        classified_clone.fetch_add(1, Ordering::SeqCst);
+        err.is_status(Status::CL_INVALID_VALUE)
+    });
+
+    assert!(!policy.is_retryable(&status_err::<()>(Status::CL_OUT_OF_RESOURCES).unwrap_err()));
+
+    let result = policy.run(|attempt| {
+        if attempt == 0 { status_err(Status::CL_INVALID_VALUE) } else { Ok(attempt) }
+    });
+
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(classified.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn retry_delay_saturates() {
+    let mut calls = 0;
+
+    // Without saturation the delay would overflow a `Duration` within a few
+    // attempts:
+    let policy = RetryPolicy::new().max_attempts(40).initial_delay(Duration::from_secs(1))
+        .backoff_factor(::std::u32::MAX).max_delay(Duration::from_millis(1));
+
+    let result: OclResult<()> = policy.run(|_| {
+        calls += 1;
+        status_err(Status::CL_OUT_OF_RESOURCES)
+    });
+
+    assert!(result.unwrap_err().is_status(Status::CL_OUT_OF_RESOURCES));
+    assert_eq!(calls, 40);
+}
@@ -0,0 +1,69 @@
+//! Tests sampler info queries through both `Sampler` and
+//! `core::get_sampler_info`.
+
+use core::{self, Sampler as SamplerCore};
+use standard::{Context, Sampler};
+use enums::{AddressingMode, FilterMode, SamplerInfo, SamplerInfoResult};
+
+const MODES: [(bool, AddressingMode, FilterMode); 3] = [
+    (false, AddressingMode::None, FilterMode::Nearest),
+    (true, AddressingMode::Repeat, FilterMode::Linear),
+    (true, AddressingMode::MirroredRepeat, FilterMode::Nearest),
+];
+
+#[test]
+fn sampler_info() {
+    let context = Context::builder().build().unwrap();
+
+    for &(norm, addr_mode, filter_mode) in MODES.iter() {
+        let sampler = Sampler::new(&context, norm, addr_mode, filter_mode).unwrap();
+
+        assert_eq!(sampler.normalized_coords().unwrap(), norm);
+        assert_eq!(sampler.addressing_mode().unwrap(), addr_mode);
+        assert_eq!(sampler.filter_mode().unwrap(), filter_mode);
+    }
+}
+
+#[test]
+fn sampler_info_core() {
+    let context = Context::builder().build().unwrap();
+
+    for &(norm, addr_mode, filter_mode) in MODES.iter() {
+        let sampler = core::create_sampler(&context, norm, addr_mode, filter_mode).unwrap();
+
+        let ref_count = || match core::get_sampler_info(&sampler, SamplerInfo::ReferenceCount) {
+            SamplerInfoResult::ReferenceCount(count) => count,
+            res => panic!("Unexpected result: {:?}", res),
+        };
+        let initial_count = ref_count();
+
+        // Wrap the raw handle as if it had been obtained elsewhere:
+        let raw = unsafe { SamplerCore::from_raw_retained(sampler.as_ptr()).unwrap() };
+        assert_eq!(ref_count(), initial_count + 1);
+
+        match core::get_sampler_info(&raw, SamplerInfo::Context) {
+            SamplerInfoResult::Context(ctx) => {
+                assert_eq!(unsafe { ctx.as_ptr() }, unsafe { context.core_as_ref().as_ptr() });
+            },
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_sampler_info(&raw, SamplerInfo::NormalizedCoords) {
+            SamplerInfoResult::NormalizedCoords(n) => assert_eq!(n, norm),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_sampler_info(&raw, SamplerInfo::AddressingMode) {
+            SamplerInfoResult::AddressingMode(mode) => assert_eq!(mode, addr_mode),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match core::get_sampler_info(&raw, SamplerInfo::FilterMode) {
+            SamplerInfoResult::FilterMode(mode) => assert_eq!(mode, filter_mode),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        drop(raw);
+        assert_eq!(ref_count(), initial_count);
+    }
+}
@@ -0,0 +1,82 @@
+//! Tests that `scope` waits for every command before returning, whether the
+//! closure succeeds, returns an error, or panics.
+
+use std::panic::{self, AssertUnwindSafe};
+use core::CommandExecutionStatus;
+use error::Error as OclError;
+use standard::{scope, ProQue, Event};
+
+static SRC: &'static str = r#"
+    __kernel void add_one(__global uint* buf) {
+        buf[get_global_id(0)] += 1;
+    }
+"#;
+
+const LEN: usize = 1 << 16;
+
+fn assert_complete(events: &[Event]) {
+    assert!(!events.is_empty());
+    for event in events {
+        assert_eq!(event.status().unwrap(), CommandExecutionStatus::Complete);
+    }
+}
+
+#[test]
+fn scope_success() {
+    let proque = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let buffer = proque.create_buffer::<u32>().unwrap();
+    let kernel = proque.create_kernel("add_one").unwrap().arg_buf(&buffer);
+
+    let src: Vec<u32> = (0..LEN as u32).collect();
+    let mut result = vec![0u32; LEN];
+
+    let events = scope(|s| {
+        try!(s.write_async(&buffer, &src));
+        try!(s.enq_kernel(&kernel));
+        try!(s.read_async(&buffer, &mut result));
+        Ok(s.events().to_vec())
+    }).unwrap();
+
+    assert_eq!(events.len(), 3);
+    assert_complete(&events);
+
+    for (i, &val) in result.iter().enumerate() {
+        assert_eq!(val, i as u32 + 1);
+    }
+}
+
+#[test]
+fn scope_error() {
+    let proque = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let buffer = proque.create_buffer::<u32>().unwrap();
+    let mut result = vec![0u32; LEN];
+    let mut events = Vec::new();
+
+    let res: ::error::Result<()> = scope(|s| {
+        try!(s.read_async(&buffer, &mut result));
+        events = s.events().to_vec();
+        OclError::err("Early error.")
+    });
+
+    assert_eq!(res.unwrap_err().to_string(), "Early error.");
+    assert_complete(&events);
+}
+
+#[test]
+fn scope_panic() {
+    let proque = ProQue::builder().src(SRC).dims([LEN]).build().unwrap();
+    let buffer = proque.create_buffer::<u32>().unwrap();
+    let mut result = vec![0u32; LEN];
+    let mut events = Vec::new();
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+        scope::<_, ()>(|s| {
+            try!(s.read_async(&buffer, &mut result));
+            events = s.events().to_vec();
+            panic!("Panic within scope.");
+        })
+    }));
+
+    assert!(res.is_err());
+    assert_complete(&events);
+}
@@ -0,0 +1,129 @@
+//! Default platform and device selection by environment variable.
+//!
+//! The variables are changed with `std::env`, which would affect any other
+//! test running at the same time, so `overrides` re-runs this test binary to
+//! run `overrides_child` on its own.
+
+use std::env;
+use std::process::Command;
+use core;
+use standard::{Platform, Device, Context};
+
+const CHILD_VAR: &'static str = "OCL_TEST_SELECTION_CHILD";
+const VARS: &'static [&'static str] = &["OCL_PLATFORM_INDEX", "OCL_DEFAULT_PLATFORM_IDX",
+    "OCL_DEVICE_INDEX", "OCL_DEVICE_TYPE", "OCL_DEFAULT_DEVICE_TYPE"];
+
+fn clear_vars() {
+    for var in VARS { env::remove_var(var); }
+}
+
+fn err_string<T: ::std::fmt::Debug>(result: ::error::Result<T>) -> String {
+    match result {
+        Ok(val) => panic!("Expected an error, got: {:?}", val),
+        Err(err) => err.to_string(),
+    }
+}
+
+/// Run (only) by `overrides`, in a process of its own.
+#[test]
+fn overrides_child() {
+    if env::var(CHILD_VAR).is_err() { return; }
+    clear_vars();
+
+    let platforms = match core::get_platform_ids() {
+        Ok(ref platforms) if !platforms.is_empty() => platforms.clone(),
+        _ => { println!("No platforms found. Skipping."); return; },
+    };
+    let last_platform = platforms.len() - 1;
+
+    // Platform index:
+    env::set_var("OCL_PLATFORM_INDEX", last_platform.to_string());
+    assert_eq!(core::default_platform().unwrap(), platforms[last_platform]);
+    assert!(core::selection_report().contains("selected by OCL_PLATFORM_INDEX"));
+
+    env::set_var("OCL_PLATFORM_INDEX", platforms.len().to_string());
+    let err = err_string(core::default_platform());
+    assert!(err.contains("out of range") && err.contains("Available platforms: [0]"), "{}", err);
+    assert!(core::selection_report().contains("Platform: Error:"));
+
+    env::set_var("OCL_PLATFORM_INDEX", "first");
+    assert!(err_string(Platform::try_default()).contains("not a valid platform index"));
+
+    // The newer name takes precedence over the older:
+    env::set_var("OCL_DEFAULT_PLATFORM_IDX", "0");
+    assert!(Platform::try_default().is_err());
+    env::remove_var("OCL_PLATFORM_INDEX");
+    env::set_var("OCL_DEFAULT_PLATFORM_IDX", last_platform.to_string());
+    assert_eq!(core::default_platform().unwrap(), platforms[last_platform]);
+
+    // An explicit platform wins:
+    env::set_var("OCL_DEFAULT_PLATFORM_IDX", platforms.len().to_string());
+    let platform = Platform::new(platforms[0]);
+    let explicit_devices = Device::list_all(&platform).unwrap();
+    Context::builder().platform(platform).build().unwrap();
+    env::remove_var("OCL_DEFAULT_PLATFORM_IDX");
+
+    // Device index:
+    let platform_id = core::default_platform().unwrap();
+    let devices = core::default_devices(&platform_id).unwrap();
+    let last_device = devices.len() - 1;
+
+    env::set_var("OCL_DEVICE_INDEX", last_device.to_string());
+    assert_eq!(core::default_devices(&platform_id).unwrap(), vec![devices[last_device]]);
+    let context = Context::builder().build().unwrap();
+    assert_eq!(context.devices().len(), 1);
+    assert_eq!(*context.devices()[0].as_core(), devices[last_device]);
+    assert!(core::selection_report().contains("selected by OCL_DEVICE_INDEX"));
+
+    env::set_var("OCL_DEVICE_INDEX", devices.len().to_string());
+    let err = err_string(core::default_devices(&platform_id));
+    assert!(err.contains("out of range") && err.contains("Available devices: [0]"), "{}", err);
+    assert!(Context::builder().build().is_err());
+
+    // Explicit devices win:
+    let context = Context::builder().devices(&explicit_devices).build().unwrap();
+    assert_eq!(context.devices().len(), explicit_devices.len());
+    env::remove_var("OCL_DEVICE_INDEX");
+
+    // Device type:
+    env::set_var("OCL_DEVICE_TYPE", "all");
+    assert_eq!(core::default_devices(&platform_id).unwrap(), devices);
+
+    env::set_var("OCL_DEVICE_TYPE", "TPU");
+    let err = err_string(core::default_devices(&platform_id));
+    assert!(err.contains("OCL_DEVICE_TYPE") && err.contains("'TPU'"), "{}", err);
+
+    // Each type narrows the defaults or, if there are none of it, is an error
+    // listing the devices:
+    for &type_name in &["CPU", "GPU", "ACCELERATOR"] {
+        env::set_var("OCL_DEVICE_TYPE", type_name);
+        match core::default_devices(&platform_id) {
+            Ok(typed) => assert!(typed.iter().all(|d| devices.contains(d))),
+            Err(err) => assert!(err.to_string().contains("Available devices: [0]"), "{}", err),
+        }
+    }
+
+    clear_vars();
+}
+
+#[test]
+fn overrides() {
+    let output = Command::new(env::current_exe().unwrap())
+        .arg("tests::selection::overrides_child")
+        .arg("--exact")
+        .arg("--nocapture")
+        .env(CHILD_VAR, "1")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{}{}", String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("1 passed"));
+}
+
+#[test]
+fn report() {
+    let report = core::selection_report();
+    assert!(report.starts_with("OpenCL default selection:"));
+    assert!(report.contains("OCL_PLATFORM_INDEX="));
+}
@@ -0,0 +1,102 @@
+//! Tests round-tripping the plain-data types supported by the `serde`
+//! feature through JSON.
+
+use std::fmt::Debug;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json;
+use core::{self, ImageFormat, ImageDescriptor, ImageChannelOrder, ImageChannelDataType,
+    MemObjectType, MemFlags, CommandQueueProperties};
+use standard::{Context, Buffer, SpatialDims, DeviceLimits};
+use tests::fixtures;
+
+/// Serializes `val`, checks the JSON against `expected`, then deserializes
+/// it and compares the result (by its `Debug` representation).
+fn round_trip<T: Serialize + DeserializeOwned + Debug>(val: &T, expected: &str) {
+    let json = serde_json::to_string(val).unwrap();
+    assert_eq!(json, expected);
+    let back: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(format!("{:?}", back), format!("{:?}", val));
+}
+
+#[test]
+fn image_formats() {
+    round_trip(&ImageChannelOrder::Rgba, r#""Rgba""#);
+    round_trip(&ImageChannelDataType::UnormInt8, r#""UnormInt8""#);
+    round_trip(&MemObjectType::Image2d, r#""Image2d""#);
+    round_trip(&ImageFormat::new(ImageChannelOrder::Bgra, ImageChannelDataType::Float),
+        r#"{"channel_order":"Bgra","channel_data_type":"Float"}"#);
+
+    // Unknown variants:
+    assert!(serde_json::from_str::<ImageChannelOrder>(r#""Rgbb""#).is_err());
+    assert!(serde_json::from_str::<ImageChannelDataType>(r#""UnormInt9""#).is_err());
+    assert!(serde_json::from_str::<ImageFormat>(
+        r#"{"channel_order":"Rgba","channel_data_type":"Double"}"#).is_err());
+}
+
+#[test]
+fn image_descriptor() {
+    let desc = ImageDescriptor::new(MemObjectType::Image3d, 64, 32, 16, 0, 0, 0, None);
+    round_trip(&desc, concat!(r#"{"image_type":"Image3d","image_width":64,"image_height":32,"#,
+        r#""image_depth":16,"image_array_size":0,"image_row_pitch":0,"image_slice_pitch":0,"#,
+        r#""num_mip_levels":0,"num_samples":0}"#));
+
+    // A `buffer` is never read back:
+    let json = r#"{"image_type":"Image1d","image_width":8,"image_height":0,"image_depth":0,
+        "image_array_size":0,"image_row_pitch":0,"image_slice_pitch":0,"num_mip_levels":0,
+        "num_samples":0,"buffer":null}"#;
+    let desc: ImageDescriptor = serde_json::from_str(json).unwrap();
+    assert!(desc.buffer.is_none());
+    assert!(serde_json::from_str::<ImageDescriptor>(&json.replace("Image1d", "Image4d"))
+        .is_err());
+
+    // Nor written:
+    let context = Context::builder().build().unwrap();
+    let buffer = Buffer::<u8>::builder()
+        .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+        .dims([8])
+        .build_in_context_with_data(&context, &[0; 8])
+        .unwrap();
+    let desc = ImageDescriptor::new(MemObjectType::Image1dBuffer, 8, 0, 0, 0, 0, 0,
+        Some(buffer.core_as_ref().clone()));
+    let err = serde_json::to_string(&desc).unwrap_err().to_string();
+    assert!(err.contains("can not be serialized"), "{}", err);
+}
+
+#[test]
+fn spatial_dims() {
+    round_trip(&SpatialDims::Unspecified, r#""Unspecified""#);
+    round_trip(&SpatialDims::One(1024), r#"{"One":1024}"#);
+    round_trip(&SpatialDims::Two(16, 8), r#"{"Two":[16,8]}"#);
+    round_trip(&SpatialDims::Three(4, 2, 1), r#"{"Three":[4,2,1]}"#);
+
+    assert!(serde_json::from_str::<SpatialDims>(r#"{"Four":[4,3,2,1]}"#).is_err());
+}
+
+#[test]
+fn flags() {
+    round_trip(&(core::MEM_READ_ONLY | core::MEM_COPY_HOST_PTR),
+        r#"["MEM_READ_ONLY","MEM_COPY_HOST_PTR"]"#);
+    round_trip(&MemFlags::empty(), r#"[]"#);
+    round_trip(&core::QUEUE_PROFILING_ENABLE, r#"["QUEUE_PROFILING_ENABLE"]"#);
+
+    // Order and repetition don't matter when reading:
+    let flags: MemFlags = serde_json::from_str(
+        r#"["MEM_COPY_HOST_PTR","MEM_READ_ONLY","MEM_READ_ONLY"]"#).unwrap();
+    assert_eq!(flags, core::MEM_READ_ONLY | core::MEM_COPY_HOST_PTR);
+
+    // Unknown names and raw bits:
+    let err = serde_json::from_str::<MemFlags>(r#"["MEM_READ_ONLY","MEM_FAST"]"#)
+        .unwrap_err().to_string();
+    assert!(err.contains("MEM_FAST"), "{}", err);
+    assert!(serde_json::from_str::<CommandQueueProperties>(r#"["MEM_READ_ONLY"]"#).is_err());
+    assert!(serde_json::from_str::<MemFlags>("5").is_err());
+}
+
+#[test]
+fn device_limits() {
+    let limits = DeviceLimits::new(&fixtures::nvidia_discrete()).unwrap();
+    let json = serde_json::to_string(&limits).unwrap();
+    assert_eq!(serde_json::from_str::<DeviceLimits>(&json).unwrap(), limits);
+    assert!(json.contains(&format!(r#""max_wg_size":{}"#, limits.max_wg_size())), "{}", json);
+}
@@ -0,0 +1,62 @@
+//! Launches a `SharedKernel` from many threads, each with its own scalar
+//! argument, checking that no launch uses another thread's argument.
+
+use std::sync::Arc;
+use std::thread;
+use standard::ProQue;
+
+static SRC: &'static str = r#"
+    __kernel void set(__global uint* buf, uint val) {
+        buf[get_global_id(0)] = val;
+    }
+"#;
+
+const THREAD_COUNT: usize = 8;
+const ITERS: usize = 50;
+const REGION_LEN: usize = 64;
+
+#[test]
+fn shared_kernel() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([REGION_LEN * THREAD_COUNT])
+        .build().unwrap();
+
+    let buffer = Arc::new(proque.create_buffer::<u32>().unwrap());
+    let kernel = Arc::new(proque.create_kernel("set").unwrap()
+        .gws([REGION_LEN])
+        .arg_buf(&*buffer)
+        .arg_scl_named::<u32>("val", None)
+        .into_shared());
+
+    let threads: Vec<_> = (0..THREAD_COUNT).map(|t| {
+        let buffer = buffer.clone();
+        let kernel = kernel.clone();
+
+        thread::spawn(move || {
+            let offset = t * REGION_LEN;
+            let mut result = vec![0u32; REGION_LEN];
+
+            for i in 0..ITERS {
+                let val = (t * ITERS + i) as u32;
+
+                kernel.with(|k| {
+                    k.set_arg_scl_named("val", val).unwrap();
+                    k.cmd().gwo([offset]).enq()
+                }).unwrap();
+
+                // Only this thread writes to its region:
+                buffer.read(&mut result).offset(offset).enq().unwrap();
+                assert!(result.iter().all(|&v| v == val), "thread: {}, iter: {}, result: {:?}",
+                    t, i, result);
+            }
+        })
+    }).collect();
+
+    for th in threads.into_iter() {
+        th.join().unwrap();
+    }
+
+    let kernel = Arc::try_unwrap(kernel).unwrap().into_inner();
+    assert_eq!(kernel.arg_count(), 2);
+}
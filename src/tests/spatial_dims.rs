@@ -0,0 +1,59 @@
+//! Tests for `SpatialDims` index and coordinate conversions.
+
+use rand::{self, Rng};
+use standard::SpatialDims;
+
+const ITERS: usize = 1000;
+
+fn rand_dims<R: Rng>(rng: &mut R) -> SpatialDims {
+    match rng.gen_range(1, 4) {
+        1 => SpatialDims::One(rng.gen_range(1, 4096)),
+        2 => SpatialDims::Two(rng.gen_range(1, 256), rng.gen_range(1, 256)),
+        _ => SpatialDims::Three(rng.gen_range(1, 64), rng.gen_range(1, 64), rng.gen_range(1, 64)),
+    }
+}
+
+#[test]
+fn index_coords_round_trip() {
+    let mut rng = rand::weak_rng();
+
+    for _ in 0..ITERS {
+        let dims = rand_dims(&mut rng);
+        let len = dims.to_len();
+        let idx = rng.gen_range(0, len);
+        let coords = dims.to_coords(idx).unwrap();
+
+        assert_eq!(dims.to_index(coords), Some(idx), "dims: {:?}, coords: {:?}", dims, coords);
+        assert_eq!(dims.to_coords(len), None);
+    }
+}
+
+#[test]
+fn index_coords_ordering() {
+    let dims = SpatialDims::Three(4, 3, 2);
+
+    assert_eq!(dims.to_index([1, 0, 0]), Some(1));
+    assert_eq!(dims.to_index([0, 1, 0]), Some(4));
+    assert_eq!(dims.to_index([0, 0, 1]), Some(12));
+    assert_eq!(dims.to_index([3, 2, 1]), Some(23));
+    assert_eq!(dims.to_index([4, 0, 0]), None);
+    assert_eq!(dims.to_index([0, 3, 0]), None);
+    assert_eq!(SpatialDims::Two(4, 3).to_index([0, 0, 1]), None);
+    assert_eq!(SpatialDims::Unspecified.to_index([0, 0, 0]), None);
+    assert_eq!(SpatialDims::Unspecified.to_coords(0), None);
+}
+
+#[test]
+fn coords_iter() {
+    let dims = SpatialDims::Three(5, 3, 2);
+    let mut count = 0;
+
+    for (idx, coords) in dims.coords_iter().enumerate() {
+        assert_eq!(dims.to_index(coords), Some(idx));
+        count += 1;
+    }
+
+    assert_eq!(count, dims.to_len());
+    assert_eq!(dims.coords_iter().len(), 30);
+    assert_eq!(SpatialDims::Unspecified.coords_iter().next(), None);
+}
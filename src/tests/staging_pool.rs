@@ -0,0 +1,68 @@
+//! Tests chunked transfers through a `StagingPool` on each device.
+
+use standard::{Buffer, StagingPool};
+use testing;
+
+const CHUNK_SIZE: usize = 1024;
+const CHUNK_LEN: usize = CHUNK_SIZE / 4;
+const CHUNK_COUNT: usize = 3;
+
+// Smaller than, equal to, and larger than (both evenly and unevenly) a
+// single chunk, including more chunks than the pool contains:
+const LENS: [usize; 6] = [1, CHUNK_LEN - 1, CHUNK_LEN, CHUNK_LEN + 1, CHUNK_LEN * 7,
+    CHUNK_LEN * 7 + 13];
+
+#[test]
+fn staging_pool() {
+    testing::for_each_device(|queue| {
+        let pool = try!(StagingPool::new(queue, CHUNK_SIZE, CHUNK_COUNT));
+        assert_eq!(pool.chunk_size(), CHUNK_SIZE);
+        assert_eq!(pool.chunk_count(), CHUNK_COUNT);
+
+        let buffer_len = LENS[LENS.len() - 1];
+
+        for (i, &len) in LENS.iter().enumerate() {
+            let buffer = try!(Buffer::<u32>::new(queue, None, [buffer_len], None));
+            let src: Vec<u32> = (0..len as u32).map(|v| v * 3 + i as u32).collect();
+            try!(pool.write(&buffer, &src));
+
+            // Compare with a direct read (the remainder must be untouched):
+            let mut direct = vec![0u32; buffer_len];
+            try!(buffer.read(&mut direct).enq());
+            assert_eq!(&direct[..len], &src[..], "len: {}", len);
+            assert!(direct[len..].iter().all(|&v| v == 0), "len: {}", len);
+
+            // Read back through the pool:
+            let mut staged = vec![0u32; len];
+            try!(pool.read(&buffer, &mut staged));
+            assert_eq!(staged, src, "len: {}", len);
+
+            // Read a buffer written directly:
+            let direct_src: Vec<u32> = (0..buffer_len as u32).rev().collect();
+            try!(buffer.write(&direct_src).enq());
+            try!(pool.read(&buffer, &mut staged));
+            assert_eq!(&staged[..], &direct_src[..len], "len: {}", len);
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn staging_pool_errors() {
+    testing::for_each_device(|queue| {
+        assert!(StagingPool::new(queue, 0, CHUNK_COUNT).is_err());
+        assert!(StagingPool::new(queue, CHUNK_SIZE, 0).is_err());
+
+        let pool = try!(StagingPool::new(queue, CHUNK_SIZE, CHUNK_COUNT));
+        let buffer = try!(Buffer::<u32>::new(queue, None, [16], None));
+
+        // Data longer than the buffer:
+        assert!(pool.write(&buffer, &[0u32; 17]).is_err());
+        assert!(pool.read(&buffer, &mut [0u32; 17]).is_err());
+
+        // Chunks smaller than a single element:
+        let pool = try!(StagingPool::new(queue, 2, CHUNK_COUNT));
+        assert!(pool.write(&buffer, &[0u32; 4]).is_err());
+        Ok(())
+    });
+}
@@ -0,0 +1,94 @@
+//! The stub backend: objects can be created, queried, and dropped, while
+//! anything which would execute returns `Error::StubBackend`.
+
+use std::sync::Arc;
+use core::{self, CommandExecutionStatus};
+use standard::{Platform, Device, Context, Queue, Buffer, Program};
+use enums::{ContextInfo, ContextInfoResult, MemInfo, MemInfoResult, EventInfo, EventInfoResult};
+use error::Error as OclError;
+
+fn context_ref_count(context: &Context) -> u32 {
+    match context.info(ContextInfo::ReferenceCount) {
+        ContextInfoResult::ReferenceCount(c) => c,
+        res => panic!("{:?}", res),
+    }
+}
+
+#[test]
+fn lifecycle() {
+    assert!(!core::is_opencl_available());
+
+    let platforms = Platform::list().unwrap();
+    assert_eq!(platforms.len(), 1);
+    assert!(platforms[0].name().contains("not conformant"));
+    assert_eq!(Device::list_all(&platforms[0]).unwrap().len(), 1);
+
+    let context = Context::builder().build().unwrap();
+    assert_eq!(context_ref_count(&context), 1);
+
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let buffer = Buffer::<f32>::builder().dims([256]).uninit().build(&queue).unwrap();
+    let program = Program::builder().src("__kernel void k() {}").devices(context.devices()[0]);
+
+    // The objects created hold references to the context:
+    assert!(context_ref_count(&context) > 1);
+
+    match buffer.mem_info(MemInfo::Size) {
+        MemInfoResult::Size(size) => assert_eq!(size, 256 * 4),
+        res => panic!("{:?}", res),
+    }
+
+    match program.build(&context) {
+        Err(OclError::StubBackend(fn_name)) => assert_eq!(fn_name, "clBuildProgram"),
+        res => panic!("Expected StubBackend, got: {:?}", res),
+    }
+
+    let mut data = vec![0.0f32; 256];
+    match buffer.read(&mut data).enq() {
+        Err(OclError::StubBackend(fn_name)) => assert_eq!(fn_name, "clEnqueueReadBuffer"),
+        res => panic!("Expected StubBackend, got: {:?}", res),
+    }
+
+    drop(buffer);
+    drop(queue);
+    assert_eq!(context_ref_count(&context), 1);
+}
+
+#[test]
+fn user_event() {
+    let context = Context::builder().build().unwrap();
+    let event = core::create_user_event(&context).unwrap();
+
+    match core::get_event_info(&event, EventInfo::CommandExecutionStatus) {
+        EventInfoResult::CommandExecutionStatus(s) => assert_eq!(s, CommandExecutionStatus::Submitted),
+        res => panic!("{:?}", res),
+    }
+
+    core::set_user_event_status(&event, CommandExecutionStatus::Complete).unwrap();
+
+    match core::get_event_info(&event, EventInfo::CommandExecutionStatus) {
+        EventInfoResult::CommandExecutionStatus(s) => assert_eq!(s, CommandExecutionStatus::Complete),
+        res => panic!("{:?}", res),
+    }
+
+    // Waiting is not supported:
+    match core::wait_for_event(&event) {
+        Err(OclError::StubBackend(_)) => (),
+        res => panic!("Expected StubBackend, got: {:?}", res),
+    }
+}
+
+#[test]
+fn retain_until_complete_marker_failure() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let payload = Arc::new(());
+
+    // The marker can not be enqueued so the payload is dropped (after
+    // finishing the queue) rather than leaked or retained:
+    match queue.retain_until_complete(payload.clone()) {
+        Err(OclError::StubBackend(_)) => (),
+        res => panic!("Expected StubBackend, got: {:?}", res),
+    }
+    assert_eq!(Arc::strong_count(&payload), 1);
+}
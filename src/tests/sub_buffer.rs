@@ -0,0 +1,68 @@
+//! Tests creating sub-buffers with `Buffer::create_sub_buffer`.
+
+use std::mem;
+use core::{BufferRegion, MemFlags};
+use flags;
+use standard::{Context, Queue, Buffer};
+
+#[test]
+fn buffer_region() {
+    let region = BufferRegion::from_elements::<f32>(16, 8).unwrap();
+    assert_eq!(region, BufferRegion { origin: 64, size: 32 });
+
+    let raw = region.to_raw();
+    assert_eq!((raw.origin, raw.size), (64, 32));
+
+    assert!(BufferRegion::from_elements::<u64>(::std::usize::MAX / 4, 1).is_err());
+    assert!(BufferRegion::from_elements::<u8>(::std::usize::MAX, 1).is_err());
+}
+
+#[test]
+fn invalid_regions() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let buffer = Buffer::<f32>::builder().dims([64]).uninit().build(&queue).unwrap();
+
+    // Each is refused before calling into the library:
+    for &(origin, len) in [(0, 0), (0, 65), (60, 8), (::std::usize::MAX, 2)].iter() {
+        let err = buffer.create_sub_buffer(MemFlags::empty(), origin, len).unwrap_err();
+        assert!(err.to_string().starts_with("ocl::Buffer::create_sub_buffer:"), "{}", err);
+    }
+}
+
+#[test]
+fn write_through_sub_buffer() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0]).unwrap();
+    let align = queue.device_limits().mem_base_addr_align_bytes() / mem::size_of::<u32>();
+    let len = align * 4;
+
+    let data: Vec<u32> = (0..len as u32).collect();
+    let buffer = Buffer::builder()
+        .flags(flags::MEM_READ_WRITE | flags::MEM_COPY_HOST_PTR)
+        .dims([len])
+        .build_with_data(&queue, &data).unwrap();
+
+    let sub_buffer = buffer.create_sub_buffer(MemFlags::empty(), align, align).unwrap();
+    assert_eq!(sub_buffer.len(), align);
+    assert_eq!(sub_buffer.size_bytes().unwrap(), align * mem::size_of::<u32>());
+    assert!(sub_buffer.parent_core_as_ref().is_some());
+    assert!(sub_buffer.create_sub_buffer(MemFlags::empty(), 0, 1).is_err());
+
+    // Reading through the sub-buffer starts at its origin:
+    assert_eq!(sub_buffer.read_to_vec().unwrap(), &data[align..align * 2]);
+
+    // Writes through the sub-buffer only modify its region of the parent:
+    sub_buffer.write_all(&vec![0u32; align]).unwrap();
+
+    let mut expected = data.clone();
+    for val in expected[align..align * 2].iter_mut() {
+        *val = 0;
+    }
+    assert_eq!(buffer.read_to_vec().unwrap(), expected);
+
+    // The sub-buffer outlives its parent:
+    drop(buffer);
+    sub_buffer.write_all(&vec![7u32; align]).unwrap();
+    assert_eq!(sub_buffer.read_to_vec().unwrap(), vec![7u32; align]);
+}
@@ -0,0 +1,265 @@
+//! The `testing` helpers: outcomes are recorded per device and failures
+//! (errors or panics) are summarized, and buffer comparisons report their
+//! mismatches.
+
+use std::f32;
+use std::ffi::CString;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use core;
+use error::Error as OclError;
+use standard::{Buffer, Kernel, Program};
+use testing::{self, Outcome, Comparison, Mismatch, Tolerance, MISMATCHES_REPORTED,
+    VerifiedKernel, ExpectedBuffer, Verification, BufferVerification};
+
+#[test]
+fn outcomes() {
+    let calls = AtomicUsize::new(0);
+    let runs = testing::run_on_each_device(|queue| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        queue.finish()
+    });
+
+    if runs.is_empty() {
+        println!("No platforms found. Skipping.");
+        return;
+    }
+
+    let passed = runs.iter().filter(|run| run.outcome == Outcome::Passed).count();
+    assert_eq!(passed, calls.load(Ordering::SeqCst));
+    assert!(testing::summary(&runs).ends_with(&format!("{} passed, 0 failed, {} skipped",
+        passed, runs.len() - passed)));
+
+    let runs = testing::run_on_each_device(|_| OclError::err("Closure error."));
+    for run in runs.iter().filter(|run| run.outcome != Outcome::Passed) {
+        match run.outcome {
+            Outcome::Failed(ref reason) => assert!(reason.contains("Closure error.")),
+            Outcome::Skipped(_) => (),
+            Outcome::Passed => unreachable!(),
+        }
+    }
+    assert!(runs.iter().all(|run| run.outcome != Outcome::Passed));
+
+    let runs = testing::run_on_each_device(|_| panic!("Closure panic."));
+    assert!(runs.iter().all(|run| match run.outcome {
+        Outcome::Failed(ref reason) => reason.contains("Panicked: Closure panic."),
+        Outcome::Skipped(_) => true,
+        Outcome::Passed => false,
+    }));
+}
+
+#[test]
+fn for_each_device_panics_on_failure() {
+    let runs = testing::for_each_device(|_| Ok(()));
+
+    if !runs.iter().any(|run| run.outcome == Outcome::Passed) {
+        println!("No usable devices found. Skipping.");
+        return;
+    }
+
+    let result = panic::catch_unwind(|| {
+        testing::for_each_device(|_| OclError::err("Closure error."));
+    });
+
+    let payload = result.err().expect("for_each_device did not panic");
+    let msg = payload.downcast_ref::<String>().unwrap();
+    assert!(msg.contains("[FAIL]") && msg.contains("Closure error."), "{}", msg);
+    assert!(msg.contains(&format!("0 passed, {} failed", runs.iter()
+        .filter(|run| run.outcome == Outcome::Passed).count())), "{}", msg);
+}
+
+#[test]
+fn comparison_report() {
+    let expected: Vec<i32> = (0..20).collect();
+    let mut actual = expected.clone();
+    for idx in 3..17 { actual[idx] = -1; }
+
+    let comparison = Comparison::new(&expected, &actual, |e, a| e == a);
+    assert!(!comparison.is_match());
+    assert_eq!(comparison.mismatch_count, 14);
+    assert_eq!(comparison.mismatches.len(), MISMATCHES_REPORTED);
+    assert_eq!(comparison.mismatches[0], Mismatch { index: 3, expected: 3, actual: -1 });
+
+    let report = comparison.report("exact");
+    let mut lines = report.lines();
+    assert_eq!(lines.next(), Some("14 of 20 elements differ (exact):"));
+    assert_eq!(lines.next(), Some("    [3]: expected: 3, actual: -1"));
+    assert_eq!(lines.clone().count(), MISMATCHES_REPORTED);
+    assert_eq!(lines.last(), Some("    ... and 4 more"));
+
+    let comparison = Comparison::new(&expected, &expected, |e, a| e == a);
+    assert!(comparison.is_match());
+    assert_eq!(comparison.report("exact"), "0 of 20 elements differ (exact):");
+}
+
+#[test]
+fn tolerance() {
+    let abs = Tolerance::new(0.5, 0.0);
+    assert!(abs.is_close(1.0, 1.5));
+    assert!(!abs.is_close(1.0, 1.5001));
+    assert!(!abs.is_close(1000.0, 1001.0));
+
+    // Relative to the larger magnitude:
+    let rel = Tolerance::new(0.0, 0.01);
+    assert!(rel.is_close(1000.0, 1010.0));
+    assert!(rel.is_close(1010.0, 1000.0));
+    assert!(!rel.is_close(1000.0, 1011.0));
+    assert!(!rel.is_close(0.0, 1e-30));
+
+    // Either tolerance suffices:
+    let both = Tolerance::new(1e-6, 1e-3);
+    assert!(both.is_close(0.0, 1e-7));
+    assert!(both.is_close(1e6, 1e6 + 999.0));
+
+    let inf = f64::from(f32::INFINITY);
+    assert!(both.is_close(inf, inf));
+    assert!(!both.is_close(inf, -inf));
+
+    let nan = f64::from(f32::NAN);
+    assert!(!both.is_close(nan, nan));
+    assert!(!both.is_close(nan, 1.0));
+    assert!(both.nan_eq(true).is_close(nan, nan));
+    assert!(!both.nan_eq(true).is_close(nan, 1.0));
+    assert!(!both.nan_eq(true).is_close(1.0, nan));
+}
+
+static SCALE_SRC: &'static str = r#"
+    __kernel void scale(__global float* buffer, float factor) {
+        buffer[get_global_id(0)] *= factor;
+    }
+"#;
+
+#[test]
+fn assert_buffers() {
+    testing::for_each_device(|queue| {
+        let len = 1024;
+        let src: Vec<f32> = (0..len).map(|i| i as f32 / 3.0).collect();
+        let expected: Vec<f32> = src.iter().map(|&v| v * 1.1).collect();
+
+        let program = try!(Program::new(vec![CString::new(SCALE_SRC).unwrap()],
+            CString::new("").unwrap(), queue.context_core_as_ref(), &[*queue.device()]));
+        let buffer = try!(Buffer::<f32>::new(queue, Some(core::MEM_READ_WRITE |
+            core::MEM_COPY_HOST_PTR), [len], Some(&src)));
+        let kernel = try!(Kernel::new("scale", &program, queue)).gws([len])
+            .arg_buf(&buffer).arg_scl(1.1f32);
+        try!(kernel.enq());
+
+        testing::assert_buffers_close(&expected, &buffer, 0.0, 1e-5);
+
+        let copy = try!(Buffer::<f32>::new(queue, None, [len], None));
+        try!(buffer.cmd().copy(&copy, 0, len).enq());
+        testing::assert_buffers_eq(&copy, &buffer);
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            testing::assert_buffers_eq(&src[..], &buffer)
+        }));
+        let payload = result.err().expect("assert_buffers_eq did not panic");
+        let msg = payload.downcast_ref::<String>().unwrap();
+        assert!(msg.starts_with("ocl::testing::assert_buffers_eq: "), "{}", msg);
+        assert!(msg.contains("elements differ (exact):\n    [1]: expected: "), "{}", msg);
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            testing::assert_buffers_eq(&src[..8], &buffer)
+        }));
+        let payload = result.err().expect("assert_buffers_eq did not panic");
+        let msg = payload.downcast_ref::<String>().unwrap();
+        assert!(msg.contains("Lengths differ (expected: 8, actual: 1024)"), "{}", msg);
+        Ok(())
+    });
+}
+
+#[test]
+fn verification_report() {
+    let matching = BufferVerification { arg_idx: 0, report: None };
+    let verification = Verification { buffers: vec![matching.clone(), matching.clone()] };
+    assert!(verification.is_match());
+    assert_eq!(verification.report(), "All 2 output buffers match.");
+    verification.assert();
+
+    let verification = Verification { buffers: vec![matching, BufferVerification { arg_idx: 2,
+        report: Some("1 of 4 elements differ (exact):\n    [3]: expected: 1, actual: 2".into()) }] };
+    assert!(!verification.is_match());
+    assert_eq!(verification.to_string(), "1 of 2 output buffers differ:\n  argument [2]: 1 of 4 \
+        elements differ (exact):\n      [3]: expected: 1, actual: 2");
+
+    let result = panic::catch_unwind(|| verification.assert());
+    let payload = result.err().expect("Verification::assert did not panic");
+    let msg = payload.downcast_ref::<String>().unwrap();
+    assert!(msg.contains("argument [2]"), "{}", msg);
+}
+
+static MAP_SRC: &'static str = r#"
+    __kernel void correct(__global const float* input, __global float* output, float factor) {
+        uint idx = get_global_id(0);
+        output[idx] = input[idx] * factor;
+    }
+
+    __kernel void wrong(__global const float* input, __global float* output, float factor) {
+        uint idx = get_global_id(0);
+        output[idx] = input[idx] * factor + (idx % 100 == 7 ? 0.5f : 0.0f);
+    }
+"#;
+
+#[test]
+fn verified_kernel() {
+    testing::for_each_device(|queue| {
+        let len = 1024;
+        let src: Vec<f32> = (0..len).map(|i| i as f32 / 3.0).collect();
+
+        let program = try!(Program::new(vec![CString::new(MAP_SRC).unwrap(),
+            CString::new(SCALE_SRC).unwrap()], CString::new("").unwrap(),
+            queue.context_core_as_ref(), &[*queue.device()]));
+        let input = try!(Buffer::<f32>::new(queue, Some(core::MEM_READ_WRITE |
+            core::MEM_COPY_HOST_PTR), [len], Some(&src)));
+        let output = try!(Buffer::<f32>::new(queue, None, [len], None));
+
+        let host = |args: &testing::KernelArgsSnapshot| {
+            let input = args.buffer::<f32>(0).unwrap();
+            let factor = args.scalar::<f32>(2).unwrap();
+            vec![ExpectedBuffer::new(1, input.iter().map(|&v| v * factor).collect())]
+        };
+
+        let kernel = try!(VerifiedKernel::new(try!(Kernel::new("correct", &program, queue))
+            .gws([len]), host))
+            .arg_buf(&input).arg_buf(&output).arg_scl(1.1f32);
+        let verification = try!(kernel.enq());
+        assert!(verification.is_match(), "{}", verification);
+
+        let kernel = try!(VerifiedKernel::new(try!(Kernel::new("wrong", &program, queue))
+            .gws([len]), host))
+            .arg_buf(&input).arg_buf(&output).arg_scl(1.1f32)
+            .tolerance(Tolerance::new(1e-3, 1e-5));
+        let verification = try!(kernel.enq());
+        assert!(!verification.is_match());
+        let report = verification.report();
+        assert!(report.contains("argument [1]: 11 of 1024 elements differ"), "{}", report);
+        assert!(report.contains("[7]: expected: ") && report.contains("[107]: expected: "),
+            "{}", report);
+
+        // Inputs are captured before launch (this kernel scales in place):
+        let kernel = try!(VerifiedKernel::new(try!(Kernel::new("scale", &program, queue))
+            .gws([len]), |args| {
+                let buffer = args.buffer::<f32>(0).unwrap();
+                let factor = args.scalar::<f32>(1).unwrap();
+                vec![ExpectedBuffer::new(0, buffer.iter().map(|&v| v * factor).collect())]
+            }))
+            .arg_buf(&input).arg_scl(2.0f32);
+        try!(kernel.enq()).assert();
+
+        // Expected values of the wrong type or for a non-buffer argument:
+        let kernel = try!(VerifiedKernel::new(try!(Kernel::new("scale", &program, queue))
+            .gws([len]), |_| vec![ExpectedBuffer::exact(0, vec![0u32; 1024])]))
+            .arg_buf(&input).arg_scl(2.0f32);
+        assert!(kernel.enq().is_err());
+
+        let kernel = try!(VerifiedKernel::new(try!(Kernel::new("scale", &program, queue))
+            .gws([len]), |_| vec![ExpectedBuffer::new(1, vec![0.0f32])]))
+            .arg_buf(&input).arg_scl(2.0f32);
+        assert!(kernel.enq().is_err());
+
+        // Arguments must be set through the wrapper:
+        let set = try!(Kernel::new("scale", &program, queue)).arg_buf(&input);
+        assert!(VerifiedKernel::new(set, |_| Vec::new()).is_err());
+        Ok(())
+    });
+}
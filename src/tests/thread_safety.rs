@@ -0,0 +1,67 @@
+//! Shares a context, queue, program, and buffer among several threads and
+//! moves a kernel to another thread.
+
+use std::sync::Arc;
+use std::thread;
+use standard::{ProQue, Kernel};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global uint* buf, uint addend) {
+        buf[get_global_id(0)] += addend;
+    }
+"#;
+
+const THREAD_COUNT: usize = 4;
+const REGION_LEN: usize = 256;
+
+#[test]
+fn thread_safety() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([REGION_LEN * THREAD_COUNT])
+        .build().unwrap();
+
+    let buffer = Arc::new(proque.create_buffer::<u32>().unwrap());
+    let mut threads = Vec::with_capacity(THREAD_COUNT);
+
+    // Each thread writes, processes, and reads back its own region of the
+    // shared buffer using its own kernel:
+    for i in 0..THREAD_COUNT {
+        let proque = proque.clone();
+        let buffer = buffer.clone();
+
+        threads.push(thread::spawn(move || {
+            let offset = i * REGION_LEN;
+            let src = vec![i as u32; REGION_LEN];
+            buffer.write(&src).offset(offset).enq().unwrap();
+
+            let kernel = Kernel::new("add", proque.program(), proque.queue()).unwrap()
+                .gwo([offset])
+                .gws([REGION_LEN])
+                .arg_buf(&*buffer)
+                .arg_scl(100u32);
+            kernel.enq().unwrap();
+
+            let mut result = vec![0u32; REGION_LEN];
+            buffer.read(&mut result).offset(offset).enq().unwrap();
+            assert!(result.iter().all(|&v| v == i as u32 + 100), "thread: {}", i);
+        }));
+    }
+
+    for th in threads.into_iter() {
+        th.join().unwrap();
+    }
+
+    // A kernel created here may be used on another thread:
+    let kernel = proque.create_kernel("add").unwrap()
+        .arg_buf(&*buffer)
+        .arg_scl(1u32);
+    thread::spawn(move || kernel.enq().unwrap()).join().unwrap();
+
+    let mut result = vec![0u32; REGION_LEN * THREAD_COUNT];
+    buffer.read(&mut result).enq().unwrap();
+
+    for (idx, &val) in result.iter().enumerate() {
+        assert_eq!(val, (idx / REGION_LEN) as u32 + 101);
+    }
+}
@@ -0,0 +1,69 @@
+//! Tests device and host timers and translating event profiling timestamps
+//! to the host clock with `TimelineTranslator`.
+
+use error::Error as OclError;
+use standard::{Context, Queue, Buffer, Event, TimelineTranslator};
+
+#[test]
+fn translate() {
+    // One sample, a constant offset:
+    let translator = TimelineTranslator::from_samples(&[(1000, 5000)]).unwrap();
+    assert_eq!(translator.to_host(1000), 5000);
+    assert_eq!(translator.to_host(1500), 5500);
+    assert_eq!(translator.to_host(0), 4000);
+
+    // Host clock running at twice the rate of the device clock, samples out
+    // of order:
+    let translator = TimelineTranslator::from_samples(&[(2000, 7000), (1000, 5000),
+        (3000, 9000)]).unwrap();
+    assert_eq!(translator.samples(), &[(1000, 5000), (2000, 7000), (3000, 9000)]);
+    assert_eq!(translator.to_host(1500), 6000);
+    assert_eq!(translator.to_host(2000), 7000);
+    assert_eq!(translator.to_host(4000), 11000);
+    assert_eq!(translator.to_host(500), 4000);
+
+    // Never negative:
+    let translator = TimelineTranslator::from_samples(&[(5000, 1000)]).unwrap();
+    assert_eq!(translator.to_host(0), 0);
+
+    assert!(TimelineTranslator::from_samples(&[]).is_err());
+    assert!(TimelineTranslator::from_samples(&[(0, 0)]).unwrap().resample().is_err());
+}
+
+#[test]
+fn event_spans() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+    let queue = Queue::new(&context, device).unwrap();
+
+    let mut translator = match TimelineTranslator::new(device) {
+        Ok(translator) => translator,
+        Err(OclError::VersionUnsupported { found, .. }) => {
+            // Pre-2.1 platforms are refused before calling into the library:
+            assert!(found < (2, 1));
+            assert!(device.host_timer().is_err());
+            return;
+        },
+        Err(err) => panic!("{}", err),
+    };
+
+    let buffer = Buffer::<f32>::builder().dims([1 << 20]).uninit().build(&queue).unwrap();
+
+    let host_before = device.host_timer().unwrap();
+    let mut event = Event::empty();
+    buffer.cmd().fill(&[1.0], None).enew(&mut event).enq().unwrap();
+    event.wait().unwrap();
+    let host_after = device.host_timer().unwrap();
+
+    translator.resample().unwrap();
+    let spans = translator.event_spans(&event).unwrap();
+
+    assert!(host_before <= spans.queued, "{} > {:?}", host_before, spans);
+    assert!(spans.queued <= spans.submit && spans.submit <= spans.start &&
+        spans.start <= spans.end, "{:?}", spans);
+    assert!(spans.end <= host_after, "{:?} > {}", spans, host_after);
+
+    for &(_, begin, end) in spans.spans().iter() {
+        assert!(begin <= end);
+    }
+}
@@ -0,0 +1,86 @@
+//! Tests that reads and writes produce identical results whether performed
+//! by copying or by mapping (`TransferMode`).
+
+use flags;
+use core::MemFlags;
+use standard::{ProQue, Buffer, Event, TransferMode};
+
+const LEN: usize = 1 << 12;
+const OFFSET: usize = 100;
+
+const MODES: [TransferMode; 3] = [TransferMode::Auto, TransferMode::Copy, TransferMode::Map];
+
+fn check_modes(proque: &ProQue, flags: MemFlags) {
+    let mut buffer = Buffer::<f32>::builder()
+        .flags(flags)
+        .dims([LEN])
+        .build(proque.queue()).unwrap();
+
+    assert_eq!(buffer.transfer_mode(), TransferMode::Auto);
+
+    for (i, &write_mode) in MODES.iter().enumerate() {
+        let src: Vec<f32> = (0..LEN).map(|v| (v * (i + 1)) as f32).collect();
+        buffer.set_transfer_mode(write_mode);
+        buffer.write(&src).enq().unwrap();
+
+        for &read_mode in MODES.iter() {
+            let mut dst = vec![0.0f32; LEN];
+            buffer.read(&mut dst).transfer_mode(read_mode).enq().unwrap();
+            assert!(dst == src, "flags: {:?}, write: {:?}, read: {:?}", flags, write_mode,
+                read_mode);
+
+            // Partial reads at an offset:
+            let mut part = vec![0.0f32; LEN - OFFSET * 2];
+            buffer.read(&mut part).offset(OFFSET).transfer_mode(read_mode).enq().unwrap();
+            assert!(&part[..] == &src[OFFSET..LEN - OFFSET], "flags: {:?}, write: {:?}, \
+                read: {:?}", flags, write_mode, read_mode);
+        }
+
+        // Partial writes at an offset, with a new event:
+        let part = vec![-1.0f32; OFFSET];
+        let mut event = Event::empty();
+        buffer.write(&part).offset(OFFSET).enew(&mut event).enq().unwrap();
+        event.wait().unwrap();
+
+        let mut dst = vec![0.0f32; LEN];
+        buffer.read(&mut dst).transfer_mode(TransferMode::Copy).enq().unwrap();
+        assert!(&dst[..OFFSET] == &src[..OFFSET]);
+        assert!(dst[OFFSET..OFFSET * 2].iter().all(|&v| v == -1.0));
+        assert!(&dst[OFFSET * 2..] == &src[OFFSET * 2..]);
+    }
+}
+
+#[test]
+fn transfer_mode() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([LEN])
+        .build().unwrap();
+
+    check_modes(&proque, flags::MEM_READ_WRITE);
+    check_modes(&proque, flags::MEM_READ_WRITE | flags::MEM_ALLOC_HOST_PTR);
+}
+
+#[test]
+fn transfer_mode_use_host_ptr() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([LEN])
+        .build().unwrap();
+
+    let host_data = vec![3.0f32; LEN];
+    let buffer = Buffer::<f32>::builder()
+        .flags(flags::MEM_READ_WRITE | flags::MEM_USE_HOST_PTR)
+        .dims([LEN])
+        .transfer_mode(TransferMode::Map)
+        .build_with_data(proque.queue(), &host_data).unwrap();
+
+    let mut dst = vec![0.0f32; LEN];
+    buffer.read(&mut dst).enq().unwrap();
+    assert!(dst == host_data);
+
+    let src: Vec<f32> = (0..LEN).map(|v| v as f32).collect();
+    buffer.write(&src).enq().unwrap();
+    buffer.read(&mut dst).transfer_mode(TransferMode::Copy).enq().unwrap();
+    assert!(dst == src);
+}
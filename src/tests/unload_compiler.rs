@@ -0,0 +1,29 @@
+//! Tests that programs can still be built after unloading the compiler.
+
+use standard::{Platform, ProQue};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buf, float val) {
+        buf[get_global_id(0)] += val;
+    }
+"#;
+
+#[test]
+fn unload_compiler() {
+    let platform = Platform::default();
+
+    let proque = ProQue::builder().platform(platform).src(SRC).dims([64]).build().unwrap();
+    let buffer = proque.create_buffer::<f32>().unwrap();
+    proque.create_kernel("add").unwrap().arg_buf(&buffer).arg_scl(1.0f32).enq().unwrap();
+
+    platform.unload_compiler().unwrap();
+
+    // Building again reloads the compiler:
+    let proque = ProQue::builder().platform(platform).src(SRC).dims([64]).build().unwrap();
+    let buffer = proque.create_buffer::<f32>().unwrap();
+    proque.create_kernel("add").unwrap().arg_buf(&buffer).arg_scl(2.0f32).enq().unwrap();
+
+    let mut vec = vec![0.0f32; 64];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 2.0));
+}
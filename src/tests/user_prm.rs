@@ -0,0 +1,66 @@
+//! Tests `impl_ocl_prm!` with a user-defined struct used as a buffer element
+//! and kernel argument.
+
+use standard::ProQue;
+use vectors::ClFloat4;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+struct Particle {
+    pos: ClFloat4,
+    vel: ClFloat4,
+    mass: f32,
+    _pad: [f32; 3],
+}
+
+impl_ocl_prm!(unsafe Particle => 48);
+
+static SRC: &'static str = r#"
+    typedef struct Particle {
+        float4 pos;
+        float4 vel;
+        float mass;
+    } Particle;
+
+    __kernel void advance(__global Particle* particles, Particle gravity, float dt) {
+        uint idx = get_global_id(0);
+        particles[idx].pos += (particles[idx].vel + gravity.vel) * dt;
+    }
+"#;
+
+#[test]
+fn user_prm_buffer_kernel() {
+    let pro_que = ProQue::builder().src(SRC).dims([256]).build().unwrap();
+    let gravity = Particle { vel: ClFloat4([0.0, -10.0, 0.0, 0.0]), ..Default::default() };
+    let dt = 0.5f32;
+
+    let vec_init: Vec<Particle> = (0..256).map(|i| {
+        let i = i as f32;
+        Particle {
+            pos: ClFloat4([i, i, i, 0.0]),
+            vel: ClFloat4([1.0, 2.0, 3.0, 0.0]),
+            mass: i * 2.0,
+            _pad: [0.0; 3],
+        }
+    }).collect();
+
+    let buffer = pro_que.create_buffer::<Particle>().unwrap();
+    buffer.write(&vec_init).enq().unwrap();
+
+    let kernel = pro_que.create_kernel("advance").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(gravity)
+        .arg_scl(dt);
+    kernel.enq().unwrap();
+
+    let mut vec_result = vec![Particle::default(); 256];
+    buffer.read(&mut vec_result).enq().unwrap();
+
+    for (init, result) in vec_init.iter().zip(vec_result.iter()) {
+        for c in 0..4 {
+            assert_eq!(result.pos[c], init.pos[c] + (init.vel[c] + gravity.vel[c]) * dt);
+        }
+        assert_eq!(result.vel, init.vel);
+        assert_eq!(result.mass, init.mass);
+    }
+}
@@ -0,0 +1,72 @@
+//! Tests `util::vec_cast`, `util::slice_cast`, and `util::slice_cast_mut`.
+
+use core::ClFloat4;
+use util::{vec_cast, slice_cast, slice_cast_mut};
+
+#[test]
+fn vec_cast_f32_u8_round_trip() {
+    let floats = vec![1.0f32, -2.5, 3.25, 0.0, 1.0e-7];
+    let bytes: Vec<u8> = vec_cast(floats.clone()).unwrap();
+    assert_eq!(bytes.len(), floats.len() * 4);
+    assert_eq!(&bytes[..4], &1.0f32.to_ne_bytes()[..]);
+
+    let round_trip: Vec<f32> = vec_cast(bytes).unwrap();
+    assert_eq!(round_trip, floats);
+}
+
+#[test]
+fn vec_cast_in_place() {
+    // Same alignment and a compatible capacity reuses the allocation:
+    let mut ints = Vec::with_capacity(8);
+    ints.extend_from_slice(&[1u32, 2, 3, 4]);
+    let (ptr, cap) = (ints.as_ptr() as usize, ints.capacity());
+    let floats: Vec<f32> = vec_cast(ints).unwrap();
+    assert_eq!(floats.as_ptr() as usize, ptr);
+    assert_eq!(floats.capacity(), cap);
+    assert_eq!(floats[0].to_bits(), 1);
+
+    let vecs: Vec<ClFloat4> = vec_cast(vec![0.0f32; 8]).unwrap();
+    assert_eq!(vecs.len(), 2);
+}
+
+#[test]
+fn vec_cast_misaligned_len() {
+    let bytes = vec![0u8; 12];
+    let err = vec_cast::<u8, f64>(bytes).unwrap_err();
+    assert!(err.to_string().contains("expected a multiple of 8 bytes, actual: 12 bytes"));
+
+    assert!(vec_cast::<u8, f64>(vec![0u8; 16]).is_ok());
+    assert!(vec_cast::<u8, f64>(Vec::new()).unwrap().is_empty());
+}
+
+#[test]
+fn slice_cast_checks() {
+    let ints = [0x01020304u32, 0x05060708, 0x090a0b0c];
+    let bytes: &[u8] = slice_cast(&ints[..]).unwrap();
+    assert_eq!(bytes.len(), 12);
+    assert_eq!(&bytes[..4], &0x01020304u32.to_ne_bytes()[..]);
+
+    let back: &[u32] = slice_cast(bytes).unwrap();
+    assert_eq!(back, &ints[..]);
+
+    // Misaligned start and length:
+    assert!(slice_cast::<u8, u32>(&bytes[1..9]).is_err());
+    assert!(slice_cast::<u8, u32>(&bytes[..6]).is_err());
+    assert!(slice_cast::<u8, f64>(bytes).is_err());
+}
+
+#[test]
+fn slice_cast_mut_writes() {
+    let mut floats = [0.0f32; 4];
+    {
+        let ints: &mut [u32] = slice_cast_mut(&mut floats[..]).unwrap();
+        ints[2] = 1.5f32.to_bits();
+    }
+    assert_eq!(floats, [0.0, 0.0, 1.5, 0.0]);
+
+    let mut bytes = [0u32; 2];
+    let bytes: &mut [u8] = slice_cast_mut(&mut bytes[..]).unwrap();
+    assert!(slice_cast_mut::<u8, u16>(&mut bytes[1..3]).is_err());
+    assert!(slice_cast_mut::<u8, u16>(&mut bytes[..3]).is_err());
+    assert_eq!(slice_cast_mut::<u8, u16>(&mut bytes[2..6]).unwrap().len(), 2);
+}
@@ -0,0 +1,116 @@
+//! Tests the OpenCL vector types as buffer elements and kernel arguments.
+
+use std::mem;
+use std::slice;
+use standard::ProQue;
+use vectors::{AsClSlice, ClFloat3, ClFloat4, ClUint2, ClUchar2, ClDouble16};
+
+fn bytes<T>(vals: &[T]) -> &[u8] {
+    unsafe { slice::from_raw_parts(vals.as_ptr() as *const u8, vals.len() * mem::size_of::<T>()) }
+}
+
+static SRC: &'static str = r#"
+    __kernel void scale_add(__global float4* buffer, float4 scale) {
+        uint idx = get_global_id(0);
+        float4 val = buffer[idx];
+        buffer[idx] = (float4)(val.x * scale.x, val.y + scale.y, val.z - scale.z, val.w * val.w);
+    }
+"#;
+
+#[test]
+fn vector_types_layout() {
+    assert_eq!(mem::size_of::<ClFloat3>(), 16);
+    assert_eq!(mem::align_of::<ClFloat3>(), 16);
+    assert_eq!(mem::size_of::<ClUint2>(), 8);
+    assert_eq!(mem::align_of::<ClDouble16>(), 128);
+
+    let mut vec = ClFloat4::from([1.0, 2.0, 3.0, 4.0]);
+    vec[2] = 7.0;
+    assert_eq!(vec, ClFloat4([1.0, 2.0, 7.0, 4.0]));
+    assert_eq!(<[f32; 4]>::from(vec)[2], 7.0);
+    assert_eq!(ClUint2::default(), ClUint2([0, 0]));
+}
+
+#[test]
+fn vector_types_buffer_kernel() {
+    let pro_que = ProQue::builder().src(SRC).dims([1024]).build().unwrap();
+    let scale = ClFloat4([2.0, 10.0, 0.5, 0.0]);
+
+    let vec_init: Vec<ClFloat4> = (0..1024).map(|i| {
+        let i = i as f32;
+        ClFloat4([i, i + 1.0, i + 2.0, i + 3.0])
+    }).collect();
+
+    let buffer = pro_que.create_buffer::<ClFloat4>().unwrap();
+    buffer.write(&vec_init).enq().unwrap();
+
+    let kernel = pro_que.create_kernel("scale_add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(scale);
+    kernel.enq().unwrap();
+
+    let mut vec_result = vec![ClFloat4::default(); 1024];
+    buffer.read(&mut vec_result).enq().unwrap();
+
+    for (init, result) in vec_init.iter().zip(vec_result.iter()) {
+        assert_eq!(result[0], init[0] * scale[0]);
+        assert_eq!(result[1], init[1] + scale[1]);
+        assert_eq!(result[2], init[2] - scale[2]);
+        assert_eq!(result[3], init[3] * init[3]);
+    }
+}
+
+#[test]
+fn vector_types_conversions() {
+    let arrays: Vec<[f32; 4]> = (0..64).map(|i| {
+        let i = i as f32;
+        [i, -i, i * 0.5, i * 2.0]
+    }).collect();
+
+    let vecs: Vec<ClFloat4> = arrays.iter().map(|&a| ClFloat4::from(a)).collect();
+    assert_eq!(bytes(&vecs), bytes(&arrays));
+    assert_eq!(ClFloat4::as_array_slice(&vecs), &arrays[..]);
+
+    let tuples: Vec<(f32, f32, f32, f32)> = vecs.iter().map(|&v| v.into()).collect();
+    let vecs_from_tuples: Vec<ClFloat4> = tuples.into_iter().map(ClFloat4::from).collect();
+    assert_eq!(bytes(&vecs_from_tuples), bytes(&arrays));
+
+    assert_eq!(ClUint2::from((3, 4)), ClUint2([3, 4]));
+    assert_eq!(<(f32, f32, f32)>::from(ClFloat3([1.0, 2.0, 3.0])), (1.0, 2.0, 3.0));
+}
+
+#[test]
+fn vector_types_as_cl_slice() {
+    // Arrays stored within vectors are always suitably aligned:
+    let vecs: Vec<ClFloat4> = (0..64).map(|i| ClFloat4([i as f32; 4])).collect();
+    let arrays = ClFloat4::as_array_slice(&vecs);
+    let cast: &[ClFloat4] = arrays.as_cl_slice().unwrap();
+    assert_eq!(cast.as_ptr(), vecs.as_ptr());
+    assert_eq!(bytes(cast), bytes(arrays));
+
+    // Misaligned arrays are rejected:
+    #[repr(C, align(4))]
+    struct Misaligned { _pad: u8, arrays: [[u8; 2]; 4] }
+    let misaligned = Misaligned { _pad: 0, arrays: [[1, 2]; 4] };
+    assert!(AsClSlice::<ClUchar2>::as_cl_slice(&misaligned.arrays[..]).is_none());
+}
+
+#[cfg(feature = "mint")]
+#[test]
+fn vector_types_mint() {
+    use mint::{Vector3, Vector4};
+
+    let mint_vecs: Vec<Vector4<f32>> = (0..64).map(|i| {
+        let i = i as f32;
+        Vector4 { x: i, y: i + 1.0, z: i + 2.0, w: i + 3.0 }
+    }).collect();
+
+    let vecs: Vec<ClFloat4> = mint_vecs.iter().map(|&v| ClFloat4::from(v)).collect();
+    assert_eq!(bytes(&vecs), bytes(&mint_vecs));
+
+    let back: Vector4<f32> = vecs[5].into();
+    assert_eq!(back, mint_vecs[5]);
+
+    let vec3 = ClFloat3::from(Vector3 { x: 1.0, y: 2.0, z: 3.0 });
+    assert_eq!(vec3, ClFloat3([1.0, 2.0, 3.0]));
+}
@@ -0,0 +1,87 @@
+//! Tests waiting on slices of events (`core::wait_for_events`).
+
+use core::{self, CommandExecutionStatus};
+use cl_h;
+use error::Error as OclError;
+use enums::Status;
+use standard::{Context, Event, ProQue};
+
+#[test]
+fn wait_for_events_empty() {
+    core::wait_for_events(&[]).unwrap();
+}
+
+#[test]
+fn wait_for_events() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([64])
+        .build().unwrap();
+
+    let kernel = proque.create_kernel("nop").unwrap();
+    let mut events = Vec::new();
+
+    for _ in 0..4 {
+        let mut event = Event::empty();
+        kernel.cmd().enew(&mut event).enq().unwrap();
+        events.push(event.core_as_ref().unwrap().clone());
+    }
+
+    let user_event = core::create_user_event(proque.context().core_as_ref()).unwrap();
+    core::set_user_event_status(&user_event, CommandExecutionStatus::Complete).unwrap();
+    events.push(user_event);
+
+    core::wait_for_events(&events).unwrap();
+
+    for event in events.iter() {
+        assert_eq!(core::get_event_status(event).unwrap(), CommandExecutionStatus::Complete);
+    }
+
+    // Delegates to `core::wait_for_events`:
+    let event = unsafe { Event::from_core(events.pop().unwrap()) };
+    event.wait().unwrap();
+}
+
+#[test]
+fn wait_for_events_failed() {
+    let context = Context::builder().build().unwrap();
+
+    let events: Vec<_> = (0..3).map(|_| {
+        core::create_user_event(context.core_as_ref()).unwrap()
+    }).collect();
+
+    core::set_user_event_status(&events[0], CommandExecutionStatus::Complete).unwrap();
+    core::set_user_event_status(&events[2], CommandExecutionStatus::Complete).unwrap();
+
+    // Terminate the second event abnormally:
+    let errcode = unsafe { cl_h::clSetUserEventStatus(*events[1].as_ptr_ref(),
+        Status::CL_INVALID_VALUE as i32) };
+    assert_eq!(errcode, Status::CL_SUCCESS as i32);
+
+    match core::wait_for_events(&events) {
+        Err(OclError::Status { status: Status::CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST,
+                fn_info, .. }) => {
+            assert!(fn_info.contains("[1]: CL_INVALID_VALUE"), "{}", fn_info);
+            assert!(!fn_info.contains("[0]") && !fn_info.contains("[2]"), "{}", fn_info);
+        },
+        res => panic!("Unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn wait_for_events_mixed_contexts() {
+    let context_a = Context::builder().build().unwrap();
+    let context_b = Context::builder().build().unwrap();
+
+    let events = [
+        core::create_user_event(context_a.core_as_ref()).unwrap(),
+        core::create_user_event(context_b.core_as_ref()).unwrap(),
+    ];
+
+    for event in events.iter() {
+        core::set_user_event_status(event, CommandExecutionStatus::Complete).unwrap();
+    }
+
+    let err = core::wait_for_events(&events).unwrap_err();
+    assert!(err.to_string().contains("same context"), "{}", err);
+}
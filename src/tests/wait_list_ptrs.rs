@@ -0,0 +1,88 @@
+//! Tests that wait lists are passed to the core enqueue functions directly
+//! from the caller's storage and that doing so does not change behavior.
+
+use core::{self, ClWaitList, Event as EventCore, EventList as EventListCore,
+    CommandExecutionStatus};
+use standard::{ProQue, Buffer, Event, EventList};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global uint* buf, uint addend) {
+        buf[get_global_id(0)] += addend;
+    }
+"#;
+
+const LEN: usize = 256;
+const ITERS: usize = 8;
+
+#[test]
+fn wait_list_ptrs_zero_copy() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    let events: Vec<EventCore> = (0..4).map(|_| {
+        let event = core::create_user_event(proque.context().core_as_ref()).unwrap();
+        core::set_user_event_status(&event, CommandExecutionStatus::Complete).unwrap();
+        event
+    }).collect();
+
+    let slice = &events[..];
+    assert_eq!(slice.count(), 4);
+    assert_eq!(events.count(), 4);
+
+    unsafe {
+        assert_eq!(slice.as_ptr_ptr(), events[0].as_ptr_ref() as *const _);
+        assert_eq!(events.as_ptr_ptr(), events[0].as_ptr_ref() as *const _);
+
+        for (i, event) in events.iter().enumerate() {
+            assert_eq!(*slice.as_ptr_ptr().offset(i as isize), *event.as_ptr_ref());
+        }
+    }
+
+    let empty: Vec<EventCore> = Vec::new();
+    assert_eq!(empty.count(), 0);
+    assert!(unsafe { empty.as_ptr_ptr().is_null() });
+}
+
+#[test]
+fn wait_list_ptrs_enqueue() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    let buffer = Buffer::<u32>::new(proque.queue(), None, [LEN], None).unwrap();
+    let kernel = proque.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1u32);
+
+    // Chain each command on the events of the previous, using a `Vec` of
+    // core events, an `EventList`, and a single `Event` as wait lists:
+    let mut core_events: Vec<EventCore> = Vec::new();
+
+    for _ in 0..ITERS {
+        let mut new_event = unsafe { EventCore::null() };
+        kernel.cmd().ewait(&core_events).enew(&mut new_event).enq().unwrap();
+        core_events = vec![new_event];
+    }
+
+    let mut event_list = EventList::new();
+    kernel.cmd().ewait(&core_events).enew(&mut event_list).enq().unwrap();
+
+    for _ in 1..ITERS {
+        let wait_list = event_list.clone();
+        kernel.cmd().ewait(&wait_list).enew(&mut event_list).enq().unwrap();
+    }
+
+    let mut event = Event::empty();
+    kernel.cmd().ewait(&event_list).enew(&mut event).enq().unwrap();
+    event.wait().unwrap();
+
+    let mut vec = vec![0u32; LEN];
+    buffer.read(&mut vec).ewait(&event).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == (ITERS * 2 + 1) as u32));
+
+    let event_list_core: &EventListCore = event_list.core_as_ref();
+    assert_eq!(event_list_core.count(), ITERS as u32);
+}
@@ -0,0 +1,127 @@
+//! Tests waiting on events and queues with a timeout.
+
+use std::time::{Duration, Instant};
+use core::{self, CommandExecutionStatus};
+use cl_h;
+use enums::{Status, WaitOutcome};
+use standard::{Context, Event, EventList, ProQue};
+
+static SRC: &'static str = r#"
+    __kernel void spin(__global uint* buf, uint iters) {
+        uint idx = get_global_id(0);
+        uint val = buf[idx];
+
+        for (uint i = 0; i < iters; i++) {
+            val = val * 1664525u + 1013904223u;
+        }
+
+        buf[idx] = val;
+    }
+"#;
+
+const SHORT: Duration = Duration::from_millis(20);
+const LONG: Duration = Duration::from_secs(30);
+
+#[test]
+fn user_event_timeout() {
+    let context = Context::builder().build().unwrap();
+    let user_event = core::create_user_event(context.core_as_ref()).unwrap();
+    let event = unsafe { Event::from_core(user_event.clone()) };
+
+    let start = Instant::now();
+    assert_eq!(event.wait_timeout(SHORT).unwrap(), WaitOutcome::TimedOut);
+    assert!(start.elapsed() >= SHORT);
+
+    core::set_user_event_status(&user_event, CommandExecutionStatus::Complete).unwrap();
+    assert_eq!(event.wait_timeout(LONG).unwrap(), WaitOutcome::Complete);
+
+    // Already complete:
+    assert!(event.wait_timeout(Duration::from_millis(0)).unwrap().is_complete());
+}
+
+#[test]
+fn user_event_failed() {
+    let context = Context::builder().build().unwrap();
+    let user_event = core::create_user_event(context.core_as_ref()).unwrap();
+    let event = unsafe { Event::from_core(user_event.clone()) };
+
+    let errcode = unsafe { cl_h::clSetUserEventStatus(*user_event.as_ptr_ref(),
+        Status::CL_INVALID_VALUE as i32) };
+    assert_eq!(errcode, Status::CL_SUCCESS as i32);
+
+    assert!(event.wait_timeout(LONG).is_err());
+}
+
+#[test]
+fn event_list_timeout() {
+    let context = Context::builder().build().unwrap();
+    let user_events: Vec<_> = (0..3).map(|_| {
+        core::create_user_event(context.core_as_ref()).unwrap()
+    }).collect();
+
+    let ptrs: Vec<_> = user_events.iter().map(|ev| unsafe { *ev.as_ptr_ref() }).collect();
+    let list = unsafe { EventList::from_raw_retained(&ptrs).unwrap() };
+
+    core::set_user_event_status(&user_events[0], CommandExecutionStatus::Complete).unwrap();
+    core::set_user_event_status(&user_events[2], CommandExecutionStatus::Complete).unwrap();
+
+    let start = Instant::now();
+    assert_eq!(list.wait_all_timeout(SHORT).unwrap(), WaitOutcome::TimedOut);
+    assert!(start.elapsed() >= SHORT);
+
+    core::set_user_event_status(&user_events[1], CommandExecutionStatus::Complete).unwrap();
+    assert_eq!(list.wait_all_timeout(LONG).unwrap(), WaitOutcome::Complete);
+
+    assert_eq!(EventList::new().wait_all_timeout(SHORT).unwrap(), WaitOutcome::Complete);
+}
+
+#[test]
+fn kernel_timeout() {
+    let proque = ProQue::builder().src(SRC).dims([64]).build().unwrap();
+    let buffer = proque.create_buffer::<u32>().unwrap();
+
+    // Long enough to outlast the short timeout on any device:
+    let kernel = proque.create_kernel("spin").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1u32 << 26);
+
+    let mut event = Event::empty();
+    kernel.cmd().enew(&mut event).enq().unwrap();
+    assert_eq!(event.wait_timeout(Duration::from_millis(1)).unwrap(), WaitOutcome::TimedOut);
+    assert_eq!(proque.queue().finish_timeout(Duration::from_millis(1)).unwrap(),
+        WaitOutcome::TimedOut);
+
+    // Let it finish rather than leaving it to occupy the device:
+    proque.queue().finish().unwrap();
+    assert_eq!(event.wait_timeout(SHORT).unwrap(), WaitOutcome::Complete);
+
+    // A short one:
+    let kernel = proque.create_kernel("spin").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1u32);
+
+    let mut event = Event::empty();
+    kernel.cmd().enew(&mut event).enq().unwrap();
+    assert_eq!(event.wait_timeout(LONG).unwrap(), WaitOutcome::Complete);
+    assert_eq!(proque.queue().finish_timeout(LONG).unwrap(), WaitOutcome::Complete);
+}
+
+#[test]
+fn gated_kernel_timeout() {
+    let proque = ProQue::builder().src(SRC).dims([64]).build().unwrap();
+    let buffer = proque.create_buffer::<u32>().unwrap();
+    let kernel = proque.create_kernel("spin").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1u32);
+
+    // The kernel can not start until the gate opens:
+    let gate = core::create_user_event(proque.context().core_as_ref()).unwrap();
+    let gate_list = unsafe { EventList::from_raw_retained(&[*gate.as_ptr_ref()]).unwrap() };
+
+    let mut event = Event::empty();
+    kernel.cmd().ewait(&gate_list).enew(&mut event).enq().unwrap();
+    assert_eq!(event.wait_timeout(SHORT).unwrap(), WaitOutcome::TimedOut);
+
+    core::set_user_event_status(&gate, CommandExecutionStatus::Complete).unwrap();
+    assert_eq!(event.wait_timeout(LONG).unwrap(), WaitOutcome::Complete);
+}
@@ -0,0 +1,90 @@
+//! Tests that `Context`, `Queue`, and `Program` handles share one underlying
+//! object (cloning does not retain it again) and that weak handles neither
+//! keep it alive nor upgrade once every handle has been dropped.
+
+use standard::{Context, Queue, Program};
+use enums::{ContextInfo, ContextInfoResult, CommandQueueInfo, CommandQueueInfoResult,
+    ProgramInfo, ProgramInfoResult};
+
+fn context_ref_count(context: &Context) -> u32 {
+    match context.info(ContextInfo::ReferenceCount) {
+        ContextInfoResult::ReferenceCount(c) => c,
+        res => panic!("{:?}", res),
+    }
+}
+
+fn queue_ref_count(queue: &Queue) -> u32 {
+    match queue.info(CommandQueueInfo::ReferenceCount) {
+        CommandQueueInfoResult::ReferenceCount(c) => c,
+        res => panic!("{:?}", res),
+    }
+}
+
+fn program_ref_count(program: &Program) -> u32 {
+    match program.info(ProgramInfo::ReferenceCount) {
+        ProgramInfoResult::ReferenceCount(c) => c,
+        res => panic!("{:?}", res),
+    }
+}
+
+#[test]
+fn context_handles() {
+    let context = Context::builder().build().unwrap();
+    let ref_count = context_ref_count(&context);
+    assert_eq!(context.handle_count(), 1);
+
+    let weak = context.downgrade();
+    assert_eq!(context.handle_count(), 1);
+
+    let clones: Vec<Context> = (0..4).map(|_| context.clone()).collect();
+    assert_eq!(context.handle_count(), 5);
+    assert_eq!(context_ref_count(&context), ref_count);
+
+    drop(context);
+    assert!(weak.upgrade().is_some());
+    assert_eq!(weak.upgrade().unwrap().handle_count(), 5);
+
+    drop(clones);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn queue_handles() {
+    let context = Context::builder().build().unwrap();
+    let queue = Queue::new(&context, context.devices()[0].clone()).unwrap();
+    let context_weak = context.downgrade();
+    let queue_weak = queue.downgrade();
+    let ref_count = queue_ref_count(&queue);
+
+    let clone = queue.clone();
+    assert_eq!(queue.handle_count(), 2);
+    assert_eq!(queue_ref_count(&queue), ref_count);
+
+    // The queue keeps the context object, but not a `Context` handle, alive:
+    drop(context);
+    assert!(context_weak.upgrade().is_none());
+    clone.finish().unwrap();
+
+    drop(queue);
+    assert!(queue_weak.upgrade().is_some());
+    drop(clone);
+    assert!(queue_weak.upgrade().is_none());
+}
+
+#[test]
+fn program_handles() {
+    let context = Context::builder().build().unwrap();
+    let program = Program::builder().src("__kernel void nop() {}")
+        .devices(context.devices()[0]).build(&context).unwrap();
+    let weak = program.downgrade();
+    let ref_count = program_ref_count(&program);
+
+    let clone = program.clone();
+    assert_eq!(program.handle_count(), 2);
+    assert_eq!(program_ref_count(&clone), ref_count);
+
+    drop(program);
+    assert_eq!(weak.upgrade().unwrap().handle_count(), 2);
+    drop(clone);
+    assert!(weak.upgrade().is_none());
+}
@@ -0,0 +1,36 @@
+//! Tests for `core::WorkSizes` construction and raw pointer generation.
+
+use core::WorkSizes;
+
+#[test]
+fn work_dims_mismatched_dims() {
+    assert!(WorkSizes::new(&[], None, None).is_err());
+    assert!(WorkSizes::new(&[1, 2, 3, 4], None, None).is_err());
+    assert!(WorkSizes::new(&[64, 64], Some(&[8]), None).is_err());
+    assert!(WorkSizes::new(&[64, 64], None, Some(&[0, 0, 0])).is_err());
+    assert!(WorkSizes::new(&[64], None, None).unwrap().lws(&[8, 8]).is_err());
+    assert!(WorkSizes::new(&[64, 64, 64], None, None).unwrap().gwo(&[1, 1]).is_err());
+
+    let err = WorkSizes::new(&[64, 64], Some(&[8, 8, 8]), None).unwrap_err();
+    assert!(format!("{}", err).contains("local work size"));
+}
+
+#[test]
+fn work_dims_ptrs() {
+    let dims = WorkSizes::new(&[1024, 16], None, None).unwrap();
+    assert_eq!(dims.dim_count(), 2);
+    assert_eq!(dims.global_work_size(), &[1024, 16, 1]);
+    assert!(dims.lws_ptr().is_null());
+    assert!(dims.gwo_ptr().is_null());
+
+    let gws = unsafe { ::std::slice::from_raw_parts(dims.gws_ptr(), 3) };
+    assert_eq!(gws, &[1024, 16, 1]);
+
+    let dims = dims.lws(&[8, 4]).unwrap().gwo(&[2, 3]).unwrap();
+    assert_eq!(dims, WorkSizes::new(&[1024, 16], Some(&[8, 4]), Some(&[2, 3])).unwrap());
+
+    let lws = unsafe { ::std::slice::from_raw_parts(dims.lws_ptr(), 3) };
+    let gwo = unsafe { ::std::slice::from_raw_parts(dims.gwo_ptr(), 3) };
+    assert_eq!(lws, &[8, 4, 1]);
+    assert_eq!(gwo, &[2, 3, 0]);
+}
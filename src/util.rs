@@ -8,13 +8,14 @@
 use std::ops::Range;
 use std::mem;
 use std::ptr;
+use std::slice;
 use std::iter;
 use num::{Integer, FromPrimitive, ToPrimitive};
 use rand;
 use rand::distributions::{IndependentSample, Range as RandRange};
 use error::{Result as OclResult, Error as OclError};
 
-use core::OclPrm;
+use core::{OclPrm, OclScl};
 
 //=============================================================================
 //================================= MACROS ====================================
@@ -163,79 +164,252 @@ pub fn bytes_to_u32(bytes: &[u8]) -> u32 {
     ((bytes[3] as u32) << 24)
 }
 
+/// Returns an error unless `byte_count` is exactly the size of `T`.
+fn check_size<T>(fn_name: &str, byte_count: usize) -> OclResult<()> {
+    if byte_count == mem::size_of::<T>() {
+        Ok(())
+    } else {
+        OclError::err(format!("ocl::util::{}: Byte length mismatch (expected: {} bytes, \
+            actual: {} bytes).", fn_name, mem::size_of::<T>(), byte_count))
+    }
+}
+
+/// Returns an error unless `byte_count` is a multiple of the size of `T`.
+fn check_size_multiple<T>(fn_name: &str, byte_count: usize) -> OclResult<()> {
+    let size = mem::size_of::<T>();
+    if size != 0 && byte_count % size == 0 {
+        Ok(())
+    } else {
+        OclError::err(format!("ocl::util::{}: Byte length mismatch (expected a multiple of \
+            {} bytes, actual: {} bytes).", fn_name, size, byte_count))
+    }
+}
+
 /// Converts a vector of bytes into a value of arbitrary type.
 ///
+/// ### Errors
+///
+/// Returns an error if the length of `vec` is not exactly the size of `T`.
+///
 /// ### Safety
 ///
-/// Roughly equivalent to a weekend in Tijuana.
+/// The bytes must be a valid bit pattern for `T`.
 ///
-// [NOTE]: Not sure this is the best or simplest way to do this but whatever.
-// Would be nice to not even have to copy anything and just basically 
-// transmute the vector into the result type. TODO: Fiddle with this 
-// at some point. 
-//
-pub unsafe fn bytes_into<T>(vec: Vec<u8>) -> T {
-    let byte_count = mem::size_of::<u8>() * vec.len();
-    assert_eq!(mem::size_of::<T>(), byte_count);
-
-    let mut new_val: T = mem::uninitialized();
-
-    ptr::copy(vec.as_ptr(), &mut new_val as *mut _ as *mut u8, byte_count);
-
-    new_val
+pub unsafe fn bytes_into<T>(vec: Vec<u8>) -> OclResult<T> {
+    bytes_to(&vec)
 }
 
 /// Copies a slice of bytes to a new value of arbitrary type.
 ///
+/// ### Errors
+///
+/// Returns an error if the length of `bytes` is not exactly the size of `T`.
+///
 /// ### Safety
 ///
-/// You may want to wear a helmet.
+/// The bytes must be a valid bit pattern for `T`.
 ///
-pub unsafe fn bytes_to<T>(bytes: &[u8]) -> T {
-    let byte_count = mem::size_of::<u8>() * bytes.len();
-    assert_eq!(mem::size_of::<T>(), byte_count);
-
-    let mut new_val: T = mem::uninitialized();
-
-    ptr::copy(bytes.as_ptr(), &mut new_val as *mut _ as *mut u8, byte_count);
-
-    new_val
+pub unsafe fn bytes_to<T>(bytes: &[u8]) -> OclResult<T> {
+    try!(check_size::<T>("bytes_to", bytes.len()));
+    Ok(ptr::read_unaligned(bytes.as_ptr() as *const T))
 }
 
 /// Converts a vector of bytes into a vector of arbitrary type.
 ///
-/// ### Safety
+/// ### Errors
 ///
-/// Ummm... Say what?
+/// Returns an error if the length of `vec` is not a multiple of the size of
+/// `T`.
 ///
-/// TODO: Consider using alloc::heap::reallocate_inplace` equivalent.
+/// ### Safety
 ///
-pub unsafe fn bytes_into_vec<T>(mut vec: Vec<u8>) -> Vec<T> {
-    debug_assert!(vec.len() % mem::size_of::<T>() == 0);
-    let new_len = vec.len() / mem::size_of::<T>();
-    let new_cap = vec.capacity() / mem::size_of::<T>();
-    let ptr = vec.as_mut_ptr();
-    mem::forget(vec);
-    let mut new_vec: Vec<T> = Vec::from_raw_parts(ptr as *mut T, new_len, new_cap);
-    new_vec.shrink_to_fit();
-    new_vec
+/// Each chunk of bytes must be a valid bit pattern for `T`.
+///
+pub unsafe fn bytes_into_vec<T>(vec: Vec<u8>) -> OclResult<Vec<T>> {
+    bytes_to_vec(&vec)
 }
 
 /// Copies a slice of bytes into a vector of arbitrary type.
 ///
+/// ### Errors
+///
+/// Returns an error if the length of `bytes` is not a multiple of the size
+/// of `T`.
+///
 /// ### Safety
 ///
-/// Negative.
+/// Each chunk of bytes must be a valid bit pattern for `T`.
 ///
-pub unsafe fn bytes_to_vec<T>(bytes: &[u8]) -> Vec<T> {
-    debug_assert!(bytes.len() % mem::size_of::<T>() == 0);
+pub unsafe fn bytes_to_vec<T>(bytes: &[u8]) -> OclResult<Vec<T>> {
+    try!(check_size_multiple::<T>("bytes_to_vec", bytes.len()));
     let new_len = bytes.len() / mem::size_of::<T>();
     let mut new_vec: Vec<T> = Vec::with_capacity(new_len);
-    ptr::copy(bytes.as_ptr(), new_vec.as_mut_ptr() as *mut _ as *mut u8, bytes.len());
+    ptr::copy_nonoverlapping(bytes.as_ptr(), new_vec.as_mut_ptr() as *mut u8, bytes.len());
     new_vec.set_len(new_len);
-    new_vec
+    Ok(new_vec)
+}
+
+/// Returns an error unless `addr` is suitably aligned for a `T`.
+fn check_align<T>(fn_name: &str, addr: usize) -> OclResult<()> {
+    if try!(is_aligned(addr, mem::align_of::<T>())) {
+        Ok(())
+    } else {
+        OclError::err(format!("ocl::util::{}: Data is not aligned to {} bytes (address: {:#x}).",
+            fn_name, mem::align_of::<T>(), addr))
+    }
+}
+
+/// Reinterprets a vector of `T` as a vector of `U` (e.g. a `Vec<u8>` read
+/// from a buffer as a `Vec<f32>`).
+///
+/// The allocation is reused when `T` and `U` have the same alignment and
+/// its capacity in bytes is a multiple of the size of `U`. Otherwise the
+/// contents are copied into a new vector.
+///
+/// Elements are reinterpreted in host byte order. Padding bytes (those of
+/// the 3-component vector types or of user structs with gaps) have
+/// unspecified values and should not be cast to a smaller type.
+///
+/// ### Errors
+///
+/// Returns an error if the length of `vec` in bytes is not a multiple of
+/// the size of `U`.
+pub fn vec_cast<T: OclPrm, U: OclPrm>(vec: Vec<T>) -> OclResult<Vec<U>> {
+    let byte_len = vec.len() * mem::size_of::<T>();
+    try!(check_size_multiple::<U>("vec_cast", byte_len));
+    let new_len = byte_len / mem::size_of::<U>();
+    let byte_cap = vec.capacity() * mem::size_of::<T>();
+
+    if mem::align_of::<T>() == mem::align_of::<U>() && byte_cap % mem::size_of::<U>() == 0 {
+        let mut vec = mem::ManuallyDrop::new(vec);
+        Ok(unsafe { Vec::from_raw_parts(vec.as_mut_ptr() as *mut U, new_len,
+            byte_cap / mem::size_of::<U>()) })
+    } else {
+        let mut new_vec: Vec<U> = Vec::with_capacity(new_len);
+        unsafe {
+            ptr::copy_nonoverlapping(vec.as_ptr() as *const u8, new_vec.as_mut_ptr() as *mut u8,
+                byte_len);
+            new_vec.set_len(new_len);
+        }
+        Ok(new_vec)
+    }
 }
 
+/// Reinterprets a slice of `T` as a slice of `U` without copying.
+///
+/// The returned slice borrows `data` and covers exactly the same bytes, in
+/// host byte order. See `vec_cast` regarding padding.
+///
+/// ### Errors
+///
+/// Returns an error if the length of `slice` in bytes is not a multiple of
+/// the size of `U` or if `slice` is not aligned for `U`.
+pub fn slice_cast<T: OclPrm, U: OclPrm>(data: &[T]) -> OclResult<&[U]> {
+    let byte_len = data.len() * mem::size_of::<T>();
+    try!(check_size_multiple::<U>("slice_cast", byte_len));
+    try!(check_align::<U>("slice_cast", data.as_ptr() as usize));
+    Ok(unsafe { slice::from_raw_parts(data.as_ptr() as *const U,
+        byte_len / mem::size_of::<U>()) })
+}
+
+/// Reinterprets a mutable slice of `T` as a mutable slice of `U` without
+/// copying.
+///
+/// `data` is mutably borrowed, and therefore inaccessible, for
+/// as long as the returned slice is in use. Any values written through it
+/// are visible as (reinterpreted) `T`s afterwards.
+///
+/// ### Errors
+///
+/// Returns an error if the length of `data` in bytes is not a multiple of
+/// the size of `U` or if `data` is not aligned for `U`.
+pub fn slice_cast_mut<T: OclPrm, U: OclPrm>(data: &mut [T]) -> OclResult<&mut [U]> {
+    let byte_len = data.len() * mem::size_of::<T>();
+    try!(check_size_multiple::<U>("slice_cast_mut", byte_len));
+    try!(check_align::<U>("slice_cast_mut", data.as_ptr() as usize));
+    Ok(unsafe { slice::from_raw_parts_mut(data.as_mut_ptr() as *mut U,
+        byte_len / mem::size_of::<U>()) })
+}
+
+/// Converts the bytes of a string info query result (device names,
+/// extensions, build logs, etc.) into a `String`. Never fails.
+///
+/// All trailing NULs are removed, as are any other ASCII control characters
+/// apart from tabs and line breaks (including embedded NULs). Invalid UTF-8
+/// sequences are replaced with `U+FFFD`.
+///
+/// Use the `core::get_*_info_bytes` functions to obtain the unaltered bytes.
+pub fn string_from_info_bytes(mut bytes: Vec<u8>) -> String {
+    while bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+
+    // Bytes below 0x20 never occur within multi-byte UTF-8 sequences:
+    bytes.retain(|&b| b >= 0x20 || b == b'\t' || b == b'\n' || b == b'\r');
+
+    match String::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+    }
+}
+
+/// Returns the name of `T` without module paths (including those of any type
+/// parameters), e.g. `ClFloat4` rather than `ocl::core::types::vectors::ClFloat4`.
+pub(crate) fn short_type_name<T: ?Sized>() -> String {
+    let name = ::std::any::type_name::<T>();
+    let mut short = String::with_capacity(name.len());
+    let mut segment_start = 0;
+
+    for (idx, c) in name.char_indices() {
+        if c.is_alphanumeric() || c == '_' || c == ':' { continue; }
+        short.push_str(strip_path(&name[segment_start..idx]));
+        short.push(c);
+        segment_start = idx + c.len_utf8();
+    }
+    short.push_str(strip_path(&name[segment_start..]));
+    short
+}
+
+/// Returns the last segment of a path.
+fn strip_path(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+
+/// Types which can be serialized into the native-endian byte representation
+/// expected by the OpenCL API (as used by `ContextProperties::to_bytes`).
+pub trait ToClBytes {
+    /// Returns the native-endian bytes of this value.
+    fn to_cl_bytes(&self) -> Vec<u8>;
+}
+
+/// Also covers `cl_uint` and `cl_bool`.
+impl ToClBytes for u32 {
+    fn to_cl_bytes(&self) -> Vec<u8> {
+        self.to_ne_bytes().to_vec()
+    }
+}
+
+/// Also covers `size_t`.
+impl ToClBytes for usize {
+    fn to_cl_bytes(&self) -> Vec<u8> {
+        self.to_ne_bytes().to_vec()
+    }
+}
+
+/// Serializes the address (not the pointee). Covers `cl_platform_id` etc.
+impl<T> ToClBytes for *mut T {
+    fn to_cl_bytes(&self) -> Vec<u8> {
+        (*self as usize).to_cl_bytes()
+    }
+}
+
+/// Serializes the address (not the pointee).
+impl<T> ToClBytes for *const T {
+    fn to_cl_bytes(&self) -> Vec<u8> {
+        (*self as usize).to_cl_bytes()
+    }
+}
 
 /// [UNTESTED] Copies an arbitrary primitive or struct into core bytes.
 ///
@@ -254,7 +428,8 @@ pub unsafe fn bytes_to_vec<T>(bytes: &[u8]) -> Vec<T> {
 ///
 /// Don't ask.
 ///
-/// [FIXME]: Evaluate the ins and outs of this and lock this down a bit.
+/// Copies padding bytes, which are uninitialized, for structs with padding.
+#[deprecated(since = "0.10.0", note = "use `util::ToClBytes` instead")]
 pub unsafe fn into_bytes<T>(val: T) -> Vec<u8> {
     // let big_endian = false;
     let size = mem::size_of::<T>();
@@ -270,16 +445,63 @@ pub unsafe fn into_bytes<T>(val: T) -> Vec<u8> {
 }
 
 /// Pads `len` to make it evenly divisible by `incr`.
-pub fn padded_len(len: usize, incr: usize) -> usize {
+///
+/// ### Errors
+///
+/// Returns an error if `incr` is zero or if the padded length would overflow
+/// a `usize`.
+pub fn padded_len(len: usize, incr: usize) -> OclResult<usize> {
+    if incr == 0 {
+        return OclError::err("ocl::util::padded_len: Increment must be non-zero.");
+    }
+
     let len_mod = len % incr;
 
     if len_mod == 0 {
-        len
+        Ok(len)
+    } else {
+        match len.checked_add(incr - len_mod) {
+            Some(padded_len) => Ok(padded_len),
+            None => OclError::err(format!("ocl::util::padded_len: Padding length {} to a \
+                multiple of {} overflows.", len, incr)),
+        }
+    }
+}
+
+/// Rounds `value` up to the nearest multiple of `align`.
+///
+/// Equivalent to `padded_len` but faster when `align` is a power of two
+/// (the usual case for memory alignments).
+///
+/// ### Errors
+///
+/// Returns an error if `align` is zero or if the result would overflow a
+/// `usize`.
+pub fn align_up(value: usize, align: usize) -> OclResult<usize> {
+    if align.is_power_of_two() {
+        let mask = align - 1;
+        match value.checked_add(mask) {
+            Some(v) => Ok(v & !mask),
+            None => OclError::err(format!("ocl::util::align_up: Aligning {} to {} overflows.",
+                value, align)),
+        }
+    } else {
+        padded_len(value, align)
+    }
+}
+
+/// Returns `true` if `value` is a multiple of `align`.
+///
+/// ### Errors
+///
+/// Returns an error if `align` is zero.
+pub fn is_aligned(value: usize, align: usize) -> OclResult<bool> {
+    if align == 0 {
+        OclError::err("ocl::util::is_aligned: Alignment must be non-zero.")
+    } else if align.is_power_of_two() {
+        Ok(value & (align - 1) == 0)
     } else {
-        let pad = incr - len_mod;
-        let padded_len = len + pad;
-        debug_assert_eq!(padded_len % incr, 0);
-        padded_len
+        Ok(value % align == 0)
     }
 }
 
@@ -345,7 +567,7 @@ pub fn vec_remove_rebuild<T: Clone + Copy>(orig_vec: &mut Vec<T>, remove_list: &
 }
 
 /// Wraps (`%`) each value in the list `vals` if it equals or exceeds `val_n`.
-pub fn wrap_vals<T: OclPrm + Integer>(vals: &[T], val_n: T) -> Vec<T> {
+pub fn wrap_vals<T: Integer + Copy>(vals: &[T], val_n: T) -> Vec<T> {
     vals.iter().map(|&v| v % val_n).collect()
 }
 
@@ -353,7 +575,7 @@ pub fn wrap_vals<T: OclPrm + Integer>(vals: &[T], val_n: T) -> Vec<T> {
 
 /// Returns a vector with length `size` containing random values in the (half-open)
 /// range `[vals.0, vals.1)`.
-pub fn scrambled_vec<T: OclPrm>(vals: (T, T), size: usize) -> Vec<T> {
+pub fn scrambled_vec<T: OclScl>(vals: (T, T), size: usize) -> Vec<T> {
     assert!(size > 0, "\nbuffer::shuffled_vec(): Vector size must be greater than zero.");
     assert!(vals.0 < vals.1, "\nbuffer::shuffled_vec(): Minimum value must be less than maximum.");
     let mut rng = rand::weak_rng();
@@ -367,7 +589,7 @@ pub fn scrambled_vec<T: OclPrm>(vals: (T, T), size: usize) -> Vec<T> {
 /// number of integers in the aforementioned range, the integers will repeat. After
 /// being filled with `size` values, the vector is shuffled and the order of its
 /// values is randomized.
-pub fn shuffled_vec<T: OclPrm>(vals: (T, T), size: usize) -> Vec<T> {
+pub fn shuffled_vec<T: OclScl>(vals: (T, T), size: usize) -> Vec<T> {
     let mut vec: Vec<T> = Vec::with_capacity(size);
     assert!(size > 0, "\nbuffer::shuffled_vec(): Vector size must be greater than zero.");
     assert!(vals.0 < vals.1, "\nbuffer::shuffled_vec(): Minimum value must be less than maximum.");
@@ -418,6 +640,304 @@ pub fn shuffle<T: OclPrm>(vec: &mut [T]) {
 //     lens.iter().map(|len| len * mem::size_of::<T>()).collect()
 // }
 
+//=============================================================================
+//=============================== DIAGNOSTICS =================================
+//=============================================================================
+
+/// Returns a description of how the OpenCL library was linked (or loaded)
+/// and the path of the library providing its functions, which may not be
+/// the one expected when several are installed (a vendor library rather
+/// than the ICD loader, for example).
+///
+/// The path is found by looking up the module containing
+/// `clGetPlatformIDs` (with `dladdr` or `GetModuleHandleEx`).
+///
+/// ## Examples
+///
+/// ```text
+/// OpenCL library: linked at build time
+/// Library path: /usr/lib/x86_64-linux-gnu/libOpenCL.so.1
+/// ```
+pub fn link_info() -> String {
+    let (how, addr) = link_addr();
+    let path = match addr {
+        Some(addr) => module_path(addr).unwrap_or_else(|| String::from("unknown")),
+        None => String::from("none (not loaded)"),
+    };
+    format!("OpenCL library: {}\nLibrary path: {}", how, path)
+}
+
+/// The environment variables reported by `diagnostics`: those of the ICD
+/// loader (the Khronos loader and `ocl-icd` both honor these) and of this
+/// library.
+#[cfg(feature = "dynamic-loading")]
+const DIAGNOSTIC_VARS: &'static [&'static str] = &["OCL_LIBRARY", "OCL_ICD_VENDORS",
+    "OCL_ICD_FILENAMES", "OCL_PLATFORM_INDEX"];
+#[cfg(not(feature = "dynamic-loading"))]
+const DIAGNOSTIC_VARS: &'static [&'static str] = &["OCL_ICD_VENDORS", "OCL_ICD_FILENAMES",
+    "OCL_PLATFORM_INDEX"];
+
+/// The directory searched for `.icd` files by the ICD loader when
+/// `OCL_ICD_VENDORS` is not set.
+#[cfg(all(unix, not(target_os = "macos")))]
+const ICD_VENDORS_DIR: &'static str = "/etc/OpenCL/vendors";
+
+/// Information about the OpenCL installation which may explain why no
+/// platforms are available, as gathered by `diagnostics`.
+///
+/// Displayed as a report ending with suggestions of what to try. Also
+/// contained by `Error::NoPlatforms`.
+#[derive(Clone, Debug)]
+pub struct Diagnostics {
+    /// How the OpenCL library was linked or loaded (see `link_info`).
+    pub library: String,
+    /// The path of the library providing the OpenCL functions, or `None` if
+    /// no library is loaded.
+    pub library_path: Option<String>,
+    /// The directory searched for vendor `.icd` files by the ICD loader, or
+    /// `None` where no such directory is used (macOS and Windows).
+    pub icd_vendors_dir: Option<String>,
+    /// The names of the `.icd` files within `icd_vendors_dir`, or `None` if
+    /// it does not exist (or can not be read).
+    pub icd_files: Option<Vec<String>>,
+    /// Each relevant environment variable and its value, if set.
+    pub env_vars: Vec<(String, Option<String>)>,
+}
+
+impl Diagnostics {
+    /// Returns suggestions of what to try, most likely first.
+    pub fn hints(&self) -> Vec<String> {
+        let mut hints = Vec::with_capacity(3);
+
+        if cfg!(feature = "stub") {
+            hints.push(String::from("Disable the `stub` feature, which replaces the OpenCL \
+                library with a stand-in which has no platforms."));
+            return hints;
+        }
+
+        if self.library_path.is_none() {
+            hints.push(String::from("Install an OpenCL ICD loader (such as the \
+                'ocl-icd-libopencl1' or 'khronos-opencl-icd-loader' package) or set OCL_LIBRARY \
+                to the name or path of the library to load."));
+        }
+
+        match (&self.icd_vendors_dir, &self.icd_files) {
+            (&Some(ref dir), &Some(ref files)) if !files.is_empty() => {
+                hints.push(format!("Drivers are registered in '{}' but none reported a \
+                    platform. Check that the library named in each file exists and supports \
+                    your hardware (and that your user may access the device).", dir));
+            },
+            (&Some(ref dir), _) => {
+                hints.push(format!("No drivers are registered ('{}' is {}). Install the OpenCL \
+                    driver (ICD) for your device (such as 'intel-opencl-icd', \
+                    'mesa-opencl-icd', or 'pocl-opencl-icd' for any CPU), or point the loader \
+                    at one with OCL_ICD_VENDORS (a directory of '.icd' files) or \
+                    OCL_ICD_FILENAMES (driver library paths).", dir,
+                    if self.icd_files.is_some() { "empty" } else { "missing" }));
+            },
+            (&None, _) => {
+                hints.push(String::from("Install the OpenCL driver for your device."));
+            },
+        }
+
+        hints
+    }
+}
+
+/// Displays each item on its own line, ending with the hints.
+impl ::std::fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        try!(writeln!(f, "OpenCL library: {}", self.library));
+        try!(writeln!(f, "Library path: {}", self.library_path.as_ref().map(|p| p.as_str())
+            .unwrap_or("none (not loaded)")));
+
+        if let Some(ref dir) = self.icd_vendors_dir {
+            match self.icd_files {
+                Some(ref files) if files.is_empty() => {
+                    try!(writeln!(f, "ICD vendors directory: {} (empty)", dir))
+                },
+                Some(ref files) => {
+                    try!(writeln!(f, "ICD vendors directory: {} ({})", dir, files.join(", ")))
+                },
+                None => try!(writeln!(f, "ICD vendors directory: {} (missing)", dir)),
+            }
+        }
+
+        try!(write!(f, "Environment:"));
+        for &(ref var, ref val) in self.env_vars.iter() {
+            try!(write!(f, " {}={}", var, val.as_ref().map(|v| v.as_str()).unwrap_or("(unset)")));
+        }
+
+        for hint in self.hints() {
+            try!(write!(f, "\nHint: {}", hint));
+        }
+        Ok(())
+    }
+}
+
+/// Returns information about the OpenCL installation which may explain why
+/// no platforms are available: the library loaded (see `link_info`), the
+/// drivers registered with the ICD loader (on Linux and other Unix-likes
+/// other than macOS), and the relevant environment variables.
+///
+/// The same information is included in the `Error::NoPlatforms` returned
+/// when the default platform is requested but none exist.
+pub fn diagnostics() -> Diagnostics {
+    let (library, addr) = link_addr();
+    let library_path = addr.map(|addr| module_path(addr).unwrap_or_else(|| String::from("unknown")));
+    let icd_vendors_dir = icd_vendors_dir();
+    let icd_files = icd_vendors_dir.as_ref().and_then(|dir| icd_files(::std::path::Path::new(dir)));
+
+    Diagnostics {
+        library: library,
+        library_path: library_path,
+        icd_vendors_dir: icd_vendors_dir,
+        icd_files: icd_files,
+        env_vars: DIAGNOSTIC_VARS.iter().map(|&var| (var.to_owned(), ::std::env::var(var).ok()))
+            .collect(),
+    }
+}
+
+/// Returns the directory searched for `.icd` files by the ICD loader.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn icd_vendors_dir() -> Option<String> {
+    match ::std::env::var("OCL_ICD_VENDORS") {
+        Ok(ref dir) if !dir.is_empty() => Some(dir.clone()),
+        _ => Some(String::from(ICD_VENDORS_DIR)),
+    }
+}
+
+/// Returns the directory searched for `.icd` files by the ICD loader.
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn icd_vendors_dir() -> Option<String> {
+    None
+}
+
+/// Returns the sorted names of the `.icd` files in `dir` or `None` if it can
+/// not be read.
+pub(crate) fn icd_files(dir: &::std::path::Path) -> Option<Vec<String>> {
+    let entries = match ::std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return None,
+    };
+
+    let mut files: Vec<String> = entries.filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".icd"))
+        .collect();
+    files.sort();
+    Some(files)
+}
+
+/// Returns how the library was linked and the address of its
+/// `clGetPlatformIDs`, if loaded.
+#[cfg(feature = "dynamic-loading")]
+fn link_addr() -> (String, Option<usize>) {
+    let how = match ::std::env::var(::loader::LIBRARY_VAR) {
+        Ok(name) => format!("loaded at runtime ({}={:?})", ::loader::LIBRARY_VAR, name),
+        Err(_) => String::from("loaded at runtime"),
+    };
+    (how, ::loader::get_platform_ids_addr())
+}
+
+/// Returns how the library was linked and the address of its
+/// `clGetPlatformIDs`, if loaded.
+/// Returns how the library was linked and the address of its
+/// `clGetPlatformIDs`, if loaded.
+#[cfg(feature = "stub")]
+fn link_addr() -> (String, Option<usize>) {
+    (String::from("none, replaced by the stub backend (the `stub` feature is enabled)"), None)
+}
+
+/// Returns how the library was linked and the address of its
+/// `clGetPlatformIDs`, if loaded.
+#[cfg(not(any(feature = "dynamic-loading", feature = "stub")))]
+fn link_addr() -> (String, Option<usize>) {
+    (String::from("linked at build time"), Some(::cl_h::clGetPlatformIDs as *const () as usize))
+}
+
+/// Returns the path of the library or executable containing `addr`.
+#[cfg(unix)]
+fn module_path(addr: usize) -> Option<String> {
+    use std::ffi::CStr;
+    use libc;
+
+    unsafe {
+        let mut info: libc::Dl_info = mem::zeroed();
+
+        if libc::dladdr(addr as *const libc::c_void, &mut info) == 0 || info.dli_fname.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(info.dli_fname).to_string_lossy().into_owned())
+        }
+    }
+}
+
+/// Returns the path of the library or executable containing `addr`.
+#[cfg(windows)]
+fn module_path(addr: usize) -> Option<String> {
+    use libc::c_void;
+
+    const GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT: u32 = 0x2;
+    const GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS: u32 = 0x4;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetModuleHandleExW(flags: u32, module_name: *const u16, module: *mut *mut c_void) -> i32;
+        fn GetModuleFileNameW(module: *mut c_void, file_name: *mut u16, size: u32) -> u32;
+    }
+
+    unsafe {
+        let mut module = ptr::null_mut();
+
+        if GetModuleHandleExW(GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS |
+                GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT, addr as *const u16, &mut module) == 0
+        {
+            return None;
+        }
+
+        let mut file_name = vec![0u16; 1024];
+        let len = GetModuleFileNameW(module, file_name.as_mut_ptr(), file_name.len() as u32);
+
+        if len == 0 {
+            None
+        } else {
+            Some(String::from_utf16_lossy(&file_name[..len as usize]))
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn module_path(_: usize) -> Option<String> {
+    None
+}
+
+//=============================================================================
+//======================== THREAD SAFETY ASSERTIONS ===========================
+//=============================================================================
+
+// Used within `const _: fn() = || { ... };` blocks to encode (at compile
+// time) which types may be sent or shared between threads.
+
+/// Compiles only if `T` is `Send`.
+pub(crate) fn assert_send<T: ?Sized + Send>() {}
+
+/// Compiles only if `T` is `Sync`.
+pub(crate) fn assert_sync<T: ?Sized + Sync>() {}
+
+/// Implemented twice for `Send` types, making `<T as NotSend<_>>::check`
+/// ambiguous (and a compile error) unless `T` is not `Send`.
+pub(crate) trait NotSend<A> { fn check() {} }
+impl<T: ?Sized> NotSend<()> for T {}
+impl<T: ?Sized + Send> NotSend<u8> for T {}
+
+/// Implemented twice for `Sync` types, making `<T as NotSync<_>>::check`
+/// ambiguous (and a compile error) unless `T` is not `Sync`.
+pub(crate) trait NotSync<A> { fn check() {} }
+impl<T: ?Sized> NotSync<()> for T {}
+impl<T: ?Sized + Sync> NotSync<u8> for T {}
+
+
 //=============================================================================
 //=========================== PRINTING FUNCTIONS ==============================
 //=============================================================================
@@ -435,7 +955,7 @@ pub fn print_bytes_as_hex(bytes: &Vec<u8>) {
 #[allow(unused_assignments, unused_variables)]
 /// [UNSTABLE]: MAY BE REMOVED AT ANY TIME
 /// Prints a vector to stdout. Used for debugging.
-pub fn print_slice<T: OclPrm>(
+pub fn print_slice<T: OclScl>(
             vec: &[T], 
             every: usize, 
             val_range: Option<(T, T)>, 
@@ -571,13 +1091,13 @@ pub fn print_slice<T: OclPrm>(
 }
 
 
-pub fn print_simple<T: OclPrm>(slice: &[T]) {
+pub fn print_simple<T: OclScl>(slice: &[T]) {
     print_slice(slice, 1, None, None, true);
 }
 
 
 
-pub fn print_val_range<T: OclPrm>(slice: &[T], every: usize, val_range: Option<(T, T)>) {
+pub fn print_val_range<T: OclScl>(slice: &[T], every: usize, val_range: Option<(T, T)>) {
     print_slice(slice, every, val_range, None, true);
 }
 